@@ -1,7 +1,8 @@
 use crate::config::Config;
 use crate::screens::{
-    ActionResult, MainMenuScreen, ManagePackagesScreen, ManageProfilesScreen,
-    Screen as ScreenTrait, StorageSetupScreen, SyncWithRemoteScreen,
+    ActionResult, AliasesScreen, BootstrapScreen, ForceSyncScreen, HealthScreen, HistoryScreen,
+    ImportScreen, MachinesScreen, MainMenuScreen, ManagePackagesScreen, ManageProfilesScreen,
+    ReconcileScreen, Screen as ScreenTrait, StatsScreen, StorageSetupScreen, SyncWithRemoteScreen,
 };
 use crate::tui::Tui;
 use crate::ui::{GitHubSetupStep, Screen, UiState};
@@ -32,6 +33,8 @@ struct DialogState {
 pub struct App {
     config: Config,
     config_path: PathBuf,
+    /// Detects hand edits to the config file so they apply without a restart
+    config_watcher: crate::utils::ConfigWatcher,
     tui: Tui,
     ui_state: UiState,
     should_quit: bool,
@@ -47,6 +50,17 @@ pub struct App {
     manage_profiles_screen: ManageProfilesScreen,
     manage_packages_screen: ManagePackagesScreen,
     settings_screen: crate::screens::SettingsScreen,
+    health_screen: HealthScreen,
+    history_screen: HistoryScreen,
+    machines_screen: MachinesScreen,
+    import_screen: ImportScreen,
+    aliases_screen: AliasesScreen,
+    stats_screen: StatsScreen,
+    notifications_screen: crate::screens::NotificationsScreen,
+    bootstrap_screen: BootstrapScreen,
+    force_sync_screen: ForceSyncScreen,
+    reconcile_screen: ReconcileScreen,
+    reauth_screen: crate::screens::ReauthScreen,
     /// Modal dialog state (for error messages, confirmations)
     dialog_state: Option<DialogState>,
     /// Toast notification manager for non-blocking notifications
@@ -62,10 +76,21 @@ pub struct App {
         Option<oneshot::Receiver<Result<Option<crate::version_check::UpdateInfo>, String>>>,
     /// Receiver for async git status check
     git_status_receiver: Option<oneshot::Receiver<crate::services::git_service::GitStatus>>,
+    /// Receiver for a pull started from a remote-ahead banner
+    pull_receiver: Option<oneshot::Receiver<crate::services::git_service::SyncResult>>,
+    /// Track if we've checked the GitHub token yet (once per launch)
+    has_checked_token: bool,
+    /// Receiver for the background GitHub token check
+    token_check_receiver: Option<oneshot::Receiver<Option<crate::services::ReauthReason>>>,
+    /// Whether a background check already sent the user to re-authenticate
+    /// (so periodic checks don't keep interrupting them)
+    has_prompted_reauth: bool,
     /// Last time git status was checked
     last_git_status_check: Option<std::time::Instant>,
     /// Receiver for async storage setup step
     setup_step_handle: Option<crate::services::StepHandle>,
+    /// Menu item opened on startup instead of showing the main menu (`--screen`)
+    start_item: Option<crate::screens::main_menu::MenuItem>,
 }
 
 impl App {
@@ -93,6 +118,7 @@ impl App {
         let _config_clone = config.clone();
         let main_menu_screen = MainMenuScreen::with_config(&config, has_changes);
         let app = Self {
+            config_watcher: crate::utils::ConfigWatcher::new(&config_path),
             config_path,
             config,
             tui,
@@ -108,6 +134,17 @@ impl App {
             manage_profiles_screen: ManageProfilesScreen::new(),
             manage_packages_screen: ManagePackagesScreen::new(),
             settings_screen: crate::screens::SettingsScreen::new(),
+            health_screen: HealthScreen::new(),
+            history_screen: HistoryScreen::new(),
+            machines_screen: MachinesScreen::new(),
+            import_screen: ImportScreen::new(),
+            aliases_screen: AliasesScreen::new(),
+            stats_screen: StatsScreen::new(),
+            notifications_screen: crate::screens::NotificationsScreen::new(),
+            bootstrap_screen: BootstrapScreen::new(),
+            force_sync_screen: ForceSyncScreen::new(),
+            reconcile_screen: ReconcileScreen::new(),
+            reauth_screen: crate::screens::ReauthScreen::new(),
 
             dialog_state: None,
             toast_manager: ToastManager::new(),
@@ -116,16 +153,39 @@ impl App {
             has_checked_updates: false,
             update_check_receiver: None,
             git_status_receiver: None,
+            pull_receiver: None,
+            has_checked_token: false,
+            token_check_receiver: None,
+            has_prompted_reauth: false,
             last_git_status_check: None,
             setup_step_handle: None,
+            start_item: None,
         };
 
         Ok(app)
     }
 
+    /// Open `item` on startup instead of showing the main menu
+    pub fn start_on(&mut self, item: crate::screens::main_menu::MenuItem) {
+        self.start_item = Some(item);
+    }
+
+    /// Show `message` as a toast once the TUI is up, as a warning unless `ok`
+    pub fn notify(&mut self, message: String, ok: bool) {
+        use crate::widgets::ToastVariant;
+        let variant = if ok {
+            ToastVariant::Info
+        } else {
+            ToastVariant::Warning
+        };
+        self.toast_manager.push(Toast::new(message, variant));
+    }
+
     pub fn run(&mut self) -> Result<()> {
         info!("Entering TUI mode");
         self.tui.enter()?;
+        // Query graphics protocol support before any events are read
+        crate::utils::image_preview::init();
 
         // Update check is deferred until after first render to avoid blocking startup
         // This allows the UI to appear immediately
@@ -148,8 +208,22 @@ impl App {
             });
         }
 
-        // Always start with main menu (which is now the welcome screen)
+        self.warn_about_config_issues();
+        self.warn_about_newer_repo();
+
+        // Start with main menu (which is now the welcome screen), or open the
+        // item asked for with --screen as if it was chosen there
         self.ui_state.current_screen = Screen::MainMenu;
+        if let Some(item) = self.start_item.take() {
+            use crate::screens::ScreenContext;
+            let ctx = ScreenContext::new(&self.config, &self.config_path);
+            let action = self.main_menu_screen.open_item(item, &ctx)?;
+            self.ui_state.selected_index = self.main_menu_screen.selected_index();
+            if let crate::screens::ScreenAction::Navigate(target) = &action {
+                self.handle_menu_navigation(*target)?;
+            }
+            self.process_screen_action(action)?;
+        }
         // Set last_screen to None so first draw will detect the transition
         self.last_screen = None;
         info!("Starting main event loop");
@@ -161,6 +235,11 @@ impl App {
             // Tick toast manager to remove expired toasts
             self.toast_manager.tick();
 
+            // Apply edits made to the config file outside the app
+            if self.config_watcher.poll() {
+                self.reload_config();
+            }
+
             // Start async update check after first render (non-blocking for UI)
             if !self.has_checked_updates
                 && self.config.updates.check_enabled
@@ -230,6 +309,16 @@ impl App {
                         sync_state.changed_files = status.uncommitted_files.clone();
                         sync_state.git_status = Some(status.clone());
 
+                        // A fetch refused for bad credentials means the token needs replacing
+                        if status
+                            .fetch_error
+                            .as_deref()
+                            .is_some_and(crate::services::TokenService::is_auth_failure)
+                            && crate::services::TokenService::uses_token(&self.config)
+                        {
+                            self.prompt_reauth(crate::services::ReauthReason::Rejected);
+                        }
+
                         self.git_status_receiver = None;
                         self.last_git_status_check = Some(std::time::Instant::now());
                     }
@@ -240,8 +329,25 @@ impl App {
                 }
             }
 
+            // Check for a pull started from a remote-ahead banner
+            if let Some(receiver) = &mut self.pull_receiver {
+                match receiver.try_recv() {
+                    Ok(result) => {
+                        self.pull_receiver = None;
+                        self.finish_pull(result);
+                    }
+                    Err(oneshot::error::TryRecvError::Empty) => {} // Still running
+                    Err(_) => {
+                        self.pull_receiver = None; // Failed or cancelled
+                    }
+                }
+            }
+
             // Check for storage setup step completion
             if let Some(handle) = &mut self.setup_step_handle {
+                if let Some(progress) = handle.latest_progress() {
+                    self.storage_setup_screen.get_state_mut().transfer_progress = Some(progress);
+                }
                 match handle.receiver.try_recv() {
                     Ok(Ok(result)) => {
                         // Note: handle_setup_step_result may set a NEW setup_step_handle
@@ -253,6 +359,7 @@ impl App {
                     }
                     Ok(Err(e)) => {
                         error!("Setup step failed: {}", e);
+                        self.record_setup_failure(&e.to_string());
                         crate::services::StorageSetupService::cleanup_failed_setup(
                             &mut self.config,
                             &self.config_path,
@@ -292,10 +399,100 @@ impl App {
                 }
             };
 
+            // Poll background diagnostics and fixes on the health screen
+            match self.health_screen.tick(&self.config) {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Poll the statistics being collected for the stats screen
+            match self.stats_screen.tick() {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Poll the import screen's clone of the repository to import from
+            match self.import_screen.tick() {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Poll the bootstrap scripts running on the bootstrap screen
+            match self.bootstrap_screen.tick() {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Poll the force sync screen's fetch and its result
+            match self.force_sync_screen.tick(&self.config) {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Poll the reconcile screen's fetch and its commit and push
+            match self.reconcile_screen.tick(&self.config) {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Start the GitHub token check once, after the first render
+            if !self.has_checked_token {
+                self.has_checked_token = true;
+                if crate::services::TokenService::uses_token(&self.config) {
+                    let config_clone = self.config.clone();
+                    let (tx, rx) = oneshot::channel();
+                    thread::spawn(move || {
+                        let _ = tx.send(crate::services::TokenService::check(&config_clone));
+                    });
+                    self.token_check_receiver = Some(rx);
+                }
+            }
+            if let Some(receiver) = &mut self.token_check_receiver {
+                match receiver.try_recv() {
+                    Ok(reason) => {
+                        self.token_check_receiver = None;
+                        if let Some(reason) = reason {
+                            self.prompt_reauth(reason);
+                        }
+                    }
+                    Err(oneshot::error::TryRecvError::Empty) => {} // Still running
+                    Err(_) => self.token_check_receiver = None,
+                }
+            }
+
+            // Poll the storage setup screen's repository listing for restores
+            match self.storage_setup_screen.tick() {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Poll the re-authenticate screen's token validation
+            match self.reauth_screen.tick() {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
+            // Poll the background sync for transfer progress and its result
+            let sync_busy = self.sync_with_remote_screen.is_busy();
+            match self.sync_with_remote_screen.tick(&self.config) {
+                crate::screens::ScreenAction::None | crate::screens::ScreenAction::Refresh => {}
+                action => self.process_screen_action(action)?,
+            }
+
             // Poll for events - use short timeout during active operations for responsive UI
             let poll_timeout = if needs_fast_refresh
                 || self.setup_step_handle.is_some()
                 || self.manage_packages_screen.get_state_mut().is_checking
+                || self.health_screen.is_busy()
+                || self.stats_screen.is_busy()
+                || self.import_screen.is_busy()
+                || self.bootstrap_screen.is_busy()
+                || self.force_sync_screen.is_busy()
+                || self.reconcile_screen.is_busy()
+                || self.reauth_screen.is_busy()
+                || self.storage_setup_screen.is_busy()
+                || self.pull_receiver.is_some()
+                || sync_busy
             {
                 Duration::from_millis(50) // Fast refresh for active operations
             } else {
@@ -335,10 +532,31 @@ impl App {
 
         // Update config
         self.config.theme = next_theme.to_config_string().to_string();
+        Self::apply_theme(next_theme);
 
+        // Save config
+        if let Err(e) = self.config.save(&self.config_path) {
+            warn!("Failed to save theme change: {}", e);
+        } else {
+            info!("Theme saved to config: {}", self.config.theme);
+        }
+
+        Ok(())
+    }
+
+    /// Re-apply the theme after the active profile changed, in case the new
+    /// profile overrides it. `previous_theme` is the theme before the switch.
+    fn refresh_profile_theme(&mut self, previous_theme: &str) {
+        if self.config.theme != previous_theme {
+            Self::apply_theme(self.config.theme.parse().unwrap_or_default());
+        }
+    }
+
+    /// Switch the UI to `theme_type`
+    fn apply_theme(theme_type: crate::styles::ThemeType) {
         // Update NO_COLOR environment variable based on theme
         // This allows colors to be restored when cycling from nocolor to a color theme
-        if next_theme == ThemeType::NoColor {
+        if theme_type == crate::styles::ThemeType::NoColor {
             std::env::set_var("NO_COLOR", "1");
             info!("NO_COLOR environment variable set");
         } else {
@@ -347,20 +565,152 @@ impl App {
         }
 
         // Re-initialize theme
-        crate::styles::init_theme(next_theme);
-        info!("Theme changed to: {:?}", next_theme);
+        crate::styles::init_theme(theme_type);
+        info!("Theme changed to: {:?}", theme_type);
+    }
 
-        // Save config
-        if let Err(e) = self.config.save(&self.config_path) {
-            warn!("Failed to save theme change: {}", e);
-        } else {
-            info!("Theme saved to config: {}", self.config.theme);
+    /// Reload the config file after it was edited outside the app.
+    ///
+    /// Theme, keymap, icons, backup and other settings take effect on the next
+    /// draw. A file that no longer parses keeps the current config running.
+    fn reload_config(&mut self) {
+        use crate::widgets::ToastVariant;
+
+        let reloaded = match Config::load_or_create(&self.config_path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Ignoring config change: {:#}", e);
+                self.toast_manager.push(Toast::new(
+                    format!("Config not reloaded: {e:#}"),
+                    ToastVariant::Error,
+                ));
+                return;
+            }
+        };
+
+        // Our own saves also change the file; only react to real differences
+        let unchanged = toml::to_string(&reloaded).ok() == toml::to_string(&self.config).ok();
+        if unchanged {
+            return;
         }
 
-        Ok(())
+        info!("Config file changed on disk, reloading");
+        if reloaded.theme != self.config.theme {
+            Self::apply_theme(reloaded.theme.parse().unwrap_or_default());
+        }
+        crate::styles::set_compact(reloaded.compact);
+        self.config = reloaded;
+        self.toast_manager.push(Toast::new(
+            "Config reloaded".to_string(),
+            ToastVariant::Info,
+        ));
+        self.warn_about_config_issues();
+    }
+
+    /// Write an error report for `error` to the cache directory and put it on
+    /// the clipboard, for pasting into a bug report
+    fn copy_error_report(&mut self, error: &str) {
+        use crate::utils::error_report;
+        use crate::widgets::ToastVariant;
+        use std::io::Write;
+
+        let report = error_report::build(
+            Some(error),
+            &self.config_path,
+            &crate::utils::get_cache_dir().join("dotstate.log"),
+        );
+        let toast = match error_report::save(&report) {
+            Ok(path) => {
+                let mut stdout = std::io::stdout();
+                let _ = stdout.write_all(error_report::osc52(&report).as_bytes());
+                let _ = stdout.flush();
+                Toast::new(
+                    format!(
+                        "Error report copied and saved to {}",
+                        crate::utils::path::format_path_for_display(&path)
+                    ),
+                    ToastVariant::Success,
+                )
+            }
+            Err(e) => Toast::new(
+                format!("Failed to save error report: {e:#}"),
+                ToastVariant::Error,
+            ),
+        };
+        self.toast_manager.push(toast);
+    }
+
+    /// Point out unknown keys and invalid values in the config file, which
+    /// otherwise fall back to defaults without a word.
+    fn warn_about_config_issues(&mut self) {
+        use crate::widgets::ToastVariant;
+
+        let Ok(content) = std::fs::read_to_string(&self.config_path) else {
+            return;
+        };
+        let issues = crate::utils::config_validation::validate_config(&content);
+        let Some(first) = issues.first() else {
+            return;
+        };
+        warn!(
+            "Config file has {} problems, first: {}",
+            issues.len(),
+            first
+        );
+        let more = if issues.len() > 1 {
+            format!(" (+{} more)", issues.len() - 1)
+        } else {
+            String::new()
+        };
+        self.toast_manager.push(Toast::new(
+            format!("config.toml {first}{more}. Repair it from Health Check"),
+            ToastVariant::Warning,
+        ));
+    }
+
+    /// A newer dotstate last wrote the repository: say so and offer the
+    /// upgrade from the main menu, since this version won't write to it
+    fn warn_about_newer_repo(&mut self) {
+        use crate::version_check::{current_version, UpdateInfo};
+
+        if !self.config.is_repo_configured() {
+            return;
+        }
+        let Ok(manifest) = crate::utils::ProfileManifest::load(&self.config.repo_path) else {
+            return;
+        };
+        let Some(required) = manifest.required_version() else {
+            return;
+        };
+        warn!(
+            "Repository was written by dotstate {}, this is {}",
+            required,
+            current_version()
+        );
+        self.main_menu_screen.set_update_info(Some(UpdateInfo {
+            current_version: current_version().to_string(),
+            latest_version: required.to_string(),
+            release_url: UpdateInfo::releases_url(),
+        }));
+        self.dialog_state = Some(DialogState {
+            title: "Upgrade Needed".to_string(),
+            content: format!(
+                "Your repository was last written by dotstate {required}, \
+                newer than this one ({}).\n\n\
+                To keep this version from undoing what the newer one wrote, \
+                dotstate won't change the repository until you upgrade.\n\n\
+                Select the update entry in the main menu, or run:\n\n\
+                dotstate upgrade",
+                current_version()
+            ),
+            variant: DialogVariant::Warning,
+            scroll_offset: 0,
+        });
     }
 
     fn draw(&mut self) -> Result<()> {
+        let _span =
+            tracing::debug_span!("render", screen = ?self.ui_state.current_screen).entered();
         // Check for screen transitions and update state accordingly
         let current_screen = self.ui_state.current_screen;
         if self.last_screen != Some(current_screen) {
@@ -372,6 +722,14 @@ impl App {
             // Screen changed - check for changes when entering MainMenu
             if current_screen == Screen::MainMenu {
                 self.trigger_git_status_check(true);
+                self.notifications_screen.refresh(&self.config);
+                // Run the doctor once in the background so the dashboard can show pending issues
+                if self.config.is_repo_configured()
+                    && self.health_screen.summary().is_none()
+                    && !self.health_screen.is_busy()
+                {
+                    self.health_screen.start_diagnostics(&self.config);
+                }
             }
             // Handle ManagePackages screen transitions
             if current_screen == Screen::ManagePackages {
@@ -403,9 +761,36 @@ impl App {
         }
 
         // Update components with current state
+        let behind = self.ui_state.git_status.as_ref().map_or(0, |s| s.behind);
+        let pulling = self.pull_receiver.is_some();
+        self.dotfile_selection_screen
+            .set_remote_status(behind, pulling);
+        self.manage_profiles_screen
+            .set_remote_status(behind, pulling);
+
+        if matches!(
+            self.ui_state.current_screen,
+            Screen::MainMenu | Screen::Notifications
+        ) {
+            self.notifications_screen.update(
+                self.ui_state.git_status.as_ref(),
+                self.health_screen.report(),
+            );
+        }
+
         if self.ui_state.current_screen == Screen::MainMenu {
+            self.main_menu_screen
+                .set_notification_count(self.notifications_screen.count());
             self.main_menu_screen
                 .set_git_status(self.ui_state.git_status.clone());
+            self.main_menu_screen
+                .set_checking_remote(self.git_status_receiver.is_some());
+            self.main_menu_screen.set_health_summary(
+                self.health_screen
+                    .summary()
+                    .map(|summary| (summary.errors, summary.warnings)),
+                self.health_screen.is_busy(),
+            );
         }
 
         // DotfileSelectionScreen handles its own state and rendering
@@ -438,7 +823,10 @@ impl App {
                     self.main_menu_screen.update_config(config_clone.clone());
                     // Router pattern - delegate to screen's render method
                     use crate::screens::{RenderContext, Screen as ScreenTrait};
-                    let syntax_theme = crate::utils::get_current_syntax_theme(&self.theme_set);
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
                     let ctx = RenderContext::new(
                         &config_clone,
                         &self.syntax_set,
@@ -452,7 +840,10 @@ impl App {
                 Screen::StorageSetup => {
                     // Router pattern - delegate to screen's render method
                     use crate::screens::{RenderContext, Screen as ScreenTrait};
-                    let syntax_theme = crate::utils::get_current_syntax_theme(&self.theme_set);
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
                     let ctx = RenderContext::new(
                         &config_clone,
                         &self.syntax_set,
@@ -466,7 +857,10 @@ impl App {
                 Screen::DotfileSelection => {
                     // Router pattern - delegate to screen's render method
                     use crate::screens::{RenderContext, Screen as ScreenTrait};
-                    let syntax_theme = crate::utils::get_current_syntax_theme(&self.theme_set);
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
                     let ctx = RenderContext::new(
                         &config_clone,
                         &self.syntax_set,
@@ -480,7 +874,10 @@ impl App {
                 Screen::SyncWithRemote => {
                     // Router pattern - delegate to screen's render method
                     use crate::screens::{RenderContext, Screen as ScreenTrait};
-                    let syntax_theme = crate::utils::get_current_syntax_theme(&self.theme_set);
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
                     let ctx = RenderContext::new(
                         &config_clone,
                         &self.syntax_set,
@@ -494,7 +891,10 @@ impl App {
                 Screen::ManageProfiles => {
                     // Router pattern - delegate to screen's render method
                     use crate::screens::{RenderContext, Screen as ScreenTrait};
-                    let syntax_theme = crate::utils::get_current_syntax_theme(&self.theme_set);
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
                     let ctx = RenderContext::new(
                         &config_clone,
                         &self.syntax_set,
@@ -507,7 +907,10 @@ impl App {
                 }
                 Screen::ManagePackages => {
                     use crate::screens::{RenderContext, Screen as ScreenTrait};
-                    let syntax_theme = crate::utils::get_current_syntax_theme(&self.theme_set);
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
                     let ctx = RenderContext::new(
                         &config_clone,
                         &self.syntax_set,
@@ -526,7 +929,10 @@ impl App {
                 Screen::Settings => {
                     // Router pattern - delegate to screen's render method
                     use crate::screens::{RenderContext, Screen as ScreenTrait};
-                    let syntax_theme = crate::utils::get_current_syntax_theme(&self.theme_set);
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
                     let ctx = RenderContext::new(
                         &config_clone,
                         &self.syntax_set,
@@ -537,6 +943,182 @@ impl App {
                         error!("Failed to render settings screen: {}", e);
                     }
                 }
+                Screen::Health => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.health_screen.render(frame, area, &ctx) {
+                        error!("Failed to render health screen: {}", e);
+                    }
+                }
+                Screen::History => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.history_screen.render(frame, area, &ctx) {
+                        error!("Failed to render history screen: {}", e);
+                    }
+                }
+                Screen::Machines => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.machines_screen.render(frame, area, &ctx) {
+                        error!("Failed to render machines screen: {}", e);
+                    }
+                }
+                Screen::Import => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.import_screen.render(frame, area, &ctx) {
+                        error!("Failed to render import screen: {}", e);
+                    }
+                }
+                Screen::Aliases => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.aliases_screen.render(frame, area, &ctx) {
+                        error!("Failed to render aliases screen: {}", e);
+                    }
+                }
+                Screen::Stats => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.stats_screen.render(frame, area, &ctx) {
+                        error!("Failed to render stats screen: {}", e);
+                    }
+                }
+                Screen::Notifications => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.notifications_screen.render(frame, area, &ctx) {
+                        error!("Failed to render notifications screen: {}", e);
+                    }
+                }
+                Screen::Bootstrap => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.bootstrap_screen.render(frame, area, &ctx) {
+                        error!("Failed to render bootstrap screen: {}", e);
+                    }
+                }
+                Screen::ForceSync => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.force_sync_screen.render(frame, area, &ctx) {
+                        error!("Failed to render force sync screen: {}", e);
+                    }
+                }
+                Screen::Reconcile => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.reconcile_screen.render(frame, area, &ctx) {
+                        error!("Failed to render reconcile screen: {}", e);
+                    }
+                }
+                Screen::Reauth => {
+                    use crate::screens::{RenderContext, Screen as ScreenTrait};
+                    let syntax_theme = crate::utils::get_current_syntax_theme(
+                        &self.theme_set,
+                        &config_clone.syntax_theme,
+                    );
+                    let ctx = RenderContext::new(
+                        &config_clone,
+                        &self.syntax_set,
+                        &self.theme_set,
+                        syntax_theme,
+                    );
+                    if let Err(e) = self.reauth_screen.render(frame, area, &ctx) {
+                        error!("Failed to render re-authenticate screen: {}", e);
+                    }
+                }
             }
 
             // Render profile selection popup on top of screen content
@@ -545,7 +1127,11 @@ impl App {
 
             // Render dialog on top of screen content (modal overlay)
             if let Some(ref dialog) = self.dialog_state {
-                let footer = "↑↓/jk: Scroll  Enter: Close";
+                let footer = if matches!(dialog.variant, DialogVariant::Error) {
+                    "↑↓/jk: Scroll  r: Copy Error Report  Enter: Close"
+                } else {
+                    "↑↓/jk: Scroll  Enter: Close"
+                };
                 let dlg = Dialog::new(&dialog.title, &dialog.content)
                     .variant(dialog.variant)
                     .height(50) // Increased height for better visibility
@@ -598,6 +1184,12 @@ impl App {
                 self.manage_packages_screen.is_input_focused()
             }
 
+            // Aliases - add/edit popup with text input
+            Screen::Aliases => {
+                use crate::screens::Screen as ScreenTrait;
+                self.aliases_screen.is_input_focused()
+            }
+
             // Storage Setup - form has text input
             Screen::StorageSetup => {
                 use crate::screens::Screen as ScreenTrait;
@@ -673,6 +1265,10 @@ impl App {
                         self.ui_state.show_help_overlay = !self.ui_state.show_help_overlay;
                         return Ok(());
                     }
+                    if action == Action::TogglePreview {
+                        crate::utils::layout::toggle_side_panes();
+                        return Ok(());
+                    }
                 }
             }
         }
@@ -748,6 +1344,10 @@ impl App {
                         KeyCode::Home => {
                             dialog.scroll_offset = 0;
                         }
+                        KeyCode::Char('r') if matches!(dialog.variant, DialogVariant::Error) => {
+                            let error = format!("{}\n\n{}", dialog.title, dialog.content);
+                            self.copy_error_report(&error);
+                        }
                         // Dismiss on Enter, Esc, or 'q'
                         KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => {
                             self.dialog_state = None;
@@ -879,6 +1479,127 @@ impl App {
                 self.process_screen_action(action)?;
                 Ok(())
             }
+            Screen::Health => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.health_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::History => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.history_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Machines => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.machines_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Import => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.import_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Aliases => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.aliases_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Stats => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.stats_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Notifications => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.notifications_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Bootstrap => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.bootstrap_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::ForceSync => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.force_sync_screen.handle_event(event, &ctx)?;
+
+                // Back on the sync screen, reload changes and remote status
+                if let crate::screens::ScreenAction::Navigate(Screen::SyncWithRemote) = &action {
+                    self.handle_menu_navigation(Screen::SyncWithRemote)?;
+                }
+
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Reconcile => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.reconcile_screen.handle_event(event, &ctx)?;
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+            Screen::Reauth => {
+                use crate::screens::ScreenContext;
+                let ctx = ScreenContext::new(&self.config, &self.config_path);
+                let action = self.reauth_screen.handle_event(event, &ctx)?;
+                if let crate::screens::ScreenAction::Navigate(target) = &action {
+                    self.handle_menu_navigation(*target)?;
+                }
+                self.process_screen_action(action)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Send the user to the re-authenticate screen from a background check.
+    ///
+    /// Only interrupts the main menu, and only once per launch; elsewhere a
+    /// toast points at the problem instead.
+    fn prompt_reauth(&mut self, reason: crate::services::ReauthReason) {
+        use crate::widgets::ToastVariant;
+        if self.has_prompted_reauth || self.ui_state.current_screen == Screen::Reauth {
+            return;
+        }
+        self.has_prompted_reauth = true;
+        if self.ui_state.current_screen == Screen::MainMenu {
+            self.open_reauth(reason);
+        } else {
+            self.toast_manager.push(Toast::new(
+                reason.message(chrono::Utc::now()),
+                ToastVariant::Warning,
+            ));
+        }
+    }
+
+    /// Open the re-authenticate screen, returning to the current screen afterwards.
+    fn open_reauth(&mut self, reason: crate::services::ReauthReason) {
+        let return_to = match self.ui_state.current_screen {
+            Screen::SyncWithRemote | Screen::ForceSync | Screen::Reconcile => {
+                Screen::SyncWithRemote
+            }
+            _ => Screen::MainMenu,
+        };
+        self.reauth_screen.set_reason(reason, return_to);
+        self.ui_state.current_screen = Screen::Reauth;
+        if let Err(e) = self.call_on_enter(Screen::Reauth) {
+            error!("Failed to open re-authenticate screen: {}", e);
         }
     }
 
@@ -910,6 +1631,8 @@ impl App {
         thread::spawn(move || {
             let status =
                 crate::services::git_service::GitService::fetch_and_check_status(&config_clone);
+            // Keep `dotstate prompt` up to date
+            crate::services::git_service::GitService::record_status(&status);
             // Ignore send error
             let _ = tx.send(status);
         });
@@ -917,6 +1640,73 @@ impl App {
         self.git_status_receiver = Some(rx);
     }
 
+    /// Pull from remote in the background (the "pull now" banner action)
+    fn start_pull(&mut self) {
+        if self.pull_receiver.is_some() {
+            return;
+        }
+        info!("Pulling from remote before editing");
+        let config_clone = self.config.clone();
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            let result = crate::services::git_service::GitService::pull(&config_clone);
+            let _ = tx.send(result);
+        });
+        self.pull_receiver = Some(rx);
+    }
+
+    /// Report a finished pull and reload the screen it was started from
+    fn finish_pull(&mut self, result: crate::services::git_service::SyncResult) {
+        use crate::services::TokenService;
+        use crate::widgets::ToastVariant;
+        if !result.success
+            && TokenService::uses_token(&self.config)
+            && TokenService::is_auth_failure(&result.message)
+        {
+            self.open_reauth(crate::services::ReauthReason::Rejected);
+            return;
+        }
+        if !result.success {
+            self.dialog_state = Some(DialogState {
+                title: "Pull Failed".to_string(),
+                content: result.message,
+                variant: DialogVariant::Error,
+                scroll_offset: 0,
+            });
+            return;
+        }
+
+        let message = match result.pulled_count {
+            Some(count) if count > 0 => format!("Pulled {count} change(s) from remote"),
+            _ => "Already up to date with remote".to_string(),
+        };
+        self.toast_manager
+            .push(Toast::new(message, ToastVariant::Success));
+
+        // The banner goes away now; the status check confirms it
+        if let Some(status) = &mut self.ui_state.git_status {
+            status.behind = 0;
+        }
+        self.trigger_git_status_check(true);
+
+        match self.ui_state.current_screen {
+            Screen::DotfileSelection => {
+                if let Err(e) = self.dotfile_selection_screen.scan_dotfiles(&self.config) {
+                    error!("Failed to rescan dotfiles after pull: {}", e);
+                }
+            }
+            Screen::ManageProfiles => {
+                if let Err(e) = self
+                    .manage_profiles_screen
+                    .refresh_profiles(&self.config.repo_path)
+                {
+                    error!("Failed to refresh profiles after pull: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Handle navigation-specific logic when navigating from `MainMenu`
     fn handle_menu_navigation(&mut self, target: Screen) -> Result<()> {
         match target {
@@ -935,6 +1725,10 @@ impl App {
                 // Trigger git status check to fetch ahead/behind commits
                 self.trigger_git_status_check(true);
             }
+            Screen::ManageProfiles => {
+                // Check whether the remote is ahead before editing profiles
+                self.trigger_git_status_check(false);
+            }
 
             Screen::ManagePackages
                 if self.config.active_profile
@@ -1033,11 +1827,40 @@ impl App {
                     }
                 }
             }
+            ScreenAction::UpdateTextSetting { setting, value } => {
+                if crate::screens::settings::SettingsScreen::apply_text_setting_to_config(
+                    &mut self.config,
+                    &setting,
+                    &value,
+                ) {
+                    if let Err(e) = self.config.save(&self.config_path) {
+                        error!("Failed to save config after settings change: {}", e);
+                    }
+                }
+            }
             ScreenAction::SetHasChanges(has_changes) => {
                 self.ui_state.has_changes_to_push = has_changes;
             }
+            ScreenAction::Reauthenticate => {
+                self.open_reauth(crate::services::ReauthReason::Rejected);
+            }
+            ScreenAction::TokenReplaced => {
+                use crate::widgets::ToastVariant;
+                self.config = Config::load_or_create(&self.config_path)?;
+                self.has_prompted_reauth = false;
+                self.toast_manager.push(Toast::new(
+                    "GitHub token updated".to_string(),
+                    ToastVariant::Success,
+                ));
+                let target = self.reauth_screen.return_to();
+                self.handle_menu_navigation(target)?;
+                self.ui_state.current_screen = target;
+                self.call_on_enter(target)?;
+                self.trigger_git_status_check(true);
+            }
             ScreenAction::ConfigUpdated => {
-                // Reload config if needed
+                // Reload config from disk (e.g. after a doctor fix rewrote it)
+                self.config = Config::load_or_create(&self.config_path)?;
             }
             ScreenAction::ShowHelp => {
                 self.ui_state.show_help_overlay = true;
@@ -1073,6 +1896,7 @@ impl App {
                     self.storage_setup_screen.reset();
                     self.main_menu_screen.update_config(self.config.clone());
                     self.ui_state.current_screen = Screen::MainMenu;
+                    self.warn_about_foreign_managers();
                 } else {
                     // Show profile selection popup
                     self.storage_setup_screen.reset();
@@ -1109,6 +1933,7 @@ impl App {
                 state.step = StorageSetupStep::Processing(GitHubSetupStep::Connecting);
                 state.status_message = Some("Connecting to GitHub...".to_string());
                 state.setup_data = Some(data.clone());
+                crate::services::SetupProgress::new(GitHubSetupStep::Connecting, &data).save();
 
                 // Start async setup
                 self.setup_step_handle = Some(crate::services::StorageSetupService::start_step(
@@ -1118,78 +1943,55 @@ impl App {
                     &self.config,
                 ));
             }
+            ScreenAction::ResumeGitHubSetup { progress } => {
+                use crate::screens::storage_setup::StorageSetupStep;
+
+                let step = progress.resume_step();
+                if step == GitHubSetupStep::CloningRepo {
+                    crate::services::StorageSetupService::discard_partial_clone(
+                        &self.config.repo_path,
+                    );
+                }
+                let data = progress.setup_data();
+                let state = self.storage_setup_screen.get_state_mut();
+                state.step = StorageSetupStep::Processing(step);
+                state.status_message = Some(format!("Resuming: {}...", step.label()));
+                state.setup_data = Some(data.clone());
+                crate::services::SetupProgress::new(step, &data).save();
+
+                self.setup_step_handle = Some(crate::services::StorageSetupService::start_step(
+                    &self.runtime,
+                    step,
+                    data,
+                    &self.config,
+                ));
+            }
             ScreenAction::UpdateGitHubToken { token } => {
-                // Update the GitHub token with validation and remote URL update
-                let github_config = if let Some(gh) = &self.config.github {
-                    gh.clone()
-                } else {
+                // Validate the token against the repository and replace it in place
+                let mut updated = self.config.clone();
+                if let Err(e) = crate::services::TokenService::replace_token(&mut updated, &token) {
                     self.storage_setup_screen.get_state_mut().error_message =
-                        Some("No GitHub configuration to update".to_string());
+                        Some(format!("Token validation failed: {e}"));
+                    return Ok(());
+                }
+                if let Err(e) = updated.save(&self.config_path) {
+                    self.storage_setup_screen.get_state_mut().error_message =
+                        Some(format!("Failed to save token: {e}"));
                     return Ok(());
-                };
-
-                // Show validating status
-                self.storage_setup_screen.get_state_mut().status_message =
-                    Some("Validating token access to repository...".to_string());
-
-                // Validate the token by checking repo access (not user info)
-                // This works with scoped tokens that only have repo access
-                let owner = github_config.owner.clone();
-                let repo = github_config.repo.clone();
-                let validation_result = self.runtime.block_on(async {
-                    let client = crate::github::GitHubClient::new(token.clone());
-                    client.repo_exists(&owner, &repo).await
-                });
-
-                match validation_result {
-                    Ok(exists) => {
-                        if !exists {
-                            self.storage_setup_screen.get_state_mut().error_message =
-                                Some(format!("Token cannot access repository {owner}/{repo}"));
-                            return Ok(());
-                        }
-
-                        // Token can access repo - update config
-                        if let Some(ref mut github) = self.config.github {
-                            github.token = Some(token.clone());
-                        }
-
-                        // Update the git remote URL with new token
-                        if self.config.repo_path.exists() {
-                            match crate::git::GitManager::open_or_init(&self.config.repo_path) {
-                                Ok(mut git_manager) => {
-                                    if let Err(e) =
-                                        git_manager.update_remote_token("origin", &token)
-                                    {
-                                        // Non-fatal: log warning but continue
-                                        warn!("Failed to update remote URL with new token: {}", e);
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("Failed to open git repository to update token: {}", e);
-                                }
-                            }
-                        }
-
-                        // Save config
-                        if let Err(e) = self.config.save(&self.config_path) {
-                            self.storage_setup_screen.get_state_mut().error_message =
-                                Some(format!("Failed to save token: {e}"));
-                            return Ok(());
-                        }
-
-                        // Show success and reset
-                        self.storage_setup_screen.get_state_mut().status_message =
-                            Some(format!("✅ Token updated for {owner}/{repo}"));
-                        self.storage_setup_screen.get_state_mut().is_editing_token = false;
-                        self.storage_setup_screen.get_state_mut().token_input =
-                            crate::utils::TextInput::with_text("••••••••••••••••••••");
-                    }
-                    Err(e) => {
-                        self.storage_setup_screen.get_state_mut().error_message =
-                            Some(format!("Token validation failed: {e}"));
-                    }
                 }
+                self.config = updated;
+
+                // Show success and reset
+                let (owner, repo) = self
+                    .config
+                    .github
+                    .as_ref()
+                    .map(|gh| (gh.owner.clone(), gh.repo.clone()))
+                    .unwrap_or_default();
+                let state = self.storage_setup_screen.get_state_mut();
+                state.status_message = Some(format!("✅ Token updated for {owner}/{repo}"));
+                state.is_editing_token = false;
+                state.token_input = crate::utils::TextInput::with_text("••••••••••••••••••••");
             }
             ScreenAction::ShowProfileSelection { profiles: _ } => {
                 // Show the profile selection popup (it loads profiles from manifest)
@@ -1212,6 +2014,7 @@ impl App {
             }
             // Dotfile selection actions
             // Dotfile selection actions - delegate to screen
+            ScreenAction::PullFromRemote => self.start_pull(),
             ScreenAction::ScanDotfiles => {
                 use crate::screens::dotfile_selection::DotfileAction;
                 let result = self.dotfile_selection_screen.process_action(
@@ -1294,11 +2097,13 @@ impl App {
             }
             ScreenAction::SwitchProfile { name } => {
                 use crate::screens::manage_profiles::ProfileAction;
+                let previous_theme = self.config.theme.clone();
                 let result = self.manage_profiles_screen.process_action(
                     ProfileAction::SwitchProfile { name },
                     &mut self.config,
                     &self.config_path,
                 )?;
+                self.refresh_profile_theme(&previous_theme);
                 self.handle_action_result(result)?;
             }
             ScreenAction::RenameProfile { old_name, new_name } => {
@@ -1319,6 +2124,18 @@ impl App {
                 )?;
                 self.handle_action_result(result)?;
             }
+            ScreenAction::EditFile { path } => {
+                // The editor gets the terminal until it exits
+                self.tui.exit()?;
+                let edited = crate::utils::external::edit(&path);
+                self.tui.enter()?;
+                self.tui.terminal_mut().clear()?;
+                if let Err(e) = edited {
+                    use crate::widgets::ToastVariant;
+                    self.toast_manager
+                        .push(Toast::new(format!("{e:#}"), ToastVariant::Error));
+                }
+            }
             ScreenAction::MoveToCommon {
                 file_index,
                 is_common,
@@ -1336,6 +2153,15 @@ impl App {
                 )?;
                 self.handle_action_result(result)?;
             }
+            ScreenAction::StoreLargeFile { storage } => {
+                use crate::screens::dotfile_selection::DotfileAction;
+                let result = self.dotfile_selection_screen.process_action(
+                    DotfileAction::StoreLargeFile { storage },
+                    &mut self.config,
+                    &self.config_path,
+                )?;
+                self.handle_action_result(result)?;
+            }
             ScreenAction::RemoveCustomFile { file_index } => {
                 use crate::screens::dotfile_selection::DotfileAction;
                 let result = self.dotfile_selection_screen.process_action(
@@ -1345,6 +2171,39 @@ impl App {
                 )?;
                 self.handle_action_result(result)?;
             }
+            ScreenAction::MoveManyToCommon { files } => {
+                use crate::screens::dotfile_selection::DotfileAction;
+                let result = self.dotfile_selection_screen.process_action(
+                    DotfileAction::MoveManyToCommon { files },
+                    &mut self.config,
+                    &self.config_path,
+                )?;
+                self.handle_action_result(result)?;
+            }
+            ScreenAction::ApplyFileQueue { operations } => {
+                use crate::screens::dotfile_selection::DotfileAction;
+                let result = self.dotfile_selection_screen.process_action(
+                    DotfileAction::ApplyFileQueue { operations },
+                    &mut self.config,
+                    &self.config_path,
+                )?;
+                self.handle_action_result(result)?;
+            }
+            ScreenAction::RenameFile {
+                file_index,
+                new_path,
+            } => {
+                use crate::screens::dotfile_selection::DotfileAction;
+                let result = self.dotfile_selection_screen.process_action(
+                    DotfileAction::RenameFile {
+                        file_index,
+                        new_path,
+                    },
+                    &mut self.config,
+                    &self.config_path,
+                )?;
+                self.handle_action_result(result)?;
+            }
         }
         Ok(())
     }
@@ -1398,10 +2257,64 @@ impl App {
             }
             Screen::ManagePackages => self.manage_packages_screen.on_enter(&ctx)?,
             Screen::Settings => self.settings_screen.on_enter(&ctx)?,
+            Screen::Health => self.health_screen.on_enter(&ctx)?,
+            Screen::History => self.history_screen.on_enter(&ctx)?,
+            Screen::Machines => self.machines_screen.on_enter(&ctx)?,
+            Screen::Import => self.import_screen.on_enter(&ctx)?,
+            Screen::Aliases => self.aliases_screen.on_enter(&ctx)?,
+            Screen::Stats => self.stats_screen.on_enter(&ctx)?,
+            Screen::Notifications => self.notifications_screen.on_enter(&ctx)?,
+            Screen::Bootstrap => self.bootstrap_screen.on_enter(&ctx)?,
+            Screen::ForceSync => self.force_sync_screen.on_enter(&ctx)?,
+            Screen::Reconcile => self.reconcile_screen.on_enter(&ctx)?,
+            Screen::Reauth => self.reauth_screen.on_enter(&ctx)?,
         }
         Ok(())
     }
 
+    /// Setup wizard step: warn when another dotfile manager is already set up,
+    /// since files managed by both tools get overwritten by whichever runs last.
+    fn warn_about_foreign_managers(&mut self) {
+        let detected =
+            crate::utils::foreign_managers::detect_managers(&crate::utils::get_home_dir());
+        if detected.is_empty() || self.dialog_state.is_some() {
+            return;
+        }
+        info!(
+            "Detected other dotfile managers: {:?}",
+            detected
+                .iter()
+                .map(|d| d.manager.name())
+                .collect::<Vec<_>>()
+        );
+
+        let mut content = String::from("DotState found traces of other dotfile managers:\n\n");
+        for d in &detected {
+            content.push_str(&format!(
+                "  • {} ({})\n",
+                d.manager.name(),
+                d.evidence.display()
+            ));
+        }
+        content.push_str(
+            "\nIf a file is managed by both tools, whichever runs last wins.\n\n\
+            To move a file over, add it from Manage Files: DotState copies its current \
+            content into your repository and replaces the other tool's link. Then stop \
+            managing it in the other tool:\n\n",
+        );
+        for d in &detected {
+            content.push_str(&format!("  {}\n", d.manager.release_hint()));
+        }
+        content.push_str("\nRun `dotstate doctor` at any time to find files linked by both.");
+
+        self.dialog_state = Some(DialogState {
+            title: "Other Dotfile Managers Detected".to_string(),
+            content,
+            variant: DialogVariant::Warning,
+            scroll_offset: 0,
+        });
+    }
+
     /// Handle the result from the profile selection popup
     fn handle_profile_selection_result(
         &mut self,
@@ -1453,7 +2366,10 @@ impl App {
         }
 
         // Set active profile and save config
+        let previous_theme = self.config.theme.clone();
         self.config.active_profile = name.to_string();
+        self.config.apply_profile_overrides();
+        self.refresh_profile_theme(&previous_theme);
         if let Err(e) = self.config.save(&self.config_path) {
             error!("Failed to save config with active profile: {}", e);
             self.dialog_state = Some(DialogState {
@@ -1490,9 +2406,16 @@ impl App {
                     return Ok(());
                 }
 
-                // Navigate to main menu
-                self.ui_state.current_screen = Screen::MainMenu;
-                self.call_on_enter(Screen::MainMenu)?;
+                // Offer the profile's bootstrap scripts on its first activation
+                // here, otherwise go to the main menu
+                let next =
+                    if crate::utils::bootstrap::pending(&self.config.repo_path, name).is_empty() {
+                        Screen::MainMenu
+                    } else {
+                        Screen::Bootstrap
+                    };
+                self.ui_state.current_screen = next;
+                self.call_on_enter(next)?;
 
                 // Show success toast
                 self.toast_manager.push(Toast::new(
@@ -1514,6 +2437,18 @@ impl App {
         Ok(())
     }
 
+    /// Record the failed setup step, so the next attempt resumes from it
+    fn record_setup_failure(&self, error_message: &str) {
+        use crate::screens::storage_setup::StorageSetupStep;
+
+        let state = self.storage_setup_screen.get_state();
+        if let (StorageSetupStep::Processing(step), Some(data)) = (state.step, &state.setup_data) {
+            let mut progress = crate::services::SetupProgress::new(step, data);
+            progress.error = Some(error_message.to_string());
+            progress.save();
+        }
+    }
+
     /// Handle the result of an async setup step
     fn handle_setup_step_result(&mut self, result: crate::services::StepResult) -> Result<()> {
         use crate::screens::storage_setup::StorageSetupStep;
@@ -1526,9 +2461,11 @@ impl App {
                 status_message,
                 delay_ms,
             } => {
+                crate::services::SetupProgress::new(next_step, &setup_data).save();
                 let state = self.storage_setup_screen.get_state_mut();
                 state.step = StorageSetupStep::Processing(next_step);
                 state.status_message = Some(status_message);
+                state.transfer_progress = None;
                 state.setup_data = Some(setup_data.clone());
 
                 // If there's a delay, we schedule the next step after the delay
@@ -1566,6 +2503,7 @@ impl App {
             } => {
                 // Clear the step handle - setup is complete
                 self.setup_step_handle = None;
+                crate::services::SetupProgress::clear();
 
                 // Update config with GitHub info
                 self.config.github = Some(github_config.clone());
@@ -1574,6 +2512,7 @@ impl App {
 
                 // Reset screen state
                 self.storage_setup_screen.reset();
+                self.warn_about_foreign_managers();
 
                 // Navigate based on profiles found
                 if profiles.is_empty() {
@@ -1610,6 +2549,7 @@ impl App {
             } => {
                 // Clear the step handle - setup failed
                 self.setup_step_handle = None;
+                self.record_setup_failure(&error_message);
 
                 crate::services::StorageSetupService::cleanup_failed_setup(
                     &mut self.config,
@@ -0,0 +1,87 @@
+//! Storage backend CLI commands.
+//!
+//! Moves the encrypted archive configured in `[storage_backend]` by hand:
+//! - `status` - Where the archive is kept and whether it replaces origin
+//! - `push` - Upload the current branch
+//! - `pull` - Download the archive and rebase onto it
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::config::{BackendKind, StorageBackendConfig};
+use crate::git::GitManager;
+use crate::utils::storage_backend;
+use anyhow::{Context, Result};
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum BackendCommand {
+    /// Show the configured backend
+    Status,
+    /// Upload an encrypted archive of the current branch
+    Push,
+    /// Download the archive and rebase the current branch onto it
+    Pull,
+}
+
+/// Execute a backend subcommand
+pub fn execute(command: BackendCommand) -> Result<()> {
+    let ctx = CliContext::load()?;
+    let Some(backend) = ctx.config.storage_backend.as_ref() else {
+        print_error("No storage backend is configured");
+        println!("   Add a [storage_backend] section to the config file; see the README.");
+        std::process::exit(1);
+    };
+    let repo_path = &ctx.config.repo_path;
+    let git_mgr = GitManager::open_or_init(repo_path).context("Failed to open repository")?;
+    let branch = git_mgr
+        .get_current_branch()
+        .unwrap_or_else(|| ctx.config.default_branch.clone());
+
+    match command {
+        BackendCommand::Status => {
+            print_status(backend);
+            if storage_backend::replacing_remote(&ctx.config, &git_mgr).is_some() {
+                println!("  Mode:       replaces git hosting (no origin remote)");
+            } else {
+                println!("  Mode:       mirror of origin, uploaded after each push");
+            }
+        }
+        BackendCommand::Push => {
+            storage_backend::push(backend, repo_path, &branch)?;
+            print_success(&format!("Uploaded branch '{branch}' to {}", backend.object));
+        }
+        BackendCommand::Pull => {
+            if git_mgr.has_uncommitted_changes()? {
+                print_error("The repository has uncommitted changes; sync or stash them first");
+                std::process::exit(1);
+            }
+            match storage_backend::pull(backend, repo_path, &branch)? {
+                0 => print_info("Already up to date with the archive"),
+                count => print_success(&format!("Pulled {count} commit(s) from the archive")),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_status(backend: &StorageBackendConfig) {
+    let kind = match backend.kind {
+        BackendKind::S3 => format!("S3 ({})", backend.region),
+        BackendKind::Webdav => "WebDAV".to_string(),
+        BackendKind::Plugin => format!(
+            "plugin ({})",
+            backend.plugin.as_deref().unwrap_or("not set")
+        ),
+    };
+    println!("Storage backend:");
+    println!("  Kind:       {kind}");
+    println!("  URL:        {}", backend.url);
+    println!("  Archive:    {}", backend.object);
+    println!("  Recipients: {}", backend.recipients.len());
+    println!(
+        "  Identity:   {}",
+        backend
+            .identity
+            .as_deref()
+            .unwrap_or("(not set, pull disabled)")
+    );
+}
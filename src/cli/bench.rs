@@ -0,0 +1,250 @@
+//! Bench command: time the operations behind "dotstate is slow" reports.
+//!
+//! Each step runs a few times against the user's real repository and the
+//! median is printed, so the breakdown can be pasted into an issue. Nothing
+//! is changed: activation goes into a scratch home directory.
+
+use super::common::{print_info, CliContext};
+use crate::components::file_preview::FilePreview;
+use crate::git::DIFF_CHUNK_BYTES;
+use crate::services::{GitService, SyncService};
+use crate::utils::binary;
+use crate::utils::scratch::ScratchDir;
+use crate::utils::SymlinkManager;
+use anyhow::{Context, Result};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+
+/// Size of the terminal the preview is rendered into
+const PREVIEW_SIZE: (u16, u16) = (120, 40);
+
+/// A timed step
+struct Step {
+    name: &'static str,
+    median: Duration,
+    note: String,
+}
+
+/// Run `f` `runs` times and return the median time and the last result
+fn time<T>(runs: usize, mut f: impl FnMut() -> Result<T>) -> Result<(Duration, T)> {
+    let mut times = Vec::with_capacity(runs);
+    let mut last = None;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        last = Some(f()?);
+        times.push(start.elapsed());
+    }
+    times.sort();
+    let result = last.context("No runs")?;
+    Ok((times[times.len() / 2], result))
+}
+
+/// Execute the bench command, running each step `runs` times.
+pub fn execute(runs: usize) -> Result<()> {
+    let ctx = CliContext::load()?;
+    let config = &ctx.config;
+    let repo_path = &config.repo_path;
+    let profile = config.active_profile.clone();
+    let resolved = ctx.manifest.resolve_files(&profile)?;
+
+    println!("⏱  Timing dotstate on this machine (median of {runs} runs)...");
+    println!();
+
+    let mut steps = Vec::new();
+
+    let (median, found) = time(runs, || SyncService::scan_dotfiles(config))?;
+    steps.push(Step {
+        name: "Dotfile scan",
+        median,
+        note: format!("{} files found", found.len()),
+    });
+
+    let (median, operations) = time(runs, || {
+        let scratch = ScratchDir::new("dotstate-bench")?;
+        let home = scratch.path().join("home");
+        std::fs::create_dir(&home).context("Failed to create scratch home directory")?;
+        let mut symlink_mgr = SymlinkManager::new_with_data_dir(
+            repo_path.clone(),
+            false,
+            scratch.path().join("data"),
+        )?;
+        symlink_mgr.activate_resolved_with_home(&profile, &resolved, &home)
+    })?;
+    steps.push(Step {
+        name: "Activation",
+        median,
+        note: format!(
+            "{} files of '{profile}', into a scratch home",
+            operations.len()
+        ),
+    });
+
+    let (median, changed) = time(runs, || Ok(GitService::load_changed_files(repo_path)))?;
+    steps.push(Step {
+        name: "git status",
+        median,
+        note: format!("{} changed files", changed.len()),
+    });
+
+    let (median, diffs) = time(runs, || {
+        Ok(changed
+            .iter()
+            .filter_map(|file| GitService::get_diff_for_file(repo_path, file, DIFF_CHUNK_BYTES))
+            .map(|diff| diff.text.len())
+            .sum::<usize>())
+    })?;
+    steps.push(Step {
+        name: "git diff",
+        median,
+        note: format!("{} bytes over {} files", diffs, changed.len()),
+    });
+
+    let (median, (syntax_set, theme_set)) = time(runs, || {
+        Ok((
+            SyntaxSet::load_defaults_newlines(),
+            ThemeSet::load_defaults(),
+        ))
+    })?;
+    steps.push(Step {
+        name: "Syntax definitions",
+        median,
+        note: "loaded once at TUI startup".to_string(),
+    });
+
+    match largest_file(repo_path, &resolved) {
+        Some((path, size)) => {
+            let theme = crate::utils::syntax_theme::get_current_syntax_theme(
+                &theme_set,
+                &config.syntax_theme,
+            );
+            let (median, ()) = time(runs, || {
+                let mut terminal = Terminal::new(TestBackend::new(PREVIEW_SIZE.0, PREVIEW_SIZE.1))?;
+                let mut result = Ok(());
+                terminal.draw(|frame| {
+                    result = FilePreview::render(
+                        frame,
+                        frame.area(),
+                        &path,
+                        &mut 0,
+                        &mut crate::utils::ScrollbarDrag::new(),
+                        true,
+                        None,
+                        None,
+                        &syntax_set,
+                        theme,
+                        config,
+                    );
+                })?;
+                result
+            })?;
+            let name = path.strip_prefix(repo_path).unwrap_or(&path);
+            steps.push(Step {
+                name: "Preview render",
+                median,
+                note: format!("{} ({})", name.display(), binary::format_size(size)),
+            });
+        }
+        None => print_info("No synced files to preview; skipping the preview render"),
+    }
+
+    let width = steps.iter().map(|step| step.name.len()).max().unwrap_or(0);
+    for step in &steps {
+        println!(
+            "  {:<width$}  {:>10}   {}",
+            step.name,
+            format_duration(step.median),
+            step.note
+        );
+    }
+    println!();
+    println!(
+        "   dotstate {} · {} {}",
+        crate::version_check::current_version(),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    println!("   Include this output when reporting slowness.");
+    Ok(())
+}
+
+/// The largest synced file of the profile, with its size in bytes
+fn largest_file(
+    repo_path: &Path,
+    resolved: &[crate::utils::ResolvedFile],
+) -> Option<(PathBuf, u64)> {
+    resolved
+        .iter()
+        .map(|file| {
+            repo_path
+                .join(&file.source_profile)
+                .join(&file.relative_path)
+        })
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(&path).ok()?;
+            metadata.is_file().then_some((path, metadata.len()))
+        })
+        .max_by_key(|(_, size)| *size)
+}
+
+fn format_duration(duration: Duration) -> String {
+    let ms = duration.as_secs_f64() * 1000.0;
+    if ms >= 1000.0 {
+        format!("{:.2} s", ms / 1000.0)
+    } else {
+        format!("{ms:.1} ms")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_returns_the_median_and_last_result() {
+        let sleeps = [30, 1, 10];
+        let mut run = 0;
+        let (median, last) = time(sleeps.len(), || {
+            std::thread::sleep(Duration::from_millis(sleeps[run]));
+            run += 1;
+            Ok(run)
+        })
+        .unwrap();
+        assert_eq!(last, 3);
+        assert!(median >= Duration::from_millis(10));
+        assert!(median < Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_time_runs_at_least_once() {
+        let mut calls = 0;
+        let (_, last) = time(0, || {
+            calls += 1;
+            Ok("done")
+        })
+        .unwrap();
+        assert_eq!(calls, 1);
+        assert_eq!(last, "done");
+    }
+
+    #[test]
+    fn test_time_stops_at_the_first_error() {
+        let mut calls = 0;
+        let result = time(3, || -> Result<()> {
+            calls += 1;
+            anyhow::bail!("boom")
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(2500)), "2.5 ms");
+        assert_eq!(format_duration(Duration::from_millis(999)), "999.0 ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50 s");
+    }
+}
@@ -0,0 +1,104 @@
+//! Bootstrap command: run a profile's bootstrap scripts.
+
+use super::prompt_confirm;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::utils::bootstrap::{self, BootstrapScript};
+use anyhow::{Context, Result};
+use std::io::IsTerminal;
+
+/// Execute the bootstrap command: run the scripts of `profile` (default: the
+/// active profile), even if they already ran on this machine.
+pub fn execute(profile: Option<String>, yes: bool) -> Result<()> {
+    let config_path = crate::utils::get_config_path();
+    let config = Config::load_or_create(&config_path).context("Failed to load configuration")?;
+    let icons = Icons::from_config(&config);
+
+    if !config.is_repo_configured() {
+        eprintln!(
+            "{} Repository not configured. Please run 'dotstate' to set up repository.",
+            icons.error()
+        );
+        std::process::exit(1);
+    }
+
+    let profile = profile.unwrap_or_else(|| config.active_profile.clone());
+    let scripts = bootstrap::scripts(&config.repo_path, &profile)?;
+    if scripts.is_empty() {
+        println!(
+            "{} Profile '{profile}' has no bootstrap scripts ({} or {}/ in its directory).",
+            icons.info(),
+            bootstrap::BOOTSTRAP_SCRIPT,
+            bootstrap::BOOTSTRAP_DIR
+        );
+        return Ok(());
+    }
+
+    print_scripts(&config, &profile, &scripts);
+    if !yes && !prompt_confirm("Run them now?")? {
+        return Ok(());
+    }
+    if !run_scripts(&config, &profile, &scripts, &icons) {
+        std::process::exit(1);
+    }
+    bootstrap::mark_done(&profile);
+    Ok(())
+}
+
+/// Offer to run the bootstrap scripts of a profile that was just activated,
+/// if it was never bootstrapped on this machine. Declining is remembered;
+/// `dotstate bootstrap` runs them later.
+pub fn offer_after_activation(config: &Config, icons: &Icons) -> Result<()> {
+    let profile = &config.active_profile;
+    let scripts = bootstrap::pending(&config.repo_path, profile);
+    if scripts.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    print_scripts(config, profile, &scripts);
+    if !std::io::stdin().is_terminal() {
+        println!("   Run 'dotstate bootstrap' to run them.");
+        return Ok(());
+    }
+    if prompt_confirm("Run them now?")? {
+        if run_scripts(config, profile, &scripts, icons) {
+            bootstrap::mark_done(profile);
+        } else {
+            println!("   Run 'dotstate bootstrap' to try again.");
+        }
+    } else {
+        bootstrap::mark_done(profile);
+        println!("   Skipped. Run 'dotstate bootstrap' to run them later.");
+    }
+    Ok(())
+}
+
+fn print_scripts(config: &Config, profile: &str, scripts: &[BootstrapScript]) {
+    println!("Bootstrap scripts for profile '{profile}':");
+    for script in scripts {
+        println!("   • {}", script.display_name(&config.repo_path));
+    }
+}
+
+/// Run the scripts in order with the terminal attached, stopping at the
+/// first failure. Returns whether all succeeded.
+fn run_scripts(config: &Config, profile: &str, scripts: &[BootstrapScript], icons: &Icons) -> bool {
+    for script in scripts {
+        let name = script.display_name(&config.repo_path);
+        println!("\n{} Running {name}", icons.sync());
+        match script.command(&config.repo_path, profile).status() {
+            Ok(status) if status.success() => {}
+            Ok(status) => {
+                eprintln!("{} {name} failed ({status})", icons.error());
+                return false;
+            }
+            Err(e) => {
+                eprintln!("{} Failed to run {name}: {e}", icons.error());
+                return false;
+            }
+        }
+    }
+    println!("\n{} Bootstrap complete", icons.success());
+    true
+}
@@ -0,0 +1,58 @@
+//! Broadcast command: sync here, then on every other registered machine.
+
+use super::{print_error, print_info, print_success, print_warning, CliContext};
+use crate::utils::broadcast::{self, Outcome};
+use crate::utils::machine_registry::{current_hostname, MachineRegistry};
+use crate::utils::parallel::parallel_map;
+use anyhow::Result;
+
+/// Execute the broadcast command: sync this machine (unless `no_sync`), then
+/// sync and activate on the machines in `machines.toml` over SSH (only
+/// `hosts` when given), reporting each one.
+pub fn execute(hosts: &[String], no_sync: bool) -> Result<()> {
+    if !no_sync {
+        super::sync::execute(None)?;
+        println!();
+    }
+    let config = CliContext::load()?.config;
+    let registry = MachineRegistry::load(&config.repo_path)?;
+    let targets = broadcast::hosts(&registry, &config.ssh_hosts, &current_hostname(), hosts)?;
+    if targets.is_empty() {
+        print_info("No other machines to broadcast to (machines that haven't synced for a month are skipped)");
+        return Ok(());
+    }
+
+    print_info(&format!(
+        "Syncing {} machine(s): {}",
+        targets.len(),
+        targets
+            .iter()
+            .map(|h| h.hostname.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    let outcomes = parallel_map(&targets, broadcast::run);
+    let mut failed = false;
+    for (host, outcome) in targets.iter().zip(&outcomes) {
+        match outcome {
+            Outcome::Updated => print_success(&format!("{}: synced and activated", host.hostname)),
+            Outcome::Unreachable(reason) => {
+                print_warning(&format!("{}: unreachable ({reason})", host.hostname));
+            }
+            Outcome::Failed(reason) => {
+                print_error(&format!("{}: {reason}", host.hostname));
+                failed = true;
+            }
+        }
+    }
+    if outcomes
+        .iter()
+        .any(|o| matches!(o, Outcome::Unreachable(_)))
+    {
+        println!("   Map a hostname to an SSH destination under [ssh_hosts] in the config.");
+    }
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! CI command: check a storage repository checkout without a terminal.
+
+use super::{print_error, print_success, print_warning};
+use crate::utils::ci::{self, Finding};
+use crate::utils::fsck::Severity;
+use crate::utils::scratch::ScratchDir;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Execute the ci command against `repo` (default: the current directory).
+/// Prints GitHub Actions annotations with `annotations` or when running in
+/// GitHub Actions. Exits with status 1 when there are errors.
+pub fn execute(repo: Option<PathBuf>, profiles: &[String], annotations: bool) -> Result<()> {
+    let repo_path = match repo {
+        Some(path) => path,
+        None => std::env::current_dir().context("Failed to get the current directory")?,
+    };
+    let repo_path = repo_path
+        .canonicalize()
+        .with_context(|| format!("Repository not found: {}", repo_path.display()))?;
+    let annotations = annotations || std::env::var("GITHUB_ACTIONS").is_ok_and(|v| v == "true");
+
+    let scratch = ScratchDir::new("dotstate-ci")?;
+    let findings = ci::run(&repo_path, profiles, scratch.path());
+    drop(scratch);
+    let findings = findings?;
+
+    for finding in &findings {
+        if annotations {
+            println!("{}", ci::annotation(finding));
+        }
+        let line = describe(finding);
+        match finding.severity {
+            Severity::Error => print_error(&line),
+            Severity::Warning => print_warning(&line),
+        }
+    }
+
+    let errors = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .count();
+    let warnings = findings.len() - errors;
+    if errors > 0 {
+        print_error(&format!("{errors} error(s), {warnings} warning(s)"));
+        std::process::exit(1);
+    }
+    print_success(&format!(
+        "{} is ready to activate ({warnings} warning(s))",
+        repo_path.display()
+    ));
+    Ok(())
+}
+
+fn describe(finding: &Finding) -> String {
+    let file = finding
+        .file
+        .as_ref()
+        .map(|file| format!(" {file}:"))
+        .unwrap_or_default();
+    format!(
+        "[{} {}]{file} {}",
+        finding.stage.label(),
+        finding.location,
+        finding.message
+    )
+}
@@ -377,6 +377,7 @@ pub fn prompt_manager(is_active_profile: bool) -> Result<PackageManager> {
                 PackageManager::Pip3 => "pip3",
                 PackageManager::Gem => "gem",
                 PackageManager::Custom => "custom",
+                PackageManager::Plugin(name) => name.as_str(),
             };
             // Only show "(installed)" markers for active profile
             let suffix = if is_active_profile && PackageService::is_manager_installed(m) {
@@ -401,6 +402,9 @@ pub fn prompt_manager(is_active_profile: bool) -> Result<PackageManager> {
 /// The parsed `PackageManager`, or None if invalid
 #[must_use]
 pub fn parse_manager(s: &str) -> Option<PackageManager> {
+    if let Some(name) = s.strip_prefix("plugin:").filter(|name| !name.is_empty()) {
+        return Some(PackageManager::Plugin(name.to_string()));
+    }
     match s.to_lowercase().as_str() {
         "brew" | "homebrew" => Some(PackageManager::Brew),
         "apt" | "apt-get" => Some(PackageManager::Apt),
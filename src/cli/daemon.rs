@@ -0,0 +1,222 @@
+//! Daemon command: serve the JSON socket external integrations talk to.
+//!
+//! See [`crate::utils::ipc`] for the protocol. Each connection is handled on
+//! its own thread, so status requests are answered while a sync runs; syncs
+//! and activations are run one at a time. Another thread watches the tracked
+//! symlinks and notifies when one is replaced or broken.
+
+use super::{print_info, print_warning, CliContext};
+use crate::config::Config;
+use crate::git::GitManager;
+use crate::services::{GitService, ProfileService};
+use crate::utils::ipc::{self, Request, Response, Status};
+use crate::utils::link_monitor::{self, LinkMonitor};
+use crate::utils::sync_history::SyncOrigin;
+use crate::utils::StatusCache;
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Execute the daemon command. Runs until interrupted.
+#[cfg(unix)]
+pub fn execute() -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    let context = CliContext::load()?;
+    let socket = ipc::socket_path();
+    if socket.exists() {
+        if UnixStream::connect(&socket).is_ok() {
+            anyhow::bail!("A daemon is already listening on {}", socket.display());
+        }
+        // Left behind by a daemon that didn't shut down cleanly
+        std::fs::remove_file(&socket)
+            .with_context(|| format!("Failed to remove stale socket {}", socket.display()))?;
+    }
+    // The socket is created with the default umask, so it lives in a
+    // directory only the user can enter from the moment it's bound
+    if let Some(parent) = socket.parent() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(parent)
+            .context("Failed to create the socket directory")?;
+        std::fs::set_permissions(parent, std::fs::Permissions::from_mode(0o700))
+            .context("Failed to restrict socket directory permissions")?;
+    }
+    let listener = UnixListener::bind(&socket)
+        .with_context(|| format!("Failed to listen on {}", socket.display()))?;
+    std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600))
+        .context("Failed to restrict socket permissions")?;
+
+    info!("Daemon listening on {}", socket.display());
+    print_info(&format!(
+        "Listening on {} (press Ctrl+C to stop)",
+        socket.display()
+    ));
+
+    let config_path = Arc::new(context.config_path);
+    let busy = Arc::new(Mutex::new(()));
+    {
+        let config_path = Arc::clone(&config_path);
+        let busy = Arc::clone(&busy);
+        std::thread::spawn(move || {
+            let mut monitor = LinkMonitor::new();
+            loop {
+                // Skipped while a sync or activation is moving links around
+                if let Ok(_guard) = busy.try_lock() {
+                    check_links(&config_path, &mut monitor);
+                }
+                std::thread::sleep(link_monitor::MONITOR_INTERVAL);
+            }
+        });
+    }
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let config_path = Arc::clone(&config_path);
+        let busy = Arc::clone(&busy);
+        std::thread::spawn(move || {
+            let Ok(mut writer) = stream.try_clone() else {
+                return;
+            };
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = match ipc::parse_request(&line) {
+                    Ok(Request::Status) => handle(&config_path, Request::Status),
+                    Ok(request) => {
+                        let _guard = busy.lock().unwrap_or_else(PoisonError::into_inner);
+                        handle(&config_path, request)
+                    }
+                    Err(e) => Response::error(format!("{e:#}")),
+                };
+                if writer
+                    .write_all(ipc::response_line(&response).as_bytes())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Execute the daemon command. Named pipes aren't supported yet.
+#[cfg(not(unix))]
+pub fn execute() -> Result<()> {
+    anyhow::bail!("The daemon socket is only available on Unix systems")
+}
+
+/// Report the tracked symlinks that were replaced or broken since the last
+/// check, in the log, on the terminal and as a desktop notification
+#[cfg(unix)]
+fn check_links(config_path: &Path, monitor: &mut LinkMonitor) {
+    let problems = match Config::load_or_create(config_path)
+        .and_then(|config| link_monitor::check(&config.repo_path))
+    {
+        Ok(problems) => problems,
+        Err(e) => {
+            warn!("Failed to check symlinks: {:#}", e);
+            return;
+        }
+    };
+    for problem in monitor.poll(&problems) {
+        warn!("{}", problem.describe());
+        let message = format!("{}. {}", problem.describe(), problem.hint());
+        print_warning(&message);
+        link_monitor::notify_desktop("dotstate", &message);
+    }
+}
+
+/// Answer a request. The config is loaded for each one, so changes made in
+/// the TUI or the CLI apply right away.
+fn handle(config_path: &Path, request: Request) -> Response {
+    let mut config = match Config::load_or_create(config_path) {
+        Ok(config) => config,
+        Err(e) => return Response::error(format!("Failed to load configuration: {e:#}")),
+    };
+    info!("Daemon request: {:?}", request);
+    match request {
+        Request::Status => match status(&config) {
+            Ok(status) => Response {
+                ok: true,
+                message: None,
+                status: Some(status),
+            },
+            Err(e) => Response::error(format!("{e:#}")),
+        },
+        Request::Sync => {
+            if let Some(message) = GitService::preflight(&config) {
+                return Response::error(message.trim_start_matches("Error: "));
+            }
+            let result = GitService::sync_from(&config, SyncOrigin::Daemon);
+            if result.success {
+                Response::ok(result.message)
+            } else {
+                Response::error(result.message)
+            }
+        }
+        Request::Activate => {
+            match ProfileService::activate_profile(
+                &config.repo_path,
+                &config.active_profile,
+                config.backup_enabled,
+            ) {
+                Ok(result) => {
+                    config.profile_activated = true;
+                    if let Err(e) = config.save(config_path) {
+                        warn!("Failed to save config: {}", e);
+                    }
+                    Response::ok(format!(
+                        "Activated profile '{}' ({} symlinks)",
+                        config.active_profile, result.success_count
+                    ))
+                }
+                Err(e) => Response::error(format!("{e:#}")),
+            }
+        }
+        Request::Adopt { path } => repair(&config, &path, true),
+        Request::Restore { path } => repair(&config, &path, false),
+    }
+}
+
+fn repair(config: &Config, path: &str, adopt: bool) -> Response {
+    match link_monitor::repair(&config.repo_path, config.backup_enabled, path, adopt) {
+        Ok(message) => Response::ok(message),
+        Err(e) => Response::error(format!("{e:#}")),
+    }
+}
+
+/// Status from the repository as it is, without fetching
+fn status(config: &Config) -> Result<Status> {
+    let git_mgr = GitManager::open_or_init(&config.repo_path)?;
+    let branch = git_mgr
+        .get_current_branch()
+        .unwrap_or_else(|| config.default_branch.clone());
+    let uncommitted = git_mgr.get_changed_files()?;
+    let (ahead, behind) = git_mgr
+        .get_ahead_behind("origin", &branch)
+        .unwrap_or_default();
+    Ok(Status {
+        profile: config.active_profile.clone(),
+        branch,
+        segment: StatusCache::new(uncommitted.len(), ahead, behind).segment(),
+        uncommitted,
+        ahead,
+        behind,
+        last_sync: GitService::last_sync_time(),
+        read_only: config.read_only,
+        link_problems: link_monitor::check(&config.repo_path).unwrap_or_default(),
+    })
+}
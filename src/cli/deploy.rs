@@ -0,0 +1,48 @@
+//! Deploy command: copy the dotfiles to a remote host over SSH.
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::utils::deploy;
+use anyhow::Result;
+
+/// Execute the deploy command: copy the storage repository to `target` and
+/// activate `profile` (default: the active profile) there, installing
+/// dotstate first with `install`. With `export`, copy only the profile's
+/// files into the remote home directory.
+pub fn execute(target: &str, profile: Option<&str>, install: bool, export: bool) -> Result<()> {
+    let target = deploy::parse_target(target)?;
+    let ctx = CliContext::load()?;
+    let profile_name = ctx.resolve_profile(profile);
+    if !ctx.profile_exists(&profile_name) {
+        print_error(&format!("Profile '{profile_name}' not found"));
+        std::process::exit(1);
+    }
+    let repo_path = &ctx.config.repo_path;
+
+    if export {
+        print_info(&format!(
+            "Copying the files of '{profile_name}' to {}...",
+            target.host
+        ));
+        let count = deploy::send_export(&ctx.manifest, repo_path, &profile_name, &target)?;
+        print_success(&format!(
+            "Copied {count} file(s) to the home directory on {}",
+            target.host
+        ));
+        println!("   They are plain copies; deploy again to update them.");
+        return Ok(());
+    }
+
+    print_info(&format!(
+        "Copying the storage repository to {}:{}...",
+        target.destination(),
+        target.path
+    ));
+    deploy::send_repo(repo_path, &target)?;
+    print_info(&format!(
+        "Activating '{profile_name}' on {}...",
+        target.host
+    ));
+    deploy::activate(&target, &profile_name, install)?;
+    print_success(&format!("Deployed '{profile_name}' to {}", target.host));
+    Ok(())
+}
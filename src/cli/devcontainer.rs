@@ -0,0 +1,40 @@
+//! Devcontainer command: write the `install.sh` GitHub Codespaces and dev
+//! containers run from a dotfiles repository.
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::utils::devcontainer;
+use crate::utils::repo_workflow::WriteOutcome;
+use anyhow::Result;
+
+/// Execute the devcontainer command: write (or update) the script that
+/// activates `profile` (default: the active profile). With `print`, only
+/// show it.
+pub fn execute(profile: Option<&str>, print: bool, force: bool) -> Result<()> {
+    let ctx = CliContext::load()?;
+    let profile_name = ctx.resolve_profile(profile);
+    if !ctx.profile_exists(&profile_name) {
+        print_error(&format!("Profile '{profile_name}' not found"));
+        std::process::exit(1);
+    }
+    if print {
+        print!("{}", devcontainer::generate(&profile_name));
+        return Ok(());
+    }
+
+    let repo_path = &ctx.config.repo_path;
+    let path = devcontainer::path(repo_path);
+    match devcontainer::write(repo_path, &profile_name, force)? {
+        WriteOutcome::Unchanged => {
+            print_info(&format!("{} is up to date", path.display()));
+            return Ok(());
+        }
+        WriteOutcome::Created => print_success(&format!("Created {}", path.display())),
+        WriteOutcome::Updated => print_success(&format!("Updated {}", path.display())),
+    }
+    println!(
+        "   Codespaces and dev containers run it after cloning the repository as your dotfiles;"
+    );
+    println!("   it activates '{profile_name}' unless DOTSTATE_PROFILE names another profile.");
+    println!("   Commit it with 'dotstate sync'.");
+    Ok(())
+}
@@ -1,11 +1,13 @@
 //! Doctor command for running diagnostics.
 
 use crate::config::Config;
-use crate::utils::doctor::{Doctor, DoctorOptions};
+use crate::utils::doctor::{self, Doctor, DoctorOptions};
 use anyhow::{Context, Result};
 
 /// Execute the doctor command.
-pub fn execute(fix: bool, verbose: bool, json: bool) -> Result<()> {
+pub fn execute(options: DoctorOptions) -> Result<()> {
+    doctor::validate_fix_only(&options.fix_only)?;
+    let json = options.json_output;
     let config_path = crate::utils::get_config_path();
     let config = Config::load_or_create(&config_path).context("Failed to load configuration")?;
 
@@ -20,12 +22,6 @@ pub fn execute(fix: bool, verbose: bool, json: bool) -> Result<()> {
         std::process::exit(1);
     }
 
-    let options = DoctorOptions {
-        fix_mode: fix,
-        verbose,
-        json_output: json,
-    };
-
     let mut doctor = Doctor::new(config, options);
     let report = doctor.run_diagnostics()?;
 
@@ -0,0 +1,147 @@
+//! Environment variable CLI commands.
+//!
+//! Manages the exported variables kept in `env.toml` of common and the
+//! profile directories:
+//! - `list` - Variables in effect for a profile on this machine
+//! - `set` - Set a variable
+//! - `unset` - Remove a variable
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::utils::env_vars::{self, EnvFile, EnvShell};
+use crate::utils::machine_registry::current_hostname;
+use crate::utils::shell_snippets;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand, Debug)]
+pub enum EnvCommand {
+    /// List the variables in effect for a profile on this machine
+    List {
+        /// Target profile (defaults to active profile)
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Set a variable. `$HOME` and other variables in the value expand when
+    /// the shell starts; a `{{ secret "op://..." }}` value is fetched from the
+    /// secret manager on activation
+    Set {
+        /// Variable name
+        name: String,
+        /// Value to export
+        value: String,
+        #[command(flatten)]
+        scope: EnvScope,
+    },
+    /// Remove a variable
+    Unset {
+        /// Variable name
+        name: String,
+        #[command(flatten)]
+        scope: EnvScope,
+    },
+}
+
+/// Where a variable is defined
+#[derive(Args, Debug)]
+pub struct EnvScope {
+    /// Target profile (defaults to active profile)
+    #[arg(short, long, conflicts_with = "common")]
+    profile: Option<String>,
+    /// Define it for every profile
+    #[arg(long)]
+    common: bool,
+    /// Only on this machine
+    #[arg(long)]
+    machine: bool,
+}
+
+/// Execute an env subcommand.
+pub fn execute(command: EnvCommand) -> Result<()> {
+    match command {
+        EnvCommand::List { profile } => cmd_list(profile),
+        EnvCommand::Set { name, value, scope } => cmd_set(&name, Some(value), &scope),
+        EnvCommand::Unset { name, scope } => cmd_set(&name, None, &scope),
+    }
+}
+
+fn cmd_list(profile: Option<String>) -> Result<()> {
+    let ctx = CliContext::load()?;
+    let profile_name = ctx.resolve_profile(profile.as_deref());
+    if !ctx.profile_exists(&profile_name) {
+        print_error(&format!("Profile '{profile_name}' not found"));
+        std::process::exit(1);
+    }
+
+    let hostname = current_hostname();
+    let vars = env_vars::resolve(&ctx.config.repo_path, &profile_name, &hostname)?;
+    println!("Environment variables for profile '{profile_name}' on {hostname}:");
+    if vars.is_empty() {
+        println!("  (none; add one with 'dotstate env set NAME VALUE')");
+    }
+    let width = vars.keys().map(String::len).max().unwrap_or_default();
+    for (name, var) in &vars {
+        println!("  {name:<width$}  {}  ({})", var.value, var.source);
+    }
+    if ctx.is_active_profile(&profile_name) && !vars.is_empty() {
+        println!();
+        println!("Rendered to {}", EnvShell::Posix.path().display());
+        println!(
+            "  fish: add 'source {}' to config.fish",
+            EnvShell::Fish.path().display()
+        );
+    }
+    Ok(())
+}
+
+/// Set `name` to `value` in the scope's `env.toml`, or remove it without a
+/// value, then re-render the active profile's variables
+fn cmd_set(name: &str, value: Option<String>, scope: &EnvScope) -> Result<()> {
+    let ctx = CliContext::load()?;
+    let dir = if scope.common {
+        "common".to_string()
+    } else {
+        let profile_name = ctx.resolve_profile(scope.profile.as_deref());
+        if !ctx.profile_exists(&profile_name) {
+            print_error(&format!("Profile '{profile_name}' not found"));
+            std::process::exit(1);
+        }
+        profile_name
+    };
+    let hostname = scope.machine.then(current_hostname);
+    let target = match &hostname {
+        Some(host) => format!("{dir} on {host}"),
+        None => dir.clone(),
+    };
+
+    let repo_path = &ctx.config.repo_path;
+    let mut file = EnvFile::load(repo_path, &dir)?;
+    let vars = file.vars_mut(hostname.as_deref());
+    if let Some(value) = value {
+        if let Err(e) = env_vars::validate_name(name) {
+            print_error(&e);
+            std::process::exit(1);
+        }
+        vars.insert(name.to_string(), value);
+        file.save(repo_path, &dir)?;
+        print_success(&format!("Set ${name} for {target}"));
+    } else {
+        if vars.remove(name).is_none() {
+            print_info(&format!("${name} isn't set for {target}"));
+            return Ok(());
+        }
+        file.save(repo_path, &dir)?;
+        print_success(&format!("Removed ${name} from {target}"));
+    }
+
+    let active = &ctx.config.active_profile;
+    if !active.is_empty() {
+        shell_snippets::write_loader(repo_path, active)?;
+    }
+    if !shell_snippets::RC_FILES.iter().any(|rc| {
+        std::fs::read_to_string(crate::utils::get_home_dir().join(rc))
+            .is_ok_and(|c| shell_snippets::has_line(&c))
+    }) {
+        println!("   Run 'dotstate snippets --install' so shells load the variables.");
+    }
+    Ok(())
+}
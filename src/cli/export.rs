@@ -0,0 +1,110 @@
+//! Export CLI commands.
+//!
+//! Exports a profile in the format of another configuration tool:
+//! - `nix` - Nix home-manager module or flake
+//! - `ansible` - Ansible role
+
+use super::{print_error, print_success, CliContext};
+use crate::utils::{ansible_export, nix_export};
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand, Debug)]
+pub enum ExportCommand {
+    /// Print a home-manager module (`home.nix`) declaring the profile's files
+    /// and packages
+    Nix {
+        /// Profile to export (defaults to active profile)
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Print a standalone `flake.nix` for the root of the storage repository instead
+        #[arg(long)]
+        flake: bool,
+    },
+    /// Write an Ansible role that installs the profile's files and packages
+    Ansible {
+        /// Directory to write the role into, e.g. `roles/dotfiles`
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Profile to export (defaults to active profile)
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Write into a directory that isn't empty
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Execute an export subcommand
+pub fn execute(command: ExportCommand) -> Result<()> {
+    match command {
+        ExportCommand::Nix { profile, flake } => cmd_nix(profile.as_deref(), flake),
+        ExportCommand::Ansible {
+            output,
+            profile,
+            force,
+        } => cmd_ansible(&output, profile.as_deref(), force),
+    }
+}
+
+fn load_profile(profile: Option<&str>) -> Result<(CliContext, String)> {
+    let ctx = CliContext::load()?;
+    let profile_name = ctx.resolve_profile(profile);
+    if !ctx.profile_exists(&profile_name) {
+        print_error(&format!("Profile '{profile_name}' not found"));
+        std::process::exit(1);
+    }
+    Ok((ctx, profile_name))
+}
+
+fn cmd_nix(profile: Option<&str>, flake: bool) -> Result<()> {
+    let (ctx, profile_name) = load_profile(profile)?;
+    let export = nix_export::collect(&ctx.manifest, &ctx.config.repo_path, &profile_name)?;
+    if flake {
+        let user = std::env::var("USER").unwrap_or_else(|_| "me".to_string());
+        print!(
+            "{}",
+            nix_export::flake(
+                &export,
+                &user,
+                &crate::utils::get_home_dir(),
+                &nix_export::current_system()
+            )
+        );
+    } else {
+        print!(
+            "{}",
+            nix_export::home_module(&export, &ctx.config.repo_path)
+        );
+    }
+    Ok(())
+}
+
+fn cmd_ansible(output: &Path, profile: Option<&str>, force: bool) -> Result<()> {
+    let (ctx, profile_name) = load_profile(profile)?;
+    let not_empty = output
+        .read_dir()
+        .is_ok_and(|mut entries| entries.next().is_some());
+    if not_empty && !force {
+        print_error(&format!("{} is not empty", output.display()));
+        println!("   Pick another directory, or run again with --force to write into it.");
+        std::process::exit(1);
+    }
+    let summary = ansible_export::write_role(
+        &ctx.manifest,
+        &ctx.config.repo_path,
+        &profile_name,
+        &crate::utils::get_home_dir(),
+        output,
+    )
+    .context("Failed to write the Ansible role")?;
+    print_success(&format!(
+        "Wrote an Ansible role for profile '{profile_name}' to {} ({} file(s), {} package(s))",
+        output.display(),
+        summary.files,
+        summary.packages
+    ));
+    println!("   Set dotfiles_mode: link to symlink from a checkout of the storage repository instead of copying.");
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! File-sync CLI commands.
+//!
+//! Helps when the storage directory is kept in Syncthing, Dropbox or a
+//! similar service:
+//! - `status` - The sync lock and the conflict copies left by the service
+//! - `resolve` - Keep one version of each conflicted file
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::utils::file_sync::{self, Keep};
+use anyhow::Result;
+use clap::{Subcommand, ValueEnum};
+
+#[derive(Subcommand, Debug)]
+pub enum FileSyncCommand {
+    /// Show who holds the sync lock and list conflict copies
+    Status,
+    /// Keep one version of conflicted files (all of them without paths)
+    Resolve {
+        /// Conflict copies to resolve, relative to the repository
+        paths: Vec<String>,
+        /// Which version to keep
+        #[arg(long, value_enum)]
+        keep: KeepArg,
+    },
+}
+
+/// Version to keep, as given on the command line
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum KeepArg {
+    /// The file as it is; the copy is deleted
+    Original,
+    /// The copy, which replaces the file
+    Copy,
+}
+
+/// Execute a file-sync subcommand
+pub fn execute(command: FileSyncCommand) -> Result<()> {
+    let config = CliContext::load()?.config;
+    let repo_path = &config.repo_path;
+    match command {
+        FileSyncCommand::Status => {
+            if !config.file_sync_mode {
+                print_info("File-sync mode is off; turn it on with 'file_sync_mode = true'");
+            }
+            match file_sync::holder(repo_path) {
+                Some(holder) => println!(
+                    "Sync lock: held by {} (pid {}) since {}",
+                    holder.host,
+                    holder.pid,
+                    holder
+                        .since
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                ),
+                None => println!("Sync lock: free"),
+            }
+            let conflicts = file_sync::find_conflicts(repo_path)?;
+            if conflicts.is_empty() {
+                println!("No conflict copies.");
+                return Ok(());
+            }
+            println!("Conflict copies:");
+            for conflict in &conflicts {
+                println!("  {}  (copy of {})", conflict.path, conflict.original);
+            }
+            println!();
+            println!("Keep one version with 'dotstate file-sync resolve --keep original|copy [PATH]...',");
+            println!("then check the repository with 'dotstate fsck'.");
+        }
+        FileSyncCommand::Resolve { paths, keep } => {
+            let keep = match keep {
+                KeepArg::Original => Keep::Original,
+                KeepArg::Copy => Keep::Copy,
+            };
+            // Nothing may write to the repository meanwhile
+            let lock = file_sync::acquire(repo_path)?;
+            let conflicts = file_sync::find_conflicts(repo_path)?;
+            let mut failed = false;
+            for path in &paths {
+                if !conflicts.iter().any(|c| &c.path == path) {
+                    print_error(&format!("{path} is not a conflict copy"));
+                    failed = true;
+                }
+            }
+            let selected = conflicts
+                .iter()
+                .filter(|c| paths.is_empty() || paths.contains(&c.path));
+            let mut resolved = 0;
+            for conflict in selected {
+                file_sync::resolve(repo_path, conflict, keep)?;
+                resolved += 1;
+            }
+            if resolved > 0 {
+                print_success(&format!("Resolved {resolved} conflict copy(ies)"));
+                println!("   Check the repository with 'dotstate fsck' before syncing.");
+            } else if !failed {
+                print_info("No conflict copies to resolve");
+            }
+            if failed {
+                drop(lock);
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,10 +1,10 @@
-//! File management commands: list, add, remove.
+//! File management commands: list, add, remove, mv, local-only, repair.
 
 use crate::config::Config;
-use crate::services::{AddFileResult, RemoveFileResult, SyncService};
+use crate::services::{AddFileResult, AddStorage, RemoveFileResult, SyncService};
 use anyhow::{Context, Result};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 /// Execute the list command.
@@ -166,7 +166,13 @@ fn print_file_info(
 }
 
 /// Execute the add command.
-pub fn cmd_add(path: PathBuf, common: bool) -> Result<()> {
+pub fn cmd_add(
+    path: PathBuf,
+    common: bool,
+    block: bool,
+    git_include: bool,
+    storage: AddStorage,
+) -> Result<()> {
     let config_path = crate::utils::get_config_path();
     let config = Config::load_or_create(&config_path).context("Failed to load configuration")?;
 
@@ -179,6 +185,13 @@ pub fn cmd_add(path: PathBuf, common: bool) -> Result<()> {
         std::env::current_dir()?.join(path)
     };
 
+    if block {
+        return cmd_add_block(&config, &home, &resolved_path);
+    }
+    if git_include {
+        return cmd_add_git_include(&config, &home, &resolved_path);
+    }
+
     if !resolved_path.exists() {
         eprintln!("❌ File not found: {resolved_path:?}");
         std::process::exit(1);
@@ -199,6 +212,24 @@ pub fn cmd_add(path: PathBuf, common: bool) -> Result<()> {
     if common {
         println!("\n   This file will be shared across ALL profiles.");
     }
+    if storage == AddStorage::Plain {
+        if let Some(report) =
+            crate::utils::file_policy::inspect(&resolved_path, config.large_file_threshold_mb)
+        {
+            println!(
+                "\n⚠️  This path is large or holds binary files (limit: {} MB, large_file_threshold_mb):",
+                config.large_file_threshold_mb
+            );
+            for line in report.summary().lines() {
+                println!("   {line}");
+            }
+            println!("\n   git keeps every version of it: the repository grows for good and every");
+            println!("   clone downloads it. Binary files don't show diffs either.");
+            println!(
+                "   To store it differently, run again with --lfs (Git LFS) or --encrypt (sops)."
+            );
+        }
+    }
     println!("\n   Make sure you know what you are doing.");
     print!("   Continue? [y/N]: ");
     io::stdout().flush().context("Failed to flush stdout")?;
@@ -228,11 +259,12 @@ pub fn cmd_add(path: PathBuf, common: bool) -> Result<()> {
             config.backup_enabled,
         )?
     } else {
-        SyncService::add_file_to_sync(
+        SyncService::add_file_to_sync_as(
             &config,
             &resolved_path,
             &relative_str,
             config.backup_enabled,
+            storage,
         )?
     };
 
@@ -264,6 +296,59 @@ pub fn cmd_add(path: PathBuf, common: bool) -> Result<()> {
     Ok(())
 }
 
+/// Add a managed block. The file is left in place (and may not exist yet),
+/// so there is nothing to confirm.
+fn cmd_add_block(config: &Config, home: &Path, resolved_path: &Path) -> Result<()> {
+    let relative_str = resolved_path
+        .strip_prefix(home)
+        .unwrap_or(resolved_path)
+        .to_string_lossy()
+        .to_string();
+
+    info!("CLI: Adding managed block: {}", relative_str);
+
+    match SyncService::add_block_to_sync(config, &relative_str)? {
+        AddFileResult::Success => {
+            println!("✅ Added a managed block to {relative_str}");
+            println!("   Lines between the dotstate markers are synced; the rest of the file stays local.");
+        }
+        AddFileResult::AlreadySynced => {
+            println!("ℹ️  File is already synced: {relative_str}");
+        }
+        AddFileResult::ValidationFailed(msg) => {
+            eprintln!("❌ {msg}");
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Add a git config in include mode
+fn cmd_add_git_include(config: &Config, home: &Path, resolved_path: &Path) -> Result<()> {
+    let relative_str = resolved_path
+        .strip_prefix(home)
+        .unwrap_or(resolved_path)
+        .to_string_lossy()
+        .to_string();
+
+    info!("CLI: Adding git include: {}", relative_str);
+
+    match SyncService::add_git_include_to_sync(config, &relative_str)? {
+        AddFileResult::Success => {
+            println!("✅ Moved the shared settings of {relative_str} to the repository");
+            println!("   {relative_str} now includes them; [user], [credential] and [include] sections stay local.");
+        }
+        AddFileResult::AlreadySynced => {
+            println!("ℹ️  File is already synced: {relative_str}");
+        }
+        AddFileResult::ValidationFailed(msg) => {
+            eprintln!("❌ {msg}");
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
 /// Execute the remove command.
 pub fn cmd_remove(path: String, common: bool) -> Result<()> {
     let config_path = crate::utils::get_config_path();
@@ -318,3 +403,118 @@ pub fn cmd_remove(path: String, common: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Path relative to the home directory for a `~/...`, absolute or
+/// home-relative argument
+fn home_relative(path: &str) -> Option<String> {
+    let home = crate::utils::get_home_dir();
+    crate::utils::expand_path(path)
+        .strip_prefix(&home)
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+/// Execute the mv command: rename or move a synced file, keeping its history.
+pub fn cmd_mv(old: String, new: String) -> Result<()> {
+    let config_path = crate::utils::get_config_path();
+    let mut config =
+        Config::load_or_create(&config_path).context("Failed to load configuration")?;
+
+    let (Some(old_relative), Some(new_relative)) = (home_relative(&old), home_relative(&new))
+    else {
+        eprintln!("❌ Both paths must be inside the home directory");
+        std::process::exit(1);
+    };
+
+    info!("CLI: Renaming {} to {}", old_relative, new_relative);
+
+    if let Err(e) = SyncService::rename_file(&config, &old_relative, &new_relative) {
+        eprintln!("❌ {e:#}");
+        std::process::exit(1);
+    }
+
+    if let Some(entry) = config.custom_files.iter_mut().find(|f| **f == old_relative) {
+        *entry = new_relative.clone();
+        config.save(&config_path)?;
+    }
+
+    println!("✅ Moved {old_relative} to {new_relative}");
+    println!("   Commit the rename with 'dotstate sync'.");
+    Ok(())
+}
+
+/// Execute the local-only command: keep a child of a synced directory out
+/// of the repository, sync it again with `remove`, or list them.
+pub fn cmd_local_only(path: Option<String>, remove: bool) -> Result<()> {
+    let config_path = crate::utils::get_config_path();
+    let config = Config::load_or_create(&config_path).context("Failed to load configuration")?;
+    let repo_path = &config.repo_path;
+
+    let Some(path) = path else {
+        let manifest = crate::utils::ProfileManifest::load_or_backfill(repo_path)?;
+        if manifest.local_only.is_empty() {
+            println!("No local-only files.");
+        }
+        for entry in &manifest.local_only {
+            println!("~/{entry}");
+        }
+        return Ok(());
+    };
+
+    let Some(relative) = home_relative(&path) else {
+        eprintln!("❌ The path must be inside the home directory");
+        std::process::exit(1);
+    };
+
+    if remove {
+        if crate::utils::local_only::unmark(repo_path, &relative)? {
+            println!("✅ ~/{relative} is synced again");
+            println!("   Commit the change with 'dotstate sync'.");
+        } else {
+            println!("ℹ️  ~/{relative} isn't local-only");
+        }
+        return Ok(());
+    }
+
+    info!("CLI: Marking {} local-only", relative);
+    if let Err(e) = crate::utils::local_only::mark(repo_path, &relative) {
+        eprintln!("❌ {e:#}");
+        std::process::exit(1);
+    }
+    println!("✅ ~/{relative} is now local-only: this machine keeps its own copy");
+    println!("   Commit the change with 'dotstate sync'.");
+    Ok(())
+}
+
+/// Execute the repair command: relink a tracked symlink that was replaced or
+/// removed, adopt the file found in its place, or list the ones that need it.
+pub fn cmd_repair(path: Option<String>, adopt: bool) -> Result<()> {
+    let config_path = crate::utils::get_config_path();
+    let config = Config::load_or_create(&config_path).context("Failed to load configuration")?;
+
+    let Some(path) = path else {
+        let problems = crate::utils::link_monitor::check(&config.repo_path)?;
+        if problems.is_empty() {
+            println!("✅ All tracked symlinks are in place");
+        }
+        for problem in &problems {
+            println!("⚠️  {}", problem.describe());
+            println!("   {}", problem.hint());
+        }
+        return Ok(());
+    };
+
+    info!("CLI: Repairing {} (adopt: {})", path, adopt);
+    match crate::utils::link_monitor::repair(&config.repo_path, config.backup_enabled, &path, adopt)
+    {
+        Ok(message) => {
+            println!("✅ {message}");
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("❌ {e:#}");
+            std::process::exit(1);
+        }
+    }
+}
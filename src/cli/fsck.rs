@@ -0,0 +1,71 @@
+//! Fsck command: check the storage repository's layout and repair it.
+
+use super::{print_error, print_info, print_success, print_warning, prompt_confirm, CliContext};
+use crate::utils::fsck::{self, Severity};
+use anyhow::Result;
+
+/// Execute the fsck command. Lists the issues found; with `fix`, offers
+/// each available repair (applied without asking with `yes`). Exits with
+/// status 1 when errors remain.
+pub fn execute(fix: bool, yes: bool) -> Result<()> {
+    let config = CliContext::load()?.config;
+    let data_dir = crate::utils::get_data_dir();
+
+    let issues = fsck::check(&config.repo_path, &data_dir);
+    if issues.is_empty() {
+        print_success("Repository layout and symlink tracking are consistent");
+        return Ok(());
+    }
+
+    let mut remaining_errors = 0;
+    let mut repaired = 0;
+    for issue in &issues {
+        let line = format!("[{}] {}", issue.location, issue.message);
+        match issue.severity {
+            Severity::Error => print_error(&line),
+            Severity::Warning => print_warning(&line),
+        }
+
+        let fixed = match (&issue.repair, fix) {
+            (Some(repair), true) => {
+                if yes || prompt_confirm(&format!("   {}?", repair.description()))? {
+                    match fsck::apply(&config.repo_path, &data_dir, repair) {
+                        Ok(()) => {
+                            print_success(&format!("   {}", repair.description()));
+                            true
+                        }
+                        Err(e) => {
+                            print_error(&format!("   Repair failed: {e:#}"));
+                            false
+                        }
+                    }
+                } else {
+                    false
+                }
+            }
+            (Some(repair), false) => {
+                println!("   Repair: {}", repair.description());
+                false
+            }
+            (None, _) => false,
+        };
+        if fixed {
+            repaired += 1;
+        } else if issue.severity == Severity::Error {
+            remaining_errors += 1;
+        }
+    }
+
+    println!();
+    if repaired > 0 {
+        print_success(&format!(
+            "Repaired {repaired} issue(s). Commit the changes with 'dotstate sync'."
+        ));
+    } else if !fix && issues.iter().any(|i| i.repair.is_some()) {
+        print_info("Run 'dotstate fsck --fix' to apply the repairs");
+    }
+    if remaining_errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Git command: run git inside the storage repository.
+
+use super::CliContext;
+use crate::config::RepoMode;
+use anyhow::{Context, Result};
+use std::process::Command;
+
+/// Environment variable the credential helper reads the token from, so it
+/// never shows up in the process list
+const TOKEN_ENV: &str = "DOTSTATE_GIT_TOKEN";
+
+/// Credential config key scoped to GitHub, so the token is never offered to
+/// other hosts (e.g. a submodule or second remote elsewhere)
+const HELPER_KEY: &str = "credential.https://github.com.helper";
+
+/// Credential helper answering `get` requests with the GitHub token
+const TOKEN_HELPER: &str = "!f() { test \"$1\" = get && echo username=x-access-token && echo \"password=$DOTSTATE_GIT_TOKEN\"; }; f";
+
+/// Execute the git command: run `git <args>` in the storage repository with
+/// the GitHub token (if any) available to HTTPS remotes, and exit with git's
+/// exit code.
+pub fn execute(args: Vec<String>) -> Result<()> {
+    let config = CliContext::load()?.config;
+
+    let token = match config.repo_mode {
+        RepoMode::Local => None,
+        RepoMode::GitHub => config.get_github_token(),
+    };
+
+    let mut command = Command::new("git");
+    command.current_dir(&config.repo_path);
+    if let Some(token) = token {
+        // Clear configured GitHub helpers first so a stale stored
+        // credential doesn't win over the token
+        command
+            .args(["-c", &format!("{HELPER_KEY}=")])
+            .args(["-c", &format!("{HELPER_KEY}={TOKEN_HELPER}")])
+            .env(TOKEN_ENV, token);
+    }
+    let status = command
+        .args(&args)
+        .status()
+        .context("Failed to run git. Is git installed?")?;
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_passes_arguments_through() {
+        let cli =
+            Cli::try_parse_from(["dotstate", "git", "--", "log", "--oneline", "-n", "5"]).unwrap();
+        let Some(Commands::Git { args }) = cli.command else {
+            panic!("expected the git command");
+        };
+        assert_eq!(args, ["log", "--oneline", "-n", "5"]);
+
+        let cli = Cli::try_parse_from(["dotstate", "git", "status", "--short"]).unwrap();
+        assert!(
+            matches!(cli.command, Some(Commands::Git { args }) if args == ["status", "--short"])
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Gitignore command: write the storage repository's default `.gitignore`.
+
+use super::{print_info, print_success, CliContext};
+use crate::utils::repo_gitignore;
+use anyhow::Result;
+
+/// Execute the gitignore command: create the `.gitignore` with the default
+/// patterns, or add the ones an existing file lacks. With `print`, only show
+/// the default content.
+pub fn execute(print: bool) -> Result<()> {
+    if print {
+        print!("{}", repo_gitignore::default_content());
+        return Ok(());
+    }
+
+    let config = CliContext::load()?.config;
+    let added = repo_gitignore::ensure_defaults(&config.repo_path)?;
+    if added.is_empty() {
+        print_info(".gitignore already has all default patterns");
+        return Ok(());
+    }
+    print_success(&format!(
+        "Added {} pattern(s) to {}",
+        added.len(),
+        config.repo_path.join(repo_gitignore::GITIGNORE).display()
+    ));
+    for pattern in &added {
+        println!("   {pattern}");
+    }
+    println!("   Commit it with 'dotstate sync'.");
+    Ok(())
+}
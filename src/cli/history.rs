@@ -0,0 +1,96 @@
+//! History command: list past syncs from the local sync history.
+
+use crate::utils::sync_history::{self, SyncRecord};
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate, TimeZone};
+
+/// Options for the history command.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryOptions {
+    /// Maximum number of syncs to show
+    pub limit: usize,
+    /// Only show syncs on or after this date (YYYY-MM-DD)
+    pub since: Option<String>,
+    /// Only show failed syncs
+    pub failed: bool,
+    /// List the files each sync pushed and pulled
+    pub verbose: bool,
+    /// Print the records as JSON
+    pub json: bool,
+}
+
+/// Execute the history command.
+pub fn execute(options: HistoryOptions) -> Result<()> {
+    let since = options
+        .since
+        .as_deref()
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .with_context(|| format!("Invalid date '{date}', expected YYYY-MM-DD"))
+        })
+        .transpose()?;
+
+    let records: Vec<SyncRecord> = sync_history::load()
+        .into_iter()
+        .filter(|record| !options.failed || !record.success)
+        .filter(|record| {
+            since.is_none_or(|date| record.timestamp.with_timezone(&Local).date_naive() >= date)
+        })
+        .take(options.limit)
+        .collect();
+
+    if options.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&records).context("Failed to serialize history")?
+        );
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        println!("No syncs recorded yet.");
+        return Ok(());
+    }
+
+    for record in &records {
+        print_record(record, options.verbose);
+    }
+    Ok(())
+}
+
+fn print_record(record: &SyncRecord, verbose: bool) {
+    let status = if record.conflict {
+        "⚠️ "
+    } else if record.success {
+        "✅"
+    } else {
+        "❌"
+    };
+    println!(
+        "{status} {}  {} ({}, {})  {}",
+        Local
+            .from_utc_datetime(&record.timestamp.naive_utc())
+            .format("%Y-%m-%d %H:%M"),
+        record.kind.label(),
+        record.origin.label(),
+        record.profile,
+        record.summary()
+    );
+
+    if verbose {
+        if record.pulled_commits > 0 {
+            println!("   Pulled {} commit(s)", record.pulled_commits);
+        }
+        let pushed = if record.success {
+            "pushed"
+        } else {
+            "not pushed"
+        };
+        for entry in &record.committed {
+            println!("   ↑ {entry} ({pushed})");
+        }
+        for entry in &record.pulled {
+            println!("   ↓ {entry}");
+        }
+    }
+}
@@ -1,9 +1,12 @@
-//! Info commands: help, logs, config, repository.
+//! Info commands: help, logs, config, repository, path.
 
 use anyhow::{Context, Result};
 use clap::CommandFactory;
 
-use super::Cli;
+use super::{Cli, PathCommand};
+use crate::config::Config;
+use crate::utils::{ProfileManifest, ResolvedFile};
+use std::path::Path;
 
 /// Execute the help command.
 pub fn cmd_help(command: Option<String>) -> Result<()> {
@@ -30,19 +33,57 @@ pub fn cmd_help(command: Option<String>) -> Result<()> {
 
 /// Execute the logs command.
 pub fn cmd_logs() -> Result<()> {
-    let log_dir = dirs::cache_dir()
-        .unwrap_or_else(|| dirs::home_dir().unwrap_or_default())
-        .join("dotstate");
-    let log_file = log_dir.join("dotstate.log");
+    let log_file = crate::utils::get_cache_dir().join("dotstate.log");
     println!("{}", log_file.display());
     Ok(())
 }
 
 /// Execute the config command.
-pub fn cmd_config() -> Result<()> {
+///
+/// Prints the config file location, or checks or repairs its contents.
+pub fn cmd_config(check: bool, repair: bool) -> Result<()> {
+    use crate::utils::config_validation::{repair_config_file, validate_config};
+
     let config_path = crate::utils::get_config_path();
-    println!("{}", config_path.display());
-    Ok(())
+    if !check && !repair {
+        println!("{}", config_path.display());
+        return Ok(());
+    }
+    if !config_path.exists() {
+        println!("No config file at {}", config_path.display());
+        return Ok(());
+    }
+
+    if repair {
+        let fixed = repair_config_file(&config_path)?;
+        if fixed.is_empty() {
+            println!("✅ {} has no problems to repair", config_path.display());
+        } else {
+            println!("🔧 Repaired {}:", config_path.display());
+            for issue in &fixed {
+                println!("   • {issue}");
+            }
+            println!(
+                "   The original was saved as {}",
+                config_path.with_extension("toml.bak").display()
+            );
+        }
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config file: {config_path:?}"))?;
+    let issues = validate_config(&content);
+    if issues.is_empty() {
+        println!("✅ {} is valid", config_path.display());
+        return Ok(());
+    }
+    eprintln!("❌ {} has problems:", config_path.display());
+    for issue in &issues {
+        eprintln!("   • {issue}");
+    }
+    eprintln!("\nRun 'dotstate config --repair' to reset these settings to their defaults.");
+    std::process::exit(1);
 }
 
 /// Execute the repository command.
@@ -52,6 +93,79 @@ pub fn cmd_repository() -> Result<()> {
     Ok(())
 }
 
+/// Execute the path command: print one path and nothing else, for scripts.
+pub fn cmd_path(command: PathCommand) -> Result<()> {
+    let path = match command {
+        PathCommand::Repo => {
+            crate::utils::get_repository_path().context("Failed to get repository path")?
+        }
+        PathCommand::Config => crate::utils::get_config_path(),
+        PathCommand::Logs => crate::utils::get_cache_dir().join("dotstate.log"),
+        PathCommand::File { name } => {
+            let config = Config::load_or_create(&crate::utils::get_config_path())
+                .context("Failed to load configuration")?;
+            let manifest = ProfileManifest::load_or_backfill(&config.repo_path)
+                .context("Failed to load profile manifest")?;
+            let files = manifest
+                .resolve_files(&config.active_profile)
+                .context("Failed to resolve files for profile")?;
+            match find_tracked_file(&files, &name, &crate::utils::get_home_dir()) {
+                Ok(file) => config
+                    .repo_path
+                    .join(&file.source_profile)
+                    .join(&file.relative_path),
+                Err(message) => {
+                    eprintln!("❌ {message}");
+                    std::process::exit(1);
+                }
+            }
+        }
+    };
+    println!("{}", path.display());
+    Ok(())
+}
+
+/// Find the tracked file `name` refers to: its tracked path, a path in the
+/// home directory (`~/.zshrc`, absolute), or a file name matching exactly
+/// one tracked file.
+fn find_tracked_file<'a>(
+    files: &'a [ResolvedFile],
+    name: &str,
+    home: &Path,
+) -> std::result::Result<&'a ResolvedFile, String> {
+    let expanded = crate::utils::path::expand_vars(name);
+    let relative = Path::new(&expanded)
+        .strip_prefix(home)
+        .map_or(expanded.as_str(), |p| p.to_str().unwrap_or_default())
+        .trim_start_matches("./");
+
+    if let Some(file) = files.iter().find(|f| f.relative_path == relative) {
+        return Ok(file);
+    }
+
+    let matches: Vec<&ResolvedFile> = files
+        .iter()
+        .filter(|f| {
+            Path::new(&f.relative_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n == relative || n == format!(".{relative}"))
+        })
+        .collect();
+    match matches.as_slice() {
+        [file] => Ok(file),
+        [] => Err(format!("'{name}' is not tracked by the active profile")),
+        _ => Err(format!(
+            "'{name}' matches several tracked files: {}",
+            matches
+                .iter()
+                .map(|f| f.relative_path.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
 /// Print all available commands with their descriptions.
 pub fn print_all_commands() {
     let cli = Cli::command();
@@ -97,3 +211,38 @@ pub fn print_all_commands() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_tracked_file() {
+        let files = vec![
+            ResolvedFile {
+                relative_path: ".zshrc".to_string(),
+                source_profile: "Work".to_string(),
+            },
+            ResolvedFile {
+                relative_path: ".config/nvim/init.lua".to_string(),
+                source_profile: "common".to_string(),
+            },
+            ResolvedFile {
+                relative_path: ".config/helix/init.lua".to_string(),
+                source_profile: "Work".to_string(),
+            },
+        ];
+        let home = Path::new("/home/me");
+        let found = |name| find_tracked_file(&files, name, home).map(|f| &f.relative_path);
+
+        assert_eq!(found(".zshrc").unwrap(), ".zshrc");
+        assert_eq!(found("zshrc").unwrap(), ".zshrc");
+        assert_eq!(found("/home/me/.zshrc").unwrap(), ".zshrc");
+        assert_eq!(
+            found(".config/nvim/init.lua").unwrap(),
+            ".config/nvim/init.lua"
+        );
+        assert!(found("init.lua").unwrap_err().contains("several"));
+        assert!(found(".bashrc").unwrap_err().contains("not tracked"));
+    }
+}
@@ -1,24 +1,76 @@
 //! CLI module for `DotState` command-line interface.
 //!
 //! This module provides a modular structure for CLI commands:
+//! - `backend` - Encrypted archive on S3 or `WebDAV` storage
+//! - `bench` - Timings of scan, activation, git and preview on this machine
+//! - `bootstrap` - Per-profile bootstrap scripts
+//! - `broadcast` - Syncs run on every registered machine over SSH
+//! - `ci` - Headless repository checks for CI
 //! - `common` - Shared utilities (`CliContext`, prompts, output helpers)
 //! - `sync` - Sync with remote repository
-//! - `files` - File management (list, add, remove)
+//! - `files` - File management (list, add, remove, mv, local-only)
+//! - `file_sync` - Storage directory kept in Syncthing, Dropbox and the like
 //! - `profiles` - Profile activation/deactivation
 //! - `packages` - Package management
+//! - `daemon` - JSON socket for external integrations
+//! - `deploy` - Dotfiles copied to a remote host over SSH
+//! - `devcontainer` - `install.sh` for GitHub Codespaces and dev containers
 //! - `doctor` - Diagnostics
-//! - `info` - Help, logs, config, repository info
+//! - `env` - Exported environment variables
+//! - `export` - Profiles in the formats of other configuration tools (Nix, Ansible)
+//! - `fsck` - Storage repository layout checks and repairs
+//! - `git` - Run git inside the storage repository
+//! - `gitignore` - Default `.gitignore` for the storage repository
+//! - `history` - Past syncs from the local sync history
+//! - `info` - Help, logs, config, repository, path info
+//! - `plugins` - Installed plugins
+//! - `pre_commit` - Git pre-commit hook for the storage repository
+//! - `prompt` - Status segment for shell prompts
+//! - `report` - Error report for bug reports
+//! - `show` - Screens printed as plain text tables
+//! - `snippets` - Shell snippets sourced from the rc files
+//! - `ssh_remote` - Storage repository hosted on a plain SSH server
+//! - `starters` - Starter configs for files that don't exist yet
+//! - `uninstall` - Remove symlinks and `DotState` data
 //! - `upgrade` - Update checker
+//! - `watch` - Live events from the repository, backups and syncs
+//! - `workflow` - GitHub Actions workflow for the storage repository
 
+mod backend;
+mod bench;
+mod bootstrap;
+mod broadcast;
+mod ci;
 mod common;
 mod completions;
+mod daemon;
+mod deploy;
+mod devcontainer;
 mod doctor;
+mod env;
+mod export;
+mod file_sync;
 mod files;
+mod fsck;
+mod git;
+mod gitignore;
+mod history;
 mod info;
 pub mod packages;
+mod plugins;
+mod pre_commit;
 mod profiles;
+mod prompt;
+mod report;
+mod show;
+mod snippets;
+mod ssh_remote;
+mod starters;
 mod sync;
+mod uninstall;
 mod upgrade;
+mod watch;
+mod workflow;
 
 // Re-export common utilities for use by CLI commands
 pub use common::*;
@@ -26,8 +78,9 @@ pub use common::*;
 // Re-export packages command enum for external use
 pub use packages::PackagesCommand;
 
+use crate::screens::main_menu::MenuItem;
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use std::path::PathBuf;
 
@@ -38,9 +91,42 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
+    /// Open the TUI on this screen instead of the main menu
+    #[arg(long, value_name = "SCREEN")]
+    pub screen: Option<StartScreen>,
+
     /// Disable colors in the TUI (also respects `NO_COLOR` env var)
     #[arg(long, global = true)]
     pub no_colors: bool,
+
+    /// Record timing spans (scan, symlinks, git, rendering) and write them to
+    /// FILE in the Chrome trace format, or send them to an OTLP/HTTP
+    /// collector when given an `http(s)://` URL
+    #[arg(long, global = true, value_name = "FILE")]
+    pub trace: Option<String>,
+}
+
+/// Screens the TUI can be opened on with `--screen`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartScreen {
+    /// Manage Files: add and remove synced files
+    ManageFiles,
+    /// Sync with Remote: commit, pull and push
+    Sync,
+    /// Manage Profiles
+    Profiles,
+}
+
+impl StartScreen {
+    /// The main menu item that opens the screen
+    #[must_use]
+    pub fn menu_item(self) -> MenuItem {
+        match self {
+            StartScreen::ManageFiles => MenuItem::ScanDotfiles,
+            StartScreen::Sync => MenuItem::SyncWithRemote,
+            StartScreen::Profiles => MenuItem::ManageProfiles,
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -57,6 +143,15 @@ pub enum Commands {
         #[arg(short, long)]
         verbose: bool,
     },
+    /// Print what a TUI screen shows as a plain text table, for piping into
+    /// `grep` or `fzf`
+    Show {
+        /// The screen to print
+        screen: show::ShowScreen,
+        /// Leave out the header line
+        #[arg(long)]
+        no_header: bool,
+    },
     /// Add a file to sync
     Add {
         /// Path to the file to add
@@ -64,6 +159,19 @@ pub enum Commands {
         /// Add as a common file (shared across all profiles)
         #[arg(long)]
         common: bool,
+        /// Sync only a managed block inside the file, leaving the rest machine-local
+        #[arg(long, conflicts_with = "common")]
+        block: bool,
+        /// Sync a git config's shared settings through an include.path, keeping
+        /// identity and credentials machine-local
+        #[arg(long, conflicts_with_all = ["common", "block"])]
+        git_include: bool,
+        /// Store the file with Git LFS, committing only a pointer to it
+        #[arg(long, conflicts_with_all = ["common", "block", "git_include"])]
+        lfs: bool,
+        /// Encrypt the file with sops (the repository needs a .sops.yaml)
+        #[arg(long, conflicts_with_all = ["common", "block", "git_include", "lfs"])]
+        encrypt: bool,
     },
     /// Remove a file from sync
     Remove {
@@ -73,10 +181,60 @@ pub enum Commands {
         #[arg(long)]
         common: bool,
     },
+    /// Rename or move a synced file: moves it in storage (keeping its git
+    /// history), updates the manifest and re-creates the symlink
+    Mv {
+        /// Current path (e.g., "~/.vimrc" or ".vimrc")
+        old: String,
+        /// New path (e.g., "~/.config/vim/vimrc")
+        new: String,
+    },
+    /// Keep a child of a synced directory out of the repository, so each
+    /// machine keeps its own copy (e.g. `~/.config/fish/fish_variables`).
+    /// Without a path, lists the local-only files
+    LocalOnly {
+        /// Path inside a synced directory
+        path: Option<String>,
+        /// Sync the path again
+        #[arg(long, requires = "path")]
+        remove: bool,
+    },
+    /// Fix a tracked symlink that was replaced or removed, e.g. by an
+    /// installer writing its own `.zshrc`: the link is re-created and the
+    /// file found in its place backed up. Without a path, lists the symlinks
+    /// that need it
+    Repair {
+        /// The symlink (e.g., "~/.zshrc")
+        path: Option<String>,
+        /// Keep the file found in the link's place instead, moving it into
+        /// the repository
+        #[arg(long, requires = "path")]
+        adopt: bool,
+    },
     /// Activate the symlinks, restores app state after deactivation.
-    Activate,
+    Activate {
+        /// Use the git clone at this path as the storage repository first
+        /// (e.g. a dotfiles repository cloned by GitHub Codespaces)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+        /// Profile to activate when none is active yet
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
     /// Deactivate symlinks. this might be useful if you are going to uninstall dotstate or you need the original files.
     Deactivate,
+    /// Run a profile's bootstrap scripts (`install.sh` and `bootstrap/` in its directory)
+    ///
+    /// They are offered automatically the first time a profile is activated on
+    /// a machine; this runs them again.
+    Bootstrap {
+        /// Profile to bootstrap (default: the active profile)
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
     /// Get or switch the active profile
     Profile {
         #[command(subcommand)]
@@ -87,6 +245,12 @@ pub enum Commands {
         /// Attempt to auto-fix detected issues
         #[arg(long)]
         fix: bool,
+        /// Confirm each fix with a y/n/a prompt (implies --fix)
+        #[arg(short, long, conflicts_with = "json")]
+        interactive: bool,
+        /// Only fix issues reported by the given check (e.g. `tracking`); repeatable, implies --fix
+        #[arg(long, value_name = "CHECK")]
+        fix_only: Vec<String>,
         /// Show detailed diagnostic information
         #[arg(short, long)]
         verbose: bool,
@@ -94,12 +258,219 @@ pub enum Commands {
         #[arg(long)]
         json: bool,
     },
+    /// Check the storage repository's layout against the manifest and the
+    /// symlink tracking file, and offer repairs
+    Fsck {
+        /// Offer the available repairs, one by one
+        #[arg(long)]
+        fix: bool,
+        /// Apply every repair without asking (with --fix)
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+    },
+    /// Check a storage repository checkout in CI: fsck's layout checks, the
+    /// doctor's profile checks and a dry-run activation of every profile into
+    /// a scratch home directory
+    ///
+    /// Needs no config or terminal. Exits with status 1 on errors and prints
+    /// GitHub Actions annotations when run there.
+    Ci {
+        /// Repository to check (default: the current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+        /// Only check this profile; repeatable (default: every profile)
+        #[arg(long = "profile", value_name = "PROFILE")]
+        profiles: Vec<String>,
+        /// Print GitHub Actions annotations even outside GitHub Actions
+        #[arg(long)]
+        annotations: bool,
+    },
+    /// Show past syncs: when they ran, what they pushed and pulled, and how they ended
+    History {
+        /// Number of syncs to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Only show syncs on or after this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+        /// Only show failed syncs
+        #[arg(long)]
+        failed: bool,
+        /// List the files each sync pushed and pulled
+        #[arg(short, long)]
+        verbose: bool,
+        /// Output the history as JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
     /// Shows logs location and how to view them
     Logs,
+    /// Time the dotfile scan, activation (into a scratch home), git status
+    /// and diff, and the file preview on this machine
+    Bench {
+        /// How many times to run each step; the median is shown
+        #[arg(long, default_value_t = 3)]
+        runs: usize,
+    },
+    /// Gather the last error, environment, redacted config and recent log
+    /// lines into a file to attach to a bug report
+    Report {
+        /// Print the report instead of writing a file
+        #[arg(long, conflicts_with = "copy")]
+        print: bool,
+        /// Also copy the report to the clipboard (via the terminal)
+        #[arg(long)]
+        copy: bool,
+    },
     /// Configuration file location
-    Config,
+    Config {
+        /// Check the config file for unknown keys and invalid values
+        #[arg(long)]
+        check: bool,
+        /// Remove unknown keys and invalid values so their defaults apply
+        /// (the original is kept as config.toml.bak)
+        #[arg(long, conflicts_with = "check")]
+        repair: bool,
+    },
     /// Repository location
     Repository,
+    /// Run a git command in the storage repository, e.g. `dotstate git -- log --oneline`
+    ///
+    /// Uses the GitHub token for HTTPS remotes, so pushes and fetches work
+    /// the same as from `DotState`.
+    Git {
+        /// Arguments passed to git
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Source shell snippets (`shell.d/` in common and the profile directories)
+    /// from `.zshrc`/`.bashrc` through a single managed line. Without flags,
+    /// lists the active profile's snippets
+    Snippets {
+        /// Add the source line to the rc files
+        #[arg(long, conflicts_with = "remove")]
+        install: bool,
+        /// Remove the source line from the rc files
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Add starter configs (`.tmux.conf`, `.gitconfig`, a starship preset, ...)
+    /// for files that don't exist yet. Without names, lists the gallery
+    Starters {
+        /// Starters to write into the home directory and sync to the active profile
+        names: Vec<String>,
+    },
+    /// Manage exported environment variables (`env.toml` in common and the
+    /// profile directories), loaded through the shell snippet loader
+    Env {
+        #[command(subcommand)]
+        command: env::EnvCommand,
+    },
+    /// Write the storage repository's `.gitignore`, or add the default
+    /// patterns (OS files, backups, plugin caches) it lacks
+    Gitignore {
+        /// Print the default patterns instead of writing them
+        #[arg(long)]
+        print: bool,
+    },
+    /// Check the changes staged in the storage repository for secrets and
+    /// manifest problems, as its git pre-commit hook does
+    ///
+    /// `--install` adds the hook, so commits made with git directly are
+    /// checked too.
+    PreCommit {
+        /// Install the pre-commit hook in the storage repository
+        #[arg(long, conflicts_with = "uninstall")]
+        install: bool,
+        /// Remove the pre-commit hook
+        #[arg(long)]
+        uninstall: bool,
+        /// Replace a pre-commit hook that wasn't installed by `DotState` (with --install)
+        #[arg(long, requires = "install")]
+        force: bool,
+    },
+    /// Write a GitHub Actions workflow into the storage repository that
+    /// scans for secrets and runs `dotstate ci` on every push
+    Workflow {
+        /// Print the workflow instead of writing it
+        #[arg(long)]
+        print: bool,
+        /// Replace a workflow that wasn't generated by `DotState`
+        #[arg(long)]
+        force: bool,
+    },
+    /// Sync, then sync and activate on every other machine in `machines.toml`
+    /// over SSH
+    Broadcast {
+        /// Only these machines, by hostname in `machines.toml`
+        #[arg(long = "host")]
+        hosts: Vec<String>,
+        /// Don't sync this machine first
+        #[arg(long)]
+        no_sync: bool,
+    },
+    /// Copy the dotfiles to a remote host over SSH and activate them there
+    Deploy {
+        /// Host as `[user@]host[:path]` or `ssh://[user@]host[:port][/path]`;
+        /// the path defaults to `~/.local/share/dotstate/deploy`
+        target: String,
+        /// Profile to deploy (defaults to active profile)
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Install dotstate on the host when it isn't there
+        #[arg(long)]
+        install: bool,
+        /// Copy only the profile's files into the remote home directory, as
+        /// plain files, instead of the repository (dotstate isn't needed there)
+        #[arg(long, conflicts_with = "install")]
+        export: bool,
+    },
+    /// Write an `install.sh` to the storage repository that sets it up in
+    /// GitHub Codespaces and dev containers
+    Devcontainer {
+        /// Profile the script activates when `DOTSTATE_PROFILE` isn't set
+        /// (defaults to active profile)
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Print the script instead of writing it
+        #[arg(long)]
+        print: bool,
+        /// Replace an `install.sh` that wasn't generated by `DotState`
+        #[arg(long)]
+        force: bool,
+    },
+    /// Upload or download the encrypted archive kept on the S3 or `WebDAV`
+    /// storage backend
+    Backend {
+        #[command(subcommand)]
+        command: backend::BackendCommand,
+    },
+    /// Export a profile's files and packages as configuration for another
+    /// tool: a Nix home-manager module or an Ansible role
+    Export {
+        #[command(subcommand)]
+        command: export::ExportCommand,
+    },
+    /// Check and repair a storage directory kept in Syncthing, Dropbox or
+    /// a similar file-sync service
+    FileSync {
+        #[command(subcommand)]
+        command: file_sync::FileSyncCommand,
+    },
+    /// Host the storage repository on a server you own: create a bare
+    /// repository there over SSH, make it `origin` and push to it
+    SshRemote {
+        /// Where the repository goes: `user@host:path` or `ssh://user@host[:port]/path`
+        target: String,
+        /// Replace an existing `origin` remote
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print a path for scripts, e.g. `cd $(dotstate path repo)`
+    Path {
+        #[command(subcommand)]
+        command: PathCommand,
+    },
     /// Show help for a specific command
     Help {
         /// Command to show help for
@@ -116,6 +487,50 @@ pub enum Commands {
         #[command(subcommand)]
         command: PackagesCommand,
     },
+    /// List the installed plugins (executables in the config directory's
+    /// `plugins/`) and the capabilities they provide
+    Plugins,
+    /// Print a compact sync status segment (e.g. `⚑2↑1`) for shell prompts
+    ///
+    /// Reads the last known status, so it never blocks the prompt. Prints
+    /// nothing when everything is in sync.
+    Prompt {
+        /// Print a starship custom module that uses this command
+        #[arg(long)]
+        starship: bool,
+        /// Check the repository and fetch from origin before printing
+        #[arg(long, conflicts_with = "starship")]
+        refresh: bool,
+    },
+    /// Remove all symlinks, restore your files and optionally delete `DotState` data
+    ///
+    /// Prints the files and directories that are left afterwards.
+    Uninstall {
+        /// Also delete the local clone of the storage repository
+        #[arg(long)]
+        remove_storage: bool,
+        /// Also delete the config directory (config, symlink tracking, caches)
+        #[arg(long)]
+        remove_config: bool,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Stay attached and print events as they happen: files edited, backups
+    /// taken, commits made and sync results. Runs until interrupted
+    Watch {
+        /// Also print the sync status (uncommitted, ahead, behind) whenever it changes
+        #[arg(long)]
+        status: bool,
+    },
+    /// Serve a local JSON socket that reports the sync status and runs syncs
+    /// and activations, for menubar apps, status bars and editors
+    ///
+    /// Send one request per line, e.g. `{"command":"status"}`; the commands are
+    /// `status`, `sync`, `activate`, `adopt` and `restore` (the last two take a
+    /// `path`). Tracked symlinks that get replaced or broken are reported with
+    /// a desktop notification. Runs until interrupted.
+    Daemon,
     /// Generate command-line completions
     #[clap(alias = "completion")]
     Completions {
@@ -138,24 +553,136 @@ pub enum ProfileCommand {
     },
 }
 
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum PathCommand {
+    /// The storage repository
+    Repo,
+    /// The config file
+    Config,
+    /// The log file
+    Logs,
+    /// Where a tracked file is stored in the repository
+    File {
+        /// The file as tracked or as in your home directory (e.g. `.zshrc`, `~/.config/nvim/init.lua`)
+        name: String,
+    },
+}
+
 impl Cli {
     /// Execute the CLI command
     pub fn execute(self) -> Result<()> {
         match self.command {
             Some(Commands::Sync { message }) => sync::execute(message),
             Some(Commands::List { verbose }) => files::cmd_list(verbose),
-            Some(Commands::Add { path, common }) => files::cmd_add(path, common),
+            Some(Commands::Show { screen, no_header }) => show::execute(screen, no_header),
+            Some(Commands::Add {
+                path,
+                common,
+                block,
+                git_include,
+                lfs,
+                encrypt,
+            }) => {
+                use crate::services::AddStorage;
+                let storage = if lfs {
+                    AddStorage::Lfs
+                } else if encrypt {
+                    AddStorage::Encrypted
+                } else {
+                    AddStorage::Plain
+                };
+                files::cmd_add(path, common, block, git_include, storage)
+            }
             Some(Commands::Remove { path, common }) => files::cmd_remove(path, common),
-            Some(Commands::Activate) => profiles::cmd_activate(),
+            Some(Commands::Mv { old, new }) => files::cmd_mv(old, new),
+            Some(Commands::LocalOnly { path, remove }) => files::cmd_local_only(path, remove),
+            Some(Commands::Repair { path, adopt }) => files::cmd_repair(path, adopt),
+            Some(Commands::Activate { repo, profile }) => profiles::cmd_activate(repo, profile),
             Some(Commands::Deactivate) => profiles::cmd_deactivate(),
+            Some(Commands::Bootstrap { profile, yes }) => bootstrap::execute(profile, yes),
             Some(Commands::Profile { command }) => profiles::execute(command.unwrap_or_default()),
-            Some(Commands::Doctor { fix, verbose, json }) => doctor::execute(fix, verbose, json),
+            Some(Commands::Doctor {
+                fix,
+                interactive,
+                fix_only,
+                verbose,
+                json,
+            }) => doctor::execute(crate::utils::doctor::DoctorOptions {
+                fix_mode: fix || interactive || !fix_only.is_empty(),
+                interactive,
+                fix_only,
+                verbose,
+                json_output: json,
+                quiet: false,
+            }),
+            Some(Commands::Fsck { fix, yes }) => fsck::execute(fix, yes),
+            Some(Commands::Ci {
+                repo,
+                profiles,
+                annotations,
+            }) => ci::execute(repo, &profiles, annotations),
             Some(Commands::Help { command }) => info::cmd_help(command),
+            Some(Commands::History {
+                limit,
+                since,
+                failed,
+                verbose,
+                json,
+            }) => history::execute(history::HistoryOptions {
+                limit,
+                since,
+                failed,
+                verbose,
+                json,
+            }),
             Some(Commands::Logs) => info::cmd_logs(),
-            Some(Commands::Config) => info::cmd_config(),
+            Some(Commands::Bench { runs }) => bench::execute(runs),
+            Some(Commands::Report { print, copy }) => report::execute(print, copy),
+            Some(Commands::Config { check, repair }) => info::cmd_config(check, repair),
             Some(Commands::Repository) => info::cmd_repository(),
+            Some(Commands::Git { args }) => git::execute(args),
+            Some(Commands::Snippets { install, remove }) => snippets::execute(install, remove),
+            Some(Commands::Starters { names }) => starters::execute(&names),
+            Some(Commands::Env { command }) => env::execute(command),
+            Some(Commands::Gitignore { print }) => gitignore::execute(print),
+            Some(Commands::Workflow { print, force }) => workflow::execute(print, force),
+            Some(Commands::Broadcast { hosts, no_sync }) => broadcast::execute(&hosts, no_sync),
+            Some(Commands::Deploy {
+                target,
+                profile,
+                install,
+                export,
+            }) => deploy::execute(&target, profile.as_deref(), install, export),
+            Some(Commands::Devcontainer {
+                profile,
+                print,
+                force,
+            }) => devcontainer::execute(profile.as_deref(), print, force),
+            Some(Commands::Backend { command }) => backend::execute(command),
+            Some(Commands::SshRemote { target, force }) => ssh_remote::execute(&target, force),
+            Some(Commands::FileSync { command }) => file_sync::execute(command),
+            Some(Commands::Export { command }) => export::execute(command),
+            Some(Commands::PreCommit {
+                install,
+                uninstall,
+                force,
+            }) => pre_commit::execute(install, uninstall, force),
+            Some(Commands::Path { command }) => info::cmd_path(command),
             Some(Commands::Upgrade { check }) => upgrade::execute(check),
             Some(Commands::Packages { command }) => packages::execute(command),
+            Some(Commands::Plugins) => plugins::execute(),
+            Some(Commands::Prompt { starship, refresh }) => prompt::execute(starship, refresh),
+            Some(Commands::Uninstall {
+                remove_storage,
+                remove_config,
+                yes,
+            }) => uninstall::execute(uninstall::UninstallOptions {
+                remove_storage,
+                remove_config,
+                yes,
+            }),
+            Some(Commands::Watch { status }) => watch::execute(status),
+            Some(Commands::Daemon) => daemon::execute(),
             Some(Commands::Completions { shell }) => completions::generate(shell),
             None => {
                 // No command provided, launch TUI
@@ -164,3 +691,19 @@ impl Cli {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_screen() {
+        let cli = Cli::try_parse_from(["dotstate", "--screen", "manage-files"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(
+            cli.screen.map(StartScreen::menu_item),
+            Some(MenuItem::ScanDotfiles)
+        );
+        assert!(Cli::try_parse_from(["dotstate", "--screen", "nowhere"]).is_err());
+    }
+}
@@ -35,7 +35,7 @@ pub enum PackagesCommand {
         /// Package display name
         #[arg(short, long)]
         name: Option<String>,
-        /// Package manager (brew, cargo, apt, npm, pip, custom, etc.)
+        /// Package manager (brew, cargo, apt, npm, pip, custom, plugin:<name>, etc.)
         #[arg(short, long)]
         manager: Option<String>,
         /// Binary name to check for existence
@@ -136,7 +136,7 @@ fn cmd_help(command: Option<String>) -> Result<()> {
             println!("Options:");
             println!("  -p, --profile <NAME>       Target profile (defaults to active profile)");
             println!("  -n, --name <NAME>          Package display name");
-            println!("  -m, --manager <MANAGER>    Package manager (brew, cargo, apt, npm, pip, custom, etc.)");
+            println!("  -m, --manager <MANAGER>    Package manager (brew, cargo, apt, npm, pip, custom, plugin:<name>, etc.)");
             println!("  -b, --binary <NAME>        Binary name to check for existence");
             println!("      --description <TEXT>   Optional description");
             println!("      --package-name <NAME>  Package name in the manager (defaults to binary name)");
@@ -372,7 +372,7 @@ fn cmd_add(
     let manager = match manager {
         Some(m) => parse_manager(&m).ok_or_else(|| {
             anyhow::anyhow!(
-                "Invalid manager '{m}'. Valid: brew, apt, cargo, npm, pip, custom, etc."
+                "Invalid manager '{m}'. Valid: brew, apt, cargo, npm, pip, custom, plugin:<name>, etc."
             )
         })?,
         None => prompt_manager(is_active)?,
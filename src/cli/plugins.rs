@@ -0,0 +1,39 @@
+//! Plugins command: list installed plugins and what they provide.
+
+use super::{print_info, print_success, print_warning};
+use crate::utils::plugins;
+use anyhow::Result;
+
+/// Execute the plugins command.
+pub fn execute() -> Result<()> {
+    let dir = plugins::plugin_dir();
+    let installed = plugins::discover();
+    if installed.is_empty() {
+        print_info(&format!(
+            "No plugins installed. Put executables in {}",
+            dir.display()
+        ));
+        return Ok(());
+    }
+
+    for plugin in &installed {
+        match &plugin.description {
+            Ok(description) => {
+                let version = description
+                    .version
+                    .as_deref()
+                    .map(|v| format!(" {v}"))
+                    .unwrap_or_default();
+                let capabilities = if description.capabilities.is_empty() {
+                    "no capabilities".to_string()
+                } else {
+                    description.capabilities.join(", ")
+                };
+                print_success(&format!("{}{version} ({capabilities})", plugin.name()));
+            }
+            Err(e) => print_warning(&format!("{}: {e}", plugin.name())),
+        }
+        println!("   {}", plugin.path.display());
+    }
+    Ok(())
+}
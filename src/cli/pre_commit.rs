@@ -0,0 +1,48 @@
+//! Pre-commit command: install the storage repository's git hook, or run
+//! its checks on the staged changes.
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::utils::git_hooks;
+use anyhow::{Context, Result};
+
+/// Execute the pre-commit command. With `install` or `uninstall`, manages
+/// the hook in the storage repository; otherwise checks the changes staged
+/// in the repository of the current directory (as the hook does) and exits
+/// with status 1 on problems.
+pub fn execute(install: bool, uninstall: bool, force: bool) -> Result<()> {
+    if install || uninstall {
+        let config = CliContext::load()?.config;
+        if uninstall {
+            if git_hooks::uninstall(&config.repo_path)? {
+                print_success("Removed the pre-commit hook");
+            } else {
+                print_info("No dotstate pre-commit hook is installed");
+            }
+            return Ok(());
+        }
+        let exe = std::env::current_exe().context("Failed to locate the dotstate binary")?;
+        let path = git_hooks::install(&config.repo_path, &exe, force)?;
+        print_success(&format!("Installed {}", path.display()));
+        println!("   Commits to the storage repository are now checked for secrets and");
+        println!(
+            "   manifest problems. Skip the check for a commit with 'git commit --no-verify'."
+        );
+        return Ok(());
+    }
+
+    let cwd = std::env::current_dir().context("Failed to get the current directory")?;
+    let problems = git_hooks::check_staged(&cwd)?;
+    if problems.is_empty() {
+        return Ok(());
+    }
+    print_error("dotstate: the commit was stopped");
+    for problem in &problems {
+        let location = match problem.line {
+            Some(line) => format!("{}:{line}", problem.path),
+            None => problem.path.clone(),
+        };
+        eprintln!("   {location}: {}", problem.message);
+    }
+    eprintln!("   Fix them, or commit with 'git commit --no-verify' to skip this check.");
+    std::process::exit(1);
+}
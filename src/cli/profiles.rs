@@ -7,6 +7,7 @@ use crate::services::ProfileService;
 use crate::utils::symlink_manager::OperationStatus;
 use crate::utils::SymlinkManager;
 use anyhow::{Context, Result};
+use std::path::PathBuf;
 
 /// Execute a profile subcommand.
 pub fn execute(command: ProfileCommand) -> Result<()> {
@@ -117,10 +118,11 @@ pub fn cmd_switch(name: String) -> Result<()> {
             &config.repo_path,
             &config.active_profile,
             &name,
-            config.backup_enabled,
+            config.backup_enabled_for(&name),
         )?;
 
         config.active_profile = name.clone();
+        config.apply_profile_overrides();
         config.profile_activated = true;
         config
             .save(&config_path)
@@ -131,7 +133,7 @@ pub fn cmd_switch(name: String) -> Result<()> {
             "   Removed {} symlinks, created {} symlinks",
             result.removed_count, result.created_count
         );
-        return Ok(());
+        return super::bootstrap::offer_after_activation(&config, &icons);
     }
 
     let resolved_files = manifest
@@ -146,8 +148,10 @@ pub fn cmd_switch(name: String) -> Result<()> {
         std::process::exit(1);
     }
 
-    let mut symlink_mgr =
-        SymlinkManager::new_with_backup(config.repo_path.clone(), config.backup_enabled)?;
+    let mut symlink_mgr = SymlinkManager::new_with_backup(
+        config.repo_path.clone(),
+        config.backup_enabled_for(&name),
+    )?;
     let operations = symlink_mgr.activate_resolved(&name, &resolved_files)?;
 
     let success_count = operations
@@ -175,6 +179,7 @@ pub fn cmd_switch(name: String) -> Result<()> {
     }
 
     config.active_profile = name.clone();
+    config.apply_profile_overrides();
     config.profile_activated = true;
     config
         .save(&config_path)
@@ -183,15 +188,74 @@ pub fn cmd_switch(name: String) -> Result<()> {
     println!("{} Switched to profile '{name}'", icons.success());
     println!("   Activated {success_count} symlinks");
 
-    Ok(())
+    super::bootstrap::offer_after_activation(&config, &icons)
 }
 
-/// Execute the activate command.
-pub fn cmd_activate() -> Result<()> {
+/// Execute the activate command. `repo` sets up a local clone as the
+/// storage repository first (as the devcontainer `install.sh` does), and
+/// `profile` picks the profile when none is active yet.
+pub fn cmd_activate(repo: Option<PathBuf>, profile: Option<String>) -> Result<()> {
     let config_path = crate::utils::get_config_path();
     let mut config =
         Config::load_or_create(&config_path).context("Failed to load configuration")?;
 
+    // Setting up a clone links whatever it holds now, even when already
+    // activated (e.g. a new deploy of the same repository)
+    let setup = repo.is_some();
+    if let Some(repo) = repo {
+        let repo = repo
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", repo.display()))?;
+        if !repo.join(".git").exists() {
+            let icons = Icons::from_config(&config);
+            eprintln!(
+                "{} {} is not a git repository",
+                icons.error(),
+                repo.display()
+            );
+            std::process::exit(1);
+        }
+        if config.profile_activated && config.repo_path != repo {
+            let icons = Icons::from_config(&config);
+            eprintln!(
+                "{} Profile '{}' is active from {}. Run 'dotstate deactivate' first.",
+                icons.error(),
+                config.active_profile,
+                config.repo_path.display()
+            );
+            std::process::exit(1);
+        }
+        config.repo_mode = crate::config::RepoMode::Local;
+        config.repo_path = repo;
+        config.github = None;
+        config
+            .save(&config_path)
+            .context("Failed to save configuration")?;
+    }
+
+    if let Some(profile) = profile.filter(|p| *p != config.active_profile) {
+        let icons = Icons::from_config(&config);
+        if config.profile_activated {
+            eprintln!(
+                "{} Profile '{}' is active. Use 'dotstate profile switch {profile}' instead.",
+                icons.error(),
+                config.active_profile
+            );
+            std::process::exit(1);
+        }
+        let manifest = crate::utils::ProfileManifest::load_or_backfill(&config.repo_path)
+            .context("Failed to load profile manifest")?;
+        if !manifest.has_profile(&profile) {
+            eprintln!("{} Profile '{profile}' not found", icons.error());
+            std::process::exit(1);
+        }
+        config.active_profile = profile;
+        config.apply_profile_overrides();
+        config
+            .save(&config_path)
+            .context("Failed to save configuration")?;
+    }
+
     if !config.is_repo_configured() {
         let icons = Icons::from_config(&config);
         eprintln!(
@@ -204,7 +268,7 @@ pub fn cmd_activate() -> Result<()> {
     let icons = Icons::from_config(&config);
 
     // Check if already activated
-    if config.profile_activated {
+    if config.profile_activated && !setup {
         println!(
             "{} Profile '{}' is already activated.",
             icons.info(),
@@ -283,6 +347,14 @@ pub fn cmd_activate() -> Result<()> {
         }
         std::process::exit(1);
     } else {
+        crate::utils::hooks::fire(
+            &config.repo_path,
+            &active_profile_name,
+            crate::utils::hooks::HookEvent::Activate {
+                created: success_count,
+            },
+        );
+
         // Mark as activated in config
         config.profile_activated = true;
         config
@@ -296,7 +368,7 @@ pub fn cmd_activate() -> Result<()> {
         println!("   {success_count} symlinks created");
     }
 
-    Ok(())
+    super::bootstrap::offer_after_activation(&config, &icons)
 }
 
 /// Execute the deactivate command.
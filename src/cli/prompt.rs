@@ -0,0 +1,48 @@
+//! Prompt command for shell prompt integrations (starship, powerlevel10k).
+
+use crate::config::Config;
+use crate::services::GitService;
+use crate::utils::StatusCache;
+use anyhow::{Context, Result};
+
+/// Starship custom module showing the `dotstate prompt` segment.
+///
+/// Starship hides custom modules whose command prints nothing, so the segment
+/// only appears when something needs syncing.
+const STARSHIP_MODULE: &str = r#"# DotState sync status: ⚑ uncommitted, ↑ ahead, ↓ behind
+# Add to ~/.config/starship.toml (and "${custom.dotstate}" to `format` if you set one)
+[custom.dotstate]
+command = "dotstate prompt"
+when = true
+format = "[$output]($style) "
+style = "bold yellow"
+description = "DotState sync status"
+"#;
+
+/// Execute the prompt command.
+///
+/// Prints the cached status segment without touching git, so it is safe to
+/// run on every prompt. With `refresh`, the status is checked (including a
+/// fetch from origin) and cached first.
+pub fn execute(starship: bool, refresh: bool) -> Result<()> {
+    if starship {
+        print!("{STARSHIP_MODULE}");
+        return Ok(());
+    }
+
+    if refresh {
+        let config_path = crate::utils::get_config_path();
+        let config =
+            Config::load_or_create(&config_path).context("Failed to load configuration")?;
+        if config.is_repo_configured() {
+            GitService::record_status(&GitService::fetch_and_check_status(&config));
+        }
+    }
+
+    if let Some(segment) = StatusCache::load().map(|cache| cache.segment()) {
+        if !segment.is_empty() {
+            println!("{segment}");
+        }
+    }
+    Ok(())
+}
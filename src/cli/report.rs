@@ -0,0 +1,37 @@
+//! Report command: gather an error report for a bug report.
+
+use super::print_success;
+use crate::utils::error_report;
+use anyhow::Result;
+use std::io::Write;
+
+/// Execute the report command: write a report for the last failed command
+/// and show where it is. With `print`, write it to stdout instead; with
+/// `copy`, also put it on the terminal's clipboard.
+pub fn execute(print: bool, copy: bool) -> Result<()> {
+    let error = error_report::last_error();
+    let report = error_report::build(
+        error.as_deref(),
+        &crate::utils::get_config_path(),
+        &crate::utils::get_cache_dir().join("dotstate.log"),
+    );
+    if print {
+        print!("{report}");
+        return Ok(());
+    }
+
+    let path = error_report::save(&report)?;
+    print_success(&format!("Wrote {}", path.display()));
+    if copy {
+        let mut stdout = std::io::stdout();
+        stdout.write_all(error_report::osc52(&report).as_bytes())?;
+        stdout.flush()?;
+        println!("   Copied to the clipboard (if your terminal allows it).");
+    }
+    if error.is_none() {
+        println!("   No failed command was recorded; describe the problem in the issue.");
+    }
+    println!("   Check it for anything private, then paste it into a new issue:");
+    println!("   {}", error_report::ISSUES_URL);
+    Ok(())
+}
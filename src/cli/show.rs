@@ -0,0 +1,206 @@
+//! Show command: the data behind a TUI screen as plain text, for piping
+//! into `grep`, `fzf` or `awk`.
+//!
+//! Each screen prints one row per item with aligned columns under a header
+//! line (left out with `--no-header`). Anything that isn't a row, like the
+//! ahead/behind summary of `status`, goes to stderr so pipes only see rows.
+
+use super::CliContext;
+use crate::services::GitService;
+use crate::utils::backup_manager::{self, BackupManager};
+use crate::utils::binary::format_size;
+use crate::utils::link_monitor::{self, LinkProblemKind};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+
+/// Screens that can be printed
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShowScreen {
+    /// Files synced for the active profile, with where they come from and
+    /// the state of their symlink
+    SyncedFiles,
+    /// Profiles, their parent and file count
+    Profiles,
+    /// Changed files waiting to be synced
+    Status,
+    /// Backup sessions, newest first
+    Backups,
+}
+
+/// Execute the show command.
+pub fn execute(screen: ShowScreen, no_header: bool) -> Result<()> {
+    let ctx = CliContext::load()?;
+    let (header, rows) = match screen {
+        ShowScreen::SyncedFiles => synced_files(&ctx)?,
+        ShowScreen::Profiles => profiles(&ctx),
+        ShowScreen::Status => status(&ctx),
+        ShowScreen::Backups => backups(),
+    };
+    let header = (!no_header).then_some(header.as_slice());
+    for line in table(header, &rows) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+type Rows = (Vec<&'static str>, Vec<Vec<String>>);
+
+fn synced_files(ctx: &CliContext) -> Result<Rows> {
+    let profile = &ctx.config.active_profile;
+    let files = ctx
+        .manifest
+        .resolve_files(profile)
+        .context("Failed to resolve files for profile")?;
+    let problems = link_monitor::check(&ctx.config.repo_path).unwrap_or_default();
+    let home = crate::utils::get_home_dir();
+    let rows = files
+        .into_iter()
+        .map(|file| {
+            let target = home.join(&file.relative_path);
+            let state = match problems.iter().find(|p| p.target == target) {
+                Some(problem) => match problem.kind {
+                    LinkProblemKind::Replaced => "replaced",
+                    LinkProblemKind::Removed => "removed",
+                    LinkProblemKind::Retargeted => "retargeted",
+                    LinkProblemKind::Broken => "broken",
+                },
+                None if target.symlink_metadata().is_ok() => "linked",
+                None => "not linked",
+            };
+            vec![file.relative_path, file.source_profile, state.to_string()]
+        })
+        .collect();
+    Ok((vec!["PATH", "FROM", "STATE"], rows))
+}
+
+fn profiles(ctx: &CliContext) -> Rows {
+    let rows = ctx
+        .manifest
+        .profiles
+        .iter()
+        .map(|profile| {
+            let files = ctx
+                .manifest
+                .resolve_files(&profile.name)
+                .map_or(profile.synced_files.len(), |files| files.len());
+            vec![
+                profile.name.clone(),
+                if ctx.is_active_profile(&profile.name) {
+                    "*".to_string()
+                } else {
+                    String::new()
+                },
+                profile.inherits.clone().unwrap_or_default(),
+                files.to_string(),
+                profile.description.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    (
+        vec!["NAME", "ACTIVE", "INHERITS", "FILES", "DESCRIPTION"],
+        rows,
+    )
+}
+
+fn status(ctx: &CliContext) -> Rows {
+    let status = GitService::fetch_and_check_status(&ctx.config);
+    if let Some(error) = status.error.as_deref().or(status.fetch_error.as_deref()) {
+        eprintln!("{error}");
+    }
+    eprintln!(
+        "{} changed, {} ahead, {} behind",
+        status.uncommitted_files.len(),
+        status.ahead,
+        status.behind
+    );
+    let rows = status
+        .uncommitted_files
+        .iter()
+        .map(|entry| {
+            let code = entry.split_once(' ').map_or("", |(code, _)| code);
+            vec![code.to_string(), GitService::entry_path(entry).to_string()]
+        })
+        .collect();
+    (vec!["CHANGE", "PATH"], rows)
+}
+
+fn backups() -> Rows {
+    let rows = backup_manager::list_sessions(&BackupManager::default_root())
+        .into_iter()
+        .map(|session| {
+            vec![
+                session.name,
+                session.files.to_string(),
+                format_size(session.size),
+                session.path.display().to_string(),
+            ]
+        })
+        .collect();
+    (vec!["SESSION", "FILES", "SIZE", "PATH"], rows)
+}
+
+/// `rows` as lines with each column padded to its widest cell, the last
+/// column left unpadded
+fn table(header: Option<&[&str]>, rows: &[Vec<String>]) -> Vec<String> {
+    let header: Option<Vec<String>> =
+        header.map(|header| header.iter().map(|cell| (*cell).to_string()).collect());
+    let all: Vec<&Vec<String>> = header.iter().chain(rows).collect();
+    let columns = all.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            all.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    all.iter()
+        .map(|row| {
+            let last = row.len().saturating_sub(1);
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    if column == last {
+                        cell.clone()
+                    } else {
+                        format!("{cell:<width$}", width = widths[column])
+                    }
+                })
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let rows = vec![
+            vec![
+                ".zshrc".to_string(),
+                "common".to_string(),
+                "linked".to_string(),
+            ],
+            vec![
+                ".config/nvim".to_string(),
+                "work".to_string(),
+                String::new(),
+            ],
+        ];
+        assert_eq!(
+            table(Some(&["PATH", "FROM", "STATE"]), &rows),
+            vec![
+                "PATH          FROM    STATE",
+                ".zshrc        common  linked",
+                ".config/nvim  work",
+            ]
+        );
+        assert_eq!(table(None, &rows).len(), 2);
+        assert!(table(None, &[]).is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+//! Snippets command: the shell snippet loader and its source line.
+
+use super::{print_info, print_success, CliContext};
+use crate::utils::shell_snippets;
+use anyhow::Result;
+
+/// Execute the snippets command: with `install`, write the loader and put
+/// its source line in the rc files; with `remove`, take the line out.
+/// Otherwise show the active profile's snippets and where the line is.
+pub fn execute(install: bool, remove: bool) -> Result<()> {
+    if remove {
+        let changed = shell_snippets::uninstall()?;
+        if changed.is_empty() {
+            print_info("No rc file sources the snippets");
+        }
+        for rc in changed {
+            print_success(&format!("Removed the source line from {}", rc.display()));
+        }
+        return Ok(());
+    }
+
+    let config = CliContext::load()?.config;
+    let profile = &config.active_profile;
+
+    if install {
+        let changed = shell_snippets::install(&config.repo_path, profile)?;
+        if changed.is_empty() {
+            print_info("The rc files already source the snippets");
+        }
+        for rc in changed {
+            print_success(&format!("Added the source line to {}", rc.display()));
+        }
+        println!(
+            "   Put snippets in {}/ in common or a profile directory of the storage repository.",
+            shell_snippets::SNIPPETS_DIR
+        );
+        return Ok(());
+    }
+
+    let snippets = shell_snippets::snippets(&config.repo_path, profile)?;
+    println!("Shell snippets for profile '{profile}':");
+    if snippets.is_empty() {
+        println!(
+            "  (none; add *.sh, *.zsh or *.bash files to {}/ in common or a profile directory)",
+            shell_snippets::SNIPPETS_DIR
+        );
+    }
+    for path in &snippets {
+        let shown = path.strip_prefix(&config.repo_path).unwrap_or(path);
+        println!("  {}", shown.display());
+    }
+    println!();
+    println!("Loader: {}", shell_snippets::loader_path().display());
+    for name in shell_snippets::RC_FILES {
+        let rc = crate::utils::get_home_dir().join(name);
+        let Ok(content) = std::fs::read_to_string(&rc) else {
+            continue;
+        };
+        let state = if shell_snippets::has_line(&content) {
+            "sources the snippets"
+        } else {
+            "doesn't source the snippets (run 'dotstate snippets --install')"
+        };
+        println!("~/{name}: {state}");
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! SSH remote command: host the storage repository on a plain SSH server.
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::config::RepoMode;
+use crate::git::GitManager;
+use crate::utils::ssh_remote::{self, SshTarget};
+use anyhow::{Context, Result};
+
+/// Execute the ssh-remote command: create a bare repository at `target`
+/// over SSH, make it the storage repository's `origin` and push the current
+/// branch to it. An existing `origin` is only replaced with `force`.
+pub fn execute(target: &str, force: bool) -> Result<()> {
+    let target = SshTarget::parse(target)?;
+    let CliContext {
+        mut config,
+        config_path,
+        ..
+    } = CliContext::load()?;
+    let mut git_mgr =
+        GitManager::open_or_init(&config.repo_path).context("Failed to open repository")?;
+    if git_mgr.has_remote("origin") && !force {
+        print_error("The storage repository already has an 'origin' remote");
+        println!("   Run again with --force to point it at the SSH host instead.");
+        std::process::exit(1);
+    }
+
+    print_info(&format!("Creating the repository on {}...", target.host));
+    ssh_remote::init_bare(&target)?;
+    let url = target.remote_url();
+    git_mgr.add_remote("origin", &url)?;
+
+    let branch = git_mgr
+        .get_current_branch()
+        .unwrap_or_else(|| config.default_branch.clone());
+    if git_mgr.head_oid().is_some() {
+        print_info(&format!("Pushing '{branch}'..."));
+        git_mgr
+            .push("origin", &branch, None)
+            .context("Failed to push to the SSH host")?;
+    }
+
+    // Syncing an SSH remote needs no GitHub token
+    config.repo_mode = RepoMode::Local;
+    config.save(&config_path)?;
+    print_success(&format!("The storage repository now syncs with {url}"));
+    println!("   Other machines can clone it with 'git clone {url}' and set it up as a local repository.");
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Starters command: add starter configs for files that don't exist yet.
+
+use super::{print_error, print_info, print_success, CliContext};
+use crate::services::{AddFileResult, SyncService};
+use crate::utils::starters::{self, STARTERS};
+use anyhow::Result;
+
+/// Execute the starters command: without `names`, list the gallery; with
+/// them, write each starter into the home directory and sync it to the
+/// active profile. Exits with status 1 when a starter couldn't be added.
+pub fn execute(names: &[String]) -> Result<()> {
+    let home = crate::utils::get_home_dir();
+    if names.is_empty() {
+        println!("Starter configs:");
+        for starter in STARTERS {
+            let state = if starters::is_available(starter, &home) {
+                ""
+            } else {
+                "  (exists)"
+            };
+            println!(
+                "  {:<14}{:<24}{}{state}",
+                starter.name, starter.path, starter.description
+            );
+        }
+        println!();
+        println!("Add one with 'dotstate starters <name>...'.");
+        return Ok(());
+    }
+
+    let CliContext {
+        mut config,
+        config_path,
+        ..
+    } = CliContext::load()?;
+    let mut failed = false;
+    for name in names {
+        let Some(starter) = starters::find(name) else {
+            print_error(&format!("Unknown starter '{name}'"));
+            failed = true;
+            continue;
+        };
+        if !starters::is_available(starter, &home) {
+            print_info(&format!(
+                "~/{} already exists, skipping the {name} starter",
+                starter.path
+            ));
+            continue;
+        }
+        let path = starters::write(starter, &home)?;
+        match SyncService::add_file_to_sync(&config, &path, starter.path, config.backup_enabled)? {
+            AddFileResult::Success | AddFileResult::AlreadySynced => {
+                // Keep files outside the default candidates visible in the TUI
+                if SyncService::is_custom_file(starter.path)
+                    && !config.custom_files.iter().any(|f| f == starter.path)
+                {
+                    config.custom_files.push(starter.path.to_string());
+                    config.save(&config_path)?;
+                }
+                print_success(&format!(
+                    "Added ~/{} to profile '{}'",
+                    starter.path, config.active_profile
+                ));
+            }
+            AddFileResult::ValidationFailed(msg) => {
+                print_error(&format!(
+                    "~/{} was written but not synced: {msg}",
+                    starter.path
+                ));
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+    println!("   Edit them as you like, then commit with 'dotstate sync'.");
+    Ok(())
+}
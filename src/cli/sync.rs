@@ -3,6 +3,9 @@
 use crate::config::{Config, RepoMode};
 use crate::git::GitManager;
 use crate::services::ProfileService;
+use crate::utils::profile_manifest::ProfileManifest;
+use crate::utils::storage_backend;
+use crate::utils::sync_history::{self, SyncKind, SyncOrigin, SyncRecord};
 use anyhow::{Context, Result};
 use tracing::{info, warn};
 
@@ -20,8 +23,15 @@ pub fn execute(message: Option<String>) -> Result<()> {
         std::process::exit(1);
     }
 
+    // Block early if something would make the sync fail half-way
+    if let Some(message) = crate::services::GitService::preflight(&config) {
+        warn!("CLI sync: blocked by pre-flight checks");
+        eprintln!("❌ {}", message.trim_start_matches("Error: "));
+        std::process::exit(1);
+    }
+
     let repo_path = &config.repo_path;
-    let git_mgr = GitManager::open_or_init(repo_path).context("Failed to open repository")?;
+    let mut git_mgr = GitManager::open_or_init(repo_path).context("Failed to open repository")?;
 
     let branch = git_mgr
         .get_current_branch()
@@ -48,35 +58,207 @@ pub fn execute(message: Option<String>) -> Result<()> {
         std::process::exit(1);
     }
 
+    // Taken after the checks above, which exit without releasing it
+    let _lock = if config.file_sync_mode {
+        Some(crate::utils::file_sync::acquire(&config.repo_path)?)
+    } else {
+        None
+    };
+
+    let kind = if config.read_only {
+        SyncKind::Pull
+    } else {
+        SyncKind::Sync
+    };
+    let mut record = SyncRecord::start(kind, SyncOrigin::Cli, &config.active_profile);
+    record.branch = Some(branch.clone());
+
+    let result = if config.read_only {
+        pull_only(
+            &config,
+            &mut git_mgr,
+            &branch,
+            token,
+            message.is_some(),
+            &mut record,
+        )
+    } else {
+        commit_pull_push(&config, &mut git_mgr, &branch, token, message, &mut record)
+    };
+    match &result {
+        Ok(()) => record.finish(true, ""),
+        Err(e) => record.finish(false, &format!("{e:#}")),
+    }
+    sync_history::save(&record);
+    crate::utils::hooks::fire(
+        &config.repo_path,
+        &config.active_profile,
+        crate::utils::hooks::HookEvent::Sync(&record),
+    );
+    result
+}
+
+/// Commit local changes, pull with rebase and push.
+fn commit_pull_push(
+    config: &Config,
+    git_mgr: &mut GitManager,
+    branch: &str,
+    token: Option<&str>,
+    message: Option<String>,
+    record: &mut SyncRecord,
+) -> Result<()> {
+    ProfileManifest::load(&config.repo_path)?.ensure_writable()?;
+    let reencrypted = crate::utils::sops::reencrypt_changed(&config.repo_path)
+        .context("Failed to re-encrypt sops files")?;
+    for path in &reencrypted {
+        println!("🔐 Re-encrypted {path}");
+    }
+    let captured = crate::utils::managed_block::capture_changed(&config.repo_path)
+        .context("Failed to read managed blocks")?;
+    for path in &captured {
+        println!("🧩 Updated {path} from its managed block");
+    }
+    if let Err(e) = crate::utils::integrity::record(&config.repo_path) {
+        eprintln!("⚠️  Failed to record content hashes: {e:#}");
+    }
+    crate::services::GitService::record_machine(config, git_mgr);
+    match crate::utils::repo_readme::update(config) {
+        Ok(true) => println!("📄 Regenerated README.md"),
+        Ok(false) => {}
+        Err(e) => eprintln!("⚠️  Failed to regenerate README.md: {e:#}"),
+    }
+
     println!("📝 Committing changes...");
     let commit_msg = message.unwrap_or_else(|| {
-        git_mgr
-            .generate_commit_message()
-            .unwrap_or_else(|_| "Update dotfiles".to_string())
+        config.format_commit_message(
+            &git_mgr
+                .generate_commit_message()
+                .unwrap_or_else(|_| "Update dotfiles".to_string()),
+        )
     });
+    let changed = git_mgr.get_changed_files().unwrap_or_default();
     git_mgr
         .commit_all(&commit_msg)
         .context("Failed to commit changes")?;
+    if !changed.is_empty() {
+        record.committed = changed;
+        record.set_commit(git_mgr.head_oid());
+    }
 
     println!("📥 Pulling changes from remote...");
-    let pulled_count = git_mgr
-        .pull_with_rebase("origin", &branch, token)
+    let pulled_count = pull(config, git_mgr, branch, token, record)?;
+    if pulled_count > 0 {
+        ProfileManifest::load(&config.repo_path)?.ensure_writable()?;
+    }
+
+    if let Some(backend) = storage_backend::replacing_remote(config, git_mgr) {
+        println!("📤 Uploading encrypted archive...");
+        storage_backend::push(backend, &config.repo_path, branch)
+            .context("Failed to upload to the storage backend")?;
+    } else {
+        let push_dest = match config.repo_mode {
+            RepoMode::GitHub => "GitHub",
+            RepoMode::Local => "remote",
+        };
+        println!("📤 Pushing to {push_dest}...");
+        git_mgr
+            .push("origin", branch, token)
+            .context("Failed to push to remote")?;
+        if let Some(backend) = &config.storage_backend {
+            println!("📤 Uploading encrypted archive...");
+            if let Err(e) = storage_backend::push(backend, &config.repo_path, branch) {
+                eprintln!("⚠️  Failed to upload the encrypted archive: {e:#}");
+            }
+        }
+    }
+    crate::services::GitService::record_successful_sync();
+
+    ensure_symlinks_after_pull(config, pulled_count);
+    Ok(())
+}
+
+/// Pull with rebase (from the storage backend when it replaces origin),
+/// noting what was pulled in `record`.
+fn pull(
+    config: &Config,
+    git_mgr: &GitManager,
+    branch: &str,
+    token: Option<&str>,
+    record: &mut SyncRecord,
+) -> Result<usize> {
+    let head_before = git_mgr.head_oid();
+    let pulled = match storage_backend::replacing_remote(config, git_mgr) {
+        Some(backend) => storage_backend::pull(backend, &config.repo_path, branch),
+        None => git_mgr.pull_with_rebase("origin", branch, token),
+    };
+    let pulled_count = pulled
+        .inspect_err(|e| {
+            crate::utils::conflict_journal::record_pull_error(
+                e,
+                record,
+                if record.commit.is_some() {
+                    "Sync stopped; the local commit is kept, not pushed"
+                } else {
+                    "Stopped before anything changed; local changes were kept"
+                },
+            );
+        })
         .context("Failed to pull from remote")?;
+    record.pulled_commits = pulled_count;
+    if let Some(old) = head_before.filter(|_| pulled_count > 0) {
+        record.pulled = git_mgr.changed_since(old).unwrap_or_default();
+    }
+    Ok(pulled_count)
+}
+
+/// Sync on a pull-only machine: local changes are set aside, the remote is
+/// pulled, and nothing is committed or pushed.
+fn pull_only(
+    config: &Config,
+    git_mgr: &mut GitManager,
+    branch: &str,
+    token: Option<&str>,
+    has_message: bool,
+    record: &mut SyncRecord,
+) -> Result<()> {
+    println!("🔒 Pull-only machine: local changes are not committed or pushed.");
+    if has_message {
+        eprintln!("⚠️  Ignoring --message: nothing is committed on a pull-only machine.");
+    }
 
-    let push_dest = match config.repo_mode {
-        RepoMode::GitHub => "GitHub",
-        RepoMode::Local => "remote",
+    let stashed = git_mgr
+        .stash_changes("dotstate: local changes on pull-only machine")
+        .context("Failed to set aside local changes")?;
+
+    println!("📥 Pulling changes from remote...");
+    let pulled = pull(config, git_mgr, branch, token, record);
+
+    if stashed {
+        if let Err(e) = git_mgr.stash_pop() {
+            warn!("Failed to restore stashed changes: {}", e);
+            eprintln!("⚠️  Could not restore your local changes: {e}");
+            eprintln!("   They are saved in the stash. Run 'git stash pop' in the repository to restore them.");
+        }
+    }
+    let pulled_count = pulled?;
+    crate::services::GitService::record_successful_sync();
+
+    ensure_symlinks_after_pull(config, pulled_count);
+    Ok(())
+}
+
+/// Report the pull and create symlinks for any files it brought in.
+fn ensure_symlinks_after_pull(config: &Config, pulled_count: usize) {
+    let repo_path = &config.repo_path;
+    let done = if config.read_only {
+        "pulled from remote"
+    } else {
+        "synced with remote"
     };
-    println!("📤 Pushing to {push_dest}...");
-    git_mgr
-        .push("origin", &branch, token)
-        .context("Failed to push to remote")?;
 
     if pulled_count > 0 {
         info!("CLI sync completed: pulled {} commit(s)", pulled_count);
-        println!(
-            "✅ Successfully synced with remote! Pulled {pulled_count} change(s) from remote."
-        );
+        println!("✅ Successfully {done}! Pulled {pulled_count} change(s) from remote.");
 
         // Ensure symlinks for any new files pulled from remote
         println!("🔗 Checking for new files to symlink...");
@@ -127,7 +309,6 @@ pub fn execute(message: Option<String>) -> Result<()> {
         }
     } else {
         info!("CLI sync completed: no changes pulled");
-        println!("✅ Successfully synced with remote! No changes pulled from remote.");
+        println!("✅ Successfully {done}! No changes pulled from remote.");
     }
-    Ok(())
 }
@@ -0,0 +1,256 @@
+//! Uninstall command: remove symlinks, restore files and optionally delete `DotState` data.
+
+use super::common::prompt_confirm;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::services::GitService;
+use crate::utils::symlink_manager::OperationStatus;
+use crate::utils::SymlinkManager;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Options for the uninstall command.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UninstallOptions {
+    /// Delete the local clone of the storage repository
+    pub remove_storage: bool,
+    /// Delete the config directory (config, symlink tracking, caches)
+    pub remove_config: bool,
+    /// Skip the confirmation prompt
+    pub yes: bool,
+}
+
+/// Execute the uninstall command.
+///
+/// Symlinks are replaced with copies of the files from the repository (or
+/// their backups), so the machine keeps working without `DotState`. Storage
+/// and config are only deleted after every file was restored.
+pub fn execute(options: UninstallOptions) -> Result<()> {
+    let config_path = crate::utils::get_config_path();
+    let config = if config_path.exists() {
+        Some(Config::load_or_create(&config_path).context("Failed to load configuration")?)
+    } else {
+        None
+    };
+    let icons = config.as_ref().map_or_else(Icons::new, Icons::from_config);
+    let configured = config.as_ref().is_some_and(Config::is_repo_configured);
+
+    println!("{} Uninstalling DotState", icons.wrench());
+    if configured {
+        println!("   • Remove all symlinks and restore the files they point to");
+    }
+    if let Some(config) = config.as_ref().filter(|_| options.remove_storage) {
+        if config.repo_path.exists() {
+            println!(
+                "   • Delete the storage repository at {}",
+                config.repo_path.display()
+            );
+            if GitService::check_changes_to_push(config).has_changes {
+                println!(
+                    "     {} It has changes that were never synced and will be lost",
+                    icons.warning()
+                );
+            }
+        }
+    }
+    if options.remove_config {
+        println!(
+            "   • Delete the config directory at {} and the symlink tracking in {}",
+            crate::utils::get_config_dir().display(),
+            crate::utils::get_data_dir().display()
+        );
+    }
+    println!();
+
+    if !options.yes && !prompt_confirm("Continue?")? {
+        println!("Uninstall cancelled.");
+        return Ok(());
+    }
+
+    if let Some(mut config) = config.filter(|_| configured) {
+        restore_files(&mut config, &icons)?;
+        if !options.remove_config {
+            config
+                .save(&config_path)
+                .context("Failed to save configuration")?;
+        }
+
+        if options.remove_storage && config.repo_path.exists() {
+            if let Some(problem) =
+                storage_removal_problem(&config.repo_path, &crate::utils::get_home_dir())
+            {
+                anyhow::bail!(problem);
+            }
+            std::fs::remove_dir_all(&config.repo_path).with_context(|| {
+                format!(
+                    "Failed to delete storage repository {}",
+                    config.repo_path.display()
+                )
+            })?;
+            println!(
+                "{} Deleted storage repository {}",
+                icons.success(),
+                config.repo_path.display()
+            );
+        }
+    }
+
+    if options.remove_config {
+        let config_dir = crate::utils::get_config_dir();
+        if config_dir.exists() {
+            std::fs::remove_dir_all(&config_dir).with_context(|| {
+                format!("Failed to delete config directory {}", config_dir.display())
+            })?;
+            println!(
+                "{} Deleted config directory {}",
+                icons.success(),
+                config_dir.display()
+            );
+        }
+
+        let data_dir = crate::utils::get_data_dir();
+        if data_dir != config_dir && data_dir.exists() {
+            for name in [
+                "symlinks.json",
+                "last_sync",
+                "sync_history.jsonl",
+                "bootstrap.json",
+            ] {
+                let path = data_dir.join(name);
+                if path.exists() {
+                    std::fs::remove_file(&path)
+                        .with_context(|| format!("Failed to delete {}", path.display()))?;
+                }
+            }
+            // Only goes away when nothing else, like the storage repo, is left in it
+            if std::fs::remove_dir(&data_dir).is_ok() {
+                println!(
+                    "{} Deleted data directory {}",
+                    icons.success(),
+                    data_dir.display()
+                );
+            }
+        }
+    }
+
+    print_remaining(&icons);
+    Ok(())
+}
+
+/// Replace every tracked symlink with the file it points to.
+///
+/// Exits with an error when a file couldn't be restored, so nothing is
+/// deleted while its only copy is still in the repository.
+fn restore_files(config: &mut Config, icons: &Icons) -> Result<()> {
+    let mut symlink_mgr =
+        SymlinkManager::new_with_backup(config.repo_path.clone(), config.backup_enabled)?;
+    let operations = symlink_mgr.deactivate_profile_with_restore(&config.active_profile, true)?;
+
+    let failed: Vec<_> = operations
+        .iter()
+        .filter_map(|op| match &op.status {
+            OperationStatus::Failed(msg) => Some((op, msg)),
+            _ => None,
+        })
+        .collect();
+    if !failed.is_empty() {
+        eprintln!(
+            "{} {} files could not be restored, nothing was deleted:",
+            icons.error(),
+            failed.len()
+        );
+        for (op, msg) in failed {
+            eprintln!("   {} {}: {}", icons.error(), op.target.display(), msg);
+        }
+        std::process::exit(1);
+    }
+
+    let restored = operations
+        .iter()
+        .filter(|op| op.status == OperationStatus::Success)
+        .count();
+    println!(
+        "{} Removed symlinks and restored {restored} files",
+        icons.success()
+    );
+    config.profile_activated = false;
+    Ok(())
+}
+
+/// Explain why deleting `repo_path` would be unsafe, if it would be.
+fn storage_removal_problem(repo_path: &Path, home: &Path) -> Option<String> {
+    if home.starts_with(repo_path) {
+        return Some(format!(
+            "Refusing to delete {}: it contains your home directory",
+            repo_path.display()
+        ));
+    }
+    if !crate::utils::is_git_repo(repo_path) {
+        return Some(format!(
+            "Refusing to delete {}: it is not a git repository",
+            repo_path.display()
+        ));
+    }
+    None
+}
+
+/// Paths `DotState` leaves behind after uninstalling, if they exist.
+fn remaining_paths() -> Vec<(&'static str, PathBuf)> {
+    let config_path = crate::utils::get_config_path();
+    let repo_path = config_path
+        .exists()
+        .then(|| crate::utils::get_repository_path().ok())
+        .flatten();
+
+    let mut paths = vec![("Config directory", crate::utils::get_config_dir())];
+    if let Some(repo_path) = repo_path {
+        paths.push(("Storage repository", repo_path));
+    }
+    paths.push(("Backups", crate::utils::BackupManager::default_root()));
+    paths.push(("Data directory", crate::utils::get_data_dir()));
+    paths.push(("Logs and caches", crate::utils::get_cache_dir()));
+    if let Ok(exe) = std::env::current_exe() {
+        paths.push(("Binary", exe));
+    }
+    paths.retain(|(_, path)| path.exists());
+    paths
+}
+
+/// Print what is still on disk and how to remove it.
+fn print_remaining(icons: &Icons) {
+    println!();
+    println!(
+        "{} Your files are restored and no longer symlinked.",
+        icons.success()
+    );
+    println!("   Still on this machine:");
+    for (label, path) in remaining_paths() {
+        println!("   • {label}: {}", path.display());
+    }
+    println!("   • Packages installed through profiles were not removed");
+    println!(
+        "{} Delete these manually to remove DotState completely.",
+        icons.lightbulb()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::storage_removal_problem;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_storage_removal_problem() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        let repo = home.join(".local/share/dotstate/storage");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+
+        assert!(storage_removal_problem(&repo, &home).is_none());
+        assert!(storage_removal_problem(&home, &home).is_some());
+        assert!(storage_removal_problem(temp.path(), &home).is_some());
+        assert!(storage_removal_problem(&home.join("notes"), &home).is_some());
+        assert!(storage_removal_problem(Path::new("/"), &home).is_some());
+    }
+}
@@ -0,0 +1,256 @@
+//! Watch command: stay attached and print events as they happen.
+//!
+//! Polls the storage repository, the backup directory and the sync history
+//! once per [`POLL_INTERVAL`] and prints a line for each file edited, backup
+//! taken, commit made and sync finished, whether the TUI, a CLI command or
+//! an editor caused it. Meant for a terminal or tmux pane next to the one
+//! you tweak your configs in.
+
+use super::CliContext;
+use crate::git::{short_id, GitManager};
+use crate::utils::sync_history::{self, SyncRecord};
+use crate::utils::BackupManager;
+use crate::utils::StatusCache;
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// How often the repository and the backups are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the watch compares between polls
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Snapshot {
+    /// Uncommitted files ("X path" entries) with their modification times
+    changed: BTreeMap<String, Option<SystemTime>>,
+    /// Commit HEAD points to
+    head: Option<git2::Oid>,
+    /// Backup session directories
+    backups: BTreeSet<String>,
+    /// Start of the newest recorded sync
+    last_sync: Option<DateTime<Utc>>,
+    /// Commits ahead of and behind origin, as last fetched
+    ahead_behind: (usize, usize),
+}
+
+/// Something that happened between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum WatchEvent {
+    /// A file was edited, added or deleted ("X path")
+    FileChanged(String),
+    /// A backup session was created
+    Backup(String),
+    /// HEAD moved to a new commit
+    Commit(git2::Oid),
+    /// A sync finished
+    Sync(Box<SyncRecord>),
+}
+
+impl Snapshot {
+    fn take(git_mgr: &GitManager, repo_path: &Path, backup_root: &Path, branch: &str) -> Self {
+        let changed = git_mgr
+            .get_changed_files()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| {
+                let modified = entry
+                    .get(2..)
+                    .and_then(|path| std::fs::symlink_metadata(repo_path.join(path)).ok())
+                    .and_then(|metadata| metadata.modified().ok());
+                (entry, modified)
+            })
+            .collect();
+        let backups = std::fs::read_dir(backup_root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            changed,
+            head: git_mgr.head_oid(),
+            backups,
+            last_sync: sync_history::load().first().map(|r| r.timestamp),
+            ahead_behind: git_mgr
+                .get_ahead_behind("origin", branch)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Events between `self` and the `newer` snapshot. `records` is the
+    /// sync history, newest first.
+    fn events(&self, newer: &Snapshot, records: &[SyncRecord]) -> Vec<WatchEvent> {
+        let mut events: Vec<WatchEvent> = newer
+            .changed
+            .iter()
+            .filter(|(entry, modified)| self.changed.get(*entry) != Some(modified))
+            .map(|(entry, _)| WatchEvent::FileChanged(entry.clone()))
+            .collect();
+        events.extend(
+            newer
+                .backups
+                .difference(&self.backups)
+                .map(|name| WatchEvent::Backup(name.clone())),
+        );
+        if let Some(head) = newer.head.filter(|head| self.head != Some(*head)) {
+            events.push(WatchEvent::Commit(head));
+        }
+        if newer.last_sync != self.last_sync {
+            let new_syncs = records
+                .iter()
+                .take_while(|record| self.last_sync.is_none_or(|last| record.timestamp > last))
+                .count();
+            events.extend(
+                records[..new_syncs]
+                    .iter()
+                    .rev()
+                    .map(|record| WatchEvent::Sync(Box::new(record.clone()))),
+            );
+        }
+        events
+    }
+
+    /// Status segment like `dotstate prompt` prints
+    fn status(&self) -> String {
+        let (ahead, behind) = self.ahead_behind;
+        let segment = StatusCache::new(self.changed.len(), ahead, behind).segment();
+        if segment.is_empty() {
+            "in sync".to_string()
+        } else {
+            segment
+        }
+    }
+}
+
+fn describe(event: &WatchEvent, git_mgr: &GitManager, backup_root: &Path) -> String {
+    match event {
+        WatchEvent::FileChanged(entry) => {
+            let (status, path) = entry.split_at(2.min(entry.len()));
+            let verb = match status.trim() {
+                "A" => "Added",
+                "D" => "Deleted",
+                _ => "Changed",
+            };
+            format!("✎ {verb} {path}")
+        }
+        WatchEvent::Backup(name) => format!("🗄 Backup taken: {}", backup_root.join(name).display()),
+        WatchEvent::Commit(oid) => {
+            let summary = git_mgr
+                .repo()
+                .find_commit(*oid)
+                .ok()
+                .and_then(|commit| commit.summary().ok().flatten().map(str::to_string))
+                .unwrap_or_default();
+            format!("● Commit {} {summary}", short_id(*oid))
+        }
+        WatchEvent::Sync(record) => {
+            let status = if record.conflict {
+                "⚠️ "
+            } else if record.success {
+                "✅"
+            } else {
+                "❌"
+            };
+            format!(
+                "{status} {} ({}): {}",
+                record.kind.label(),
+                record.origin.label(),
+                record.summary()
+            )
+        }
+    }
+}
+
+fn print_line(line: &str) {
+    println!("{}  {line}", Local::now().format("%H:%M:%S"));
+}
+
+/// Execute the watch command. Runs until interrupted. With `status`, also
+/// prints the sync status whenever it changes.
+pub fn execute(status: bool) -> Result<()> {
+    let config = CliContext::load()?.config;
+    let git_mgr = GitManager::open_or_init(&config.repo_path)?;
+    let backup_root = BackupManager::default_root();
+    let branch = git_mgr
+        .get_current_branch()
+        .unwrap_or_else(|| config.default_branch.clone());
+
+    println!(
+        "Watching {} (press Ctrl+C to stop)",
+        config.repo_path.display()
+    );
+    let mut snapshot = Snapshot::take(&git_mgr, &config.repo_path, &backup_root, &branch);
+    if status {
+        print_line(&format!("Status: {}", snapshot.status()));
+    }
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let newer = Snapshot::take(&git_mgr, &config.repo_path, &backup_root, &branch);
+        if newer == snapshot {
+            continue;
+        }
+        let records = if newer.last_sync == snapshot.last_sync {
+            Vec::new()
+        } else {
+            sync_history::load()
+        };
+        for event in snapshot.events(&newer, &records) {
+            print_line(&describe(&event, &git_mgr, &backup_root));
+        }
+        if status && newer.status() != snapshot.status() {
+            print_line(&format!("Status: {}", newer.status()));
+        }
+        snapshot = newer;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sync_history::{SyncKind, SyncOrigin};
+
+    #[test]
+    fn test_events_between_snapshots() {
+        let time = SystemTime::UNIX_EPOCH;
+        let older = Snapshot {
+            changed: BTreeMap::from([("M common/.zshrc".to_string(), Some(time))]),
+            backups: BTreeSet::from(["2026-01-01T10-00-00".to_string()]),
+            ..Snapshot::default()
+        };
+        let mut sync = SyncRecord::start(SyncKind::Sync, SyncOrigin::Cli, "work");
+        sync.finish(true, "");
+        let newer = Snapshot {
+            changed: BTreeMap::from([
+                ("M common/.zshrc".to_string(), Some(time)),
+                ("A work/.vimrc".to_string(), Some(time)),
+            ]),
+            backups: BTreeSet::from([
+                "2026-01-01T10-00-00".to_string(),
+                "2026-01-02T10-00-00".to_string(),
+            ]),
+            head: Some(git2::Oid::ZERO_SHA1),
+            last_sync: Some(sync.timestamp),
+            ahead_behind: (1, 0),
+        };
+
+        let events = older.events(&newer, std::slice::from_ref(&sync));
+        assert_eq!(
+            events,
+            vec![
+                WatchEvent::FileChanged("A work/.vimrc".to_string()),
+                WatchEvent::Backup("2026-01-02T10-00-00".to_string()),
+                WatchEvent::Commit(git2::Oid::ZERO_SHA1),
+                WatchEvent::Sync(Box::new(sync.clone())),
+            ]
+        );
+        // Syncs already seen aren't printed again
+        assert!(newer.events(&newer, &[sync]).is_empty());
+        assert_eq!(older.status(), "⚑1");
+    }
+}
@@ -0,0 +1,29 @@
+//! Workflow command: write a GitHub Actions workflow into the storage repository.
+
+use super::{print_info, print_success, CliContext};
+use crate::utils::repo_workflow::{self, WriteOutcome};
+use anyhow::Result;
+
+/// Execute the workflow command: write (or update) the workflow running
+/// secret scanning and `dotstate ci`. With `print`, only show it.
+pub fn execute(print: bool, force: bool) -> Result<()> {
+    let version = env!("CARGO_PKG_VERSION");
+    if print {
+        print!("{}", repo_workflow::generate(version));
+        return Ok(());
+    }
+
+    let config = CliContext::load()?.config;
+    let path = repo_workflow::path(&config.repo_path);
+    match repo_workflow::write(&config.repo_path, version, force)? {
+        WriteOutcome::Unchanged => {
+            print_info(&format!("{} is up to date", path.display()));
+            return Ok(());
+        }
+        WriteOutcome::Created => print_success(&format!("Created {}", path.display())),
+        WriteOutcome::Updated => print_success(&format!("Updated {}", path.display())),
+    }
+    println!("   It scans for secrets and runs 'dotstate ci' on every push and pull request.");
+    println!("   Commit it with 'dotstate sync'.");
+    Ok(())
+}
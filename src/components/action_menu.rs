@@ -0,0 +1,312 @@
+//! Action menu component.
+//!
+//! A small popup listing everything that can be done with one item of a
+//! list, so the actions don't all have to be remembered as keys. Each entry
+//! shows the key that does the same thing directly, when there is one.
+
+use crate::components::popup::Popup;
+use crate::config::Config;
+use crate::keymap::Action;
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::utils::mouse::MouseRegions;
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::{List, ListItem, ListState};
+
+/// One entry in the menu
+#[derive(Debug, Clone)]
+pub struct MenuEntry<T> {
+    pub label: String,
+    /// Key that does the same from the list, shown next to the label
+    pub key: Option<String>,
+    pub value: T,
+}
+
+impl<T> MenuEntry<T> {
+    #[must_use]
+    pub fn new(label: impl Into<String>, value: T) -> Self {
+        Self {
+            label: label.into(),
+            key: None,
+            value,
+        }
+    }
+
+    /// Show `key` next to the label
+    #[must_use]
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+}
+
+/// Result of action menu interaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionMenuResult<T> {
+    /// No action taken
+    None,
+    /// The menu was closed without choosing
+    Closed,
+    /// An entry was chosen; the menu is closed
+    Chosen(T),
+}
+
+/// Action menu component state
+#[derive(Debug)]
+pub struct ActionMenu<T> {
+    /// Title of the popup, usually the item the actions apply to
+    title: String,
+    entries: Vec<MenuEntry<T>>,
+    list_state: ListState,
+    /// Clickable entry rows (value = entry index)
+    row_regions: MouseRegions<usize>,
+    /// The popup area, clicks outside it close the menu
+    popup_area: Option<Rect>,
+}
+
+impl<T> Default for ActionMenu<T> {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            entries: Vec::new(),
+            list_state: ListState::default(),
+            row_regions: MouseRegions::new(),
+            popup_area: None,
+        }
+    }
+}
+
+impl<T: Clone> ActionMenu<T> {
+    /// Create a closed menu
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the menu is showing
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        !self.entries.is_empty()
+    }
+
+    /// Show `entries` under `title`, with the first one selected. Nothing
+    /// opens when there are no entries.
+    pub fn open(&mut self, title: impl Into<String>, entries: Vec<MenuEntry<T>>) {
+        self.title = title.into();
+        self.entries = entries;
+        self.list_state.select(Some(0));
+        self.popup_area = None;
+    }
+
+    /// Close the menu
+    pub fn close(&mut self) {
+        self.entries.clear();
+        self.row_regions.clear();
+        self.popup_area = None;
+    }
+
+    fn choose(&mut self, index: usize) -> ActionMenuResult<T> {
+        match self.entries.get(index) {
+            Some(entry) => {
+                let value = entry.value.clone();
+                self.close();
+                ActionMenuResult::Chosen(value)
+            }
+            None => ActionMenuResult::None,
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let last = self.entries.len().saturating_sub(1);
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(last);
+        self.list_state.select(Some(next));
+    }
+
+    /// Handle an event while open
+    pub fn handle_event(&mut self, event: Event, config: &Config) -> ActionMenuResult<T> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                match config.keymap.get_action(key.code, key.modifiers) {
+                    Some(Action::MoveUp) => self.move_selection(-1),
+                    Some(Action::MoveDown) => self.move_selection(1),
+                    Some(Action::GoToTop | Action::PageUp) => self.list_state.select(Some(0)),
+                    Some(Action::GoToEnd | Action::PageDown) => self.move_selection(isize::MAX),
+                    Some(Action::Confirm) => {
+                        return self.choose(self.list_state.selected().unwrap_or(0));
+                    }
+                    Some(Action::Cancel | Action::Quit) => {
+                        self.close();
+                        return ActionMenuResult::Closed;
+                    }
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left | MouseButton::Right) => {
+                    if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                        return self.choose(index);
+                    }
+                    let inside = self
+                        .popup_area
+                        .is_some_and(|area| area.contains(Position::new(mouse.column, mouse.row)));
+                    if !inside {
+                        self.close();
+                        return ActionMenuResult::Closed;
+                    }
+                }
+                MouseEventKind::Moved => {
+                    if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                        self.list_state.select(Some(index));
+                    }
+                }
+                MouseEventKind::ScrollUp => self.move_selection(-1),
+                MouseEventKind::ScrollDown => self.move_selection(1),
+                _ => {}
+            },
+            _ => {}
+        }
+        ActionMenuResult::None
+    }
+
+    /// Render the menu over `area`
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        if !self.is_open() {
+            return;
+        }
+        let t = theme();
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Navigate | {}: Choose | {}: Close",
+            config.keymap.navigation_display(),
+            k(Action::Confirm),
+            k(Action::Cancel)
+        );
+        let key_width = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.key.as_ref())
+            .map(|key| key.chars().count())
+            .max()
+            .unwrap_or(0);
+        let label_width = self
+            .entries
+            .iter()
+            .map(|entry| entry.label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width = (label_width + key_width + LIST_HIGHLIGHT_SYMBOL.chars().count() + 8)
+            .max(self.title.chars().count() + 4)
+            .max(footer_text.chars().count() + 4) as u16;
+        // Borders, title, a blank row under it and the footer
+        let height = self.entries.len() as u16 + 6;
+
+        let Some(popup) = Popup::new()
+            .width(0)
+            .height(0)
+            .min_width(width.min(area.width))
+            .min_height(height.min(area.height))
+            .title(self.title.clone())
+            .dim_background(false)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return;
+        };
+        let content = popup.content_area;
+        self.popup_area = Some(Rect::new(
+            content.x.saturating_sub(1),
+            content.y.saturating_sub(2),
+            content.width + 2,
+            content.height + 5,
+        ));
+
+        let list_area = Rect::new(
+            content.x + 1,
+            content.y + 1,
+            content.width.saturating_sub(2),
+            content.height.saturating_sub(1),
+        );
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let key = entry.key.clone().unwrap_or_default();
+                let gap = list_area
+                    .width
+                    .saturating_sub(LIST_HIGHLIGHT_SYMBOL.chars().count() as u16)
+                    .saturating_sub((entry.label.chars().count() + key.chars().count()) as u16);
+                ListItem::new(Line::from(vec![
+                    Span::styled(entry.label.clone(), t.text_style()),
+                    Span::raw(" ".repeat(gap as usize)),
+                    Span::styled(key, t.muted_style()),
+                ]))
+            })
+            .collect();
+        frame.render_stateful_widget(
+            List::new(items)
+                .highlight_style(t.highlight_style())
+                .highlight_symbol(LIST_HIGHLIGHT_SYMBOL),
+            list_area,
+            &mut self.list_state,
+        );
+
+        self.row_regions.clear();
+        for (row, index) in (self.list_state.offset()..self.entries.len()).enumerate() {
+            if row as u16 >= list_area.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(list_area.x, list_area.y + row as u16, list_area.width, 1),
+                index,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_choose_and_close() {
+        let config = Config::default();
+        let mut menu = ActionMenu::new();
+        assert!(!menu.is_open());
+        menu.open(
+            "~/.zshrc",
+            vec![
+                MenuEntry::new("Edit", 'e'),
+                MenuEntry::new("Diff", 'd').key("t"),
+                MenuEntry::new("Reveal", 'r'),
+            ],
+        );
+        assert!(menu.is_open());
+
+        assert_eq!(
+            menu.handle_event(press(KeyCode::Down), &config),
+            ActionMenuResult::None
+        );
+        assert_eq!(
+            menu.handle_event(press(KeyCode::Enter), &config),
+            ActionMenuResult::Chosen('d')
+        );
+        assert!(!menu.is_open());
+
+        menu.open("~/.zshrc", vec![MenuEntry::new("Edit", 'e')]);
+        // Selection stays on the entries
+        menu.handle_event(press(KeyCode::Up), &config);
+        menu.handle_event(press(KeyCode::End), &config);
+        assert_eq!(menu.list_state.selected(), Some(0));
+        assert_eq!(
+            menu.handle_event(press(KeyCode::Esc), &config),
+            ActionMenuResult::Closed
+        );
+        assert!(!menu.is_open());
+    }
+}
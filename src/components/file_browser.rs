@@ -11,8 +11,9 @@ use crate::components::popup::Popup;
 use crate::config::Config;
 use crate::keymap::Action;
 use crate::styles::{theme as ui_theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::utils::layout::create_split_layout;
 use crate::utils::list_navigation::ListStateExt;
-use crate::utils::mouse::MouseRegions;
+use crate::utils::mouse::{DoubleClick, MouseRegions, ScrollbarDrag};
 use crate::utils::style::{focused_border_style, unfocused_border_style};
 use crate::utils::text_input::TextInput;
 use crate::widgets::text_input::{TextInputWidget, TextInputWidgetExt};
@@ -77,6 +78,12 @@ pub struct FileBrowser {
     pub focus: FileBrowserFocus,
     /// Mouse regions for file list items (value = entry index)
     mouse_regions: MouseRegions<usize>,
+    /// Double-clicks on list entries
+    row_clicks: DoubleClick<usize>,
+    /// Dragging and clicking the list scrollbar
+    list_scrollbar: ScrollbarDrag,
+    /// Dragging and clicking the preview scrollbar
+    preview_scrollbar: ScrollbarDrag,
     /// Stored list pane area for scroll hit-testing
     list_pane_area: Option<Rect>,
     /// Stored preview pane area for scroll hit-testing
@@ -105,6 +112,9 @@ impl FileBrowser {
             preview_scroll: 0,
             focus: FileBrowserFocus::List,
             mouse_regions: MouseRegions::new(),
+            row_clicks: DoubleClick::new(),
+            list_scrollbar: ScrollbarDrag::new(),
+            preview_scrollbar: ScrollbarDrag::new(),
             list_pane_area: None,
             preview_pane_area: None,
             path_input_area: None,
@@ -472,19 +482,40 @@ impl FileBrowser {
     fn handle_mouse_event(
         &mut self,
         mouse: crossterm::event::MouseEvent,
-        _config: &Config,
+        config: &Config,
     ) -> Result<FileBrowserResult> {
         let pos = ratatui::layout::Position::new(mouse.column, mouse.row);
 
+        let selected = self.list_state.selected().unwrap_or(0);
+        if let Some(idx) = self.list_scrollbar.handle(mouse, selected) {
+            self.list_state.select(Some(idx));
+            self.scrollbar_state = self.scrollbar_state.position(idx);
+            if idx != selected {
+                self.preview_scroll = 0;
+            }
+            self.focus = FileBrowserFocus::List;
+            return Ok(FileBrowserResult::None);
+        }
+        if let Some(scroll) = self.preview_scrollbar.handle(mouse, self.preview_scroll) {
+            self.preview_scroll = scroll;
+            self.focus = FileBrowserFocus::Preview;
+            return Ok(FileBrowserResult::None);
+        }
+
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
-                // Click on file list item
+                // Click on file list item, double-click opens or picks it
                 if let Some(&idx) = self.mouse_regions.hit_test(mouse.column, mouse.row) {
+                    if self.list_state.selected() != Some(idx) {
+                        self.preview_scroll = 0;
+                    }
                     self.list_state.select(Some(idx));
-                    self.preview_scroll = 0;
                     self.focus = FileBrowserFocus::List;
                     // Update scrollbar
                     self.scrollbar_state = self.scrollbar_state.position(idx);
+                    if self.row_clicks.click(idx) {
+                        return self.handle_selection(config);
+                    }
                     return Ok(FileBrowserResult::None);
                 }
 
@@ -512,6 +543,16 @@ impl FileBrowser {
                     }
                 }
             }
+            MouseEventKind::Moved => {
+                // Hovering an entry previews it
+                if let Some(&idx) = self.mouse_regions.hit_test(mouse.column, mouse.row) {
+                    if self.list_state.selected() != Some(idx) {
+                        self.list_state.select(Some(idx));
+                        self.scrollbar_state = self.scrollbar_state.position(idx);
+                        self.preview_scroll = 0;
+                    }
+                }
+            }
             MouseEventKind::ScrollDown => {
                 if let Some(area) = self.list_pane_area {
                     if area.contains(pos) {
@@ -627,10 +668,7 @@ impl FileBrowser {
         frame.render_text_input_widget(widget, chunks[1]);
 
         // Split list and preview horizontally
-        let list_preview_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(chunks[2]);
+        let list_preview_chunks = create_split_layout(chunks[2], &[50, 50]);
 
         // Render file list
         self.render_list(frame, list_preview_chunks[0], config);
@@ -721,12 +759,15 @@ impl FileBrowser {
 
         // Render scrollbar
         frame.render_stateful_widget(
-            Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .begin_symbol(Some("↑"))
-                .end_symbol(Some("↓")),
+            self.list_scrollbar.style(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(Some("↑"))
+                    .end_symbol(Some("↓")),
+            ),
             area,
             &mut self.scrollbar_state,
         );
+        self.list_scrollbar.set(area, self.entries.len());
     }
 
     /// Render the preview pane
@@ -739,6 +780,7 @@ impl FileBrowser {
         config: &crate::config::Config,
     ) -> Result<()> {
         let t = ui_theme();
+        self.preview_scrollbar.clear();
 
         if let Some(selected_index) = self.list_state.selected() {
             if selected_index < self.entries.len() {
@@ -762,6 +804,7 @@ impl FileBrowser {
                     area,
                     &full_path,
                     &mut self.preview_scroll,
+                    &mut self.preview_scrollbar,
                     is_focused,
                     Some("Preview"),
                     None,
@@ -1,20 +1,72 @@
-use crate::utils::{focused_border_style, unfocused_border_style};
+use crate::utils::file_window::{self, FileWindow};
+use crate::utils::{binary, image_preview, secret_mask, word_diff};
+use crate::utils::{focused_border_style, unfocused_border_style, ScrollbarDrag};
 use anyhow::Result;
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{
     Block, Borders, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Style as SyntectStyle, Theme};
-use syntect::parsing::SyntaxSet;
+use syntect::parsing::{SyntaxReference, SyntaxSet};
 use syntect::util::LinesWithEndings;
 
+/// Maximum number of bytes shown in the hex view of a binary file
+const HEX_PREVIEW_LIMIT: usize = 64 * 1024;
+
+/// Index of the last large file previewed, reused while it's unchanged on disk
+static WINDOW: Mutex<Option<FileWindow>> = Mutex::new(None);
+
+/// Whether previews show secrets instead of masking them
+static REVEAL_SECRETS: AtomicBool = AtomicBool::new(false);
+
+/// Rows (and columns) the preview block takes from its area: borders, plus
+/// padding outside the compact layout
+fn pane_inset() -> u16 {
+    2 + crate::styles::padding(Padding::uniform(1)).top * 2
+}
+
 /// Common file preview component
 pub struct FilePreview;
 
 impl FilePreview {
+    /// Whether secrets are currently shown in previews and diffs
+    #[must_use]
+    pub fn secrets_revealed() -> bool {
+        REVEAL_SECRETS.load(Ordering::Relaxed)
+    }
+
+    /// Toggle between masking and showing secrets in every preview, returning
+    /// whether they are now shown
+    pub fn toggle_secrets() -> bool {
+        !REVEAL_SECRETS.fetch_xor(true, Ordering::Relaxed)
+    }
+
+    /// Render the scrollbar of a preview scrolled to `scroll_offset` of
+    /// `max_scroll`, and record it in `drag` for mouse handling
+    fn render_scrollbar(
+        frame: &mut Frame,
+        area: Rect,
+        max_scroll: usize,
+        scroll_offset: usize,
+        drag: &mut ScrollbarDrag,
+    ) {
+        let mut scrollbar_state = ScrollbarState::new(max_scroll).position(scroll_offset);
+        let scrollbar = drag.style(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓"))
+                .track_symbol(Some("│"))
+                .thumb_symbol("█"),
+        );
+        frame.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
+        drag.set(area, max_scroll + 1);
+    }
+
     /// Render a file preview with syntax highlighting
     ///
     /// # Arguments
@@ -22,6 +74,7 @@ impl FilePreview {
     /// * `area` - The area to render the preview in
     /// * `file_path` - Path to the file to preview
     /// * `scroll_offset` - Number of lines to skip from the top
+    /// * `scrollbar` - Where the scrollbar was drawn, for dragging it
     /// * `focused` - Whether the preview pane is focused (for border color)
     /// * `title` - Optional custom title (defaults to "Preview")
     /// * `syntax_set` - Syntax definitions for highlighting
@@ -33,6 +86,7 @@ impl FilePreview {
         area: Rect,
         file_path: &PathBuf,
         scroll_offset: &mut usize,
+        scrollbar: &mut ScrollbarDrag,
         focused: bool,
         title: Option<&str>,
         content_override: Option<&str>,
@@ -40,6 +94,7 @@ impl FilePreview {
         theme: &Theme,
         config: &crate::config::Config,
     ) -> Result<()> {
+        scrollbar.clear();
         let preview_title = title.unwrap_or("Preview");
         let no_color = crate::styles::theme().theme_type == crate::styles::ThemeType::NoColor;
         let t = crate::styles::theme();
@@ -49,6 +104,50 @@ impl FilePreview {
             (unfocused_border_style(), t.border_type)
         };
 
+        // Binary files get a summary and hex view instead of garbage glyphs
+        if content_override.is_none()
+            && file_path.is_file()
+            && binary::read_prefix(file_path, binary::SNIFF_LEN)
+                .is_ok_and(|sample| binary::is_binary(&sample))
+        {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {preview_title} "))
+                .border_type(border_type)
+                .title_alignment(Alignment::Center)
+                .border_style(border_style)
+                .style(t.background_style())
+                .padding(crate::styles::padding(Padding::uniform(1)));
+            Self::render_binary(frame, area, file_path, scroll_offset, scrollbar, block);
+            return Ok(());
+        }
+
+        // Huge files only load the lines on screen plus a buffer
+        if content_override.is_none()
+            && std::fs::metadata(file_path)
+                .is_ok_and(|m| m.is_file() && m.len() > file_window::STREAM_THRESHOLD)
+        {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(border_type)
+                .title_alignment(Alignment::Center)
+                .border_style(border_style)
+                .style(t.background_style())
+                .padding(crate::styles::padding(Padding::uniform(1)));
+            Self::render_streamed(
+                frame,
+                area,
+                file_path,
+                scroll_offset,
+                scrollbar,
+                preview_title,
+                block,
+                syntax_set,
+                theme,
+            );
+            return Ok(());
+        }
+
         // Read file content or use override
         if file_path.is_file() || content_override.is_some() {
             let content_result = if let Some(content) = content_override {
@@ -58,10 +157,20 @@ impl FilePreview {
             };
 
             if let Ok(content) = content_result {
+                let masked = if Self::secrets_revealed() {
+                    content.clone()
+                } else {
+                    secret_mask::mask(&content)
+                };
+                let title = if masked == content {
+                    format!(" {preview_title} ")
+                } else {
+                    format!(" {preview_title} · secrets masked ")
+                };
+                let content = masked;
                 let total_lines = content.lines().count().max(1);
-                // borders(2) + padding(2) = 4 vertical, same horizontal
-                let visible_height = area.height.saturating_sub(4) as usize;
-                let inner_width = area.width.saturating_sub(4) as usize;
+                let visible_height = area.height.saturating_sub(pane_inset()) as usize;
+                let inner_width = area.width.saturating_sub(pane_inset()) as usize;
 
                 // Clamp scroll offset: count backwards from end to find how many
                 // content lines fit, accounting for long lines that wrap
@@ -84,9 +193,12 @@ impl FilePreview {
                 *scroll_offset = (*scroll_offset).min(max_scroll);
 
                 // Determine syntax
-                let syntax = if let Some(content_str) = content_override {
+                let is_diff = content_override.is_some_and(|content_str| {
+                    content_str.starts_with("diff --git") || content_str.starts_with("--- a/")
+                });
+                let syntax = if content_override.is_some() {
                     // If content override is provided, try to detect syntax from content or default to Diff if it looks like one
-                    if content_str.starts_with("diff --git") || content_str.starts_with("--- a/") {
+                    if is_diff {
                         syntax_set
                             .find_syntax_by_name("Diff")
                             .or_else(|| syntax_set.find_syntax_by_extension("diff"))
@@ -100,45 +212,7 @@ impl FilePreview {
                             .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
                     }
                 } else {
-                    // Standard detection logic
-                    // First check for overrides based on filename
-                    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                    if file_name.ends_with("rc")
-                        || file_name.contains("profile")
-                        || file_name == ".aliases"
-                        || file_name == ".functions"
-                    {
-                        // Assume shell for *rc files, profile, aliases, functions
-                        syntax_set
-                            .find_syntax_by_name("Bourne Again Shell (bash)")
-                            .or_else(|| syntax_set.find_syntax_by_extension("sh"))
-                            .or_else(|| syntax_set.find_syntax_for_file(file_path).unwrap_or(None))
-                            .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
-                    } else if file_name.ends_with(".conf") || file_name.ends_with(".config") {
-                        // Try to find a specific syntax, otherwise fallback to INI/Shell or just rely on extension
-                        syntax_set
-                            .find_syntax_for_file(file_path)
-                            .unwrap_or(None)
-                            .or_else(|| syntax_set.find_syntax_by_extension("ini"))
-                            .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
-                    } else if file_name.ends_with(".vim")
-                        || file_name == ".vimrc"
-                        || file_name.contains("vim")
-                    {
-                        syntax_set
-                            .find_syntax_by_extension("vim")
-                            .or_else(|| syntax_set.find_syntax_by_name("VimL"))
-                            .or_else(|| syntax_set.find_syntax_by_name("Vim Script"))
-                            .or_else(|| syntax_set.find_syntax_by_extension("lua"))
-                            .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
-                    } else {
-                        // Standard detection
-                        syntax_set
-                            .find_syntax_for_file(file_path)
-                            .unwrap_or(None)
-                            .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
-                    }
+                    Self::syntax_for_path(syntax_set, file_path)
                 };
 
                 let mut highlighter = HighlightLines::new(syntax, theme);
@@ -151,30 +225,16 @@ impl FilePreview {
 
                 // Process only visible lines
                 let mut preview_lines = Vec::new();
-                for line in lines_iter.take(visible_height) {
-                    if no_color {
-                        // No-color mode: do not emit any syntax-highlight fg/bg colors.
-                        preview_lines.push(Line::from(Span::raw(line.to_string())));
+                for (index, line) in (*scroll_offset..).zip(lines_iter.take(visible_height)) {
+                    let highlighted =
+                        Self::highlight_line(&mut highlighter, line, syntax_set, no_color);
+                    // Word-level changes, worked out for the lines on screen only
+                    let changed = if is_diff {
+                        word_diff::emphasis(&content_lines, index)
                     } else {
-                        // Highlight the line
-                        let ranges: Vec<(SyntectStyle, &str)> = highlighter
-                            .highlight_line(line, syntax_set)
-                            .unwrap_or_default();
-
-                        // Convert to Ratatui spans
-                        let spans: Vec<Span> = ranges
-                            .into_iter()
-                            .map(|(style, text)| {
-                                let fg = Color::Rgb(
-                                    style.foreground.r,
-                                    style.foreground.g,
-                                    style.foreground.b,
-                                );
-                                Span::styled(text.to_string(), Style::default().fg(fg))
-                            })
-                            .collect();
-                        preview_lines.push(Line::from(spans));
-                    }
+                        Vec::new()
+                    };
+                    preview_lines.push(Self::emphasize(highlighted, &changed));
                 }
 
                 // Create text with lines
@@ -199,27 +259,19 @@ impl FilePreview {
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
-                            .title(format!(" {preview_title} "))
+                            .title(title)
                             .border_type(border_type)
                             .title_alignment(Alignment::Center)
                             .border_style(border_style)
                             .style(t.background_style())
-                            .padding(Padding::uniform(1)),
+                            .padding(crate::styles::padding(Padding::uniform(1))),
                     )
                     .wrap(Wrap { trim: false }); // Don't trim whitespace
 
                 frame.render_widget(preview, area);
 
                 // === SCROLLBAR IMPLEMENTATION ===
-                let mut scrollbar_state = ScrollbarState::new(max_scroll).position(*scroll_offset);
-
-                let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                    .begin_symbol(Some("↑"))
-                    .end_symbol(Some("↓"))
-                    .track_symbol(Some("│"))
-                    .thumb_symbol("█");
-
-                frame.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
+                Self::render_scrollbar(frame, area, max_scroll, *scroll_offset, scrollbar);
             } else {
                 let error_text = format!("Unable to read file: {file_path:?}");
                 let preview = Paragraph::new(error_text).block(
@@ -230,7 +282,7 @@ impl FilePreview {
                         .title_alignment(Alignment::Center)
                         .border_style(border_style)
                         .style(t.background_style())
-                        .padding(Padding::uniform(1)),
+                        .padding(crate::styles::padding(Padding::uniform(1))),
                 );
                 frame.render_widget(preview, area);
             }
@@ -246,7 +298,7 @@ impl FilePreview {
             preview_lines.push(Line::from(""));
 
             let mut total_entries = 0;
-            let visible_height = area.height.saturating_sub(4) as usize;
+            let visible_height = area.height.saturating_sub(pane_inset()) as usize;
             let mut dir_max_scroll = 0;
 
             match std::fs::read_dir(file_path) {
@@ -317,19 +369,12 @@ impl FilePreview {
                     .title_alignment(Alignment::Center)
                     .border_style(border_style)
                     .style(t.background_style())
-                    .padding(Padding::uniform(1)),
+                    .padding(crate::styles::padding(Padding::uniform(1))),
             );
             frame.render_widget(preview, area);
 
             if total_entries > visible_height {
-                let mut scrollbar_state =
-                    ScrollbarState::new(dir_max_scroll).position(*scroll_offset);
-                let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                    .begin_symbol(Some("↑"))
-                    .end_symbol(Some("↓"))
-                    .track_symbol(Some("│"))
-                    .thumb_symbol("█");
-                frame.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
+                Self::render_scrollbar(frame, area, dir_max_scroll, *scroll_offset, scrollbar);
             }
         } else {
             let path_text = format!("Path: {file_path:?}");
@@ -341,11 +386,314 @@ impl FilePreview {
                     .title_alignment(Alignment::Center)
                     .border_style(border_style)
                     .style(t.background_style())
-                    .padding(Padding::uniform(1)),
+                    .padding(crate::styles::padding(Padding::uniform(1))),
             );
             frame.render_widget(preview, area);
         }
 
         Ok(())
     }
+
+    /// Pick a syntax for a file, with overrides for common dotfile names
+    fn syntax_for_path<'a>(syntax_set: &'a SyntaxSet, file_path: &Path) -> &'a SyntaxReference {
+        // Standard detection logic
+        // First check for overrides based on filename
+        let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        if file_name.ends_with("rc")
+            || file_name.contains("profile")
+            || file_name == ".aliases"
+            || file_name == ".functions"
+        {
+            // Assume shell for *rc files, profile, aliases, functions
+            syntax_set
+                .find_syntax_by_name("Bourne Again Shell (bash)")
+                .or_else(|| syntax_set.find_syntax_by_extension("sh"))
+                .or_else(|| syntax_set.find_syntax_for_file(file_path).unwrap_or(None))
+                .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+        } else if file_name.ends_with(".conf") || file_name.ends_with(".config") {
+            // Try to find a specific syntax, otherwise fallback to INI/Shell or just rely on extension
+            syntax_set
+                .find_syntax_for_file(file_path)
+                .unwrap_or(None)
+                .or_else(|| syntax_set.find_syntax_by_extension("ini"))
+                .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+        } else if file_name.ends_with(".vim") || file_name == ".vimrc" || file_name.contains("vim")
+        {
+            syntax_set
+                .find_syntax_by_extension("vim")
+                .or_else(|| syntax_set.find_syntax_by_name("VimL"))
+                .or_else(|| syntax_set.find_syntax_by_name("Vim Script"))
+                .or_else(|| syntax_set.find_syntax_by_extension("lua"))
+                .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+        } else {
+            // Standard detection
+            syntax_set
+                .find_syntax_for_file(file_path)
+                .unwrap_or(None)
+                .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+        }
+    }
+
+    /// Highlight one line, or emit it uncolored in no-color mode
+    fn highlight_line(
+        highlighter: &mut HighlightLines,
+        line: &str,
+        syntax_set: &SyntaxSet,
+        no_color: bool,
+    ) -> Line<'static> {
+        if no_color {
+            // No-color mode: do not emit any syntax-highlight fg/bg colors.
+            return Line::from(Span::raw(line.to_string()));
+        }
+
+        let ranges: Vec<(SyntectStyle, &str)> = highlighter
+            .highlight_line(line, syntax_set)
+            .unwrap_or_default();
+
+        // Convert to Ratatui spans
+        let spans: Vec<Span> = ranges
+            .into_iter()
+            .map(|(style, text)| {
+                let fg = Color::Rgb(style.foreground.r, style.foreground.g, style.foreground.b);
+                Span::styled(text.to_string(), Style::default().fg(fg))
+            })
+            .collect();
+        Line::from(spans)
+    }
+
+    /// Reverse the colors of the bytes of `line` within `ranges`
+    fn emphasize(line: Line<'static>, ranges: &[std::ops::Range<usize>]) -> Line<'static> {
+        if ranges.is_empty() {
+            return line;
+        }
+        let mut spans = Vec::new();
+        let mut offset = 0;
+        for span in line.spans {
+            let text = span.content.as_ref();
+            let end = offset + text.len();
+            // Split the span at every range boundary inside it
+            let mut cuts = vec![offset, end];
+            for range in ranges {
+                cuts.extend([range.start, range.end].into_iter().filter(|&cut| {
+                    cut > offset && cut < end && text.is_char_boundary(cut - offset)
+                }));
+            }
+            cuts.sort_unstable();
+            cuts.dedup();
+            for piece in cuts.windows(2) {
+                let changed = ranges
+                    .iter()
+                    .any(|range| range.start <= piece[0] && piece[1] <= range.end);
+                let style = if changed {
+                    span.style.add_modifier(Modifier::REVERSED)
+                } else {
+                    span.style
+                };
+                spans.push(Span::styled(
+                    text[piece[0] - offset..piece[1] - offset].to_string(),
+                    style,
+                ));
+            }
+            offset = end;
+        }
+        Line::from(spans)
+    }
+
+    /// Render a large file from a line-indexed window, with its size and
+    /// line count in the title
+    #[allow(clippy::too_many_arguments)]
+    fn render_streamed(
+        frame: &mut Frame,
+        area: Rect,
+        file_path: &Path,
+        scroll_offset: &mut usize,
+        scrollbar: &mut ScrollbarDrag,
+        preview_title: &str,
+        block: Block,
+        syntax_set: &SyntaxSet,
+        theme: &Theme,
+    ) {
+        let t = crate::styles::theme();
+        let no_color = t.theme_type == crate::styles::ThemeType::NoColor;
+        let mut cached = WINDOW
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if !cached.as_ref().is_some_and(|w| w.is_current(file_path)) {
+            *cached = FileWindow::open(file_path).ok();
+        }
+        let Some(window) = cached.as_mut() else {
+            let preview = Paragraph::new(format!("Unable to read file: {file_path:?}"))
+                .block(block.title(format!(" {preview_title} ")));
+            frame.render_widget(preview, area);
+            return;
+        };
+
+        let total_lines = window.total_lines().max(1);
+        let visible_height = area.height.saturating_sub(pane_inset()) as usize;
+        let max_scroll = total_lines.saturating_sub(visible_height);
+        *scroll_offset = (*scroll_offset).min(max_scroll);
+
+        let title = format!(
+            " {preview_title} · {} · {} lines ",
+            binary::format_size(window.size()),
+            window.total_lines()
+        );
+
+        let syntax = Self::syntax_for_path(syntax_set, file_path);
+        let mut highlighter = HighlightLines::new(syntax, theme);
+        let preview_lines: Vec<Line> = match window.lines(*scroll_offset, visible_height) {
+            Ok(lines) => lines
+                .iter()
+                .map(|line| {
+                    let line = if Self::secrets_revealed() {
+                        line.clone()
+                    } else {
+                        secret_mask::mask_line(line)
+                    };
+                    Self::highlight_line(
+                        &mut highlighter,
+                        &format!("{line}\n"),
+                        syntax_set,
+                        no_color,
+                    )
+                })
+                .collect(),
+            Err(e) => vec![Line::from(Span::styled(
+                format!("Error reading file: {e}"),
+                t.error_style(),
+            ))],
+        };
+
+        frame.render_widget(
+            Paragraph::new(Text::from(preview_lines)).block(block.title(title)),
+            area,
+        );
+
+        Self::render_scrollbar(frame, area, max_scroll, *scroll_offset, scrollbar);
+    }
+
+    /// Render an image inline when the terminal supports a graphics protocol,
+    /// below a summary of its type, dimensions and size
+    fn render_image(
+        frame: &mut Frame,
+        area: Rect,
+        file_path: &Path,
+        bytes: &[u8],
+        size: u64,
+        block: Block,
+    ) {
+        let t = crate::styles::theme();
+        let mut lines = vec![
+            Line::from(Span::styled("Image", t.title_style())),
+            Line::from(vec![
+                Span::styled("Type: ", t.muted_style()),
+                Span::styled(binary::detect_type(bytes), t.text_style()),
+            ]),
+        ];
+        if let Some((width, height)) = image_preview::dimensions(file_path) {
+            lines.push(Line::from(vec![
+                Span::styled("Dimensions: ", t.muted_style()),
+                Span::styled(format!("{width} × {height}"), t.text_style()),
+            ]));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Size: ", t.muted_style()),
+            Span::styled(binary::describe(bytes, size), t.text_style()),
+        ]));
+        lines.push(Line::from(""));
+
+        let inner = block.inner(area);
+        let header_height = (lines.len() as u16).min(inner.height);
+        let image_area = Rect {
+            y: inner.y + header_height,
+            height: inner.height - header_height,
+            ..inner
+        };
+
+        frame.render_widget(block, area);
+        let rendered = image_preview::render(frame, image_area, file_path);
+        if !rendered && !image_preview::is_supported() {
+            lines.push(Line::from(Span::styled(
+                "Inline preview needs a terminal with kitty, iTerm2 or sixel graphics",
+                t.muted_style(),
+            )));
+        }
+        let text_height = if rendered {
+            header_height
+        } else {
+            inner.height
+        };
+        frame.render_widget(
+            Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false }),
+            Rect {
+                height: text_height,
+                ..inner
+            },
+        );
+    }
+
+    /// Render a summary header (type, size) followed by a scrollable hex dump
+    fn render_binary(
+        frame: &mut Frame,
+        area: Rect,
+        file_path: &Path,
+        scroll_offset: &mut usize,
+        scrollbar: &mut ScrollbarDrag,
+        block: Block,
+    ) {
+        let t = crate::styles::theme();
+        let bytes = match binary::read_prefix(file_path, HEX_PREVIEW_LIMIT) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let preview = Paragraph::new(format!("Unable to read file: {e}")).block(block);
+                frame.render_widget(preview, area);
+                return;
+            }
+        };
+        let size = std::fs::metadata(file_path).map_or(bytes.len() as u64, |m| m.len());
+
+        if binary::is_image(&bytes) {
+            Self::render_image(frame, area, file_path, &bytes, size, block);
+            return;
+        }
+
+        let mut header = vec![
+            Line::from(Span::styled("Binary file", t.title_style())),
+            Line::from(vec![
+                Span::styled("Type: ", t.muted_style()),
+                Span::styled(binary::detect_type(&bytes), t.text_style()),
+            ]),
+            Line::from(vec![
+                Span::styled("Size: ", t.muted_style()),
+                Span::styled(binary::describe(&bytes, size), t.text_style()),
+            ]),
+            Line::from(""),
+        ];
+
+        let hex_lines = binary::hex_dump(&bytes);
+        let visible_height =
+            (area.height.saturating_sub(pane_inset()) as usize).saturating_sub(header.len());
+        let max_scroll = hex_lines.len().saturating_sub(visible_height);
+        *scroll_offset = (*scroll_offset).min(max_scroll);
+
+        let end = (*scroll_offset + visible_height).min(hex_lines.len());
+        header.extend(
+            hex_lines[*scroll_offset..end]
+                .iter()
+                .map(|line| Line::from(Span::styled(line.clone(), t.text_style()))),
+        );
+        if end == hex_lines.len() && size > bytes.len() as u64 {
+            header.push(Line::from(Span::styled(
+                format!("... (showing first {} KB)", HEX_PREVIEW_LIMIT / 1024),
+                t.muted_style(),
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(Text::from(header)).block(block), area);
+
+        if max_scroll > 0 {
+            Self::render_scrollbar(frame, area, max_scroll, *scroll_offset, scrollbar);
+        }
+    }
 }
@@ -0,0 +1,541 @@
+//! File timeline component.
+//!
+//! A modal "time machine" for one tracked file: every version of it in the
+//! git history with its date and commit message, a diff between any two
+//! versions, and restoring an old version into the repository (which takes
+//! effect through the symlink at once and is shared on the next sync).
+
+use crate::components::file_preview::FilePreview;
+use crate::components::popup::Popup;
+use crate::config::Config;
+use crate::git::{short_id, GitManager};
+use crate::keymap::Action;
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::utils::mouse::MouseRegions;
+use crate::utils::reconcile::unified_diff;
+use crate::utils::secret_mask;
+use crate::utils::style::{focused_border_style, unfocused_border_style};
+use crate::utils::text::format_time_ago;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEventKind};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+use std::path::{Path, PathBuf};
+
+/// Result of file timeline interaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileTimelineResult {
+    /// No action taken
+    None,
+    /// The timeline was closed
+    Closed,
+    /// A version was restored; the message describes it
+    Restored(String),
+    /// Restoring failed; the message says why
+    Failed(String),
+    /// The user asked to show or mask secrets
+    ToggleSecrets,
+}
+
+/// One point on the timeline
+#[derive(Debug, Clone)]
+struct TimelineEntry {
+    /// Short commit id, `None` for the file as it is now
+    commit: Option<String>,
+    time: Option<DateTime<Utc>>,
+    summary: String,
+    /// Content, `None` where the file was deleted
+    content: Option<Vec<u8>>,
+}
+
+/// File timeline component state
+#[derive(Debug, Default)]
+pub struct FileTimeline {
+    is_open: bool,
+    /// Path shown in the title (`~/.zshrc`)
+    display_path: String,
+    /// The repository
+    repo_path: PathBuf,
+    /// Path in the repository (`common/.zshrc`)
+    repo_relative: String,
+    /// The file in the repository
+    file_path: PathBuf,
+    /// The file as it is now, then its committed versions, newest first
+    entries: Vec<TimelineEntry>,
+    list_state: ListState,
+    /// Version the selected one is compared with, instead of the one before it
+    marked: Option<usize>,
+    /// Show the content of the selected version instead of a diff
+    show_content: bool,
+    scroll: u16,
+    /// Whether the restore confirmation is showing
+    confirm_restore: bool,
+    /// Clickable version rows (value = entry index)
+    row_regions: MouseRegions<usize>,
+    /// Version list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+}
+
+impl FileTimeline {
+    /// Create a closed timeline
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the timeline is showing
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Open the timeline of `file_path`, a file in the repository at
+    /// `repo_path`, titled with `display_path`
+    pub fn open(&mut self, repo_path: &Path, file_path: &Path, display_path: &str) -> Result<()> {
+        let repo_relative = file_path
+            .strip_prefix(repo_path)
+            .context("File is not in the repository")?
+            .to_string_lossy()
+            .to_string();
+        let git_mgr = GitManager::open_or_init(repo_path)?;
+        let versions = git_mgr.file_history(&repo_relative)?;
+
+        let current = std::fs::read(file_path).ok();
+        let summary = if versions.first().and_then(|v| v.content.as_ref()) == current.as_ref() {
+            "Current (synced)"
+        } else {
+            "Current (changes not synced)"
+        };
+        let mut entries = vec![TimelineEntry {
+            commit: None,
+            time: None,
+            summary: summary.to_string(),
+            content: current,
+        }];
+        entries.extend(versions.into_iter().map(|version| TimelineEntry {
+            commit: Some(short_id(version.commit)),
+            time: DateTime::from_timestamp(version.time, 0),
+            summary: version.summary,
+            content: version.content,
+        }));
+
+        *self = Self {
+            is_open: true,
+            display_path: display_path.to_string(),
+            repo_path: repo_path.to_path_buf(),
+            repo_relative,
+            file_path: file_path.to_path_buf(),
+            entries,
+            ..Self::default()
+        };
+        // Start on the newest commit, showing what it changed
+        self.list_state
+            .select(Some(if self.entries.len() > 1 { 1 } else { 0 }));
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.is_open = false;
+        self.entries.clear();
+    }
+
+    fn selected(&self) -> usize {
+        self.list_state.selected().unwrap_or(0)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.selected();
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        if next != current {
+            self.scroll = 0;
+        }
+        self.list_state.select(Some(next));
+    }
+
+    /// The two versions the diff compares: the marked one (or the version
+    /// before the selected one) and the selected one
+    fn compared(&self) -> (Option<usize>, usize) {
+        let selected = self.selected();
+        match self.marked {
+            Some(marked) if marked != selected => (Some(marked), selected),
+            _ => (
+                Some(selected + 1).filter(|i| *i < self.entries.len()),
+                selected,
+            ),
+        }
+    }
+
+    fn label(&self, index: usize) -> String {
+        self.entries[index]
+            .commit
+            .clone()
+            .unwrap_or_else(|| "current".to_string())
+    }
+
+    /// Write the selected version to the file in the repository
+    fn restore(&mut self) -> Result<String> {
+        let index = self.selected();
+        let entry = &self.entries[index];
+        let content = entry
+            .content
+            .as_ref()
+            .context("The file doesn't exist in that version")?;
+        std::fs::write(&self.file_path, content)
+            .with_context(|| format!("Failed to write {}", self.file_path.display()))?;
+        let message = format!(
+            "Restored {} to {}. Sync to share it.",
+            self.display_path,
+            self.label(index)
+        );
+        // Reload so the current version shows what was restored
+        let (repo_path, file_path, display_path) = (
+            self.repo_path.clone(),
+            self.file_path.clone(),
+            self.display_path.clone(),
+        );
+        self.open(&repo_path, &file_path, &display_path)?;
+        Ok(message)
+    }
+
+    /// Handle an event while open
+    pub fn handle_event(&mut self, event: Event, config: &Config) -> FileTimelineResult {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(action) = config.keymap.get_action(key.code, key.modifiers) else {
+                    return FileTimelineResult::None;
+                };
+                if self.confirm_restore {
+                    match action {
+                        Action::Confirm | Action::Yes => {
+                            self.confirm_restore = false;
+                            return match self.restore() {
+                                Ok(message) => FileTimelineResult::Restored(message),
+                                Err(e) => {
+                                    FileTimelineResult::Failed(format!("Restore failed: {e}"))
+                                }
+                            };
+                        }
+                        Action::Cancel | Action::No | Action::Quit => self.confirm_restore = false,
+                        _ => {}
+                    }
+                    return FileTimelineResult::None;
+                }
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        self.close();
+                        return FileTimelineResult::Closed;
+                    }
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::GoToTop => self.move_selection(isize::MIN / 2),
+                    Action::GoToEnd => self.move_selection(isize::MAX / 2),
+                    Action::ToggleSelect => {
+                        let selected = self.selected();
+                        self.marked = (self.marked != Some(selected)).then_some(selected);
+                        self.scroll = 0;
+                    }
+                    Action::NextTab | Action::PrevTab => {
+                        self.show_content = !self.show_content;
+                        self.scroll = 0;
+                    }
+                    Action::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+                    Action::ScrollDown => self.scroll = self.scroll.saturating_add(1),
+                    Action::Confirm => {
+                        let entry = &self.entries[self.selected()];
+                        if entry.commit.is_some() && entry.content.is_some() {
+                            self.confirm_restore = true;
+                        }
+                    }
+                    Action::RevealSecrets => return FileTimelineResult::ToggleSecrets,
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => {
+                let pos = Position::new(mouse.column, mouse.row);
+                let over_list = self.list_area.is_some_and(|a| a.contains(pos));
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                            if self.selected() != index {
+                                self.scroll = 0;
+                            }
+                            self.list_state.select(Some(index));
+                        }
+                    }
+                    MouseEventKind::ScrollUp if over_list => self.move_selection(-3),
+                    MouseEventKind::ScrollDown if over_list => self.move_selection(3),
+                    MouseEventKind::ScrollUp => self.scroll = self.scroll.saturating_sub(3),
+                    MouseEventKind::ScrollDown => self.scroll = self.scroll.saturating_add(3),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        FileTimelineResult::None
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        self.list_area = Some(area);
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let mark = if self.marked == Some(index) {
+                    "● "
+                } else {
+                    "  "
+                };
+                let mut spans = vec![Span::styled(mark, Style::default().fg(t.warning))];
+                if let (Some(commit), Some(time)) = (&entry.commit, entry.time) {
+                    spans.push(Span::styled(
+                        time.with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M  ")
+                            .to_string(),
+                        t.muted_style(),
+                    ));
+                    spans.push(Span::styled(format!("{commit}  "), t.emphasis_style()));
+                }
+                let summary = if entry.content.is_none() && entry.commit.is_some() {
+                    format!("{} (deleted)", entry.summary)
+                } else {
+                    entry.summary.clone()
+                };
+                spans.push(Span::styled(summary, t.text_style()));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Versions ({}) ", self.entries.len() - 1))
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for (row, index) in (self.list_state.offset()..self.entries.len()).enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                index,
+            );
+        }
+    }
+
+    fn mask(line: &str) -> String {
+        if FilePreview::secrets_revealed() {
+            line.to_string()
+        } else {
+            secret_mask::mask_line(line)
+        }
+    }
+
+    /// Title and lines of the right pane
+    fn detail(&self) -> (String, Vec<Line<'static>>) {
+        let t = theme();
+        let selected = self.selected();
+        let Some(entry) = self.entries.get(selected) else {
+            return (String::new(), Vec::new());
+        };
+
+        if self.show_content {
+            let lines = match &entry.content {
+                None => vec![Line::from(Span::styled(
+                    "The file doesn't exist in this version.",
+                    t.muted_style(),
+                ))],
+                Some(content) if content.contains(&0) => {
+                    vec![Line::from(Span::styled("(binary file)", t.muted_style()))]
+                }
+                Some(content) => String::from_utf8_lossy(content)
+                    .lines()
+                    .map(|line| Line::from(Span::styled(Self::mask(line), t.text_style())))
+                    .collect(),
+            };
+            return (format!(" Content at {} ", self.label(selected)), lines);
+        }
+
+        let (old, new) = self.compared();
+        let Some(old) = old else {
+            return (
+                " Diff ".to_string(),
+                vec![Line::from(Span::styled(
+                    "The first version of the file. Press Tab to see its content.",
+                    t.muted_style(),
+                ))],
+            );
+        };
+        let title = format!(" Diff {} → {} ", self.label(old), self.label(new));
+        let diff = unified_diff(
+            &self.repo_relative,
+            self.entries[old].content.as_deref(),
+            self.entries[new].content.as_deref(),
+        );
+        let mut lines = Vec::new();
+        // Skip the file header lines, the path is in the title
+        for line in diff.lines().skip_while(|l| !l.starts_with("@@")) {
+            let style = if line.starts_with('+') {
+                Style::default().fg(t.success)
+            } else if line.starts_with('-') {
+                Style::default().fg(t.error)
+            } else if line.starts_with("@@") {
+                t.muted_style()
+            } else {
+                t.text_style()
+            };
+            lines.push(Line::from(Span::styled(Self::mask(line), style)));
+        }
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No text differences.",
+                t.muted_style(),
+            )));
+        }
+        (title, lines)
+    }
+
+    /// Render the timeline over `area`
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        if !self.is_open {
+            return;
+        }
+        let t = theme();
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = if self.confirm_restore {
+            format!(
+                "{}: Restore | {}: Cancel",
+                k(Action::Confirm),
+                k(Action::Cancel)
+            )
+        } else {
+            format!(
+                "{}: Navigate | {}: Mark to Compare | {}: {} | {}/{}: Scroll | {}: Restore | {}: Close",
+                config.keymap.navigation_display(),
+                k(Action::ToggleSelect),
+                k(Action::NextTab),
+                if self.show_content { "Diff" } else { "Content" },
+                k(Action::ScrollUp),
+                k(Action::ScrollDown),
+                k(Action::Confirm),
+                k(Action::Cancel),
+            )
+        };
+        let Some(popup) = Popup::new()
+            .width(90)
+            .height(85)
+            .min_height(16)
+            .min_width(70)
+            .title(format!("Timeline: {}", self.display_path))
+            .dim_background(true)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(popup.content_area);
+        self.render_list(frame, chunks[0]);
+
+        let (title, mut lines) = self.detail();
+        if self.confirm_restore {
+            let selected = self.selected();
+            lines = vec![
+                Line::from(Span::styled(
+                    format!("Restore {} to {}?", self.display_path, self.label(selected)),
+                    t.title_style(),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "The file is overwritten with that version and shows up as a change to sync. \
+                    Edits that haven't been synced yet are lost.",
+                    Style::default().fg(t.warning),
+                )),
+            ];
+        }
+        let when = self
+            .entries
+            .get(self.selected())
+            .and_then(|entry| entry.time)
+            .map(|time| format!("{} ", format_time_ago(time, Utc::now())))
+            .unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(title)
+                        .title_bottom(Line::from(when).alignment(Alignment::Right))
+                        .border_type(t.border_type(false))
+                        .border_style(unfocused_border_style())
+                        .padding(crate::styles::padding(Padding::horizontal(1)))
+                        .style(t.background_style()),
+                ),
+            chunks[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_compare_and_restore_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        let git_mgr = GitManager::open_or_init(repo).unwrap();
+        let file = repo.join("common/.zshrc");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "one\n").unwrap();
+        git_mgr.commit_all("First").unwrap();
+        std::fs::write(&file, "two\n").unwrap();
+        git_mgr.commit_all("Second").unwrap();
+        std::fs::write(&file, "three\n").unwrap();
+
+        let mut timeline = FileTimeline::new();
+        timeline.open(repo, &file, "~/.zshrc").unwrap();
+        assert_eq!(timeline.entries.len(), 3);
+        assert_eq!(timeline.entries[0].summary, "Current (changes not synced)");
+
+        // The newest commit is compared with the one before it, or the marked one
+        assert_eq!(timeline.compared(), (Some(2), 1));
+        timeline.marked = Some(0);
+        assert_eq!(timeline.compared(), (Some(0), 1));
+        timeline.move_selection(1);
+        assert_eq!(timeline.compared(), (Some(0), 2));
+
+        assert!(timeline.restore().unwrap().contains("~/.zshrc"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "one\n");
+        assert_eq!(timeline.entries[0].summary, "Current (changes not synced)");
+        assert!(timeline.is_open());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::styles::theme;
+use crate::styles::{is_compact, theme};
 use anyhow::Result;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Paragraph};
@@ -38,7 +38,11 @@ impl Footer {
         }
 
         let footer_block = Block::default()
-            .borders(Borders::TOP)
+            .borders(if is_compact() {
+                Borders::NONE
+            } else {
+                Borders::TOP
+            })
             .border_style(t.border_focused_style())
             .border_type(t.border_type(false))
             .style(t.background_style());
@@ -1,4 +1,4 @@
-use crate::styles::theme;
+use crate::styles::{is_compact, theme};
 use crate::widgets::DotstateLogo;
 use anyhow::Result;
 use ratatui::prelude::*;
@@ -26,6 +26,30 @@ impl Header {
     ) -> Result<u16, anyhow::Error> {
         let app_version = env!("CARGO_PKG_VERSION");
         let t = theme();
+
+        // Compact layout: the title and the description's first line, on one line
+        if is_compact() {
+            let version = format!(" v{app_version}");
+            let mut spans = vec![Span::styled(format!(" {title}"), t.title_style())];
+            if let Some(line) = description.lines().next().filter(|l| !l.is_empty()) {
+                spans.push(Span::styled(format!(" · {line}"), t.muted_style()));
+            }
+            let header = Paragraph::new(Line::from(spans)).style(t.background_style());
+            let [text_area, version_area] = Layout::horizontal([
+                Constraint::Min(0),
+                Constraint::Length(version.chars().count() as u16 + 1),
+            ])
+            .areas(area);
+            frame.render_widget(header, text_area);
+            frame.render_widget(
+                Paragraph::new(version)
+                    .style(t.muted_style())
+                    .alignment(Alignment::Right),
+                version_area,
+            );
+            return Ok(area.height);
+        }
+
         // Main header block with theme border, padding, and title
         let header_block = Block::default()
             .borders(Borders::ALL)
@@ -44,7 +44,9 @@ impl MessageBox {
             .title(format!(" {final_title} "))
             .title_alignment(Alignment::Center)
             .border_style(Style::default().fg(final_color))
-            .padding(ratatui::widgets::Padding::new(2, 2, 2, 2));
+            .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                2, 2, 2, 2,
+            )));
 
         let message_para = Paragraph::new(message)
             .style(t.text_style())
@@ -1,8 +1,10 @@
 // Component-based architecture for dotstate TUI
 
+pub mod action_menu;
 pub mod component;
 pub mod file_browser;
 pub mod file_preview;
+pub mod file_timeline;
 pub mod footer;
 pub mod header;
 pub mod help_overlay;
@@ -10,9 +12,13 @@ pub mod message;
 pub mod message_box;
 pub mod popup;
 pub mod profile_selection_popup;
+pub mod remote_banner;
 
+pub use action_menu::{ActionMenu, ActionMenuResult, MenuEntry};
 pub use component::{Component, ComponentAction};
 pub use file_browser::{FileBrowser, FileBrowserFocus, FileBrowserResult};
+pub use file_timeline::{FileTimeline, FileTimelineResult};
 pub use message::MessageComponent;
 pub use popup::{Popup, PopupRenderResult};
 pub use profile_selection_popup::{ProfileSelectionPopup, ProfileSelectionResult};
+pub use remote_banner::RemoteBanner;
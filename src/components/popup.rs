@@ -237,6 +237,24 @@ impl Default for Popup<'_> {
     }
 }
 
+/// Render a centered "terminal too small" message into `area`.
+fn render_too_small(frame: &mut Frame, area: Rect, min_width: u16, min_height: u16) {
+    let t = theme();
+
+    // Dim the background so the message stands out.
+    frame.render_widget(Block::default().style(t.dim_style()), area);
+
+    let msg = format!(
+        "Terminal too small\n\nNeeds at least {min_width}×{min_height}\nCurrent: {}×{}",
+        area.width, area.height
+    );
+    let para = Paragraph::new(msg)
+        .alignment(Alignment::Center)
+        .style(t.text_style().bg(t.background))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,21 +328,3 @@ mod tests {
         );
     }
 }
-
-/// Render a centered "terminal too small" message into `area`.
-fn render_too_small(frame: &mut Frame, area: Rect, min_width: u16, min_height: u16) {
-    let t = theme();
-
-    // Dim the background so the message stands out.
-    frame.render_widget(Block::default().style(t.dim_style()), area);
-
-    let msg = format!(
-        "Terminal too small\n\nNeeds at least {min_width}×{min_height}\nCurrent: {}×{}",
-        area.width, area.height
-    );
-    let para = Paragraph::new(msg)
-        .alignment(Alignment::Center)
-        .style(t.text_style().bg(t.background))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(para, area);
-}
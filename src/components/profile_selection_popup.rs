@@ -8,7 +8,8 @@ use crate::config::Config;
 use crate::keymap::Action;
 use crate::styles::theme;
 use crate::utils::{
-    focused_border_style, unfocused_border_style, MouseRegions, ProfileManifest, TextInput,
+    focused_border_style, unfocused_border_style, DoubleClick, MouseRegions, ProfileManifest,
+    TextInput,
 };
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
@@ -39,6 +40,8 @@ pub struct ProfileSelectionPopup {
     visible: bool,
     /// Clickable regions for list items
     mouse_regions: MouseRegions<usize>,
+    /// Double-clicks on list items choose them
+    row_clicks: DoubleClick<usize>,
     /// Stored list area for scroll hit-testing
     list_area: Option<Rect>,
 }
@@ -62,6 +65,7 @@ impl ProfileSelectionPopup {
             create_input: TextInput::new(),
             visible: false,
             mouse_regions: MouseRegions::new(),
+            row_clicks: DoubleClick::new(),
             list_area: None,
         }
     }
@@ -232,11 +236,11 @@ impl ProfileSelectionPopup {
             MouseEventKind::Down(MouseButton::Left) => {
                 if let Some(&idx) = self.mouse_regions.hit_test(mouse.column, mouse.row) {
                     self.list_state.select(Some(idx));
-                    if idx < self.profiles.len() {
+                    if self.row_clicks.click(idx) && idx < self.profiles.len() {
                         let name = self.profiles[idx].name.clone();
                         return Some(ProfileSelectionResult::SelectExisting(name));
                     }
-                    // Clicked "Create New" — just select it, don't act
+                    // A single click, or "Create New", only selects
                 }
             }
             MouseEventKind::ScrollUp => {
@@ -460,7 +464,7 @@ impl ProfileSelectionPopup {
                     .title(" Profile Details ")
                     .border_type(t.border_type(false))
                     .border_style(unfocused_border_style())
-                    .padding(Padding::new(1, 1, 1, 1)),
+                    .padding(crate::styles::padding(Padding::new(1, 1, 1, 1))),
             )
             .wrap(Wrap { trim: true });
 
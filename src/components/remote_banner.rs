@@ -0,0 +1,122 @@
+use crate::icons::Icons;
+use crate::styles::theme;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+/// Banner shown above editing screens when the remote has commits that
+/// haven't been pulled, so edits don't diverge from another machine's.
+#[derive(Debug, Default)]
+pub struct RemoteBanner {
+    /// Commits on the remote that aren't local
+    behind: usize,
+    /// Whether a pull started from the banner is running
+    pulling: bool,
+    /// Where the banner was last drawn (for click hit-testing)
+    area: Option<Rect>,
+}
+
+impl RemoteBanner {
+    /// Height the banner takes when shown
+    pub const HEIGHT: u16 = 3;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update from the latest remote status.
+    pub fn set_status(&mut self, behind: usize, pulling: bool) {
+        self.behind = behind;
+        self.pulling = pulling;
+    }
+
+    /// Whether the banner should be shown
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        self.behind > 0 || self.pulling
+    }
+
+    /// Whether "pull now" can be triggered
+    #[must_use]
+    pub fn can_pull(&self) -> bool {
+        self.behind > 0 && !self.pulling
+    }
+
+    /// Whether a click at the given position hits the banner
+    #[must_use]
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        self.area
+            .is_some_and(|area| area.contains(Position::new(column, row)))
+    }
+
+    /// Render the banner at the top of `area` when visible and return the
+    /// area left for the screen's content.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, pull_key: &str, icons: &Icons) -> Rect {
+        if !self.is_visible() || area.height <= Self::HEIGHT {
+            self.area = None;
+            return area;
+        }
+        let t = theme();
+        let banner = Rect::new(area.x, area.y, area.width, Self::HEIGHT);
+        let rest = Rect::new(
+            area.x,
+            area.y + Self::HEIGHT,
+            area.width,
+            area.height - Self::HEIGHT,
+        );
+
+        let warning = Style::default().fg(t.warning);
+        let line = if self.pulling {
+            Line::from(Span::styled(
+                format!("{} Pulling from remote...", icons.sync()),
+                warning,
+            ))
+        } else {
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{} The remote has {} new commit(s). Pull before editing to avoid conflicts. ",
+                        icons.warning(),
+                        self.behind
+                    ),
+                    warning,
+                ),
+                Span::styled(format!("[{pull_key}: Pull now]"), t.emphasis_style()),
+            ])
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(t.border_type(false))
+            .border_style(warning)
+            .style(t.background_style());
+        frame.render_widget(
+            Paragraph::new(line)
+                .alignment(Alignment::Center)
+                .block(block),
+            banner,
+        );
+        self.area = Some(banner);
+        rest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visibility() {
+        let mut banner = RemoteBanner::new();
+        assert!(!banner.is_visible());
+
+        banner.set_status(2, false);
+        assert!(banner.is_visible());
+        assert!(banner.can_pull());
+
+        // Stays up while pulling, but can't start a second pull
+        banner.set_status(2, true);
+        assert!(banner.is_visible());
+        assert!(!banner.can_pull());
+    }
+}
@@ -44,6 +44,10 @@ pub const DEFAULT_DOTFILES: &[DotfileCandidate] = &[
         path: ".zshenv",
         description: "Zsh environment configuration executed for all shell invocations.",
     },
+    DotfileCandidate {
+        path: ".config/fish",
+        description: "Fish shell configuration directory: config.fish, functions, and completions.",
+    },
     DotfileCandidate {
         path: ".p10k.zsh",
         description: "Powerlevel10k prompt configuration for Zsh.",
@@ -114,6 +118,10 @@ pub const DEFAULT_DOTFILES: &[DotfileCandidate] = &[
         path: ".tmux.conf",
         description: "tmux terminal multiplexer configuration.",
     },
+    DotfileCandidate {
+        path: ".config/tmux",
+        description: "tmux configuration directory (tmux 3.1+ reads tmux.conf from here).",
+    },
     DotfileCandidate {
         path: ".config/zellij",
         description: "Zellij terminal multiplexer configuration.",
@@ -144,6 +152,10 @@ pub const DEFAULT_DOTFILES: &[DotfileCandidate] = &[
         path: ".config/foot",
         description: "Foot terminal emulator configuration.",
     },
+    DotfileCandidate {
+        path: ".config/ghostty",
+        description: "Ghostty terminal emulator configuration.",
+    },
 
     // --- CLI UX tools ---
     DotfileCandidate {
@@ -166,6 +178,22 @@ pub const DEFAULT_DOTFILES: &[DotfileCandidate] = &[
         path: ".config/eza",
         description: "Configuration for `eza`, a modern `ls` replacement.",
     },
+    DotfileCandidate {
+        path: ".config/gh/config.yml",
+        description: "GitHub CLI (`gh`) settings and aliases. The login token is kept in hosts.yml, which isn't synced.",
+    },
+    DotfileCandidate {
+        path: ".config/lazygit",
+        description: "Lazygit terminal UI configuration.",
+    },
+    DotfileCandidate {
+        path: ".config/yazi",
+        description: "Yazi terminal file manager configuration.",
+    },
+    DotfileCandidate {
+        path: ".config/atuin/config.toml",
+        description: "Atuin shell history settings. The history database and sync key stay local.",
+    },
     DotfileCandidate {
         path: ".config/direnv",
         description: "direnv configuration directory.",
@@ -184,7 +184,6 @@ impl FileManager {
     }
 
     /// Get home directory
-    #[allow(dead_code)]
     #[must_use]
     pub fn home_dir(&self) -> &Path {
         &self.home_dir
@@ -193,6 +192,10 @@ impl FileManager {
 
 /// Recursively copy a directory, preserving symlinks.
 ///
+/// Directories and symlinks are recreated first, then regular files are
+/// copied in parallel. If any file fails to copy, the error lists every
+/// failure rather than just the first one.
+///
 /// NOTE: This function creates symlinks directly with `std::os::unix::fs::symlink()`
 /// instead of using `SymlinkManager`. This is intentional because:
 /// - These are **internal symlinks within dotfile content** (e.g., `~/.config/app/current -> versions/v1`)
@@ -200,15 +203,66 @@ impl FileManager {
 /// - `SymlinkManager` only tracks symlinks that link home files to the repo (e.g., `~/.zshrc -> repo/Profile/.zshrc`)
 /// - Internal content symlinks should be preserved as-is without tracking
 pub fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
+    let mut plan = CopyPlan::default();
+    plan_dir_copy(src, dst, &mut plan)?;
+
+    let failures: Vec<String> = crate::utils::parallel::parallel_map(&plan.files, |(from, to)| {
+        if let Ok(metadata) = from.metadata() {
+            debug!(
+                "Copying file ({} bytes): {:?} -> {:?}",
+                metadata.len(),
+                from,
+                to
+            );
+        } else {
+            debug!("Copying file: {:?} -> {:?}", from, to);
+        }
+        fs::copy(from, to)
+            .err()
+            .map(|e| format!("{}: {e}", from.display()))
+    })
+    .into_iter()
+    .flatten()
+    .collect();
+
+    debug!(
+        "Directory copy complete: {:?} -> {:?} ({} files, {} dirs, {} symlinks, {} skipped)",
+        src,
+        dst,
+        plan.files.len() - failures.len(),
+        plan.dirs_copied,
+        plan.symlinks_copied,
+        plan.skipped
+    );
+
+    if !failures.is_empty() {
+        anyhow::bail!(
+            "Failed to copy {} file(s) from {src:?}:\n  {}",
+            failures.len(),
+            failures.join("\n  ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Work collected by walking a directory tree before copying it.
+#[derive(Default)]
+struct CopyPlan {
+    /// Regular files to copy: (source, destination)
+    files: Vec<(PathBuf, PathBuf)>,
+    dirs_copied: usize,
+    symlinks_copied: usize,
+    skipped: usize,
+}
+
+/// Recreate the directory structure and symlinks of `src` under `dst`,
+/// queueing regular files in `plan` to be copied afterwards.
+fn plan_dir_copy(src: &Path, dst: &Path, plan: &mut CopyPlan) -> Result<()> {
     debug!("Creating destination directory: {:?}", dst);
     fs::create_dir_all(dst)
         .with_context(|| format!("Failed to create destination directory: {dst:?}"))?;
 
-    let mut files_copied = 0;
-    let mut dirs_copied = 0;
-    let mut symlinks_copied = 0;
-    let mut skipped = 0;
-
     for entry in fs::read_dir(src).with_context(|| format!("Failed to read directory: {src:?}"))? {
         let entry = entry?;
         let path = entry.path();
@@ -236,7 +290,7 @@ pub fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
                                 "Failed to create symlink {:?} -> {:?}: {}",
                                 dst_path, link_target, e
                             );
-                            skipped += 1;
+                            plan.skipped += 1;
                             continue;
                         }
                     }
@@ -255,41 +309,26 @@ pub fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
                                 "Failed to create symlink {:?} -> {:?}: {}",
                                 dst_path, link_target, e
                             );
-                            skipped += 1;
+                            plan.skipped += 1;
                             continue;
                         }
                     }
-                    symlinks_copied += 1;
+                    plan.symlinks_copied += 1;
                 }
                 Err(e) => {
                     warn!("Failed to read symlink target for {:?}: {}", path, e);
-                    skipped += 1;
+                    plan.skipped += 1;
                 }
             }
         } else if file_type.is_dir() {
             debug!("Copying subdirectory: {:?} -> {:?}", path, dst_path);
-            copy_dir_all(&path, &dst_path)?;
-            dirs_copied += 1;
+            plan_dir_copy(&path, &dst_path, plan)?;
+            plan.dirs_copied += 1;
         } else {
-            if let Ok(metadata) = path.metadata() {
-                let file_size = metadata.len();
-                debug!(
-                    "Copying file ({} bytes): {:?} -> {:?}",
-                    file_size, path, dst_path
-                );
-            } else {
-                debug!("Copying file: {:?} -> {:?}", path, dst_path);
-            }
-            fs::copy(&path, &dst_path).with_context(|| format!("Failed to copy file: {path:?}"))?;
-            files_copied += 1;
+            plan.files.push((path, dst_path));
         }
     }
 
-    debug!(
-        "Directory copy complete: {:?} -> {:?} ({} files, {} dirs, {} symlinks, {} skipped)",
-        src, dst, files_copied, dirs_copied, symlinks_copied, skipped
-    );
-
     Ok(())
 }
 
@@ -1,9 +1,30 @@
 use anyhow::{Context, Result};
 use git2::{build::RepoBuilder, Cred, FetchOptions, RemoteCallbacks, Repository, Signature};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::process::Command;
 use tracing::{debug, info};
 
+/// Bytes of a diff loaded at a time for previews
+pub const DIFF_CHUNK_BYTES: usize = 256 * 1024;
+
+/// A file's diff, possibly cut short
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    pub text: String,
+    /// More of the diff follows `text`
+    pub truncated: bool,
+}
+
+impl FileDiff {
+    fn whole(text: String) -> Self {
+        Self {
+            text,
+            truncated: false,
+        }
+    }
+}
+
 /// Redact credentials/tokens from a git URL for safe display/logging.
 ///
 /// Handles formats like:
@@ -79,6 +100,238 @@ pub fn is_ssh_url(url: &str) -> bool {
     url.starts_with("git@") || url.starts_with("ssh://")
 }
 
+/// Extract the host and port a git remote URL connects to.
+///
+/// Handles:
+/// - `https://[credentials@]host[:port]/path` (default port 443)
+/// - `ssh://[user@]host[:port]/path` (default port 22)
+/// - `user@host:path` scp-like SSH URLs (port 22)
+///
+/// Returns `None` for local paths, `file://` URLs and anything unrecognized.
+#[must_use]
+pub fn remote_endpoint(url: &str) -> Option<(String, u16)> {
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let default_port = match scheme {
+            "https" => 443,
+            "http" => 80,
+            "ssh" | "git+ssh" | "ssh+git" => 22,
+            "git" => 9418,
+            _ => return None,
+        };
+        let authority = rest.split('/').next()?;
+        let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+        let (host, port) = match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().ok()?),
+            None => (host_port, default_port),
+        };
+        if host.is_empty() {
+            return None;
+        }
+        return Some((host.to_string(), port));
+    }
+
+    // scp-like syntax: [user@]host:path
+    let (user_host, _) = url.split_once(':')?;
+    let host = user_host.rsplit_once('@').map_or(user_host, |(_, h)| h);
+    if host.is_empty() || host.contains('/') || url.starts_with('/') {
+        return None;
+    }
+    Some((host.to_string(), 22))
+}
+
+/// Phase of a network transfer reported by [`TransferProgress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferStage {
+    /// Downloading objects (clone/fetch)
+    Receiving,
+    /// Resolving deltas after all objects arrived (clone/fetch)
+    Resolving,
+    /// Uploading objects (push)
+    Pushing,
+}
+
+/// Snapshot of a clone, fetch or push transfer.
+///
+/// Built from git2's transfer callbacks. Transfers delegated to the system
+/// git CLI (SSH remotes) don't report progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferProgress {
+    pub stage: TransferStage,
+    /// Objects received or sent (deltas indexed while resolving)
+    pub current: usize,
+    /// Total objects (total deltas while resolving)
+    pub total: usize,
+    /// Bytes transferred so far
+    pub bytes: usize,
+}
+
+/// Callback receiving transfer progress updates.
+pub type ProgressFn<'a> = &'a dyn Fn(TransferProgress);
+
+impl TransferProgress {
+    /// Build from a git2 fetch/clone progress report
+    #[must_use]
+    pub fn from_fetch(progress: &git2::Progress) -> Self {
+        let receiving =
+            progress.received_objects() < progress.total_objects() || progress.total_deltas() == 0;
+        if receiving {
+            Self {
+                stage: TransferStage::Receiving,
+                current: progress.received_objects(),
+                total: progress.total_objects(),
+                bytes: progress.received_bytes(),
+            }
+        } else {
+            Self {
+                stage: TransferStage::Resolving,
+                current: progress.indexed_deltas(),
+                total: progress.total_deltas(),
+                bytes: progress.received_bytes(),
+            }
+        }
+    }
+
+    /// Fraction complete in `0.0..=1.0` (0 while the total is unknown)
+    #[must_use]
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.current as f64 / self.total as f64).clamp(0.0, 1.0)
+    }
+
+    /// Human-readable summary, e.g. "Receiving objects: 120/300 (1.2 MB)"
+    #[must_use]
+    pub fn label(&self) -> String {
+        let action = match self.stage {
+            TransferStage::Receiving => "Receiving objects",
+            TransferStage::Resolving => "Resolving deltas",
+            TransferStage::Pushing => "Writing objects",
+        };
+        format!(
+            "{action}: {}/{} ({})",
+            self.current,
+            self.total,
+            crate::utils::binary::format_size(self.bytes as u64)
+        )
+    }
+}
+
+/// A pull that stopped on conflicting changes.
+///
+/// Returned (inside the `anyhow` error) by the pull functions so callers can
+/// tell which files collided and between which commits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullConflict {
+    /// Whether it happened while rebasing local commits (otherwise merging)
+    pub rebase: bool,
+    /// Files changed on both sides
+    pub files: Vec<String>,
+    /// Local commit, short id
+    pub local_commit: String,
+    /// Remote commit, short id
+    pub remote_commit: String,
+}
+
+impl std::fmt::Display for PullConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.rebase {
+            write!(
+                f,
+                "Rebase conflicts detected. Please resolve manually:\n\
+                1. Run 'git status' to see conflicted files\n\
+                2. Edit files to resolve conflicts\n\
+                3. Run 'git add <file>' for each resolved file\n\
+                4. Run 'git rebase --continue'"
+            )
+        } else {
+            write!(f, "Merge conflicts detected. Please resolve manually.")
+        }
+    }
+}
+
+impl std::error::Error for PullConflict {}
+
+/// A file whose local content differs from the remote branch.
+///
+/// Each side is `None` where the file doesn't exist. `base` is the content at
+/// the commit both sides started from, when there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivergedFile {
+    /// Path in the repository (`common/.zshrc`)
+    pub path: String,
+    pub base: Option<Vec<u8>>,
+    /// Content in the working tree, uncommitted changes included
+    pub local: Option<Vec<u8>>,
+    pub remote: Option<Vec<u8>>,
+    /// `.gitattributes` says not to diff or merge it as text
+    pub binary: bool,
+}
+
+/// A committed version of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileVersion {
+    /// Commit that introduced this version
+    pub commit: git2::Oid,
+    /// Commit time, seconds since the epoch
+    pub time: i64,
+    pub summary: String,
+    /// Content at this commit, `None` where the commit deleted the file
+    pub content: Option<Vec<u8>>,
+}
+
+/// Short id of a commit, as shown by `git log --oneline`
+#[must_use]
+pub fn short_id(oid: git2::Oid) -> String {
+    oid.to_string().chars().take(7).collect()
+}
+
+/// Paths of the conflicting entries in `index`
+fn conflicted_paths(index: &git2::Index) -> Vec<String> {
+    let mut paths: Vec<String> = index
+        .conflicts()
+        .map(|conflicts| {
+            conflicts
+                .flatten()
+                .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+                .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.dedup();
+    paths
+}
+
+/// `content` with CRLF line endings turned into LF, as git stores text
+fn crlf_to_lf(content: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(content.len());
+    for (i, &byte) in content.iter().enumerate() {
+        if byte != b'\r' || content.get(i + 1) != Some(&b'\n') {
+            out.push(byte);
+        }
+    }
+    out
+}
+
+/// Forward git2 transfer callbacks to `progress`, if any.
+fn attach_progress<'a>(callbacks: &mut RemoteCallbacks<'a>, progress: Option<ProgressFn<'a>>) {
+    let Some(progress) = progress else {
+        return;
+    };
+    callbacks.transfer_progress(move |stats| {
+        progress(TransferProgress::from_fetch(&stats));
+        true
+    });
+    callbacks.push_transfer_progress(move |current, total, bytes| {
+        progress(TransferProgress {
+            stage: TransferStage::Pushing,
+            current,
+            total,
+            bytes,
+        });
+    });
+}
+
 /// Fetch from remote using system git CLI.
 ///
 /// This is used for SSH URLs where libssh2 (used by git2) has compatibility
@@ -106,6 +359,75 @@ fn fetch_via_cli(repo_path: &Path, remote_name: &str, branch: &str) -> Result<()
     Ok(())
 }
 
+/// Build the key=value description `git credential` reads on stdin.
+fn credential_request(url: &str, username: Option<&str>, password: Option<&str>) -> String {
+    let (protocol, rest) = url.split_once("://").unwrap_or(("https", url));
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    // Credentials embedded in the URL are never passed on to the helper
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+
+    let mut request = format!("protocol={protocol}\nhost={host}\n");
+    if !path.is_empty() {
+        request.push_str(&format!("path={path}\n"));
+    }
+    if let Some(username) = username {
+        request.push_str(&format!("username={username}\n"));
+    }
+    if let Some(password) = password {
+        request.push_str(&format!("password={password}\n"));
+    }
+    request
+}
+
+/// Run `git credential <action>` with the given request on stdin.
+///
+/// Terminal prompts are disabled so a missing helper fails instead of
+/// blocking on (or drawing over) the TUI.
+fn credential_helper(action: &str, request: &str) -> Option<String> {
+    use std::io::Write;
+    let output = Command::new("git")
+        .args(["credential", action])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(request.as_bytes())?;
+                stdin.write_all(b"\n")?;
+            }
+            child.wait_with_output()
+        })
+        .ok()?;
+    if !output.status.success() {
+        debug!("git credential {} failed", action);
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Ask git's configured credential helper (osxkeychain, libsecret, Git
+/// Credential Manager, ...) for a username and password for `url`.
+fn credential_fill(url: &str, username: Option<&str>) -> Option<(String, String)> {
+    let output = credential_helper("fill", &credential_request(url, username, None))?;
+    parse_credential_output(&output)
+}
+
+/// Extract the username and password from `git credential fill` output.
+fn parse_credential_output(output: &str) -> Option<(String, String)> {
+    let mut username = None;
+    let mut password = None;
+    for line in output.lines() {
+        match line.split_once('=') {
+            Some(("username", value)) => username = Some(value.to_string()),
+            Some(("password", value)) => password = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    Some((username?, password?))
+}
+
 /// Push to remote using system git CLI.
 ///
 /// Used for SSH URLs to ensure compatibility with all SSH agent implementations.
@@ -197,9 +519,28 @@ impl GitManager {
             ));
         }
 
+        #[cfg(target_os = "macos")]
+        Self::ensure_precomposed_unicode(&repo);
+
         Ok(Self { repo })
     }
 
+    /// Have git report accented file names precomposed, as git on macOS
+    /// does by default, so a name decomposed by the filesystem isn't seen as
+    /// a different file from the one committed on Linux. Left alone when the
+    /// user has set it.
+    #[cfg(target_os = "macos")]
+    fn ensure_precomposed_unicode(repo: &Repository) {
+        let Ok(mut config) = repo.config() else {
+            return;
+        };
+        if config.get_bool("core.precomposeunicode").is_err() {
+            if let Err(e) = config.set_bool("core.precomposeunicode", true) {
+                tracing::warn!("Failed to set core.precomposeunicode: {}", e);
+            }
+        }
+    }
+
     /// Detect the object format (SHA-1 vs experimental SHA-256) of this repo.
     #[must_use]
     pub fn object_format(&self) -> ObjectFormat {
@@ -229,29 +570,18 @@ impl GitManager {
 
     /// Ensure .gitignore exists with common patterns for frequently changing files
     fn ensure_gitignore(repo_path: &Path) -> Result<()> {
-        use std::fs;
-        use std::io::Write;
-
-        let gitignore_path = repo_path.join(".gitignore");
+        let gitignore_path = repo_path.join(crate::utils::repo_gitignore::GITIGNORE);
 
         // If .gitignore already exists, don't overwrite it
         if gitignore_path.exists() {
             return Ok(());
         }
 
-        let mut file = fs::File::create(&gitignore_path)
-            .with_context(|| format!("Failed to create .gitignore at {gitignore_path:?}"))?;
-
-        // Write common patterns for files that shouldn't be tracked
-        writeln!(file, "# OS files")?;
-        writeln!(file, ".DS_Store")?;
-        writeln!(file, "Thumbs.db")?;
-        writeln!(file)?;
-        writeln!(file, "# Backup files")?;
-        writeln!(file, "*.bak")?;
-        writeln!(file, "*.swp")?;
-        writeln!(file, "*.swo")?;
-        writeln!(file, "*~")?;
+        std::fs::write(
+            &gitignore_path,
+            crate::utils::repo_gitignore::default_content(),
+        )
+        .with_context(|| format!("Failed to create .gitignore at {gitignore_path:?}"))?;
 
         Ok(())
     }
@@ -298,9 +628,15 @@ impl GitManager {
     /// Generate a commit message based on changed files
     pub fn generate_commit_message(&self) -> Result<String> {
         let changed_files = self.get_changed_files()?;
+        Ok(Self::commit_message_for(&changed_files))
+    }
 
+    /// Generate a commit message for changed file entries ("X path", as
+    /// returned by [`Self::get_changed_files`])
+    #[must_use]
+    pub fn commit_message_for(changed_files: &[String]) -> String {
         if changed_files.is_empty() {
-            return Ok("Update dotfiles".to_string());
+            return "Update dotfiles".to_string();
         }
 
         const MANIFEST_FILE: &str = ".dotstate-profiles.toml";
@@ -317,7 +653,7 @@ impl GitManager {
         if !manifest_changes.is_empty() && other_files.is_empty() {
             // Check if it's a modification (not add/delete since manifest is permanent)
             if manifest_changes.iter().any(|s| s.starts_with("M ")) {
-                return Ok("Update profile configuration".to_string());
+                return "Update profile configuration".to_string();
             }
         }
 
@@ -387,7 +723,7 @@ impl GitManager {
             message.push_str(&format!(" ({}+ files changed)", file_names.len()));
         }
 
-        Ok(message)
+        message
     }
 
     /// Add all changes and commit
@@ -405,6 +741,7 @@ impl GitManager {
 
         // Use add_all with "." to add all files (equivalent to "git add .")
         // Skip vim bundles since they are git repos themselves and vimrc will install them
+        let mut lfs_paths = Vec::new();
         index
             .add_all(
                 ["."],
@@ -414,6 +751,9 @@ impl GitManager {
                     let path_str = path.to_string_lossy();
                     if path_str.contains(".vim/bundle/") || path_str.contains(".vim/plugged/") {
                         1 // Skip vim bundles
+                    } else if self.is_lfs(path) {
+                        lfs_paths.push(path_str.into_owned());
+                        1 // Staged through git below
                     } else {
                         0 // Accept everything else
                     }
@@ -422,7 +762,130 @@ impl GitManager {
             .context("Failed to add files to index (git add .)")?;
 
         index.write().context("Failed to write index")?;
+        self.stage_lfs(&mut index, &lfs_paths)?;
+
+        self.commit_index(&mut index, message)
+    }
+
+    /// Commit only `paths`, leaving every other change uncommitted
+    /// (git reset -- <others>; git add -A -- <paths>; git commit).
+    ///
+    /// Changes to other paths that were already staged are unstaged first,
+    /// so they stay in the working tree instead of sneaking into the commit.
+    ///
+    /// Paths in `skipped_hunks` are committed hunk by hunk: the listed hunk
+    /// indices (numbered as in [`Self::get_diff_for_file`]) are left out and
+    /// stay in the working tree.
+    pub fn commit_paths(
+        &self,
+        paths: &[String],
+        skipped_hunks: &HashMap<String, HashSet<usize>>,
+        message: &str,
+    ) -> Result<()> {
+        info!("Starting commit of {} path(s): {}", paths.len(), message);
+
+        let (partial, whole): (Vec<String>, Vec<String>) = paths
+            .iter()
+            .cloned()
+            .partition(|path| skipped_hunks.get(path).is_some_and(|h| !h.is_empty()));
+
+        let head = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let staged_elsewhere: Vec<String> = self
+            .repo
+            .statuses(None)
+            .context("Failed to get repository status")?
+            .iter()
+            .filter(|entry| {
+                entry.status().intersects(
+                    git2::Status::INDEX_NEW
+                        | git2::Status::INDEX_MODIFIED
+                        | git2::Status::INDEX_DELETED
+                        | git2::Status::INDEX_RENAMED
+                        | git2::Status::INDEX_TYPECHANGE,
+                )
+            })
+            .filter_map(|entry| entry.path().ok().map(str::to_string))
+            .filter(|path| !whole.contains(path))
+            .collect();
+
+        if !staged_elsewhere.is_empty() {
+            if let Some(head) = &head {
+                self.repo
+                    .reset_default(Some(head.as_object()), &staged_elsewhere)
+                    .context("Failed to unstage unselected files")?;
+            } else {
+                let mut index = self.repo.index().context("Failed to get index")?;
+                for path in &staged_elsewhere {
+                    index.remove_path(Path::new(path))?;
+                }
+                index.write().context("Failed to write index")?;
+            }
+        }
+
+        let mut index = self
+            .repo
+            .index()
+            .context("Failed to get repository index")?;
+        index.read(true).context("Failed to refresh index")?;
+        if !whole.is_empty() {
+            let mut lfs_paths = Vec::new();
+            index
+                .add_all(
+                    &whole,
+                    git2::IndexAddOption::DISABLE_PATHSPEC_MATCH,
+                    Some(&mut |path: &Path, _matched_spec: &[u8]| {
+                        if self.is_lfs(path) {
+                            lfs_paths.push(path.to_string_lossy().into_owned());
+                            1
+                        } else {
+                            0
+                        }
+                    }),
+                )
+                .context("Failed to add selected files to index")?;
+            // Stage deletions of selected files too
+            index
+                .update_all(&whole, None)
+                .context("Failed to stage removed files")?;
+            index.write().context("Failed to write index")?;
+            self.stage_lfs(&mut index, &lfs_paths)?;
+        }
+
+        for path in &partial {
+            self.stage_hunks(path, &skipped_hunks[path])?;
+        }
+        index.read(true).context("Failed to refresh index")?;
+
+        self.commit_index(&mut index, message)
+    }
+
+    /// Stage `paths`, stored with Git LFS, through the git CLI (libgit2
+    /// can't run the LFS filter) and reload `index` with them
+    fn stage_lfs(&self, index: &mut git2::Index, paths: &[String]) -> Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        crate::utils::lfs::stage(self.repo_workdir()?, paths)?;
+        index.read(true).context("Failed to refresh index")
+    }
+
+    /// Stage the hunks of `path` except those in `skipped` (git add -p)
+    fn stage_hunks(&self, path: &str, skipped: &HashSet<usize>) -> Result<()> {
+        let diff = self.file_diff(path)?;
+        let mut hunk_index = 0;
+        let mut opts = git2::ApplyOptions::new();
+        opts.hunk_callback(|_hunk| {
+            let apply = !skipped.contains(&hunk_index);
+            hunk_index += 1;
+            apply
+        });
+        self.repo
+            .apply(&diff, git2::ApplyLocation::Index, Some(&mut opts))
+            .with_context(|| format!("Failed to stage selected hunks of {path}"))
+    }
 
+    /// Write `index` as a tree and commit it on top of HEAD
+    fn commit_index(&self, index: &mut git2::Index, message: &str) -> Result<()> {
         let tree_id = index.write_tree().context("Failed to write tree")?;
         let tree = self
             .repo
@@ -478,6 +941,37 @@ impl GitManager {
         Ok(())
     }
 
+    /// Stash all uncommitted changes, including untracked files
+    /// (git stash push --include-untracked).
+    ///
+    /// Returns false when there was nothing to stash.
+    pub fn stash_changes(&mut self, message: &str) -> Result<bool> {
+        let signature = Self::get_signature()?;
+        match self.repo.stash_save(
+            &signature,
+            message,
+            Some(git2::StashFlags::INCLUDE_UNTRACKED),
+        ) {
+            Ok(oid) => {
+                info!("Stashed uncommitted changes: {}", oid);
+                Ok(true)
+            }
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(false),
+            Err(e) => Err(e).context("Failed to stash changes"),
+        }
+    }
+
+    /// Restore the most recent stash and drop it (git stash pop).
+    ///
+    /// On conflict the stash is kept, so nothing is lost.
+    pub fn stash_pop(&mut self) -> Result<()> {
+        self.repo
+            .stash_pop(0, None)
+            .context("Failed to restore stashed changes")?;
+        info!("Restored stashed changes");
+        Ok(())
+    }
+
     /// Reset the last commit, keeping changes staged (git reset --soft HEAD~1)
     ///
     /// This is useful when a push is rejected - we can undo the commit while
@@ -580,13 +1074,49 @@ impl GitManager {
     /// If token is provided, it will be used for authentication.
     /// Otherwise, attempts to extract token from remote URL.
     pub fn push(&self, remote_name: &str, branch: &str, token: Option<&str>) -> Result<()> {
+        self.push_with_progress(remote_name, branch, token, None)
+    }
+
+    /// Push to remote, reporting upload progress to `progress`.
+    pub fn push_with_progress(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        token: Option<&str>,
+        progress: Option<ProgressFn>,
+    ) -> Result<()> {
+        self.push_branch(remote_name, branch, token, progress, false)
+    }
+
+    /// Force push, replacing the remote branch with the local one
+    /// (git push --force). Remote commits missing locally are lost.
+    pub fn force_push(&self, remote_name: &str, branch: &str, token: Option<&str>) -> Result<()> {
+        self.push_branch(remote_name, branch, token, None, true)
+    }
+
+    fn push_branch(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        token: Option<&str>,
+        progress: Option<ProgressFn>,
+        force: bool,
+    ) -> Result<()> {
         use std::cell::RefCell;
         use std::rc::Rc;
         use tracing::info;
-        info!("Pushing to remote: {} (branch: {})", remote_name, branch);
+        info!(
+            "Pushing to remote: {} (branch: {}, force: {})",
+            remote_name, branch, force
+        );
+        // A leading '+' lets the remote ref move to a non-descendant commit
+        let force_prefix = if force { "+" } else { "" };
 
         let remote_url = self.get_remote_url(remote_name)?;
 
+        // LFS objects go up first, as git's pre-push hook would do
+        crate::utils::lfs::push(self.repo_workdir()?, remote_name, branch)?;
+
         // Use system git for SSH URLs (libssh2 has compatibility issues with
         // some SSH agents like 1Password, `YubiKey`, Secretive)
         if is_ssh_url(&remote_url) {
@@ -596,12 +1126,12 @@ impl GitManager {
             let branch_ref = format!("refs/heads/{branch}");
             let refspec = if self.repo.find_reference(&branch_ref).is_err() {
                 if let Some(current_branch) = self.get_current_branch() {
-                    format!("refs/heads/{current_branch}:refs/heads/{branch}")
+                    format!("{force_prefix}refs/heads/{current_branch}:refs/heads/{branch}")
                 } else {
                     anyhow::bail!("No branch '{branch}' exists and no current branch found");
                 }
             } else {
-                format!("refs/heads/{branch}:refs/heads/{branch}")
+                format!("{force_prefix}refs/heads/{branch}:refs/heads/{branch}")
             };
 
             push_via_cli(repo_path, remote_name, &refspec)?;
@@ -619,6 +1149,7 @@ impl GitManager {
             .map(std::string::ToString::to_string)
             .or_else(|| Self::extract_token_from_url(&remote_url));
         Self::setup_credentials(&mut callbacks, token_to_use);
+        attach_progress(&mut callbacks, progress);
 
         // Capture push errors from server-side hooks/rejections
         // The push_update_reference callback is called for each ref being updated,
@@ -651,13 +1182,15 @@ impl GitManager {
 
         let mut push_options = git2::PushOptions::new();
         push_options.remote_callbacks(callbacks);
+        push_options.proxy_options(crate::utils::proxy::git_proxy_options(&remote_url));
 
         // Check if branch exists locally
         let branch_ref = format!("refs/heads/{branch}");
         if self.repo.find_reference(&branch_ref).is_err() {
             // Branch doesn't exist, try to get current branch
             if let Some(current_branch) = self.get_current_branch() {
-                let refspec = format!("refs/heads/{current_branch}:refs/heads/{branch}");
+                let refspec =
+                    format!("{force_prefix}refs/heads/{current_branch}:refs/heads/{branch}");
                 remote
                     .push(&[&refspec], Some(&mut push_options))
                     .with_context(|| format!("Failed to push to remote '{remote_name}'"))?;
@@ -680,7 +1213,7 @@ impl GitManager {
             ));
         }
 
-        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+        let refspec = format!("{force_prefix}refs/heads/{branch}:refs/heads/{branch}");
         remote
             .push(&[&refspec], Some(&mut push_options))
             .with_context(|| {
@@ -744,62 +1277,28 @@ impl GitManager {
                 Cred::userpass_plaintext(username, &token_clone)
             });
         } else {
+            let mut helper_attempt: Option<(String, String)> = None;
             callbacks.credentials(move |url, username_from_url, allowed_types| {
                 let url_str = url.to_string();
                 let username = username_from_url.unwrap_or("git");
 
-                // Try credential helper for HTTPS URLs (only if allowed)
+                // Try git's credential helper for HTTPS URLs (only if allowed).
+                // libgit2 calls back again when the server rejects what we sent,
+                // so a second call means the helper's credentials are stale.
                 if url_str.starts_with("https://") && allowed_types.is_user_pass_plaintext() {
-                    // Parse URL to extract protocol, host, and path (standard format)
-                    let (host, path) = if let Some(after_proto) = url_str.strip_prefix("https://") {
-                        if let Some(slash_idx) = after_proto.find('/') {
-                            let h = &after_proto[..slash_idx];
-                            let p = &after_proto[slash_idx + 1..];
-                            (h, p)
-                        } else {
-                            (after_proto, "")
-                        }
-                    } else {
-                        ("", "")
-                    };
-
-                    let credential_input = format!("protocol=https\nhost={host}\npath={path}\n");
-
-                    if let Ok(output) = Command::new("git")
-                        .arg("credential")
-                        .arg("fill")
-                        .stdin(std::process::Stdio::piped())
-                        .stdout(std::process::Stdio::piped())
-                        .stderr(std::process::Stdio::piped())
-                        .spawn()
-                        .and_then(|mut child| {
-                            use std::io::Write;
-                            if let Some(mut stdin) = child.stdin.take() {
-                                stdin.write_all(credential_input.as_bytes())?;
-                                stdin.flush()?;
-                            }
-                            child.wait_with_output()
-                        })
-                    {
-                        if output.status.success() {
-                            let output_str = String::from_utf8_lossy(&output.stdout);
-                            let mut parsed_username = None;
-                            let mut parsed_password = None;
-
-                            for line in output_str.lines() {
-                                if let Some((key, value)) = line.split_once('=') {
-                                    match key {
-                                        "username" => parsed_username = Some(value.to_string()),
-                                        "password" => parsed_password = Some(value.to_string()),
-                                        _ => {}
-                                    }
-                                }
-                            }
-
-                            if let (Some(user), Some(pass)) = (parsed_username, parsed_password) {
-                                return Cred::userpass_plaintext(&user, &pass);
-                            }
-                        }
+                    if let Some((user, pass)) = helper_attempt.take() {
+                        credential_helper(
+                            "reject",
+                            &credential_request(&url_str, Some(&user), Some(&pass)),
+                        );
+                        return Err(git2::Error::from_str(
+                            "Credentials from the git credential helper were rejected",
+                        ));
+                    }
+                    if let Some((user, pass)) = credential_fill(&url_str, username_from_url) {
+                        let cred = Cred::userpass_plaintext(&user, &pass);
+                        helper_attempt = Some((user, pass));
+                        return cred;
                     }
                 }
 
@@ -873,6 +1372,7 @@ impl GitManager {
 
             let mut fetch_options = FetchOptions::new();
             fetch_options.remote_callbacks(callbacks);
+            fetch_options.proxy_options(crate::utils::proxy::git_proxy_options(&remote_url));
 
             remote
                 .fetch(&[branch], Some(&mut fetch_options), None)
@@ -921,9 +1421,13 @@ impl GitManager {
 
                 // Check if merge resulted in conflicts
                 if index.has_conflicts() {
-                    return Err(anyhow::anyhow!(
-                        "Merge conflicts detected. Please resolve manually."
-                    ));
+                    return Err(PullConflict {
+                        rebase: false,
+                        files: conflicted_paths(&index),
+                        local_commit: short_id(local_commit.id()),
+                        remote_commit: short_id(fetch_commit.id()),
+                    }
+                    .into());
                 }
 
                 // Write the index after merge
@@ -982,6 +1486,32 @@ impl GitManager {
         remote_name: &str,
         branch: &str,
         token: Option<&str>,
+    ) -> Result<usize> {
+        self.pull_with_rebase_with_progress(remote_name, branch, token, None)
+    }
+
+    /// Pull with rebase, reporting fetch progress to `progress`.
+    pub fn pull_with_rebase_with_progress(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        token: Option<&str>,
+        progress: Option<ProgressFn>,
+    ) -> Result<usize> {
+        let pulled = self.fetch_and_rebase(remote_name, branch, token, progress)?;
+        if pulled > 0 {
+            crate::utils::lfs::pull(self.repo_workdir()?, remote_name)?;
+        }
+        Ok(pulled)
+    }
+
+    /// Fetch `branch` and rebase local commits onto it
+    fn fetch_and_rebase(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        token: Option<&str>,
+        progress: Option<ProgressFn>,
     ) -> Result<usize> {
         info!(
             "Pulling with rebase from remote: {} (branch: {})",
@@ -1004,9 +1534,11 @@ impl GitManager {
                 .map(std::string::ToString::to_string)
                 .or_else(|| Self::extract_token_from_url(&remote_url));
             Self::setup_credentials(&mut callbacks, token_to_use);
+            attach_progress(&mut callbacks, progress);
 
             let mut fetch_options = FetchOptions::new();
             fetch_options.remote_callbacks(callbacks);
+            fetch_options.proxy_options(crate::utils::proxy::git_proxy_options(&remote_url));
 
             remote
                 .fetch(&[branch], Some(&mut fetch_options), None)
@@ -1122,15 +1654,15 @@ impl GitManager {
                         // Check for conflicts
                         let index = self.repo.index().context("Failed to get index")?;
                         if index.has_conflicts() {
+                            let conflict = PullConflict {
+                                rebase: true,
+                                files: conflicted_paths(&index),
+                                local_commit: short_id(local_commit.id()),
+                                remote_commit: short_id(fetch_commit_id),
+                            };
                             // Abort the rebase on conflict
                             let _ = rebase.abort();
-                            return Err(anyhow::anyhow!(
-                                "Rebase conflicts detected. Please resolve manually:\n\
-                                1. Run 'git status' to see conflicted files\n\
-                                2. Edit files to resolve conflicts\n\
-                                3. Run 'git add <file>' for each resolved file\n\
-                                4. Run 'git rebase --continue'"
-                            ));
+                            return Err(conflict.into());
                         }
 
                         // Commit the rebased change
@@ -1251,6 +1783,7 @@ impl GitManager {
 
         let mut fetch_options = FetchOptions::new();
         fetch_options.remote_callbacks(callbacks);
+        fetch_options.proxy_options(crate::utils::proxy::git_proxy_options(&remote_url));
 
         remote
             .fetch(&[branch], Some(&mut fetch_options), None)
@@ -1270,56 +1803,372 @@ impl GitManager {
             Err(_) => return Ok((0, 0)), // Local branch doesn't exist yet
         };
 
-        // For remote, we look for FETCH_HEAD since we just fetched,
-        // or try to find the remote tracking branch via standard naming
-        let remote_oid = if let Ok(fetch_head) = self.repo.find_reference("FETCH_HEAD") {
-            fetch_head.peel_to_commit()?.id()
-        } else {
-            // Fallback to finding the remote tracking branch ref
-            // Note: This might be stale if we didn't just fetch
-            let remote_ref_name = format!("refs/remotes/{remote_name}/{branch}");
-            match self.repo.refname_to_id(&remote_ref_name) {
-                Ok(oid) => oid,
-                Err(_) => return Ok((0, 0)), // Remote branch doesn't exist
-            }
+        let Some(remote_oid) = self.remote_branch_oid(remote_name, branch)? else {
+            return Ok((0, 0)); // Remote branch doesn't exist
         };
 
         let (ahead, behind) = self.repo.graph_ahead_behind(local_oid, remote_oid)?;
         Ok((ahead, behind))
     }
 
-    /// Add a remote (or update if it exists)
-    pub fn add_remote(&mut self, name: &str, url: &str) -> Result<()> {
-        // remote_set_url doesn't exist in git2, so we delete and recreate
-        if self.repo.find_remote(name).is_ok() {
-            self.repo
-                .remote_delete(name)
-                .with_context(|| format!("Failed to delete existing remote '{name}'"))?;
+    /// Commit the remote branch points to, if known
+    pub fn remote_branch_oid(&self, remote_name: &str, branch: &str) -> Result<Option<git2::Oid>> {
+        // For remote, we look for FETCH_HEAD since we just fetched,
+        // or try to find the remote tracking branch via standard naming
+        if let Ok(fetch_head) = self.repo.find_reference("FETCH_HEAD") {
+            return Ok(Some(fetch_head.peel_to_commit()?.id()));
         }
-        self.repo
-            .remote(name, url)
-            .with_context(|| format!("Failed to add remote '{name}'"))?;
+        // Fallback to finding the remote tracking branch ref
+        // Note: This might be stale if we didn't just fetch
+        let remote_ref_name = format!("refs/remotes/{remote_name}/{branch}");
+        Ok(self.repo.refname_to_id(&remote_ref_name).ok())
+    }
 
-        // Configure remote tracking for the current branch
-        self.configure_remote_tracking(name)?;
+    /// Files that differ between HEAD and the remote branch, as "X filename"
+    /// entries. With `towards_remote` they describe what changes locally when
+    /// matching the remote; otherwise what changes on the remote when it is
+    /// overwritten with HEAD.
+    pub fn diff_against_remote(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        towards_remote: bool,
+    ) -> Result<Vec<String>> {
+        let remote_tree = match self.remote_branch_oid(remote_name, branch)? {
+            Some(oid) => Some(self.repo.find_commit(oid)?.tree()?),
+            None => None,
+        };
+        let head_tree = match self.repo.head() {
+            Ok(head) => Some(head.peel_to_tree()?),
+            Err(_) => None,
+        };
+        let (old_tree, new_tree) = if towards_remote {
+            (head_tree, remote_tree)
+        } else {
+            (remote_tree, head_tree)
+        };
 
-        Ok(())
+        let diff = self
+            .repo
+            .diff_tree_to_tree(old_tree.as_ref(), new_tree.as_ref(), None)
+            .context("Failed to compare with remote")?;
+        Ok(Self::diff_entries(&diff))
     }
 
-    /// Update the remote URL to either embed or remove credentials based on setting.
-    ///
-    /// # Arguments
-    /// * `remote_name` - Name of the remote (usually "origin")
-    /// * `token` - The token to embed (if `embed_credentials` is true)
-    /// * `embed_credentials` - Whether to embed credentials in the URL
-    pub fn update_remote_credentials(
-        &mut self,
+    /// Commits only on one side, as "abc1234 subject" entries, newest first:
+    /// with `local` those HEAD has and the remote branch lacks, otherwise
+    /// the other way round
+    pub fn divergent_commits(
+        &self,
         remote_name: &str,
-        token: Option<&str>,
-        embed_credentials: bool,
-    ) -> Result<()> {
-        let remote = self
-            .repo
+        branch: &str,
+        local: bool,
+    ) -> Result<Vec<String>> {
+        let (Some(head), Some(remote)) = (
+            self.head_oid(),
+            self.remote_branch_oid(remote_name, branch)?,
+        ) else {
+            return Ok(Vec::new());
+        };
+        let (show, hide) = if local {
+            (head, remote)
+        } else {
+            (remote, head)
+        };
+        let mut walk = self.repo.revwalk()?;
+        walk.push(show)?;
+        walk.hide(hide)?;
+        let mut commits = Vec::new();
+        for oid in walk {
+            let commit = self.repo.find_commit(oid?)?;
+            commits.push(format!(
+                "{} {}",
+                short_id(commit.id()),
+                commit.summary().ok().flatten().unwrap_or_default()
+            ));
+        }
+        Ok(commits)
+    }
+
+    /// Files whose content in the working tree differs from the remote
+    /// branch, sorted by path
+    pub fn diverged_files(&self, remote_name: &str, branch: &str) -> Result<Vec<DivergedFile>> {
+        let remote_oid = self
+            .remote_branch_oid(remote_name, branch)?
+            .with_context(|| format!("Remote branch '{remote_name}/{branch}' not found"))?;
+        let remote_tree = self.repo.find_commit(remote_oid)?.tree()?;
+        let base_tree = match self
+            .head_oid()
+            .and_then(|head| self.repo.merge_base(head, remote_oid).ok())
+        {
+            Some(base) => Some(self.repo.find_commit(base)?.tree()?),
+            None => None,
+        };
+        let workdir = self
+            .repo
+            .workdir()
+            .context("Repository has no working directory")?;
+
+        let mut opts = git2::DiffOptions::new();
+        opts.include_untracked(true).recurse_untracked_dirs(true);
+        let diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(Some(&remote_tree), Some(&mut opts))
+            .context("Failed to compare with remote")?;
+
+        let content_at = |tree: Option<&git2::Tree>, path: &Path| -> Option<Vec<u8>> {
+            let entry = tree?.get_path(path).ok()?;
+            let blob = entry.to_object(&self.repo).ok()?.peel_to_blob().ok()?;
+            Some(blob.content().to_vec())
+        };
+        let mut files = Vec::new();
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                continue;
+            };
+            // Compared as it would be committed, line endings normalized
+            let local = std::fs::read(workdir.join(path)).ok().map(|content| {
+                if self.normalizes_line_endings(path) {
+                    crlf_to_lf(&content)
+                } else {
+                    content
+                }
+            });
+            let remote = content_at(Some(&remote_tree), path);
+            if local == remote {
+                continue;
+            }
+            files.push(DivergedFile {
+                path: path.to_string_lossy().to_string(),
+                base: content_at(base_tree.as_ref(), path),
+                local,
+                remote,
+                binary: self.diffs_as_binary(path)
+                    || self.attribute(path, "merge") == git2::AttrValue::False,
+            });
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Commit the working tree as a merge of HEAD and the remote branch, so
+    /// pushing it fast-forwards the remote. A side the other already
+    /// contains isn't added as a parent.
+    pub fn commit_merge(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        message: &str,
+    ) -> Result<git2::Oid> {
+        let remote_oid = self
+            .remote_branch_oid(remote_name, branch)?
+            .with_context(|| format!("Remote branch '{remote_name}/{branch}' not found"))?;
+        let head_oid = self.head_oid().context("Repository has no commits")?;
+        // Leftover merge state would otherwise be mistaken for this merge;
+        // the working tree holds the reconciled files, so it isn't reset
+        self.repo
+            .cleanup_state()
+            .context("Failed to clear unfinished merge state")?;
+
+        let mut index = self
+            .repo
+            .index()
+            .context("Failed to get repository index")?;
+        index.read(true).context("Failed to refresh index")?;
+        let mut lfs_paths = Vec::new();
+        index
+            .add_all(
+                ["."],
+                git2::IndexAddOption::DEFAULT,
+                Some(&mut |path: &Path, _matched_spec: &[u8]| {
+                    if self.is_lfs(path) {
+                        lfs_paths.push(path.to_string_lossy().into_owned());
+                        1
+                    } else {
+                        0
+                    }
+                }),
+            )
+            .context("Failed to add files to index")?;
+        index
+            .update_all(["."], None)
+            .context("Failed to stage removed files")?;
+        index.write().context("Failed to write index")?;
+        self.stage_lfs(&mut index, &lfs_paths)?;
+        let tree = self
+            .repo
+            .find_tree(index.write_tree().context("Failed to write tree")?)?;
+
+        let parent_oids =
+            if head_oid == remote_oid || self.repo.graph_descendant_of(head_oid, remote_oid)? {
+                vec![head_oid]
+            } else if self.repo.graph_descendant_of(remote_oid, head_oid)? {
+                vec![remote_oid]
+            } else {
+                vec![head_oid, remote_oid]
+            };
+        let parents = parent_oids
+            .iter()
+            .map(|oid| self.repo.find_commit(*oid))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+
+        let signature = Self::get_signature()?;
+        let oid = self
+            .repo
+            .commit(None, &signature, &signature, message, &tree, &parent_refs)
+            .context("Failed to create commit")?;
+        self.repo
+            .head()?
+            .set_target(oid, message)
+            .context("Failed to move the branch to the new commit")?;
+        info!("Created reconciling commit: {} ({})", oid, message);
+        Ok(oid)
+    }
+
+    /// Versions of the file at `path` (relative to the repository) in the
+    /// history of HEAD, newest first: one per commit that changed it
+    pub fn file_history(&self, path: &str) -> Result<Vec<FileVersion>> {
+        let Some(head) = self.head_oid() else {
+            return Ok(Vec::new());
+        };
+        let path = Path::new(path);
+        let entry_id = |commit: &git2::Commit| -> Option<git2::Oid> {
+            commit
+                .tree()
+                .ok()?
+                .get_path(path)
+                .ok()
+                .map(|entry| entry.id())
+        };
+
+        let mut walk = self.repo.revwalk()?;
+        walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        walk.push(head)?;
+        let mut versions = Vec::new();
+        for oid in walk {
+            let commit = self.repo.find_commit(oid?)?;
+            let id = entry_id(&commit);
+            // Like `git log <path>`, a merge that took the file from one of its
+            // parents didn't change it
+            let unchanged = if commit.parent_count() == 0 {
+                id.is_none()
+            } else {
+                commit.parents().any(|parent| entry_id(&parent) == id)
+            };
+            if unchanged {
+                continue;
+            }
+            versions.push(FileVersion {
+                commit: commit.id(),
+                time: commit.time().seconds(),
+                summary: commit
+                    .summary()
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default()
+                    .to_string(),
+                content: id
+                    .and_then(|id| self.repo.find_blob(id).ok())
+                    .map(|blob| blob.content().to_vec()),
+            });
+        }
+        Ok(versions)
+    }
+
+    /// Commit HEAD points to, if any
+    #[must_use]
+    pub fn head_oid(&self) -> Option<git2::Oid> {
+        self.repo.head().ok()?.peel_to_commit().ok().map(|c| c.id())
+    }
+
+    /// Files that differ between `old` and HEAD, as "X filename" entries
+    pub fn changed_since(&self, old: git2::Oid) -> Result<Vec<String>> {
+        let old_tree = self.repo.find_commit(old)?.tree()?;
+        let head_tree = self.repo.head()?.peel_to_tree()?;
+        let diff = self
+            .repo
+            .diff_tree_to_tree(Some(&old_tree), Some(&head_tree), None)
+            .context("Failed to compare commits")?;
+        Ok(Self::diff_entries(&diff))
+    }
+
+    /// A tree diff as "X filename" entries
+    fn diff_entries(diff: &git2::Diff) -> Vec<String> {
+        diff.deltas()
+            .filter_map(|delta| {
+                let prefix = match delta.status() {
+                    git2::Delta::Added => "A",
+                    git2::Delta::Deleted => "D",
+                    _ => "M",
+                };
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())?;
+                Some(format!("{prefix} {}", path.display()))
+            })
+            .collect()
+    }
+
+    /// Discard local commits and changes to tracked files, moving the branch
+    /// to the remote's commit (git reset --hard). Untracked files are kept.
+    pub fn reset_hard_to_remote(&self, remote_name: &str, branch: &str) -> Result<()> {
+        let oid = self
+            .remote_branch_oid(remote_name, branch)?
+            .with_context(|| format!("Remote branch '{remote_name}/{branch}' not found"))?;
+        let commit = self
+            .repo
+            .find_commit(oid)
+            .context("Failed to find remote commit")?;
+
+        // A stuck rebase or merge would otherwise survive the reset
+        if self.repo.state() != git2::RepositoryState::Clean {
+            self.cleanup_failed_operation(branch)?;
+            self.repo
+                .cleanup_state()
+                .context("Failed to clear in-progress operation")?;
+        }
+
+        self.repo
+            .reset(commit.as_object(), git2::ResetType::Hard, None)
+            .context("Failed to reset to remote")?;
+        info!("Reset {} to {}/{} ({})", branch, remote_name, branch, oid);
+        Ok(())
+    }
+
+    /// Add a remote (or update if it exists)
+    pub fn add_remote(&mut self, name: &str, url: &str) -> Result<()> {
+        // remote_set_url doesn't exist in git2, so we delete and recreate
+        if self.repo.find_remote(name).is_ok() {
+            self.repo
+                .remote_delete(name)
+                .with_context(|| format!("Failed to delete existing remote '{name}'"))?;
+        }
+        self.repo
+            .remote(name, url)
+            .with_context(|| format!("Failed to add remote '{name}'"))?;
+
+        // Configure remote tracking for the current branch
+        self.configure_remote_tracking(name)?;
+
+        Ok(())
+    }
+
+    /// Update the remote URL to either embed or remove credentials based on setting.
+    ///
+    /// # Arguments
+    /// * `remote_name` - Name of the remote (usually "origin")
+    /// * `token` - The token to embed (if `embed_credentials` is true)
+    /// * `embed_credentials` - Whether to embed credentials in the URL
+    pub fn update_remote_credentials(
+        &mut self,
+        remote_name: &str,
+        token: Option<&str>,
+        embed_credentials: bool,
+    ) -> Result<()> {
+        let remote = self
+            .repo
             .find_remote(remote_name)
             .with_context(|| format!("Remote '{remote_name}' not found"))?;
 
@@ -1567,6 +2416,9 @@ impl GitManager {
 
         let mut fetch_opts = FetchOptions::new();
         fetch_opts.remote_callbacks(remote_callbacks);
+        fetch_opts.proxy_options(crate::utils::proxy::git_proxy_options(
+            remote.url().unwrap_or_default(),
+        ));
 
         // Try to fetch (ignore errors - remote might not be accessible)
         let _ = remote.fetch(&[branch], Some(&mut fetch_opts), None);
@@ -1670,6 +2522,26 @@ impl GitManager {
         path: &Path,
         token: Option<&str>,
         embed_credentials: bool,
+    ) -> Result<(Self, bool)> {
+        Self::clone_or_open_inner(url, path, token, embed_credentials, None)
+    }
+
+    /// Like [`Self::clone_or_open`], reporting clone progress to `progress`.
+    pub fn clone_or_open_with_progress(
+        url: &str,
+        path: &Path,
+        token: Option<&str>,
+        progress: ProgressFn,
+    ) -> Result<(Self, bool)> {
+        Self::clone_or_open_inner(url, path, token, true, Some(progress))
+    }
+
+    fn clone_or_open_inner(
+        url: &str,
+        path: &Path,
+        token: Option<&str>,
+        embed_credentials: bool,
+        progress: Option<ProgressFn>,
     ) -> Result<(Self, bool)> {
         // Check if repository already exists
         if path.join(".git").exists() {
@@ -1690,7 +2562,7 @@ impl GitManager {
                         std::fs::remove_dir_all(path)
                             .with_context(|| format!("Failed to remove directory {path:?}"))?;
                         let manager =
-                            Self::clone_with_options(url, path, token, embed_credentials)?;
+                            Self::clone_inner(url, path, token, embed_credentials, progress)?;
                         return Ok((manager, false));
                     }
 
@@ -1718,7 +2590,7 @@ impl GitManager {
         }
 
         // Clone fresh
-        let manager = Self::clone_with_options(url, path, token, embed_credentials)?;
+        let manager = Self::clone_inner(url, path, token, embed_credentials, progress)?;
         Ok((manager, false))
     }
 
@@ -1794,6 +2666,16 @@ impl GitManager {
         path: &Path,
         token: Option<&str>,
         embed_credentials: bool,
+    ) -> Result<Self> {
+        Self::clone_inner(url, path, token, embed_credentials, None)
+    }
+
+    fn clone_inner(
+        url: &str,
+        path: &Path,
+        token: Option<&str>,
+        embed_credentials: bool,
+        progress: Option<ProgressFn>,
     ) -> Result<Self> {
         // Use system git for SSH URLs (libssh2 has compatibility issues with
         // some SSH agents like 1Password, `YubiKey`, Secretive)
@@ -1827,12 +2709,18 @@ impl GitManager {
         };
 
         let mut builder = RepoBuilder::new();
+        let mut callbacks = RemoteCallbacks::new();
+        let mut needs_callbacks = progress.is_some();
+        attach_progress(&mut callbacks, progress);
 
         // Set up credentials callback for authentication (used when not embedding in URL)
-        if !embed_credentials {
+        if token.is_none() {
+            // No token: fall back to git's credential helper
+            Self::setup_credentials(&mut callbacks, None);
+            needs_callbacks = true;
+        } else if !embed_credentials {
             if let Some(token) = token {
                 let token_clone = token.to_string();
-                let mut callbacks = RemoteCallbacks::new();
                 callbacks.credentials(move |_url, username_from_url, allowed_types| {
                     if !allowed_types.is_user_pass_plaintext() {
                         return Err(git2::Error::from_str(
@@ -1842,13 +2730,17 @@ impl GitManager {
                     let username = username_from_url.unwrap_or("x-access-token");
                     Cred::userpass_plaintext(username, &token_clone)
                 });
-
-                let mut fetch_opts = FetchOptions::new();
-                fetch_opts.remote_callbacks(callbacks);
-                builder.fetch_options(fetch_opts);
+                needs_callbacks = true;
             }
         }
 
+        let mut fetch_opts = FetchOptions::new();
+        if needs_callbacks {
+            fetch_opts.remote_callbacks(callbacks);
+        }
+        fetch_opts.proxy_options(crate::utils::proxy::git_proxy_options(&clone_url));
+        builder.fetch_options(fetch_opts);
+
         // Clone with improved error handling
         let repo = builder.clone(&clone_url, path).map_err(|e| {
             // Provide more detailed error message
@@ -1869,80 +2761,188 @@ impl GitManager {
 
     /// Get diff for a specific file as a string
     pub fn get_diff_for_file(&self, path: &str) -> Result<Option<String>> {
-        let mut diff_opts = git2::DiffOptions::new();
-        diff_opts.pathspec(path);
-        diff_opts.context_lines(3); // Standard context
-
-        // 1. Check for unstaged changes (Workdir vs Index)
-        let diff_workdir = self
-            .repo
-            .diff_index_to_workdir(None, Some(&mut diff_opts))
-            .context("Failed to get workdir diff")?;
-
-        // 2. Check for staged changes (Index vs HEAD)
-        let head_tree = match self.repo.head() {
-            Ok(head) => Some(head.peel_to_tree()?),
-            Err(_) => None,
-        };
+        Ok(self
+            .get_diff_for_file_limited(path, usize::MAX)?
+            .map(|diff| diff.text))
+    }
 
-        let diff_index = if let Some(tree) = head_tree.as_ref() {
-            Some(
-                self.repo
-                    .diff_tree_to_index(Some(tree), Some(&self.repo.index()?), Some(&mut diff_opts))
-                    .context("Failed to get index diff")?,
-            )
-        } else {
-            None
-        };
+    /// Get the diff for a specific file, stopping at the first line past
+    /// `max_bytes`.
+    ///
+    /// The patch is streamed line by line, so a multi-megabyte change costs
+    /// no more than the part that's shown. Untracked files are read up to
+    /// the same limit.
+    pub fn get_diff_for_file_limited(
+        &self,
+        path: &str,
+        max_bytes: usize,
+    ) -> Result<Option<FileDiff>> {
+        // Staged and unstaged changes together (HEAD vs working tree), which is
+        // what committing the file records
+        let diff = self.file_diff(path)?;
 
-        // Combine diffs or select relevant one
-        // If we have both, we probably want to show both or prioritize workdir?
-        // Let's format them into a single buffer
         let mut diff_buf = Vec::new();
-
-        // Helper to format a diff into the buffer
-        let print_diff = |diff: &git2::Diff, buf: &mut Vec<u8>| -> Result<()> {
-            diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
-                let origin = line.origin();
-                match origin {
-                    '+' | '-' | ' ' => {
-                        buf.push(origin as u8);
-                    }
-                    _ => {}
+        let mut is_binary = false;
+        let mut truncated = false;
+        let printed = diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+            let origin = line.origin();
+            if origin == 'B' || delta.flags().is_binary() {
+                is_binary = true;
+            }
+            if diff_buf.len() >= max_bytes {
+                truncated = true;
+                return false;
+            }
+            match origin {
+                '+' | '-' | ' ' => {
+                    diff_buf.push(origin as u8);
                 }
-                buf.extend_from_slice(line.content());
-                true
-            })
-            .map_err(|e| anyhow::anyhow!("Diff print error: {e}"))?;
-            Ok(())
-        };
+                _ => {}
+            }
+            diff_buf.extend_from_slice(line.content());
+            true
+        });
+        // Stopping early makes libgit2 report a user error
+        if !truncated {
+            printed.map_err(|e| anyhow::anyhow!("Diff print error: {e}"))?;
+        }
+
+        let full_path = self
+            .repo
+            .workdir()
+            .map_or_else(|| Path::new(path).to_path_buf(), |dir| dir.join(path));
 
-        if let Some(diff) = diff_index {
-            print_diff(&diff, &mut diff_buf)?;
+        // A byte-level diff of a binary file is meaningless, summarize it instead
+        if is_binary {
+            return Ok(Some(FileDiff::whole(Self::describe_binary_change(
+                path, &full_path,
+            ))));
         }
-        print_diff(&diff_workdir, &mut diff_buf)?;
 
         if diff_buf.is_empty() {
             // Might be an untracked file or binary?
             // If it's untracked (New), we might want to just show the file content
-            let path_obj = Path::new(path);
-            if path_obj.exists() && path_obj.is_file() {
+            if full_path.is_file() {
                 // Check if it's untracked
                 let status = self
                     .repo
-                    .status_file(path_obj)
+                    .status_file(Path::new(path))
                     .unwrap_or(git2::Status::empty());
                 if status.contains(git2::Status::WT_NEW) {
-                    return Ok(Some(
-                        std::fs::read_to_string(path_obj)
-                            .unwrap_or_else(|_| "Binary file or unreadable".to_string()),
-                    ));
+                    let sample = crate::utils::binary::read_prefix(
+                        &full_path,
+                        crate::utils::binary::SNIFF_LEN,
+                    )
+                    .unwrap_or_default();
+                    if self.diffs_as_binary(Path::new(path))
+                        || crate::utils::binary::is_binary(&sample)
+                    {
+                        return Ok(Some(FileDiff::whole(Self::describe_binary_change(
+                            path, &full_path,
+                        ))));
+                    }
+                    let Ok(mut content) =
+                        crate::utils::binary::read_prefix(&full_path, max_bytes.saturating_add(1))
+                    else {
+                        return Ok(Some(FileDiff::whole("Unable to read file".to_string())));
+                    };
+                    let truncated = content.len() > max_bytes;
+                    if truncated {
+                        // Whole lines only
+                        let end = content[..max_bytes]
+                            .iter()
+                            .rposition(|&b| b == b'\n')
+                            .map_or(max_bytes, |i| i + 1);
+                        content.truncate(end);
+                    }
+                    if self.normalizes_line_endings(Path::new(path)) {
+                        content = crlf_to_lf(&content);
+                    }
+                    return Ok(Some(FileDiff {
+                        text: String::from_utf8_lossy(&content).to_string(),
+                        truncated,
+                    }));
                 }
             }
             return Ok(None);
         }
 
-        Ok(Some(String::from_utf8_lossy(&diff_buf).to_string()))
+        Ok(Some(FileDiff {
+            text: String::from_utf8_lossy(&diff_buf).to_string(),
+            truncated,
+        }))
+    }
+
+    /// Diff of one file from HEAD to the working tree (including staged
+    /// changes), with standard context.
+    ///
+    /// Hunks are numbered in the order of this diff, both in the preview
+    /// from [`Self::get_diff_for_file`] and when [`Self::commit_paths`]
+    /// leaves hunks out.
+    fn file_diff(&self, path: &str) -> Result<git2::Diff<'_>> {
+        let mut diff_opts = git2::DiffOptions::new();
+        diff_opts.pathspec(path);
+        diff_opts.disable_pathspec_match(true);
+        diff_opts.context_lines(3); // Standard context
+
+        let head_tree = self.repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+        match head_tree {
+            Some(tree) => self
+                .repo
+                .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut diff_opts))
+                .context("Failed to get workdir diff"),
+            None => self
+                .repo
+                .diff_index_to_workdir(None, Some(&mut diff_opts))
+                .context("Failed to get workdir diff"),
+        }
+    }
+
+    /// Value of a `.gitattributes` attribute for `path` (relative to the
+    /// repository root), `Unspecified` when no rule sets it
+    fn attribute(&self, path: &Path, name: &str) -> git2::AttrValue<'_> {
+        self.repo
+            .get_attr(path, name, git2::AttrCheckFlags::FILE_THEN_INDEX)
+            .map_or(git2::AttrValue::Unspecified, git2::AttrValue::from_string)
+    }
+
+    /// Whether `.gitattributes` marks `path` as not diffable (`-diff`, or
+    /// `binary`, which implies it). libgit2 honors this in its own diffs;
+    /// content dotstate reads itself is checked here.
+    pub fn diffs_as_binary(&self, path: &Path) -> bool {
+        self.attribute(path, "diff") == git2::AttrValue::False
+    }
+
+    /// Whether `.gitattributes` stores `path` with Git LFS (`filter=lfs`)
+    fn is_lfs(&self, path: &Path) -> bool {
+        self.attribute(path, "filter") == git2::AttrValue::String("lfs")
+    }
+
+    /// Whether git converts CRLF line endings of `path` to LF when it's
+    /// committed: `text`, `text=auto` or an `eol` setting in `.gitattributes`
+    fn normalizes_line_endings(&self, path: &Path) -> bool {
+        match self.attribute(path, "text") {
+            git2::AttrValue::True => true,
+            git2::AttrValue::String(value) => value == "auto",
+            git2::AttrValue::False => false,
+            _ => matches!(self.attribute(path, "eol"), git2::AttrValue::String(_)),
+        }
+    }
+
+    /// Summary shown in place of a diff for a binary file
+    fn describe_binary_change(path: &str, full_path: &Path) -> String {
+        match crate::utils::binary::read_prefix(full_path, crate::utils::binary::SNIFF_LEN) {
+            Ok(sample) => {
+                let size = std::fs::metadata(full_path).map_or(0, |m| m.len());
+                format!(
+                    "Binary file {path} changed ({})\n\nNo text diff available for binary content.",
+                    crate::utils::binary::describe(&sample, size)
+                )
+            }
+            Err(_) => {
+                format!("Binary file {path} removed\n\nNo text diff available for binary content.")
+            }
+        }
     }
 
     /// Check if a remote exists
@@ -2129,6 +3129,68 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_transfer_progress_label_and_ratio() {
+        let progress = TransferProgress {
+            stage: TransferStage::Receiving,
+            current: 50,
+            total: 200,
+            bytes: 3 * 1024 * 1024 / 2,
+        };
+        assert!((progress.ratio() - 0.25).abs() < f64::EPSILON);
+        assert_eq!(progress.label(), "Receiving objects: 50/200 (1.5 MB)");
+
+        let unknown = TransferProgress {
+            stage: TransferStage::Pushing,
+            current: 0,
+            total: 0,
+            bytes: 0,
+        };
+        assert!(unknown.ratio().abs() < f64::EPSILON);
+        assert_eq!(unknown.label(), "Writing objects: 0/0 (0 B)");
+    }
+
+    #[test]
+    fn test_credential_request_and_output() {
+        assert_eq!(
+            credential_request("https://tok@github.com/me/dotfiles.git", Some("me"), None),
+            "protocol=https\nhost=github.com\npath=me/dotfiles.git\nusername=me\n"
+        );
+        assert_eq!(
+            credential_request("https://git.corp:8443", None, Some("s3cret")),
+            "protocol=https\nhost=git.corp:8443\npassword=s3cret\n"
+        );
+
+        let output = "protocol=https\nhost=github.com\nusername=me\npassword=a=b\n";
+        assert_eq!(
+            parse_credential_output(output),
+            Some(("me".to_string(), "a=b".to_string()))
+        );
+        assert_eq!(parse_credential_output("username=me\n"), None);
+    }
+
+    #[test]
+    fn test_remote_endpoint() {
+        assert_eq!(
+            remote_endpoint("https://ghp_abc@github.com/user/repo.git"),
+            Some(("github.com".to_string(), 443))
+        );
+        assert_eq!(
+            remote_endpoint("https://git.example.com:8443/user/repo.git"),
+            Some(("git.example.com".to_string(), 8443))
+        );
+        assert_eq!(
+            remote_endpoint("git@github.com:user/repo.git"),
+            Some(("github.com".to_string(), 22))
+        );
+        assert_eq!(
+            remote_endpoint("ssh://git@gitlab.com:2222/user/repo.git"),
+            Some(("gitlab.com".to_string(), 2222))
+        );
+        assert_eq!(remote_endpoint("/srv/git/dotfiles.git"), None);
+        assert_eq!(remote_endpoint("file:///srv/git/dotfiles.git"), None);
+    }
+
     #[test]
     fn test_git_init() {
         let temp_dir = TempDir::new().unwrap();
@@ -2188,6 +3250,310 @@ mod tests {
         assert!(msg.contains("Update") || msg.contains("file"));
     }
 
+    #[test]
+    fn test_diff_for_binary_file_is_summarized() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        let git_mgr = GitManager::open_or_init(repo_path).unwrap();
+
+        // Untracked binary file
+        std::fs::write(
+            repo_path.join("icon.png"),
+            b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0d",
+        )
+        .unwrap();
+        let diff = git_mgr.get_diff_for_file("icon.png").unwrap().unwrap();
+        assert!(diff.starts_with("Binary file icon.png changed (PNG image, 12 B)"));
+
+        // Modified tracked binary file
+        git_mgr.commit_all("Add icon").unwrap();
+        std::fs::write(repo_path.join("icon.png"), b"\x89PNG\r\n\x1a\n\x00\x01").unwrap();
+        let diff = git_mgr.get_diff_for_file("icon.png").unwrap().unwrap();
+        assert!(diff.starts_with("Binary file icon.png changed (PNG image, 10 B)"));
+        assert!(!diff.contains("PNG\r"));
+    }
+
+    #[test]
+    fn test_diff_honors_gitattributes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        let git_mgr = GitManager::open_or_init(repo_path).unwrap();
+        std::fs::write(
+            repo_path.join(".gitattributes"),
+            "*.key -diff\n*.bat text eol=crlf\n",
+        )
+        .unwrap();
+        std::fs::write(repo_path.join("id.key"), "secret text\n").unwrap();
+        std::fs::write(repo_path.join("run.bat"), "echo one\r\necho two\r\n").unwrap();
+
+        let diff = git_mgr.get_diff_for_file("id.key").unwrap().unwrap();
+        assert!(diff.starts_with("Binary file id.key changed"));
+        let diff = git_mgr.get_diff_for_file("run.bat").unwrap().unwrap();
+        assert_eq!(diff, "echo one\necho two\n");
+        assert!(git_mgr.diffs_as_binary(Path::new("id.key")));
+        assert!(!git_mgr.diffs_as_binary(Path::new("run.bat")));
+    }
+
+    #[test]
+    fn test_limited_diff_stops_at_whole_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        let git_mgr = GitManager::open_or_init(repo_path).unwrap();
+
+        let lines: Vec<String> = (0..1000).map(|i| format!("export VAR_{i}=1")).collect();
+        std::fs::write(repo_path.join("env"), lines.join("\n") + "\n").unwrap();
+
+        // Untracked: the start of the file, cut at a line end
+        let diff = git_mgr
+            .get_diff_for_file_limited("env", 100)
+            .unwrap()
+            .unwrap();
+        assert!(diff.truncated);
+        assert!(diff.text.len() <= 100 && diff.text.ends_with('\n'));
+
+        // Tracked: the start of the patch
+        git_mgr.commit_all("Add env").unwrap();
+        let edited: Vec<String> = lines.iter().map(|line| format!("{line}0")).collect();
+        std::fs::write(repo_path.join("env"), edited.join("\n") + "\n").unwrap();
+        let diff = git_mgr
+            .get_diff_for_file_limited("env", 1000)
+            .unwrap()
+            .unwrap();
+        assert!(diff.truncated);
+        assert!(diff.text.contains("-export VAR_0=1\n"));
+        assert!(diff.text.len() < 1100);
+
+        let whole = git_mgr
+            .get_diff_for_file_limited("env", usize::MAX)
+            .unwrap()
+            .unwrap();
+        assert!(!whole.truncated);
+        assert!(whole.text.contains("+export VAR_999=10\n"));
+    }
+
+    #[test]
+    fn test_commit_paths_leaves_other_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        let mut git_mgr = GitManager::open_or_init(repo_path).unwrap();
+
+        std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+        std::fs::write(repo_path.join("b.txt"), "b").unwrap();
+        git_mgr.commit_all("Initial commit").unwrap();
+
+        // a and b modified (b already staged), c untracked
+        std::fs::write(repo_path.join("a.txt"), "a2").unwrap();
+        std::fs::write(repo_path.join("b.txt"), "b2").unwrap();
+        std::fs::write(repo_path.join("c.txt"), "c").unwrap();
+        let mut index = git_mgr.repo.index().unwrap();
+        index.add_path(Path::new("b.txt")).unwrap();
+        index.write().unwrap();
+
+        git_mgr
+            .commit_paths(&["a.txt".to_string()], &HashMap::new(), "Update a")
+            .unwrap();
+
+        let mut changed = git_mgr.get_changed_files().unwrap();
+        changed.sort();
+        assert_eq!(changed, ["A c.txt", "M b.txt"]);
+
+        // Stash the rest and bring it back
+        assert!(git_mgr.stash_changes("left out").unwrap());
+        assert!(git_mgr.get_changed_files().unwrap().is_empty());
+        assert!(!git_mgr.stash_changes("nothing").unwrap());
+        git_mgr.stash_pop().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(repo_path.join("b.txt")).unwrap(),
+            "b2"
+        );
+        assert!(repo_path.join("c.txt").exists());
+    }
+
+    #[test]
+    fn test_commit_paths_skips_hunks() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        let git_mgr = GitManager::open_or_init(repo_path).unwrap();
+
+        let original: Vec<String> = (0..20).map(|i| format!("line {i}")).collect();
+        std::fs::write(repo_path.join("zshrc"), original.join("\n") + "\n").unwrap();
+        git_mgr.commit_all("Initial commit").unwrap();
+
+        // Two changes far enough apart to be separate hunks
+        let mut edited = original.clone();
+        edited[1] = "fix".to_string();
+        edited[18] = "experiment".to_string();
+        std::fs::write(repo_path.join("zshrc"), edited.join("\n") + "\n").unwrap();
+
+        let diff = git_mgr.get_diff_for_file("zshrc").unwrap().unwrap();
+        assert_eq!(diff.lines().filter(|l| l.starts_with("@@")).count(), 2);
+
+        let skipped = HashMap::from([("zshrc".to_string(), HashSet::from([1]))]);
+        git_mgr
+            .commit_paths(&["zshrc".to_string()], &skipped, "Fix zshrc")
+            .unwrap();
+
+        // The fix is committed, the experiment stays in the working tree
+        let head_tree = git_mgr.repo.head().unwrap().peel_to_tree().unwrap();
+        let blob = head_tree
+            .get_path(Path::new("zshrc"))
+            .unwrap()
+            .to_object(&git_mgr.repo)
+            .unwrap()
+            .peel_to_blob()
+            .unwrap();
+        let committed = String::from_utf8_lossy(blob.content()).to_string();
+        assert!(committed.contains("fix"));
+        assert!(!committed.contains("experiment"));
+
+        let diff = git_mgr.get_diff_for_file("zshrc").unwrap().unwrap();
+        assert!(diff.contains("+experiment"));
+        assert!(!diff.contains("+fix"));
+    }
+
+    #[test]
+    fn test_force_sync_both_directions() {
+        let temp_dir = TempDir::new().unwrap();
+        let remote_path = temp_dir.path().join("remote.git");
+        Repository::init_bare(&remote_path).unwrap();
+        let repo_path = temp_dir.path().join("local");
+        let git_mgr = GitManager::open_or_init(&repo_path).unwrap();
+        git_mgr
+            .repo
+            .remote("origin", remote_path.to_str().unwrap())
+            .unwrap();
+
+        std::fs::write(repo_path.join("zshrc"), "remote").unwrap();
+        git_mgr.commit_all("Initial commit").unwrap();
+        let branch = git_mgr.get_current_branch().unwrap();
+        git_mgr.push("origin", &branch, None).unwrap();
+
+        // A local commit the remote doesn't have
+        std::fs::write(repo_path.join("zshrc"), "local").unwrap();
+        git_mgr.commit_all("Local commit").unwrap();
+
+        git_mgr.fetch("origin", &branch, None).unwrap();
+        assert_eq!(git_mgr.get_ahead_behind("origin", &branch).unwrap(), (1, 0));
+        assert_eq!(
+            git_mgr
+                .diff_against_remote("origin", &branch, false)
+                .unwrap(),
+            ["M zshrc"]
+        );
+
+        // Force pushing makes the remote take the local commit
+        git_mgr.force_push("origin", &branch, None).unwrap();
+        git_mgr.fetch("origin", &branch, None).unwrap();
+        assert_eq!(git_mgr.get_ahead_behind("origin", &branch).unwrap(), (0, 0));
+
+        // Matching the remote discards tracked changes but keeps untracked files
+        git_mgr.reset_soft_head().unwrap();
+        git_mgr.commit_all("Replacement").unwrap();
+        std::fs::write(repo_path.join("zshrc"), "uncommitted").unwrap();
+        std::fs::write(repo_path.join("new"), "kept").unwrap();
+        git_mgr.reset_hard_to_remote("origin", &branch).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(repo_path.join("zshrc")).unwrap(),
+            "local"
+        );
+        assert!(repo_path.join("new").exists());
+        assert_eq!(git_mgr.get_ahead_behind("origin", &branch).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_file_history_lists_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        let git_mgr = GitManager::open_or_init(repo_path).unwrap();
+
+        std::fs::write(repo_path.join("zshrc"), "one").unwrap();
+        git_mgr.commit_all("Add zshrc").unwrap();
+        std::fs::write(repo_path.join("vimrc"), "other").unwrap();
+        git_mgr.commit_all("Add vimrc").unwrap();
+        std::fs::write(repo_path.join("zshrc"), "two").unwrap();
+        git_mgr.commit_all("Update zshrc").unwrap();
+
+        let versions = git_mgr.file_history("zshrc").unwrap();
+        let summaries: Vec<&str> = versions.iter().map(|v| v.summary.as_str()).collect();
+        assert_eq!(summaries, ["Update zshrc", "Add zshrc"]);
+        assert_eq!(versions[0].content.as_deref(), Some(b"two".as_slice()));
+        assert_eq!(versions[1].content.as_deref(), Some(b"one".as_slice()));
+
+        std::fs::remove_file(repo_path.join("zshrc")).unwrap();
+        git_mgr.commit_all("Remove zshrc").unwrap();
+        let versions = git_mgr.file_history("zshrc").unwrap();
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions[0].content, None);
+    }
+
+    #[test]
+    fn test_reconcile_diverged_branches() {
+        use crate::utils::reconcile;
+
+        let temp_dir = TempDir::new().unwrap();
+        let remote_path = temp_dir.path().join("remote.git");
+        Repository::init_bare(&remote_path).unwrap();
+        let repo_path = temp_dir.path().join("local");
+        let git_mgr = GitManager::open_or_init(&repo_path).unwrap();
+        git_mgr
+            .repo
+            .remote("origin", remote_path.to_str().unwrap())
+            .unwrap();
+
+        std::fs::write(repo_path.join("zshrc"), "1\n2\n3\n").unwrap();
+        std::fs::write(repo_path.join("vimrc"), "same").unwrap();
+        git_mgr.commit_all("Initial commit").unwrap();
+        let initial = git_mgr.head_oid().unwrap();
+        let branch = git_mgr.get_current_branch().unwrap();
+
+        // Another machine changes the last line and pushes
+        std::fs::write(repo_path.join("zshrc"), "1\n2\nR\n").unwrap();
+        git_mgr.commit_all("Remote commit").unwrap();
+        git_mgr.push("origin", &branch, None).unwrap();
+
+        // This machine changed the first line from the initial commit
+        let initial_commit = git_mgr.repo.find_commit(initial).unwrap();
+        git_mgr
+            .repo
+            .reset(initial_commit.as_object(), git2::ResetType::Hard, None)
+            .unwrap();
+        std::fs::write(repo_path.join("zshrc"), "L\n2\n3\n").unwrap();
+        git_mgr.commit_all("Local commit").unwrap();
+        std::fs::write(repo_path.join("gitconfig"), "uncommitted").unwrap();
+
+        git_mgr.fetch("origin", &branch, None).unwrap();
+        assert_eq!(git_mgr.get_ahead_behind("origin", &branch).unwrap(), (1, 1));
+        let files = git_mgr.diverged_files("origin", &branch).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["gitconfig", "zshrc"]);
+        assert_eq!(files[0].remote, None);
+        assert_eq!(files[1].base.as_deref(), Some(b"1\n2\n3\n".as_slice()));
+
+        let resolved: Vec<(String, Option<Vec<u8>>)> = files
+            .iter()
+            .map(|file| {
+                let merged = reconcile::merge(file).unwrap();
+                let choice = reconcile::default_choice(file, merged.as_ref());
+                let content = reconcile::resolve(file, choice, merged.as_ref());
+                (file.path.clone(), content.map(<[u8]>::to_vec))
+            })
+            .collect();
+        reconcile::apply(&repo_path, &resolved).unwrap();
+        git_mgr
+            .commit_merge("origin", &branch, "Reconcile")
+            .unwrap();
+
+        // The merge contains the remote commit, so a normal push fast-forwards
+        git_mgr.push("origin", &branch, None).unwrap();
+        git_mgr.fetch("origin", &branch, None).unwrap();
+        assert_eq!(git_mgr.get_ahead_behind("origin", &branch).unwrap(), (0, 0));
+        assert_eq!(
+            std::fs::read_to_string(repo_path.join("zshrc")).unwrap(),
+            "L\n2\nR\n"
+        );
+        assert!(!git_mgr.has_uncommitted_changes().unwrap());
+    }
+
     #[test]
     fn test_generate_commit_message_multiple_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::io::Write;
@@ -13,16 +14,63 @@ pub struct GitHubClient {
 #[derive(Debug, Deserialize)]
 pub struct GitHubUser {
     pub login: String,
+    /// Scopes of a classic token (from `X-OAuth-Scopes`). `None` for
+    /// fine-grained tokens, whose permissions GitHub doesn't list.
+    #[serde(skip)]
+    pub scopes: Option<Vec<String>>,
+}
+
+/// What the authenticated user may do with a repository
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub struct RepoPermissions {
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(default)]
+    pub push: bool,
+    #[serde(default)]
+    pub pull: bool,
 }
 
 #[derive(Debug, Deserialize)]
+struct RepoPermissionsResponse {
+    #[serde(default)]
+    permissions: Option<RepoPermissions>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct GitHubRepo {
-    #[allow(dead_code)]
     pub name: String,
-    #[allow(dead_code)]
     pub full_name: String,
     #[allow(dead_code)]
     pub default_branch: String,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Whether a token is still accepted by GitHub
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenStatus {
+    /// GitHub rejected the token (401): expired, revoked or mistyped
+    Rejected,
+    /// The token works. Fine-grained and expiring classic tokens report
+    /// when they expire.
+    Valid { expires_at: Option<DateTime<Utc>> },
+}
+
+/// Parse GitHub's `github-authentication-token-expiration` header
+/// (e.g. `2026-11-01 09:30:00 UTC` or `2026-11-01 09:30:00 +0200`).
+fn parse_token_expiration(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Some(naive) = value.strip_suffix(" UTC") {
+        return NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|dt| dt.and_utc());
+    }
+    DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z")
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
 }
 
 #[derive(Debug, Serialize)]
@@ -39,7 +87,7 @@ impl GitHubClient {
     #[must_use]
     pub fn new(token: String) -> Self {
         Self {
-            http_client: Client::new(),
+            http_client: crate::utils::proxy::http_client(),
             token,
         }
     }
@@ -49,39 +97,24 @@ impl GitHubClient {
         let url = "https://api.github.com/user";
         let auth_header = format!("token {}", self.token);
 
-        // Log request details (mask token for security)
-        let token_preview = if self.token.len() > 8 {
-            format!(
-                "{}...{}",
-                &self.token[..4],
-                &self.token[self.token.len() - 4..]
-            )
-        } else {
-            "***".to_string()
-        };
-
+        // Log request details. Never the token or header value; the log
+        // file also scrubs anything that looks like a credential.
         info!("=== GitHub API Request ===");
         info!("URL: {}", url);
         info!("Method: GET");
-        info!("Token preview: {}", token_preview);
         info!("Token length: {} characters", self.token.len());
         info!(
-            "Token starts with: {}",
+            "Token type: {}",
             if self.token.starts_with("ghp_") {
-                "ghp_ (classic)"
+                "classic"
             } else if self.token.starts_with("github_pat_") {
-                "github_pat_ (fine-grained)"
+                "fine-grained"
             } else {
-                &self.token[..self.token.len().min(4)]
+                "unknown"
             }
         );
-        info!("Authorization header: token {}", token_preview);
         info!("User-Agent: dotstate");
         info!("Accept: application/vnd.github.v3+json");
-        info!(
-            "Full auth header value (first 20 chars): {}",
-            &auth_header[..auth_header.len().min(20)]
-        );
 
         let request = self
             .http_client
@@ -139,14 +172,71 @@ impl GitHubClient {
             anyhow::bail!("GitHub API error ({status}): {error_text}");
         }
 
-        let user: GitHubUser = response
+        let scopes = response
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|scope| !scope.is_empty())
+                    .map(String::from)
+                    .collect()
+            });
+
+        let mut user: GitHubUser = response
             .json()
             .await
             .context("Failed to parse user response. The token may be invalid.")?;
+        user.scopes = scopes;
 
         Ok(user)
     }
 
+    /// The token's permissions on a repository, or `None` if the token can't
+    /// see it (missing or private without access).
+    pub async fn repo_permissions(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> Result<Option<RepoPermissions>> {
+        let url = format!("https://api.github.com/repos/{owner}/{repo}");
+        let response = self
+            .http_client
+            .get(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .header("User-Agent", "dotstate")
+            .header("Accept", "application/vnd.github.v3+json")
+            .send()
+            .await
+            .context("Failed to check repository")?;
+
+        let status = response.status();
+        info!("Repository permissions check status: {}", status);
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!("Failed to check repository ({status}): {error_text}");
+        }
+
+        let body: RepoPermissionsResponse = response
+            .json()
+            .await
+            .context("Failed to parse repository response")?;
+        // Not reported: don't block here, a failed push will explain
+        Ok(Some(body.permissions.unwrap_or(RepoPermissions {
+            admin: false,
+            push: true,
+            pull: true,
+        })))
+    }
+
     /// Check if a repository exists
     pub async fn repo_exists(&self, owner: &str, repo: &str) -> Result<bool> {
         let url = format!("https://api.github.com/repos/{owner}/{repo}");
@@ -182,6 +272,84 @@ impl GitHubClient {
         Ok(true)
     }
 
+    /// Check whether the token is still accepted, and when it expires.
+    ///
+    /// Uses the repository endpoint so scoped tokens without user access
+    /// work. Only a 401 counts as rejected; a missing repository is reported
+    /// elsewhere.
+    pub async fn token_status(&self, owner: &str, repo: &str) -> Result<TokenStatus> {
+        let url = format!("https://api.github.com/repos/{owner}/{repo}");
+        let response = self
+            .http_client
+            .get(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .header("User-Agent", "dotstate")
+            .header("Accept", "application/vnd.github.v3+json")
+            .send()
+            .await
+            .context("Failed to check token")?;
+
+        let status = response.status();
+        debug!("Token check status: {}", status);
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(TokenStatus::Rejected);
+        }
+        if status.is_server_error() {
+            anyhow::bail!("GitHub API error ({status})");
+        }
+
+        let expires_at = response
+            .headers()
+            .get("github-authentication-token-expiration")
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_token_expiration);
+        Ok(TokenStatus::Valid { expires_at })
+    }
+
+    /// List repositories owned by the authenticated user, most recently
+    /// pushed first.
+    pub async fn list_repos(&self) -> Result<Vec<GitHubRepo>> {
+        const PER_PAGE: usize = 100;
+        const MAX_PAGES: usize = 10;
+
+        let mut repos = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let url = format!(
+                "https://api.github.com/user/repos?affiliation=owner&sort=pushed&per_page={PER_PAGE}&page={page}"
+            );
+            let response = self
+                .http_client
+                .get(&url)
+                .header("Authorization", format!("token {}", self.token))
+                .header("User-Agent", "dotstate")
+                .header("Accept", "application/vnd.github.v3+json")
+                .send()
+                .await
+                .context("Failed to list repositories")?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let error_text = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                anyhow::bail!("Failed to list repositories ({status}): {error_text}");
+            }
+
+            let batch: Vec<GitHubRepo> = response
+                .json()
+                .await
+                .context("Failed to parse repository list")?;
+            let done = batch.len() < PER_PAGE;
+            repos.extend(batch);
+            if done {
+                break;
+            }
+        }
+        info!("Listed {} repositories", repos.len());
+        Ok(repos)
+    }
+
     /// Create a new repository
     pub async fn create_repo(
         &self,
@@ -198,20 +366,10 @@ impl GitHubClient {
 
         let url = "https://api.github.com/user/repos";
         let auth_header = format!("token {}", self.token);
-        let token_preview = if self.token.len() > 8 {
-            format!(
-                "{}...{}",
-                &self.token[..4],
-                &self.token[self.token.len() - 4..]
-            )
-        } else {
-            "***".to_string()
-        };
 
         info!("=== GitHub API Request (Create Repo) ===");
         info!("URL: {}", url);
         info!("Method: POST");
-        info!("Authorization header: token {}", token_preview);
         info!("User-Agent: dotstate");
         info!("Accept: application/vnd.github.v3+json");
         info!(
@@ -283,7 +441,7 @@ pub async fn authenticate_with_pat() -> Result<String> {
     }
 
     // Verify token works
-    let client = Client::new();
+    let client = crate::utils::proxy::http_client();
     let response = client
         .get("https://api.github.com/user")
         .header("Authorization", format!("Bearer {token}"))
@@ -298,3 +456,23 @@ pub async fn authenticate_with_pat() -> Result<String> {
 
     Ok(token)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_token_expiration() {
+        let expected = Utc.with_ymd_and_hms(2026, 11, 1, 9, 30, 0).unwrap();
+        assert_eq!(
+            parse_token_expiration("2026-11-01 09:30:00 UTC"),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_token_expiration("2026-11-01 11:30:00 +0200"),
+            Some(expected)
+        );
+        assert_eq!(parse_token_expiration("soon"), None);
+    }
+}
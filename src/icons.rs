@@ -356,6 +356,66 @@ impl Icons {
         }
     }
 
+    #[must_use]
+    pub fn health(&self) -> &'static str {
+        match self.icon_set {
+            IconSet::NerdFonts => "\u{f21e}", // Heartbeat
+            IconSet::Unicode => "♥",
+            IconSet::Emoji => "🩺",
+            IconSet::Ascii => "[+]",
+        }
+    }
+
+    #[must_use]
+    pub fn machine(&self) -> &'static str {
+        match self.icon_set {
+            IconSet::NerdFonts => "\u{f108}", // Desktop
+            IconSet::Unicode => "▣",
+            IconSet::Emoji => "💻",
+            IconSet::Ascii => "[PC]",
+        }
+    }
+
+    #[must_use]
+    pub fn terminal(&self) -> &'static str {
+        match self.icon_set {
+            IconSet::NerdFonts => "\u{f120}", // Terminal
+            IconSet::Unicode => "❯",
+            IconSet::Emoji => "🐚",
+            IconSet::Ascii => "[$]",
+        }
+    }
+
+    #[must_use]
+    pub fn history(&self) -> &'static str {
+        match self.icon_set {
+            IconSet::NerdFonts => "\u{f1da}", // History
+            IconSet::Unicode => "⟲",
+            IconSet::Emoji => "🕘",
+            IconSet::Ascii => "[H]",
+        }
+    }
+
+    #[must_use]
+    pub fn chart(&self) -> &'static str {
+        match self.icon_set {
+            IconSet::NerdFonts => "\u{f080}", // Bar chart
+            IconSet::Unicode => "▤",
+            IconSet::Emoji => "📊",
+            IconSet::Ascii => "[#]",
+        }
+    }
+
+    #[must_use]
+    pub fn bell(&self) -> &'static str {
+        match self.icon_set {
+            IconSet::NerdFonts => "\u{f0f3}", // Bell
+            IconSet::Unicode => "◈",
+            IconSet::Emoji => "🔔",
+            IconSet::Ascii => "[!]",
+        }
+    }
+
     #[must_use]
     pub fn cog(&self) -> &'static str {
         match self.icon_set {
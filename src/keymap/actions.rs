@@ -47,6 +47,8 @@ pub enum Action {
     Quit,
     /// Show help overlay
     Help,
+    /// Show or hide the side panes on a narrow terminal
+    TogglePreview,
 
     // ============ Screen-specific actions ============
     /// Delete selected item
@@ -61,6 +63,16 @@ pub enum Action {
     Refresh,
     /// Sync with remote
     Sync,
+    /// Open force sync (overwrite local or remote)
+    ForceSync,
+    /// Pull from remote without committing or pushing
+    Pull,
+    /// Show the decrypted preview of an encrypted file
+    Unlock,
+    /// Show or mask secrets in previews and diffs
+    RevealSecrets,
+    /// Browse the git history of a file
+    Timeline,
     /// Check status (e.g., package installation status)
     CheckStatus,
     /// Install selected item
@@ -69,6 +81,12 @@ pub enum Action {
     Import,
     /// Move selected item to common
     Move,
+    /// Repeat the last action on the selected item
+    Repeat,
+    /// Re-create a replaced or broken symlink
+    Relink,
+    /// Keep the file found in place of a symlink, moving it into the repository
+    Adopt,
 
     // ============ Text editing ============
     /// Delete character before cursor
@@ -123,13 +141,22 @@ impl Action {
             Action::DeselectAll => "Deselect all",
             Action::Quit => "Quit",
             Action::Help => "Show help",
+            Action::TogglePreview => "Show/hide preview (narrow terminals)",
             Action::Delete => "Delete",
             Action::Edit => "Edit",
             Action::Create => "Create new",
             Action::Search => "Search",
             Action::Move => "Move",
+            Action::Repeat => "Repeat last action",
+            Action::Relink => "Relink symlink",
+            Action::Adopt => "Adopt replaced file",
             Action::Refresh => "Refresh",
             Action::Sync => "Sync with remote",
+            Action::ForceSync => "Force sync",
+            Action::Pull => "Pull from remote",
+            Action::Unlock => "Unlock encrypted preview",
+            Action::Timeline => "File timeline",
+            Action::RevealSecrets => "Show/mask secrets in previews",
             Action::CheckStatus => "Check status",
             Action::Install => "Install",
             Action::Import => "Import from system",
@@ -167,7 +194,7 @@ impl Action {
             | Action::SelectAll
             | Action::DeselectAll => "Selection",
 
-            Action::Quit | Action::Help => "Global",
+            Action::Quit | Action::Help | Action::TogglePreview => "Global",
 
             Action::Delete
             | Action::Edit
@@ -175,7 +202,15 @@ impl Action {
             | Action::Search
             | Action::Refresh
             | Action::Move
+            | Action::Repeat
+            | Action::Relink
+            | Action::Adopt
             | Action::Sync
+            | Action::ForceSync
+            | Action::Pull
+            | Action::Unlock
+            | Action::RevealSecrets
+            | Action::Timeline
             | Action::CheckStatus
             | Action::Install
             | Action::Import => "Actions",
@@ -3,6 +3,7 @@
 //! Provides parsing of key strings like "ctrl+n", "shift+tab", "j"
 
 use super::Action;
+#[cfg(feature = "tui")]
 use crossterm::event::{KeyCode, KeyModifiers};
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +22,7 @@ pub struct KeyBinding {
 }
 
 /// Parsed key representation for matching
+#[cfg(feature = "tui")]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedKey {
     pub code: KeyCode,
@@ -39,6 +41,7 @@ impl KeyBinding {
     }
 
     /// Check if this binding matches the given key event
+    #[cfg(feature = "tui")]
     #[must_use]
     pub fn matches(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
         if let Ok(parsed) = self.parse() {
@@ -51,6 +54,7 @@ impl KeyBinding {
     }
 
     /// Parse the key string into `KeyCode` and `KeyModifiers`
+    #[cfg(feature = "tui")]
     pub fn parse(&self) -> Result<ParsedKey, String> {
         parse_key_string(&self.key)
     }
@@ -77,6 +81,7 @@ impl KeyBinding {
 /// - Some terminals send Char('G') + NONE for Shift+G (just uppercase, no modifier)
 ///
 /// We normalize both to Char('g') + SHIFT for consistent matching.
+#[cfg(feature = "tui")]
 fn normalize_shift_char(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
     if let KeyCode::Char(c) = code {
         if c.is_ascii_uppercase() {
@@ -91,6 +96,7 @@ fn normalize_shift_char(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, Key
 }
 
 /// Parse a key string like "ctrl+shift+n" into `KeyCode` and `KeyModifiers`
+#[cfg(feature = "tui")]
 pub fn parse_key_string(key: &str) -> Result<ParsedKey, String> {
     let key = key.trim().to_lowercase();
     let parts: Vec<&str> = key.split('+').collect();
@@ -120,6 +126,7 @@ pub fn parse_key_string(key: &str) -> Result<ParsedKey, String> {
 }
 
 /// Parse a single key name into `KeyCode`
+#[cfg(feature = "tui")]
 fn parse_key_code(key: &str) -> Result<KeyCode, String> {
     let key = key.trim().to_lowercase();
 
@@ -209,7 +216,7 @@ pub fn format_key_display(key: &str) -> String {
     formatted.join("+")
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tui"))]
 mod tests {
     use super::*;
     use crate::keymap::Action;
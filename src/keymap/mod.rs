@@ -12,6 +12,7 @@ pub use actions::Action;
 pub use binding::KeyBinding;
 pub use presets::KeymapPreset;
 
+#[cfg(feature = "tui")]
 use crossterm::event::{KeyCode, KeyModifiers};
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +40,7 @@ impl Default for Keymap {
 impl Keymap {
     /// Get the action for a key event, checking overrides first then preset
     /// Note: If an action is overridden, preset bindings for that action are ignored
+    #[cfg(feature = "tui")]
     #[must_use]
     pub fn get_action(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
         // Use all_bindings which already handles override shadowing
@@ -142,7 +144,7 @@ impl Keymap {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tui"))]
 mod tests {
     use super::*;
 
@@ -62,6 +62,7 @@ fn standard_bindings() -> Vec<KeyBinding> {
         KeyBinding::new("q", Action::Quit),
         KeyBinding::new("ctrl+c", Action::Quit),
         KeyBinding::new("?", Action::Help),
+        KeyBinding::new("ctrl+w", Action::TogglePreview),
         // Actions
         KeyBinding::new("d", Action::Delete),
         KeyBinding::new("e", Action::Edit),
@@ -70,11 +71,19 @@ fn standard_bindings() -> Vec<KeyBinding> {
         KeyBinding::new("r", Action::Refresh),
         KeyBinding::new("s", Action::CheckStatus),
         KeyBinding::new("shift+s", Action::Sync),
+        KeyBinding::new("shift+f", Action::ForceSync),
+        KeyBinding::new("shift+p", Action::Pull),
+        KeyBinding::new("shift+u", Action::Unlock),
+        KeyBinding::new("shift+r", Action::RevealSecrets),
+        KeyBinding::new("t", Action::Timeline),
         KeyBinding::new("i", Action::Install),
         KeyBinding::new("shift+i", Action::Import),
         KeyBinding::new("ctrl+s", Action::Save),
         KeyBinding::new("b", Action::ToggleBackup),
         KeyBinding::new("m", Action::Move),
+        KeyBinding::new(".", Action::Repeat),
+        KeyBinding::new("f", Action::Relink),
+        KeyBinding::new("a", Action::Adopt),
         // Text editing
         KeyBinding::new("backspace", Action::Backspace),
         KeyBinding::new("delete", Action::DeleteChar),
@@ -120,6 +129,7 @@ fn vim_bindings() -> Vec<KeyBinding> {
         KeyBinding::new("q", Action::Quit),
         KeyBinding::new("ctrl+c", Action::Quit),
         KeyBinding::new("?", Action::Help),
+        KeyBinding::new("ctrl+w", Action::TogglePreview),
         // Actions
         KeyBinding::new("d", Action::Delete),
         KeyBinding::new("e", Action::Edit),
@@ -128,11 +138,19 @@ fn vim_bindings() -> Vec<KeyBinding> {
         KeyBinding::new("r", Action::Refresh),
         KeyBinding::new("s", Action::CheckStatus),
         KeyBinding::new("shift+s", Action::Sync),
+        KeyBinding::new("shift+f", Action::ForceSync),
+        KeyBinding::new("shift+p", Action::Pull),
+        KeyBinding::new("shift+u", Action::Unlock),
+        KeyBinding::new("shift+r", Action::RevealSecrets),
+        KeyBinding::new("t", Action::Timeline),
         KeyBinding::new("i", Action::Install),
         KeyBinding::new("shift+i", Action::Import),
         KeyBinding::new("ctrl+s", Action::Save),
         KeyBinding::new("b", Action::ToggleBackup),
         KeyBinding::new("m", Action::Move),
+        KeyBinding::new(".", Action::Repeat),
+        KeyBinding::new("f", Action::Relink),
+        KeyBinding::new("a", Action::Adopt),
         // Text editing
         KeyBinding::new("backspace", Action::Backspace),
         KeyBinding::new("x", Action::DeleteChar), // vim style delete char
@@ -182,6 +200,7 @@ fn emacs_bindings() -> Vec<KeyBinding> {
         KeyBinding::new("ctrl+c", Action::Quit),
         KeyBinding::new("ctrl+h", Action::Help),
         KeyBinding::new("?", Action::Help),
+        KeyBinding::new("ctrl+w", Action::TogglePreview),
         // Actions
         KeyBinding::new("d", Action::Delete), // Use 'd' since Ctrl+D is DeleteChar in Emacs
         KeyBinding::new("ctrl+e", Action::Edit),
@@ -190,12 +209,20 @@ fn emacs_bindings() -> Vec<KeyBinding> {
         KeyBinding::new("ctrl+r", Action::Refresh),
         KeyBinding::new("ctrl+r", Action::Refresh),
         KeyBinding::new("ctrl+x s", Action::Sync), // Note: multi-key not supported yet
+        KeyBinding::new("shift+f", Action::ForceSync),
+        KeyBinding::new("shift+p", Action::Pull),
+        KeyBinding::new("shift+u", Action::Unlock),
+        KeyBinding::new("shift+r", Action::RevealSecrets),
+        KeyBinding::new("t", Action::Timeline),
         KeyBinding::new("s", Action::CheckStatus),
         KeyBinding::new("i", Action::Install),
         KeyBinding::new("shift+i", Action::Import),
         KeyBinding::new("ctrl+s", Action::Save),
         KeyBinding::new("b", Action::ToggleBackup), // Use 'b' since Ctrl+B is MoveLeft in Emacs
         KeyBinding::new("m", Action::Move),
+        KeyBinding::new(".", Action::Repeat),
+        KeyBinding::new("f", Action::Relink),
+        KeyBinding::new("a", Action::Adopt),
         // Text editing
         KeyBinding::new("backspace", Action::Backspace),
         KeyBinding::new("ctrl+d", Action::DeleteChar), // Forward delete (Emacs standard)
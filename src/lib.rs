@@ -2,10 +2,48 @@
 //!
 //! This library provides the core functionality for managing dotfiles,
 //! syncing with git repositories, and managing profiles.
+//!
+//! # Embedding
+//!
+//! Other tools can manage dotfiles through the same operations the TUI and
+//! the CLI use: [`ProfileService`], [`SyncService`] and [`GitService`], all
+//! working from a [`Config`] and the repository's [`ProfileManifest`]. Build
+//! with `default-features = false` to leave out the `tui` feature, and with
+//! it the terminal UI, the CLI, ratatui and crossterm:
+//!
+//! ```toml
+//! dotstate = { version = "0.4", default-features = false }
+//! ```
+//!
+//! ```no_run
+//! use dotstate::{Config, GitService, ProfileManifest, ProfileService, SyncService};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let config = Config::load_or_create(&dotstate::utils::get_config_path())?;
+//!
+//! // Track a file in the active profile, then commit, pull and push
+//! let home = dotstate::utils::get_home_dir();
+//! SyncService::add_file_to_sync(&config, &home.join(".zshrc"), ".zshrc", true)?;
+//! let result = GitService::sync(&config);
+//! println!("{}", result.message);
+//!
+//! // Create the profile's symlinks
+//! ProfileService::activate_profile(&config.repo_path, &config.active_profile, true)?;
+//!
+//! let manifest = ProfileManifest::load(&config.repo_path)?;
+//! for profile in &manifest.profiles {
+//!     println!("{}: {} files", profile.name, profile.synced_files.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
 
 // Core modules
+#[cfg(feature = "tui")]
 pub mod app;
+#[cfg(feature = "tui")]
 pub mod cli;
+#[cfg(feature = "tui")]
 pub mod components;
 pub mod config;
 pub mod dotfile_candidates;
@@ -14,18 +52,24 @@ pub mod git;
 pub mod github;
 pub mod icons;
 pub mod keymap;
+#[cfg(feature = "tui")]
 pub mod screens;
 pub mod services;
+#[cfg(feature = "tui")]
 pub mod styles;
+#[cfg(feature = "tui")]
 pub mod tui;
+#[cfg(feature = "tui")]
 pub mod ui;
 pub mod utils;
 pub mod version_check;
+#[cfg(feature = "tui")]
 pub mod widgets;
 
 // Re-exports for convenience
 pub use config::Config;
 pub use file_manager::FileManager;
+pub use services::{GitService, ProfileService, SyncService};
 pub use utils::ProfileManifest;
 pub use utils::SymlinkManager;
 
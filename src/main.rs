@@ -3,7 +3,14 @@ mod keymap;
 use clap::Parser;
 
 // Import from library instead of declaring modules
-use dotstate::{app::App, cli::Cli};
+use dotstate::app::App;
+use dotstate::cli::{Cli, Commands};
+use dotstate::utils::log_redaction::RedactingMakeWriter;
+use dotstate::utils::state_migration::MigrationReport;
+use dotstate::utils::trace_export::{self, TraceExport};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
 
 /// Set up panic hook to restore terminal state on panic
 fn setup_panic_hook() {
@@ -22,6 +29,77 @@ fn setup_panic_hook() {
     }));
 }
 
+/// Bring state files written by older versions up to date, returning what
+/// changed. Failures are reported but never block startup.
+fn migrate_state() -> Option<MigrationReport> {
+    match dotstate::utils::state_migration::migrate_state() {
+        Ok(report) if !report.is_empty() => {
+            tracing::info!("State migration: {}", report.summary());
+            Some(report)
+        }
+        Ok(_) => None,
+        Err(e) => {
+            tracing::warn!("Failed to migrate state files: {}", e);
+            None
+        }
+    }
+}
+
+/// Commands run from the shell prompt, by scripts reading a screen, or in the
+/// background: state is migrated by the next interactive command instead, so
+/// nothing is printed into their output
+fn skips_migration(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Prompt { .. } | Commands::Show { .. } | Commands::Daemon
+    )
+}
+
+/// Log to `dotstate.log` in the cache directory and, with `--trace`, record
+/// spans for the trace. The guard flushes the log when dropped.
+fn init_logging(
+    trace: Option<&str>,
+) -> Result<(
+    tracing_appender::non_blocking::WorkerGuard,
+    Option<TraceExport>,
+)> {
+    let log_dir = dotstate::utils::get_cache_dir();
+    std::fs::create_dir_all(&log_dir)?;
+
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+
+    // Write to file
+    let file_appender = tracing_appender::rolling::never(&log_dir, "dotstate.log");
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+
+    let (trace_layer, export) = trace.map(trace_export::start).unzip();
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_target(false)
+                .with_writer(RedactingMakeWriter::new(non_blocking))
+                .with_ansi(false) // Disable ANSI colors in file
+                .with_filter(filter),
+        )
+        .with(
+            trace_layer.map(|layer| layer.with_filter(tracing::level_filters::LevelFilter::DEBUG)),
+        )
+        .init();
+    Ok((guard, export))
+}
+
+/// Write the spans recorded for `--trace`
+fn finish_trace(export: Option<TraceExport>) {
+    let Some(export) = export else {
+        return;
+    };
+    match export.finish() {
+        Ok(message) => eprintln!("{message}"),
+        Err(e) => eprintln!("Failed to export the trace: {e:#}"),
+    }
+}
+
 fn main() -> Result<()> {
     // Set up panic hook to restore terminal on panic
     setup_panic_hook();
@@ -32,56 +110,36 @@ fn main() -> Result<()> {
     // If a command was provided, execute it and exit (non-TUI mode)
     if cli.command.is_some() {
         // Set up logging for CLI mode
-        let log_dir = dirs::cache_dir()
-            .unwrap_or_else(|| dirs::home_dir().unwrap_or_default())
-            .join("dotstate");
-        std::fs::create_dir_all(&log_dir)?;
-
-        let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
-
-        let file_appender = tracing_appender::rolling::never(&log_dir, "dotstate.log");
-        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-
-        tracing_subscriber::fmt()
-            .with_env_filter(filter)
-            .with_target(false)
-            .with_writer(non_blocking)
-            .with_ansi(false)
-            .init();
+        let (guard, trace) = init_logging(cli.trace.as_deref())?;
 
         use tracing::info;
         info!("Starting dotstate CLI mode");
+        let migration = cli
+            .command
+            .as_ref()
+            .filter(|command| !skips_migration(command))
+            .and_then(|_| migrate_state());
+        if let Some(report) = migration {
+            eprintln!("dotstate: {}", report.summary());
+        }
         let result = cli.execute();
+        if let Err(e) = &result {
+            // Kept for `dotstate report`
+            dotstate::utils::error_report::record_error(&format!("{e:#}"));
+        }
+        finish_trace(trace);
         drop(guard);
         return result;
     }
 
     // Otherwise, launch TUI
-    // Set up logging directory
-    let log_dir = dirs::cache_dir()
-        .unwrap_or_else(|| dirs::home_dir().unwrap_or_default())
-        .join("dotstate");
-    std::fs::create_dir_all(&log_dir)?;
-
     // Initialize tracing with file logging
-    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
-
-    // Write to file
-    let file_appender = tracing_appender::rolling::never(&log_dir, "dotstate.log");
-    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .with_writer(non_blocking)
-        .with_ansi(false) // Disable ANSI colors in file
-        .init();
+    let (guard, trace) = init_logging(cli.trace.as_deref())?;
 
     use tracing::info;
     info!("Starting dotstate TUI mode");
-    info!("Log directory: {:?}", log_dir);
+    info!("Log directory: {:?}", dotstate::utils::get_cache_dir());
+    let migration = migrate_state();
 
     // Load config to get theme preference
     let config_path = dotstate::utils::get_config_path();
@@ -109,12 +167,20 @@ fn main() -> Result<()> {
         config_theme_type
     };
     dotstate::styles::init_theme(theme_type);
+    dotstate::styles::set_compact(config.compact);
     info!("Theme initialized: {:?}", theme_type);
 
     let mut app = App::new()?;
+    if let Some(screen) = cli.screen {
+        app.start_on(screen.menu_item());
+    }
+    if let Some(report) = migration {
+        app.notify(report.summary(), report.failed.is_empty());
+    }
     let result = app.run();
 
     info!("Shutting down dotstate");
+    finish_trace(trace);
 
     // Restore terminal state on normal exit
     // (panic hook handles panics)
@@ -0,0 +1,714 @@
+//! Aliases screen controller.
+//!
+//! Edits the shell aliases kept in `aliases.toml` of the active profile,
+//! common and the other profiles. Saving regenerates the shell snippet
+//! loader, so new shells get the aliases of the active profile.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::aliases::{self, AliasFile, ResolvedAlias};
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, shell_snippets,
+    unfocused_border_style, MouseRegions, TextInput,
+};
+use crate::widgets::{Dialog, DialogVariant, TextInputWidget, TextInputWidgetExt, ToastVariant};
+use anyhow::Result;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+use std::collections::BTreeMap;
+
+/// Directory of the aliases shared by every profile
+const COMMON: &str = "common";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditorField {
+    Name,
+    Command,
+}
+
+/// The add/edit popup
+struct AliasEditor {
+    /// Name of the alias being edited, `None` when adding
+    original: Option<String>,
+    name: TextInput,
+    command: TextInput,
+    field: EditorField,
+    error: Option<String>,
+}
+
+impl AliasEditor {
+    fn focused_input(&mut self) -> &mut TextInput {
+        match self.field {
+            EditorField::Name => &mut self.name,
+            EditorField::Command => &mut self.command,
+        }
+    }
+}
+
+/// Aliases screen controller.
+pub struct AliasesScreen {
+    /// Where aliases can live: the active profile, common, then the others
+    scopes: Vec<String>,
+    scope: usize,
+    /// Aliases of the selected scope
+    entries: Vec<(String, String)>,
+    /// Aliases in effect for the active profile
+    effective: BTreeMap<String, ResolvedAlias>,
+    active_profile: String,
+    /// Whether an rc file sources the snippet loader
+    rc_sourced: bool,
+    error: Option<String>,
+    editor: Option<AliasEditor>,
+    /// Alias waiting for delete confirmation
+    confirm_delete: Option<String>,
+    list_state: ListState,
+    /// Clickable rows in the alias list (value = row index)
+    row_regions: MouseRegions<usize>,
+    /// Alias list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+}
+
+impl Default for AliasesScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AliasesScreen {
+    /// Create a new aliases screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            scopes: Vec::new(),
+            scope: 0,
+            entries: Vec::new(),
+            effective: BTreeMap::new(),
+            active_profile: String::new(),
+            rc_sourced: false,
+            error: None,
+            editor: None,
+            confirm_delete: None,
+            list_state: ListState::default(),
+            row_regions: MouseRegions::new(),
+            list_area: None,
+        }
+    }
+
+    /// Load the scopes and the selected scope's aliases.
+    fn load(&mut self, config: &Config) {
+        let current = self.scopes.get(self.scope).cloned();
+        self.active_profile.clone_from(&config.active_profile);
+        let others = crate::utils::ProfileManifest::load_or_backfill(&config.repo_path)
+            .map(|m| m.profiles.into_iter().map(|p| p.name).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        self.scopes.clear();
+        if !self.active_profile.is_empty() {
+            self.scopes.push(self.active_profile.clone());
+        }
+        self.scopes.push(COMMON.to_string());
+        self.scopes.extend(
+            others
+                .into_iter()
+                .filter(|name| *name != self.active_profile),
+        );
+        self.scope = current
+            .and_then(|c| self.scopes.iter().position(|s| *s == c))
+            .unwrap_or(0);
+
+        self.rc_sourced = shell_snippets::RC_FILES.iter().any(|name| {
+            std::fs::read_to_string(crate::utils::get_home_dir().join(name))
+                .is_ok_and(|c| shell_snippets::has_line(&c))
+        });
+        self.load_scope(config);
+    }
+
+    fn load_scope(&mut self, config: &Config) {
+        let result = AliasFile::load(&config.repo_path, self.scope_name()).and_then(|file| {
+            let effective = if self.active_profile.is_empty() {
+                BTreeMap::new()
+            } else {
+                aliases::resolve(&config.repo_path, &self.active_profile)?
+            };
+            Ok((file, effective))
+        });
+        match result {
+            Ok((file, effective)) => {
+                self.entries = file.aliases.into_iter().collect();
+                self.effective = effective;
+                self.error = None;
+            }
+            Err(e) => {
+                self.entries.clear();
+                self.error = Some(format!("{e:#}"));
+            }
+        }
+        let selected = self
+            .list_state
+            .selected()
+            .unwrap_or(0)
+            .min(self.entries.len().saturating_sub(1));
+        self.list_state
+            .select((!self.entries.is_empty()).then_some(selected));
+    }
+
+    fn scope_name(&self) -> &str {
+        self.scopes.get(self.scope).map_or(COMMON, String::as_str)
+    }
+
+    fn selected(&self) -> Option<&(String, String)> {
+        self.list_state.selected().and_then(|i| self.entries.get(i))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        self.list_state.select(Some(next));
+    }
+
+    fn switch_scope(&mut self, forward: bool, config: &Config) {
+        let len = self.scopes.len();
+        if len == 0 {
+            return;
+        }
+        self.scope = if forward {
+            (self.scope + 1) % len
+        } else {
+            (self.scope + len - 1) % len
+        };
+        self.list_state.select(Some(0));
+        self.load_scope(config);
+    }
+
+    /// How the active profile sees the alias `name` of the selected scope
+    fn status(&self, name: &str) -> Option<String> {
+        if self.active_profile.is_empty() {
+            return None;
+        }
+        match self.effective.get(name) {
+            Some(alias) if alias.source == self.scope_name() => None,
+            Some(alias) => Some(format!("overridden by {}", alias.source)),
+            None => Some(format!("not used by {}", self.active_profile)),
+        }
+    }
+
+    fn open_editor(&mut self, existing: Option<(String, String)>) {
+        let (original, name, command) = match existing {
+            Some((name, command)) => (Some(name.clone()), name, command),
+            None => (None, String::new(), String::new()),
+        };
+        self.editor = Some(AliasEditor {
+            original,
+            name: TextInput::with_text(name),
+            command: TextInput::with_text(command),
+            field: EditorField::Name,
+            error: None,
+        });
+    }
+
+    /// Write the scope's aliases and regenerate the loader
+    fn save_scope(&self, config: &Config, file: &AliasFile) -> Result<()> {
+        file.save(&config.repo_path, self.scope_name())?;
+        if !self.active_profile.is_empty() {
+            shell_snippets::write_loader(&config.repo_path, &self.active_profile)?;
+        }
+        Ok(())
+    }
+
+    fn saved_toast(&self, message: String) -> ScreenAction {
+        let hint = if self.rc_sourced {
+            "open a new shell to use it"
+        } else {
+            "run 'dotstate snippets --install' so shells load aliases"
+        };
+        ScreenAction::ShowToast {
+            message: format!("{message}; {hint}"),
+            variant: ToastVariant::Success,
+        }
+    }
+
+    fn submit_editor(&mut self, config: &Config) -> ScreenAction {
+        let Some(editor) = &mut self.editor else {
+            return ScreenAction::None;
+        };
+        let name = editor.name.text_trimmed().to_string();
+        let command = editor.command.text_trimmed().to_string();
+        if let Err(e) =
+            aliases::validate_name(&name).and_then(|()| aliases::validate_command(&command))
+        {
+            editor.error = Some(e);
+            return ScreenAction::Refresh;
+        }
+        let renamed = editor.original.as_deref() != Some(name.as_str());
+        if renamed && self.entries.iter().any(|(n, _)| *n == name) {
+            editor.error = Some(format!("'{name}' is already defined here"));
+            return ScreenAction::Refresh;
+        }
+
+        let mut file = AliasFile {
+            aliases: self.entries.iter().cloned().collect(),
+        };
+        if let Some(original) = &editor.original {
+            file.aliases.remove(original);
+        }
+        file.aliases.insert(name.clone(), command);
+        if let Err(e) = self.save_scope(config, &file) {
+            if let Some(editor) = &mut self.editor {
+                editor.error = Some(format!("{e:#}"));
+            }
+            return ScreenAction::Refresh;
+        }
+
+        self.editor = None;
+        self.load_scope(config);
+        if let Some(index) = self.entries.iter().position(|(n, _)| *n == name) {
+            self.list_state.select(Some(index));
+        }
+        self.saved_toast(format!("Saved alias '{name}'"))
+    }
+
+    fn delete(&mut self, name: &str, config: &Config) -> ScreenAction {
+        let mut file = AliasFile {
+            aliases: self.entries.iter().cloned().collect(),
+        };
+        file.aliases.remove(name);
+        if let Err(e) = self.save_scope(config, &file) {
+            return ScreenAction::ShowToast {
+                message: format!("Failed to delete alias: {e:#}"),
+                variant: ToastVariant::Error,
+            };
+        }
+        self.load_scope(config);
+        self.saved_toast(format!("Deleted alias '{name}'"))
+    }
+
+    fn handle_editor_key(&mut self, key: KeyEvent, config: &Config) -> ScreenAction {
+        let Some(editor) = &mut self.editor else {
+            return ScreenAction::None;
+        };
+        // Plain characters are always typed, so vim bindings don't interfere
+        if let KeyCode::Char(c) = key.code {
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER)
+            {
+                editor.focused_input().insert_char(c);
+                editor.error = None;
+                return ScreenAction::Refresh;
+            }
+        }
+        let Some(action) = config.keymap.get_action(key.code, key.modifiers) else {
+            return ScreenAction::None;
+        };
+        match action {
+            Action::Cancel => {
+                self.editor = None;
+                ScreenAction::Refresh
+            }
+            Action::Confirm => self.submit_editor(config),
+            Action::NextTab | Action::PrevTab | Action::MoveUp | Action::MoveDown => {
+                editor.field = match editor.field {
+                    EditorField::Name => EditorField::Command,
+                    EditorField::Command => EditorField::Name,
+                };
+                ScreenAction::Refresh
+            }
+            action => {
+                if editor.focused_input().handle_action(action) {
+                    ScreenAction::Refresh
+                } else {
+                    ScreenAction::None
+                }
+            }
+        }
+    }
+
+    fn handle_confirm_delete(&mut self, key: KeyEvent, config: &Config) -> ScreenAction {
+        match config.keymap.get_action(key.code, key.modifiers) {
+            Some(Action::Confirm | Action::Yes) => {
+                let name = self.confirm_delete.take().unwrap_or_default();
+                self.delete(&name, config)
+            }
+            Some(Action::Cancel | Action::No | Action::Quit) => {
+                self.confirm_delete = None;
+                ScreenAction::Refresh
+            }
+            _ => ScreenAction::None,
+        }
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        self.list_area = Some(area);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|(name, command)| {
+                let mut spans = vec![
+                    Span::styled(name.clone(), t.emphasis_style()),
+                    Span::styled(format!("  {command}"), t.text_style()),
+                ];
+                if let Some(status) = self.status(name) {
+                    spans.push(Span::styled(format!("  ({status})"), t.muted_style()));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let mut title = format!(" Aliases: {} ({}) ", self.scope_name(), self.entries.len());
+        if self.scope_name() == self.active_profile {
+            title = format!(
+                " Aliases: {} (active, {}) ",
+                self.active_profile,
+                self.entries.len()
+            );
+        }
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for (row, index) in (self.list_state.offset()..self.entries.len()).enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                index,
+            );
+        }
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Details ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+
+        let mut lines = match self.selected() {
+            Some((name, command)) => {
+                let field = |label: &str, value: String| {
+                    Line::from(vec![
+                        Span::styled(format!("{label}: "), t.muted_style()),
+                        Span::styled(value, t.text_style()),
+                    ])
+                };
+                let mut lines = vec![
+                    Line::from(Span::styled(name.clone(), t.title_style())),
+                    Line::from(""),
+                    field("Command", command.clone()),
+                    field("Defined in", self.scope_name().to_string()),
+                ];
+                if let Some(status) = self.status(name) {
+                    lines.push(Line::from(Span::styled(
+                        format!("Currently {status}"),
+                        Style::default().fg(t.warning),
+                    )));
+                }
+                lines
+            }
+            None => vec![Line::from(Span::styled(
+                format!(
+                    "No aliases in {} yet. Aliases here are stored in {}/{}.",
+                    self.scope_name(),
+                    self.scope_name(),
+                    aliases::ALIASES_FILE
+                ),
+                t.muted_style(),
+            ))],
+        };
+
+        lines.push(Line::from(""));
+        if self.rc_sourced {
+            lines.push(Line::from(Span::styled(
+                "Your shell loads these through the dotstate source line; new shells pick up changes.",
+                t.muted_style(),
+            )));
+        } else {
+            lines.push(Line::from(Span::styled(
+                "No rc file loads dotstate's aliases yet. Run 'dotstate snippets --install' to add the source line.",
+                Style::default().fg(t.warning),
+            )));
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+
+    fn render_editor(&self, frame: &mut Frame, area: Rect, config: &Config) {
+        use crate::components::Popup;
+
+        let Some(editor) = &self.editor else {
+            return;
+        };
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Next Field | {}: Save | {}: Cancel",
+            k(Action::NextTab),
+            k(Action::Confirm),
+            k(Action::Cancel)
+        );
+        let title = match &editor.original {
+            Some(name) => format!("Edit Alias: {name}"),
+            None => format!("New Alias in {}", self.scope_name()),
+        };
+        let Some(result) = Popup::new()
+            .width(60)
+            .height(40)
+            // 2 inputs (3 each) + 1 (err) + borders/title/footer (~5) = 12.
+            .min_height(12)
+            .min_width(50)
+            .title(title)
+            .dim_background(true)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(u16::from(editor.error.is_some())),
+                Constraint::Min(0),
+            ])
+            .split(result.content_area);
+
+        let name = TextInputWidget::new(&editor.name)
+            .title("Name")
+            .placeholder("e.g. gs")
+            .focused(editor.field == EditorField::Name);
+        frame.render_text_input_widget(name, chunks[0]);
+        let command = TextInputWidget::new(&editor.command)
+            .title("Command")
+            .placeholder("e.g. git status")
+            .focused(editor.field == EditorField::Command);
+        frame.render_text_input_widget(command, chunks[1]);
+
+        if let Some(msg) = &editor.error {
+            frame.render_widget(
+                Paragraph::new(msg.as_str())
+                    .style(Style::default().fg(theme().error))
+                    .alignment(Alignment::Center),
+                chunks[2],
+            );
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    self.list_state.select(Some(index));
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::ScrollUp if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(-3);
+            }
+            MouseEventKind::ScrollDown if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+impl Screen for AliasesScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Shell Aliases",
+            "Aliases kept per profile and in common, loaded by your shell through dotstate.",
+        )?;
+
+        if let Some(error) = &self.error {
+            let para = Paragraph::new(format!(
+                "Couldn't read {}/{}:\n\n{error}",
+                self.scope_name(),
+                aliases::ALIASES_FILE
+            ))
+            .style(Style::default().fg(t.error))
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(t.border_type(false))
+                    .title(" Aliases ")
+                    .title_alignment(Alignment::Center)
+                    .padding(crate::styles::padding(Padding::new(2, 2, 2, 2))),
+            );
+            frame.render_widget(para, content_chunk);
+        } else {
+            let panes = create_split_layout(content_chunk, &[55, 45]);
+            self.render_list(frame, panes[0]);
+            self.render_details(frame, panes[1]);
+        }
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Navigate | {}: Profile/Common | {}: Add | {}: Edit | {}: Delete | {}: Back",
+            ctx.config.keymap.navigation_display(),
+            k(Action::NextTab),
+            k(Action::Create),
+            k(Action::Edit),
+            k(Action::Delete),
+            k(Action::Cancel),
+        );
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        if self.editor.is_some() {
+            self.render_editor(frame, area, ctx.config);
+        } else if let Some(name) = &self.confirm_delete {
+            let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+            let footer_text = format!(
+                "{}: Delete | {}: Cancel",
+                k(Action::Confirm),
+                k(Action::Cancel)
+            );
+            let msg = format!("Delete the alias '{name}' from {}?", self.scope_name());
+            let dialog = Dialog::new("Delete Alias", &msg)
+                .height(20)
+                .dim_background(true)
+                .variant(DialogVariant::Warning)
+                .footer(&footer_text);
+            frame.render_widget(dialog, area);
+        }
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                if self.editor.is_some() {
+                    return Ok(self.handle_editor_key(key, ctx.config));
+                }
+                if self.confirm_delete.is_some() {
+                    return Ok(self.handle_confirm_delete(key, ctx.config));
+                }
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                let edits = matches!(action, Action::Create | Action::Edit | Action::Delete);
+                if edits && ctx.config.read_only {
+                    return Ok(ScreenAction::pull_only_blocked());
+                }
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::GoToTop => self.move_selection(isize::MIN / 2),
+                    Action::GoToEnd => self.move_selection(isize::MAX / 2),
+                    Action::NextTab | Action::MoveRight => self.switch_scope(true, ctx.config),
+                    Action::PrevTab | Action::MoveLeft => self.switch_scope(false, ctx.config),
+                    Action::Create => self.open_editor(None),
+                    Action::Edit | Action::Confirm => {
+                        if let Some(entry) = self.selected().cloned() {
+                            self.open_editor(Some(entry));
+                        }
+                    }
+                    Action::Delete => {
+                        self.confirm_delete = self.selected().map(|(name, _)| name.clone());
+                    }
+                    Action::Refresh => self.load(ctx.config),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) if self.editor.is_none() && self.confirm_delete.is_none() => {
+                return Ok(self.handle_mouse(mouse));
+            }
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn is_input_focused(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        self.editor = None;
+        self.confirm_delete = None;
+        self.load(ctx.config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_against_active_profile() {
+        let mut screen = AliasesScreen::new();
+        screen.active_profile = "Work".to_string();
+        screen.scopes = vec!["Work".to_string(), COMMON.to_string(), "Home".to_string()];
+        let resolved = |source: &str| ResolvedAlias {
+            command: "x".to_string(),
+            source: source.to_string(),
+        };
+        screen.effective.insert("ll".to_string(), resolved("Work"));
+        screen.effective.insert("g".to_string(), resolved(COMMON));
+
+        assert_eq!(screen.status("ll"), None);
+        screen.scope = 1;
+        assert_eq!(screen.status("ll").as_deref(), Some("overridden by Work"));
+        assert_eq!(screen.status("g"), None);
+        screen.scope = 2;
+        assert_eq!(screen.status("h").as_deref(), Some("not used by Work"));
+    }
+}
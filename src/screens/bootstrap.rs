@@ -0,0 +1,425 @@
+//! Bootstrap screen controller.
+//!
+//! Shown after a profile is activated for the first time on this machine
+//! when it has bootstrap scripts. Lists the scripts, runs them in the
+//! background after confirmation and streams their output.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::styles::theme;
+use crate::ui::Screen as ScreenId;
+use crate::utils::bootstrap::{self, BootstrapScript};
+use crate::utils::{create_split_layout, create_standard_layout, unfocused_border_style};
+use anyhow::Result;
+use crossterm::event::{Event, KeyEventKind, MouseEventKind};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use tracing::{info, warn};
+
+/// Progress of the scripts running in the background.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BootstrapEvent {
+    /// A script started (index into the script list)
+    Started(usize),
+    /// A line of output
+    Output(String),
+    /// A script ended; `None` on success, otherwise why it failed
+    Finished(usize, Option<String>),
+}
+
+/// Where the screen is in the bootstrap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Waiting for the user to run or skip the scripts
+    Confirm,
+    Running,
+    /// All scripts ran, or one failed
+    Done {
+        success: bool,
+    },
+}
+
+/// State of one script in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Bootstrap screen controller.
+pub struct BootstrapScreen {
+    profile: String,
+    repo_path: PathBuf,
+    scripts: Vec<BootstrapScript>,
+    statuses: Vec<ScriptStatus>,
+    stage: Stage,
+    /// Output of the scripts, with a header line per script
+    output: Vec<String>,
+    /// Scroll offset of the output panel; `None` follows the newest output
+    output_scroll: Option<u16>,
+    /// Receiver for the scripts running in the background
+    run_rx: Option<Receiver<BootstrapEvent>>,
+}
+
+impl Default for BootstrapScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BootstrapScreen {
+    /// Create a new bootstrap screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            profile: String::new(),
+            repo_path: PathBuf::new(),
+            scripts: Vec::new(),
+            statuses: Vec::new(),
+            stage: Stage::Confirm,
+            output: Vec::new(),
+            output_scroll: None,
+            run_rx: None,
+        }
+    }
+
+    /// Whether scripts are running
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.run_rx.is_some()
+    }
+
+    fn set_scripts(&mut self, profile: &str, repo_path: PathBuf, scripts: Vec<BootstrapScript>) {
+        self.profile = profile.to_string();
+        self.repo_path = repo_path;
+        self.statuses = vec![ScriptStatus::Pending; scripts.len()];
+        self.scripts = scripts;
+        self.stage = Stage::Confirm;
+        self.output.clear();
+        self.output_scroll = None;
+    }
+
+    /// Run the scripts in order in the background, stopping at the first failure.
+    fn start(&mut self) {
+        let scripts = self.scripts.clone();
+        let repo_path = self.repo_path.clone();
+        let profile = self.profile.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (index, script) in scripts.iter().enumerate() {
+                let _ = tx.send(BootstrapEvent::Started(index));
+                let error = match script.spawn(&repo_path, &profile) {
+                    Ok((mut child, output_rx)) => {
+                        for line in output_rx {
+                            let _ = tx.send(BootstrapEvent::Output(line));
+                        }
+                        match child.wait() {
+                            Ok(status) if status.success() => None,
+                            Ok(status) => Some(format!("exited with {status}")),
+                            Err(e) => Some(e.to_string()),
+                        }
+                    }
+                    Err(e) => Some(format!("{e:#}")),
+                };
+                let failed = error.is_some();
+                let _ = tx.send(BootstrapEvent::Finished(index, error));
+                if failed {
+                    break;
+                }
+            }
+        });
+        info!("Running bootstrap scripts for profile '{}'", self.profile);
+        self.run_rx = Some(rx);
+        self.stage = Stage::Running;
+    }
+
+    fn apply(&mut self, event: BootstrapEvent) {
+        match event {
+            BootstrapEvent::Started(index) => {
+                self.statuses[index] = ScriptStatus::Running;
+                if !self.output.is_empty() {
+                    self.output.push(String::new());
+                }
+                self.output.push(format!(
+                    "▶ {}",
+                    self.scripts[index].display_name(&self.repo_path)
+                ));
+            }
+            BootstrapEvent::Output(line) => self.output.push(line),
+            BootstrapEvent::Finished(index, error) => {
+                if let Some(error) = error {
+                    warn!(
+                        "Bootstrap script {} failed: {}",
+                        self.scripts[index].path.display(),
+                        error
+                    );
+                    self.output.push(format!("✗ {error}"));
+                    self.statuses[index] = ScriptStatus::Failed;
+                } else {
+                    self.statuses[index] = ScriptStatus::Succeeded;
+                }
+            }
+        }
+    }
+
+    /// Poll the running scripts. Returns `ScreenAction::Refresh` while they run.
+    pub fn tick(&mut self) -> ScreenAction {
+        let Some(rx) = self.run_rx.take() else {
+            return ScreenAction::None;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(event) => self.apply(event),
+                Err(TryRecvError::Empty) => {
+                    self.run_rx = Some(rx);
+                    return ScreenAction::Refresh;
+                }
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        let success = self.statuses.iter().all(|s| *s == ScriptStatus::Succeeded);
+        if success {
+            bootstrap::mark_done(&self.profile);
+        }
+        self.stage = Stage::Done { success };
+        ScreenAction::Refresh
+    }
+
+    fn panel(title: String) -> Block<'static> {
+        let t = theme();
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::horizontal(1)))
+            .style(t.background_style())
+    }
+
+    fn render_scripts(&self, frame: &mut Frame, area: Rect, icons: &Icons) {
+        let t = theme();
+        let mut lines: Vec<Line> = self
+            .scripts
+            .iter()
+            .zip(&self.statuses)
+            .map(|(script, status)| {
+                let (icon, style) = match status {
+                    ScriptStatus::Pending => ("·", t.muted_style()),
+                    ScriptStatus::Running => (icons.sync(), Style::default().fg(t.primary)),
+                    ScriptStatus::Succeeded => (icons.success(), Style::default().fg(t.success)),
+                    ScriptStatus::Failed => (icons.error(), Style::default().fg(t.error)),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{icon} "), style),
+                    Span::styled(script.display_name(&self.repo_path), t.text_style()),
+                ])
+            })
+            .collect();
+
+        let message = match self.stage {
+            Stage::Confirm if self.scripts.is_empty() => {
+                "This profile has no bootstrap scripts.".to_string()
+            }
+            Stage::Confirm => format!(
+                "Profile '{}' has never been bootstrapped on this machine. Run these scripts now? Skipping is remembered; `dotstate bootstrap` runs them later.",
+                self.profile
+            ),
+            Stage::Running => "Running...".to_string(),
+            Stage::Done { success: true } => "Bootstrap complete.".to_string(),
+            Stage::Done { success: false } => {
+                "A script failed. Fix it and run `dotstate bootstrap` to try again.".to_string()
+            }
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(message, t.muted_style())));
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Self::panel(format!(" Scripts ({}) ", self.profile))),
+            area,
+        );
+    }
+
+    fn render_output(&mut self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let lines: Vec<Line> = self
+            .output
+            .iter()
+            .map(|line| {
+                let style = if line.starts_with('▶') {
+                    t.emphasis_style()
+                } else if line.starts_with("[stderr]") {
+                    Style::default().fg(t.warning)
+                } else if line.starts_with('✗') {
+                    Style::default().fg(t.error)
+                } else {
+                    t.text_style()
+                };
+                Line::from(Span::styled(line.clone(), style))
+            })
+            .collect();
+
+        let height = area.height.saturating_sub(2);
+        let max_scroll = (lines.len() as u16).saturating_sub(height);
+        let scroll = self.output_scroll.map_or(max_scroll, |s| s.min(max_scroll));
+        if self.output_scroll.is_some() {
+            self.output_scroll = Some(scroll);
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .scroll((scroll, 0))
+                .block(Self::panel(" Output ".to_string())),
+            area,
+        );
+    }
+
+    fn scroll_output(&mut self, delta: i32) {
+        let current = self.output_scroll.unwrap_or(u16::MAX);
+        self.output_scroll = Some(if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs() as u16)
+        } else {
+            current.saturating_add(delta as u16)
+        });
+    }
+}
+
+impl Screen for BootstrapScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Bootstrap",
+            "Setup scripts that run the first time a profile is activated on a machine.",
+        )?;
+
+        let panes = create_split_layout(content_chunk, &[35, 65]);
+        self.render_scripts(frame, panes[0], &Icons::from_config(ctx.config));
+        self.render_output(frame, panes[1]);
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = match self.stage {
+            Stage::Confirm if !self.scripts.is_empty() => {
+                format!("{}: Run | {}: Skip", k(Action::Confirm), k(Action::Cancel))
+            }
+            Stage::Running => "Running bootstrap scripts...".to_string(),
+            _ => format!(
+                "{}/{}: Scroll Output | {}: Continue",
+                k(Action::ScrollUp),
+                k(Action::ScrollDown),
+                k(Action::Confirm)
+            ),
+        };
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        if self.stage == Stage::Running {
+            return Ok(ScreenAction::None);
+        }
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match (self.stage, action) {
+                    (Stage::Confirm, Action::Confirm | Action::Yes) if !self.scripts.is_empty() => {
+                        self.start();
+                        return Ok(ScreenAction::Refresh);
+                    }
+                    (Stage::Confirm, Action::Cancel | Action::Quit | Action::No) => {
+                        if !self.scripts.is_empty() {
+                            info!("Skipped bootstrap for profile '{}'", self.profile);
+                            bootstrap::mark_done(&self.profile);
+                        }
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    (_, Action::Confirm | Action::Cancel | Action::Quit) => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    (_, Action::ScrollUp) => self.scroll_output(-1),
+                    (_, Action::ScrollDown) => self.scroll_output(1),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => self.scroll_output(-3),
+                MouseEventKind::ScrollDown => self.scroll_output(3),
+                _ => {}
+            },
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        if self.is_busy() {
+            return Ok(());
+        }
+        let profile = &ctx.config.active_profile;
+        let scripts = bootstrap::pending(&ctx.config.repo_path, profile);
+        self.set_scripts(profile, ctx.config.repo_path.clone(), scripts);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_update_statuses_and_output() {
+        let mut screen = BootstrapScreen::new();
+        let repo = PathBuf::from("/repo");
+        let script = |name: &str| BootstrapScript {
+            profile: "Work".to_string(),
+            path: repo.join("Work").join(name),
+        };
+        screen.set_scripts(
+            "Work",
+            repo.clone(),
+            vec![script("install.sh"), script("bootstrap/10-tmux.sh")],
+        );
+
+        screen.apply(BootstrapEvent::Started(0));
+        screen.apply(BootstrapEvent::Output("cloning tpm".to_string()));
+        screen.apply(BootstrapEvent::Finished(0, None));
+        screen.apply(BootstrapEvent::Started(1));
+        screen.apply(BootstrapEvent::Finished(
+            1,
+            Some("exited with exit status: 1".to_string()),
+        ));
+
+        assert_eq!(
+            screen.statuses,
+            [ScriptStatus::Succeeded, ScriptStatus::Failed]
+        );
+        assert_eq!(
+            screen.output,
+            [
+                "▶ Work/install.sh",
+                "cloning tpm",
+                "",
+                "▶ Work/bootstrap/10-tmux.sh",
+                "✗ exited with exit status: 1"
+            ]
+        );
+    }
+}
@@ -6,22 +6,28 @@
 use crate::components::file_preview::FilePreview;
 use crate::components::footer::Footer;
 use crate::components::header::Header;
-use crate::components::{FileBrowser, FileBrowserResult};
+use crate::components::remote_banner::RemoteBanner;
+use crate::components::{
+    ActionMenu, ActionMenuResult, FileBrowser, FileBrowserResult, FileTimeline, FileTimelineResult,
+    MenuEntry,
+};
 use crate::config::Config;
 use crate::file_manager::Dotfile;
 use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
 use crate::screens::ActionResult;
-use crate::services::SyncService;
+use crate::services::{AddStorage, FileOperation, SyncService};
 use crate::styles::{theme as ui_theme, LIST_HIGHLIGHT_SYMBOL};
 use crate::ui::Screen as ScreenId;
 use crate::utils::{
-    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
-    MouseRegions, TextInput,
+    create_split_layout, create_standard_layout, focused_border_style, is_narrow,
+    unfocused_border_style, DoubleClick, MouseRegions, ScrollbarDrag, TextInput,
 };
 use crate::widgets::{Dialog, DialogVariant};
 use crate::widgets::{TextInputWidget, TextInputWidgetExt};
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+};
 use ratatui::layout::Position;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
@@ -43,6 +49,26 @@ enum DisplayItem {
     File(usize),    // Index into state.dotfiles
 }
 
+/// An entry in the action menu of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileMenuAction {
+    /// Add to sync, or remove from it
+    ToggleSync,
+    /// Open in the editor
+    Edit,
+    /// Open the timeline, which starts on the diff against the last sync
+    Diff,
+    /// Put the backup of the original file back in the repository
+    RestoreBackup,
+    /// Move to common, or back to the profile
+    Move,
+    Rename,
+    /// Remove a custom file from the list
+    Remove,
+    /// Show the file in the repository in the system file manager
+    Reveal,
+}
+
 /// Actions that can be processed by the dotfile selection screen
 #[derive(Debug, Clone)]
 pub enum DotfileAction {
@@ -65,8 +91,40 @@ pub enum DotfileAction {
         is_common: bool,
         profiles_to_cleanup: Vec<String>,
     },
+    /// Move the marked files that passed validation to common in one commit
+    MoveManyToCommon { files: Vec<(usize, Vec<String>)> },
+    /// Add the file waiting in the large file dialog, stored as chosen
+    StoreLargeFile { storage: AddStorage },
     /// Remove a custom file entry from `config.custom_files`
     RemoveCustomFile { file_index: usize },
+    /// Rename or move a synced file
+    RenameFile { file_index: usize, new_path: String },
+    /// Apply the reviewed queue of file changes, all or nothing
+    ApplyFileQueue { operations: Vec<FileOperation> },
+}
+
+/// A path being added from the list or the file browser
+#[derive(Debug, Clone)]
+pub enum AddTarget {
+    /// A file in the dotfiles list
+    Listed(usize),
+    /// A custom file picked in the file browser
+    Custom {
+        full_path: PathBuf,
+        relative_path: String,
+    },
+}
+
+/// A large or binary path waiting for a decision on how to store it
+#[derive(Debug, Clone)]
+pub struct LargeFilePrompt {
+    pub target: AddTarget,
+    pub relative_path: String,
+    pub report: crate::utils::file_policy::AddReport,
+    /// Whether Git LFS is installed
+    pub can_lfs: bool,
+    /// Whether the repository uses sops and the path is a file
+    pub can_encrypt: bool,
 }
 
 /// Focus area in dotfile selection screen
@@ -79,6 +137,49 @@ pub enum DotfileSelectionFocus {
     FileBrowserInput,   // File browser path input is focused
 }
 
+/// An action on one file that the repeat key does again on another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatAction {
+    AddToSync,
+    MoveToCommon,
+    /// Mark or unmark for moving to common
+    ToggleMark,
+}
+
+impl RepeatAction {
+    fn label(self) -> &'static str {
+        match self {
+            RepeatAction::AddToSync => "Add",
+            RepeatAction::MoveToCommon => "Move to Common",
+            RepeatAction::ToggleMark => "Mark",
+        }
+    }
+}
+
+/// A marked file in the batch move-to-common dialog
+#[derive(Debug)]
+pub struct BatchMoveItem {
+    pub file_index: usize,
+    pub relative_path: String,
+    /// Validation result, or the error that prevented validating
+    pub validation: std::result::Result<crate::utils::MoveToCommonValidation, String>,
+}
+
+impl BatchMoveItem {
+    /// Whether the file can be moved without overwriting anything
+    fn is_safe(&self) -> bool {
+        self.validation.as_ref().is_ok_and(|v| v.can_proceed)
+    }
+}
+
+/// A file change waiting in the review queue
+#[derive(Debug)]
+pub struct QueuedOperation {
+    pub operation: FileOperation,
+    /// Left out when the queue is applied
+    pub skip: bool,
+}
+
 /// Dotfile selection state
 #[derive(Debug)]
 pub struct DotfileSelectionState {
@@ -115,6 +216,24 @@ pub struct DotfileSelectionState {
     pub confirm_unsync_common: Option<usize>, // Index of common file to unsync
     // Remove custom file confirmation
     pub confirm_remove_custom: Option<usize>, // Index of custom file to remove
+    // Restore from backup confirmation
+    pub confirm_restore: Option<(usize, PathBuf)>, // Index of file to restore and its backup
+    // Rename popup
+    pub renaming: Option<usize>, // Index of synced file being renamed
+    pub rename_input: TextInput, // New path input
+    // Batch move to common
+    pub marked: std::collections::HashSet<usize>, // Synced profile files marked for moving to common
+    pub batch_move: Option<Vec<BatchMoveItem>>,   // Per-file validation shown before a batch move
+    // sops-encrypted files
+    pub decrypted_targets: std::collections::HashSet<PathBuf>, // Home paths holding decrypted copies
+    pub unlocked: std::collections::HashSet<PathBuf>, // Decrypted copies whose preview was unlocked
+    // Large or binary file being added
+    pub large_file_prompt: Option<LargeFilePrompt>,
+    // Last action on a file, for the repeat key
+    pub last_action: Option<RepeatAction>,
+    // File changes queued for review
+    pub queue: Vec<QueuedOperation>, // Changes waiting to be applied, in order
+    pub queue_review: Option<usize>, // Selected change while the queue is being reviewed
 }
 
 impl Default for DotfileSelectionState {
@@ -148,6 +267,17 @@ impl Default for DotfileSelectionState {
             move_validation: None,
             confirm_unsync_common: None,
             confirm_remove_custom: None,
+            confirm_restore: None,
+            renaming: None,
+            rename_input: TextInput::new(),
+            marked: std::collections::HashSet::new(),
+            batch_move: None,
+            decrypted_targets: std::collections::HashSet::new(),
+            unlocked: std::collections::HashSet::new(),
+            large_file_prompt: None,
+            last_action: None,
+            queue: Vec::new(),
+            queue_review: None,
         }
     }
 }
@@ -157,27 +287,464 @@ pub struct DotfileSelectionScreen {
     state: DotfileSelectionState,
     /// File browser component
     file_browser: FileBrowser,
+    /// Version history of the selected file
+    timeline: FileTimeline,
+    /// Actions for the selected file
+    file_menu: ActionMenu<FileMenuAction>,
     /// Mouse regions for dotfile list items
     mouse_regions: MouseRegions<usize>,
+    /// Double-clicks on list items toggle sync
+    row_clicks: DoubleClick<usize>,
+    /// Dragging and clicking the list scrollbar
+    list_scrollbar: ScrollbarDrag,
+    /// Dragging and clicking the preview scrollbar
+    preview_scrollbar: ScrollbarDrag,
     /// Stored list pane area for scroll hit-testing
     list_pane_area: Option<Rect>,
     /// Stored preview pane area for scroll hit-testing
     preview_pane_area: Option<Rect>,
+    /// Warning shown when the remote has commits to pull first
+    remote_banner: RemoteBanner,
 }
 
 impl DotfileSelectionScreen {
+    /// Whether the preview of `path` is hidden until explicitly unlocked
+    fn is_preview_locked(&self, path: &Path) -> bool {
+        self.state.decrypted_targets.contains(path) && !self.state.unlocked.contains(path)
+    }
+
     /// Create a new dotfile selection screen.
     #[must_use]
     pub fn new() -> Self {
         Self {
             state: DotfileSelectionState::default(),
             file_browser: FileBrowser::new(),
+            timeline: FileTimeline::new(),
+            file_menu: ActionMenu::new(),
             mouse_regions: MouseRegions::new(),
+            row_clicks: DoubleClick::new(),
+            list_scrollbar: ScrollbarDrag::new(),
+            preview_scrollbar: ScrollbarDrag::new(),
             list_pane_area: None,
             preview_pane_area: None,
+            remote_banner: RemoteBanner::new(),
+        }
+    }
+
+    /// Where a synced file is stored in the repository: found through its
+    /// symlink, falling back to where it would be stored
+    fn repo_file(dotfile: &Dotfile, config: &Config) -> PathBuf {
+        std::fs::read_link(&dotfile.original_path)
+            .ok()
+            .filter(|target| target.starts_with(&config.repo_path))
+            .unwrap_or_else(|| {
+                let dir = if dotfile.is_common {
+                    "common"
+                } else {
+                    config.active_profile.as_str()
+                };
+                config.repo_path.join(dir).join(&dotfile.relative_path)
+            })
+    }
+
+    /// Open the timeline of a synced file
+    fn open_timeline(&mut self, file_idx: usize, config: &Config) -> ScreenAction {
+        let dotfile = &self.state.dotfiles[file_idx];
+        let refuse = |message: &str| ScreenAction::ShowToast {
+            message: message.into(),
+            variant: crate::widgets::ToastVariant::Info,
+        };
+        if !dotfile.synced {
+            return refuse("Only synced files have a timeline");
+        }
+        if self
+            .state
+            .decrypted_targets
+            .contains(&dotfile.original_path)
+        {
+            return refuse("Encrypted files have no readable timeline");
+        }
+        let repo_file = Self::repo_file(dotfile, config);
+        if repo_file.is_dir() {
+            return refuse("Timeline is available for files, not folders");
+        }
+        let display_path = format!("~/{}", dotfile.relative_path.display());
+        match self
+            .timeline
+            .open(&config.repo_path, &repo_file, &display_path)
+        {
+            Ok(()) => ScreenAction::Refresh,
+            Err(e) => ScreenAction::ShowToast {
+                message: format!("Failed to load history: {e}"),
+                variant: crate::widgets::ToastVariant::Error,
+            },
+        }
+    }
+
+    /// Add the file to sync, or remove it. Removing a common file asks first.
+    /// When changes are reviewed first, the change is queued instead.
+    fn toggle_sync(&mut self, file_idx: usize, config: &Config) -> ScreenAction {
+        if config.review_changes {
+            if let Some(action) = self.queue_toggle_sync(file_idx, config) {
+                return action;
+            }
+        }
+        let is_synced = self.state.selected_for_sync.contains(&file_idx);
+        if is_synced && self.state.dotfiles[file_idx].is_common {
+            self.state.confirm_unsync_common = Some(file_idx);
+            return ScreenAction::Refresh;
+        }
+        if !is_synced {
+            self.state.last_action = Some(RepeatAction::AddToSync);
+        }
+        ScreenAction::ToggleFileSync {
+            file_index: file_idx,
+            is_synced,
+        }
+    }
+
+    /// Queue adding or removing the file, or take its change out of the
+    /// queue. Returns None when a large file has to be added right away, to
+    /// ask how to store it.
+    fn queue_toggle_sync(&mut self, file_idx: usize, config: &Config) -> Option<ScreenAction> {
+        if self.unqueue(file_idx) {
+            return Some(ScreenAction::Refresh);
+        }
+        let dotfile = &self.state.dotfiles[file_idx];
+        let relative_path = dotfile.relative_path.to_string_lossy().to_string();
+        let operation = if self.state.selected_for_sync.contains(&file_idx) {
+            FileOperation::Remove {
+                relative_path,
+                common: dotfile.is_common,
+            }
+        } else {
+            if crate::utils::file_policy::inspect(
+                &dotfile.original_path,
+                config.large_file_threshold_mb,
+            )
+            .is_some()
+            {
+                return None;
+            }
+            self.state.last_action = Some(RepeatAction::AddToSync);
+            FileOperation::Add {
+                relative_path,
+                common: false,
+            }
+        };
+        Some(self.enqueue(operation))
+    }
+
+    /// Add a change to the end of the queue
+    fn enqueue(&mut self, operation: FileOperation) -> ScreenAction {
+        self.state.queue.push(QueuedOperation {
+            operation,
+            skip: false,
+        });
+        ScreenAction::Refresh
+    }
+
+    /// Take the file's change out of the queue. Returns whether it had one.
+    fn unqueue(&mut self, file_idx: usize) -> bool {
+        let len = self.state.queue.len();
+        let path = self.state.dotfiles[file_idx]
+            .relative_path
+            .to_string_lossy();
+        self.state
+            .queue
+            .retain(|queued| queued.operation.relative_path() != path);
+        self.state.queue.len() < len
+    }
+
+    /// The queued change of a file, if any
+    fn queued(&self, dotfile: &Dotfile) -> Option<&FileOperation> {
+        let path = dotfile.relative_path.to_string_lossy();
+        self.state
+            .queue
+            .iter()
+            .map(|queued| &queued.operation)
+            .find(|operation| operation.relative_path() == path)
+    }
+
+    /// Open the review of the queued changes
+    fn open_queue_review(&mut self) -> ScreenAction {
+        if self.state.queue.is_empty() {
+            return ScreenAction::ShowToast {
+                message: "No changes queued".into(),
+                variant: crate::widgets::ToastVariant::Info,
+            };
+        }
+        self.state.queue_review = Some(0);
+        ScreenAction::Refresh
+    }
+
+    /// Mark or unmark a synced profile file for moving to common
+    fn toggle_mark(&mut self, file_idx: usize) -> ScreenAction {
+        let dotfile = &self.state.dotfiles[file_idx];
+        if !dotfile.synced || dotfile.is_common {
+            return ScreenAction::ShowToast {
+                message: "Only synced profile files can be marked for moving to common".into(),
+                variant: crate::widgets::ToastVariant::Info,
+            };
+        }
+        if !self.state.marked.remove(&file_idx) {
+            self.state.marked.insert(file_idx);
+        }
+        self.state.last_action = Some(RepeatAction::ToggleMark);
+        ScreenAction::Refresh
+    }
+
+    /// Do the last add, move to common or mark again, on another file
+    fn repeat_last_action(&mut self, file_idx: usize, config: &Config) -> ScreenAction {
+        let info = |message: &str| ScreenAction::ShowToast {
+            message: message.into(),
+            variant: crate::widgets::ToastVariant::Info,
+        };
+        let dotfile = &self.state.dotfiles[file_idx];
+        match self.state.last_action {
+            None => info("Nothing to repeat yet"),
+            Some(RepeatAction::AddToSync) if dotfile.synced => info("Already synced"),
+            Some(RepeatAction::AddToSync) => self.toggle_sync(file_idx, config),
+            Some(RepeatAction::MoveToCommon) if !dotfile.synced || dotfile.is_common => {
+                info("Only synced profile files can be moved to common")
+            }
+            Some(RepeatAction::MoveToCommon) => self.start_move(file_idx, config),
+            Some(RepeatAction::ToggleMark) => self.toggle_mark(file_idx),
+        }
+    }
+
+    /// Ask to remove a custom file from the list
+    fn start_remove(&mut self, file_idx: usize) -> ScreenAction {
+        let dotfile = &self.state.dotfiles[file_idx];
+        if dotfile.synced {
+            return ScreenAction::ShowToast {
+                message: "Unsync the file first before removing it".into(),
+                variant: crate::widgets::ToastVariant::Info,
+            };
+        }
+        if !dotfile.is_custom {
+            return ScreenAction::ShowToast {
+                message: "Only custom-added files can be removed from the list".into(),
+                variant: crate::widgets::ToastVariant::Info,
+            };
+        }
+        self.state.confirm_remove_custom = Some(file_idx);
+        ScreenAction::Refresh
+    }
+
+    /// Open the rename popup of a synced file
+    fn start_rename(&mut self, file_idx: usize) -> ScreenAction {
+        let dotfile = &self.state.dotfiles[file_idx];
+        if !dotfile.synced {
+            return ScreenAction::ShowToast {
+                message: "Only synced files can be renamed".into(),
+                variant: crate::widgets::ToastVariant::Info,
+            };
+        }
+        self.state.rename_input = TextInput::with_text(dotfile.relative_path.to_string_lossy());
+        self.state.renaming = Some(file_idx);
+        ScreenAction::Refresh
+    }
+
+    /// Ask to move a synced file to common, or back to the profile
+    fn start_move(&mut self, file_idx: usize, config: &Config) -> ScreenAction {
+        let dotfile = &self.state.dotfiles[file_idx];
+        if !dotfile.synced {
+            return ScreenAction::None;
+        }
+        if config.review_changes {
+            let relative_path = dotfile.relative_path.to_string_lossy().to_string();
+            let is_common = dotfile.is_common;
+            if self.unqueue(file_idx) {
+                return ScreenAction::Refresh;
+            }
+            if is_common {
+                return self.enqueue(FileOperation::MoveFromCommon { relative_path });
+            }
+        }
+        let dotfile = &self.state.dotfiles[file_idx];
+        // Moving from profile to common - validate first
+        if !dotfile.is_common {
+            self.state.last_action = Some(RepeatAction::MoveToCommon);
+            let relative_path = dotfile.relative_path.to_string_lossy().to_string();
+            match crate::utils::validate_move_to_common(
+                &config.repo_path,
+                &config.active_profile,
+                &relative_path,
+            ) {
+                // Queued only when nothing needs confirming
+                Ok(validation)
+                    if config.review_changes
+                        && validation.can_proceed
+                        && validation.profiles_to_cleanup.is_empty() =>
+                {
+                    return self.enqueue(FileOperation::MoveToCommon { relative_path });
+                }
+                // If there are blocking conflicts, the confirmation shows them
+                Ok(validation) => self.state.move_validation = Some(validation),
+                Err(e) => {
+                    return ScreenAction::ShowMessage {
+                        title: "Validation Error".to_string(),
+                        content: format!("Failed to validate move: {e}"),
+                    };
+                }
+            }
+        }
+        self.state.confirm_move = Some(file_idx);
+        ScreenAction::Refresh
+    }
+
+    /// The action menu entries of a file, with the key doing each directly
+    fn file_menu_entries(
+        &self,
+        file_idx: usize,
+        has_backup: bool,
+        config: &Config,
+    ) -> Vec<MenuEntry<FileMenuAction>> {
+        use crate::keymap::Action;
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let dotfile = &self.state.dotfiles[file_idx];
+        let is_dir = dotfile.original_path.is_dir();
+        let encrypted = self
+            .state
+            .decrypted_targets
+            .contains(&dotfile.original_path);
+
+        let mut entries = Vec::new();
+        if !dotfile.synced {
+            entries.push(MenuEntry::new("Add to sync", FileMenuAction::ToggleSync));
+            if !is_dir {
+                entries.push(MenuEntry::new("Edit", FileMenuAction::Edit));
+            }
+            if dotfile.is_custom {
+                entries.push(
+                    MenuEntry::new("Remove from list", FileMenuAction::Remove)
+                        .key(k(Action::Delete)),
+                );
+            }
+            return entries;
+        }
+
+        if !is_dir {
+            entries.push(MenuEntry::new("Edit", FileMenuAction::Edit));
+            if !encrypted {
+                entries.push(
+                    MenuEntry::new("Diff & history", FileMenuAction::Diff).key(k(Action::Timeline)),
+                );
+            }
+        }
+        if has_backup {
+            entries.push(MenuEntry::new(
+                "Restore from backup",
+                FileMenuAction::RestoreBackup,
+            ));
+        }
+        let move_label = if dotfile.is_common {
+            "Move to profile"
+        } else {
+            "Move to common"
+        };
+        entries.push(MenuEntry::new(move_label, FileMenuAction::Move).key(k(Action::Move)));
+        entries.push(MenuEntry::new("Rename", FileMenuAction::Rename).key(k(Action::Edit)));
+        entries.push(MenuEntry::new("Reveal in storage", FileMenuAction::Reveal));
+        entries.push(MenuEntry::new(
+            "Remove from sync",
+            FileMenuAction::ToggleSync,
+        ));
+        entries
+    }
+
+    /// Open the action menu of a file
+    fn open_file_menu(&mut self, file_idx: usize, config: &Config) {
+        let dotfile = &self.state.dotfiles[file_idx];
+        let has_backup = dotfile.synced
+            && crate::utils::SymlinkManager::new_with_backup(config.repo_path.clone(), false)
+                .is_ok_and(|mgr| mgr.backup_of(&dotfile.original_path).is_some());
+        let title = format!("~/{}", dotfile.relative_path.display());
+        let entries = self.file_menu_entries(file_idx, has_backup, config);
+        self.file_menu.open(title, entries);
+    }
+
+    /// Do what was chosen in the action menu of a file
+    fn run_file_menu_action(
+        &mut self,
+        action: FileMenuAction,
+        file_idx: usize,
+        config: &Config,
+    ) -> ScreenAction {
+        let dotfile = &self.state.dotfiles[file_idx];
+        let display_path = format!("~/{}", dotfile.relative_path.display());
+        match action {
+            FileMenuAction::ToggleSync => self.toggle_sync(file_idx, config),
+            FileMenuAction::Edit => ScreenAction::EditFile {
+                path: dotfile.original_path.clone(),
+            },
+            FileMenuAction::Diff => self.open_timeline(file_idx, config),
+            FileMenuAction::RestoreBackup => {
+                let backup =
+                    crate::utils::SymlinkManager::new_with_backup(config.repo_path.clone(), false)
+                        .ok()
+                        .and_then(|mgr| {
+                            mgr.backup_of(&dotfile.original_path).map(Path::to_path_buf)
+                        });
+                match backup {
+                    Some(backup) => {
+                        self.state.confirm_restore = Some((file_idx, backup));
+                        ScreenAction::Refresh
+                    }
+                    None => ScreenAction::ShowToast {
+                        message: format!("{display_path} has no backup"),
+                        variant: crate::widgets::ToastVariant::Info,
+                    },
+                }
+            }
+            FileMenuAction::Move => self.start_move(file_idx, config),
+            FileMenuAction::Rename => self.start_rename(file_idx),
+            FileMenuAction::Remove => self.start_remove(file_idx),
+            FileMenuAction::Reveal => {
+                let repo_file = Self::repo_file(dotfile, config);
+                match crate::utils::external::reveal(&repo_file) {
+                    Ok(()) => ScreenAction::None,
+                    // Without a file manager, say where it is
+                    Err(_) => ScreenAction::ShowToast {
+                        message: format!("Stored at {}", repo_file.display()),
+                        variant: crate::widgets::ToastVariant::Info,
+                    },
+                }
+            }
+        }
+    }
+
+    /// Overwrite the stored copy of a file with its backup, once confirmed
+    fn restore_backup(&mut self, file_idx: usize, config: &Config) -> ScreenAction {
+        let Some(dotfile) = self.state.dotfiles.get(file_idx) else {
+            return ScreenAction::Refresh;
+        };
+        let display_path = format!("~/{}", dotfile.relative_path.display());
+        let restored =
+            crate::utils::SymlinkManager::new_with_backup(config.repo_path.clone(), false)
+                .and_then(|mgr| mgr.restore_backup(&dotfile.original_path));
+        self.state.preview_scroll = 0;
+        match restored {
+            Ok(backup) => ScreenAction::ShowToast {
+                message: format!(
+                    "Restored {display_path} from {}. Sync to share it.",
+                    backup.display()
+                ),
+                variant: crate::widgets::ToastVariant::Success,
+            },
+            Err(e) => ScreenAction::ShowToast {
+                message: format!("Restore failed: {e}"),
+                variant: crate::widgets::ToastVariant::Error,
+            },
         }
     }
 
+    /// Update the remote-ahead banner from the latest remote status.
+    pub fn set_remote_status(&mut self, behind: usize, pulling: bool) {
+        self.remote_banner.set_status(behind, pulling);
+    }
+
     /// Get the current state.
     #[must_use]
     pub fn get_state(&self) -> &DotfileSelectionState {
@@ -215,13 +782,13 @@ impl DotfileSelectionScreen {
             }
         }
 
-        // 2. Profile Files
+        // 2. Profile Files (synced, or added by hand)
         let profile_indices: Vec<usize> = self
             .state
             .dotfiles
             .iter()
             .enumerate()
-            .filter(|(_, d)| !d.is_common)
+            .filter(|(_, d)| !d.is_common && !Self::is_suggestion(d))
             .map(|(i, _)| i)
             .collect();
 
@@ -237,9 +804,38 @@ impl DotfileSelectionScreen {
             }
         }
 
+        // 3. Well-known config files found in home that aren't synced yet
+        let suggestion_indices: Vec<usize> = self
+            .state
+            .dotfiles
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| Self::is_suggestion(d))
+            .map(|(i, _)| i)
+            .collect();
+
+        if !suggestion_indices.is_empty() {
+            if !items.is_empty() {
+                items.push(DisplayItem::Header(String::new())); // Spacer
+            }
+            items.push(DisplayItem::Header(format!(
+                "Suggested Additions ({})",
+                suggestion_indices.len()
+            )));
+            for idx in suggestion_indices {
+                items.push(DisplayItem::File(idx));
+            }
+        }
+
         items
     }
 
+    /// Whether `dotfile` is a well-known config file found in home that
+    /// isn't synced (files added by hand are never suggestions)
+    fn is_suggestion(dotfile: &Dotfile) -> bool {
+        !dotfile.synced && !dotfile.is_common && !dotfile.is_custom
+    }
+
     /// Handle modal confirmation events.
     fn handle_modal_event(&mut self, key_code: KeyCode, config: &Config) -> Result<ScreenAction> {
         let action = config
@@ -448,24 +1044,14 @@ impl DotfileSelectionScreen {
                     }
                 }
                 Action::Confirm => {
-                    if let Some(idx) = self.state.dotfile_list_state.selected() {
-                        if idx < display_items.len() {
-                            if let DisplayItem::File(file_idx) = &display_items[idx] {
-                                let is_synced = self.state.selected_for_sync.contains(file_idx);
-                                let dotfile = &self.state.dotfiles[*file_idx];
-
-                                // If unsyncing a common file, show confirmation dialog
-                                if is_synced && dotfile.is_common {
-                                    self.state.confirm_unsync_common = Some(*file_idx);
-                                    return Ok(ScreenAction::Refresh);
-                                }
-
-                                return Ok(ScreenAction::ToggleFileSync {
-                                    file_index: *file_idx,
-                                    is_synced,
-                                });
-                            }
-                        }
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        self.open_file_menu(*file_idx, config);
+                        return Ok(ScreenAction::Refresh);
                     }
                 }
                 Action::NextTab => {
@@ -549,75 +1135,98 @@ impl DotfileSelectionScreen {
                     });
                 }
                 Action::Delete => {
-                    if let Some(idx) = self.state.dotfile_list_state.selected() {
-                        if idx < display_items.len() {
-                            if let DisplayItem::File(file_idx) = &display_items[idx] {
-                                let dotfile = &self.state.dotfiles[*file_idx];
-                                if dotfile.synced {
-                                    return Ok(ScreenAction::ShowToast {
-                                        message: "Unsync the file first before removing it".into(),
-                                        variant: crate::widgets::ToastVariant::Info,
-                                    });
-                                }
-                                if !dotfile.is_custom {
-                                    return Ok(ScreenAction::ShowToast {
-                                        message:
-                                            "Only custom-added files can be removed from the list"
-                                                .into(),
-                                        variant: crate::widgets::ToastVariant::Info,
-                                    });
-                                }
-                                self.state.confirm_remove_custom = Some(*file_idx);
-                                return Ok(ScreenAction::Refresh);
-                            }
-                        }
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        return Ok(self.start_remove(*file_idx));
                     }
                 }
                 Action::Cancel | Action::Quit => {
+                    if !self.state.marked.is_empty() {
+                        self.state.marked.clear();
+                        return Ok(ScreenAction::Refresh);
+                    }
+                    // Queued changes are reviewed before leaving
+                    if !self.state.queue.is_empty() {
+                        return Ok(self.open_queue_review());
+                    }
                     return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
                 }
-                Action::Move => {
-                    if let Some(idx) = self.state.dotfile_list_state.selected() {
-                        if idx < display_items.len() {
-                            if let DisplayItem::File(file_idx) = &display_items[idx] {
-                                let dotfile = &self.state.dotfiles[*file_idx];
-                                if dotfile.synced {
-                                    // Validate before showing confirmation
-                                    if !dotfile.is_common {
-                                        // Moving from profile to common - validate first
-                                        let relative_path =
-                                            dotfile.relative_path.to_string_lossy().to_string();
-                                        match crate::utils::validate_move_to_common(
-                                            &config.repo_path,
-                                            &config.active_profile,
-                                            &relative_path,
-                                        ) {
-                                            Ok(validation) => {
-                                                self.state.move_validation = Some(validation);
-                                                // If there are blocking conflicts, we'll show a different dialog
-                                                // Otherwise, proceed with normal confirmation
-                                                self.state.confirm_move = Some(*file_idx);
-                                                return Ok(ScreenAction::Refresh);
-                                            }
-                                            Err(e) => {
-                                                // Validation error - show error message
-                                                return Ok(ScreenAction::ShowMessage {
-                                                    title: "Validation Error".to_string(),
-                                                    content: format!(
-                                                        "Failed to validate move: {e}"
-                                                    ),
-                                                });
-                                            }
-                                        }
-                                    }
-                                    // Moving from common to profile - no validation needed
-                                    self.state.confirm_move = Some(*file_idx);
-                                    return Ok(ScreenAction::Refresh);
-                                }
-                            }
+                Action::ToggleSelect => {
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        return Ok(self.toggle_mark(*file_idx));
+                    }
+                }
+                Action::Repeat => {
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        return Ok(self.repeat_last_action(*file_idx, config));
+                    }
+                }
+                Action::RevealSecrets => {
+                    return Ok(ScreenAction::toggle_secrets());
+                }
+                Action::Edit => {
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        return Ok(self.start_rename(*file_idx));
+                    }
+                }
+                Action::Timeline => {
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        return Ok(self.open_timeline(*file_idx, config));
+                    }
+                }
+                Action::Unlock => {
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        let path = self.state.dotfiles[*file_idx].original_path.clone();
+                        if self.is_preview_locked(&path) {
+                            self.state.unlocked.insert(path);
+                            self.state.preview_scroll = 0;
+                            return Ok(ScreenAction::Refresh);
                         }
                     }
                 }
+                Action::Move if !self.state.marked.is_empty() => {
+                    self.validate_batch_move(config);
+                    return Ok(ScreenAction::Refresh);
+                }
+                Action::Move => {
+                    if let Some(DisplayItem::File(file_idx)) = self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        return Ok(self.start_move(*file_idx, config));
+                    }
+                }
                 _ => {}
             }
         }
@@ -633,16 +1242,40 @@ impl DotfileSelectionScreen {
     ) -> Result<ScreenAction> {
         let display_items = self.get_display_items(&config.active_profile);
 
+        let selected = self.state.dotfile_list_state.selected().unwrap_or(0);
+        if let Some(idx) = self.list_scrollbar.handle(mouse, selected) {
+            // Land on the nearest file, headers can't be selected
+            let file = (idx..display_items.len())
+                .chain((0..idx).rev())
+                .find(|i| matches!(display_items[*i], DisplayItem::File(_)));
+            if let Some(file) = file.filter(|file| *file != selected) {
+                self.state.dotfile_list_state.select(Some(file));
+                self.state.preview_scroll = 0;
+            }
+            self.state.focus = DotfileSelectionFocus::FilesList;
+            return Ok(ScreenAction::None);
+        }
+        if let Some(scroll) = self
+            .preview_scrollbar
+            .handle(mouse, self.state.preview_scroll)
+        {
+            self.state.preview_scroll = scroll;
+            self.state.focus = DotfileSelectionFocus::Preview;
+            return Ok(ScreenAction::None);
+        }
+
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
-                // Click on list item
+                // Click on list item, double-click toggles its sync
                 if let Some(&idx) = self.mouse_regions.hit_test(mouse.column, mouse.row) {
-                    if idx < display_items.len() {
-                        // Skip headers
-                        if !matches!(display_items[idx], DisplayItem::Header(_)) {
-                            self.state.dotfile_list_state.select(Some(idx));
+                    if let Some(DisplayItem::File(file_idx)) = display_items.get(idx) {
+                        if self.state.dotfile_list_state.selected() != Some(idx) {
                             self.state.preview_scroll = 0;
-                            self.state.focus = DotfileSelectionFocus::FilesList;
+                        }
+                        self.state.dotfile_list_state.select(Some(idx));
+                        self.state.focus = DotfileSelectionFocus::FilesList;
+                        if self.row_clicks.click(idx) {
+                            return Ok(self.toggle_sync(*file_idx, config));
                         }
                     }
                     return Ok(ScreenAction::None);
@@ -664,6 +1297,27 @@ impl DotfileSelectionScreen {
                     }
                 }
             }
+            MouseEventKind::Moved => {
+                // Hovering a file previews it
+                if let Some(&idx) = self.mouse_regions.hit_test(mouse.column, mouse.row) {
+                    let hovered_file = matches!(display_items.get(idx), Some(DisplayItem::File(_)));
+                    if hovered_file && self.state.dotfile_list_state.selected() != Some(idx) {
+                        self.state.dotfile_list_state.select(Some(idx));
+                        self.state.preview_scroll = 0;
+                    }
+                }
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                // Right-click on a file opens its action menu
+                if let Some(&idx) = self.mouse_regions.hit_test(mouse.column, mouse.row) {
+                    if let Some(DisplayItem::File(file_idx)) = display_items.get(idx) {
+                        self.state.dotfile_list_state.select(Some(idx));
+                        self.state.preview_scroll = 0;
+                        self.state.focus = DotfileSelectionFocus::FilesList;
+                        self.open_file_menu(*file_idx, config);
+                    }
+                }
+            }
             MouseEventKind::ScrollDown => {
                 if let Some(area) = self.list_pane_area {
                     if area.contains(Position::new(mouse.column, mouse.row)) {
@@ -829,12 +1483,14 @@ impl DotfileSelectionScreen {
         let left_area = content_chunks[0];
         let preview_area = content_chunks[1];
         let icons = crate::icons::Icons::from_config(config);
-        // Split left area into list (top) and description (bottom)
+        // Split left area into list (top) and description (bottom), leaving
+        // the description out when the panes are stacked
+        let description_height = if is_narrow(content_chunk) { 0 } else { 4 };
         let left_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(0),    // List takes remaining space
-                Constraint::Length(4), // Description block (3 lines + 1 border)
+                Constraint::Min(0),                     // List takes remaining space
+                Constraint::Length(description_height), // Description block (3 lines + 1 border)
             ])
             .split(left_area);
 
@@ -881,7 +1537,12 @@ impl DotfileSelectionScreen {
 
         // Count common vs profile files for title
         let common_count = self.state.dotfiles.iter().filter(|d| d.is_common).count();
-        let profile_count = self.state.dotfiles.len() - common_count;
+        let profile_count = self
+            .state
+            .dotfiles
+            .iter()
+            .filter(|d| !d.is_common && !Self::is_suggestion(d))
+            .count();
 
         #[allow(unused)] // list_idx is unused but is needed if we want to show tree structure
         let items: Vec<ListItem> = display_items
@@ -904,6 +1565,7 @@ impl DotfileSelectionScreen {
                     } else {
                         icons.uncheck()
                     };
+                    let marked = self.state.marked.contains(idx);
 
                     // Indent files under headers
                     // Check if this is the last file in the section (next is header or end of list)
@@ -917,7 +1579,8 @@ impl DotfileSelectionScreen {
                     //     "\u{251c}" // ├
                     // };
 
-                    let prefix = "";
+                    // Marked for a batch move to common
+                    let prefix = if marked { "▸" } else { " " };
 
                     let style = if is_selected {
                         Style::default().fg(t.success)
@@ -929,9 +1592,12 @@ impl DotfileSelectionScreen {
 
                     let path_str = dotfile.relative_path.to_string_lossy();
                     let mut spans = vec![
-                        ratatui::text::Span::styled(prefix.to_string(), Style::default()),
                         ratatui::text::Span::styled(
-                            format!(" {sync_marker}\u{2009}{path_str}"),
+                            prefix.to_string(),
+                            Style::default().fg(t.primary).add_modifier(Modifier::BOLD),
+                        ),
+                        ratatui::text::Span::styled(
+                            format!("{sync_marker}\u{2009}{path_str}"),
                             style,
                         ),
                     ];
@@ -941,6 +1607,18 @@ impl DotfileSelectionScreen {
                             Style::default().fg(t.text_muted),
                         ));
                     }
+                    if let Some(operation) = self.queued(dotfile) {
+                        let change = match operation {
+                            FileOperation::Add { .. } => "add",
+                            FileOperation::Remove { .. } => "remove",
+                            FileOperation::MoveToCommon { .. } => "to common",
+                            FileOperation::MoveFromCommon { .. } => "to profile",
+                        };
+                        spans.push(ratatui::text::Span::styled(
+                            format!(" [queued: {change}]"),
+                            Style::default().fg(t.warning),
+                        ));
+                    }
                     let content = ratatui::text::Line::from(spans);
                     ListItem::new(content)
                 }
@@ -1009,12 +1687,15 @@ impl DotfileSelectionScreen {
 
         // Render scrollbar
         frame.render_stateful_widget(
-            Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .begin_symbol(Some("↑"))
-                .end_symbol(Some("↓")),
+            self.list_scrollbar.style(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(Some("↑"))
+                    .end_symbol(Some("↓")),
+            ),
             list_area,
             &mut self.state.dotfile_list_scrollbar,
         );
+        self.list_scrollbar.set(list_area, display_items.len());
 
         // Get selected dotfile (if any)
         let selected_dotfile = if let Some(idx) = self.state.dotfile_list_state.selected() {
@@ -1070,20 +1751,30 @@ impl DotfileSelectionScreen {
         if let Some(dotfile) = selected_dotfile {
             let is_focused = self.state.focus == DotfileSelectionFocus::Preview;
             let preview_title = format!("Preview: {}", dotfile.relative_path.to_string_lossy());
-
+            let locked = self.is_preview_locked(&dotfile.original_path).then(|| {
+                format!(
+                    "🔒 Decrypted copy of a sops-encrypted file.\n\nPress {} to show its content.",
+                    config
+                        .keymap
+                        .get_key_display_for_action(crate::keymap::Action::Unlock)
+                )
+            });
+
             FilePreview::render(
                 frame,
                 preview_area,
                 &dotfile.original_path,
                 &mut self.state.preview_scroll,
+                &mut self.preview_scrollbar,
                 is_focused,
                 Some(&preview_title),
-                None,
+                locked.as_deref(),
                 syntax_set,
                 theme,
                 config,
             )?;
         } else {
+            self.preview_scrollbar.clear();
             let empty_preview = Paragraph::new("No file selected").block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -1114,33 +1805,67 @@ impl DotfileSelectionScreen {
                 _ => None,
             });
 
-        let move_text = selected_dotfile.map_or("Move", |dotfile| {
-            if dotfile.is_common {
-                "Move to Profile"
-            } else {
-                "Move to Common"
-            }
-        });
+        let move_text = if self.state.marked.is_empty() {
+            selected_dotfile
+                .map_or("Move", |dotfile| {
+                    if dotfile.is_common {
+                        "Move to Profile"
+                    } else {
+                        "Move to Common"
+                    }
+                })
+                .to_string()
+        } else {
+            format!("Move {} Marked to Common", self.state.marked.len())
+        };
 
         let is_custom_selected =
             selected_dotfile.is_some_and(|dotfile| dotfile.is_custom && !dotfile.synced);
 
-        let remove_part = if is_custom_selected {
+        let mut remove_part = if is_custom_selected {
             format!(" | {}: Remove", k(crate::keymap::Action::Delete))
         } else {
             String::new()
         };
+        if selected_dotfile.is_some_and(|dotfile| dotfile.synced) {
+            remove_part.push_str(&format!(" | {}: Rename", k(crate::keymap::Action::Edit)));
+            remove_part.push_str(&format!(
+                " | {}: Timeline",
+                k(crate::keymap::Action::Timeline)
+            ));
+        }
+        if selected_dotfile.is_some_and(|dotfile| self.is_preview_locked(&dotfile.original_path)) {
+            remove_part.push_str(&format!(" | {}: Unlock", k(crate::keymap::Action::Unlock)));
+        }
+
+        if let Some(last) = self.state.last_action {
+            remove_part.push_str(&format!(
+                " | {}: Repeat {}",
+                k(crate::keymap::Action::Repeat),
+                last.label()
+            ));
+        }
+        if !self.state.queue.is_empty() {
+            remove_part.push_str(&format!(
+                " | {}: Review {} Queued",
+                k(crate::keymap::Action::Save),
+                self.state.queue.len()
+            ));
+        }
 
         let footer_text = format!(
-            "Tab: Focus | {}: Navigate | Space/{}: Toggle | {}: {} | {}: Add Custom | {}: Backup ({}){} | {}: Back",
+            "Tab: Focus | {}: Navigate | {}: Actions | {}: Mark | {}: {} | {}: Add Custom | {}: Backup ({}){} | {}: {} Secrets | {}: Back",
              config.keymap.navigation_display(),
              k(crate::keymap::Action::Confirm),
+             k(crate::keymap::Action::ToggleSelect),
              k(crate::keymap::Action::Move),
              move_text,
              k(crate::keymap::Action::Create),
              k(crate::keymap::Action::ToggleBackup),
              backup_status,
              remove_part,
+             k(crate::keymap::Action::RevealSecrets),
+             if FilePreview::secrets_revealed() { "Mask" } else { "Show" },
              k(crate::keymap::Action::Quit)
         );
 
@@ -1326,6 +2051,155 @@ impl DotfileSelectionScreen {
         }
     }
 
+    /// Validate every marked file for the batch move dialog, in list order
+    fn validate_batch_move(&mut self, config: &Config) {
+        let items = self
+            .get_display_items(&config.active_profile)
+            .into_iter()
+            .filter_map(|item| match item {
+                DisplayItem::File(idx) if self.state.marked.contains(&idx) => Some(idx),
+                _ => None,
+            })
+            .map(|file_index| {
+                let relative_path = self.state.dotfiles[file_index]
+                    .relative_path
+                    .to_string_lossy()
+                    .to_string();
+                let validation = crate::utils::validate_move_to_common(
+                    &config.repo_path,
+                    &config.active_profile,
+                    &relative_path,
+                )
+                .map_err(|e| e.to_string());
+                BatchMoveItem {
+                    file_index,
+                    relative_path,
+                    validation,
+                }
+            })
+            .collect();
+        self.state.batch_move = Some(items);
+    }
+
+    fn handle_batch_move_confirm(
+        &mut self,
+        key_code: KeyCode,
+        config: &Config,
+    ) -> Result<ScreenAction> {
+        let action = config
+            .keymap
+            .get_action(key_code, crossterm::event::KeyModifiers::NONE);
+
+        match action {
+            Some(crate::keymap::Action::Confirm) => {
+                let items = self.state.batch_move.take().unwrap_or_default();
+                let files: Vec<(usize, Vec<String>)> = items
+                    .iter()
+                    .filter(|item| item.is_safe())
+                    .filter_map(|item| {
+                        item.validation
+                            .as_ref()
+                            .ok()
+                            .map(|v| (item.file_index, v.profiles_to_cleanup.clone()))
+                    })
+                    .collect();
+                if files.is_empty() {
+                    return Ok(ScreenAction::Refresh);
+                }
+                self.state.marked.clear();
+                Ok(ScreenAction::MoveManyToCommon { files })
+            }
+            Some(crate::keymap::Action::Quit | crate::keymap::Action::Cancel) => {
+                self.state.batch_move = None;
+                Ok(ScreenAction::Refresh)
+            }
+            _ => Ok(ScreenAction::None),
+        }
+    }
+
+    fn handle_queue_review(&mut self, key_code: KeyCode, config: &Config) -> Result<ScreenAction> {
+        use crate::keymap::Action;
+        let Some(selected) = self.state.queue_review else {
+            return Ok(ScreenAction::None);
+        };
+        let action = config
+            .keymap
+            .get_action(key_code, crossterm::event::KeyModifiers::NONE);
+
+        match action {
+            Some(Action::MoveUp) => {
+                self.state.queue_review = Some(selected.saturating_sub(1));
+            }
+            Some(Action::MoveDown) => {
+                let last = self.state.queue.len().saturating_sub(1);
+                self.state.queue_review = Some((selected + 1).min(last));
+            }
+            Some(Action::ToggleSelect) => {
+                if let Some(queued) = self.state.queue.get_mut(selected) {
+                    queued.skip = !queued.skip;
+                }
+            }
+            Some(Action::Confirm) => {
+                self.state.queue_review = None;
+                let operations: Vec<FileOperation> = std::mem::take(&mut self.state.queue)
+                    .into_iter()
+                    .filter(|queued| !queued.skip)
+                    .map(|queued| queued.operation)
+                    .collect();
+                if !operations.is_empty() {
+                    return Ok(ScreenAction::ApplyFileQueue { operations });
+                }
+            }
+            Some(Action::Delete) => {
+                let count = self.state.queue.len();
+                self.state.queue.clear();
+                self.state.queue_review = None;
+                return Ok(ScreenAction::ShowToast {
+                    message: format!("Discarded {count} queued change(s)"),
+                    variant: crate::widgets::ToastVariant::Info,
+                });
+            }
+            Some(Action::Quit | Action::Cancel) => {
+                self.state.queue_review = None;
+            }
+            _ => return Ok(ScreenAction::None),
+        }
+        Ok(ScreenAction::Refresh)
+    }
+
+    fn handle_large_file_prompt(
+        &mut self,
+        key_code: KeyCode,
+        config: &Config,
+    ) -> Result<ScreenAction> {
+        let Some(prompt) = &self.state.large_file_prompt else {
+            return Ok(ScreenAction::None);
+        };
+        let storage = match key_code {
+            KeyCode::Char('a') => AddStorage::Plain,
+            KeyCode::Char('l') if prompt.can_lfs => AddStorage::Lfs,
+            KeyCode::Char('e') if prompt.can_encrypt => AddStorage::Encrypted,
+            KeyCode::Char('s') => {
+                self.state.large_file_prompt = None;
+                return Ok(ScreenAction::Refresh);
+            }
+            _ => {
+                let action = config
+                    .keymap
+                    .get_action(key_code, crossterm::event::KeyModifiers::NONE);
+                if matches!(
+                    action,
+                    Some(crate::keymap::Action::Quit | crate::keymap::Action::Cancel)
+                ) {
+                    self.state.large_file_prompt = None;
+                    return Ok(ScreenAction::Refresh);
+                }
+                return Ok(ScreenAction::None);
+            }
+        };
+        Ok(ScreenAction::StoreLargeFile { storage })
+    }
+
     fn handle_unsync_common_confirm(
         &mut self,
         key_code: KeyCode,
@@ -1378,6 +2252,102 @@ impl DotfileSelectionScreen {
         }
     }
 
+    /// Handle keys in the rename popup.
+    fn handle_rename_input(&mut self, key: KeyEvent, config: &Config) -> Result<ScreenAction> {
+        use crate::keymap::Action;
+
+        // Plain characters are always typed, so bindings like h/l don't interfere
+        if let KeyCode::Char(c) = key.code {
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER)
+            {
+                self.state.rename_input.insert_char(c);
+                return Ok(ScreenAction::Refresh);
+            }
+        }
+
+        match config.keymap.get_action(key.code, key.modifiers) {
+            Some(Action::Cancel) => {
+                self.state.renaming = None;
+                self.state.rename_input.clear();
+                Ok(ScreenAction::Refresh)
+            }
+            Some(Action::Confirm) => {
+                let new_path = self.state.rename_input.text_trimmed().to_string();
+                let Some(file_index) = self.state.renaming else {
+                    return Ok(ScreenAction::None);
+                };
+                if new_path.is_empty() {
+                    return Ok(ScreenAction::None);
+                }
+                self.state.renaming = None;
+                self.state.rename_input.clear();
+                Ok(ScreenAction::RenameFile {
+                    file_index,
+                    new_path,
+                })
+            }
+            Some(action) => {
+                self.state.rename_input.handle_action(action);
+                Ok(ScreenAction::Refresh)
+            }
+            None => Ok(ScreenAction::None),
+        }
+    }
+
+    /// Render the rename popup
+    fn render_rename_popup(&self, frame: &mut Frame, area: Rect, config: &Config) -> Result<()> {
+        use crate::components::Popup;
+
+        let current = self
+            .state
+            .renaming
+            .and_then(|idx| self.state.dotfiles.get(idx))
+            .map(|d| d.relative_path.display().to_string())
+            .unwrap_or_default();
+
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Rename | {}: Cancel",
+            k(crate::keymap::Action::Confirm),
+            k(crate::keymap::Action::Cancel)
+        );
+
+        let Some(result) = Popup::new()
+            .width(60)
+            .height(35)
+            .min_height(9)
+            .min_width(50)
+            .title(format!("Rename: ~/{current}"))
+            .dim_background(true)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return Ok(());
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(result.content_area);
+
+        let widget = TextInputWidget::new(&self.state.rename_input)
+            .title("New path (relative to ~)")
+            .placeholder(".config/app/config")
+            .focused(true);
+        frame.render_text_input_widget(widget, chunks[0]);
+
+        let hint = Paragraph::new(
+            "The file is moved in the repository, keeping its history, and the symlink is re-created at the new path.",
+        )
+        .style(ui_theme().muted_style())
+        .wrap(Wrap { trim: true });
+        frame.render_widget(hint, chunks[1]);
+
+        Ok(())
+    }
+
     fn handle_remove_custom_confirm(
         &mut self,
         key_code: KeyCode,
@@ -1505,6 +2475,146 @@ impl DotfileSelectionScreen {
         Ok(())
     }
 
+    fn render_batch_move_confirm(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        config: &Config,
+    ) -> Result<()> {
+        let Some(items) = self.state.batch_move.as_ref() else {
+            return Ok(());
+        };
+
+        let mut lines = Vec::new();
+        for item in items {
+            let line = match &item.validation {
+                Err(e) => format!("✗ {}: failed to validate: {e}", item.relative_path),
+                Ok(validation) if validation.can_proceed => {
+                    if validation.profiles_to_cleanup.is_empty() {
+                        format!("✓ {}", item.relative_path)
+                    } else {
+                        format!(
+                            "✓ {} (removes identical copy from {})",
+                            item.relative_path,
+                            validation.profiles_to_cleanup.join(", ")
+                        )
+                    }
+                }
+                Ok(validation) => {
+                    let reasons: Vec<String> = validation
+                        .conflicts
+                        .iter()
+                        .filter_map(|conflict| match conflict {
+                            crate::utils::MoveToCommonConflict::SameContentInProfile { .. } => None,
+                            crate::utils::MoveToCommonConflict::DifferentContentInProfile {
+                                profile_name,
+                                ..
+                            } => Some(format!("different content in {profile_name}")),
+                            crate::utils::MoveToCommonConflict::PathHierarchyConflict {
+                                profile_name,
+                                conflicting_path,
+                                ..
+                            } => Some(format!("conflicts with {profile_name}/{conflicting_path}")),
+                        })
+                        .collect();
+                    format!("✗ {}: {}", item.relative_path, reasons.join("; "))
+                }
+            };
+            lines.push(format!("  {line}"));
+        }
+
+        let safe = items.iter().filter(|item| item.is_safe()).count();
+        let outcome = if safe == 0 {
+            "None of the files can be moved. Move conflicting files one at a time to\n\
+            resolve their conflicts."
+                .to_string()
+        } else if safe < items.len() {
+            format!(
+                "{safe} of {} files will be moved to common and committed together.\n\
+                Files marked ✗ are skipped; move them one at a time to resolve them.",
+                items.len()
+            )
+        } else {
+            format!("{safe} files will be moved to common and committed together.")
+        };
+        let msg = format!("{}\n\n{outcome}", lines.join("\n"));
+
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = if safe == 0 {
+            format!("{}: Close", k(crate::keymap::Action::Quit))
+        } else {
+            format!(
+                "{}: Move {safe} | {}: Cancel",
+                k(crate::keymap::Action::Confirm),
+                k(crate::keymap::Action::Quit)
+            )
+        };
+
+        let dialog = Dialog::new("Move Marked Files to Common", &msg)
+            .height(50)
+            .dim_background(true)
+            .variant(if safe < items.len() {
+                DialogVariant::Warning
+            } else {
+                DialogVariant::Default
+            })
+            .footer(&footer_text);
+        frame.render_widget(dialog, area);
+
+        Ok(())
+    }
+
+    fn render_queue_review(&self, frame: &mut Frame, area: Rect, config: &Config) -> Result<()> {
+        let Some(selected) = self.state.queue_review else {
+            return Ok(());
+        };
+        let queue = &self.state.queue;
+
+        let lines: Vec<String> = queue
+            .iter()
+            .enumerate()
+            .map(|(i, queued)| {
+                // The dialog trims leading spaces, so the cursor goes last
+                let mark = if queued.skip { "✗" } else { "✓" };
+                let skipped = if queued.skip { " (skipped)" } else { "" };
+                let cursor = if i == selected { "  ◂" } else { "" };
+                format!(
+                    "{}. {mark} {}{skipped}{cursor}",
+                    i + 1,
+                    queued.operation.describe()
+                )
+            })
+            .collect();
+        let count = queue.iter().filter(|queued| !queued.skip).count();
+        let outcome = if count == 0 {
+            "Every change is skipped, nothing will be applied.".to_string()
+        } else {
+            format!(
+                "{count} of {} changes will be applied in this order. If one fails,\n\
+                the ones before it are undone.",
+                queue.len()
+            )
+        };
+        let msg = format!("{}\n\n{outcome}", lines.join("\n"));
+
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Apply {count} | {}: Skip | {}: Discard All | {}: Keep Editing",
+            k(crate::keymap::Action::Confirm),
+            k(crate::keymap::Action::ToggleSelect),
+            k(crate::keymap::Action::Delete),
+            k(crate::keymap::Action::Cancel)
+        );
+
+        let dialog = Dialog::new("Review Queued Changes", &msg)
+            .height(50)
+            .dim_background(true)
+            .footer(&footer_text);
+        frame.render_widget(dialog, area);
+
+        Ok(())
+    }
+
     fn render_move_force_dialog(
         &self,
         frame: &mut Frame,
@@ -1626,6 +2736,59 @@ impl DotfileSelectionScreen {
         Ok(())
     }
 
+    fn render_large_file_prompt(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        config: &Config,
+    ) -> Result<()> {
+        let Some(prompt) = &self.state.large_file_prompt else {
+            return Ok(());
+        };
+        let reason = match (prompt.report.over_limit, prompt.report.binary.is_empty()) {
+            (true, true) => format!("is larger than {} MB", config.large_file_threshold_mb),
+            (true, false) => format!(
+                "is larger than {} MB and holds binary files",
+                config.large_file_threshold_mb
+            ),
+            _ => "holds binary files".to_string(),
+        };
+        let mut msg = format!(
+            "'{}' {reason}.\n\n{}\n\n\
+            git keeps every version of what it's given: committed as it is, \
+            this grows the repository for good and every clone downloads it. \
+            Binary files don't show diffs either.\n\n\
+            a: Add anyway, committed as it is",
+            prompt.relative_path,
+            prompt.report.summary()
+        );
+        if prompt.can_lfs {
+            msg.push_str("\nl: Store it with Git LFS (only a pointer is committed)");
+        }
+        if prompt.can_encrypt {
+            msg.push_str("\ne: Encrypt it with sops");
+        }
+        msg.push_str("\ns: Skip it");
+
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let mut keys = vec!["a: Add".to_string()];
+        if prompt.can_lfs {
+            keys.push("l: LFS".to_string());
+        }
+        if prompt.can_encrypt {
+            keys.push("e: Encrypt".to_string());
+        }
+        keys.push(format!("{}/s: Skip", k(crate::keymap::Action::Cancel)));
+        let footer_text = keys.join(" | ");
+
+        let dialog = Dialog::new("Large File", &msg)
+            .variant(DialogVariant::Warning)
+            .footer(&footer_text);
+        frame.render_widget(dialog, area);
+
+        Ok(())
+    }
+
     fn render_unsync_common_confirm(
         &self,
         frame: &mut Frame,
@@ -1663,6 +2826,72 @@ impl DotfileSelectionScreen {
         Ok(())
     }
 
+    fn handle_restore_confirm(
+        &mut self,
+        key_code: KeyCode,
+        config: &Config,
+    ) -> Result<ScreenAction> {
+        let action = config
+            .keymap
+            .get_action(key_code, crossterm::event::KeyModifiers::NONE);
+        let confirmed = match (action, key_code) {
+            (Some(crate::keymap::Action::Confirm), _) | (_, KeyCode::Char('y')) => true,
+            (Some(crate::keymap::Action::Quit | crate::keymap::Action::Cancel), _)
+            | (_, KeyCode::Char('n')) => false,
+            _ => return Ok(ScreenAction::None),
+        };
+        let Some((file_idx, _)) = self.state.confirm_restore.take() else {
+            return Ok(ScreenAction::Refresh);
+        };
+        if !confirmed {
+            return Ok(ScreenAction::Refresh);
+        }
+        Ok(self.restore_backup(file_idx, config))
+    }
+
+    fn render_restore_confirm(&self, frame: &mut Frame, area: Rect, config: &Config) -> Result<()> {
+        let Some((file_idx, backup)) = &self.state.confirm_restore else {
+            return Ok(());
+        };
+        let Some(dotfile) = self.state.dotfiles.get(*file_idx) else {
+            return Ok(());
+        };
+
+        // Backups are stored as <session>/<relative path>, the session
+        // directory named after the time it was taken
+        let depth = dotfile.relative_path.components().count();
+        let taken = backup
+            .ancestors()
+            .nth(depth)
+            .and_then(Path::file_name)
+            .map_or_else(
+                || backup.display().to_string(),
+                |name| name.to_string_lossy().to_string(),
+            );
+        let repo_file = Self::repo_file(dotfile, config);
+        let msg = format!(
+            "Restore '~/{}' from the backup taken {taken}?\n\n\
+            The stored copy at {} will be replaced with the backup.\n\
+            Changes to it that haven't been synced will be lost.",
+            dotfile.relative_path.display(),
+            repo_file.display()
+        );
+
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}/y: Restore | {}/n: Cancel",
+            k(crate::keymap::Action::Confirm),
+            k(crate::keymap::Action::Cancel)
+        );
+
+        let dialog = Dialog::new("Restore from Backup", &msg)
+            .variant(DialogVariant::Warning)
+            .footer(&footer_text);
+        frame.render_widget(dialog, area);
+
+        Ok(())
+    }
+
     fn render_remove_custom_confirm(
         &self,
         frame: &mut Frame,
@@ -1727,11 +2956,40 @@ impl DotfileSelectionScreen {
             DotfileAction::ToggleFileSync {
                 file_index,
                 is_synced,
-            } => self.toggle_file_sync(config, file_index, is_synced),
+            } => {
+                let result = self.toggle_file_sync(config, file_index, is_synced);
+                // Adding a suggestion moves it to the profile files; keep it selected
+                let display_items = self.get_display_items(&config.active_profile);
+                if let Some(row) = display_items
+                    .iter()
+                    .position(|item| *item == DisplayItem::File(file_index))
+                {
+                    self.state.dotfile_list_state.select(Some(row));
+                }
+                result
+            }
             DotfileAction::AddCustomFileToSync {
                 full_path,
                 relative_path,
             } => self.add_custom_file_to_sync(config, config_path, full_path, relative_path),
+            DotfileAction::StoreLargeFile { storage } => {
+                let Some(prompt) = self.state.large_file_prompt.take() else {
+                    return Ok(ActionResult::None);
+                };
+                match prompt.target {
+                    AddTarget::Listed(file_index) => self.store_file(config, file_index, storage),
+                    AddTarget::Custom {
+                        full_path,
+                        relative_path,
+                    } => self.store_custom_file(
+                        config,
+                        config_path,
+                        full_path,
+                        relative_path,
+                        storage,
+                    ),
+                }
+            }
             DotfileAction::SetBackupEnabled { enabled } => {
                 self.state.backup_enabled = enabled;
                 Ok(ActionResult::None)
@@ -1741,9 +2999,15 @@ impl DotfileSelectionScreen {
                 is_common,
                 profiles_to_cleanup,
             } => self.move_to_common(config, file_index, is_common, profiles_to_cleanup),
+            DotfileAction::MoveManyToCommon { files } => self.move_many_to_common(config, &files),
             DotfileAction::RemoveCustomFile { file_index } => {
                 self.remove_custom_file(config, config_path, file_index)
             }
+            DotfileAction::RenameFile {
+                file_index,
+                new_path,
+            } => self.rename_file(config, config_path, file_index, &new_path),
+            DotfileAction::ApplyFileQueue { operations } => self.apply_queue(config, operations),
         }
     }
 
@@ -1757,6 +3021,7 @@ impl DotfileSelectionScreen {
         // Update state
         self.state.dotfiles = dotfiles;
         self.state.selected_for_sync.clear();
+        self.state.marked.clear();
 
         // Mark synced files as selected
         for (i, dotfile) in self.state.dotfiles.iter().enumerate() {
@@ -1817,19 +3082,68 @@ impl DotfileSelectionScreen {
         }
     }
 
-    /// Add a file to sync.
+    /// Add a file to sync, asking first how to store it when it's large or binary.
     fn add_file_to_sync(&mut self, config: &Config, file_index: usize) -> Result<ActionResult> {
         let dotfile = &self.state.dotfiles[file_index];
         let relative_path = dotfile.relative_path.to_string_lossy().to_string();
         let full_path = dotfile.original_path.clone();
+        if self.prompt_if_large(
+            config,
+            AddTarget::Listed(file_index),
+            &full_path,
+            &relative_path,
+        ) {
+            return Ok(ActionResult::None);
+        }
+        self.store_file(config, file_index, AddStorage::Plain)
+    }
+
+    /// Open the large file dialog for `full_path` when it's over the size
+    /// threshold or holds binary files. Returns whether it was opened.
+    fn prompt_if_large(
+        &mut self,
+        config: &Config,
+        target: AddTarget,
+        full_path: &Path,
+        relative_path: &str,
+    ) -> bool {
+        let Some(report) =
+            crate::utils::file_policy::inspect(full_path, config.large_file_threshold_mb)
+        else {
+            return false;
+        };
+        info!("Asking how to store {}: {:?}", relative_path, report);
+        self.state.large_file_prompt = Some(LargeFilePrompt {
+            target,
+            relative_path: relative_path.to_string(),
+            report,
+            can_lfs: crate::utils::lfs::is_available(),
+            can_encrypt: crate::utils::sops::is_enabled(&config.repo_path) && !full_path.is_dir(),
+        });
+        true
+    }
+
+    /// Add a file of the list to sync, stored as `storage` says.
+    fn store_file(
+        &mut self,
+        config: &Config,
+        file_index: usize,
+        storage: AddStorage,
+    ) -> Result<ActionResult> {
+        let Some(dotfile) = self.state.dotfiles.get(file_index) else {
+            return Ok(ActionResult::None);
+        };
+        let relative_path = dotfile.relative_path.to_string_lossy().to_string();
+        let full_path = dotfile.original_path.clone();
 
         info!("Adding file to sync: {}", relative_path);
 
-        match SyncService::add_file_to_sync(
+        match SyncService::add_file_to_sync_as(
             config,
             &full_path,
             &relative_path,
             self.state.backup_enabled,
+            storage,
         ) {
             Ok(crate::services::AddFileResult::Success) => {
                 // Update state
@@ -1982,12 +3296,38 @@ impl DotfileSelectionScreen {
             });
         }
 
+        let target = AddTarget::Custom {
+            full_path: full_path.clone(),
+            relative_path: relative_path.clone(),
+        };
+        if self.prompt_if_large(config, target, &full_path, &relative_path) {
+            return Ok(ActionResult::None);
+        }
+        self.store_custom_file(
+            config,
+            config_path,
+            full_path,
+            relative_path,
+            AddStorage::Plain,
+        )
+    }
+
+    /// Add a checked custom file to sync, stored as `storage` says.
+    fn store_custom_file(
+        &mut self,
+        config: &mut Config,
+        config_path: &Path,
+        full_path: PathBuf,
+        relative_path: String,
+        storage: AddStorage,
+    ) -> Result<ActionResult> {
         // Add to sync using SyncService
-        match SyncService::add_file_to_sync(
+        match SyncService::add_file_to_sync_as(
             config,
             &full_path,
             &relative_path,
             self.state.backup_enabled,
+            storage,
         ) {
             Ok(crate::services::AddFileResult::Success) => {
                 // Add to custom files in config if not already present
@@ -2034,6 +3374,47 @@ impl DotfileSelectionScreen {
     }
 
     /// Remove a custom file entry from `config.custom_files`.
+    /// Rename or move a synced file.
+    fn rename_file(
+        &mut self,
+        config: &mut Config,
+        config_path: &Path,
+        file_index: usize,
+        new_path: &str,
+    ) -> Result<ActionResult> {
+        let Some(dotfile) = self.state.dotfiles.get(file_index) else {
+            return Ok(ActionResult::ShowToast {
+                message: "Invalid file selection".into(),
+                variant: crate::widgets::ToastVariant::Error,
+            });
+        };
+        let old_path = dotfile.relative_path.to_string_lossy().to_string();
+        let new_path = new_path.trim_start_matches("~/");
+
+        if let Err(e) = SyncService::rename_file(config, &old_path, new_path) {
+            warn!("Error renaming {}: {}", old_path, e);
+            return Ok(ActionResult::ShowToast {
+                message: format!("Error: {e}"),
+                variant: crate::widgets::ToastVariant::Error,
+            });
+        }
+
+        if let Some(entry) = config.custom_files.iter_mut().find(|f| **f == old_path) {
+            *entry = new_path.to_string();
+            if let Err(e) = config.save(config_path) {
+                warn!("Failed to save config: {}", e);
+            }
+        }
+
+        self.scan_dotfiles(config)?;
+
+        info!("Renamed {} to {}", old_path, new_path);
+        Ok(ActionResult::ShowToast {
+            message: format!("Moved {old_path} to {new_path}"),
+            variant: crate::widgets::ToastVariant::Success,
+        })
+    }
+
     fn remove_custom_file(
         &mut self,
         config: &mut Config,
@@ -2164,6 +3545,93 @@ impl DotfileSelectionScreen {
             }
         }
     }
+
+    /// Apply the reviewed queue of file changes, all or nothing. When one
+    /// fails, the changes are queued again so it can be skipped.
+    fn apply_queue(
+        &mut self,
+        config: &Config,
+        operations: Vec<FileOperation>,
+    ) -> Result<ActionResult> {
+        info!("Applying {} queued file change(s)", operations.len());
+        let result = SyncService::apply_operations(config, &operations, self.state.backup_enabled);
+        self.scan_dotfiles(config)?;
+        match result {
+            Ok(applied) => Ok(ActionResult::ShowToast {
+                message: format!("Applied {applied} change(s)"),
+                variant: crate::widgets::ToastVariant::Success,
+            }),
+            Err(e) => {
+                warn!("Error applying queued changes: {:#}", e);
+                self.state.queue = operations
+                    .into_iter()
+                    .map(|operation| QueuedOperation {
+                        operation,
+                        skip: false,
+                    })
+                    .collect();
+                Ok(ActionResult::ShowDialog {
+                    title: "Changes Not Applied".to_string(),
+                    content: format!(
+                        "{e}\n\nThe changes are queued again. Skip the one that failed to apply the rest."
+                    ),
+                    variant: crate::widgets::DialogVariant::Error,
+                })
+            }
+        }
+    }
+
+    /// Move several files from the profile to common in one commit.
+    pub fn move_many_to_common(
+        &mut self,
+        config: &Config,
+        files: &[(usize, Vec<String>)],
+    ) -> Result<ActionResult> {
+        let files: Vec<(String, Vec<String>)> = files
+            .iter()
+            .filter_map(|(file_index, profiles_to_cleanup)| {
+                self.state.dotfiles.get(*file_index).map(|dotfile| {
+                    (
+                        dotfile.relative_path.to_string_lossy().to_string(),
+                        profiles_to_cleanup.clone(),
+                    )
+                })
+            })
+            .collect();
+        info!("Moving {} files from profile to common", files.len());
+
+        let result = match SyncService::move_many_to_common(config, &files) {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Error moving files to common: {}", e);
+                return Ok(ActionResult::ShowToast {
+                    message: format!("Error: {e}"),
+                    variant: crate::widgets::ToastVariant::Error,
+                });
+            }
+        };
+        self.scan_dotfiles(config)?;
+
+        let mut message = format!("Moved {} file(s) to common", result.moved.len());
+        if result.committed {
+            message.push_str(" in one commit");
+        }
+        if result.failed.is_empty() {
+            return Ok(ActionResult::ShowToast {
+                message,
+                variant: crate::widgets::ToastVariant::Success,
+            });
+        }
+        let failed: Vec<String> = result
+            .failed
+            .iter()
+            .map(|(path, e)| format!("{path}: {e}"))
+            .collect();
+        Ok(ActionResult::ShowToast {
+            message: format!("{message}; failed: {}", failed.join(", ")),
+            variant: crate::widgets::ToastVariant::Warning,
+        })
+    }
 }
 
 // Helper function to format file sizes
@@ -2204,6 +3672,15 @@ impl Screen for DotfileSelectionScreen {
             "Add or remove files to your repository. You can also add custom files. We have automatically detected some common dotfiles for you."
         )?;
 
+        let pull_key = ctx
+            .config
+            .keymap
+            .get_key_display_for_action(crate::keymap::Action::Pull);
+        let icons = crate::icons::Icons::from_config(ctx.config);
+        let content_chunk = self
+            .remote_banner
+            .render(frame, content_chunk, &pull_key, &icons);
+
         // Render main content (either custom file input or dotfile list)
         if self.state.adding_custom_file && !self.file_browser.is_open() {
             self.render_custom_file_input(frame, content_chunk, footer_chunk, ctx.config)?;
@@ -2224,6 +3701,8 @@ impl Screen for DotfileSelectionScreen {
             self.file_browser
                 .render(frame, area, ctx.config, ctx.syntax_set, ctx.syntax_theme)?;
         }
+        self.timeline.render(frame, area, ctx.config);
+        self.file_menu.render(frame, area, ctx.config);
 
         // Render modals on top of the content (not instead of it)
         if self.state.show_custom_file_confirm {
@@ -2231,12 +3710,22 @@ impl Screen for DotfileSelectionScreen {
         } else if self.state.confirm_move.is_some() {
             // Move confirmation modals render on top of the main content
             self.render_move_confirm(frame, area, ctx.config)?;
+        } else if self.state.large_file_prompt.is_some() {
+            self.render_large_file_prompt(frame, area, ctx.config)?;
         } else if self.state.confirm_unsync_common.is_some() {
             // Unsync common file confirmation
             self.render_unsync_common_confirm(frame, area, ctx.config)?;
         } else if self.state.confirm_remove_custom.is_some() {
             // Remove custom file confirmation
             self.render_remove_custom_confirm(frame, area, ctx.config)?;
+        } else if self.state.confirm_restore.is_some() {
+            self.render_restore_confirm(frame, area, ctx.config)?;
+        } else if self.state.renaming.is_some() {
+            self.render_rename_popup(frame, area, ctx.config)?;
+        } else if self.state.batch_move.is_some() {
+            self.render_batch_move_confirm(frame, area, ctx.config)?;
+        } else if self.state.queue_review.is_some() {
+            self.render_queue_review(frame, area, ctx.config)?;
         }
 
         Ok(())
@@ -2244,6 +3733,44 @@ impl Screen for DotfileSelectionScreen {
 
     fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
         // 1. Modal first - captures all events
+        if self.timeline.is_open() {
+            return Ok(match self.timeline.handle_event(event, ctx.config) {
+                FileTimelineResult::None | FileTimelineResult::Closed => ScreenAction::Refresh,
+                FileTimelineResult::ToggleSecrets => ScreenAction::toggle_secrets(),
+                FileTimelineResult::Failed(message) => ScreenAction::ShowToast {
+                    message,
+                    variant: crate::widgets::ToastVariant::Error,
+                },
+                FileTimelineResult::Restored(message) => {
+                    self.state.preview_scroll = 0;
+                    ScreenAction::ShowToast {
+                        message,
+                        variant: crate::widgets::ToastVariant::Success,
+                    }
+                }
+            });
+        }
+
+        if self.file_menu.is_open() {
+            return Ok(match self.file_menu.handle_event(event, ctx.config) {
+                ActionMenuResult::None | ActionMenuResult::Closed => ScreenAction::Refresh,
+                ActionMenuResult::Chosen(action) => {
+                    let display_items = self.get_display_items(&ctx.config.active_profile);
+                    match self
+                        .state
+                        .dotfile_list_state
+                        .selected()
+                        .and_then(|idx| display_items.get(idx))
+                    {
+                        Some(DisplayItem::File(file_idx)) => {
+                            self.run_file_menu_action(action, *file_idx, ctx.config)
+                        }
+                        _ => ScreenAction::Refresh,
+                    }
+                }
+            });
+        }
+
         if self.state.show_custom_file_confirm {
             if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
@@ -2262,6 +3789,15 @@ impl Screen for DotfileSelectionScreen {
             return Ok(ScreenAction::None);
         }
 
+        if self.state.large_file_prompt.is_some() {
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    return self.handle_large_file_prompt(key.code, ctx.config);
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
         if self.state.confirm_unsync_common.is_some() {
             if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
@@ -2280,6 +3816,42 @@ impl Screen for DotfileSelectionScreen {
             return Ok(ScreenAction::None);
         }
 
+        if self.state.confirm_restore.is_some() {
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    return self.handle_restore_confirm(key.code, ctx.config);
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if self.state.renaming.is_some() {
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    return self.handle_rename_input(key, ctx.config);
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if self.state.batch_move.is_some() {
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    return self.handle_batch_move_confirm(key.code, ctx.config);
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if self.state.queue_review.is_some() {
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    return self.handle_queue_review(key.code, ctx.config);
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
         // 2. File browser mode - delegate to component
         if self.file_browser.is_open() {
             let result = self.file_browser.handle_event(event, ctx.config)?;
@@ -2328,6 +3900,30 @@ impl Screen for DotfileSelectionScreen {
 
         // 4. Normal navigation based on focus
         match event {
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press
+                    && self.remote_banner.can_pull()
+                    && ctx.config.keymap.get_action(key.code, key.modifiers)
+                        == Some(crate::keymap::Action::Pull) =>
+            {
+                return Ok(ScreenAction::PullFromRemote);
+            }
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press
+                    && self.state.focus == DotfileSelectionFocus::FilesList
+                    && (ctx.config.review_changes || !self.state.queue.is_empty())
+                    && ctx.config.keymap.get_action(key.code, key.modifiers)
+                        == Some(crate::keymap::Action::Save) =>
+            {
+                return Ok(self.open_queue_review());
+            }
+            Event::Mouse(mouse)
+                if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                    && self.remote_banner.can_pull()
+                    && self.remote_banner.contains(mouse.column, mouse.row) =>
+            {
+                return Ok(ScreenAction::PullFromRemote);
+            }
             Event::Key(key) if key.kind == KeyEventKind::Press => match self.state.focus {
                 DotfileSelectionFocus::FilesList => {
                     return self.handle_dotfile_list(key.code, ctx.config);
@@ -2347,7 +3943,9 @@ impl Screen for DotfileSelectionScreen {
     }
 
     fn is_input_focused(&self) -> bool {
-        if self.file_browser.is_open() {
+        if self.state.renaming.is_some() {
+            true
+        } else if self.file_browser.is_open() {
             self.file_browser.is_input_focused()
         } else if self.state.adding_custom_file {
             self.state.custom_file_focused
@@ -2356,9 +3954,12 @@ impl Screen for DotfileSelectionScreen {
         }
     }
 
-    fn on_enter(&mut self, _ctx: &ScreenContext) -> Result<()> {
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
         // Request scan on enter - this will be handled by app
         // Note: We return None here but app should call scan_dotfiles when navigating to this screen
+        // Decrypted previews lock again each time the screen is entered
+        self.state.decrypted_targets = crate::utils::sops::decrypted_targets(&ctx.config.repo_path);
+        self.state.unlocked.clear();
         Ok(())
     }
 }
@@ -2374,6 +3975,177 @@ mod tests {
         assert!(screen.state.dotfiles.is_empty());
     }
 
+    #[test]
+    fn test_unsynced_candidates_are_suggested() {
+        let dotfile = |path: &str, synced, is_custom| Dotfile {
+            original_path: PathBuf::from("/home/me").join(path),
+            relative_path: PathBuf::from(path),
+            synced,
+            description: None,
+            is_common: false,
+            is_custom,
+        };
+        let mut screen = DotfileSelectionScreen::new();
+        screen.state.dotfiles = vec![
+            dotfile(".config/alacritty", false, false),
+            dotfile(".zshrc", true, false),
+            dotfile(".myrc", false, true),
+        ];
+        assert_eq!(
+            screen.get_display_items("work"),
+            vec![
+                DisplayItem::Header("Profile Files (work)".to_string()),
+                DisplayItem::File(1),
+                DisplayItem::File(2),
+                DisplayItem::Header(String::new()),
+                DisplayItem::Header("Suggested Additions (1)".to_string()),
+                DisplayItem::File(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeat_last_action() {
+        let config = Config::default();
+        let dotfile = |path: &str, synced| Dotfile {
+            original_path: PathBuf::from("/home/me").join(path),
+            relative_path: PathBuf::from(path),
+            synced,
+            description: None,
+            is_common: false,
+            is_custom: false,
+        };
+        let mut screen = DotfileSelectionScreen::new();
+        screen.state.dotfiles = vec![
+            dotfile(".zshrc", true),
+            dotfile(".vimrc", true),
+            dotfile(".tmux.conf", false),
+            dotfile(".gitconfig", false),
+        ];
+        screen.state.selected_for_sync = [0, 1].into_iter().collect();
+        assert!(matches!(
+            screen.repeat_last_action(0, &config),
+            ScreenAction::ShowToast { .. }
+        ));
+
+        screen.toggle_mark(0);
+        screen.repeat_last_action(1, &config);
+        assert_eq!(screen.state.marked, [0, 1].into_iter().collect());
+
+        assert!(matches!(
+            screen.toggle_sync(2, &config),
+            ScreenAction::ToggleFileSync {
+                file_index: 2,
+                is_synced: false
+            }
+        ));
+        assert!(matches!(
+            screen.repeat_last_action(3, &config),
+            ScreenAction::ToggleFileSync {
+                file_index: 3,
+                is_synced: false
+            }
+        ));
+        // Adding a file that's already synced does nothing
+        assert!(matches!(
+            screen.repeat_last_action(0, &config),
+            ScreenAction::ShowToast { .. }
+        ));
+    }
+
+    #[test]
+    fn test_review_queue() {
+        let config = Config {
+            review_changes: true,
+            ..Config::default()
+        };
+        let dotfile = |path: &str, synced| Dotfile {
+            original_path: PathBuf::from("/nonexistent/home").join(path),
+            relative_path: PathBuf::from(path),
+            synced,
+            description: None,
+            is_common: false,
+            is_custom: false,
+        };
+        let mut screen = DotfileSelectionScreen::new();
+        screen.state.dotfiles = vec![dotfile(".zshrc", true), dotfile(".tmux.conf", false)];
+        screen.state.selected_for_sync = [0].into_iter().collect();
+
+        // Adding and removing are queued in order instead of applied
+        assert!(matches!(
+            screen.toggle_sync(1, &config),
+            ScreenAction::Refresh
+        ));
+        screen.toggle_sync(0, &config);
+        let queued: Vec<String> = screen
+            .state
+            .queue
+            .iter()
+            .map(|queued| queued.operation.describe())
+            .collect();
+        assert_eq!(queued, vec!["Add ~/.tmux.conf", "Remove ~/.zshrc"]);
+
+        // Toggling a queued file takes it out of the queue
+        screen.toggle_sync(1, &config);
+        assert_eq!(screen.state.queue.len(), 1);
+
+        // Skipped changes are left out when the queue is applied
+        assert!(matches!(screen.open_queue_review(), ScreenAction::Refresh));
+        screen
+            .handle_queue_review(KeyCode::Char(' '), &config)
+            .unwrap();
+        assert!(matches!(
+            screen.handle_queue_review(KeyCode::Enter, &config).unwrap(),
+            ScreenAction::Refresh
+        ));
+        assert!(screen.state.queue.is_empty());
+        assert!(screen.state.queue_review.is_none());
+    }
+
+    #[test]
+    fn test_restore_from_backup_asks_first() {
+        let config = Config::default();
+        let mut screen = DotfileSelectionScreen::new();
+        screen.state.dotfiles = vec![Dotfile {
+            original_path: PathBuf::from("/nonexistent/home/.zshrc"),
+            relative_path: PathBuf::from(".zshrc"),
+            synced: true,
+            description: None,
+            is_common: false,
+            is_custom: false,
+        }];
+        let backup = PathBuf::from("/nonexistent/backups/2026-10-16T09-30-00/.zshrc");
+
+        // Cancelling closes the dialog without restoring
+        screen.state.confirm_restore = Some((0, backup.clone()));
+        assert!(matches!(
+            screen
+                .handle_restore_confirm(KeyCode::Esc, &config)
+                .unwrap(),
+            ScreenAction::Refresh
+        ));
+        assert!(screen.state.confirm_restore.is_none());
+
+        // Other keys leave it open
+        screen.state.confirm_restore = Some((0, backup));
+        assert!(matches!(
+            screen
+                .handle_restore_confirm(KeyCode::Char('x'), &config)
+                .unwrap(),
+            ScreenAction::None
+        ));
+        assert!(screen.state.confirm_restore.is_some());
+
+        // Confirming restores, which reports back either way
+        assert!(matches!(
+            screen
+                .handle_restore_confirm(KeyCode::Char('y'), &config)
+                .unwrap(),
+            ScreenAction::ShowToast { .. }
+        ));
+        assert!(screen.state.confirm_restore.is_none());
+    }
+
     #[test]
     fn test_set_backup_enabled() {
         let mut screen = DotfileSelectionScreen::new();
@@ -0,0 +1,822 @@
+//! Force sync screen controller.
+//!
+//! Recovery for when local and remote have diverged beyond what a normal
+//! sync can fix: either discard local changes and match the remote, or force
+//! push the local state over the remote. Each option shows what it will change
+//! and has to be confirmed by typing a word. A last row leads to the
+//! reconcile wizard, which keeps both sides instead.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::components::Popup;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::services::git_service::{ForceSyncMode, ForceSyncPreview, GitService, SyncResult};
+use crate::services::TokenService;
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions, TextInput,
+};
+use crate::widgets::{TextInputWidget, TextInputWidgetExt, ToastVariant};
+use anyhow::Result;
+use crossterm::event::{
+    Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use tracing::info;
+
+/// Options in the order they are listed.
+const OPTIONS: [ForceSyncMode; 2] = [ForceSyncMode::MatchRemote, ForceSyncMode::OverwriteRemote];
+
+/// Row after the options that opens the reconcile wizard
+const RECONCILE_ROW: usize = OPTIONS.len();
+
+/// Force sync screen controller.
+pub struct ForceSyncScreen {
+    /// What each option would change, from the last fetch
+    preview: Option<ForceSyncPreview>,
+    /// Receiver for an in-flight fetch and compare
+    preview_rx: Option<Receiver<ForceSyncPreview>>,
+    /// Receiver for an in-flight force sync, with the option being applied
+    run_rx: Option<(ForceSyncMode, Receiver<SyncResult>)>,
+    list_state: ListState,
+    /// Option waiting for its typed confirmation
+    confirming: Option<ForceSyncMode>,
+    confirm_input: TextInput,
+    details_scroll: u16,
+    /// Clickable option rows (value = index into `OPTIONS`, or `RECONCILE_ROW`)
+    option_regions: MouseRegions<usize>,
+    /// Details pane area (for scroll hit-testing)
+    details_area: Option<Rect>,
+    /// Confirmation popup area (clicks outside it cancel)
+    confirm_area: Option<Rect>,
+    /// Spinner animation counter while fetching or forcing
+    spinner_tick: usize,
+}
+
+impl Default for ForceSyncScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ForceSyncScreen {
+    /// Create a new force sync screen.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        Self {
+            preview: None,
+            preview_rx: None,
+            run_rx: None,
+            list_state,
+            confirming: None,
+            confirm_input: TextInput::new(),
+            details_scroll: 0,
+            option_regions: MouseRegions::new(),
+            details_area: None,
+            confirm_area: None,
+            spinner_tick: 0,
+        }
+    }
+
+    /// Whether a fetch or a force sync is currently running.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.preview_rx.is_some() || self.run_rx.is_some()
+    }
+
+    /// Fetch the remote and compare it with the local state in the background.
+    fn load_preview(&mut self, config: &Config) {
+        if self.is_busy() {
+            return;
+        }
+        let config = config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(GitService::force_sync_preview(&config));
+        });
+        self.preview_rx = Some(rx);
+        self.preview = None;
+        self.details_scroll = 0;
+    }
+
+    /// Run the confirmed option in the background.
+    fn start_force_sync(&mut self, mode: ForceSyncMode, config: &Config) {
+        if self.is_busy() {
+            return;
+        }
+        info!("Starting force sync: {:?}", mode);
+        let config = config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(GitService::force_sync(&config, mode));
+        });
+        self.run_rx = Some((mode, rx));
+    }
+
+    /// Poll background work. Returns `ScreenAction::Refresh` while work is in progress.
+    pub fn tick(&mut self, config: &Config) -> ScreenAction {
+        if let Some(rx) = &self.preview_rx {
+            self.spinner_tick = self.spinner_tick.wrapping_add(1);
+            match rx.try_recv() {
+                Ok(preview) => {
+                    self.preview = Some(preview);
+                    self.preview_rx = None;
+                }
+                Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+                Err(TryRecvError::Disconnected) => {
+                    self.preview = Some(ForceSyncPreview {
+                        error: Some("Comparing with the remote stopped unexpectedly".to_string()),
+                        ..ForceSyncPreview::default()
+                    });
+                    self.preview_rx = None;
+                }
+            }
+            return ScreenAction::Refresh;
+        }
+
+        if let Some((mode, rx)) = &self.run_rx {
+            self.spinner_tick = self.spinner_tick.wrapping_add(1);
+            let mode = *mode;
+            let result = match rx.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+                Err(TryRecvError::Disconnected) => SyncResult {
+                    success: false,
+                    message: "Force sync stopped unexpectedly".to_string(),
+                    pulled_count: None,
+                },
+            };
+            self.run_rx = None;
+
+            // Show the new state of both sides
+            self.load_preview(config);
+            if result.success {
+                return ScreenAction::ShowToast {
+                    message: match mode {
+                        ForceSyncMode::MatchRemote => "Local repository now matches the remote",
+                        ForceSyncMode::OverwriteRemote => "Remote now matches your local state",
+                    }
+                    .to_string(),
+                    variant: ToastVariant::Success,
+                };
+            }
+            if TokenService::uses_token(config) && TokenService::is_auth_failure(&result.message) {
+                return ScreenAction::Reauthenticate;
+            }
+            return ScreenAction::ShowMessage {
+                title: "Force Sync Failed".to_string(),
+                content: result.message,
+            };
+        }
+
+        ScreenAction::None
+    }
+
+    fn selected_mode(&self) -> ForceSyncMode {
+        OPTIONS[self
+            .list_state
+            .selected()
+            .unwrap_or(0)
+            .min(OPTIONS.len() - 1)]
+    }
+
+    fn select(&mut self, index: usize) {
+        if self.list_state.selected() != Some(index) {
+            self.list_state.select(Some(index));
+            self.details_scroll = 0;
+        }
+    }
+
+    /// Ask for the typed confirmation of the selected option, or open the
+    /// reconcile wizard.
+    fn open_confirm(&mut self, config: &Config) -> ScreenAction {
+        if self.list_state.selected() == Some(RECONCILE_ROW) {
+            if config.read_only {
+                return ScreenAction::pull_only_blocked();
+            }
+            return ScreenAction::Navigate(ScreenId::Reconcile);
+        }
+        let mode = self.selected_mode();
+        if mode == ForceSyncMode::OverwriteRemote && config.read_only {
+            return ScreenAction::pull_only_blocked();
+        }
+        if self.is_busy() {
+            return ScreenAction::None;
+        }
+        match &self.preview {
+            Some(preview) if preview.error.is_none() => {
+                self.confirm_input.clear();
+                self.confirming = Some(mode);
+                ScreenAction::None
+            }
+            _ => ScreenAction::ShowToast {
+                message: "Can't force sync until the remote can be compared".to_string(),
+                variant: ToastVariant::Warning,
+            },
+        }
+    }
+
+    /// Run the option being confirmed if the right word was typed.
+    fn submit_confirm(&mut self, config: &Config) -> ScreenAction {
+        let Some(mode) = self.confirming else {
+            return ScreenAction::None;
+        };
+        if self.confirm_input.text_trimmed() != confirm_word(mode) {
+            return ScreenAction::ShowToast {
+                message: format!("Type '{}' to confirm", confirm_word(mode)),
+                variant: ToastVariant::Warning,
+            };
+        }
+        self.confirming = None;
+        self.confirm_input.clear();
+        self.start_force_sync(mode, config);
+        ScreenAction::Refresh
+    }
+
+    fn scroll_details(&mut self, delta: i32) {
+        self.details_scroll = if delta < 0 {
+            self.details_scroll
+                .saturating_sub(delta.unsigned_abs() as u16)
+        } else {
+            self.details_scroll.saturating_add(delta as u16)
+        };
+    }
+
+    fn render_options(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let t = theme();
+        let icons = Icons::from_config(config);
+
+        let mut items: Vec<ListItem> = OPTIONS
+            .iter()
+            .map(|&mode| {
+                let disabled = mode == ForceSyncMode::OverwriteRemote && config.read_only;
+                let (title_style, text_style) = if disabled {
+                    (t.muted_style(), t.muted_style())
+                } else {
+                    (t.title_style(), t.muted_style())
+                };
+                let mut lines = vec![Line::from(Span::styled(
+                    format!("{} {}", icons.warning(), option_title(mode)),
+                    title_style,
+                ))];
+                let description = if disabled {
+                    "Disabled on a pull-only machine"
+                } else {
+                    option_description(mode)
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("   {description}"),
+                    text_style,
+                )));
+                lines.push(Line::from(""));
+                ListItem::new(Text::from(lines))
+            })
+            .collect();
+        let (title_style, description) = if config.read_only {
+            (t.muted_style(), "Disabled on a pull-only machine")
+        } else {
+            (
+                t.title_style(),
+                "Keep local, remote or merged for each differing file",
+            )
+        };
+        items.push(ListItem::new(Text::from(vec![
+            Line::from(Span::styled(
+                format!("{} Reconcile file by file", icons.info()),
+                title_style,
+            )),
+            Line::from(Span::styled(format!("   {description}"), t.muted_style())),
+            Line::from(""),
+        ])));
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Options ")
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        // Each option spans three lines
+        self.option_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for index in 0..=RECONCILE_ROW {
+            let y = inner.y + index as u16 * 3;
+            if y >= inner.y + inner.height {
+                break;
+            }
+            let h = 3.min(inner.y + inner.height - y);
+            self.option_regions
+                .add(Rect::new(inner.x, y, inner.width, h), index);
+        }
+    }
+
+    fn render_details(&mut self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" What will change ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+        self.details_area = Some(area);
+
+        let lines = if self.list_state.selected() == Some(RECONCILE_ROW) {
+            reconcile_lines(self.preview.as_ref())
+        } else if let Some(preview) = &self.preview {
+            summary_lines(preview, self.selected_mode())
+        } else {
+            let dots = ".".repeat(self.spinner_tick / 2 % 4);
+            vec![Line::from(Span::styled(
+                format!("Fetching remote{dots}"),
+                Style::default().fg(t.warning),
+            ))]
+        };
+        let para = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.details_scroll, 0));
+        frame.render_widget(para, area);
+    }
+
+    fn render_confirm(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let Some(mode) = self.confirming else {
+            self.confirm_area = None;
+            return;
+        };
+        let t = theme();
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Confirm | {}: Cancel",
+            k(Action::Confirm),
+            k(Action::Cancel)
+        );
+
+        let Some(result) = Popup::new()
+            .width(60)
+            .height(40)
+            .min_height(12)
+            .min_width(50)
+            .title(option_title(mode))
+            .dim_background(true)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return;
+        };
+        self.confirm_area = Some(result.content_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(2), Constraint::Length(3)])
+            .split(result.content_area);
+
+        let warning = match mode {
+            ForceSyncMode::MatchRemote => {
+                "Local commits and changes to tracked files will be discarded. This can't be undone."
+            }
+            ForceSyncMode::OverwriteRemote => {
+                "Remote commits you don't have locally will be discarded for every machine."
+            }
+        };
+        let text = vec![
+            Line::from(Span::styled(warning, Style::default().fg(t.warning))),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Type ", t.text_style()),
+                Span::styled(confirm_word(mode), t.emphasis_style()),
+                Span::styled(" to confirm:", t.text_style()),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(text).wrap(Wrap { trim: true }), chunks[0]);
+
+        let widget = TextInputWidget::new(&self.confirm_input)
+            .title("Confirmation")
+            .placeholder(confirm_word(mode))
+            .focused(true);
+        frame.render_text_input_widget(widget, chunks[1]);
+    }
+
+    /// Handle input while the confirmation popup is open
+    fn handle_confirm_event(&mut self, event: Event, ctx: &ScreenContext) -> ScreenAction {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                // Plain characters are always typed, so bindings like 'q' don't interfere
+                if let KeyCode::Char(c) = key.code {
+                    if !key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER)
+                    {
+                        self.confirm_input.insert_char(c);
+                        return ScreenAction::None;
+                    }
+                }
+                let action = ctx.config.keymap.get_action(key.code, key.modifiers);
+                match action {
+                    Some(Action::Cancel) => {
+                        self.confirming = None;
+                        self.confirm_input.clear();
+                    }
+                    Some(Action::Confirm) => return self.submit_confirm(ctx.config),
+                    _ => {
+                        self.confirm_input
+                            .handle_key_with_action(key.code, key.modifiers, action);
+                    }
+                }
+            }
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                let pos = Position::new(mouse.column, mouse.row);
+                if !self.confirm_area.is_some_and(|a| a.contains(pos)) {
+                    self.confirming = None;
+                    self.confirm_input.clear();
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &ScreenContext) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&index) = self.option_regions.hit_test(mouse.column, mouse.row) {
+                    if self.list_state.selected() == Some(index) {
+                        return self.open_confirm(ctx.config);
+                    }
+                    self.select(index);
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::ScrollUp if self.details_area.is_some_and(|a| a.contains(pos)) => {
+                self.scroll_details(-3);
+            }
+            MouseEventKind::ScrollDown if self.details_area.is_some_and(|a| a.contains(pos)) => {
+                self.scroll_details(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+fn option_title(mode: ForceSyncMode) -> &'static str {
+    match mode {
+        ForceSyncMode::MatchRemote => "Discard local changes and match remote",
+        ForceSyncMode::OverwriteRemote => "Force push local state to remote",
+    }
+}
+
+fn option_description(mode: ForceSyncMode) -> &'static str {
+    match mode {
+        ForceSyncMode::MatchRemote => "Fetch, hard reset and re-create symlinks",
+        ForceSyncMode::OverwriteRemote => "Commit local changes and replace the remote branch",
+    }
+}
+
+/// Word that has to be typed to run an option.
+fn confirm_word(mode: ForceSyncMode) -> &'static str {
+    match mode {
+        ForceSyncMode::MatchRemote => "discard",
+        ForceSyncMode::OverwriteRemote => "overwrite",
+    }
+}
+
+/// Describe what `mode` would change, given the fetched state of both sides.
+fn summary_lines(preview: &ForceSyncPreview, mode: ForceSyncMode) -> Vec<Line<'static>> {
+    let t = theme();
+    let mut lines = Vec::new();
+
+    if let Some(error) = &preview.error {
+        lines.push(Line::from(Span::styled(
+            "Can't compare with the remote:",
+            Style::default().fg(t.error),
+        )));
+        for line in error.lines() {
+            lines.push(Line::from(Span::styled(line.to_string(), t.text_style())));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Forcing is disabled until the remote can be fetched.",
+            t.muted_style(),
+        )));
+        return lines;
+    }
+
+    let count_line = |label: &str, count: usize| {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), t.muted_style()),
+            Span::styled(
+                count.to_string(),
+                if count > 0 {
+                    Style::default().fg(t.warning)
+                } else {
+                    t.text_style()
+                },
+            ),
+        ])
+    };
+    let file_section = |lines: &mut Vec<Line<'static>>, title: String, files: &[String]| {
+        if files.is_empty() {
+            return;
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(title, t.title_style())));
+        for file in files {
+            lines.push(Line::from(Span::styled(
+                format!("  {file}"),
+                t.text_style(),
+            )));
+        }
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled("Branch: ", t.muted_style()),
+        Span::styled(preview.branch.clone(), t.text_style()),
+    ]));
+
+    // Untracked files survive a hard reset; everything else uncommitted is lost
+    let (untracked, tracked): (Vec<String>, Vec<String>) = preview
+        .uncommitted_files
+        .iter()
+        .cloned()
+        .partition(|entry| entry.starts_with("A "));
+
+    match mode {
+        ForceSyncMode::MatchRemote => {
+            lines.push(count_line("Local commits discarded", preview.ahead));
+            lines.push(count_line("Uncommitted changes discarded", tracked.len()));
+            lines.push(count_line("Remote commits pulled in", preview.behind));
+            file_section(
+                &mut lines,
+                format!("Uncommitted changes lost ({})", tracked.len()),
+                &tracked,
+            );
+            file_section(
+                &mut lines,
+                format!(
+                    "Files updated to match remote ({})",
+                    preview.incoming_files.len()
+                ),
+                &preview.incoming_files,
+            );
+            if !untracked.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!("{} new file(s) not yet synced are kept.", untracked.len()),
+                    t.muted_style(),
+                )));
+            }
+        }
+        ForceSyncMode::OverwriteRemote => {
+            lines.push(count_line("Remote commits discarded", preview.behind));
+            lines.push(count_line("Local commits pushed", preview.ahead));
+            lines.push(count_line(
+                "Uncommitted changes committed first",
+                preview.uncommitted_files.len(),
+            ));
+            file_section(
+                &mut lines,
+                format!(
+                    "Committed before pushing ({})",
+                    preview.uncommitted_files.len()
+                ),
+                &preview.uncommitted_files,
+            );
+            file_section(
+                &mut lines,
+                format!(
+                    "Files replaced on remote ({})",
+                    preview.outgoing_files.len()
+                ),
+                &preview.outgoing_files,
+            );
+        }
+    }
+
+    if preview.ahead == 0 && preview.behind == 0 && preview.uncommitted_files.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Local and remote are already in step.",
+            Style::default().fg(t.success),
+        )));
+    }
+    lines
+}
+
+/// Describe the reconcile wizard, with the commit counts once fetched.
+fn reconcile_lines(preview: Option<&ForceSyncPreview>) -> Vec<Line<'static>> {
+    let t = theme();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Go through each file that differs from the remote, compare the two versions \
+            and keep the local one, the remote one or a merge of both.",
+            t.text_style(),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "The result is committed as one merge of both sides and pushed normally. \
+            No commits are discarded on either side.",
+            t.muted_style(),
+        )),
+    ];
+    if let Some(preview) = preview.filter(|p| p.error.is_none()) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Local commits: ", t.muted_style()),
+            Span::styled(preview.ahead.to_string(), t.text_style()),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Remote commits: ", t.muted_style()),
+            Span::styled(preview.behind.to_string(), t.text_style()),
+        ]));
+    }
+    lines
+}
+
+impl Screen for ForceSyncScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Force Sync",
+            "Recover a diverged or broken sync by making one side match the other, or reconcile both file by file.",
+        )?;
+
+        let panes = create_split_layout(content_chunk, &[40, 60]);
+        self.render_options(frame, panes[0], ctx.config);
+        self.render_details(frame, panes[1]);
+        self.render_confirm(frame, area, ctx.config);
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = if let Some((mode, _)) = &self.run_rx {
+            let dots = ".".repeat(self.spinner_tick / 2 % 4);
+            format!("Running: {}{dots}", option_title(*mode))
+        } else if self.confirming.is_some() {
+            format!(
+                "{}: Confirm | {}: Cancel",
+                k(Action::Confirm),
+                k(Action::Cancel)
+            )
+        } else {
+            format!(
+                "{}: Navigate | {}: Choose | {}: Re-fetch | {}: Back",
+                ctx.config.keymap.navigation_display(),
+                k(Action::Confirm),
+                k(Action::Refresh),
+                k(Action::Cancel),
+            )
+        };
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        // Nothing to do until the force sync finishes
+        if self.run_rx.is_some() {
+            return Ok(ScreenAction::None);
+        }
+        if self.confirming.is_some() {
+            return Ok(self.handle_confirm_event(event, ctx));
+        }
+
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::SyncWithRemote));
+                    }
+                    Action::MoveUp => {
+                        self.select(self.list_state.selected().unwrap_or(0).saturating_sub(1));
+                    }
+                    Action::MoveDown => {
+                        self.select(
+                            (self.list_state.selected().unwrap_or(0) + 1).min(RECONCILE_ROW),
+                        );
+                    }
+                    Action::ScrollUp => self.scroll_details(-1),
+                    Action::ScrollDown => self.scroll_details(1),
+                    Action::PageUp => self.scroll_details(-10),
+                    Action::PageDown => self.scroll_details(10),
+                    Action::Confirm => return Ok(self.open_confirm(ctx.config)),
+                    Action::Refresh => self.load_preview(ctx.config),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => return Ok(self.handle_mouse(mouse, ctx)),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn is_input_focused(&self) -> bool {
+        self.confirming.is_some()
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        self.confirming = None;
+        self.confirm_input.clear();
+        self.load_preview(ctx.config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preview() -> ForceSyncPreview {
+        ForceSyncPreview {
+            branch: "main".to_string(),
+            ahead: 2,
+            behind: 1,
+            uncommitted_files: vec!["M .zshrc".to_string(), "A .vimrc".to_string()],
+            incoming_files: vec!["M .gitconfig".to_string()],
+            outgoing_files: vec!["M .bashrc".to_string()],
+            error: None,
+        }
+    }
+
+    fn text(lines: &[Line]) -> String {
+        lines
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_summary_per_option() {
+        let match_remote = text(&summary_lines(&preview(), ForceSyncMode::MatchRemote));
+        assert!(match_remote.contains("Local commits discarded: 2"));
+        assert!(match_remote.contains("Uncommitted changes discarded: 1"));
+        assert!(match_remote.contains("M .gitconfig"));
+        assert!(match_remote.contains("1 new file(s) not yet synced are kept."));
+
+        let overwrite = text(&summary_lines(&preview(), ForceSyncMode::OverwriteRemote));
+        assert!(overwrite.contains("Remote commits discarded: 1"));
+        assert!(overwrite.contains("M .bashrc"));
+        assert!(!overwrite.contains(".gitconfig"));
+    }
+
+    #[test]
+    fn test_confirm_requires_typed_word() {
+        let config = Config::default();
+        let mut screen = ForceSyncScreen::new();
+        screen.preview = Some(preview());
+
+        screen.open_confirm(&config);
+        assert_eq!(screen.confirming, Some(ForceSyncMode::MatchRemote));
+
+        // The wrong word keeps the popup open and starts nothing
+        screen.confirm_input.set_text("overwrite");
+        let action = screen.submit_confirm(&config);
+        assert!(matches!(action, ScreenAction::ShowToast { .. }));
+        assert_eq!(screen.confirming, Some(ForceSyncMode::MatchRemote));
+        assert!(!screen.is_busy());
+    }
+
+    #[test]
+    fn test_confirm_blocked_without_remote_or_on_pull_only() {
+        let mut config = Config::default();
+        let mut screen = ForceSyncScreen::new();
+        screen.preview = Some(ForceSyncPreview {
+            error: Some("offline".to_string()),
+            ..preview()
+        });
+        screen.open_confirm(&config);
+        assert!(screen.confirming.is_none());
+
+        config.read_only = true;
+        screen.preview = Some(preview());
+        screen.select(1);
+        screen.open_confirm(&config);
+        assert!(screen.confirming.is_none());
+    }
+}
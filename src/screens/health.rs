@@ -0,0 +1,777 @@
+//! Health screen controller.
+//!
+//! Runs the `Doctor` diagnostics in the background and shows the results
+//! grouped by category, with expandable details and per-issue fixes.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::doctor::{
+    Doctor, DoctorOptions, DoctorReport, DoctorSummary, ValidationResult, ValidationStatus,
+};
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions,
+};
+use crate::widgets::ToastVariant;
+use anyhow::Result;
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use tracing::{error, info};
+
+/// A row in the results list: either a category header or a single check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HealthRow {
+    Category(String),
+    /// Index into `DoctorReport::results`
+    Check(usize),
+}
+
+/// Health screen controller.
+pub struct HealthScreen {
+    /// Last completed diagnostics report
+    report: Option<DoctorReport>,
+    /// Receiver for an in-flight diagnostics run
+    run_rx: Option<Receiver<Result<DoctorReport, String>>>,
+    /// Receiver for an in-flight fix, with the fix action being applied
+    fix_rx: Option<(String, Receiver<Result<bool, String>>)>,
+    /// Error from the last diagnostics run
+    error: Option<String>,
+    /// Toast to show on the next tick (after a fix completes)
+    pending_toast: Option<(String, ToastVariant)>,
+    list_state: ListState,
+    /// Categories the user has collapsed
+    collapsed: HashSet<String>,
+    /// Checks whose details are expanded inline
+    expanded: HashSet<usize>,
+    /// Clickable rows in the results list (value = row index)
+    row_regions: MouseRegions<usize>,
+    /// Results list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+    /// "Apply fix" button area in the details pane
+    fix_button_area: Option<Rect>,
+    /// Re-run diagnostics on the next tick (after a fix, once config is reloaded)
+    rerun_pending: bool,
+    /// Spinner animation counter while diagnostics run
+    spinner_tick: usize,
+}
+
+impl Default for HealthScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthScreen {
+    /// Create a new health screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            report: None,
+            run_rx: None,
+            fix_rx: None,
+            error: None,
+            pending_toast: None,
+            list_state: ListState::default(),
+            collapsed: HashSet::new(),
+            expanded: HashSet::new(),
+            row_regions: MouseRegions::new(),
+            list_area: None,
+            fix_button_area: None,
+            rerun_pending: false,
+            spinner_tick: 0,
+        }
+    }
+
+    /// Whether diagnostics or a fix are currently running.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.run_rx.is_some() || self.fix_rx.is_some() || self.rerun_pending
+    }
+
+    /// Summary of the most recent diagnostics run, if any
+    #[must_use]
+    pub fn summary(&self) -> Option<&DoctorSummary> {
+        self.report.as_ref().map(|report| &report.summary)
+    }
+
+    /// The most recent diagnostics report, if any
+    #[must_use]
+    pub fn report(&self) -> Option<&DoctorReport> {
+        self.report.as_ref()
+    }
+
+    fn doctor_options() -> DoctorOptions {
+        DoctorOptions {
+            fix_mode: false,
+            interactive: false,
+            fix_only: Vec::new(),
+            verbose: true,
+            json_output: false,
+            quiet: true,
+        }
+    }
+
+    /// Start a diagnostics run in the background.
+    pub fn start_diagnostics(&mut self, config: &Config) {
+        if self.run_rx.is_some() {
+            return;
+        }
+        info!("Starting health diagnostics");
+        let config = config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut doctor = Doctor::new(config, Self::doctor_options());
+            let result = doctor.run_diagnostics().map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.run_rx = Some(rx);
+        self.error = None;
+    }
+
+    /// Apply the fix for the selected check in the background.
+    fn start_fix(&mut self, config: &Config) {
+        if self.is_busy() {
+            return;
+        }
+        let Some(result) = self.selected_result().cloned() else {
+            return;
+        };
+        if !is_fixable(&result) {
+            return;
+        }
+        let action = result.fix_action.clone().unwrap_or_default();
+        info!("Applying health fix: {}", action);
+
+        let config = config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut doctor = Doctor::new(config, Self::doctor_options());
+            let outcome = doctor.apply_fix(&result).map_err(|e| e.to_string());
+            let _ = tx.send(outcome);
+        });
+        self.fix_rx = Some((action, rx));
+    }
+
+    /// Poll background work. Returns `ScreenAction::Refresh` while work is in progress.
+    pub fn tick(&mut self, config: &Config) -> ScreenAction {
+        if self.rerun_pending {
+            self.rerun_pending = false;
+            self.start_diagnostics(config);
+        }
+
+        if let Some((message, variant)) = self.pending_toast.take() {
+            return ScreenAction::ShowToast { message, variant };
+        }
+
+        if let Some(rx) = &self.run_rx {
+            self.spinner_tick = self.spinner_tick.wrapping_add(1);
+            match rx.try_recv() {
+                Ok(Ok(report)) => {
+                    self.set_report(report);
+                    self.run_rx = None;
+                }
+                Ok(Err(e)) => {
+                    error!("Health diagnostics failed: {}", e);
+                    self.error = Some(e);
+                    self.run_rx = None;
+                }
+                Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+                Err(TryRecvError::Disconnected) => {
+                    self.error = Some("Diagnostics stopped unexpectedly".to_string());
+                    self.run_rx = None;
+                }
+            }
+            return ScreenAction::Refresh;
+        }
+
+        if let Some((action, rx)) = &self.fix_rx {
+            let outcome = match rx.try_recv() {
+                Ok(outcome) => outcome,
+                Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+                Err(TryRecvError::Disconnected) => Err("Fix stopped unexpectedly".to_string()),
+            };
+            self.pending_toast = Some(match outcome {
+                Ok(true) => (format!("Applied fix: {action}"), ToastVariant::Success),
+                Ok(false) => (
+                    format!("Could not apply fix: {action}"),
+                    ToastVariant::Warning,
+                ),
+                Err(e) => (format!("Fix failed: {e}"), ToastVariant::Error),
+            });
+            self.fix_rx = None;
+
+            // Fixes may rewrite the config file: reload it, then re-run
+            // diagnostics on the next tick so the list reflects the fix
+            self.rerun_pending = true;
+            return ScreenAction::ConfigUpdated;
+        }
+
+        ScreenAction::None
+    }
+
+    fn set_report(&mut self, report: DoctorReport) {
+        // Keep the selection on the same check across re-runs when possible
+        let selected_check = self.selected_result().map(|r| r.check_name.clone());
+        self.report = Some(report);
+        self.expanded.clear();
+
+        let rows = self.rows();
+        let index = selected_check
+            .and_then(|name| {
+                rows.iter().position(|row| {
+                    matches!(row, HealthRow::Check(i) if self.result(*i).is_some_and(|r| r.check_name == name))
+                })
+            })
+            .or_else(|| {
+                // Default to the first problem, or the first check
+                rows.iter()
+                    .position(|row| {
+                        matches!(row, HealthRow::Check(i) if self.result(*i).is_some_and(|r| r.status != ValidationStatus::Pass))
+                    })
+                    .or_else(|| rows.iter().position(|row| matches!(row, HealthRow::Check(_))))
+            });
+        self.list_state.select(index);
+    }
+
+    fn result(&self, index: usize) -> Option<&ValidationResult> {
+        self.report.as_ref().and_then(|r| r.results.get(index))
+    }
+
+    /// Build the visible rows, grouping checks under their category in run order.
+    fn rows(&self) -> Vec<HealthRow> {
+        let Some(report) = &self.report else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        let mut categories: Vec<&str> = Vec::new();
+        for result in &report.results {
+            if !categories.contains(&result.category.as_str()) {
+                categories.push(&result.category);
+            }
+        }
+        for category in categories {
+            rows.push(HealthRow::Category(category.to_string()));
+            if self.collapsed.contains(category) {
+                continue;
+            }
+            for (i, result) in report.results.iter().enumerate() {
+                if result.category == category {
+                    rows.push(HealthRow::Check(i));
+                }
+            }
+        }
+        rows
+    }
+
+    fn selected_row(&self) -> Option<HealthRow> {
+        let rows = self.rows();
+        self.list_state
+            .selected()
+            .and_then(|i| rows.get(i).cloned())
+    }
+
+    fn selected_result(&self) -> Option<&ValidationResult> {
+        match self.selected_row()? {
+            HealthRow::Check(i) => self.result(i),
+            HealthRow::Category(_) => None,
+        }
+    }
+
+    /// Toggle the selected row: collapse/expand a category or a check's details.
+    fn toggle_selected(&mut self) {
+        match self.selected_row() {
+            Some(HealthRow::Category(name)) if !self.collapsed.remove(&name) => {
+                self.collapsed.insert(name);
+            }
+            Some(HealthRow::Check(i)) if !self.expanded.remove(&i) => {
+                self.expanded.insert(i);
+            }
+            _ => {}
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.rows().len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        self.list_state.select(Some(next));
+    }
+
+    fn status_style(status: &ValidationStatus) -> Style {
+        let t = theme();
+        match status {
+            ValidationStatus::Pass => Style::default().fg(t.success),
+            ValidationStatus::Warning => Style::default().fg(t.warning),
+            ValidationStatus::Error => Style::default().fg(t.error),
+        }
+    }
+
+    fn status_icon<'a>(icons: &'a Icons, status: &ValidationStatus) -> &'a str {
+        match status {
+            ValidationStatus::Pass => icons.success(),
+            ValidationStatus::Warning => icons.warning(),
+            ValidationStatus::Error => icons.error(),
+        }
+    }
+
+    /// Number of lines a check row takes when expanded inline.
+    fn detail_lines(result: &ValidationResult) -> Vec<String> {
+        let mut lines: Vec<String> = result.details.clone().unwrap_or_default();
+        if is_fixable(result) {
+            if let Some(action) = &result.fix_action {
+                lines.push(format!("Fix available: {action}"));
+            }
+        }
+        lines
+    }
+
+    fn render_results(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let t = theme();
+        let icons = Icons::from_config(config);
+        let rows = self.rows();
+        self.list_area = Some(area);
+
+        let mut heights = Vec::with_capacity(rows.len());
+        let items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| match row {
+                HealthRow::Category(name) => {
+                    let (warnings, errors) = self.category_counts(name);
+                    let marker = if self.collapsed.contains(name) {
+                        "▸"
+                    } else {
+                        "▾"
+                    };
+                    let mut spans = vec![Span::styled(format!("{marker} {name}"), t.title_style())];
+                    if errors > 0 {
+                        spans.push(Span::styled(
+                            format!("  {} {errors}", icons.error()),
+                            Style::default().fg(t.error),
+                        ));
+                    }
+                    if warnings > 0 {
+                        spans.push(Span::styled(
+                            format!("  {} {warnings}", icons.warning()),
+                            Style::default().fg(t.warning),
+                        ));
+                    }
+                    heights.push(1);
+                    ListItem::new(Line::from(spans))
+                }
+                HealthRow::Check(i) => {
+                    let Some(result) = self.result(*i) else {
+                        heights.push(1);
+                        return ListItem::new("");
+                    };
+                    let style = Self::status_style(&result.status);
+                    let mut lines = vec![Line::from(vec![
+                        Span::styled(
+                            format!("  {} ", Self::status_icon(&icons, &result.status)),
+                            style,
+                        ),
+                        Span::styled(result.message.clone(), t.text_style()),
+                    ])];
+                    if self.expanded.contains(i) {
+                        for detail in Self::detail_lines(result) {
+                            lines.push(Line::from(Span::styled(
+                                format!("      {detail}"),
+                                t.muted_style(),
+                            )));
+                        }
+                    }
+                    heights.push(lines.len());
+                    ListItem::new(Text::from(lines))
+                }
+            })
+            .collect();
+
+        let title = match &self.report {
+            Some(report) => format!(
+                " Checks ({} passed, {} warnings, {} errors) ",
+                report.summary.passed, report.summary.warnings, report.summary.errors
+            ),
+            None => " Checks ".to_string(),
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        // Register click regions for visible rows (rows can span several lines)
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let mut y = inner.y;
+        for (index, height) in heights.iter().enumerate().skip(self.list_state.offset()) {
+            if y >= inner.y + inner.height {
+                break;
+            }
+            let h = (*height as u16).min(inner.y + inner.height - y);
+            self.row_regions
+                .add(Rect::new(inner.x, y, inner.width, h), index);
+            y += h;
+        }
+    }
+
+    fn category_counts(&self, category: &str) -> (usize, usize) {
+        let Some(report) = &self.report else {
+            return (0, 0);
+        };
+        let in_category = report.results.iter().filter(|r| r.category == category);
+        let warnings = in_category
+            .clone()
+            .filter(|r| r.status == ValidationStatus::Warning)
+            .count();
+        let errors = in_category
+            .filter(|r| r.status == ValidationStatus::Error)
+            .count();
+        (warnings, errors)
+    }
+
+    fn render_details(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let t = theme();
+        let icons = Icons::from_config(config);
+        self.fix_button_area = None;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Details ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines: Vec<Line> = Vec::new();
+        let mut fixable = false;
+        match self.selected_row() {
+            Some(HealthRow::Check(i)) => {
+                if let Some(result) = self.result(i) {
+                    let style = Self::status_style(&result.status);
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("{} ", Self::status_icon(&icons, &result.status)),
+                            style,
+                        ),
+                        Span::styled(result.message.clone(), t.title_style()),
+                    ]));
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(vec![
+                        Span::styled("Check: ", t.muted_style()),
+                        Span::styled(result.check_name.clone(), t.text_style()),
+                    ]));
+                    lines.push(Line::from(vec![
+                        Span::styled("Category: ", t.muted_style()),
+                        Span::styled(result.category.clone(), t.text_style()),
+                    ]));
+                    lines.push(Line::from(vec![
+                        Span::styled("Took: ", t.muted_style()),
+                        Span::styled(format!("{}ms", result.duration_ms), t.text_style()),
+                    ]));
+                    if let Some(details) = &result.details {
+                        lines.push(Line::from(""));
+                        for detail in details {
+                            lines.push(Line::from(Span::styled(detail.clone(), t.text_style())));
+                        }
+                    }
+                    if is_fixable(result) {
+                        fixable = true;
+                        lines.push(Line::from(""));
+                        lines.push(Line::from(vec![
+                            Span::styled(format!("{} Fix: ", icons.wrench()), t.emphasis_style()),
+                            Span::styled(
+                                result.fix_action.clone().unwrap_or_default(),
+                                t.text_style(),
+                            ),
+                        ]));
+                    }
+                }
+            }
+            Some(HealthRow::Category(name)) => {
+                let (warnings, errors) = self.category_counts(&name);
+                lines.push(Line::from(Span::styled(name, t.title_style())));
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!("{errors} errors, {warnings} warnings"),
+                    t.text_style(),
+                )));
+            }
+            None => {
+                lines.push(Line::from(Span::styled(
+                    "Select a check to see its details",
+                    t.muted_style(),
+                )));
+            }
+        }
+
+        let button_height = if fixable { 2 } else { 0 };
+        let text_area = Rect::new(
+            inner.x,
+            inner.y,
+            inner.width,
+            inner.height.saturating_sub(button_height),
+        );
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), text_area);
+
+        if fixable && inner.height > button_height {
+            let k = config.keymap.get_key_display_for_action(Action::Edit);
+            let (label, style) = if let Some((action, _)) = &self.fix_rx {
+                (format!(" Applying: {action}... "), t.muted_style())
+            } else {
+                (
+                    format!(" {} Apply fix ({k}) ", icons.wrench()),
+                    t.highlight_style(),
+                )
+            };
+            let width = (label.chars().count() as u16).min(inner.width);
+            let button = Rect::new(inner.x, inner.y + inner.height - 1, width, 1);
+            frame.render_widget(Paragraph::new(label).style(style), button);
+            self.fix_button_area = Some(button);
+        }
+    }
+
+    fn render_progress(&self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let dots = ".".repeat(self.spinner_tick / 2 % 4);
+        let para = Paragraph::new(format!("Running diagnostics{dots}"))
+            .style(Style::default().fg(t.warning))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(t.border_type(false))
+                    .title(" Health ")
+                    .title_alignment(Alignment::Center)
+                    .border_style(focused_border_style())
+                    .padding(crate::styles::padding(Padding::new(2, 2, 2, 2))),
+            );
+        frame.render_widget(para, area);
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, ctx: &ScreenContext) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.fix_button_area.is_some_and(|a| a.contains(pos)) {
+                    self.start_fix(ctx.config);
+                    return ScreenAction::Refresh;
+                }
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    if self.list_state.selected() == Some(index) {
+                        self.toggle_selected();
+                    } else {
+                        self.list_state.select(Some(index));
+                    }
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::ScrollUp if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(-3);
+            }
+            MouseEventKind::ScrollDown if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+/// Whether a result has a fix worth offering.
+fn is_fixable(result: &ValidationResult) -> bool {
+    result.fixable && result.status != ValidationStatus::Pass
+}
+
+impl Screen for HealthScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Health",
+            "Diagnose your setup and fix problems. Same checks as `dotstate doctor`.",
+        )?;
+
+        if let Some(error) = &self.error {
+            let para = Paragraph::new(format!("Diagnostics failed:\n\n{error}"))
+                .style(Style::default().fg(t.error))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(t.border_type(false))
+                        .title(" Health ")
+                        .title_alignment(Alignment::Center)
+                        .padding(crate::styles::padding(Padding::new(2, 2, 2, 2))),
+                );
+            frame.render_widget(para, content_chunk);
+        } else if self.report.is_none() {
+            self.render_progress(frame, content_chunk);
+        } else {
+            let panes = create_split_layout(content_chunk, &[60, 40]);
+            self.render_results(frame, panes[0], ctx.config);
+            self.render_details(frame, panes[1], ctx.config);
+        }
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = if self.run_rx.is_some() {
+            format!("Running diagnostics... | {}: Back", k(Action::Cancel))
+        } else {
+            format!(
+                "{}: Navigate | {}: Expand | {}: Apply Fix | {}: Re-run | {}: Back",
+                ctx.config.keymap.navigation_display(),
+                k(Action::Confirm),
+                k(Action::Edit),
+                k(Action::Refresh),
+                k(Action::Cancel),
+            )
+        };
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::GoToTop => self.move_selection(isize::MIN / 2),
+                    Action::GoToEnd => self.move_selection(isize::MAX / 2),
+                    Action::Confirm | Action::ToggleSelect => self.toggle_selected(),
+                    Action::Edit => self.start_fix(ctx.config),
+                    Action::Refresh => self.start_diagnostics(ctx.config),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => return Ok(self.handle_mouse(mouse, ctx)),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        self.start_diagnostics(ctx.config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(category: &str, name: &str, status: ValidationStatus) -> ValidationResult {
+        ValidationResult {
+            category: category.to_string(),
+            check_name: name.to_string(),
+            message: name.to_string(),
+            status,
+            fixable: false,
+            fix_action: None,
+            details: None,
+            duration_ms: 0,
+        }
+    }
+
+    fn report(results: Vec<ValidationResult>) -> DoctorReport {
+        DoctorReport {
+            version: String::new(),
+            timestamp: String::new(),
+            summary: DoctorSummary {
+                total_checks: results.len(),
+                passed: 0,
+                warnings: 0,
+                errors: 0,
+                fixable: 0,
+                fixed: 0,
+                applied_fixes: Vec::new(),
+                total_duration_ms: 0,
+            },
+            results,
+        }
+    }
+
+    #[test]
+    fn test_rows_grouped_by_category() {
+        let mut screen = HealthScreen::new();
+        screen.set_report(report(vec![
+            result("Environment", "git", ValidationStatus::Pass),
+            result("Repository", "remote", ValidationStatus::Warning),
+            result("Environment", "shell", ValidationStatus::Pass),
+        ]));
+
+        assert_eq!(
+            screen.rows(),
+            vec![
+                HealthRow::Category("Environment".to_string()),
+                HealthRow::Check(0),
+                HealthRow::Check(2),
+                HealthRow::Category("Repository".to_string()),
+                HealthRow::Check(1),
+            ]
+        );
+        // First problem is selected by default
+        assert_eq!(screen.selected_result().unwrap().check_name, "remote");
+    }
+
+    #[test]
+    fn test_collapse_category() {
+        let mut screen = HealthScreen::new();
+        screen.set_report(report(vec![
+            result("Environment", "git", ValidationStatus::Pass),
+            result("Repository", "remote", ValidationStatus::Pass),
+        ]));
+        screen.list_state.select(Some(0));
+        screen.toggle_selected();
+
+        assert_eq!(
+            screen.rows(),
+            vec![
+                HealthRow::Category("Environment".to_string()),
+                HealthRow::Category("Repository".to_string()),
+                HealthRow::Check(1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,591 @@
+//! Sync history screen controller.
+//!
+//! Lists past syncs from the local sync history, newest first, with what
+//! each one committed, pushed and pulled and how it ended. A second tab shows
+//! the conflict journal: conflicts and force syncs with the files and
+//! commits involved.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::conflict_journal::{self, JournalEntry, JournalEvent};
+use crate::utils::sync_history::{self, SyncRecord};
+use crate::utils::text::format_time_ago;
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions,
+};
+use anyhow::Result;
+use chrono::{Local, Utc};
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+
+/// Sync history screen controller.
+pub struct HistoryScreen {
+    /// Records, newest first, loaded on enter
+    records: Vec<SyncRecord>,
+    /// Conflict journal entries, newest first, loaded on enter
+    journal: Vec<JournalEntry>,
+    /// Whether the conflict journal is shown instead of the syncs
+    show_journal: bool,
+    list_state: ListState,
+    /// Clickable rows in the history list (value = row index)
+    row_regions: MouseRegions<usize>,
+    /// History list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+    /// Scroll offset of the details panel
+    details_scroll: u16,
+}
+
+impl Default for HistoryScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistoryScreen {
+    /// Create a new history screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            records: Vec::new(),
+            journal: Vec::new(),
+            show_journal: false,
+            list_state: ListState::default(),
+            row_regions: MouseRegions::new(),
+            list_area: None,
+            details_scroll: 0,
+        }
+    }
+
+    fn load(&mut self) {
+        self.records = sync_history::load();
+        self.journal = conflict_journal::load();
+        self.reset_selection();
+    }
+
+    fn reset_selection(&mut self) {
+        self.list_state.select((self.len() > 0).then_some(0));
+        self.details_scroll = 0;
+    }
+
+    /// Number of rows in the shown list
+    fn len(&self) -> usize {
+        if self.show_journal {
+            self.journal.len()
+        } else {
+            self.records.len()
+        }
+    }
+
+    fn selected(&self) -> Option<&SyncRecord> {
+        self.list_state.selected().and_then(|i| self.records.get(i))
+    }
+
+    fn selected_entry(&self) -> Option<&JournalEntry> {
+        self.list_state.selected().and_then(|i| self.journal.get(i))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        if next != current {
+            self.details_scroll = 0;
+        }
+        self.list_state.select(Some(next));
+    }
+
+    fn failed_count(&self) -> usize {
+        self.records.iter().filter(|r| !r.success).count()
+    }
+
+    fn status_icon(record: &SyncRecord, icons: &Icons) -> (&'static str, Color) {
+        let t = theme();
+        if record.conflict {
+            (icons.warning(), t.warning)
+        } else if record.success {
+            (icons.success(), t.success)
+        } else {
+            (icons.error(), t.error)
+        }
+    }
+
+    fn journal_items(&self, icons: &Icons) -> Vec<ListItem<'static>> {
+        let t = theme();
+        self.journal
+            .iter()
+            .map(|entry| {
+                let (icon, color) = match entry.event {
+                    JournalEvent::Conflict => (icons.warning(), t.warning),
+                    JournalEvent::Reconcile => (icons.success(), t.success),
+                    JournalEvent::MatchRemote | JournalEvent::OverwriteRemote => {
+                        (icons.error(), t.error)
+                    }
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{icon} "), Style::default().fg(color)),
+                    Span::styled(
+                        entry
+                            .timestamp
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M  ")
+                            .to_string(),
+                        t.muted_style(),
+                    ),
+                    Span::styled(format!("{}  ", entry.event.label()), t.text_style()),
+                    Span::styled(format!("{} file(s)", entry.files.len()), t.muted_style()),
+                ]))
+            })
+            .collect()
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let t = theme();
+        let icons = Icons::from_config(config);
+        self.list_area = Some(area);
+
+        let items: Vec<ListItem> = if self.show_journal {
+            self.journal_items(&icons)
+        } else {
+            self.sync_items(&icons)
+        };
+        let failed = self.failed_count();
+        let title = if self.show_journal {
+            format!(" Conflict Journal ({}) ", self.journal.len())
+        } else if failed > 0 {
+            format!(" Syncs ({}, {failed} failed) ", self.records.len())
+        } else {
+            format!(" Syncs ({}) ", self.records.len())
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for (row, index) in (self.list_state.offset()..self.len()).enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                index,
+            );
+        }
+    }
+
+    fn sync_items(&self, icons: &Icons) -> Vec<ListItem<'static>> {
+        let t = theme();
+        self.records
+            .iter()
+            .map(|record| {
+                let (icon, color) = Self::status_icon(record, icons);
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{icon} "), Style::default().fg(color)),
+                    Span::styled(
+                        record
+                            .timestamp
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M  ")
+                            .to_string(),
+                        t.muted_style(),
+                    ),
+                    Span::styled(format!("{}  ", record.kind.label()), t.text_style()),
+                    Span::styled(
+                        record.summary(),
+                        if record.success {
+                            t.muted_style()
+                        } else {
+                            Style::default().fg(color)
+                        },
+                    ),
+                ]))
+            })
+            .collect()
+    }
+
+    /// Lines describing `record` in the details panel
+    fn detail_lines(record: &SyncRecord) -> Vec<Line<'static>> {
+        let t = theme();
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label}: "), t.muted_style()),
+                Span::styled(value, t.text_style()),
+            ])
+        };
+
+        let result = if record.conflict {
+            "Stopped on a conflict"
+        } else if record.success {
+            "Succeeded"
+        } else {
+            "Failed"
+        };
+        let mut lines = vec![
+            Line::from(Span::styled(record.kind.label(), t.title_style())),
+            Line::from(""),
+            field(
+                "When",
+                format!(
+                    "{} ({})",
+                    record
+                        .timestamp
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S"),
+                    format_time_ago(record.timestamp, Utc::now())
+                ),
+            ),
+            field("Started from", record.origin.label().to_string()),
+            field("Profile", record.profile.clone()),
+        ];
+        if let Some(branch) = &record.branch {
+            lines.push(field("Branch", branch.clone()));
+        }
+        lines.push(field("Result", result.to_string()));
+        if let Some(commit) = &record.commit {
+            lines.push(field("Commit", commit.clone()));
+        }
+        if let Some(error) = &record.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(if record.conflict { t.warning } else { t.error }),
+            )));
+        }
+
+        let mut file_section = |title: String, entries: &[String]| {
+            if entries.is_empty() {
+                return;
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(title, t.emphasis_style())));
+            for entry in entries {
+                lines.push(Line::from(Span::styled(
+                    format!("  {entry}"),
+                    t.text_style(),
+                )));
+            }
+        };
+        let committed_title = if record.success {
+            format!("Pushed {} file(s)", record.committed.len())
+        } else {
+            format!("Committed {} file(s), not pushed", record.committed.len())
+        };
+        file_section(committed_title, &record.committed);
+        file_section(
+            format!(
+                "Pulled {} commit(s) changing {} file(s)",
+                record.pulled_commits,
+                record.pulled.len()
+            ),
+            &record.pulled,
+        );
+        if record.success && record.committed.is_empty() && record.pulled.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Nothing was pushed or pulled.",
+                t.muted_style(),
+            )));
+        }
+        lines
+    }
+
+    /// Lines describing journal `entry` in the details panel
+    fn journal_lines(entry: &JournalEntry) -> Vec<Line<'static>> {
+        let t = theme();
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label}: "), t.muted_style()),
+                Span::styled(value, t.text_style()),
+            ])
+        };
+
+        let mut lines = vec![
+            Line::from(Span::styled(entry.event.label(), t.title_style())),
+            Line::from(""),
+            field(
+                "When",
+                format!(
+                    "{} ({})",
+                    entry
+                        .timestamp
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S"),
+                    format_time_ago(entry.timestamp, Utc::now())
+                ),
+            ),
+            field("Started from", entry.origin.label().to_string()),
+            field("Machine", entry.hostname.clone()),
+            field("Profile", entry.profile.clone()),
+        ];
+        if let Some(branch) = &entry.branch {
+            lines.push(field("Branch", branch.clone()));
+        }
+        if let Some(commit) = &entry.local_commit {
+            lines.push(field("Local commit", commit.clone()));
+        }
+        if let Some(commit) = &entry.remote_commit {
+            lines.push(field("Remote commit", commit.clone()));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            entry.resolution.clone(),
+            Style::default().fg(t.warning),
+        )));
+
+        let (files_title, commits_title) = match entry.event {
+            JournalEvent::Conflict => ("Conflicting files", "Commits"),
+            JournalEvent::MatchRemote => ("Discarded changes", "Discarded local commits"),
+            JournalEvent::OverwriteRemote => ("Replaced on the remote", "Replaced remote commits"),
+            JournalEvent::Reconcile => ("Reconciled files", "Remote commits merged"),
+        };
+        for (title, entries) in [(files_title, &entry.files), (commits_title, &entry.commits)] {
+            if entries.is_empty() {
+                continue;
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("{title} ({})", entries.len()),
+                t.emphasis_style(),
+            )));
+            for item in entries {
+                lines.push(Line::from(Span::styled(
+                    format!("  {item}"),
+                    t.text_style(),
+                )));
+            }
+        }
+        lines
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Details ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+
+        let lines = if self.show_journal {
+            match self.selected_entry() {
+                Some(entry) => Self::journal_lines(entry),
+                None => vec![Line::from(Span::styled(
+                    "No conflicts or force syncs recorded. They are kept here for good.",
+                    t.muted_style(),
+                ))],
+            }
+        } else {
+            match self.selected() {
+                Some(record) => Self::detail_lines(record),
+                None => vec![Line::from(Span::styled(
+                    "No syncs recorded yet. Each sync, pull and force sync is added here.",
+                    t.muted_style(),
+                ))],
+            }
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((self.details_scroll, 0))
+                .block(block),
+            area,
+        );
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    if self.list_state.selected() != Some(index) {
+                        self.details_scroll = 0;
+                    }
+                    self.list_state.select(Some(index));
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::ScrollUp if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(-3);
+            }
+            MouseEventKind::ScrollDown if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(3);
+            }
+            MouseEventKind::ScrollUp => {
+                self.details_scroll = self.details_scroll.saturating_sub(3);
+            }
+            MouseEventKind::ScrollDown => {
+                self.details_scroll = self.details_scroll.saturating_add(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+impl Screen for HistoryScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Sync History",
+            "Every sync on this machine: what it pushed and pulled, and how it ended.",
+        )?;
+
+        let panes = create_split_layout(content_chunk, &[55, 45]);
+        self.render_list(frame, panes[0], ctx.config);
+        self.render_details(frame, panes[1]);
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Navigate | {}: {} | {}/{}: Scroll Details | {}: Reload | {}: Back",
+            ctx.config.keymap.navigation_display(),
+            k(Action::NextTab),
+            if self.show_journal {
+                "Syncs"
+            } else {
+                "Conflict Journal"
+            },
+            k(Action::ScrollUp),
+            k(Action::ScrollDown),
+            k(Action::Refresh),
+            k(Action::Cancel),
+        );
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::GoToTop => self.move_selection(isize::MIN / 2),
+                    Action::GoToEnd => self.move_selection(isize::MAX / 2),
+                    Action::ScrollUp => {
+                        self.details_scroll = self.details_scroll.saturating_sub(1);
+                    }
+                    Action::ScrollDown => {
+                        self.details_scroll = self.details_scroll.saturating_add(1);
+                    }
+                    Action::NextTab | Action::PrevTab => {
+                        self.show_journal = !self.show_journal;
+                        self.reset_selection();
+                    }
+                    Action::Refresh => self.load(),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => return Ok(self.handle_mouse(mouse)),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn on_enter(&mut self, _ctx: &ScreenContext) -> Result<()> {
+        self.load();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sync_history::{SyncKind, SyncOrigin};
+
+    #[test]
+    fn test_details_describe_failed_sync() {
+        let mut record = SyncRecord::start(SyncKind::Sync, SyncOrigin::Tui, "Work");
+        record.committed = vec!["M .zshrc".to_string()];
+        record.finish(
+            false,
+            "Error: Failed to push to remote\n\nThe commit has been undone.",
+        );
+
+        let text: Vec<String> = HistoryScreen::detail_lines(&record)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+
+        assert!(text.contains(&"Result: Failed".to_string()));
+        assert!(text.contains(&"Failed to push to remote".to_string()));
+        assert!(text.contains(&"Committed 1 file(s), not pushed".to_string()));
+        assert!(text.contains(&"  M .zshrc".to_string()));
+    }
+
+    #[test]
+    fn test_journal_details_list_files_and_commits() {
+        let record = SyncRecord::start(SyncKind::Sync, SyncOrigin::Tui, "Work");
+        let mut entry = JournalEntry::new(
+            JournalEvent::MatchRemote,
+            &record,
+            "Local changes were discarded",
+        );
+        entry.files = vec![".zshrc".to_string()];
+        entry.commits = vec!["abc1234 Update .zshrc".to_string()];
+
+        let text: Vec<String> = HistoryScreen::journal_lines(&entry)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+
+        assert!(text.contains(&"Local changes were discarded".to_string()));
+        assert!(text.contains(&"Discarded changes (1)".to_string()));
+        assert!(text.contains(&"Discarded local commits (1)".to_string()));
+        assert!(text.contains(&"  abc1234 Update .zshrc".to_string()));
+    }
+}
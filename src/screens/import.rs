@@ -0,0 +1,641 @@
+//! Import screen controller.
+//!
+//! Clones a public GitHub dotfiles repository in the background, lists its
+//! files with a preview, and imports the chosen ones into the home
+//! directory (with an attribution comment) and the active profile.
+
+use crate::components::file_preview::FilePreview;
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::dotfiles_import::{self, Source};
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    DoubleClick, MouseRegions, ScrollbarDrag, TextInput,
+};
+use crate::widgets::{TextInputWidget, TextInputWidgetExt};
+use anyhow::Result;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use tracing::error;
+
+/// A repository that was cloned, with its files
+type Fetched = (Source, Vec<String>);
+
+/// The popup asking where an imported file goes
+struct DestinationPrompt {
+    /// File in the repository
+    path: String,
+    /// Destination relative to the home directory
+    input: TextInput,
+    error: Option<String>,
+}
+
+/// Import screen controller.
+pub struct ImportScreen {
+    /// Repository to import from, as typed
+    url: TextInput,
+    url_focused: bool,
+    /// Receiver for an in-flight clone
+    fetch_rx: Option<Receiver<Result<Fetched, String>>>,
+    /// The cloned repository
+    source: Option<Source>,
+    files: Vec<String>,
+    /// Files imported since the repository was cloned
+    imported: HashSet<String>,
+    error: Option<String>,
+    destination: Option<DestinationPrompt>,
+    list_state: ListState,
+    preview_focused: bool,
+    preview_scroll: usize,
+    spinner_tick: usize,
+    /// Clickable rows in the file list (value = row index)
+    row_regions: MouseRegions<usize>,
+    /// Double-clicks on rows import them
+    row_clicks: DoubleClick<usize>,
+    /// File list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+    /// Dragging and clicking the preview scrollbar
+    preview_scrollbar: ScrollbarDrag,
+}
+
+impl Default for ImportScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportScreen {
+    /// Create a new import screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            url: TextInput::new(),
+            url_focused: true,
+            fetch_rx: None,
+            source: None,
+            files: Vec::new(),
+            imported: HashSet::new(),
+            error: None,
+            destination: None,
+            list_state: ListState::default(),
+            preview_focused: false,
+            preview_scroll: 0,
+            spinner_tick: 0,
+            row_regions: MouseRegions::new(),
+            row_clicks: DoubleClick::new(),
+            list_area: None,
+            preview_scrollbar: ScrollbarDrag::new(),
+        }
+    }
+
+    /// Whether a repository is being cloned
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.fetch_rx.is_some()
+    }
+
+    /// Clone the repository typed in the URL field in the background.
+    fn start_fetch(&mut self) {
+        if self.fetch_rx.is_some() {
+            return;
+        }
+        let source = match Source::parse(self.url.text()) {
+            Ok(source) => source,
+            Err(e) => {
+                self.error = Some(format!("{e:#}"));
+                return;
+            }
+        };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let checkout = source.checkout_dir();
+            let result = dotfiles_import::fetch(&source, &checkout)
+                .and_then(|()| dotfiles_import::list_files(&checkout))
+                .map(|files| (source, files))
+                .map_err(|e| format!("{e:#}"));
+            let _ = tx.send(result);
+        });
+        self.fetch_rx = Some(rx);
+        self.error = None;
+        self.url_focused = false;
+    }
+
+    /// Poll background work. Returns `ScreenAction::Refresh` while work is in progress.
+    pub fn tick(&mut self) -> ScreenAction {
+        let Some(rx) = &self.fetch_rx else {
+            return ScreenAction::None;
+        };
+        self.spinner_tick = self.spinner_tick.wrapping_add(1);
+        match rx.try_recv() {
+            Ok(Ok((source, files))) => self.set_files(source, files),
+            Ok(Err(e)) => {
+                error!("Cloning the repository to import from failed: {}", e);
+                self.error = Some(e);
+                self.url_focused = true;
+            }
+            Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+            Err(TryRecvError::Disconnected) => {
+                self.error = Some("Cloning stopped unexpectedly".to_string());
+                self.url_focused = true;
+            }
+        }
+        self.fetch_rx = None;
+        ScreenAction::Refresh
+    }
+
+    fn set_files(&mut self, source: Source, files: Vec<String>) {
+        self.source = Some(source);
+        self.files = files;
+        self.imported.clear();
+        self.preview_scroll = 0;
+        self.list_state
+            .select((!self.files.is_empty()).then_some(0));
+    }
+
+    fn selected(&self) -> Option<&String> {
+        self.list_state.selected().and_then(|i| self.files.get(i))
+    }
+
+    fn checkout_path(&self, file: &str) -> Option<PathBuf> {
+        self.source
+            .as_ref()
+            .map(|source| source.checkout_dir().join(file))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.files.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        if Some(next) != self.list_state.selected() {
+            self.preview_scroll = 0;
+        }
+        self.list_state.select(Some(next));
+    }
+
+    fn scroll_preview(&mut self, delta: isize) {
+        self.preview_scroll = if delta < 0 {
+            self.preview_scroll.saturating_sub(delta.unsigned_abs())
+        } else {
+            self.preview_scroll.saturating_add(delta.unsigned_abs())
+        };
+    }
+
+    /// Ask where the selected file goes, suggesting a destination
+    fn open_destination(&mut self) {
+        if let Some(path) = self.selected().cloned() {
+            self.destination = Some(DestinationPrompt {
+                input: TextInput::with_text(dotfiles_import::suggest_destination(&path)),
+                path,
+                error: None,
+            });
+        }
+    }
+
+    fn submit_destination(&mut self) -> ScreenAction {
+        let (Some(prompt), Some(source)) = (&mut self.destination, &self.source) else {
+            return ScreenAction::None;
+        };
+        let destination = prompt.input.text_trimmed().to_string();
+        let home = crate::utils::get_home_dir();
+        match dotfiles_import::import(
+            &source.checkout_dir(),
+            source,
+            &prompt.path,
+            &home,
+            &destination,
+        ) {
+            Ok(full_path) => {
+                self.imported.insert(prompt.path.clone());
+                self.destination = None;
+                ScreenAction::AddCustomFileToSync {
+                    full_path,
+                    relative_path: destination,
+                }
+            }
+            Err(e) => {
+                prompt.error = Some(format!("{e:#}"));
+                ScreenAction::Refresh
+            }
+        }
+    }
+
+    /// Whether `key` is a plain character to type into a text field, so vim
+    /// bindings don't interfere
+    fn typed_char(key: KeyEvent) -> Option<char> {
+        match key.code {
+            KeyCode::Char(c)
+                if !key.modifiers.intersects(
+                    KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER,
+                ) =>
+            {
+                Some(c)
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_destination_key(&mut self, key: KeyEvent, config: &Config) -> ScreenAction {
+        let Some(prompt) = &mut self.destination else {
+            return ScreenAction::None;
+        };
+        if let Some(c) = Self::typed_char(key) {
+            prompt.input.insert_char(c);
+            prompt.error = None;
+            return ScreenAction::Refresh;
+        }
+        match config.keymap.get_action(key.code, key.modifiers) {
+            Some(Action::Cancel) => {
+                self.destination = None;
+                ScreenAction::Refresh
+            }
+            Some(Action::Confirm) => self.submit_destination(),
+            Some(action) if prompt.input.handle_action(action) => ScreenAction::Refresh,
+            _ => ScreenAction::None,
+        }
+    }
+
+    fn handle_url_key(&mut self, key: KeyEvent, config: &Config) -> ScreenAction {
+        if let Some(c) = Self::typed_char(key) {
+            self.url.insert_char(c);
+            self.error = None;
+            return ScreenAction::Refresh;
+        }
+        match config.keymap.get_action(key.code, key.modifiers) {
+            Some(Action::Cancel) if self.source.is_some() => {
+                self.url_focused = false;
+                ScreenAction::Refresh
+            }
+            Some(Action::Cancel | Action::Quit) => ScreenAction::Navigate(ScreenId::MainMenu),
+            Some(Action::Confirm) => {
+                self.start_fetch();
+                ScreenAction::Refresh
+            }
+            Some(action) if self.url.handle_action(action) => ScreenAction::Refresh,
+            _ => ScreenAction::None,
+        }
+    }
+
+    fn render_url(&self, frame: &mut Frame, area: Rect) {
+        let input = TextInputWidget::new(&self.url)
+            .title("GitHub repository")
+            .placeholder("e.g. owner/dotfiles or https://github.com/owner/dotfiles")
+            .focused(self.url_focused && self.destination.is_none());
+        frame.render_text_input_widget(input, area);
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        self.list_area = Some(area);
+
+        let items: Vec<ListItem> = self
+            .files
+            .iter()
+            .map(|file| {
+                let mut spans = vec![Span::styled(file.clone(), t.text_style())];
+                if self.imported.contains(file) {
+                    spans.push(Span::styled("  (imported)", Style::default().fg(t.success)));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let title = match &self.source {
+            Some(source) => format!(" {}/{} ({}) ", source.owner, source.repo, self.files.len()),
+            None => " Files ".to_string(),
+        };
+        let focused = !self.url_focused && !self.preview_focused;
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(focused))
+                    .border_style(if focused {
+                        focused_border_style()
+                    } else {
+                        unfocused_border_style()
+                    })
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for (row, index) in (self.list_state.offset()..self.files.len()).enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                index,
+            );
+        }
+    }
+
+    /// The message shown instead of the file list
+    fn render_status(&self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let (text, style) = if self.fetch_rx.is_some() {
+            let dots = ".".repeat(self.spinner_tick / 2 % 4);
+            (format!("Cloning{dots}"), t.muted_style())
+        } else if let Some(error) = &self.error {
+            (error.clone(), Style::default().fg(t.error))
+        } else if self.source.is_some() {
+            ("The repository has no files.".to_string(), t.muted_style())
+        } else {
+            (
+                "Enter a public GitHub dotfiles repository to browse its files. \
+                 Files you import are copied into your home directory with a comment \
+                 crediting the repository, then synced to your profile."
+                    .to_string(),
+                t.muted_style(),
+            )
+        };
+        let para = Paragraph::new(text)
+            .style(style)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(t.border_type(false))
+                    .border_style(unfocused_border_style())
+                    .title(" Files ")
+                    .title_alignment(Alignment::Center)
+                    .padding(crate::styles::padding(Padding::new(2, 2, 1, 1)))
+                    .style(t.background_style()),
+            );
+        frame.render_widget(para, area);
+    }
+
+    fn render_destination(&self, frame: &mut Frame, area: Rect, config: &Config) {
+        use crate::components::Popup;
+
+        let Some(prompt) = &self.destination else {
+            return;
+        };
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Import | {}: Cancel",
+            k(Action::Confirm),
+            k(Action::Cancel)
+        );
+        let Some(result) = Popup::new()
+            .width(60)
+            .height(30)
+            // 1 (source) + 3 (input) + 2 (err) + borders/title/footer (~5) = 11.
+            .min_height(11)
+            .min_width(50)
+            .title(format!("Import {}", prompt.path))
+            .dim_background(true)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(if prompt.error.is_some() { 2 } else { 0 }),
+                Constraint::Min(0),
+            ])
+            .split(result.content_area);
+
+        frame.render_widget(
+            Paragraph::new("Where it goes in your home directory:").style(theme().muted_style()),
+            chunks[0],
+        );
+        let input = TextInputWidget::new(&prompt.input)
+            .title("Destination")
+            .placeholder("e.g. .tmux.conf")
+            .focused(true);
+        frame.render_text_input_widget(input, chunks[1]);
+
+        if let Some(msg) = &prompt.error {
+            frame.render_widget(
+                Paragraph::new(msg.as_str())
+                    .style(Style::default().fg(theme().error))
+                    .wrap(Wrap { trim: true })
+                    .alignment(Alignment::Center),
+                chunks[2],
+            );
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, config: &Config) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        if let Some(scroll) = self.preview_scrollbar.handle(mouse, self.preview_scroll) {
+            self.url_focused = false;
+            self.preview_focused = true;
+            self.preview_scroll = scroll;
+            return ScreenAction::Refresh;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    self.url_focused = false;
+                    self.preview_focused = false;
+                    if Some(index) != self.list_state.selected() {
+                        self.preview_scroll = 0;
+                    }
+                    self.list_state.select(Some(index));
+                    if self.row_clicks.click(index) {
+                        if config.read_only {
+                            return ScreenAction::pull_only_blocked();
+                        }
+                        self.open_destination();
+                    }
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::Moved => {
+                // Hovering a file previews it
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    if Some(index) != self.list_state.selected() {
+                        self.preview_scroll = 0;
+                        self.list_state.select(Some(index));
+                        return ScreenAction::Refresh;
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(-3);
+            }
+            MouseEventKind::ScrollDown if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+impl Screen for ImportScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Import Dotfiles",
+            "Cherry-pick files from a public GitHub dotfiles repository into your profile.",
+        )?;
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(content_chunk);
+        self.render_url(frame, rows[0]);
+
+        self.preview_scrollbar.clear();
+        if self.files.is_empty() || self.fetch_rx.is_some() {
+            self.render_status(frame, rows[1]);
+        } else {
+            let panes = create_split_layout(rows[1], &[45, 55]);
+            self.render_list(frame, panes[0]);
+            if let Some(path) = self.selected().and_then(|file| self.checkout_path(file)) {
+                FilePreview::render(
+                    frame,
+                    panes[1],
+                    &path,
+                    &mut self.preview_scroll,
+                    &mut self.preview_scrollbar,
+                    self.preview_focused,
+                    None,
+                    None,
+                    ctx.syntax_set,
+                    ctx.syntax_theme,
+                    ctx.config,
+                )?;
+            }
+        }
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = if self.url_focused {
+            format!(
+                "{}: Browse | {}: Back",
+                k(Action::Confirm),
+                k(Action::Cancel)
+            )
+        } else {
+            format!(
+                "{}: Navigate | {}: List/Preview | {}: Import | {}: Change Repository | {}: Back",
+                ctx.config.keymap.navigation_display(),
+                k(Action::NextTab),
+                k(Action::Confirm),
+                k(Action::Edit),
+                k(Action::Cancel),
+            )
+        };
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        self.render_destination(frame, area, ctx.config);
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                if self.destination.is_some() {
+                    return Ok(self.handle_destination_key(key, ctx.config));
+                }
+                if self.url_focused {
+                    return Ok(self.handle_url_key(key, ctx.config));
+                }
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    Action::Edit => self.url_focused = true,
+                    Action::NextTab | Action::PrevTab => {
+                        self.preview_focused = !self.preview_focused;
+                    }
+                    Action::MoveUp if self.preview_focused => self.scroll_preview(-1),
+                    Action::MoveDown if self.preview_focused => self.scroll_preview(1),
+                    Action::PageUp if self.preview_focused => self.scroll_preview(-10),
+                    Action::PageDown if self.preview_focused => self.scroll_preview(10),
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::GoToTop => self.move_selection(isize::MIN / 2),
+                    Action::GoToEnd => self.move_selection(isize::MAX / 2),
+                    Action::Confirm => {
+                        if ctx.config.read_only {
+                            return Ok(ScreenAction::pull_only_blocked());
+                        }
+                        self.open_destination();
+                    }
+                    Action::Refresh => self.start_fetch(),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) if self.destination.is_none() => {
+                return Ok(self.handle_mouse(mouse, ctx.config));
+            }
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn is_input_focused(&self) -> bool {
+        self.url_focused || self.destination.is_some()
+    }
+
+    fn on_enter(&mut self, _ctx: &ScreenContext) -> Result<()> {
+        self.destination = None;
+        self.url_focused = self.source.is_none();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination_suggested_for_selected_file() {
+        let mut screen = ImportScreen::new();
+        screen.set_files(
+            Source::parse("octo/dotfiles").unwrap(),
+            vec!["README.md".to_string(), "tmux/.tmux.conf".to_string()],
+        );
+        screen.move_selection(1);
+        screen.open_destination();
+
+        let prompt = screen.destination.as_ref().unwrap();
+        assert_eq!(prompt.path, "tmux/.tmux.conf");
+        assert_eq!(prompt.input.text(), ".tmux.conf");
+        assert!(screen.is_input_focused());
+    }
+}
@@ -0,0 +1,384 @@
+//! Machines screen controller.
+//!
+//! Lists every machine recorded in the repository's `machines.toml`, most
+//! recently synced first, and points out machines that haven't synced in a
+//! long time.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::machine_registry::{
+    current_hostname, MachineInfo, MachineRegistry, STALE_AFTER_DAYS,
+};
+use crate::utils::text::format_time_ago;
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions,
+};
+use anyhow::Result;
+use chrono::Utc;
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+
+/// Machines screen controller.
+pub struct MachinesScreen {
+    /// Machines sorted by most recent sync, loaded on enter
+    machines: Vec<(String, MachineInfo)>,
+    /// Error from loading the registry
+    error: Option<String>,
+    /// Hostname of this machine
+    this_host: String,
+    list_state: ListState,
+    /// Clickable rows in the machine list (value = row index)
+    row_regions: MouseRegions<usize>,
+    /// Machine list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+}
+
+impl Default for MachinesScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MachinesScreen {
+    /// Create a new machines screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            machines: Vec::new(),
+            error: None,
+            this_host: String::new(),
+            list_state: ListState::default(),
+            row_regions: MouseRegions::new(),
+            list_area: None,
+        }
+    }
+
+    /// Load the registry from the repository.
+    fn load(&mut self, config: &Config) {
+        self.this_host = current_hostname();
+        match MachineRegistry::load(&config.repo_path) {
+            Ok(registry) => {
+                self.set_registry(&registry);
+                self.error = None;
+            }
+            Err(e) => {
+                self.machines.clear();
+                self.error = Some(format!("{e:#}"));
+            }
+        }
+    }
+
+    fn set_registry(&mut self, registry: &MachineRegistry) {
+        self.machines = registry
+            .by_last_sync()
+            .into_iter()
+            .map(|(name, info)| (name.clone(), info.clone()))
+            .collect();
+        let selected = self
+            .machines
+            .iter()
+            .position(|(name, _)| *name == self.this_host)
+            .unwrap_or(0);
+        self.list_state
+            .select((!self.machines.is_empty()).then_some(selected));
+    }
+
+    fn selected(&self) -> Option<&(String, MachineInfo)> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.machines.get(i))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.machines.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        self.list_state.select(Some(next));
+    }
+
+    fn stale_count(&self) -> usize {
+        let now = Utc::now();
+        self.machines
+            .iter()
+            .filter(|(_, info)| info.is_stale(now))
+            .count()
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let t = theme();
+        let icons = Icons::from_config(config);
+        let now = Utc::now();
+        self.list_area = Some(area);
+
+        let items: Vec<ListItem> = self
+            .machines
+            .iter()
+            .map(|(name, info)| {
+                let stale = info.is_stale(now);
+                let (icon, icon_style) = if stale {
+                    (icons.warning(), Style::default().fg(t.warning))
+                } else {
+                    (icons.machine(), Style::default().fg(t.primary))
+                };
+                let mut spans = vec![
+                    Span::styled(format!("{icon} "), icon_style),
+                    Span::styled(name.clone(), t.text_style()),
+                ];
+                if *name == self.this_host {
+                    spans.push(Span::styled(" (this machine)", t.muted_style()));
+                }
+                spans.push(Span::styled(
+                    format!("  {}", format_time_ago(info.last_sync, now)),
+                    if stale {
+                        Style::default().fg(t.warning)
+                    } else {
+                        t.muted_style()
+                    },
+                ));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let stale = self.stale_count();
+        let title = if stale > 0 {
+            format!(" Machines ({}, {stale} stale) ", self.machines.len())
+        } else {
+            format!(" Machines ({}) ", self.machines.len())
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for (row, index) in (self.list_state.offset()..self.machines.len()).enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                index,
+            );
+        }
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Details ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+
+        let lines = match self.selected() {
+            Some((name, info)) => {
+                let now = Utc::now();
+                let field = |label: &str, value: String| {
+                    Line::from(vec![
+                        Span::styled(format!("{label}: "), t.muted_style()),
+                        Span::styled(value, t.text_style()),
+                    ])
+                };
+                let mut lines = vec![
+                    Line::from(Span::styled(name.clone(), t.title_style())),
+                    Line::from(""),
+                    field("OS", info.os.clone()),
+                    field("Profile", info.active_profile.clone()),
+                    field("DotState", info.dotstate_version.clone()),
+                    field(
+                        "Last sync",
+                        format!(
+                            "{} ({})",
+                            info.last_sync
+                                .with_timezone(&chrono::Local)
+                                .format("%Y-%m-%d %H:%M"),
+                            format_time_ago(info.last_sync, now)
+                        ),
+                    ),
+                ];
+                if info.is_stale(now) {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            "Hasn't synced in over {STALE_AFTER_DAYS} days. Its files may be out of date."
+                        ),
+                        Style::default().fg(t.warning),
+                    )));
+                }
+                lines
+            }
+            None => vec![Line::from(Span::styled(
+                "No machines recorded yet. Each machine adds itself the next time it syncs.",
+                t.muted_style(),
+            ))],
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    self.list_state.select(Some(index));
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::ScrollUp if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(-3);
+            }
+            MouseEventKind::ScrollDown if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+impl Screen for MachinesScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Machines",
+            "Every machine that syncs with this repository and when it last synced.",
+        )?;
+
+        if let Some(error) = &self.error {
+            let para = Paragraph::new(format!("Couldn't read machines.toml:\n\n{error}"))
+                .style(Style::default().fg(t.error))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(t.border_type(false))
+                        .title(" Machines ")
+                        .title_alignment(Alignment::Center)
+                        .padding(crate::styles::padding(Padding::new(2, 2, 2, 2))),
+                );
+            frame.render_widget(para, content_chunk);
+        } else {
+            let panes = create_split_layout(content_chunk, &[55, 45]);
+            self.render_list(frame, panes[0], ctx.config);
+            self.render_details(frame, panes[1]);
+        }
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Navigate | {}: Reload | {}: Back",
+            ctx.config.keymap.navigation_display(),
+            k(Action::Refresh),
+            k(Action::Cancel),
+        );
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::GoToTop => self.move_selection(isize::MIN / 2),
+                    Action::GoToEnd => self.move_selection(isize::MAX / 2),
+                    Action::Refresh => self.load(ctx.config),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => return Ok(self.handle_mouse(mouse)),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        self.load(ctx.config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn info(days_ago: i64) -> MachineInfo {
+        MachineInfo {
+            os: "linux x86_64".to_string(),
+            active_profile: "Work".to_string(),
+            dotstate_version: "1.0.0".to_string(),
+            last_sync: Utc::now() - Duration::days(days_ago),
+        }
+    }
+
+    #[test]
+    fn test_selects_this_machine() {
+        let mut registry = MachineRegistry::default();
+        registry
+            .machines
+            .insert("old-laptop".to_string(), info(120));
+        registry.machines.insert("desktop".to_string(), info(0));
+        registry.machines.insert("server".to_string(), info(3));
+
+        let mut screen = MachinesScreen::new();
+        screen.this_host = "server".to_string();
+        screen.set_registry(&registry);
+
+        let names: Vec<&str> = screen.machines.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["desktop", "server", "old-laptop"]);
+        assert_eq!(screen.selected().unwrap().0, "server");
+        assert_eq!(screen.stale_count(), 1);
+    }
+}
@@ -8,9 +8,9 @@ use crate::components::header::Header;
 use crate::config::Config;
 use crate::icons::Icons;
 use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
-use crate::styles::theme;
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
 use crate::ui::Screen as ScreenId;
-use crate::utils::{create_standard_layout, MouseRegions};
+use crate::utils::{create_split_layout, create_standard_layout, MouseRegions};
 use crate::version_check::UpdateInfo;
 use crate::widgets::{Menu, MenuItem as MenuWidgetItem, MenuState};
 use anyhow::Result;
@@ -23,9 +23,16 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Wrap};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuItem {
     ScanDotfiles,
+    Import,
     SyncWithRemote,
+    Notifications,
+    History,
     ManageProfiles,
     ManagePackages,
+    Aliases,
+    Health,
+    Machines,
+    Stats,
     SetupRepository,
     Settings,
 }
@@ -36,9 +43,16 @@ impl MenuItem {
     pub fn all() -> Vec<MenuItem> {
         vec![
             MenuItem::ScanDotfiles,
+            MenuItem::Import,
             MenuItem::SyncWithRemote,
+            MenuItem::Notifications,
+            MenuItem::History,
             MenuItem::ManageProfiles,
             MenuItem::ManagePackages,
+            MenuItem::Aliases,
+            MenuItem::Health,
+            MenuItem::Machines,
+            MenuItem::Stats,
             MenuItem::SetupRepository,
             MenuItem::Settings,
         ]
@@ -53,10 +67,18 @@ impl MenuItem {
         }
     }
 
-    /// Check if this menu item is enabled based on setup status
+    /// Whether this item changes which files the repository tracks, which a
+    /// pull-only machine never does
     #[must_use]
-    pub fn is_enabled(&self, is_setup: bool) -> bool {
-        !self.requires_setup() || is_setup
+    pub fn is_mutating(&self) -> bool {
+        matches!(self, MenuItem::ScanDotfiles | MenuItem::Import)
+    }
+
+    /// Check if this menu item is enabled based on setup status and
+    /// pull-only mode
+    #[must_use]
+    pub fn is_enabled(&self, is_setup: bool, read_only: bool) -> bool {
+        (!self.requires_setup() || is_setup) && !(read_only && self.is_mutating())
     }
 
     /// Get the icon for this menu item using the icon provider
@@ -64,9 +86,16 @@ impl MenuItem {
     pub fn icon(&self, icons: &Icons) -> &'static str {
         match self {
             MenuItem::ScanDotfiles => icons.folder(),
+            MenuItem::Import => icons.github(),
             MenuItem::SyncWithRemote => icons.sync(),
+            MenuItem::Notifications => icons.bell(),
+            MenuItem::History => icons.history(),
             MenuItem::ManageProfiles => icons.profile(),
             MenuItem::ManagePackages => icons.package(),
+            MenuItem::Aliases => icons.terminal(),
+            MenuItem::Health => icons.health(),
+            MenuItem::Machines => icons.machine(),
+            MenuItem::Stats => icons.chart(),
             MenuItem::SetupRepository => icons.git(),
             MenuItem::Settings => icons.cog(),
         }
@@ -77,9 +106,16 @@ impl MenuItem {
     pub fn text(&self) -> &'static str {
         match self {
             MenuItem::ScanDotfiles => "Manage Files",
+            MenuItem::Import => "Import Dotfiles",
             MenuItem::SyncWithRemote => "Sync with Remote",
+            MenuItem::Notifications => "Notifications",
+            MenuItem::History => "Sync History",
             MenuItem::ManageProfiles => "Manage Profiles",
             MenuItem::ManagePackages => "Manage Packages",
+            MenuItem::Aliases => "Shell Aliases",
+            MenuItem::Health => "Health Check",
+            MenuItem::Machines => "Machines",
+            MenuItem::Stats => "Statistics",
             MenuItem::SetupRepository => "Setup git repository",
             MenuItem::Settings => "Settings",
         }
@@ -90,7 +126,7 @@ impl MenuItem {
     pub fn color(&self, has_changes: bool) -> Color {
         let t = theme();
         match self {
-            MenuItem::SyncWithRemote if has_changes => t.warning,
+            MenuItem::SyncWithRemote | MenuItem::Notifications if has_changes => t.warning,
             _ => t.text,
         }
     }
@@ -171,6 +207,86 @@ impl MenuItem {
                 ];
                 Text::from(lines)
             }
+            MenuItem::Notifications => {
+                let lines = vec![
+                    Line::from(vec![Span::styled("Notifications", t.title_style())]),
+                    Line::from(""),
+                    Line::from(vec![Span::styled(
+                        "Everything that needs your attention, in one place:",
+                        t.text_style(),
+                    )]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Symlinks", t.emphasis_style()),
+                        Span::styled(" - Replaced or broken, to relink or adopt", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Remote", t.emphasis_style()),
+                        Span::styled(" - Commits to pull or push", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Syncs", t.emphasis_style()),
+                        Span::styled(" - A last sync that failed", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Health", t.emphasis_style()),
+                        Span::styled(" - Doctor errors and available fixes", t.text_style()),
+                    ]),
+                ];
+                Text::from(lines)
+            }
+            MenuItem::History => {
+                let lines = vec![
+                    Line::from(vec![Span::styled("Sync History", t.title_style())]),
+                    Line::from(""),
+                    Line::from(vec![Span::styled(
+                        "Every sync, pull and force sync on this machine, newest first:",
+                        t.text_style(),
+                    )]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Pushed", t.emphasis_style()),
+                        Span::styled(" - Files committed and the commit created", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Pulled", t.emphasis_style()),
+                        Span::styled(
+                            " - Commits and files that came from the remote",
+                            t.text_style(),
+                        ),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Result", t.emphasis_style()),
+                        Span::styled(" - Failures and conflicts, with the reason", t.text_style()),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(
+                            icons.lightbulb(),
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            " Tip: ",
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled("Run ", t.text_style()),
+                        Span::styled("dotstate history", t.emphasis_style()),
+                        Span::styled(" to query it from the shell.", t.text_style()),
+                    ]),
+                ];
+                Text::from(lines)
+            }
             MenuItem::ManageProfiles => {
                 let lines = vec![
                     Line::from(vec![
@@ -254,6 +370,236 @@ impl MenuItem {
                 ];
                 Text::from(lines)
             }
+            MenuItem::Health => {
+                let lines = vec![
+                    Line::from(vec![Span::styled("Check Your Setup", t.title_style())]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("Run the same diagnostics as ", t.text_style()),
+                        Span::styled("dotstate doctor", t.emphasis_style()),
+                        Span::styled(" without leaving the TUI:", t.text_style()),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Repository", t.emphasis_style()),
+                        Span::styled(" - Git state, remote and branch status", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Network", t.emphasis_style()),
+                        Span::styled(" - Remote reachability and credentials", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Profiles & Symlinks", t.emphasis_style()),
+                        Span::styled(" - Manifest and link integrity", t.text_style()),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(
+                            icons.lightbulb(),
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            " Tip: ",
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            "Fixable issues can be repaired one at a time.",
+                            t.text_style(),
+                        ),
+                    ]),
+                ];
+                Text::from(lines)
+            }
+            MenuItem::Aliases => {
+                let lines = vec![
+                    Line::from(vec![Span::styled("Shell Aliases", t.title_style())]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("Keep your aliases in ", t.text_style()),
+                        Span::styled("aliases.toml", t.emphasis_style()),
+                        Span::styled(
+                            " instead of syncing your whole shell config:",
+                            t.text_style(),
+                        ),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Per profile", t.emphasis_style()),
+                        Span::styled(" - Or in common for every profile", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Inherited", t.emphasis_style()),
+                        Span::styled(
+                            " - A profile's alias overrides one it inherits",
+                            t.text_style(),
+                        ),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(
+                            icons.lightbulb(),
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            " Tip: ",
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            "Run 'dotstate snippets --install' once so your shell loads them.",
+                            t.text_style(),
+                        ),
+                    ]),
+                ];
+                Text::from(lines)
+            }
+            MenuItem::Import => {
+                let lines = vec![
+                    Line::from(vec![Span::styled(
+                        "Import from Other Dotfiles",
+                        t.title_style(),
+                    )]),
+                    Line::from(""),
+                    Line::from(vec![Span::styled(
+                        "Browse anyone's public GitHub dotfiles repository and pick the files you like:",
+                        t.text_style(),
+                    )]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Browse", t.emphasis_style()),
+                        Span::styled(" - Every file of the repository, with a preview", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Import", t.emphasis_style()),
+                        Span::styled(
+                            " - Copied into your home directory and synced to your profile",
+                            t.text_style(),
+                        ),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Attribution", t.emphasis_style()),
+                        Span::styled(
+                            " - A comment in each file says where it came from",
+                            t.text_style(),
+                        ),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(
+                            icons.lightbulb(),
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            " Tip: ",
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            "Files you already have are never replaced.",
+                            t.text_style(),
+                        ),
+                    ]),
+                ];
+                Text::from(lines)
+            }
+            MenuItem::Machines => {
+                let lines = vec![
+                    Line::from(vec![Span::styled("Your Machines", t.title_style())]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(
+                            "Every machine that syncs records itself in ",
+                            t.text_style(),
+                        ),
+                        Span::styled("machines.toml", t.emphasis_style()),
+                        Span::styled(" in your repository:", t.text_style()),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Hostname & OS", t.emphasis_style()),
+                        Span::styled(" - Which machine it is", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Profile", t.emphasis_style()),
+                        Span::styled(" - The profile active there", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Last sync", t.emphasis_style()),
+                        Span::styled(
+                            " - When it last synced, and which version it ran",
+                            t.text_style(),
+                        ),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(
+                            icons.lightbulb(),
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            " Tip: ",
+                            Style::default()
+                                .fg(t.secondary)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            "Machines that haven't synced in a month are highlighted.",
+                            t.text_style(),
+                        ),
+                    ]),
+                ];
+                Text::from(lines)
+            }
+            MenuItem::Stats => {
+                let lines = vec![
+                    Line::from(vec![Span::styled("Repository Statistics", t.title_style())]),
+                    Line::from(""),
+                    Line::from(vec![Span::styled(
+                        "See what your repository holds and what takes up space:",
+                        t.text_style(),
+                    )]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Files per profile", t.emphasis_style()),
+                        Span::styled(" - Own, linked and on-disk size", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Storage", t.emphasis_style()),
+                        Span::styled(" - Repository, git history and backups", t.text_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  • ", t.muted_style()),
+                        Span::styled("Largest & most changed", t.emphasis_style()),
+                        Span::styled(" - Files worth pruning", t.text_style()),
+                    ]),
+                ];
+                Text::from(lines)
+            }
             MenuItem::SetupRepository => {
                 let lines = vec![
                     Line::from(vec![Span::styled(
@@ -390,9 +736,16 @@ impl MenuItem {
     pub fn explanation_icon(&self, icons: &Icons) -> &'static str {
         match self {
             MenuItem::ScanDotfiles => icons.lightbulb(),
+            MenuItem::Import => icons.github(),
             MenuItem::SyncWithRemote => icons.sync(),
+            MenuItem::Notifications => icons.bell(),
+            MenuItem::History => icons.history(),
             MenuItem::ManageProfiles => icons.profile(),
             MenuItem::ManagePackages => icons.package(),
+            MenuItem::Aliases => icons.terminal(),
+            MenuItem::Health => icons.health(),
+            MenuItem::Machines => icons.machine(),
+            MenuItem::Stats => icons.chart(),
             MenuItem::SetupRepository => icons.git(),
             MenuItem::Settings => icons.cog(),
         }
@@ -421,6 +774,106 @@ impl MenuItem {
 }
 
 use crate::services::git_service::GitStatus;
+use crate::services::GitService;
+use crate::utils::SymlinkManager;
+use chrono::{DateTime, Utc};
+
+/// Rows of the status dashboard, each linked to the screen that manages it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardItem {
+    Profile,
+    Files,
+    Repository,
+    Symlinks,
+    LastSync,
+    Health,
+}
+
+impl DashboardItem {
+    /// Get all dashboard rows in display order
+    #[must_use]
+    pub fn all() -> [DashboardItem; 6] {
+        [
+            DashboardItem::Profile,
+            DashboardItem::Files,
+            DashboardItem::Repository,
+            DashboardItem::Symlinks,
+            DashboardItem::LastSync,
+            DashboardItem::Health,
+        ]
+    }
+
+    /// Get the row label
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            DashboardItem::Profile => "Profile",
+            DashboardItem::Files => "Tracked Files",
+            DashboardItem::Repository => "Repository",
+            DashboardItem::Symlinks => "Symlinks",
+            DashboardItem::LastSync => "Last Sync",
+            DashboardItem::Health => "Health",
+        }
+    }
+
+    /// Screen opened when the row is activated
+    #[must_use]
+    pub fn target(&self) -> ScreenId {
+        match self {
+            DashboardItem::Profile => ScreenId::ManageProfiles,
+            DashboardItem::Files => ScreenId::DotfileSelection,
+            DashboardItem::Repository | DashboardItem::LastSync => ScreenId::SyncWithRemote,
+            DashboardItem::Symlinks | DashboardItem::Health => ScreenId::Health,
+        }
+    }
+}
+
+/// Local state shown on the dashboard, reloaded when the menu is entered
+#[derive(Debug, Clone, Default)]
+struct DashboardSnapshot {
+    profile_count: usize,
+    profile_files: usize,
+    common_files: usize,
+    /// Tracked files changed outside dotstate since the last sync
+    integrity_issues: usize,
+    tracked_symlinks: usize,
+    /// Tracked symlinks that were replaced, removed or left dangling
+    broken_symlinks: usize,
+    last_sync: Option<DateTime<Utc>>,
+}
+
+impl DashboardSnapshot {
+    fn load(config: &Config) -> Self {
+        let manifest =
+            crate::utils::ProfileManifest::load_or_backfill(&config.repo_path).unwrap_or_default();
+        let profile_files = manifest
+            .profiles
+            .iter()
+            .find(|p| p.name == config.active_profile)
+            .map_or(0, |p| p.synced_files.len());
+
+        let (tracked_symlinks, broken_symlinks) =
+            match SymlinkManager::new(config.repo_path.clone()) {
+                Ok(manager) => {
+                    let tracked = manager.get_tracked_symlinks();
+                    let broken = crate::utils::link_monitor::scan(tracked).len();
+                    (tracked.len(), broken)
+                }
+                Err(_) => (0, 0),
+            };
+
+        Self {
+            profile_count: manifest.profiles.len(),
+            profile_files,
+            common_files: manifest.get_common_files().len(),
+            integrity_issues: crate::utils::integrity::verify(&config.repo_path)
+                .map_or(0, |issues| issues.len()),
+            tracked_symlinks,
+            broken_symlinks,
+            last_sync: GitService::last_sync_time(),
+        }
+    }
+}
 
 /// Main menu screen controller.
 pub struct MainMenuScreen {
@@ -434,6 +887,22 @@ pub struct MainMenuScreen {
     config: Option<Config>,
     /// Detailed git status
     git_status: GitStatus,
+    /// Whether a background fetch of origin is in progress
+    checking_remote: bool,
+    /// Local state shown on the dashboard
+    dashboard: DashboardSnapshot,
+    /// Latest doctor results as (errors, warnings)
+    health_summary: Option<(usize, usize)>,
+    /// Whether doctor diagnostics are running
+    checking_health: bool,
+    /// Notices on the notifications screen, shown as a badge
+    notification_count: usize,
+    /// Whether keyboard focus is on the dashboard
+    dashboard_focused: bool,
+    /// Selected dashboard row
+    dashboard_selected: DashboardItem,
+    /// Clickable dashboard rows
+    dashboard_regions: MouseRegions<DashboardItem>,
     /// Update information if a new version is available
     update_info: Option<UpdateInfo>,
     /// Whether the update item is currently selected (instead of a menu item)
@@ -458,6 +927,14 @@ impl MainMenuScreen {
             update_clickable_area: None,
             config: None,
             git_status: GitStatus::default(),
+            checking_remote: false,
+            dashboard: DashboardSnapshot::default(),
+            health_summary: None,
+            checking_health: false,
+            notification_count: 0,
+            dashboard_focused: false,
+            dashboard_selected: DashboardItem::Profile,
+            dashboard_regions: MouseRegions::new(),
             update_info: None,
             is_update_selected: false,
             icons: Icons::new(),
@@ -481,17 +958,27 @@ impl MainMenuScreen {
             ..Default::default()
         };
 
-        Self {
+        let mut screen = Self {
             selected_item: default_item,
             menu_state,
             clickable_areas: MouseRegions::new(),
             update_clickable_area: None,
             config: Some(config.clone()),
             git_status,
+            checking_remote: false,
+            dashboard: DashboardSnapshot::default(),
+            health_summary: None,
+            checking_health: false,
+            notification_count: 0,
+            dashboard_focused: false,
+            dashboard_selected: DashboardItem::Profile,
+            dashboard_regions: MouseRegions::new(),
             update_info: None,
             is_update_selected: false,
             icons: Icons::from_config(config),
-        }
+        };
+        screen.refresh_dashboard(config);
+        screen
     }
 
     /// Initialize or reinitialize the screen with configuration.
@@ -599,6 +1086,11 @@ impl MainMenuScreen {
             .is_some_and(super::super::config::Config::is_repo_configured)
     }
 
+    /// Check if this is a pull-only machine
+    fn is_read_only(&self) -> bool {
+        self.config.as_ref().is_some_and(|config| config.read_only)
+    }
+
     /// Get the currently selected menu item
     #[must_use]
     pub fn selected_item(&self) -> MenuItem {
@@ -613,6 +1105,13 @@ impl MainMenuScreen {
         self.menu_state.select(Some(index));
     }
 
+    /// Select `item` and open it as if it was chosen from the menu, going to
+    /// the setup screen instead when the repository isn't set up yet
+    pub fn open_item(&mut self, item: MenuItem, ctx: &ScreenContext) -> Result<ScreenAction> {
+        self.set_selected_item(item);
+        self.handle_selection(ctx)
+    }
+
     /// Get the selected index (for backward compatibility)
     #[must_use]
     pub fn selected_index(&self) -> usize {
@@ -627,6 +1126,11 @@ impl MainMenuScreen {
         }
     }
 
+    /// Set whether the background remote status check is still running
+    pub fn set_checking_remote(&mut self, checking: bool) {
+        self.checking_remote = checking;
+    }
+
     /// Update config (only updates config, doesn't change selection)
     pub fn update_config(&mut self, config: Config) {
         // Update icons when config changes (e.g., icon set changed in settings)
@@ -688,88 +1192,205 @@ impl MainMenuScreen {
         self.selected_item.explanation(&self.icons)
     }
 
-    /// Get stats text based on config
-    fn get_stats(&self) -> String {
-        use crate::config::RepoMode;
+    /// Reload the local part of the dashboard (manifest, symlinks, last sync)
+    pub fn refresh_dashboard(&mut self, config: &Config) {
+        self.dashboard = if config.is_repo_configured() {
+            DashboardSnapshot::load(config)
+        } else {
+            DashboardSnapshot::default()
+        };
+    }
 
-        if let Some(ref config) = self.config {
-            if !config.is_repo_configured() {
-                return "Please complete setup to see status".to_string();
-            }
+    /// Set the latest doctor results (errors, warnings) and whether a run is in progress
+    pub fn set_health_summary(&mut self, summary: Option<(usize, usize)>, checking: bool) {
+        self.health_summary = summary;
+        self.checking_health = checking;
+    }
 
-            // Get stats from manifest
-            let manifest = crate::utils::ProfileManifest::load_or_backfill(&config.repo_path)
-                .unwrap_or_default();
-            let synced_count = manifest
-                .profiles
-                .iter()
-                .find(|p| p.name == config.active_profile)
-                .map_or(0, |p| p.synced_files.len());
-            let profile_count = manifest.profiles.len();
-            let active_profile = &config.active_profile;
-
-            // Show different info based on repo mode
-            let repo_info = match config.repo_mode {
-                RepoMode::GitHub => format!("Repository: {}", config.repo_name),
-                RepoMode::Local => format!("Repository: {} (local)", config.repo_path.display()),
-            };
+    /// Set the number of notices shown as a badge on the Notifications item
+    pub fn set_notification_count(&mut self, count: usize) {
+        self.notification_count = count;
+    }
 
-            let mut stats = format!(
-                "Synced Files: {synced_count}\nProfiles: {profile_count} (Active: {active_profile})\n{repo_info}"
-            );
+    /// Whether keyboard focus is on the dashboard instead of the menu
+    #[must_use]
+    pub fn is_dashboard_focused(&self) -> bool {
+        self.dashboard_focused
+    }
 
-            // Add git status info
-            let status = &self.git_status;
-
-            // Show ahead/behind info
-            if status.ahead > 0 || status.behind > 0 {
-                stats.push_str("\n\nRemote Status:");
-                if status.behind > 0 {
-                    stats.push_str(&format!(
-                        "\n  ↓ {} commit(s) behind (pull needed)",
-                        status.behind
-                    ));
-                }
-                if status.ahead > 0 {
-                    stats.push_str(&format!(
-                        "\n  ↑ {} commit(s) ahead (push needed)",
-                        status.ahead
-                    ));
+    /// Move the dashboard selection by `delta` rows, wrapping around
+    fn move_dashboard(&mut self, delta: isize) {
+        let items = DashboardItem::all();
+        let current = items
+            .iter()
+            .position(|item| *item == self.dashboard_selected)
+            .unwrap_or(0);
+        let next = current.checked_add_signed(delta).unwrap_or(items.len() - 1) % items.len();
+        self.dashboard_selected = items[next];
+    }
+
+    /// Whether anything on the dashboard needs the user's attention
+    fn needs_attention(&self) -> bool {
+        self.git_status.has_changes
+            || self.git_status.ahead > 0
+            || self.git_status.behind > 0
+            || self.dashboard.broken_symlinks > 0
+            || self.dashboard.integrity_issues > 0
+            || self.health_summary.is_some_and(|(errors, _)| errors > 0)
+    }
+
+    /// Value text and color for a dashboard row
+    fn dashboard_value(&self, item: DashboardItem, config: &Config) -> (String, Color) {
+        use crate::config::RepoMode;
+
+        let t = theme();
+        let snapshot = &self.dashboard;
+        match item {
+            DashboardItem::Profile => (
+                format!(
+                    "{} ({} profile(s))",
+                    config.active_profile, snapshot.profile_count
+                ),
+                t.text,
+            ),
+            DashboardItem::Files => {
+                let counts = format!(
+                    "{} in profile, {} common",
+                    snapshot.profile_files, snapshot.common_files
+                );
+                match snapshot.integrity_issues {
+                    0 => (counts, t.text),
+                    issues => (
+                        format!("{counts} · {issues} changed outside dotstate"),
+                        t.warning,
+                    ),
                 }
             }
-
-            // Add pending changes if any
-            if !status.uncommitted_files.is_empty() {
-                stats.push_str(&format!(
-                    "\n\nPending Changes ({}):",
-                    status.uncommitted_files.len()
-                ));
-                // Show first few files (limit to avoid overflow)
-                let max_files = 5.min(status.uncommitted_files.len());
-                for file in status.uncommitted_files.iter().take(max_files) {
-                    // Remove status prefix (A, M, D) for display, or show it
-                    let display_file = if file.len() > 2 && file.chars().nth(1) == Some(' ') {
-                        &file[2..] // Skip "A ", "M ", "D "
-                    } else {
-                        file
-                    };
-                    stats.push_str(&format!("\n  • {display_file}"));
+            DashboardItem::Repository => {
+                let name = match config.repo_mode {
+                    RepoMode::GitHub => config.repo_name.clone(),
+                    RepoMode::Local => format!("{} (local)", config.repo_path.display()),
+                };
+                let count = self.git_status.uncommitted_files.len();
+                if count > 0 {
+                    (format!("{name} · {count} uncommitted"), t.warning)
+                } else if self.git_status.has_changes {
+                    (format!("{name} · uncommitted changes"), t.warning)
+                } else {
+                    (format!("{name} · clean"), t.text)
                 }
-                if status.uncommitted_files.len() > max_files {
-                    stats.push_str(&format!(
-                        "\n  ... and {} more",
-                        status.uncommitted_files.len() - max_files
-                    ));
+            }
+            DashboardItem::Symlinks => {
+                match (snapshot.tracked_symlinks, snapshot.broken_symlinks) {
+                    (0, _) => ("None tracked".to_string(), t.text_muted),
+                    (total, 0) => (format!("All {total} healthy"), t.success),
+                    (total, broken) => (format!("{broken} broken of {total}"), t.warning),
                 }
-            } else if status.ahead == 0 && status.behind == 0 && status.has_changes {
-                // Fallback if has_changes is true but list is empty (shouldn't happen but safe to handle)
-                stats.push_str("\n\nPending Changes: Yes");
             }
+            DashboardItem::LastSync => match snapshot.last_sync {
+                Some(time) => (
+                    crate::utils::text::format_time_ago(time, Utc::now()),
+                    t.text,
+                ),
+                None => ("Never".to_string(), t.text_muted),
+            },
+            DashboardItem::Health => match self.health_summary {
+                _ if self.checking_health => ("Checking...".to_string(), t.text_muted),
+                Some((0, 0)) => ("No issues".to_string(), t.success),
+                Some((errors, warnings)) => (
+                    format!("{errors} error(s), {warnings} warning(s)"),
+                    if errors > 0 { t.error } else { t.warning },
+                ),
+                None => ("Not checked yet".to_string(), t.text_muted),
+            },
+        }
+    }
 
-            stats
+    /// Render the status dashboard and record clickable rows
+    fn render_dashboard(&mut self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let attention = self.needs_attention();
+        let color = if attention { t.warning } else { t.success };
+        let icon = if attention {
+            self.icons.warning()
         } else {
-            "Please complete setup to see status".to_string()
+            self.icons.success()
+        };
+        let border_style = if self.dashboard_focused {
+            t.border_focused_style()
+        } else {
+            Style::default().fg(color)
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .border_type(t.border_type(self.dashboard_focused))
+            .title(format!(" {icon} Status "))
+            .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            .title_alignment(Alignment::Center)
+            .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                1, 1, 1, 1,
+            )));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        self.dashboard_regions.clear();
+        let Some(config) = self.config.clone().filter(Config::is_repo_configured) else {
+            frame.render_widget(
+                Paragraph::new("Please complete setup to see status")
+                    .style(t.text_style())
+                    .wrap(Wrap { trim: true }),
+                inner,
+            );
+            return;
+        };
+
+        let mut lines: Vec<Line> = Vec::new();
+        for item in DashboardItem::all() {
+            let selected = self.dashboard_focused && item == self.dashboard_selected;
+            let (value, value_color) = self.dashboard_value(item, &config);
+            let marker = if selected {
+                LIST_HIGHLIGHT_SYMBOL
+            } else {
+                "  "
+            };
+            let label_style = if selected {
+                t.highlight_style()
+            } else {
+                Style::default().fg(t.primary).add_modifier(Modifier::BOLD)
+            };
+
+            let row = u16::try_from(lines.len()).unwrap_or(u16::MAX);
+            if row < inner.height {
+                self.dashboard_regions
+                    .add(Rect::new(inner.x, inner.y + row, inner.width, 1), item);
+            }
+
+            lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(t.primary)),
+                Span::styled(format!("{:<14}", item.label()), label_style),
+                Span::styled(value, Style::default().fg(value_color)),
+            ]));
+
+            // Remote state sits under the repository row
+            if item == DashboardItem::Repository {
+                for detail in remote_status_lines(&self.git_status, self.checking_remote) {
+                    lines.push(Line::from(Span::styled(
+                        format!("{:16}{detail}", ""),
+                        t.muted_style(),
+                    )));
+                }
+            }
         }
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// Number of rows the dashboard needs, including borders and padding
+    fn dashboard_height(&self) -> u16 {
+        let details = remote_status_lines(&self.git_status, self.checking_remote).len();
+        u16::try_from(DashboardItem::all().len() + details + 4).unwrap_or(u16::MAX)
     }
 
     /// Render the main menu screen
@@ -793,32 +1414,42 @@ impl MainMenuScreen {
         )?;
 
         // Split content into left and right panels
-        let content_split = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(50), // Left panel
-                Constraint::Percentage(50), // Right panel
-            ])
-            .split(content_chunk);
+        let content_split = create_split_layout(content_chunk, &[50, 50]);
 
         // Menu items
         let menu_items = MenuItem::all();
         let is_setup = self.is_setup();
+        let read_only = self.is_read_only();
 
         // Convert to Menu widget items
         let mut widget_items: Vec<MenuWidgetItem> = menu_items
             .iter()
             .map(|menu_item| {
                 let icon = menu_item.icon(&self.icons);
-                let text = menu_item.text();
-                let is_enabled = menu_item.is_enabled(is_setup);
+                let text = if read_only && *menu_item == MenuItem::SyncWithRemote {
+                    "Pull from Remote"
+                } else {
+                    menu_item.text()
+                };
+                let is_enabled = menu_item.is_enabled(is_setup, read_only);
                 let has_action_needed = self.git_status.has_changes
                     || self.git_status.ahead > 0
                     || self.git_status.behind > 0;
-                let color = menu_item.color(has_action_needed);
+                let color = if *menu_item == MenuItem::Notifications {
+                    menu_item.color(self.notification_count > 0)
+                } else {
+                    menu_item.color(has_action_needed)
+                };
 
                 let mut item = MenuWidgetItem::new(icon, text, color).enabled(is_enabled);
 
+                if *menu_item == MenuItem::Notifications
+                    && self.notification_count > 0
+                    && is_enabled
+                {
+                    item = item.info(self.notification_count.to_string());
+                }
+
                 // Add info for sync item if there are pending changes
                 if *menu_item == MenuItem::SyncWithRemote && has_action_needed && is_enabled {
                     let mut info_parts = Vec::new();
@@ -889,12 +1520,12 @@ impl MainMenuScreen {
         // Render the menu inside the block
         StatefulWidget::render(menu, menu_inner, frame.buffer_mut(), &mut self.menu_state);
 
-        // Right panel: Explanation and stats
+        // Right panel: Explanation and status dashboard
         let right_split = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(60), // Explanation
-                Constraint::Percentage(40), // Stats
+                Constraint::Min(8),                          // Explanation
+                Constraint::Length(self.dashboard_height()), // Dashboard
             ])
             .split(content_split[1]);
 
@@ -915,7 +1546,9 @@ impl MainMenuScreen {
             .title(format!(" {icon} What does this do? "))
             .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
             .title_alignment(Alignment::Center)
-            .padding(ratatui::widgets::Padding::new(1, 1, 1, 1));
+            .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                1, 1, 1, 1,
+            )));
 
         let explanation_para = Paragraph::new(self.get_explanation())
             .wrap(Wrap { trim: true })
@@ -923,97 +1556,7 @@ impl MainMenuScreen {
 
         frame.render_widget(explanation_para, right_split[0]);
 
-        // Stats block with colorful styling
-        let has_pending =
-            self.git_status.has_changes || self.git_status.ahead > 0 || self.git_status.behind > 0;
-        let stats_color = if has_pending { t.warning } else { t.success };
-        let stats_icon = if has_pending {
-            self.icons.warning()
-        } else {
-            self.icons.success()
-        };
-
-        let stats_block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(stats_color))
-            .border_type(t.border_type(false))
-            .title(format!(" {stats_icon} Status "))
-            .title_style(
-                Style::default()
-                    .fg(stats_color)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .title_alignment(Alignment::Center)
-            .padding(ratatui::widgets::Padding::new(1, 1, 1, 1));
-
-        // Format stats with colors
-        let stats_text = self.get_stats();
-        let stats_lines: Vec<Line> = stats_text
-            .lines()
-            .map(|line| {
-                if line.starts_with("Synced Files:") {
-                    Line::from(vec![
-                        Span::styled(
-                            "Synced Files: ",
-                            Style::default().fg(t.primary).add_modifier(Modifier::BOLD),
-                        ),
-                        Span::styled(
-                            line.strip_prefix("Synced Files: ").unwrap_or(""),
-                            t.text_style(),
-                        ),
-                    ])
-                } else if line.starts_with("Profiles:") {
-                    Line::from(vec![
-                        Span::styled(
-                            "Profiles: ",
-                            Style::default()
-                                .fg(t.secondary)
-                                .add_modifier(Modifier::BOLD),
-                        ),
-                        Span::styled(
-                            line.strip_prefix("Profiles: ").unwrap_or(""),
-                            t.text_style(),
-                        ),
-                    ])
-                } else if line.starts_with("Repository:") {
-                    Line::from(vec![
-                        Span::styled(
-                            "Repository: ",
-                            Style::default().fg(t.tertiary).add_modifier(Modifier::BOLD),
-                        ),
-                        Span::styled(
-                            line.strip_prefix("Repository: ").unwrap_or(""),
-                            t.text_style(),
-                        ),
-                    ])
-                } else if line.starts_with("Pending Changes") {
-                    Line::from(vec![Span::styled(
-                        line,
-                        Style::default().fg(t.warning).add_modifier(Modifier::BOLD),
-                    )])
-                } else if line.starts_with("  •") {
-                    Line::from(vec![
-                        Span::styled("  • ", Style::default().fg(t.warning)),
-                        Span::styled(line.strip_prefix("  • ").unwrap_or(""), t.text_style()),
-                    ])
-                } else if line.contains("... and") {
-                    Line::from(vec![Span::styled(
-                        line,
-                        Style::default()
-                            .fg(t.text_muted)
-                            .add_modifier(Modifier::ITALIC),
-                    )])
-                } else {
-                    Line::from(Span::styled(line, t.text_style()))
-                }
-            })
-            .collect();
-
-        let stats_para = Paragraph::new(stats_lines)
-            .wrap(Wrap { trim: true })
-            .block(stats_block);
-
-        frame.render_widget(stats_para, right_split[1]);
+        self.render_dashboard(frame, right_split[1]);
 
         // Footer with dynamic keybindings from keymap
         let footer_text = self
@@ -1022,7 +1565,12 @@ impl MainMenuScreen {
             .map(|c| {
                 let t = theme();
                 let theme_name = t.theme_type.to_config_string();
-                c.keymap.footer_navigation(theme_name)
+                format!(
+                    "{} | {}: Dashboard",
+                    c.keymap.footer_navigation(theme_name),
+                    c.keymap
+                        .get_key_display_for_action(crate::keymap::Action::NextTab)
+                )
             })
             .unwrap_or_else(|| {
                 "↑↓: Navigate | Enter: Select | q: Back | ?: Help | t: Theme".to_string()
@@ -1050,18 +1598,39 @@ impl MainMenuScreen {
         if item.requires_setup() && !is_setup {
             return Ok(ScreenAction::Navigate(ScreenId::StorageSetup));
         }
+        if ctx.config.read_only && item.is_mutating() {
+            return Ok(ScreenAction::pull_only_blocked());
+        }
 
         // Navigate based on selected item
         match item {
             MenuItem::ScanDotfiles => Ok(ScreenAction::Navigate(ScreenId::DotfileSelection)),
+            MenuItem::Import => Ok(ScreenAction::Navigate(ScreenId::Import)),
             MenuItem::SyncWithRemote => Ok(ScreenAction::Navigate(ScreenId::SyncWithRemote)),
+            MenuItem::Notifications => Ok(ScreenAction::Navigate(ScreenId::Notifications)),
+            MenuItem::History => Ok(ScreenAction::Navigate(ScreenId::History)),
             MenuItem::ManageProfiles => Ok(ScreenAction::Navigate(ScreenId::ManageProfiles)),
             MenuItem::ManagePackages => Ok(ScreenAction::Navigate(ScreenId::ManagePackages)),
+            MenuItem::Aliases => Ok(ScreenAction::Navigate(ScreenId::Aliases)),
+            MenuItem::Health => Ok(ScreenAction::Navigate(ScreenId::Health)),
+            MenuItem::Machines => Ok(ScreenAction::Navigate(ScreenId::Machines)),
+            MenuItem::Stats => Ok(ScreenAction::Navigate(ScreenId::Stats)),
             MenuItem::SetupRepository => Ok(ScreenAction::Navigate(ScreenId::StorageSetup)),
             MenuItem::Settings => Ok(ScreenAction::Navigate(ScreenId::Settings)),
         }
     }
 
+    /// Navigate to the screen behind a dashboard row.
+    fn dashboard_action(item: DashboardItem, ctx: &ScreenContext) -> ScreenAction {
+        if ctx.config.read_only && item.target() == ScreenId::DotfileSelection {
+            ScreenAction::pull_only_blocked()
+        } else if ctx.config.is_repo_configured() {
+            ScreenAction::Navigate(item.target())
+        } else {
+            ScreenAction::Navigate(ScreenId::StorageSetup)
+        }
+    }
+
     /// Build the update message from `UpdateInfo`.
     fn build_update_message(&self, info: &UpdateInfo) -> (String, String) {
         let title = format!(
@@ -1095,6 +1664,7 @@ impl MainMenuScreen {
     /// Handle mouse events
     fn handle_mouse_event(&mut self, event: Event) -> Result<bool> {
         let is_setup = self.is_setup();
+        let read_only = self.is_read_only();
 
         if let Event::Mouse(mouse) = event {
             match mouse.kind {
@@ -1117,9 +1687,10 @@ impl MainMenuScreen {
                     // Check if click is in any menu clickable area
                     if let Some(&menu_item) = self.clickable_areas.hit_test(mouse.column, mouse.row)
                     {
+                        self.dashboard_focused = false;
                         self.set_selected_item(menu_item);
                         // Only trigger action if item is enabled
-                        if menu_item.is_enabled(is_setup) {
+                        if menu_item.is_enabled(is_setup, read_only) {
                             return Ok(true); // Selection made
                         }
                     }
@@ -1156,6 +1727,25 @@ impl Screen for MainMenuScreen {
                 if let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) {
                     use crate::keymap::Action;
                     match action {
+                        Action::NextTab | Action::PrevTab if self.is_setup() => {
+                            self.dashboard_focused = !self.dashboard_focused;
+                            return Ok(ScreenAction::None);
+                        }
+                        Action::MoveUp if self.dashboard_focused => {
+                            self.move_dashboard(-1);
+                            return Ok(ScreenAction::None);
+                        }
+                        Action::MoveDown if self.dashboard_focused => {
+                            self.move_dashboard(1);
+                            return Ok(ScreenAction::None);
+                        }
+                        Action::Confirm if self.dashboard_focused => {
+                            return Ok(Self::dashboard_action(self.dashboard_selected, ctx));
+                        }
+                        Action::Cancel if self.dashboard_focused => {
+                            self.dashboard_focused = false;
+                            return Ok(ScreenAction::None);
+                        }
                         Action::MoveUp => {
                             self.move_up();
                             return Ok(ScreenAction::None);
@@ -1184,6 +1774,17 @@ impl Screen for MainMenuScreen {
             }
         }
 
+        // Clicking a dashboard row opens the related screen
+        if let Event::Mouse(mouse) = &event {
+            if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                if let Some(&item) = self.dashboard_regions.hit_test(mouse.column, mouse.row) {
+                    self.dashboard_focused = true;
+                    self.dashboard_selected = item;
+                    return Ok(Self::dashboard_action(item, ctx));
+                }
+            }
+        }
+
         // Handle mouse events
         if matches!(event, Event::Mouse(_)) && self.handle_mouse_event(event)? {
             // Mouse click triggered selection
@@ -1201,6 +1802,9 @@ impl Screen for MainMenuScreen {
         // Preserve current selection when re-entering the screen
         let current_selection = self.selected_item;
         let was_update_selected = self.is_update_selected;
+        let (dashboard_focused, dashboard_selected) =
+            (self.dashboard_focused, self.dashboard_selected);
+        let (health_summary, checking_health) = (self.health_summary, self.checking_health);
 
         // Re-initialize when entering the screen
         self.init_with_config(ctx.config, false);
@@ -1208,6 +1812,9 @@ impl Screen for MainMenuScreen {
         // Restore the previous selection
         self.selected_item = current_selection;
         self.is_update_selected = was_update_selected;
+        self.dashboard_focused = dashboard_focused;
+        self.dashboard_selected = dashboard_selected;
+        self.set_health_summary(health_summary, checking_health);
         let index = current_selection.to_index();
         self.menu_state.select(Some(index));
 
@@ -1215,6 +1822,30 @@ impl Screen for MainMenuScreen {
     }
 }
 
+/// Lines describing how the local branch compares to origin.
+///
+/// Empty when there is no remote and no check is running.
+fn remote_status_lines(status: &GitStatus, checking: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    if status.behind > 0 {
+        lines.push(format!(
+            "↓ {} commit(s) behind - remote has changes, sync before editing",
+            status.behind
+        ));
+    }
+    if status.ahead > 0 {
+        lines.push(format!("↑ {} commit(s) ahead (push needed)", status.ahead));
+    }
+    if checking {
+        lines.push("Checking remote...".to_string());
+    } else if status.fetch_error.is_some() && status.has_remote {
+        lines.push("⚠ Could not reach remote (showing last known state)".to_string());
+    } else if status.remote_fetched && status.ahead == 0 && status.behind == 0 {
+        lines.push("✓ Up to date with remote".to_string());
+    }
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1255,4 +1886,55 @@ mod tests {
         let screen = MainMenuScreen::with_config(&config, false);
         assert_eq!(screen.selected_item(), MenuItem::SetupRepository);
     }
+
+    #[test]
+    fn test_remote_status_lines() {
+        let mut status = GitStatus {
+            has_remote: true,
+            remote_fetched: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            remote_status_lines(&status, false),
+            vec!["✓ Up to date with remote"]
+        );
+
+        status.behind = 2;
+        let lines = remote_status_lines(&status, false);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("remote has changes"));
+
+        // A running check replaces the up-to-date/failed line
+        assert_eq!(
+            remote_status_lines(&GitStatus::default(), true),
+            vec!["Checking remote..."]
+        );
+        assert!(remote_status_lines(&GitStatus::default(), false).is_empty());
+    }
+
+    #[test]
+    fn test_pull_only_disables_manage_files() {
+        assert!(MenuItem::ScanDotfiles.is_enabled(true, false));
+        assert!(!MenuItem::ScanDotfiles.is_enabled(true, true));
+        assert!(MenuItem::SyncWithRemote.is_enabled(true, true));
+        assert!(MenuItem::ManageProfiles.is_enabled(true, true));
+    }
+
+    #[test]
+    fn test_dashboard_targets() {
+        assert_eq!(DashboardItem::Profile.target(), ScreenId::ManageProfiles);
+        assert_eq!(DashboardItem::Files.target(), ScreenId::DotfileSelection);
+        assert_eq!(DashboardItem::LastSync.target(), ScreenId::SyncWithRemote);
+        assert_eq!(DashboardItem::Symlinks.target(), ScreenId::Health);
+    }
+
+    #[test]
+    fn test_dashboard_selection_wraps() {
+        let mut screen = MainMenuScreen::with_config(&test_config(), false);
+        assert!(!screen.is_dashboard_focused());
+        screen.move_dashboard(-1);
+        assert_eq!(screen.dashboard_selected, DashboardItem::Health);
+        screen.move_dashboard(1);
+        assert_eq!(screen.dashboard_selected, DashboardItem::Profile);
+    }
 }
@@ -13,7 +13,8 @@ use crate::utils::package_installer::PackageInstaller;
 use crate::utils::package_manager::PackageManagerImpl;
 use crate::utils::profile_manifest::{Package, PackageManager};
 use crate::utils::{
-    create_standard_layout, focused_border_style, unfocused_border_style, MouseRegions,
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions,
 };
 use crate::widgets::{TextInputWidget, TextInputWidgetExt};
 use anyhow::Result;
@@ -508,10 +509,7 @@ impl Screen for ManagePackagesScreen {
         let main_area = layout.1;
 
         // Split main area into left (list) and right (details) panels
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(main_area);
+        let chunks = create_split_layout(main_area, &[50, 50]);
 
         // Left panel: Package list
         self.render_package_list(frame, chunks[0], config)?;
@@ -524,6 +522,15 @@ impl Screen for ManagePackagesScreen {
             "Checking packages...".to_string()
         } else if !matches!(self.state.installation_step, InstallationStep::NotStarted) {
             "Installing packages...".to_string()
+        } else if config.read_only {
+            let k = |a| config.keymap.get_key_display_for_action(a);
+            format!(
+                "{}: Navigate | {}: Check | {}: Install | {}: Back",
+                config.keymap.navigation_display(),
+                k(crate::keymap::Action::Refresh),
+                k(crate::keymap::Action::Install),
+                k(crate::keymap::Action::Cancel)
+            )
         } else {
             let k = |a| config.keymap.get_key_display_for_action(a);
             format!(
@@ -622,6 +629,14 @@ impl Screen for ManagePackagesScreen {
         match event {
             Event::Key(key) => {
                 if let Some(action) = self.get_action(key.code, key.modifiers, &config.keymap) {
+                    if config.read_only
+                        && matches!(
+                            action,
+                            Action::Create | Action::Import | Action::Edit | Action::Delete
+                        )
+                    {
+                        return Ok(ScreenAction::pull_only_blocked());
+                    }
                     return self.handle_main_list_action(action);
                 }
             }
@@ -2071,7 +2086,7 @@ impl ManagePackagesScreen {
                     .border_type(theme().border_type(false))
                     .title(" Packages ")
                     .border_style(unfocused_border_style())
-                    .padding(Padding::uniform(1)),
+                    .padding(crate::styles::padding(Padding::uniform(1))),
             )
             .wrap(Wrap { trim: true })
             .alignment(Alignment::Center);
@@ -2191,7 +2206,7 @@ impl ManagePackagesScreen {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_type(theme().border_type(false))
-                    .padding(Padding::uniform(1))
+                    .padding(crate::styles::padding(Padding::uniform(1)))
                     .title(" Package Details ")
                     .style(theme().background_style())
                     .title_style(theme().title_style()),
@@ -2999,7 +3014,7 @@ impl ManagePackagesScreen {
         let content_block = Block::bordered()
             .border_set(symbols::border::PROPORTIONAL_TALL)
             .border_style(content_border_style)
-            .padding(Padding::horizontal(1));
+            .padding(crate::styles::padding(Padding::horizontal(1)));
 
         let content_inner = content_block.inner(outer_chunks[2]);
         frame.render_widget(content_block, outer_chunks[2]);
@@ -1,5 +1,6 @@
 use crate::components::footer::Footer;
 use crate::components::header::Header;
+use crate::components::remote_banner::RemoteBanner;
 use crate::config::Config;
 use crate::keymap::{Action, Keymap};
 use crate::screens::{ActionResult, RenderContext, Screen, ScreenAction, ScreenContext};
@@ -7,7 +8,8 @@ use crate::services::ProfileService;
 use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
 use crate::ui::Screen as ScreenId;
 use crate::utils::{
-    create_standard_layout, focused_border_style, unfocused_border_style, MouseRegions,
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    DoubleClick, MouseRegions, ScrollbarDrag,
 };
 use crate::widgets::{DialogVariant, TextInputWidget, TextInputWidgetExt};
 use anyhow::Result;
@@ -63,6 +65,7 @@ pub enum ProfileAction {
 pub struct ProfileManagerState {
     pub list_state: ListState,
     pub clickable_areas: MouseRegions<usize>,
+    pub row_clicks: DoubleClick<usize>,
     pub popup_type: ProfilePopupType,
     // Create popup state
     pub create_name_input: crate::utils::TextInput,
@@ -79,6 +82,7 @@ pub struct ProfileManagerState {
     pub create_description_area: Option<Rect>,
     pub create_inherits_from_area: Option<Rect>,
     pub create_copy_from_area: Option<Rect>,
+    pub create_copy_from_scrollbar: ScrollbarDrag,
     // Cached profiles to reduce disk I/O
     pub profiles: Vec<crate::utils::ProfileInfo>,
     // Cached resolved files per profile (includes inherited + common)
@@ -92,6 +96,7 @@ impl Default for ProfileManagerState {
         Self {
             list_state: ListState::default(),
             clickable_areas: MouseRegions::new(),
+            row_clicks: DoubleClick::new(),
             popup_type: ProfilePopupType::None,
             create_name_input: crate::utils::TextInput::new(),
             create_description_input: crate::utils::TextInput::new(),
@@ -104,6 +109,7 @@ impl Default for ProfileManagerState {
             create_description_area: None,
             create_inherits_from_area: None,
             create_copy_from_area: None,
+            create_copy_from_scrollbar: ScrollbarDrag::new(),
             profiles: Vec::new(),
             resolved_files: HashMap::new(),
             error_message: None,
@@ -113,6 +119,8 @@ impl Default for ProfileManagerState {
 
 pub struct ManageProfilesScreen {
     pub state: ProfileManagerState,
+    /// Warning shown when the remote has commits to pull first
+    remote_banner: RemoteBanner,
 }
 
 impl Default for ManageProfilesScreen {
@@ -126,9 +134,15 @@ impl ManageProfilesScreen {
     pub fn new() -> Self {
         Self {
             state: ProfileManagerState::default(),
+            remote_banner: RemoteBanner::new(),
         }
     }
 
+    /// Update the remote-ahead banner from the latest remote status.
+    pub fn set_remote_status(&mut self, behind: usize, pulling: bool) {
+        self.remote_banner.set_status(behind, pulling);
+    }
+
     /// Refresh the cached profiles from disk
     pub fn refresh_profiles(&mut self, repo_path: &std::path::Path) -> Result<()> {
         let profiles = crate::services::ProfileService::get_profiles(repo_path)?;
@@ -253,7 +267,7 @@ impl ManageProfilesScreen {
             &config.repo_path,
             &old_profile,
             target_name,
-            config.backup_enabled,
+            config.backup_enabled_for(target_name),
         ) {
             Ok(result) => {
                 info!(
@@ -261,8 +275,9 @@ impl ManageProfilesScreen {
                     result.removed_count, result.created_count
                 );
 
-                // Update config with new active profile
+                // Update config with new active profile and its overrides
                 config.active_profile = target_name.to_string();
+                config.apply_profile_overrides();
                 if let Err(e) = config.save(config_path) {
                     error!("Failed to save config after profile switch: {}", e);
                     return Ok(ActionResult::ShowDialog {
@@ -277,6 +292,11 @@ impl ManageProfilesScreen {
                     warn!("Failed to refresh profiles after switch: {}", e);
                 }
 
+                // First activation of this profile here: offer its bootstrap scripts
+                if !crate::utils::bootstrap::pending(&config.repo_path, target_name).is_empty() {
+                    return Ok(ActionResult::Navigate(ScreenId::Bootstrap));
+                }
+
                 Ok(ActionResult::ShowToast {
                     message: format!("Switched to profile '{target_name}'"),
                     variant: crate::widgets::ToastVariant::Success,
@@ -407,6 +427,19 @@ impl ManageProfilesScreen {
     ) -> ScreenAction {
         let popup_open = self.state.popup_type != ProfilePopupType::None;
 
+        if self.state.popup_type == ProfilePopupType::Create {
+            let current = self.state.create_copy_from.map_or(0, |i| i + 1);
+            if let Some(index) = self.state.create_copy_from_scrollbar.handle(mouse, current) {
+                self.state.create_focused_field = CreateField::CopyFrom;
+                // Row 0 is "Start Blank"
+                self.state.create_copy_from = index.checked_sub(1);
+                if self.state.create_copy_from.is_some() {
+                    self.state.create_inherits_from = None;
+                }
+                return ScreenAction::Refresh;
+            }
+        }
+
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
                 let x = mouse.column;
@@ -456,9 +489,22 @@ impl ManageProfilesScreen {
                     }
                 }
 
-                // Handle clicks in background list (only when no popup is open)
+                // Handle clicks in background list (only when no popup is open),
+                // a double-click offers to switch to the profile
                 if !popup_open {
                     if let Some(&idx) = self.state.clickable_areas.hit_test(x, y) {
+                        self.state.list_state.select(Some(idx));
+                        if self.state.row_clicks.click(idx) {
+                            self.state.popup_type = ProfilePopupType::Switch;
+                        }
+                        return ScreenAction::Refresh;
+                    }
+                }
+            }
+            MouseEventKind::Moved if !popup_open => {
+                // Hovering a profile shows its details
+                if let Some(&idx) = self.state.clickable_areas.hit_test(mouse.column, mouse.row) {
+                    if self.state.list_state.selected() != Some(idx) {
                         self.state.list_state.select(Some(idx));
                         return ScreenAction::Refresh;
                     }
@@ -733,7 +779,9 @@ impl ManageProfilesScreen {
                         .title(" Profile Details ")
                         .border_type(theme().border_type(false))
                         .border_style(unfocused_border_style())
-                        .padding(ratatui::widgets::Padding::new(1, 1, 1, 1)),
+                        .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                            1, 1, 1, 1,
+                        ))),
                 )
                 .wrap(Wrap { trim: true });
 
@@ -747,7 +795,9 @@ impl ManageProfilesScreen {
                             .title(" Profile Details ")
                             .border_type(theme().border_type(false))
                             .border_style(unfocused_border_style())
-                            .padding(ratatui::widgets::Padding::new(1, 1, 1, 1)),
+                            .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                                1, 1, 1, 1,
+                            ))),
                     )
                     .wrap(Wrap { trim: true });
             frame.render_widget(paragraph, area);
@@ -947,6 +997,7 @@ impl ManageProfilesScreen {
             " Copy From — one-time file copy "
         };
 
+        self.state.create_copy_from_scrollbar.clear();
         if self.state.profiles.is_empty() || is_disabled {
             let msg = if is_disabled {
                 "Disabled. Clear Inherits From to copy files."
@@ -1045,11 +1096,16 @@ impl ManageProfilesScreen {
                 let mut scrollbar_state =
                     ScrollbarState::new(total_items as usize).position(selected_pos);
 
-                let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                    .begin_symbol(Some("↑"))
-                    .end_symbol(Some("↓"));
+                let scrollbar = self.state.create_copy_from_scrollbar.style(
+                    Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                        .begin_symbol(Some("↑"))
+                        .end_symbol(Some("↓")),
+                );
 
                 frame.render_stateful_widget(scrollbar, chunks[4], &mut scrollbar_state);
+                self.state
+                    .create_copy_from_scrollbar
+                    .set(chunks[4], total_items as usize);
             }
         }
 
@@ -1198,12 +1254,14 @@ impl ManageProfilesScreen {
                         This will permanently delete:\n\
                         • Profile '{}'\n\
                         • All {} synced files in the repo\n\
-                        • Profile folder: ~/.config/dotstate/storage/{}/\n\n\
+                        • Profile folder: {}/\n\n\
                         Type the profile name below to confirm:",
                         icons.warning(),
                         p.name,
                         p.synced_files.len(),
-                        p.name
+                        crate::utils::path::format_path_for_display(
+                            &config.repo_path.join(&p.name)
+                        )
                     ),
                     DialogVariant::Warning,
                 )
@@ -1279,11 +1337,17 @@ impl Screen for ManageProfilesScreen {
             "Manage different profiles for different machines. Each profile has its own set of synced dotfiles."
         )?;
 
+        let pull_key = ctx
+            .config
+            .keymap
+            .get_key_display_for_action(crate::keymap::Action::Pull);
+        let icons = crate::icons::Icons::from_config(ctx.config);
+        let content_chunk = self
+            .remote_banner
+            .render(frame, content_chunk, &pull_key, &icons);
+
         // Split content: Left (profiles list), Right (profile details)
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-            .split(content_chunk);
+        let chunks = create_split_layout(content_chunk, &[40, 60]);
         let left_chunk = chunks[0];
         let right_chunk = chunks[1];
 
@@ -1298,15 +1362,24 @@ impl Screen for ManageProfilesScreen {
         // dim_background overlay dims the footer in place, leaving the page
         // hotkeys visible-but-receded behind the modal.
         let k = |a| ctx.config.keymap.get_key_display_for_action(a);
-        let footer_text = format!(
-            "{}: Navigate | {}: Switch Profile | {}: Create | {}: Rename | {}: Delete | {}: Back",
-            ctx.config.keymap.navigation_display(),
-            k(crate::keymap::Action::Confirm),
-            k(crate::keymap::Action::Create),
-            k(crate::keymap::Action::Edit),
-            k(crate::keymap::Action::Delete),
-            k(crate::keymap::Action::Cancel)
-        );
+        let footer_text = if ctx.config.read_only {
+            format!(
+                "{}: Navigate | {}: Switch Profile | {}: Back",
+                ctx.config.keymap.navigation_display(),
+                k(crate::keymap::Action::Confirm),
+                k(crate::keymap::Action::Cancel)
+            )
+        } else {
+            format!(
+                "{}: Navigate | {}: Switch Profile | {}: Create | {}: Rename | {}: Delete | {}: Back",
+                ctx.config.keymap.navigation_display(),
+                k(crate::keymap::Action::Confirm),
+                k(crate::keymap::Action::Create),
+                k(crate::keymap::Action::Edit),
+                k(crate::keymap::Action::Delete),
+                k(crate::keymap::Action::Cancel)
+            )
+        };
         Footer::render(frame, footer_chunk, &footer_text)?;
 
         // Render popups on top of the content (not instead of it)
@@ -1821,8 +1894,16 @@ impl Screen for ManageProfilesScreen {
             Event::Key(key) if key.kind == KeyEventKind::Press => {
                 let action = self.get_action(key.code, key.modifiers, &ctx.config.keymap);
                 if let Some(action) = action {
+                    if ctx.config.read_only
+                        && matches!(action, Action::Create | Action::Edit | Action::Delete)
+                    {
+                        return Ok(ScreenAction::pull_only_blocked());
+                    }
                     match action {
                         Action::Cancel => return Ok(ScreenAction::Navigate(ScreenId::MainMenu)),
+                        Action::Pull if self.remote_banner.can_pull() => {
+                            return Ok(ScreenAction::PullFromRemote);
+                        }
                         Action::MoveUp => {
                             let selected = self.state.list_state.selected().unwrap_or(0);
                             let new_selected = if selected > 0 { selected - 1 } else { selected };
@@ -1885,6 +1966,13 @@ impl Screen for ManageProfilesScreen {
                     }
                 }
             }
+            Event::Mouse(mouse)
+                if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                    && self.remote_banner.can_pull()
+                    && self.remote_banner.contains(mouse.column, mouse.row) =>
+            {
+                return Ok(ScreenAction::PullFromRemote);
+            }
             Event::Mouse(mouse) => {
                 return Ok(self.handle_mouse_event(mouse, ctx.config));
             }
@@ -48,22 +48,44 @@
 //! }
 //! ```
 
+pub mod aliases;
+pub mod bootstrap;
 pub mod dotfile_selection;
+pub mod force_sync;
+pub mod health;
+pub mod history;
+pub mod import;
+pub mod machines;
 pub mod main_menu;
 pub mod manage_packages;
 pub mod manage_profiles;
+pub mod notifications;
 pub mod profile_selection;
+pub mod reauth;
+pub mod reconcile;
 pub mod screen_trait;
 pub mod settings;
+pub mod stats;
 pub mod storage_setup;
 pub mod sync_with_remote;
 
+pub use aliases::AliasesScreen;
+pub use bootstrap::BootstrapScreen;
 pub use dotfile_selection::DotfileSelectionScreen;
+pub use force_sync::ForceSyncScreen;
+pub use health::HealthScreen;
+pub use history::HistoryScreen;
+pub use import::ImportScreen;
+pub use machines::MachinesScreen;
 pub use main_menu::MainMenuScreen;
 pub use manage_packages::ManagePackagesScreen;
 pub use manage_profiles::ManageProfilesScreen;
+pub use notifications::NotificationsScreen;
 pub use profile_selection::ProfileSelectionScreen;
+pub use reauth::ReauthScreen;
+pub use reconcile::ReconcileScreen;
 pub use screen_trait::{ActionResult, RenderContext, Screen, ScreenAction, ScreenContext};
 pub use settings::SettingsScreen;
+pub use stats::StatsScreen;
 pub use storage_setup::StorageSetupScreen;
 pub use sync_with_remote::SyncWithRemoteScreen;
@@ -0,0 +1,550 @@
+//! Notifications screen controller.
+//!
+//! Gathers everything that needs attention into one list: tracked symlinks
+//! that were replaced or broken, commits waiting on the remote, a failed
+//! last sync, and the doctor's errors and available fixes. The main menu
+//! shows how many there are. Enter opens the screen that deals with a
+//! notice; replaced symlinks are restored or adopted from here.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::services::git_service::GitStatus;
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::doctor::{DoctorReport, ValidationStatus};
+use crate::utils::link_monitor::{self, LinkProblem};
+use crate::utils::sync_history::{self, SyncRecord};
+use crate::utils::text::format_time_ago;
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions,
+};
+use crate::widgets::ToastVariant;
+use anyhow::Result;
+use chrono::Utc;
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+
+/// How urgent a notice is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// What activating a notice does
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NoticeAction {
+    /// Open the screen that deals with it
+    Open(ScreenId),
+    /// Restore or adopt the symlink from here
+    Link(LinkProblem),
+}
+
+/// Something that needs attention
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Notice {
+    severity: Severity,
+    title: String,
+    detail: String,
+    action: NoticeAction,
+}
+
+/// Notifications screen controller.
+pub struct NotificationsScreen {
+    /// Tracked symlinks that were replaced or broken, loaded by `refresh`
+    links: Vec<LinkProblem>,
+    /// The last sync, when it failed
+    failed_sync: Option<SyncRecord>,
+    /// All notices, most urgent first
+    notices: Vec<Notice>,
+    list_state: ListState,
+    /// Clickable rows in the notice list (value = row index)
+    row_regions: MouseRegions<usize>,
+    /// Notice list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+}
+
+impl Default for NotificationsScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationsScreen {
+    /// Create a new notifications screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            links: Vec::new(),
+            failed_sync: None,
+            notices: Vec::new(),
+            list_state: ListState::default(),
+            row_regions: MouseRegions::new(),
+            list_area: None,
+        }
+    }
+
+    /// Reload what's read from disk: the tracked symlinks and the last sync
+    pub fn refresh(&mut self, config: &Config) {
+        if !config.is_repo_configured() {
+            self.links.clear();
+            self.failed_sync = None;
+            return;
+        }
+        self.links = link_monitor::check(&config.repo_path).unwrap_or_default();
+        self.failed_sync = sync_history::load()
+            .into_iter()
+            .next_back()
+            .filter(|record| !record.success);
+    }
+
+    /// Rebuild the notices from the latest git status and doctor report
+    pub fn update(&mut self, git_status: Option<&GitStatus>, report: Option<&DoctorReport>) {
+        self.notices = notices(&self.links, self.failed_sync.as_ref(), git_status, report);
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select((!self.notices.is_empty()).then(|| selected.min(self.notices.len() - 1)));
+    }
+
+    /// Number of notices, shown as a badge on the main menu
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.notices.len()
+    }
+
+    fn selected(&self) -> Option<&Notice> {
+        self.list_state.selected().and_then(|i| self.notices.get(i))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.notices.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        self.list_state.select(Some(next));
+    }
+
+    /// Act on the selected notice: open its screen
+    fn open_selected(&self) -> ScreenAction {
+        match self.selected().map(|notice| &notice.action) {
+            Some(NoticeAction::Open(screen)) => ScreenAction::Navigate(*screen),
+            _ => ScreenAction::None,
+        }
+    }
+
+    /// Restore the selected symlink, or with `adopt`, keep the file found
+    /// in its place
+    fn repair_selected(&mut self, config: &Config, adopt: bool) -> ScreenAction {
+        let Some(NoticeAction::Link(problem)) = self.selected().map(|notice| &notice.action) else {
+            return ScreenAction::None;
+        };
+        if (adopt && !problem.can_adopt()) || (!adopt && !problem.can_restore()) {
+            return ScreenAction::None;
+        }
+        let target = problem.target.to_string_lossy().to_string();
+        let result = link_monitor::repair(&config.repo_path, config.backup_enabled, &target, adopt);
+        self.refresh(config);
+        match result {
+            Ok(message) => ScreenAction::ShowToast {
+                message,
+                variant: ToastVariant::Success,
+            },
+            Err(e) => ScreenAction::ShowToast {
+                message: format!("{e:#}"),
+                variant: ToastVariant::Error,
+            },
+        }
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let t = theme();
+        let icons = Icons::from_config(config);
+        self.list_area = Some(area);
+
+        let items: Vec<ListItem> = self
+            .notices
+            .iter()
+            .map(|notice| {
+                let (icon, color) = match notice.severity {
+                    Severity::Error => (icons.error(), t.error),
+                    Severity::Warning => (icons.warning(), t.warning),
+                    Severity::Info => (icons.info(), t.primary),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{icon} "), Style::default().fg(color)),
+                    Span::styled(notice.title.clone(), t.text_style()),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Notifications ({}) ", self.notices.len()))
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for (row, index) in (self.list_state.offset()..self.notices.len()).enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                index,
+            );
+        }
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Details ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+
+        let lines = match self.selected() {
+            Some(notice) => {
+                let mut lines = vec![
+                    Line::from(Span::styled(notice.title.clone(), t.title_style())),
+                    Line::from(""),
+                ];
+                lines.extend(
+                    notice
+                        .detail
+                        .lines()
+                        .map(|line| Line::from(Span::styled(line.to_string(), t.text_style()))),
+                );
+                lines.push(Line::from(""));
+                let hint = match &notice.action {
+                    NoticeAction::Open(screen) => {
+                        format!("Press Enter to open {}.", screen_name(*screen))
+                    }
+                    NoticeAction::Link(problem) if problem.can_adopt() => {
+                        "Press f to relink it (the file found there is backed up), or a to keep that file by moving it into the repository.".to_string()
+                    }
+                    NoticeAction::Link(problem) if problem.can_restore() => {
+                        "Press f to relink it.".to_string()
+                    }
+                    NoticeAction::Link(problem) => problem.hint(),
+                };
+                lines.push(Line::from(Span::styled(hint, t.muted_style())));
+                lines
+            }
+            None => vec![Line::from(Span::styled(
+                "Nothing needs your attention.",
+                Style::default().fg(t.success),
+            ))],
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    self.list_state.select(Some(index));
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::ScrollUp if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(-3);
+            }
+            MouseEventKind::ScrollDown if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+/// Name of a screen a notice opens, for the hint
+fn screen_name(screen: ScreenId) -> &'static str {
+    match screen {
+        ScreenId::SyncWithRemote => "Sync with Remote",
+        ScreenId::History => "Sync History",
+        ScreenId::Health => "Health Check",
+        _ => "it",
+    }
+}
+
+/// Everything that needs attention, most urgent first
+fn notices(
+    links: &[LinkProblem],
+    failed_sync: Option<&SyncRecord>,
+    git_status: Option<&GitStatus>,
+    report: Option<&DoctorReport>,
+) -> Vec<Notice> {
+    let mut notices: Vec<Notice> = links
+        .iter()
+        .map(|problem| Notice {
+            severity: Severity::Warning,
+            title: problem.describe(),
+            detail: format!(
+                "It should link to {}.",
+                crate::utils::path::format_path_for_display(&problem.source)
+            ),
+            action: NoticeAction::Link(problem.clone()),
+        })
+        .collect();
+
+    if let Some(record) = failed_sync {
+        notices.push(Notice {
+            severity: Severity::Error,
+            title: "The last sync failed".to_string(),
+            detail: format!(
+                "{} from the {}, {}.\n{}",
+                record.kind.label(),
+                record.origin.label(),
+                format_time_ago(record.timestamp, Utc::now()),
+                record.error.as_deref().unwrap_or("No error was recorded.")
+            ),
+            action: NoticeAction::Open(ScreenId::History),
+        });
+    }
+
+    if let Some(status) = git_status {
+        if status.behind > 0 {
+            notices.push(Notice {
+                severity: Severity::Warning,
+                title: format!("The remote has {} new commit(s)", status.behind),
+                detail: "Pull them before editing, so your changes don't conflict.".to_string(),
+                action: NoticeAction::Open(ScreenId::SyncWithRemote),
+            });
+        }
+        if status.ahead > 0 {
+            notices.push(Notice {
+                severity: Severity::Info,
+                title: format!("{} commit(s) not pushed", status.ahead),
+                detail: "Sync to push them to the remote.".to_string(),
+                action: NoticeAction::Open(ScreenId::SyncWithRemote),
+            });
+        }
+        if let Some(error) = &status.fetch_error {
+            notices.push(Notice {
+                severity: Severity::Warning,
+                title: "Couldn't check the remote".to_string(),
+                detail: error.clone(),
+                action: NoticeAction::Open(ScreenId::SyncWithRemote),
+            });
+        }
+    }
+
+    // Symlink checks are covered by the notices above, with more detail
+    for result in report
+        .iter()
+        .flat_map(|report| &report.results)
+        .filter(|result| result.category != "Symlinks")
+    {
+        let severity = match (&result.status, &result.fix_action) {
+            (ValidationStatus::Error, _) => Severity::Error,
+            (ValidationStatus::Warning, Some(_)) => Severity::Warning,
+            _ => continue,
+        };
+        let mut detail = format!("{} check: {}", result.category, result.check_name);
+        if let Some(fix) = &result.fix_action {
+            detail.push_str(&format!("\nFix available: {fix}"));
+        }
+        for line in result.details.iter().flatten() {
+            detail.push_str(&format!("\n  {line}"));
+        }
+        notices.push(Notice {
+            severity,
+            title: result.message.clone(),
+            detail,
+            action: NoticeAction::Open(ScreenId::Health),
+        });
+    }
+
+    notices.sort_by_key(|notice| notice.severity);
+    notices
+}
+
+impl Screen for NotificationsScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Notifications",
+            "Everything that needs your attention: broken symlinks, remote changes, failed syncs and health issues.",
+        )?;
+
+        let panes = create_split_layout(content_chunk, &[50, 50]);
+        self.render_list(frame, panes[0], ctx.config);
+        self.render_details(frame, panes[1]);
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let mut footer_text = format!("{}: Navigate", ctx.config.keymap.navigation_display());
+        match self.selected().map(|notice| &notice.action) {
+            Some(NoticeAction::Open(_)) => {
+                footer_text.push_str(&format!(" | {}: Open", k(Action::Confirm)));
+            }
+            Some(NoticeAction::Link(problem)) => {
+                if problem.can_restore() {
+                    footer_text.push_str(&format!(" | {}: Relink", k(Action::Relink)));
+                }
+                if problem.can_adopt() {
+                    footer_text.push_str(&format!(" | {}: Adopt", k(Action::Adopt)));
+                }
+            }
+            None => {}
+        }
+        footer_text.push_str(&format!(
+            " | {}: Reload | {}: Back",
+            k(Action::Refresh),
+            k(Action::Cancel)
+        ));
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let is_link = matches!(
+                    self.selected().map(|notice| &notice.action),
+                    Some(NoticeAction::Link(_))
+                );
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    Action::Confirm => return Ok(self.open_selected()),
+                    Action::Relink if is_link => return Ok(self.repair_selected(ctx.config, false)),
+                    Action::Adopt if is_link => return Ok(self.repair_selected(ctx.config, true)),
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::GoToTop => self.move_selection(isize::MIN / 2),
+                    Action::GoToEnd => self.move_selection(isize::MAX / 2),
+                    Action::Refresh => self.refresh(ctx.config),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => return Ok(self.handle_mouse(mouse)),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        self.refresh(ctx.config);
+        self.list_state.select(Some(0));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::link_monitor::LinkProblemKind;
+    use crate::utils::sync_history::{SyncKind, SyncOrigin};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_notices_most_urgent_first() {
+        let links = vec![LinkProblem {
+            target: PathBuf::from("/home/me/.zshrc"),
+            source: PathBuf::from("/repo/Personal/.zshrc"),
+            kind: LinkProblemKind::Replaced,
+        }];
+        let mut record = SyncRecord::start(SyncKind::Sync, SyncOrigin::Cli, "Personal");
+        record.finish(false, "Error: authentication failed");
+        let status = GitStatus {
+            behind: 2,
+            ahead: 1,
+            ..GitStatus::default()
+        };
+
+        let notices = notices(&links, Some(&record), Some(&status), None);
+        let titles: Vec<&str> = notices.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles[0], "The last sync failed");
+        assert!(notices[0].detail.contains("authentication failed"));
+        assert_eq!(titles[3], "1 commit(s) not pushed");
+        assert_eq!(notices.len(), 4);
+        assert!(matches!(notices[1].action, NoticeAction::Link(_)));
+
+        // Nothing to report
+        assert!(super::notices(&[], None, Some(&GitStatus::default()), None).is_empty());
+    }
+
+    #[test]
+    fn test_repair_keys_go_through_the_keymap() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            repo_path: temp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let ctx = ScreenContext::new(&config, temp.path());
+        let mut screen = NotificationsScreen::new();
+        screen.links = vec![LinkProblem {
+            target: temp.path().join(".zshrc"),
+            source: temp.path().join("Personal/.zshrc"),
+            kind: LinkProblemKind::Replaced,
+        }];
+        screen.notices = notices(&screen.links, None, None, None);
+        screen.list_state.select(Some(0));
+        let press = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+
+        // Ctrl+F isn't the relink key
+        assert!(matches!(
+            screen
+                .handle_event(press(KeyCode::Char('f'), KeyModifiers::CONTROL), &ctx)
+                .unwrap(),
+            ScreenAction::None
+        ));
+        // The bound key repairs (and fails here, with nothing tracked)
+        assert!(matches!(
+            screen
+                .handle_event(press(KeyCode::Char('f'), KeyModifiers::NONE), &ctx)
+                .unwrap(),
+            ScreenAction::ShowToast { .. }
+        ));
+    }
+}
@@ -9,7 +9,7 @@ use crate::screens::ActionResult;
 use crate::services::ProfileService;
 use crate::styles::theme;
 use crate::ui::{ProfileSelectionState, Screen as ScreenId};
-use crate::utils::MouseRegions;
+use crate::utils::{DoubleClick, MouseRegions};
 use crate::widgets::{DialogVariant, TextInputWidget, TextInputWidgetExt};
 use anyhow::Result;
 use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
@@ -34,6 +34,8 @@ pub struct ProfileSelectionScreen {
     state: ProfileSelectionState,
     /// Clickable regions for list items
     mouse_regions: MouseRegions<usize>,
+    /// Double-clicks on list items activate them
+    row_clicks: DoubleClick<usize>,
     /// List pane area for scroll hit-testing
     list_area: Option<Rect>,
 }
@@ -45,6 +47,7 @@ impl ProfileSelectionScreen {
         Self {
             state: ProfileSelectionState::default(),
             mouse_regions: MouseRegions::new(),
+            row_clicks: DoubleClick::new(),
             list_area: None,
         }
     }
@@ -316,8 +319,13 @@ impl ProfileSelectionScreen {
                 // Reset screen state
                 self.reset();
 
-                // Navigate to main menu
-                Ok(ActionResult::Navigate(ScreenId::MainMenu))
+                // Offer the profile's bootstrap scripts on its first
+                // activation here, otherwise go to the main menu
+                if crate::utils::bootstrap::pending(&config.repo_path, profile_name).is_empty() {
+                    Ok(ActionResult::Navigate(ScreenId::MainMenu))
+                } else {
+                    Ok(ActionResult::Navigate(ScreenId::Bootstrap))
+                }
             }
             Err(e) => {
                 error!("Failed to activate profile '{}': {}", profile_name, e);
@@ -379,6 +387,9 @@ impl Screen for ProfileSelectionScreen {
                     MouseEventKind::Down(MouseButton::Left) => {
                         if let Some(&idx) = self.mouse_regions.hit_test(mouse.column, mouse.row) {
                             self.state.list_state.select(Some(idx));
+                            if !self.row_clicks.click(idx) {
+                                return Ok(ScreenAction::None);
+                            }
                             if idx == self.state.profiles.len() {
                                 self.state.show_create_popup = true;
                                 self.state.create_name_input.clear();
@@ -0,0 +1,339 @@
+//! Re-authenticate screen controller.
+//!
+//! Shown when GitHub rejects the stored token or the token is about to
+//! expire. Replaces the token in place (config and `origin` URL) so syncing
+//! keeps working without going through storage setup again.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::services::{ReauthReason, TokenService};
+use crate::styles::theme;
+use crate::ui::Screen as ScreenId;
+use crate::utils::{center_popup, create_standard_layout, focused_border_style, TextInput};
+use crate::widgets::{TextInputWidget, TextInputWidgetExt};
+use anyhow::Result;
+use chrono::Utc;
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use tracing::info;
+
+/// Re-authenticate screen controller.
+pub struct ReauthScreen {
+    /// Why a new token is needed
+    reason: ReauthReason,
+    /// Screen to go back to when done
+    return_to: ScreenId,
+    token_input: TextInput,
+    /// Why the last token was refused
+    error: Option<String>,
+    /// Receiver for an in-flight validation and save
+    verify_rx: Option<Receiver<Result<(), String>>>,
+    /// Spinner animation counter while verifying
+    spinner_tick: usize,
+    /// Clickable "Save token" button
+    save_area: Option<Rect>,
+    /// Clickable "Later" button
+    later_area: Option<Rect>,
+}
+
+impl Default for ReauthScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReauthScreen {
+    /// Create a new re-authenticate screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            reason: ReauthReason::Rejected,
+            return_to: ScreenId::MainMenu,
+            token_input: TextInput::new(),
+            error: None,
+            verify_rx: None,
+            spinner_tick: 0,
+            save_area: None,
+            later_area: None,
+        }
+    }
+
+    /// Set why a new token is needed and where to go back to afterwards.
+    pub fn set_reason(&mut self, reason: ReauthReason, return_to: ScreenId) {
+        self.reason = reason;
+        self.return_to = return_to;
+        self.token_input.clear();
+        self.error = None;
+    }
+
+    /// Screen to go back to when done
+    #[must_use]
+    pub fn return_to(&self) -> ScreenId {
+        self.return_to
+    }
+
+    /// Whether a token is being validated
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.verify_rx.is_some()
+    }
+
+    /// Validate the typed token and save it in the background.
+    fn submit(&mut self, config: &Config, config_path: &Path) -> ScreenAction {
+        if self.is_busy() {
+            return ScreenAction::None;
+        }
+        let token = self.token_input.text_trimmed().to_string();
+        if token.is_empty() {
+            self.error = Some("Paste a new token first".to_string());
+            return ScreenAction::Refresh;
+        }
+        info!("Validating replacement GitHub token");
+        self.error = None;
+        let mut config = config.clone();
+        let config_path = config_path.to_path_buf();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = TokenService::replace_token(&mut config, &token)
+                .and_then(|()| config.save(&config_path))
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.verify_rx = Some(rx);
+        ScreenAction::Refresh
+    }
+
+    /// Poll the background validation. Returns `ScreenAction::Refresh` while it runs.
+    pub fn tick(&mut self) -> ScreenAction {
+        let Some(rx) = &self.verify_rx else {
+            return ScreenAction::None;
+        };
+        self.spinner_tick = self.spinner_tick.wrapping_add(1);
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+            Err(TryRecvError::Disconnected) => {
+                Err("Validating the token stopped unexpectedly".to_string())
+            }
+        };
+        self.verify_rx = None;
+        match result {
+            Ok(()) => {
+                self.token_input.clear();
+                ScreenAction::TokenReplaced
+            }
+            Err(e) => {
+                self.error = Some(e);
+                ScreenAction::Refresh
+            }
+        }
+    }
+
+    fn render_body(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let t = theme();
+        let icons = Icons::from_config(config);
+        let repo = config
+            .github
+            .as_ref()
+            .map(|gh| format!("{}/{}", gh.owner, gh.repo))
+            .unwrap_or_default();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" GitHub Token ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(true))
+            .border_style(focused_border_style())
+            .padding(crate::styles::padding(Padding::new(2, 2, 1, 1)))
+            .style(t.background_style());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(6),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("{} {}", icons.warning(), self.reason.message(Utc::now())),
+                Style::default().fg(t.warning),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Repository: ", t.muted_style()),
+                Span::styled(repo, t.text_style()),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Create a new token at https://github.com/settings/tokens (fine-grained: Contents read and write on the repository; classic: the 'repo' scope) and paste it below.",
+                t.text_style(),
+            )),
+        ];
+        if TokenService::token_from_env() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "DOTSTATE_GITHUB_TOKEN is set and takes precedence over the saved token. Update it in your environment too.",
+                Style::default().fg(t.warning),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[0]);
+
+        let widget = TextInputWidget::new(&self.token_input)
+            .title("New token")
+            .placeholder("ghp_... or github_pat_...")
+            .masked(true)
+            .focused(!self.is_busy());
+        frame.render_text_input_widget(widget, chunks[1]);
+
+        let status = if self.is_busy() {
+            let dots = ".".repeat(self.spinner_tick / 2 % 4);
+            Line::from(Span::styled(
+                format!("Checking the token with GitHub{dots}"),
+                Style::default().fg(t.warning),
+            ))
+        } else if let Some(error) = &self.error {
+            Line::from(Span::styled(error.clone(), Style::default().fg(t.error)))
+        } else {
+            Line::from("")
+        };
+        frame.render_widget(Paragraph::new(status), chunks[2]);
+
+        // Buttons
+        let save_label = " Save token ";
+        let later_label = " Later ";
+        let buttons = chunks[3];
+        let save = Rect::new(
+            buttons.x,
+            buttons.y + 1,
+            (save_label.len() as u16).min(buttons.width),
+            1.min(buttons.height.saturating_sub(1)),
+        );
+        let later = Rect::new(
+            save.x + save.width + 2,
+            save.y,
+            (later_label.len() as u16).min(buttons.width.saturating_sub(save.width + 2)),
+            save.height,
+        );
+        frame.render_widget(
+            Paragraph::new(Span::styled(save_label, t.highlight_style())),
+            save,
+        );
+        frame.render_widget(
+            Paragraph::new(Span::styled(later_label, t.muted_style())),
+            later,
+        );
+        self.save_area = Some(save);
+        self.later_area = Some(later);
+    }
+}
+
+impl Screen for ReauthScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Re-authenticate",
+            "Replace the GitHub token used for syncing. Nothing else in your setup changes.",
+        )?;
+
+        let body = center_popup(content_chunk, 70, 80);
+        self.render_body(frame, body, ctx.config);
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = if self.is_busy() {
+            "Checking token...".to_string()
+        } else {
+            format!(
+                "{}: Save token | {}: Later",
+                k(Action::Confirm),
+                k(Action::Cancel)
+            )
+        };
+        Footer::render(frame, footer_chunk, &footer_text)?;
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        if self.is_busy() {
+            return Ok(ScreenAction::None);
+        }
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                // Plain characters are always typed, so bindings like 'q' don't interfere
+                if let KeyCode::Char(c) = key.code {
+                    if !key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER)
+                    {
+                        self.token_input.insert_char(c);
+                        self.error = None;
+                        return Ok(ScreenAction::None);
+                    }
+                }
+                let action = ctx.config.keymap.get_action(key.code, key.modifiers);
+                match action {
+                    Some(Action::Cancel) => return Ok(ScreenAction::Navigate(self.return_to)),
+                    Some(Action::Confirm) => return Ok(self.submit(ctx.config, ctx.config_path)),
+                    _ => {
+                        self.token_input
+                            .handle_key_with_action(key.code, key.modifiers, action);
+                    }
+                }
+            }
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                let pos = Position::new(mouse.column, mouse.row);
+                if self.save_area.is_some_and(|a| a.contains(pos)) {
+                    return Ok(self.submit(ctx.config, ctx.config_path));
+                }
+                if self.later_area.is_some_and(|a| a.contains(pos)) {
+                    return Ok(ScreenAction::Navigate(self.return_to));
+                }
+            }
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn is_input_focused(&self) -> bool {
+        true
+    }
+
+    fn on_enter(&mut self, _ctx: &ScreenContext) -> Result<()> {
+        self.error = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit_requires_token() {
+        let config = Config::default();
+        let mut screen = ReauthScreen::new();
+        screen.set_reason(ReauthReason::Rejected, ScreenId::SyncWithRemote);
+
+        screen.submit(&config, Path::new("/nonexistent/config.toml"));
+        assert!(!screen.is_busy());
+        assert!(screen.error.is_some());
+        assert_eq!(screen.return_to(), ScreenId::SyncWithRemote);
+    }
+}
@@ -0,0 +1,811 @@
+//! Reconcile screen controller.
+//!
+//! A wizard for a local repository and remote that have both moved on: it
+//! goes through every file that differs, shows the local and remote
+//! versions and their diff, and lets each file keep one side or merge both.
+//! The result is committed as a single merge of both sides and pushed, so
+//! unlike a force sync neither side's history is thrown away.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::components::Popup;
+use crate::config::Config;
+use crate::git::DivergedFile;
+use crate::icons::Icons;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::services::git_service::{GitService, ReconcilePreview, ReconciledFile, SyncResult};
+use crate::services::TokenService;
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
+use crate::ui::Screen as ScreenId;
+use crate::utils::reconcile::{self, Changed, Choice, Merged};
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions,
+};
+use crate::widgets::ToastVariant;
+use anyhow::Result;
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use tracing::info;
+
+/// Choices in the order left/right steps through them
+const CHOICES: [Choice; 3] = [Choice::KeepLocal, Choice::Merge, Choice::KeepRemote];
+
+/// What the right pane shows for the selected file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    /// Diff from the local version to the remote one
+    Diff,
+    Local,
+    Remote,
+    /// Content the current choice leaves
+    Result,
+}
+
+impl View {
+    fn next(self) -> Self {
+        match self {
+            View::Diff => View::Local,
+            View::Local => View::Remote,
+            View::Remote => View::Result,
+            View::Result => View::Diff,
+        }
+    }
+
+    fn prev(self) -> Self {
+        match self {
+            View::Diff => View::Result,
+            View::Local => View::Diff,
+            View::Remote => View::Local,
+            View::Result => View::Remote,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            View::Diff => " Diff: local → remote ",
+            View::Local => " Local ",
+            View::Remote => " Remote ",
+            View::Result => " Result ",
+        }
+    }
+}
+
+/// A diverged file and what to keep of it
+struct Entry {
+    file: DivergedFile,
+    /// Both versions merged, if they can be
+    merged: Option<Merged>,
+    choice: Choice,
+}
+
+impl Entry {
+    fn new(file: DivergedFile) -> Self {
+        let merged = reconcile::merge(&file).unwrap_or_default();
+        let choice = reconcile::default_choice(&file, merged.as_ref());
+        Self {
+            file,
+            merged,
+            choice,
+        }
+    }
+
+    fn resolved(&self) -> Option<&[u8]> {
+        reconcile::resolve(&self.file, self.choice, self.merged.as_ref())
+    }
+
+    /// Whether the merge choice leaves conflict markers in the file
+    fn has_markers(&self) -> bool {
+        self.choice == Choice::Merge && self.merged.as_ref().is_some_and(|m| m.conflicts)
+    }
+}
+
+/// Reconcile screen controller.
+pub struct ReconcileScreen {
+    /// Branch, counts and errors from the last fetch
+    preview: Option<ReconcilePreview>,
+    /// Files from the last fetch, with their choices
+    entries: Vec<Entry>,
+    /// Receiver for an in-flight fetch and compare
+    preview_rx: Option<Receiver<ReconcilePreview>>,
+    /// Receiver for the in-flight commit and push
+    run_rx: Option<Receiver<SyncResult>>,
+    list_state: ListState,
+    view: View,
+    details_scroll: u16,
+    /// Whether the commit confirmation popup is open
+    confirming: bool,
+    /// Clickable file rows (value = index into `entries`)
+    row_regions: MouseRegions<usize>,
+    /// File list area (for scroll hit-testing)
+    list_area: Option<Rect>,
+    /// Confirmation popup area (clicks outside it cancel)
+    confirm_area: Option<Rect>,
+    /// Spinner animation counter while fetching or pushing
+    spinner_tick: usize,
+}
+
+impl Default for ReconcileScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReconcileScreen {
+    /// Create a new reconcile screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            preview: None,
+            entries: Vec::new(),
+            preview_rx: None,
+            run_rx: None,
+            list_state: ListState::default(),
+            view: View::Diff,
+            details_scroll: 0,
+            confirming: false,
+            row_regions: MouseRegions::new(),
+            list_area: None,
+            confirm_area: None,
+            spinner_tick: 0,
+        }
+    }
+
+    /// Whether a fetch or the final push is currently running.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.preview_rx.is_some() || self.run_rx.is_some()
+    }
+
+    /// Fetch the remote and compare it with the working tree in the background.
+    fn load_preview(&mut self, config: &Config) {
+        if self.is_busy() {
+            return;
+        }
+        let config = config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(GitService::reconcile_preview(&config));
+        });
+        self.preview_rx = Some(rx);
+        self.set_preview(None);
+    }
+
+    fn set_preview(&mut self, preview: Option<ReconcilePreview>) {
+        self.entries = preview
+            .as_ref()
+            .map(|p| p.files.iter().cloned().map(Entry::new).collect())
+            .unwrap_or_default();
+        self.preview = preview;
+        self.list_state
+            .select((!self.entries.is_empty()).then_some(0));
+        self.details_scroll = 0;
+    }
+
+    /// Poll background work. Returns `ScreenAction::Refresh` while work is in progress.
+    pub fn tick(&mut self, config: &Config) -> ScreenAction {
+        if let Some(rx) = &self.preview_rx {
+            self.spinner_tick = self.spinner_tick.wrapping_add(1);
+            let preview = match rx.try_recv() {
+                Ok(preview) => preview,
+                Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+                Err(TryRecvError::Disconnected) => ReconcilePreview {
+                    error: Some("Comparing with the remote stopped unexpectedly".to_string()),
+                    ..ReconcilePreview::default()
+                },
+            };
+            self.preview_rx = None;
+            self.set_preview(Some(preview));
+            return ScreenAction::Refresh;
+        }
+
+        if let Some(rx) = &self.run_rx {
+            self.spinner_tick = self.spinner_tick.wrapping_add(1);
+            let result = match rx.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+                Err(TryRecvError::Disconnected) => SyncResult {
+                    success: false,
+                    message: "Reconciling stopped unexpectedly".to_string(),
+                    pulled_count: None,
+                },
+            };
+            self.run_rx = None;
+
+            // Show what still differs, normally nothing
+            self.load_preview(config);
+            if result.success {
+                return ScreenAction::ShowToast {
+                    message: "Reconciled and pushed".to_string(),
+                    variant: ToastVariant::Success,
+                };
+            }
+            if TokenService::uses_token(config) && TokenService::is_auth_failure(&result.message) {
+                return ScreenAction::Reauthenticate;
+            }
+            return ScreenAction::ShowMessage {
+                title: "Reconcile Failed".to_string(),
+                content: result.message,
+            };
+        }
+
+        ScreenAction::None
+    }
+
+    fn selected(&self) -> Option<&Entry> {
+        self.list_state.selected().and_then(|i| self.entries.get(i))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = if delta < 0 {
+            current.saturating_sub(delta.unsigned_abs())
+        } else {
+            current.saturating_add(delta.unsigned_abs()).min(len - 1)
+        };
+        if next != current {
+            self.details_scroll = 0;
+        }
+        self.list_state.select(Some(next));
+    }
+
+    /// Step the selected file's choice left or right, skipping a merge
+    /// that isn't possible
+    fn step_choice(&mut self, right: bool) {
+        let Some(entry) = self
+            .list_state
+            .selected()
+            .and_then(|i| self.entries.get_mut(i))
+        else {
+            return;
+        };
+        let mut index = CHOICES.iter().position(|c| *c == entry.choice).unwrap_or(0);
+        loop {
+            index = if right {
+                index + 1
+            } else {
+                match index.checked_sub(1) {
+                    Some(index) => index,
+                    None => return,
+                }
+            };
+            let Some(&choice) = CHOICES.get(index) else {
+                return;
+            };
+            if choice != Choice::Merge || entry.merged.is_some() {
+                entry.choice = choice;
+                return;
+            }
+        }
+    }
+
+    /// The files with their choices and resulting content, for committing
+    fn reconciled_files(&self) -> Vec<ReconciledFile> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.file.path.clone(),
+                    entry.choice,
+                    entry.resolved().map(<[u8]>::to_vec),
+                )
+            })
+            .collect()
+    }
+
+    /// Ask for confirmation before committing.
+    fn open_confirm(&mut self, config: &Config) -> ScreenAction {
+        if config.read_only {
+            return ScreenAction::pull_only_blocked();
+        }
+        if self.is_busy() || self.entries.is_empty() {
+            return ScreenAction::None;
+        }
+        self.confirming = true;
+        ScreenAction::None
+    }
+
+    /// Commit the choices and push in the background.
+    fn start_reconcile(&mut self, config: &Config) {
+        self.confirming = false;
+        let Some(remote_commit) = self.preview.as_ref().and_then(|p| p.remote_commit) else {
+            return;
+        };
+        if self.is_busy() {
+            return;
+        }
+        info!("Reconciling {} file(s) with the remote", self.entries.len());
+        let files = self.reconciled_files();
+        let config = config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(GitService::reconcile(&config, remote_commit, &files));
+        });
+        self.run_rx = Some(rx);
+    }
+
+    fn choice_marker(choice: Choice) -> &'static str {
+        match choice {
+            Choice::KeepLocal => "[L]",
+            Choice::KeepRemote => "[R]",
+            Choice::Merge => "[M]",
+        }
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        self.list_area = Some(area);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let color = if entry.has_markers() {
+                    t.warning
+                } else {
+                    t.success
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", Self::choice_marker(entry.choice)),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(entry.file.path.clone(), t.text_style()),
+                    Span::styled(
+                        match reconcile::changed(&entry.file) {
+                            Changed::Both => "  both",
+                            Changed::Local => "  local",
+                            Changed::Remote => "  remote",
+                        },
+                        t.muted_style(),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Files ({}) ", self.entries.len()))
+                    .title_alignment(Alignment::Center)
+                    .border_type(t.border_type(true))
+                    .border_style(focused_border_style())
+                    .style(t.background_style()),
+            )
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        self.row_regions.clear();
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        for (row, index) in (self.list_state.offset()..self.entries.len()).enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            self.row_regions.add(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                index,
+            );
+        }
+    }
+
+    /// Lines showing `content`, or why there is nothing to show
+    fn content_lines(content: Option<&[u8]>, missing: &str) -> Vec<Line<'static>> {
+        let t = theme();
+        match content {
+            None => vec![Line::from(Span::styled(
+                missing.to_string(),
+                t.muted_style(),
+            ))],
+            Some(bytes) if bytes.contains(&0) => {
+                vec![Line::from(Span::styled("(binary file)", t.muted_style()))]
+            }
+            Some(bytes) => String::from_utf8_lossy(bytes)
+                .lines()
+                .map(|line| Line::from(Span::styled(line.to_string(), t.text_style())))
+                .collect(),
+        }
+    }
+
+    /// Lines describing `entry` in the right pane for `view`
+    fn detail_lines(entry: &Entry, view: View) -> Vec<Line<'static>> {
+        let t = theme();
+        let file = &entry.file;
+        let mut lines = vec![
+            Line::from(Span::styled(file.path.clone(), t.title_style())),
+            Line::from(vec![
+                Span::styled(
+                    format!("{}. ", reconcile::changed(file).label()),
+                    t.muted_style(),
+                ),
+                Span::styled("Choice: ", t.muted_style()),
+                Span::styled(entry.choice.label(), t.emphasis_style()),
+            ]),
+        ];
+        if entry.has_markers() {
+            lines.push(Line::from(Span::styled(
+                "Some lines conflict and are kept between <<<<<<< and >>>>>>> markers; edit the file after syncing.",
+                Style::default().fg(t.warning),
+            )));
+        } else if entry.merged.is_none() {
+            lines.push(Line::from(Span::styled(
+                "Can't be merged: it's deleted on one side or isn't text.",
+                t.muted_style(),
+            )));
+        }
+        lines.push(Line::from(""));
+
+        match view {
+            View::Diff => {
+                let diff = if file.binary {
+                    String::new()
+                } else {
+                    reconcile::unified_diff(
+                        &file.path,
+                        file.local.as_deref(),
+                        file.remote.as_deref(),
+                    )
+                };
+                if diff.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        "No text diff to show.",
+                        t.muted_style(),
+                    )));
+                }
+                // Skip the file header lines, the path is shown above
+                for line in diff.lines().skip_while(|l| !l.starts_with("@@")) {
+                    let style = if line.starts_with('+') {
+                        Style::default().fg(t.success)
+                    } else if line.starts_with('-') {
+                        Style::default().fg(t.error)
+                    } else if line.starts_with("@@") {
+                        t.muted_style()
+                    } else {
+                        t.text_style()
+                    };
+                    lines.push(Line::from(Span::styled(line.to_string(), style)));
+                }
+            }
+            View::Local => lines.extend(Self::content_lines(
+                file.local.as_deref(),
+                "(deleted on this machine)",
+            )),
+            View::Remote => lines.extend(Self::content_lines(
+                file.remote.as_deref(),
+                "(deleted on the remote)",
+            )),
+            View::Result => lines.extend(Self::content_lines(
+                entry.resolved(),
+                "(the file is deleted)",
+            )),
+        }
+        lines
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        let t = theme();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.view.title())
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(false))
+            .border_style(unfocused_border_style())
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+
+        let lines = match (&self.preview, self.selected()) {
+            (None, _) => {
+                let dots = ".".repeat(self.spinner_tick / 2 % 4);
+                vec![Line::from(Span::styled(
+                    format!("Fetching remote{dots}"),
+                    Style::default().fg(t.warning),
+                ))]
+            }
+            (Some(preview), _) if preview.error.is_some() => {
+                let mut lines = vec![Line::from(Span::styled(
+                    "Can't compare with the remote:",
+                    Style::default().fg(t.error),
+                ))];
+                for line in preview.error.iter().flat_map(|e| e.lines()) {
+                    lines.push(Line::from(Span::styled(line.to_string(), t.text_style())));
+                }
+                lines
+            }
+            (Some(_), Some(entry)) => Self::detail_lines(entry, self.view),
+            (Some(_), None) => vec![Line::from(Span::styled(
+                "Nothing differs from the remote.",
+                Style::default().fg(t.success),
+            ))],
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((self.details_scroll, 0))
+                .block(block),
+            area,
+        );
+    }
+
+    fn render_confirm(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        if !self.confirming {
+            self.confirm_area = None;
+            return;
+        }
+        let t = theme();
+        let icons = Icons::from_config(config);
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Commit & Push | {}: Cancel",
+            k(Action::Confirm),
+            k(Action::Cancel)
+        );
+        let Some(result) = Popup::new()
+            .width(60)
+            .height(40)
+            .min_height(12)
+            .min_width(50)
+            .title("Commit Reconciled Files")
+            .dim_background(true)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return;
+        };
+        self.confirm_area = Some(result.content_area);
+
+        let count = |choice| self.entries.iter().filter(|e| e.choice == choice).count();
+        let mut text = vec![
+            Line::from(Span::styled(
+                format!(
+                    "Commit {} file(s) as one merge of both sides and push it?",
+                    self.entries.len()
+                ),
+                t.text_style(),
+            )),
+            Line::from(""),
+        ];
+        for choice in CHOICES {
+            text.push(Line::from(vec![
+                Span::styled(format!("{}: ", choice.label()), t.muted_style()),
+                Span::styled(count(choice).to_string(), t.text_style()),
+            ]));
+        }
+        let markers = self.entries.iter().filter(|e| e.has_markers()).count();
+        if markers > 0 {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(
+                format!(
+                    "{} {markers} merged file(s) still have conflict markers.",
+                    icons.warning()
+                ),
+                Style::default().fg(t.warning),
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: true }),
+            result.content_area,
+        );
+    }
+
+    fn handle_confirm_event(&mut self, event: Event, ctx: &ScreenContext) -> ScreenAction {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                match ctx.config.keymap.get_action(key.code, key.modifiers) {
+                    Some(Action::Confirm | Action::Yes) => {
+                        self.start_reconcile(ctx.config);
+                        return ScreenAction::Refresh;
+                    }
+                    Some(Action::Cancel | Action::No | Action::Quit) => self.confirming = false,
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                let pos = Position::new(mouse.column, mouse.row);
+                if !self.confirm_area.is_some_and(|a| a.contains(pos)) {
+                    self.confirming = false;
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> ScreenAction {
+        let pos = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&index) = self.row_regions.hit_test(mouse.column, mouse.row) {
+                    if self.list_state.selected() != Some(index) {
+                        self.details_scroll = 0;
+                    }
+                    self.list_state.select(Some(index));
+                    return ScreenAction::Refresh;
+                }
+            }
+            MouseEventKind::ScrollUp if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(-3);
+            }
+            MouseEventKind::ScrollDown if self.list_area.is_some_and(|a| a.contains(pos)) => {
+                self.move_selection(3);
+            }
+            MouseEventKind::ScrollUp => {
+                self.details_scroll = self.details_scroll.saturating_sub(3);
+            }
+            MouseEventKind::ScrollDown => {
+                self.details_scroll = self.details_scroll.saturating_add(3);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+impl Screen for ReconcileScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Reconcile",
+            "Choose what to keep of each file that differs from the remote. The result is committed as one merge and pushed.",
+        )?;
+
+        let panes = create_split_layout(content_chunk, &[40, 60]);
+        self.render_list(frame, panes[0]);
+        self.render_details(frame, panes[1]);
+        self.render_confirm(frame, area, ctx.config);
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = if self.run_rx.is_some() {
+            let dots = ".".repeat(self.spinner_tick / 2 % 4);
+            format!("Committing and pushing{dots}")
+        } else if self.confirming {
+            format!(
+                "{}: Commit & Push | {}: Cancel",
+                k(Action::Confirm),
+                k(Action::Cancel)
+            )
+        } else {
+            format!(
+                "{}: Navigate | {}/{}: Choose Version | {}: Switch View | {}/{}: Scroll | {}: Commit & Push | {}: Re-fetch | {}: Back",
+                ctx.config.keymap.navigation_display(),
+                k(Action::MoveLeft),
+                k(Action::MoveRight),
+                k(Action::NextTab),
+                k(Action::ScrollUp),
+                k(Action::ScrollDown),
+                k(Action::Confirm),
+                k(Action::Refresh),
+                k(Action::Cancel),
+            )
+        };
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        // Nothing to do until the push finishes
+        if self.run_rx.is_some() {
+            return Ok(ScreenAction::None);
+        }
+        if self.confirming {
+            return Ok(self.handle_confirm_event(event, ctx));
+        }
+
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(action) = ctx.config.keymap.get_action(key.code, key.modifiers) else {
+                    return Ok(ScreenAction::None);
+                };
+                match action {
+                    Action::Cancel | Action::Quit => {
+                        return Ok(ScreenAction::Navigate(ScreenId::ForceSync));
+                    }
+                    Action::MoveUp => self.move_selection(-1),
+                    Action::MoveDown => self.move_selection(1),
+                    Action::PageUp => self.move_selection(-10),
+                    Action::PageDown => self.move_selection(10),
+                    Action::MoveLeft => self.step_choice(false),
+                    Action::MoveRight => self.step_choice(true),
+                    Action::NextTab => {
+                        self.view = self.view.next();
+                        self.details_scroll = 0;
+                    }
+                    Action::PrevTab => {
+                        self.view = self.view.prev();
+                        self.details_scroll = 0;
+                    }
+                    Action::ScrollUp => {
+                        self.details_scroll = self.details_scroll.saturating_sub(1);
+                    }
+                    Action::ScrollDown => {
+                        self.details_scroll = self.details_scroll.saturating_add(1);
+                    }
+                    Action::Confirm => return Ok(self.open_confirm(ctx.config)),
+                    Action::Refresh => self.load_preview(ctx.config),
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => return Ok(self.handle_mouse(mouse)),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        self.confirming = false;
+        self.view = View::Diff;
+        self.load_preview(ctx.config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diverged(path: &str, base: &str, local: Option<&str>, remote: &str) -> DivergedFile {
+        DivergedFile {
+            path: path.to_string(),
+            base: Some(base.as_bytes().to_vec()),
+            local: local.map(|c| c.as_bytes().to_vec()),
+            remote: Some(remote.as_bytes().to_vec()),
+            binary: false,
+        }
+    }
+
+    #[test]
+    fn test_choices_and_reconciled_files() {
+        let mut screen = ReconcileScreen::new();
+        screen.set_preview(Some(ReconcilePreview {
+            files: vec![
+                diverged("common/.zshrc", "a\nb\nc\n", Some("A\nb\nc\n"), "a\nb\nC\n"),
+                diverged("Work/.vimrc", "x\n", None, "y\n"),
+            ],
+            ..ReconcilePreview::default()
+        }));
+        assert_eq!(screen.entries[0].choice, Choice::Merge);
+        assert_eq!(screen.entries[1].choice, Choice::KeepLocal);
+
+        screen.step_choice(true);
+        assert_eq!(screen.entries[0].choice, Choice::KeepRemote);
+        screen.step_choice(true);
+        assert_eq!(screen.entries[0].choice, Choice::KeepRemote);
+
+        // A deleted file can't be merged, so right goes straight to remote
+        screen.move_selection(1);
+        screen.step_choice(true);
+        assert_eq!(screen.entries[1].choice, Choice::KeepRemote);
+
+        let files = screen.reconciled_files();
+        assert_eq!(
+            files,
+            vec![
+                (
+                    "common/.zshrc".to_string(),
+                    Choice::KeepRemote,
+                    Some(b"a\nb\nC\n".to_vec())
+                ),
+                (
+                    "Work/.vimrc".to_string(),
+                    Choice::KeepRemote,
+                    Some(b"y\n".to_vec())
+                ),
+            ]
+        );
+    }
+}
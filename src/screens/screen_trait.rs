@@ -127,6 +127,11 @@ pub enum ScreenAction {
         /// Whether the repo should be private.
         is_private: bool,
     },
+    /// Resume a GitHub setup that was interrupted or failed.
+    ResumeGitHubSetup {
+        /// Where the earlier attempt stopped.
+        progress: crate::services::SetupProgress,
+    },
     /// Update the GitHub token only (for already configured repos).
     UpdateGitHubToken {
         /// New token to save.
@@ -147,7 +152,13 @@ pub enum ScreenAction {
         /// Name of the profile to activate.
         name: String,
     },
+    /// GitHub rejected the token; ask for a new one.
+    Reauthenticate,
+    /// A replacement token was validated and saved; reload the config.
+    TokenReplaced,
     // Dotfile selection actions
+    /// Pull from remote in the background, without committing or pushing.
+    PullFromRemote,
     /// Scan for dotfiles and refresh the list.
     ScanDotfiles,
     /// Refresh the file browser entries.
@@ -171,11 +182,28 @@ pub enum ScreenAction {
         /// Whether backups are enabled.
         enabled: bool,
     },
+    /// Add the large or binary file waiting for a decision, stored as chosen.
+    StoreLargeFile {
+        /// How to store it in the repository.
+        storage: crate::services::AddStorage,
+    },
     /// Remove a custom file entry from the config (not synced, user-added).
     RemoveCustomFile {
         /// Index of the file in the dotfiles list.
         file_index: usize,
     },
+    /// Rename or move a synced file.
+    RenameFile {
+        /// Index of the file in the dotfiles list.
+        file_index: usize,
+        /// New path relative to the home directory.
+        new_path: String,
+    },
+    /// Open a file in the user's editor, with the TUI suspended until it exits.
+    EditFile {
+        /// The file to edit.
+        path: PathBuf,
+    },
     /// Move a file to/from common (shared across all profiles).
     MoveToCommon {
         /// Index of the file in the dotfiles list.
@@ -185,6 +213,16 @@ pub enum ScreenAction {
         /// Profiles that need cleanup (same file with same content) - only used when moving to common.
         profiles_to_cleanup: Vec<String>,
     },
+    /// Move several profile files to common in one commit.
+    MoveManyToCommon {
+        /// Index of each file in the dotfiles list, with the profiles holding
+        /// an identical copy to clean up.
+        files: Vec<(usize, Vec<String>)>,
+    },
+    /// Apply the reviewed queue of file changes, all or nothing.
+    ApplyFileQueue {
+        operations: Vec<crate::services::FileOperation>,
+    },
     // Profile management actions
     /// Create a new profile.
     CreateProfile {
@@ -225,6 +263,38 @@ pub enum ScreenAction {
         /// Index of the selected option.
         option_index: usize,
     },
+    /// Update a setting that is typed in.
+    UpdateTextSetting {
+        /// Name of the setting being updated.
+        setting: String,
+        /// The value typed, already validated.
+        value: String,
+    },
+}
+
+impl ScreenAction {
+    /// Toast explaining that an action is disabled on a pull-only machine.
+    #[must_use]
+    pub fn pull_only_blocked() -> Self {
+        Self::ShowToast {
+            message: "Disabled on a pull-only machine (see Settings)".to_string(),
+            variant: crate::widgets::ToastVariant::Warning,
+        }
+    }
+
+    /// Toggle whether previews show secrets, with a toast saying which.
+    #[must_use]
+    pub fn toggle_secrets() -> Self {
+        let message = if crate::components::file_preview::FilePreview::toggle_secrets() {
+            "Secrets are shown in previews"
+        } else {
+            "Secrets are masked in previews"
+        };
+        Self::ShowToast {
+            message: message.to_string(),
+            variant: crate::widgets::ToastVariant::Info,
+        }
+    }
 }
 
 /// Result of processing a screen-specific action.
@@ -0,0 +1,499 @@
+//! Statistics screen controller.
+//!
+//! Shows tracked files per profile, repository and backup sizes, the largest
+//! files and the files changed most often, collected in the background.
+
+use crate::components::footer::Footer;
+use crate::components::header::Header;
+use crate::config::Config;
+use crate::keymap::Action;
+use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::services::{RepoStats, StatsService};
+use crate::styles::theme;
+use crate::ui::Screen as ScreenId;
+use crate::utils::binary::format_size;
+use crate::utils::{
+    create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    MouseRegions,
+};
+use anyhow::Result;
+use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use tracing::error;
+
+/// Panels of the statistics, in focus order
+const PANELS: usize = 4;
+
+/// Lines scrolled per mouse wheel step
+const WHEEL_LINES: i32 = 3;
+
+/// Statistics screen controller.
+pub struct StatsScreen {
+    stats: Option<RepoStats>,
+    error: Option<String>,
+    /// Receiver for statistics being collected in the background
+    collect_rx: Option<Receiver<std::result::Result<RepoStats, String>>>,
+    spinner_tick: usize,
+    /// Panel scrolled by the keyboard
+    focus: usize,
+    /// Scroll offset of each panel
+    scroll: [u16; PANELS],
+    /// Furthest each panel can scroll, as of the last render
+    max_scroll: [u16; PANELS],
+    /// Panel areas (value = panel index)
+    panel_regions: MouseRegions<usize>,
+}
+
+impl Default for StatsScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatsScreen {
+    /// Create a new statistics screen.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            stats: None,
+            error: None,
+            collect_rx: None,
+            spinner_tick: 0,
+            focus: 0,
+            scroll: [0; PANELS],
+            max_scroll: [0; PANELS],
+            panel_regions: MouseRegions::new(),
+        }
+    }
+
+    /// Whether statistics are being collected
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.collect_rx.is_some()
+    }
+
+    /// Collect statistics in the background.
+    pub fn start_collecting(&mut self, config: &Config) {
+        if self.collect_rx.is_some() {
+            return;
+        }
+        let config = config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(StatsService::collect(&config).map_err(|e| format!("{e:#}")));
+        });
+        self.collect_rx = Some(rx);
+        self.error = None;
+    }
+
+    /// Poll background work. Returns `ScreenAction::Refresh` while work is in progress.
+    pub fn tick(&mut self) -> ScreenAction {
+        let Some(rx) = &self.collect_rx else {
+            return ScreenAction::None;
+        };
+        self.spinner_tick = self.spinner_tick.wrapping_add(1);
+        match rx.try_recv() {
+            Ok(Ok(stats)) => self.stats = Some(stats),
+            Ok(Err(e)) => {
+                error!("Collecting statistics failed: {}", e);
+                self.error = Some(e);
+            }
+            Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+            Err(TryRecvError::Disconnected) => {
+                self.error = Some("Collecting statistics stopped unexpectedly".to_string());
+            }
+        }
+        self.collect_rx = None;
+        ScreenAction::Refresh
+    }
+
+    /// Scroll panel `index` by `delta` lines, within what it can show
+    fn scroll_panel(&mut self, index: usize, delta: i32) {
+        let current = i32::from(self.scroll[index]);
+        let max = i32::from(self.max_scroll[index]);
+        self.scroll[index] = u16::try_from((current + delta).clamp(0, max)).unwrap_or(0);
+    }
+
+    fn panel(title: &str) -> Block<'static> {
+        Self::panel_with_focus(title, false)
+    }
+
+    fn panel_with_focus(title: &str, focused: bool) -> Block<'static> {
+        let t = theme();
+        let border_style = if focused {
+            focused_border_style()
+        } else {
+            unfocused_border_style()
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {title} "))
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(focused))
+            .border_style(border_style)
+            .padding(crate::styles::padding(Padding::horizontal(1)))
+            .style(t.background_style())
+    }
+
+    /// A line with `label` on the left and `value` right after it, padded to `width`
+    fn row(label: &str, value: String, width: usize) -> Line<'static> {
+        let t = theme();
+        Line::from(vec![
+            Span::styled(format!("{label:<width$} "), t.text_style()),
+            Span::styled(value, t.muted_style()),
+        ])
+    }
+
+    fn profile_lines(stats: &RepoStats) -> Vec<Line<'static>> {
+        let t = theme();
+        let width = stats
+            .profiles
+            .iter()
+            .map(|p| p.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(7);
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{:<width$} {:>5} {:>7} {:>9}",
+                "Profile", "Own", "Linked", "Size"
+            ),
+            t.emphasis_style(),
+        ))];
+        lines.extend(stats.profiles.iter().map(|p| {
+            Line::from(vec![
+                Span::styled(format!("{:<width$} ", p.name), t.text_style()),
+                Span::styled(
+                    format!(
+                        "{:>5} {:>7} {:>9}",
+                        p.files,
+                        p.resolved,
+                        format_size(p.size)
+                    ),
+                    t.muted_style(),
+                ),
+            ])
+        }));
+        lines
+    }
+
+    fn storage_lines(stats: &RepoStats) -> Vec<Line<'static>> {
+        let backups = if stats.backup_sessions == 0 {
+            "None".to_string()
+        } else {
+            format!(
+                "{} in {} session(s)",
+                format_size(stats.backup_size),
+                stats.backup_sessions
+            )
+        };
+        vec![
+            Self::row("Tracked files", format_size(stats.repo_size), 14),
+            Self::row("Git history", format_size(stats.git_size), 14),
+            Self::row(
+                "Repository",
+                format_size(stats.repo_size + stats.git_size),
+                14,
+            ),
+            Self::row("Backups", backups, 14),
+            Line::from(Span::styled(
+                stats.backup_root.display().to_string(),
+                theme().muted_style(),
+            )),
+        ]
+    }
+
+    fn file_lines<T>(
+        entries: &[(String, T)],
+        value: impl Fn(&T) -> String,
+        empty: &str,
+    ) -> Vec<Line<'static>> {
+        if entries.is_empty() {
+            return vec![Line::from(Span::styled(
+                empty.to_string(),
+                theme().muted_style(),
+            ))];
+        }
+        let width = entries
+            .iter()
+            .map(|(path, _)| path.chars().count())
+            .max()
+            .unwrap_or(0);
+        entries
+            .iter()
+            .map(|(path, v)| Self::row(path, value(v), width))
+            .collect()
+    }
+
+    fn render_stats(&mut self, frame: &mut Frame, area: Rect, stats: &RepoStats) {
+        let columns = create_split_layout(area, &[50, 50]);
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(6), Constraint::Length(7)])
+            .split(columns[0]);
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(columns[1]);
+
+        let panels = [
+            (
+                Self::profile_lines(stats),
+                "Files per Profile".to_string(),
+                left[0],
+            ),
+            (Self::storage_lines(stats), "Storage".to_string(), left[1]),
+            (
+                Self::file_lines(&stats.largest_files, |size| format_size(*size), "No files"),
+                "Largest Files".to_string(),
+                right[0],
+            ),
+            (
+                Self::file_lines(
+                    &stats.most_changed,
+                    |count| format!("{count} commits"),
+                    "No commits yet",
+                ),
+                format!("Most Changed (last {} commits)", stats.commits_scanned),
+                right[1],
+            ),
+        ];
+        self.panel_regions.clear();
+        for (index, (lines, title, panel_area)) in panels.into_iter().enumerate() {
+            let block = Self::panel_with_focus(&title, index == self.focus);
+            let visible = block.inner(panel_area).height;
+            self.max_scroll[index] = u16::try_from(lines.len())
+                .unwrap_or(u16::MAX)
+                .saturating_sub(visible);
+            self.scroll[index] = self.scroll[index].min(self.max_scroll[index]);
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .scroll((self.scroll[index], 0)),
+                panel_area,
+            );
+            self.panel_regions.add(panel_area, index);
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> ScreenAction {
+        let Some(&index) = self.panel_regions.hit_test(mouse.column, mouse.row) else {
+            return ScreenAction::None;
+        };
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.focus = index,
+            MouseEventKind::ScrollUp => self.scroll_panel(index, -WHEEL_LINES),
+            MouseEventKind::ScrollDown => self.scroll_panel(index, WHEEL_LINES),
+            _ => return ScreenAction::None,
+        }
+        ScreenAction::Refresh
+    }
+}
+
+impl Screen for StatsScreen {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) -> Result<()> {
+        let t = theme();
+        frame.render_widget(Block::default().style(t.background_style()), area);
+
+        let (header_chunk, content_chunk, footer_chunk) = create_standard_layout(area, 5, 3);
+        Header::render(
+            frame,
+            header_chunk,
+            "DotState - Statistics",
+            "What your repository holds and what takes up space.",
+        )?;
+
+        if let Some(error) = &self.error {
+            let para = Paragraph::new(format!("Couldn't collect statistics:\n\n{error}"))
+                .style(Style::default().fg(t.error))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Self::panel("Statistics")
+                        .padding(crate::styles::padding(Padding::new(2, 2, 2, 2))),
+                );
+            frame.render_widget(para, content_chunk);
+        } else if let Some(stats) = self.stats.take() {
+            self.render_stats(frame, content_chunk, &stats);
+            self.stats = Some(stats);
+        } else {
+            let dots = ".".repeat(self.spinner_tick / 2 % 4);
+            let para = Paragraph::new(format!("Collecting statistics{dots}"))
+                .style(t.muted_style())
+                .block(
+                    Self::panel("Statistics")
+                        .padding(crate::styles::padding(Padding::new(2, 2, 2, 2))),
+                );
+            frame.render_widget(para, content_chunk);
+        }
+
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Switch Panel | {}: Scroll | {}: Refresh | {}: Back",
+            k(Action::NextTab),
+            ctx.config.keymap.navigation_display(),
+            k(Action::Refresh),
+            k(Action::Cancel)
+        );
+        Footer::render(frame, footer_chunk, &footer_text)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event, ctx: &ScreenContext) -> Result<ScreenAction> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let focus = self.focus;
+                match ctx.config.keymap.get_action(key.code, key.modifiers) {
+                    Some(Action::Cancel | Action::Quit) => {
+                        return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
+                    }
+                    Some(Action::Refresh) => self.start_collecting(ctx.config),
+                    Some(Action::NextTab | Action::MoveRight) => {
+                        self.focus = (focus + 1) % PANELS;
+                    }
+                    Some(Action::PrevTab | Action::MoveLeft) => {
+                        self.focus = (focus + PANELS - 1) % PANELS;
+                    }
+                    Some(Action::MoveUp | Action::ScrollUp) => self.scroll_panel(focus, -1),
+                    Some(Action::MoveDown | Action::ScrollDown) => self.scroll_panel(focus, 1),
+                    Some(Action::PageUp) => self.scroll_panel(focus, -10),
+                    Some(Action::PageDown) => self.scroll_panel(focus, 10),
+                    Some(Action::GoToTop) => self.scroll[focus] = 0,
+                    Some(Action::GoToEnd) => self.scroll[focus] = self.max_scroll[focus],
+                    _ => {}
+                }
+            }
+            Event::Mouse(mouse) => return Ok(self.handle_mouse(mouse)),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn on_enter(&mut self, ctx: &ScreenContext) -> Result<()> {
+        self.start_collecting(ctx.config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use syntect::highlighting::{Theme, ThemeSet};
+    use syntect::parsing::SyntaxSet;
+
+    fn screen_with_stats() -> StatsScreen {
+        let mut screen = StatsScreen::new();
+        screen.stats = Some(RepoStats {
+            largest_files: (0..30)
+                .map(|i| (format!("Work/file{i:02}"), 4096 - i))
+                .collect(),
+            commits_scanned: 12,
+            ..RepoStats::default()
+        });
+        screen
+    }
+
+    fn render(screen: &mut StatsScreen, config: &Config) -> String {
+        let (syntax_set, theme_set, theme) = (SyntaxSet::new(), ThemeSet::new(), Theme::default());
+        let ctx = RenderContext::new(config, &syntax_set, &theme_set, &theme);
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|frame| screen.render(frame, frame.area(), &ctx).unwrap())
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_renders_panels() {
+        let config = Config::default();
+        let mut screen = StatsScreen::new();
+        assert!(render(&mut screen, &config).contains("Collecting statistics"));
+
+        let mut screen = screen_with_stats();
+        let text = render(&mut screen, &config);
+        assert!(text.contains("Largest Files"));
+        assert!(text.contains("Work/file00"));
+        assert!(!text.contains("Work/file29"));
+        assert!(text.contains("Most Changed (last 12 commits)"));
+        assert!(text.contains("No commits yet"));
+    }
+
+    #[test]
+    fn test_mouse_focuses_and_scrolls_panels() {
+        let config = Config::default();
+        let ctx = ScreenContext::new(&config, std::path::Path::new(""));
+        let mut screen = screen_with_stats();
+        render(&mut screen, &config);
+
+        // The wheel scrolls the panel under the pointer
+        let largest = (75, 8);
+        screen
+            .handle_event(
+                mouse(MouseEventKind::ScrollDown, largest.0, largest.1),
+                &ctx,
+            )
+            .unwrap();
+        assert_eq!(screen.scroll[2], 3);
+        assert!(render(&mut screen, &config).contains("Work/file03"));
+        screen
+            .handle_event(mouse(MouseEventKind::ScrollUp, largest.0, largest.1), &ctx)
+            .unwrap();
+        screen
+            .handle_event(mouse(MouseEventKind::ScrollUp, largest.0, largest.1), &ctx)
+            .unwrap();
+        assert_eq!(screen.scroll[2], 0);
+
+        // A click focuses it, and the keyboard then scrolls it
+        screen
+            .handle_event(
+                mouse(
+                    MouseEventKind::Down(MouseButton::Left),
+                    largest.0,
+                    largest.1,
+                ),
+                &ctx,
+            )
+            .unwrap();
+        assert_eq!(screen.focus, 2);
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        screen.handle_event(key(KeyCode::Down), &ctx).unwrap();
+        assert_eq!(screen.scroll[2], 1);
+        screen.handle_event(key(KeyCode::End), &ctx).unwrap();
+        assert_eq!(screen.scroll[2], screen.max_scroll[2]);
+        assert!(render(&mut screen, &config).contains("Work/file29"));
+
+        // Panels that fit don't scroll
+        screen.handle_event(key(KeyCode::Tab), &ctx).unwrap();
+        assert_eq!(screen.focus, 3);
+        screen.handle_event(key(KeyCode::Down), &ctx).unwrap();
+        assert_eq!(screen.scroll[3], 0);
+
+        assert!(matches!(
+            screen.handle_event(key(KeyCode::Esc), &ctx).unwrap(),
+            ScreenAction::Navigate(ScreenId::MainMenu)
+        ));
+    }
+}
@@ -1,15 +1,17 @@
 //! Storage setup screen for configuring dotfiles storage.
 //!
 //! Provides a two-pane interface matching the settings screen pattern:
-//! - Left: Storage method selection (GitHub or Local)
+//! - Left: Storage method selection (new GitHub repo, restore from GitHub, or Local)
 //! - Right: Form fields and context-sensitive help
 
 use crate::components::footer::Footer;
 use crate::components::header::Header;
+use crate::github::GitHubRepo;
 use crate::icons::Icons;
 use crate::keymap::Action;
 use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
-use crate::styles::theme;
+use crate::services::{SetupProgress, StorageSetupService};
+use crate::styles::{theme, LIST_HIGHLIGHT_SYMBOL};
 use crate::ui::{GitHubSetupData, GitHubSetupStep};
 use crate::utils::{
     create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
@@ -21,8 +23,11 @@ use crossterm::event::{Event, KeyEventKind, MouseButton, MouseEventKind};
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, StatefulWidget, Wrap};
+use ratatui::widgets::{
+    Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, StatefulWidget, Wrap,
+};
 use ratatui::Frame;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 
 /// Focus within the storage setup screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -37,18 +42,24 @@ pub enum StorageSetupFocus {
 pub enum StorageMethod {
     #[default]
     GitHub,
+    /// Clone an existing dotfiles repository from GitHub
+    Restore,
     Local,
 }
 
 impl StorageMethod {
     fn all() -> Vec<StorageMethod> {
-        vec![StorageMethod::GitHub, StorageMethod::Local]
+        vec![
+            StorageMethod::GitHub,
+            StorageMethod::Restore,
+            StorageMethod::Local,
+        ]
     }
 
-    #[allow(dead_code)] // Utility method for potential future use
     fn name(&self) -> &'static str {
         match self {
             StorageMethod::GitHub => "GitHub Repository",
+            StorageMethod::Restore => "Restore from GitHub",
             StorageMethod::Local => "Local Repository",
         }
     }
@@ -56,19 +67,29 @@ impl StorageMethod {
     fn index(&self) -> usize {
         match self {
             StorageMethod::GitHub => 0,
-            StorageMethod::Local => 1,
+            StorageMethod::Restore => 1,
+            StorageMethod::Local => 2,
         }
     }
 
     fn from_index(index: usize) -> Option<StorageMethod> {
         match index {
             0 => Some(StorageMethod::GitHub),
-            1 => Some(StorageMethod::Local),
+            1 => Some(StorageMethod::Restore),
+            2 => Some(StorageMethod::Local),
             _ => None,
         }
     }
 }
 
+/// Restore form fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestoreField {
+    #[default]
+    Token,
+    Repos,
+}
+
 /// GitHub form fields
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum GitHubField {
@@ -119,6 +140,11 @@ pub enum StorageSetupStep {
 }
 
 /// Storage setup screen state
+/// Default storage path as shown in the setup forms
+fn default_storage_display() -> String {
+    crate::utils::path::format_path_for_display(&crate::utils::get_default_storage_path())
+}
+
 #[derive(Debug)]
 pub struct StorageSetupState {
     // Focus and selection
@@ -133,6 +159,14 @@ pub struct StorageSetupState {
     pub is_private: bool,
     pub github_field: GitHubField,
 
+    // Restore form fields (the token is shared with the GitHub form)
+    pub restore_field: RestoreField,
+    /// Repositories to restore from; `None` until listed
+    pub restore_repos: Option<Vec<GitHubRepo>>,
+    pub restore_list_state: ListState,
+    /// Receiver for an in-flight repository listing
+    pub restore_loading: Option<Receiver<Result<Vec<GitHubRepo>, String>>>,
+
     // Local form field
     pub local_path_input: TextInput,
 
@@ -152,6 +186,12 @@ pub struct StorageSetupState {
     // When Some, a confirm popup is shown; holds the (repo_path, profiles)
     // to proceed with once the user accepts.
     pub pending_sha256_repo: Option<(std::path::PathBuf, Vec<String>)>,
+
+    // Latest transfer snapshot while cloning
+    pub transfer_progress: Option<crate::git::TransferProgress>,
+
+    // Unfinished setup from an earlier run, resumed when submitted unchanged
+    pub resume: Option<SetupProgress>,
 }
 
 impl Default for StorageSetupState {
@@ -165,10 +205,14 @@ impl Default for StorageSetupState {
             menu_state,
             token_input: TextInput::default(),
             repo_name_input: TextInput::with_text(crate::config::default_repo_name()),
-            repo_path_input: TextInput::with_text("~/.config/dotstate/storage"),
+            repo_path_input: TextInput::with_text(default_storage_display()),
             is_private: true,
             github_field: GitHubField::Token,
-            local_path_input: TextInput::with_text("~/.config/dotstate/storage"),
+            restore_field: RestoreField::Token,
+            restore_repos: None,
+            restore_list_state: ListState::default(),
+            restore_loading: None,
+            local_path_input: TextInput::with_text(default_storage_display()),
             status_message: None,
             error_message: None,
             is_reconfiguring: false,
@@ -176,6 +220,8 @@ impl Default for StorageSetupState {
             step: StorageSetupStep::Input,
             setup_data: None,
             pending_sha256_repo: None,
+            transfer_progress: None,
+            resume: None,
         }
     }
 }
@@ -189,6 +235,8 @@ pub struct StorageSetupScreen {
     method_pane_area: Option<Rect>,
     /// Clickable regions for form fields
     form_field_regions: MouseRegions<usize>,
+    /// Inner area of the restore repository list (for click hit-testing)
+    restore_list_area: Option<Rect>,
 }
 
 impl Default for StorageSetupScreen {
@@ -205,6 +253,7 @@ impl StorageSetupScreen {
             method_regions: MouseRegions::new(),
             method_pane_area: None,
             form_field_regions: MouseRegions::new(),
+            restore_list_area: None,
         }
     }
 
@@ -220,6 +269,135 @@ impl StorageSetupScreen {
         matches!(self.state.step, StorageSetupStep::Processing(_))
     }
 
+    /// Whether repositories are being listed for a restore
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.state.restore_loading.is_some()
+    }
+
+    /// Poll the background repository listing. Returns `ScreenAction::Refresh` while it runs.
+    pub fn tick(&mut self) -> ScreenAction {
+        let Some(rx) = &self.state.restore_loading else {
+            return ScreenAction::None;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+            Err(TryRecvError::Disconnected) => {
+                Err("Listing repositories stopped unexpectedly".to_string())
+            }
+        };
+        self.state.restore_loading = None;
+        self.state.status_message = None;
+        match result {
+            Ok(repos) if repos.is_empty() => {
+                self.state.error_message =
+                    Some("No repositories found for this account".to_string());
+            }
+            Ok(repos) => {
+                self.state.restore_list_state.select(Some(0));
+                self.state.restore_repos = Some(repos);
+                self.state.restore_field = RestoreField::Repos;
+            }
+            Err(e) => self.state.error_message = Some(e),
+        }
+        ScreenAction::Refresh
+    }
+
+    /// List the token owner's repositories in the background.
+    fn start_repo_listing(&mut self) -> ScreenAction {
+        if self.is_busy() {
+            return ScreenAction::None;
+        }
+        let token = self.state.token_input.text_trimmed().to_string();
+        if !token.starts_with("ghp_") && !token.starts_with("github_pat_") {
+            self.state.error_message =
+                Some("Token must start with 'ghp_' or 'github_pat_'".to_string());
+            return ScreenAction::None;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result =
+                StorageSetupService::list_restorable_repos(&token).map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.state.restore_loading = Some(rx);
+        self.state.restore_repos = None;
+        self.state.status_message = Some("Loading your repositories...".to_string());
+        ScreenAction::Refresh
+    }
+
+    /// The repository highlighted in the restore list
+    fn selected_restore_repo(&self) -> Option<&GitHubRepo> {
+        let repos = self.state.restore_repos.as_ref()?;
+        repos.get(self.state.restore_list_state.selected()?)
+    }
+
+    /// Move the restore list selection by `delta` rows
+    fn move_restore_selection(&mut self, delta: isize) {
+        let Some(repos) = &self.state.restore_repos else {
+            return;
+        };
+        if repos.is_empty() {
+            return;
+        }
+        let current = self.state.restore_list_state.selected().unwrap_or(0);
+        let next = current
+            .saturating_add_signed(delta)
+            .min(repos.len().saturating_sub(1));
+        self.state.restore_list_state.select(Some(next));
+    }
+
+    /// Prefill the GitHub form from an unfinished setup and explain how to
+    /// carry on with it
+    fn offer_resume(&mut self, progress: SetupProgress) {
+        self.state.method = StorageMethod::GitHub;
+        self.state
+            .menu_state
+            .select(Some(StorageMethod::GitHub.index()));
+        self.state.focus = StorageSetupFocus::Form;
+        self.state.token_input = TextInput::with_text(progress.token.clone());
+        self.state.repo_name_input = TextInput::with_text(progress.repo_name.clone());
+        self.state.is_private = progress.is_private;
+        let stopped = match &progress.error {
+            Some(error) => format!(
+                "The last setup failed while {}:\n{error}",
+                progress.step.label().to_lowercase()
+            ),
+            None => format!(
+                "The last setup was interrupted while {}.",
+                progress.step.label().to_lowercase()
+            ),
+        };
+        self.state.status_message = Some(format!(
+            "{stopped}\n\nPress Enter to resume from {}. Change the token or repository to start over instead.",
+            progress.resume_step().label().to_lowercase()
+        ));
+        self.state.resume = Some(progress);
+    }
+
+    /// Start GitHub setup, or resume the unfinished one when the inputs are
+    /// the ones it started with
+    fn github_setup_action(
+        &self,
+        token: String,
+        repo_name: String,
+        is_private: bool,
+    ) -> ScreenAction {
+        match &self.state.resume {
+            Some(progress) if progress.matches(&token, &repo_name, is_private) => {
+                ScreenAction::ResumeGitHubSetup {
+                    progress: progress.clone(),
+                }
+            }
+            _ => ScreenAction::StartGitHubSetup {
+                token,
+                repo_name,
+                is_private,
+            },
+        }
+    }
+
     /// Get the current state (read-only).
     #[must_use]
     pub fn get_state(&self) -> &StorageSetupState {
@@ -254,11 +432,12 @@ impl StorageSetupScreen {
         let items: Vec<MenuItem> = StorageMethod::all()
             .iter()
             .map(|method| {
-                let (icon, text, color) = match method {
-                    StorageMethod::GitHub => (icons.github(), "GitHub Repository", t.success),
-                    StorageMethod::Local => (icons.folder(), "Local Repository", t.tertiary),
+                let (icon, color) = match method {
+                    StorageMethod::GitHub => (icons.github(), t.success),
+                    StorageMethod::Restore => (icons.sync(), t.secondary),
+                    StorageMethod::Local => (icons.folder(), t.tertiary),
                 };
-                MenuItem::new(icon, text, color)
+                MenuItem::new(icon, method.name(), color)
             })
             .collect();
 
@@ -306,6 +485,9 @@ impl StorageSetupScreen {
             StorageMethod::GitHub => {
                 self.render_github_form(frame, chunks[0], ctx, is_focused);
             }
+            StorageMethod::Restore => {
+                self.render_restore_form(frame, chunks[0], is_focused);
+            }
             StorageMethod::Local => {
                 self.render_local_form(frame, chunks[0], ctx, is_focused);
             }
@@ -338,7 +520,7 @@ impl StorageSetupScreen {
             .title_alignment(Alignment::Center)
             .border_type(t.border_type(is_pane_focused))
             .border_style(border_style)
-            .padding(Padding::new(1, 1, 1, 1))
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 1)))
             .style(t.background_style());
 
         let inner = form_block.inner(area);
@@ -380,9 +562,10 @@ impl StorageSetupScreen {
 
         // Repo path field (field index 2)
         let repo_path_focused = is_pane_focused && self.state.github_field == GitHubField::RepoPath;
+        let default_path = default_storage_display();
         let repo_path_widget = TextInputWidget::new(&self.state.repo_path_input)
             .title("Local Path")
-            .placeholder("~/.config/dotstate/storage")
+            .placeholder(&default_path)
             .focused(repo_path_focused)
             .disabled(self.state.is_reconfiguring);
         frame.render_text_input_widget(repo_path_widget, fields[2]);
@@ -427,6 +610,102 @@ impl StorageSetupScreen {
         self.form_field_regions.add(fields[3], 3);
     }
 
+    /// Render the restore form: token and the list of repositories
+    fn render_restore_form(&mut self, frame: &mut Frame, area: Rect, is_pane_focused: bool) {
+        let t = theme();
+
+        let border_style = if is_pane_focused {
+            focused_border_style()
+        } else {
+            unfocused_border_style()
+        };
+
+        let form_block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Restore From GitHub ")
+            .title_alignment(Alignment::Center)
+            .border_type(t.border_type(is_pane_focused))
+            .border_style(border_style)
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 0)))
+            .style(t.background_style());
+
+        let inner = form_block.inner(area);
+        frame.render_widget(form_block, area);
+
+        let fields = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Token
+                Constraint::Min(3),    // Repositories
+            ])
+            .split(inner);
+
+        // Token field (field index 0)
+        let token_focused = is_pane_focused && self.state.restore_field == RestoreField::Token;
+        let token_widget = TextInputWidget::new(&self.state.token_input)
+            .title("GitHub Token")
+            .placeholder("ghp_...")
+            .focused(token_focused)
+            .disabled(self.state.is_reconfiguring);
+        frame.render_text_input_widget(token_widget, fields[0]);
+        self.form_field_regions.add(fields[0], 0);
+
+        let list_focused = is_pane_focused && self.state.restore_field == RestoreField::Repos;
+        let list_block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Repositories ")
+            .border_type(t.border_type(list_focused))
+            .border_style(if list_focused {
+                focused_border_style()
+            } else {
+                unfocused_border_style()
+            })
+            .style(t.background_style());
+        let list_inner = list_block.inner(fields[1]);
+
+        let Some(repos) = &self.state.restore_repos else {
+            let hint = if self.is_busy() {
+                Span::styled(
+                    "Loading your repositories...",
+                    Style::default().fg(t.warning),
+                )
+            } else {
+                Span::styled(
+                    "Enter your token and press Enter to list your repositories.",
+                    t.muted_style(),
+                )
+            };
+            let para = Paragraph::new(Line::from(hint))
+                .block(list_block)
+                .wrap(Wrap { trim: true });
+            frame.render_widget(para, fields[1]);
+            self.restore_list_area = None;
+            return;
+        };
+
+        let items: Vec<ListItem> = repos
+            .iter()
+            .map(|repo| {
+                let visibility = if repo.private { "private" } else { "public" };
+                let mut spans = vec![
+                    Span::styled(repo.name.clone(), t.text_style()),
+                    Span::styled(format!("  {visibility}"), t.muted_style()),
+                ];
+                if let Some(description) = repo.description.as_deref().filter(|d| !d.is_empty()) {
+                    spans.push(Span::styled(format!(" · {description}"), t.muted_style()));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(list_block)
+            .highlight_style(t.highlight_style())
+            .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+        frame.render_stateful_widget(list, fields[1], &mut self.state.restore_list_state);
+        self.restore_list_area = Some(list_inner);
+    }
+
     /// Render Local form fields
     fn render_local_form(
         &mut self,
@@ -450,7 +729,7 @@ impl StorageSetupScreen {
             .title_alignment(Alignment::Center)
             .border_type(t.border_type(is_pane_focused))
             .border_style(border_style)
-            .padding(Padding::new(1, 1, 1, 1))
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 1)))
             .style(t.background_style());
 
         let inner = form_block.inner(area);
@@ -489,9 +768,10 @@ impl StorageSetupScreen {
         frame.render_widget(instructions_para, fields[0]);
 
         // Path input (field index 0 for local)
+        let default_path = default_storage_display();
         let path_widget = TextInputWidget::new(&self.state.local_path_input)
             .title("Repository Path")
-            .placeholder("~/.config/dotstate/storage")
+            .placeholder(&default_path)
             .focused(is_pane_focused)
             .disabled(self.state.is_reconfiguring);
         frame.render_text_input_widget(path_widget, fields[2]);
@@ -511,7 +791,7 @@ impl StorageSetupScreen {
                 .title(" Error ")
                 .title_alignment(Alignment::Center)
                 .border_style(Style::default().fg(t.error))
-                .padding(Padding::proportional(1));
+                .padding(crate::styles::padding(Padding::proportional(1)));
             let error_para = Paragraph::new(error.as_str())
                 .block(error_block)
                 .wrap(Wrap { trim: true })
@@ -528,7 +808,7 @@ impl StorageSetupScreen {
                 .title(" Status ")
                 .title_alignment(Alignment::Center)
                 .border_style(Style::default().fg(t.success))
-                .padding(Padding::proportional(1));
+                .padding(crate::styles::padding(Padding::proportional(1)));
             let status_para = Paragraph::new(status.as_str())
                 .block(status_block)
                 .wrap(Wrap { trim: true });
@@ -541,6 +821,7 @@ impl StorageSetupScreen {
             StorageSetupFocus::MethodList => self.get_method_help(),
             StorageSetupFocus::Form => match self.state.method {
                 StorageMethod::GitHub => self.get_github_field_help(),
+                StorageMethod::Restore => self.get_restore_help(),
                 StorageMethod::Local => self.get_local_help(),
             },
         };
@@ -551,7 +832,7 @@ impl StorageSetupScreen {
             .title_alignment(Alignment::Center)
             .border_style(Style::default().fg(t.primary))
             .border_type(t.border_type(false))
-            .padding(Padding::proportional(1))
+            .padding(crate::styles::padding(Padding::proportional(1)))
             .style(t.background_style());
 
         let help_para = Paragraph::new(help_text)
@@ -602,6 +883,32 @@ impl StorageSetupScreen {
                     Style::default().fg(t.text_muted),
                 )),
             ]),
+            StorageMethod::Restore => Text::from(vec![
+                Line::from(Span::styled(
+                    "Restore from GitHub",
+                    Style::default()
+                        .fg(t.secondary)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                Line::from("Setting up a new machine?"),
+                Line::from("Pick your existing dotfiles repo,"),
+                Line::from("clone it and activate a profile."),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "You'll need a token with:",
+                    Style::default().fg(t.primary),
+                )),
+                Line::from(vec![
+                    Span::styled("  • ", t.muted_style()),
+                    Span::styled("Contents", Style::default().fg(t.text_emphasis)),
+                    Span::raw(" read & write"),
+                ]),
+                Line::from(Span::styled(
+                    "  (classic: select 'repo' scope)",
+                    Style::default().fg(t.text_muted),
+                )),
+            ]),
             StorageMethod::Local => Text::from(vec![
                 Line::from(Span::styled(
                     "Local Repository",
@@ -713,7 +1020,7 @@ impl StorageSetupScreen {
                 Line::from(""),
                 Line::from("Where dotfiles are stored locally."),
                 Line::from(""),
-                Line::from("Default: ~/.config/dotstate/storage"),
+                Line::from(format!("Default: {}", default_storage_display())),
             ]),
             GitHubField::Visibility => Text::from(vec![
                 Line::from(Span::styled("Repository Visibility", t.title_style())),
@@ -732,6 +1039,33 @@ impl StorageSetupScreen {
         }
     }
 
+    /// Get help text for the restore form
+    fn get_restore_help(&self) -> Text<'static> {
+        let t = theme();
+
+        match self.state.restore_field {
+            RestoreField::Token => Text::from(vec![
+                Line::from(Span::styled("GitHub Token", t.title_style())),
+                Line::from(""),
+                Line::from("Token for the account that owns"),
+                Line::from("your dotfiles repository."),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Press Enter to list your repositories.",
+                    Style::default().fg(t.primary),
+                )),
+            ]),
+            RestoreField::Repos => Text::from(vec![
+                Line::from(Span::styled("Choose a Repository", t.title_style())),
+                Line::from(""),
+                Line::from("Likely dotfiles repos are listed first."),
+                Line::from(""),
+                Line::from("DotState clones it, then shows the"),
+                Line::from("profiles in it so you can activate one."),
+            ]),
+        }
+    }
+
     /// Get help text for Local form
     fn get_local_help(&self) -> Text<'static> {
         let t = theme();
@@ -766,22 +1100,22 @@ impl StorageSetupScreen {
 
         // Build progress content
         let steps = [
-            (GitHubSetupStep::Connecting, "Connecting to GitHub"),
-            (GitHubSetupStep::ValidatingToken, "Validating token"),
-            (GitHubSetupStep::CheckingRepo, "Checking repository"),
-            (GitHubSetupStep::CloningRepo, "Cloning repository"),
-            (GitHubSetupStep::CreatingRepo, "Creating repository"),
-            (GitHubSetupStep::InitializingRepo, "Initializing repository"),
-            (GitHubSetupStep::DiscoveringProfiles, "Discovering profiles"),
-            (GitHubSetupStep::Complete, "Complete"),
+            GitHubSetupStep::Connecting,
+            GitHubSetupStep::ValidatingToken,
+            GitHubSetupStep::CheckingRepo,
+            GitHubSetupStep::CloningRepo,
+            GitHubSetupStep::CreatingRepo,
+            GitHubSetupStep::InitializingRepo,
+            GitHubSetupStep::DiscoveringProfiles,
+            GitHubSetupStep::Complete,
         ];
 
-        let current_step_index = steps.iter().position(|(s, _)| *s == step).unwrap_or(0);
+        let current_step_index = steps.iter().position(|s| *s == step).unwrap_or(0);
 
         let mut lines: Vec<Line> = Vec::new();
         lines.push(Line::from(""));
 
-        for (i, (_, label)) in steps.iter().enumerate() {
+        for (i, s) in steps.iter().enumerate() {
             let (prefix, style) = if i < current_step_index {
                 ("✓ ", Style::default().fg(t.success))
             } else if i == current_step_index {
@@ -795,7 +1129,7 @@ impl StorageSetupScreen {
 
             lines.push(Line::from(vec![
                 Span::styled(prefix, style),
-                Span::styled(*label, style),
+                Span::styled(s.label(), style),
             ]));
         }
 
@@ -814,14 +1148,28 @@ impl StorageSetupScreen {
             .title_alignment(Alignment::Center)
             .border_style(Style::default().fg(t.primary))
             .border_type(t.border_type(true))
-            .padding(Padding::proportional(1))
+            .padding(crate::styles::padding(Padding::proportional(1)))
             .style(t.background_style());
 
+        let inner = progress_block.inner(popup_area);
+        let text_height = u16::try_from(lines.len()).unwrap_or(u16::MAX);
+
         let para = Paragraph::new(lines)
             .block(progress_block)
             .wrap(Wrap { trim: true });
 
         frame.render_widget(para, popup_area);
+
+        // Clone progress bar below the status message
+        if step == GitHubSetupStep::CloningRepo {
+            if let Some(progress) = &self.state.transfer_progress {
+                let bar_y = inner.y.saturating_add(text_height).saturating_add(1);
+                if bar_y < inner.bottom() {
+                    let bar_area = Rect::new(inner.x, bar_y, inner.width, 1);
+                    frame.render_widget(crate::widgets::ProgressBar::new(progress), bar_area);
+                }
+            }
+        }
     }
 
     /// Handle mouse events
@@ -842,6 +1190,16 @@ impl StorageSetupScreen {
                         return Ok(ScreenAction::Refresh);
                     }
                 }
+                // Check restore list click: select a row, click it again to restore
+                if let Some(index) = self.restore_row_at(mouse.column, mouse.row) {
+                    self.state.focus = StorageSetupFocus::Form;
+                    self.state.restore_field = RestoreField::Repos;
+                    if self.state.restore_list_state.selected() == Some(index) {
+                        return self.handle_submit();
+                    }
+                    self.state.restore_list_state.select(Some(index));
+                    return Ok(ScreenAction::Refresh);
+                }
                 // Check form field click
                 if let Some(&field_idx) = self.form_field_regions.hit_test(mouse.column, mouse.row)
                 {
@@ -857,6 +1215,9 @@ impl StorageSetupScreen {
                             };
                             self.state.github_field = field;
                         }
+                        StorageMethod::Restore => {
+                            self.state.restore_field = RestoreField::Token;
+                        }
                         StorageMethod::Local => {
                             // Only one field in local mode
                         }
@@ -864,10 +1225,17 @@ impl StorageSetupScreen {
                     return Ok(ScreenAction::Refresh);
                 }
             }
+            MouseEventKind::ScrollUp if self.restore_list_contains(mouse.column, mouse.row) => {
+                self.move_restore_selection(-1);
+                return Ok(ScreenAction::Refresh);
+            }
+            MouseEventKind::ScrollDown if self.restore_list_contains(mouse.column, mouse.row) => {
+                self.move_restore_selection(1);
+                return Ok(ScreenAction::Refresh);
+            }
             MouseEventKind::ScrollUp => {
                 if let Some(area) = self.method_pane_area {
                     if area.contains(ratatui::layout::Position::new(mouse.column, mouse.row)) {
-                        // Only 2 items, just move to previous
                         if let Some(current) = self.state.menu_state.selected() {
                             if current > 0 {
                                 self.state.menu_state.select(Some(current - 1));
@@ -901,6 +1269,25 @@ impl StorageSetupScreen {
         Ok(ScreenAction::None)
     }
 
+    /// Whether a position is inside the rendered restore list
+    fn restore_list_contains(&self, column: u16, row: u16) -> bool {
+        self.state.method == StorageMethod::Restore
+            && self
+                .restore_list_area
+                .is_some_and(|a| a.contains(ratatui::layout::Position::new(column, row)))
+    }
+
+    /// Index of the restore list row at a position, if any
+    fn restore_row_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.restore_list_contains(column, row) {
+            return None;
+        }
+        let area = self.restore_list_area?;
+        let index = self.state.restore_list_state.offset() + usize::from(row - area.y);
+        let count = self.state.restore_repos.as_ref().map_or(0, Vec::len);
+        (index < count).then_some(index)
+    }
+
     /// Handle events when method list is focused
     fn handle_list_event(&mut self, action: Option<Action>) -> Result<ScreenAction> {
         if let Some(action) = action {
@@ -962,6 +1349,10 @@ impl StorageSetupScreen {
                             true
                         }
                     }
+                    StorageMethod::Restore => {
+                        self.state.restore_field == RestoreField::Token
+                            && !self.state.is_reconfiguring
+                    }
                     StorageMethod::Local => !self.state.is_reconfiguring,
                 };
 
@@ -974,6 +1365,7 @@ impl StorageSetupScreen {
                             GitHubField::RepoPath => self.state.repo_path_input.insert_char(c),
                             GitHubField::Visibility => {} // Not a text field
                         },
+                        StorageMethod::Restore => self.state.token_input.insert_char(c),
                         StorageMethod::Local => self.state.local_path_input.insert_char(c),
                     }
                     return Ok(ScreenAction::None);
@@ -1026,6 +1418,10 @@ impl StorageSetupScreen {
                     GitHubField::RepoPath => self.state.repo_path_input.cursor() == 0,
                     GitHubField::Visibility => false, // MoveLeft toggles visibility, doesn't exit
                 },
+                StorageMethod::Restore => match self.state.restore_field {
+                    RestoreField::Token => self.state.token_input.cursor() == 0,
+                    RestoreField::Repos => true,
+                },
                 StorageMethod::Local => self.state.local_path_input.cursor() == 0,
             };
 
@@ -1037,10 +1433,49 @@ impl StorageSetupScreen {
 
         match self.state.method {
             StorageMethod::GitHub => self.handle_github_form_input(action),
+            StorageMethod::Restore => self.handle_restore_form_input(action),
             StorageMethod::Local => self.handle_local_form_input(action),
         }
     }
 
+    /// Handle restore form input (character input handled at top of `handle_form_event`)
+    fn handle_restore_form_input(&mut self, action: Option<Action>) -> Result<ScreenAction> {
+        let Some(action) = action else {
+            return Ok(ScreenAction::None);
+        };
+        let has_repos = self.state.restore_repos.is_some();
+
+        match (self.state.restore_field, action) {
+            (RestoreField::Token, Action::NextTab) if has_repos => {
+                self.state.restore_field = RestoreField::Repos;
+            }
+            (RestoreField::Token, Action::PrevTab) => {
+                self.state.focus = StorageSetupFocus::MethodList;
+            }
+            (RestoreField::Repos, Action::NextTab | Action::PrevTab) => {
+                self.state.restore_field = RestoreField::Token;
+            }
+            (RestoreField::Repos, Action::MoveUp) => self.move_restore_selection(-1),
+            (RestoreField::Repos, Action::MoveDown) => self.move_restore_selection(1),
+            (RestoreField::Repos, Action::PageUp) => self.move_restore_selection(-10),
+            (RestoreField::Repos, Action::PageDown) => self.move_restore_selection(10),
+            (RestoreField::Token, act) if !self.state.is_reconfiguring => {
+                let input = &mut self.state.token_input;
+                match act {
+                    Action::Backspace => input.backspace(),
+                    Action::DeleteChar => input.delete(),
+                    Action::MoveLeft => input.move_left(),
+                    Action::MoveRight => input.move_right(),
+                    Action::Home => input.move_home(),
+                    Action::End => input.move_end(),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
     /// Handle GitHub form input (character input handled at top of `handle_form_event`)
     fn handle_github_form_input(&mut self, action: Option<Action>) -> Result<ScreenAction> {
         // Handle field navigation
@@ -1181,12 +1616,22 @@ impl StorageSetupScreen {
                     return Ok(ScreenAction::None);
                 }
 
-                // Return action to start GitHub setup
-                Ok(ScreenAction::StartGitHubSetup {
-                    token,
-                    repo_name,
-                    is_private: self.state.is_private,
-                })
+                // Return action to start (or resume) GitHub setup
+                Ok(self.github_setup_action(token, repo_name, self.state.is_private))
+            }
+            StorageMethod::Restore => {
+                if self.state.restore_field == RestoreField::Token {
+                    return Ok(self.start_repo_listing());
+                }
+                let Some(repo) = self.selected_restore_repo() else {
+                    return Ok(ScreenAction::None);
+                };
+                // The repository exists, so setup clones it and then offers its profiles
+                Ok(self.github_setup_action(
+                    self.state.token_input.text_trimmed().to_string(),
+                    repo.name.clone(),
+                    repo.private,
+                ))
             }
             StorageMethod::Local => {
                 let path_str = self.state.local_path_input.text_trimmed();
@@ -1353,6 +1798,22 @@ impl Screen for StorageSetupScreen {
                                 self.key_display(ctx, Action::Cancel),
                             )
                         }
+                    } else if self.state.method == StorageMethod::Restore {
+                        if self.state.restore_field == RestoreField::Repos {
+                            format!(
+                                "{}: Navigate | {}: Restore | {}: Token | {}: Back",
+                                ctx.config.keymap.navigation_display(),
+                                self.key_display(ctx, Action::Confirm),
+                                self.key_display(ctx, Action::NextTab),
+                                self.key_display(ctx, Action::Cancel),
+                            )
+                        } else {
+                            format!(
+                                "{}: List Repositories | {}: Back",
+                                self.key_display(ctx, Action::Confirm),
+                                self.key_display(ctx, Action::Cancel),
+                            )
+                        }
                     } else {
                         format!(
                             "{}: Next Field | {}: Submit | {}: Back",
@@ -1424,6 +1885,9 @@ impl Screen for StorageSetupScreen {
                 // In fresh setup, all text fields are editable
                 true
             }
+            StorageMethod::Restore => {
+                self.state.restore_field == RestoreField::Token && !self.state.is_reconfiguring
+            }
             StorageMethod::Local => {
                 // Local path is editable only in fresh setup
                 !self.state.is_reconfiguring
@@ -1445,7 +1909,9 @@ impl Screen for StorageSetupScreen {
             if ctx.config.github.is_some() {
                 // GitHub mode
                 self.state.method = StorageMethod::GitHub;
-                self.state.menu_state.select(Some(0));
+                self.state
+                    .menu_state
+                    .select(Some(StorageMethod::GitHub.index()));
 
                 if let Some(ref github) = ctx.config.github {
                     // Pre-fill token (masked display - actual token not shown)
@@ -1463,7 +1929,9 @@ impl Screen for StorageSetupScreen {
             } else {
                 // Local mode
                 self.state.method = StorageMethod::Local;
-                self.state.menu_state.select(Some(1));
+                self.state
+                    .menu_state
+                    .select(Some(StorageMethod::Local.index()));
                 self.state.local_path_input =
                     TextInput::with_text(ctx.config.repo_path.to_string_lossy().to_string());
             }
@@ -1473,8 +1941,11 @@ impl Screen for StorageSetupScreen {
             self.state.step = StorageSetupStep::Input;
             self.state.setup_data = None;
         } else {
-            // Fresh setup - reset to defaults
+            // Fresh setup - reset to defaults, picking up an unfinished one
             self.reset();
+            if let Some(progress) = SetupProgress::load() {
+                self.offer_resume(progress);
+            }
         }
         Ok(())
     }
@@ -1487,14 +1958,16 @@ mod tests {
     #[test]
     fn test_storage_method_index() {
         assert_eq!(StorageMethod::GitHub.index(), 0);
-        assert_eq!(StorageMethod::Local.index(), 1);
+        assert_eq!(StorageMethod::Restore.index(), 1);
+        assert_eq!(StorageMethod::Local.index(), 2);
     }
 
     #[test]
     fn test_storage_method_from_index() {
         assert_eq!(StorageMethod::from_index(0), Some(StorageMethod::GitHub));
-        assert_eq!(StorageMethod::from_index(1), Some(StorageMethod::Local));
-        assert_eq!(StorageMethod::from_index(2), None);
+        assert_eq!(StorageMethod::from_index(1), Some(StorageMethod::Restore));
+        assert_eq!(StorageMethod::from_index(2), Some(StorageMethod::Local));
+        assert_eq!(StorageMethod::from_index(3), None);
     }
 
     #[test]
@@ -1504,6 +1977,81 @@ mod tests {
         assert_eq!(GitHubField::Token.prev(), GitHubField::Visibility);
     }
 
+    #[test]
+    fn test_restore_requires_valid_token() {
+        let mut screen = StorageSetupScreen::new();
+        screen.state.method = StorageMethod::Restore;
+        screen.state.token_input = TextInput::with_text("not-a-token");
+
+        assert!(matches!(
+            screen.handle_submit().unwrap(),
+            ScreenAction::None
+        ));
+        assert!(!screen.is_busy());
+        assert!(screen.state.error_message.is_some());
+    }
+
+    #[test]
+    fn test_restore_selected_repo_starts_setup() {
+        let mut screen = StorageSetupScreen::new();
+        screen.state.method = StorageMethod::Restore;
+        screen.state.restore_field = RestoreField::Repos;
+        screen.state.token_input = TextInput::with_text("ghp_abc");
+        let repo = |name: &str, private| GitHubRepo {
+            name: name.to_string(),
+            full_name: format!("me/{name}"),
+            default_branch: "main".to_string(),
+            private,
+            description: None,
+        };
+        screen.state.restore_repos = Some(vec![repo("dotfiles", true), repo("site", false)]);
+        screen.state.restore_list_state.select(Some(1));
+
+        match screen.handle_submit().unwrap() {
+            ScreenAction::StartGitHubSetup {
+                token,
+                repo_name,
+                is_private,
+            } => {
+                assert_eq!(token, "ghp_abc");
+                assert_eq!(repo_name, "site");
+                assert!(!is_private);
+            }
+            other => panic!("Expected StartGitHubSetup, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unchanged_inputs_resume_setup() {
+        let mut screen = StorageSetupScreen::new();
+        let data = GitHubSetupData {
+            token: "ghp_abc".to_string(),
+            repo_name: "dotfiles".to_string(),
+            username: Some("me".to_string()),
+            repo_exists: Some(false),
+            is_private: true,
+            delay_until: None,
+            is_new_repo: true,
+        };
+        screen.offer_resume(SetupProgress::new(GitHubSetupStep::InitializingRepo, &data));
+        assert_eq!(screen.state.focus, StorageSetupFocus::Form);
+        assert_eq!(screen.state.token_input.text(), "ghp_abc");
+
+        match screen.handle_submit().unwrap() {
+            ScreenAction::ResumeGitHubSetup { progress } => {
+                assert_eq!(progress.resume_step(), GitHubSetupStep::InitializingRepo);
+            }
+            other => panic!("Expected ResumeGitHubSetup, got {other:?}"),
+        }
+
+        // Another repository starts over
+        screen.state.repo_name_input = TextInput::with_text("other");
+        assert!(matches!(
+            screen.handle_submit().unwrap(),
+            ScreenAction::StartGitHubSetup { .. }
+        ));
+    }
+
     #[test]
     fn test_default_state() {
         let screen = StorageSetupScreen::new();
@@ -5,21 +5,31 @@
 use crate::components::file_preview::FilePreview;
 use crate::components::footer::Footer;
 use crate::components::header::Header;
+use crate::components::popup::Popup;
+use crate::config::Config;
+use crate::git::DIFF_CHUNK_BYTES;
+use crate::icons::Icons;
 use crate::screens::screen_trait::{RenderContext, Screen, ScreenAction, ScreenContext};
+use crate::services::git_service::{SyncProgress, SyncResult, SyncSelection};
+use crate::services::{GitService, TokenService};
 use crate::styles::{theme as ui_theme, LIST_HIGHLIGHT_SYMBOL};
 use crate::ui::{Screen as ScreenId, SyncWithRemoteState};
 use crate::utils::{
     create_split_layout, create_standard_layout, focused_border_style, unfocused_border_style,
+    DoubleClick, MouseRegions, ScrollbarDrag, TextInput,
 };
+use crate::widgets::ProgressBar;
 use anyhow::Result;
 use crossterm::event::Event;
 use ratatui::layout::{Alignment, Position, Rect};
 use ratatui::prelude::*;
 use ratatui::widgets::{
-    Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+    Block, Borders, Clear, List, ListItem, Padding, Paragraph, Scrollbar, ScrollbarOrientation,
     StatefulWidget, Wrap,
 };
 use ratatui::Frame;
+use std::fmt::Write as _;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 
 /// Focus area in sync with remote screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -28,6 +38,15 @@ pub enum SyncFocus {
     Preview,
 }
 
+/// Columns taken by the highlight symbol and checkbox at the start of a row
+const CHECKBOX_COLUMNS: u16 = 6;
+
+/// Message from the background sync thread
+enum SyncMessage {
+    Progress(crate::services::git_service::SyncProgress),
+    Done(crate::services::git_service::SyncResult),
+}
+
 /// Sync with remote screen controller.
 ///
 /// This screen handles reviewing and syncing changes with the remote repository.
@@ -41,6 +60,18 @@ pub struct SyncWithRemoteScreen {
     list_pane_area: Option<Rect>,
     /// Stored preview pane area for mouse hit-testing
     preview_pane_area: Option<Rect>,
+    /// Receiver for the running background sync
+    sync_rx: Option<Receiver<SyncMessage>>,
+    /// Clickable rows of the changed files list
+    file_regions: MouseRegions<usize>,
+    /// Double-clicks on changed files toggle them
+    file_clicks: DoubleClick<usize>,
+    /// Stored commit message popup area for mouse hit-testing
+    commit_editor_area: Option<Rect>,
+    /// Dragging and clicking the changed files scrollbar
+    list_scrollbar: ScrollbarDrag,
+    /// Dragging and clicking the preview scrollbar
+    preview_scrollbar: ScrollbarDrag,
 }
 
 impl SyncWithRemoteScreen {
@@ -52,6 +83,12 @@ impl SyncWithRemoteScreen {
             focus: SyncFocus::FilesList,
             list_pane_area: None,
             preview_pane_area: None,
+            sync_rx: None,
+            file_regions: MouseRegions::new(),
+            file_clicks: DoubleClick::new(),
+            commit_editor_area: None,
+            list_scrollbar: ScrollbarDrag::new(),
+            preview_scrollbar: ScrollbarDrag::new(),
         }
     }
 
@@ -72,12 +109,90 @@ impl SyncWithRemoteScreen {
         self.focus = SyncFocus::FilesList;
         self.list_pane_area = None;
         self.preview_pane_area = None;
+        self.sync_rx = None;
+        self.file_regions.clear();
+        self.commit_editor_area = None;
+    }
+
+    /// Whether a sync is running in the background
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.sync_rx.is_some()
+    }
+
+    /// Poll the background sync for progress and its final result.
+    pub fn tick(&mut self, config: &Config) -> ScreenAction {
+        let Some(rx) = &self.sync_rx else {
+            return ScreenAction::None;
+        };
+
+        let result = loop {
+            match rx.try_recv() {
+                Ok(SyncMessage::Progress(SyncProgress::Stage(stage))) => {
+                    self.state.sync_progress = Some(stage);
+                    self.state.transfer_progress = None;
+                }
+                Ok(SyncMessage::Progress(SyncProgress::Transfer(progress))) => {
+                    self.state.transfer_progress = Some(progress);
+                }
+                Ok(SyncMessage::Done(done)) => break done,
+                Err(TryRecvError::Empty) => return ScreenAction::Refresh,
+                Err(TryRecvError::Disconnected) => {
+                    break SyncResult {
+                        success: false,
+                        message: "Error: Sync stopped unexpectedly.".to_string(),
+                        pulled_count: None,
+                    }
+                }
+            }
+        };
+
+        // Update state with result
+        self.sync_rx = None;
+        self.state.is_syncing = false;
+        self.state.sync_progress = None;
+        self.state.transfer_progress = None;
+        if !result.success
+            && TokenService::uses_token(config)
+            && TokenService::is_auth_failure(&result.message)
+        {
+            return ScreenAction::Reauthenticate;
+        }
+        self.state.sync_result = Some(result.message);
+        self.state.pulled_changes_count = result.pulled_count;
+        self.state.show_result_popup = true;
+        self.state.result_scroll = 0; // Reset scroll for new result
+        ScreenAction::Refresh
     }
 
     /// Load changed files from git repository
     pub fn load_changed_files(&mut self, ctx: &ScreenContext) {
-        use crate::services::GitService;
+        // Edited decrypted copies and managed blocks only show up once copied
+        // back, and changed files need their new hashes in the manifest
+        if !ctx.config.read_only {
+            if let Err(e) = crate::utils::sops::reencrypt_changed(&ctx.config.repo_path) {
+                tracing::warn!("Failed to re-encrypt sops files: {}", e);
+            }
+            if let Err(e) = crate::utils::managed_block::capture_changed(&ctx.config.repo_path) {
+                tracing::warn!("Failed to read managed blocks: {}", e);
+            }
+            if let Err(e) = crate::utils::integrity::record(&ctx.config.repo_path) {
+                tracing::warn!("Failed to record content hashes: {}", e);
+            }
+        }
         self.state.changed_files = GitService::load_changed_files(&ctx.config.repo_path);
+        self.state.excluded_files.clear();
+        self.state.excluded_hunks.clear();
+        // Offer to ignore junk among new files, once per visit
+        if !ctx.config.read_only && !self.state.junk_prompt_dismissed {
+            self.state.junk_patterns = crate::utils::repo_gitignore::junk_patterns(
+                self.state
+                    .changed_files
+                    .iter()
+                    .filter(|entry| entry.starts_with("A "))
+                    .map(|entry| GitService::entry_path(entry)),
+            );
+        }
         // Select first item if list is not empty
         if !self.state.changed_files.is_empty() {
             self.state.list_state.select(Some(0));
@@ -85,10 +200,178 @@ impl SyncWithRemoteScreen {
         }
     }
 
+    /// Whether a changed file entry is checked for the next sync
+    fn is_included(&self, file_info: &str) -> bool {
+        !self
+            .state
+            .excluded_files
+            .contains(GitService::entry_path(file_info))
+    }
+
+    /// Changed file entries checked for the next sync
+    fn included_files(&self) -> Vec<String> {
+        self.state
+            .changed_files
+            .iter()
+            .filter(|file| self.is_included(file))
+            .cloned()
+            .collect()
+    }
+
+    /// Number of changed files checked for the next sync
+    fn included_count(&self) -> usize {
+        self.state
+            .changed_files
+            .iter()
+            .filter(|file| self.is_included(file))
+            .count()
+    }
+
+    /// Check or uncheck a changed file
+    fn toggle_file(&mut self, index: usize) {
+        if let Some(file_info) = self.state.changed_files.get(index) {
+            let path = GitService::entry_path(file_info).to_string();
+            self.state.excluded_hunks.remove(&path);
+            if !self.state.excluded_files.remove(&path) {
+                self.state.excluded_files.insert(path);
+            }
+        }
+    }
+
+    /// Path of the file selected in the list
+    fn selected_path(&self) -> Option<String> {
+        let index = self.state.list_state.selected()?;
+        let file_info = self.state.changed_files.get(index)?;
+        Some(GitService::entry_path(file_info).to_string())
+    }
+
+    /// Line numbers of the hunk headers ("@@ ... @@") in the diff preview
+    fn hunk_lines(&self) -> Vec<usize> {
+        self.state
+            .diff_content
+            .as_deref()
+            .map(|diff| {
+                diff.lines()
+                    .enumerate()
+                    .filter(|(_, line)| line.starts_with("@@"))
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Hunk at the top of the preview: the last one starting at or above
+    /// the scroll position
+    fn current_hunk(&self, hunk_lines: &[usize]) -> Option<usize> {
+        if hunk_lines.is_empty() {
+            return None;
+        }
+        let above = hunk_lines
+            .iter()
+            .take_while(|&&line| line <= self.state.preview_scroll)
+            .count();
+        Some(above.saturating_sub(1))
+    }
+
+    /// Whether hunk `hunk` of `path` is checked for the next sync
+    fn is_hunk_included(&self, path: &str, hunk: usize) -> bool {
+        !self.state.excluded_files.contains(path)
+            && !self
+                .state
+                .excluded_hunks
+                .get(path)
+                .is_some_and(|hunks| hunks.contains(&hunk))
+    }
+
+    /// Check or uncheck the hunk at the top of the diff preview.
+    ///
+    /// Checking a hunk of an unchecked file checks only that hunk, and
+    /// unchecking every hunk unchecks the whole file.
+    fn toggle_hunk(&mut self, ctx: &ScreenContext) {
+        // Every hunk has to be known to check or uncheck the whole file
+        if self.state.diff_truncated {
+            self.state.diff_limit = usize::MAX;
+            self.load_diff(ctx);
+        }
+        let hunk_lines = self.hunk_lines();
+        let (Some(path), Some(hunk)) = (self.selected_path(), self.current_hunk(&hunk_lines))
+        else {
+            return;
+        };
+        let hunk_count = hunk_lines.len();
+
+        if self.state.excluded_files.remove(&path) {
+            let others = (0..hunk_count).filter(|&i| i != hunk).collect();
+            self.state.excluded_hunks.insert(path, others);
+            return;
+        }
+
+        let skipped = self.state.excluded_hunks.entry(path.clone()).or_default();
+        if !skipped.remove(&hunk) {
+            skipped.insert(hunk);
+        }
+        if skipped.len() >= hunk_count {
+            self.state.excluded_hunks.remove(&path);
+            self.state.excluded_files.insert(path);
+        } else if skipped.is_empty() {
+            self.state.excluded_hunks.remove(&path);
+        }
+    }
+
+    /// Diff preview with each hunk header marked as checked or skipped
+    fn annotated_diff(&self, path: &str, preview_focused: bool, icons: &Icons) -> Option<String> {
+        let diff = self.state.diff_content.as_deref()?;
+        let hunk_lines = self.hunk_lines();
+        if hunk_lines.is_empty() {
+            return Some(diff.to_string());
+        }
+        let current = self.current_hunk(&hunk_lines);
+        let mut hunk = 0;
+        let mut annotated = String::with_capacity(diff.len());
+        for line in diff.lines() {
+            annotated.push_str(line);
+            if line.starts_with("@@") {
+                let mark = if self.is_hunk_included(path, hunk) {
+                    icons.check()
+                } else {
+                    "(skipped)"
+                };
+                let _ = write!(annotated, "  hunk {}/{} {mark}", hunk + 1, hunk_lines.len());
+                if preview_focused && current == Some(hunk) {
+                    annotated.push_str(" ◀");
+                }
+                hunk += 1;
+            }
+            annotated.push('\n');
+        }
+        Some(annotated)
+    }
+
+    /// Whether confirming would do anything: checked files to commit or
+    /// commits to pull/push. A pull-only machine can always pull.
+    fn can_sync(&self, read_only: bool) -> bool {
+        if read_only {
+            return true;
+        }
+        let has_remote_changes = self
+            .state
+            .git_status
+            .as_ref()
+            .is_some_and(|status| status.ahead > 0 || status.behind > 0);
+        self.included_count() > 0 || has_remote_changes
+    }
+
     /// Update the diff preview based on the selected file
     fn update_diff_preview(&mut self, ctx: &ScreenContext) {
-        use crate::services::GitService;
+        self.state.diff_limit = DIFF_CHUNK_BYTES;
+        self.load_diff(ctx);
+        self.state.preview_scroll = 0;
+    }
+
+    /// Load the diff of the selected file, up to the current limit
+    fn load_diff(&mut self, ctx: &ScreenContext) {
         self.state.diff_content = None;
+        self.state.diff_truncated = false;
 
         let selected_idx = match self.state.list_state.selected() {
             Some(idx) => idx,
@@ -100,35 +383,91 @@ impl SyncWithRemoteScreen {
         }
 
         let file_info = &self.state.changed_files[selected_idx];
-        if let Some(diff) = GitService::get_diff_for_file(&ctx.config.repo_path, file_info) {
-            self.state.diff_content = Some(diff);
-            self.state.preview_scroll = 0;
+        if let Some(diff) =
+            GitService::get_diff_for_file(&ctx.config.repo_path, file_info, self.state.diff_limit)
+        {
+            self.state.diff_content = Some(diff.text);
+            self.state.diff_truncated = diff.truncated;
+        }
+    }
+
+    /// Load the next chunk of a long diff once its end is on screen
+    fn show_more_diff(&mut self, ctx: &ScreenContext) {
+        if !self.state.diff_truncated {
+            return;
         }
+        let total_lines = self
+            .state
+            .diff_content
+            .as_deref()
+            .map_or(0, |diff| diff.lines().count());
+        // borders(2) + padding, which the compact layout drops
+        let inset = 2 + crate::styles::padding(Padding::uniform(1)).top * 2;
+        let visible_height = self
+            .preview_pane_area
+            .map_or(0, |area| area.height.saturating_sub(inset) as usize);
+        if self.state.preview_scroll + visible_height >= total_lines {
+            self.state.diff_limit = self.state.diff_limit.saturating_add(DIFF_CHUNK_BYTES);
+            self.load_diff(ctx);
+        }
+    }
+
+    /// Note under a diff that was cut short, saying how to load the rest
+    fn truncation_note(&self) -> String {
+        format!(
+            "\n⋯ Showing the first {} of this diff. Scroll down to load more.\n",
+            crate::utils::binary::format_size(self.state.diff_limit as u64)
+        )
+    }
+
+    /// Open the commit message editor, pre-filled with the generated message
+    /// for the checked files.
+    fn open_commit_editor(&mut self, config: &Config) {
+        let message = GitService::commit_message_for(config, &self.included_files());
+        self.state.commit_editor = Some(TextInput::with_text(message));
     }
 
-    /// Start syncing changes (push/pull)
-    fn start_sync(&mut self, ctx: &ScreenContext) -> Result<()> {
-        use crate::services::GitService;
+    /// Start syncing changes (push/pull) on a background thread.
+    ///
+    /// Only checked files are committed; unchecked ones stay uncommitted.
+    /// Without a `message`, the commit message is generated.
+    fn start_sync(&mut self, ctx: &ScreenContext, message: Option<String>) {
         use tracing::info;
 
         info!("Starting sync operation");
 
+        let selection = if self.state.excluded_files.is_empty()
+            && self.state.excluded_hunks.is_empty()
+            && message.is_none()
+        {
+            None
+        } else {
+            Some(SyncSelection {
+                paths: self
+                    .included_files()
+                    .iter()
+                    .map(|file| GitService::entry_path(file).to_string())
+                    .collect(),
+                skipped_hunks: self.state.excluded_hunks.clone(),
+                message,
+            })
+        };
+
         // Mark as syncing
         self.state.is_syncing = true;
-        self.state.sync_progress = Some("Syncing...".to_string());
-
-        // Perform sync using service
-        let result = GitService::sync(ctx.config);
-
-        // Update state with result
-        self.state.is_syncing = false;
-        self.state.sync_progress = None;
-        self.state.sync_result = Some(result.message);
-        self.state.pulled_changes_count = result.pulled_count;
-        self.state.show_result_popup = true;
-        self.state.result_scroll = 0; // Reset scroll for new result
+        self.state.sync_progress = Some("Preparing sync...".to_string());
+        self.state.transfer_progress = None;
 
-        Ok(())
+        let (tx, rx) = mpsc::channel();
+        let config = ctx.config.clone();
+        std::thread::spawn(move || {
+            let report = |progress| {
+                let _ = tx.send(SyncMessage::Progress(progress));
+            };
+            let result = GitService::sync_with_progress(&config, selection.as_ref(), &report);
+            let _ = tx.send(SyncMessage::Done(result));
+        });
+        self.sync_rx = Some(rx);
     }
 
     /// Render the result popup
@@ -177,6 +516,185 @@ impl SyncWithRemoteScreen {
         Ok(())
     }
 
+    /// Render the prompt offering to add junk patterns to .gitignore
+    fn render_junk_prompt(&self, frame: &mut Frame, area: Rect, config: &Config) {
+        use crate::widgets::{Dialog, DialogVariant};
+
+        let mut content = String::from(
+            "Some new files look like junk (OS files, editor backups or caches).\n\
+             Add these patterns to the repository's .gitignore?\n",
+        );
+        for pattern in &self.state.junk_patterns {
+            let _ = write!(content, "\n  {pattern}");
+        }
+        let k = |a| config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Add to .gitignore  {}: Not now",
+            k(crate::keymap::Action::Yes),
+            k(crate::keymap::Action::No)
+        );
+        let dialog = Dialog::new("Ignore Junk Files", &content)
+            .height(40)
+            .variant(DialogVariant::Warning)
+            .footer(&footer_text);
+        frame.render_widget(dialog, area);
+    }
+
+    /// Answer the junk prompt: add the patterns to .gitignore (so the files
+    /// drop out of the list) or leave them for this visit.
+    fn resolve_junk_prompt(&mut self, ctx: &ScreenContext, accept: bool) -> ScreenAction {
+        let patterns = std::mem::take(&mut self.state.junk_patterns);
+        self.state.junk_prompt_dismissed = true;
+        if !accept {
+            return ScreenAction::Refresh;
+        }
+        match crate::utils::repo_gitignore::append(&ctx.config.repo_path, &patterns) {
+            Ok(added) => {
+                self.load_changed_files(ctx);
+                ScreenAction::ShowToast {
+                    message: format!("Added {} pattern(s) to .gitignore", added.len()),
+                    variant: crate::widgets::ToastVariant::Success,
+                }
+            }
+            Err(e) => ScreenAction::ShowToast {
+                message: format!("Failed to update .gitignore: {e}"),
+                variant: crate::widgets::ToastVariant::Error,
+            },
+        }
+    }
+
+    /// Render the commit message editor popup
+    fn render_commit_editor(&mut self, frame: &mut Frame, area: Rect, ctx: &RenderContext) {
+        let Some(editor) = &self.state.commit_editor else {
+            return;
+        };
+        let t = ui_theme();
+        let k = |a| ctx.config.keymap.get_key_display_for_action(a);
+        let footer_text = format!(
+            "{}: Commit & Sync | Alt+{}: New Line | {}: Cancel",
+            k(crate::keymap::Action::Confirm),
+            k(crate::keymap::Action::Confirm),
+            k(crate::keymap::Action::Cancel)
+        );
+
+        let Some(result) = Popup::new()
+            .width(60)
+            .height(50)
+            // 2 (summary) + 5 (editor) + borders/title/footer (~5).
+            .min_height(12)
+            .min_width(50)
+            .title("Commit Message")
+            .dim_background(true)
+            .footer(&footer_text)
+            .render(frame, area)
+        else {
+            return;
+        };
+        self.commit_editor_area = Some(result.content_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(3)])
+            .split(result.content_area);
+
+        let included = self.included_count();
+        let summary = format!(
+            "Committing {included} file{}",
+            if included == 1 { "" } else { "s" }
+        );
+        frame.render_widget(Paragraph::new(summary).style(t.muted_style()), chunks[0]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(focused_border_style())
+            .border_type(ui_theme().border_type(true))
+            .title(" Message ");
+        let inner = block.inner(chunks[1]);
+
+        // Keep the cursor in view
+        let (line, col) = editor.cursor_line_col();
+        let scroll_y = line.saturating_sub(inner.height.saturating_sub(1) as usize);
+        let scroll_x = col.saturating_sub(inner.width.saturating_sub(1) as usize);
+
+        let paragraph = Paragraph::new(editor.text())
+            .style(t.text_style())
+            .block(block)
+            .scroll((scroll_y as u16, scroll_x as u16));
+        frame.render_widget(paragraph, chunks[1]);
+        frame.set_cursor_position(Position::new(
+            inner.x + (col - scroll_x) as u16,
+            inner.y + (line - scroll_y) as u16,
+        ));
+    }
+
+    /// Handle input while the commit message editor is open
+    fn handle_commit_editor_event(&mut self, event: Event, ctx: &ScreenContext) -> ScreenAction {
+        use crate::keymap::Action;
+        use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let Some(editor) = self.state.commit_editor.as_mut() else {
+                    return ScreenAction::None;
+                };
+
+                // Alt+Enter (or Shift+Enter where the terminal reports it) adds a line
+                if key.code == KeyCode::Enter
+                    && key
+                        .modifiers
+                        .intersects(KeyModifiers::ALT | KeyModifiers::SHIFT)
+                {
+                    editor.insert_newline();
+                    return ScreenAction::None;
+                }
+
+                // Plain characters are always typed, even if bound to an action
+                if let KeyCode::Char(c) = key.code {
+                    if !key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER)
+                    {
+                        editor.insert_char(c);
+                        return ScreenAction::None;
+                    }
+                }
+
+                let action = ctx.config.keymap.get_action(key.code, key.modifiers);
+                match action {
+                    Some(Action::Cancel) => {
+                        self.state.commit_editor = None;
+                    }
+                    Some(Action::Confirm) => {
+                        let message = editor.text_trimmed().to_string();
+                        if !message.is_empty() {
+                            self.state.commit_editor = None;
+                            self.start_sync(ctx, Some(message));
+                        }
+                    }
+                    Some(Action::MoveUp) => editor.move_up(),
+                    Some(Action::MoveDown) => editor.move_down(),
+                    _ => {
+                        editor.handle_key_with_action(key.code, key.modifiers, action);
+                    }
+                }
+            }
+            Event::Mouse(mouse) => {
+                // Clicking outside the popup cancels
+                if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+                    let pos = Position::new(mouse.column, mouse.row);
+                    if !self
+                        .commit_editor_area
+                        .is_some_and(|area| area.contains(pos))
+                    {
+                        self.state.commit_editor = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
     /// Render the syncing progress indicator
     fn render_progress(&self, frame: &mut Frame, content_chunk: Rect) {
         let t = ui_theme();
@@ -185,20 +703,35 @@ impl SyncWithRemoteScreen {
             .sync_progress
             .as_deref()
             .unwrap_or("Processing...");
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ui_theme().border_type(false))
+            .title(" Progress ")
+            .title_alignment(Alignment::Center)
+            .border_style(focused_border_style())
+            .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                2, 2, 2, 2,
+            )));
+        let inner = block.inner(content_chunk);
+        frame.render_widget(block, content_chunk);
+
+        let [text_area, _, bar_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
         let progress_para = Paragraph::new(progress_text)
             .style(Style::default().fg(t.warning))
             .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true })
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(ui_theme().border_type(false))
-                    .title(" Progress ")
-                    .title_alignment(Alignment::Center)
-                    .border_style(focused_border_style())
-                    .padding(ratatui::widgets::Padding::new(2, 2, 2, 2)),
-            );
-        frame.render_widget(progress_para, content_chunk);
+            .wrap(Wrap { trim: true });
+        frame.render_widget(progress_para, text_area);
+
+        if let Some(progress) = &self.state.transfer_progress {
+            frame.render_widget(ProgressBar::new(progress), bar_area);
+        }
     }
 
     /// Render the changed files list and diff preview
@@ -227,7 +760,9 @@ impl SyncWithRemoteScreen {
                     .border_type(ui_theme().border_type(false))
                     .title(" No Changes ")
                     .title_alignment(Alignment::Center)
-                    .padding(ratatui::widgets::Padding::new(2, 2, 2, 2)),
+                    .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                        2, 2, 2, 2,
+                    ))),
             );
             frame.render_widget(empty_message, content_chunk);
             return Ok(());
@@ -259,7 +794,9 @@ impl SyncWithRemoteScreen {
                         .border_type(ui_theme().border_type(false))
                         .title(" Sync Status ")
                         .title_alignment(Alignment::Center)
-                        .padding(ratatui::widgets::Padding::new(2, 2, 2, 2)),
+                        .padding(crate::styles::padding(ratatui::widgets::Padding::new(
+                            2, 2, 2, 2,
+                        ))),
                 );
             frame.render_widget(status_para, content_chunk);
             return Ok(());
@@ -291,12 +828,21 @@ impl SyncWithRemoteScreen {
             .content_length(total_items)
             .position(selected_index);
 
+        let icons = Icons::from_config(ctx.config);
+        let read_only = ctx.config.read_only;
         let items: Vec<ListItem> = self
             .state
             .changed_files
             .iter()
             .map(|file| {
-                let style = if file.starts_with("A ") {
+                if read_only {
+                    // Nothing is committed here, so there's nothing to check
+                    return ListItem::new(file.as_str()).style(t.muted_style());
+                }
+                let included = self.is_included(file);
+                let style = if !included {
+                    t.muted_style()
+                } else if file.starts_with("A ") {
                     Style::default().fg(t.success) // Added
                 } else if file.starts_with("M ") {
                     Style::default().fg(t.warning) // Modified
@@ -305,22 +851,42 @@ impl SyncWithRemoteScreen {
                 } else {
                     t.text_style()
                 };
-                ListItem::new(file.as_str()).style(style)
+                let check = if included {
+                    icons.check()
+                } else {
+                    icons.uncheck()
+                };
+                let partial = if self
+                    .state
+                    .excluded_hunks
+                    .contains_key(GitService::entry_path(file))
+                {
+                    " (partial)"
+                } else {
+                    ""
+                };
+                ListItem::new(format!("{check} {file}{partial}")).style(style)
             })
             .collect();
 
+        let included = self.included_count();
+        let title = if read_only {
+            format!(" Local Changes ({total_items}, not synced) ")
+        } else if included == total_items {
+            format!(" Changed Files ({total_items}) ")
+        } else {
+            format!(" Changed Files ({included}/{total_items} selected) ")
+        };
+
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(list_border_style)
                     .border_type(ui_theme().border_type(list_focused))
-                    .title(format!(
-                        " Changed Files ({}) ",
-                        self.state.changed_files.len()
-                    ))
+                    .title(title)
                     .title_alignment(Alignment::Center)
-                    .padding(ratatui::widgets::Padding::new(1, 1, 1, 1)),
+                    .padding(crate::styles::padding(Padding::new(1, 1, 1, 1))),
             )
             .highlight_style(t.highlight_style())
             .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
@@ -334,14 +900,34 @@ impl SyncWithRemoteScreen {
 
         // Render scrollbar
         frame.render_stateful_widget(
-            Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .begin_symbol(Some("↑"))
-                .end_symbol(Some("↓")),
+            self.list_scrollbar.style(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(Some("↑"))
+                    .end_symbol(Some("↓")),
+            ),
             list_area,
             &mut self.state.scrollbar_state,
         );
+        self.list_scrollbar.set(list_area, total_items);
+
+        // Populate mouse regions for list rows
+        self.file_regions.clear();
+        let inner = Block::default()
+            .borders(Borders::ALL)
+            .padding(crate::styles::padding(Padding::new(1, 1, 1, 1)))
+            .inner(list_area);
+        let scroll_offset = self.state.list_state.offset();
+        for i in scroll_offset..total_items {
+            let visible_row = (i - scroll_offset) as u16;
+            if visible_row >= inner.height {
+                break;
+            }
+            let row_area = Rect::new(inner.x, inner.y + visible_row, inner.width, 1);
+            self.file_regions.add(row_area, i);
+        }
 
         // Render Preview
+        self.preview_scrollbar.clear();
         if let Some(selected_idx) = self.state.list_state.selected() {
             if selected_idx < self.state.changed_files.len() {
                 let file_info = &self.state.changed_files[selected_idx];
@@ -351,15 +937,26 @@ impl SyncWithRemoteScreen {
                     let path_str = parts[1].trim();
                     let path = std::path::PathBuf::from(path_str);
                     let preview_title = format!("Diff: {path_str}");
+                    let mut diff = if read_only {
+                        self.state.diff_content.clone()
+                    } else {
+                        self.annotated_diff(path_str, preview_focused, &icons)
+                    };
+                    if self.state.diff_truncated {
+                        if let Some(diff) = diff.as_mut() {
+                            diff.push_str(&self.truncation_note());
+                        }
+                    }
 
                     FilePreview::render(
                         frame,
                         preview_area,
                         &path,
                         &mut self.state.preview_scroll,
+                        &mut self.preview_scrollbar,
                         preview_focused,
                         Some(&preview_title),
-                        self.state.diff_content.as_deref(),
+                        diff.as_deref(),
                         ctx.syntax_set,
                         ctx.syntax_theme,
                         ctx.config,
@@ -397,6 +994,8 @@ impl Screen for SyncWithRemoteScreen {
         // Header
         let description = if self.state.is_syncing {
             "Syncing with remote repository..."
+        } else if ctx.config.read_only {
+            "Pull-only machine: pull updates from the remote repository"
         } else {
             "Review changes before syncing with remote"
         };
@@ -417,6 +1016,10 @@ impl Screen for SyncWithRemoteScreen {
         // Render popups on top of the content (not instead of it)
         if self.state.show_result_popup {
             self.render_result_popup(frame, area, ctx.config)?;
+        } else if self.state.commit_editor.is_some() {
+            self.render_commit_editor(frame, area, ctx);
+        } else if !self.state.junk_patterns.is_empty() {
+            self.render_junk_prompt(frame, area, ctx.config);
         }
 
         // Footer
@@ -427,20 +1030,57 @@ impl Screen for SyncWithRemoteScreen {
         } else {
             false
         };
-        let can_sync = !self.state.changed_files.is_empty() || has_remote_changes;
+        let can_sync =
+            !self.state.changed_files.is_empty() || has_remote_changes || ctx.config.read_only;
+        let toggle_hint = if self.state.changed_files.is_empty() || ctx.config.read_only {
+            String::new()
+        } else if self.focus == SyncFocus::Preview {
+            format!(" | {}: Toggle Hunk", k(crate::keymap::Action::ToggleSelect))
+        } else {
+            format!(
+                " | {}: Toggle | {}: All | {}: None",
+                k(crate::keymap::Action::ToggleSelect),
+                k(crate::keymap::Action::SelectAll),
+                k(crate::keymap::Action::DeselectAll)
+            )
+        };
 
         let footer_text = if self.state.show_result_popup {
             "Press any key or click to close".to_string()
         } else if self.state.is_syncing {
             "Syncing with remote...".to_string()
+        } else if self.state.commit_editor.is_some() {
+            format!(
+                "{}: Commit & Sync | Alt+{}: New Line | {}: Cancel",
+                k(crate::keymap::Action::Confirm),
+                k(crate::keymap::Action::Confirm),
+                k(crate::keymap::Action::Cancel)
+            )
         } else if !can_sync {
-            format!("{}: Back to Main Menu", k(crate::keymap::Action::Cancel))
+            format!(
+                "{}: Force Sync | {}: Back to Main Menu",
+                k(crate::keymap::Action::ForceSync),
+                k(crate::keymap::Action::Cancel)
+            )
         } else {
             format!(
-                "{}: Sync with Remote | {}: Navigate | {}: Switch Pane | {}: Back",
+                "{}: {}{} | {}: Navigate | {}: Switch Pane | {}: {} Secrets | {}: Force Sync | {}: Back",
                 k(crate::keymap::Action::Confirm),
+                if ctx.config.read_only {
+                    "Pull from Remote"
+                } else {
+                    "Sync with Remote"
+                },
+                toggle_hint,
                 ctx.config.keymap.navigation_display(),
                 k(crate::keymap::Action::NextTab),
+                k(crate::keymap::Action::RevealSecrets),
+                if FilePreview::secrets_revealed() {
+                    "Mask"
+                } else {
+                    "Show"
+                },
+                k(crate::keymap::Action::ForceSync),
                 k(crate::keymap::Action::Cancel)
             )
         };
@@ -453,6 +1093,11 @@ impl Screen for SyncWithRemoteScreen {
         use crate::keymap::Action;
         use crossterm::event::{KeyEventKind, MouseButton, MouseEventKind};
 
+        // Nothing to do until the background sync finishes
+        if self.state.is_syncing {
+            return Ok(ScreenAction::None);
+        }
+
         // Result popup captures all events
         if self.state.show_result_popup {
             match event {
@@ -513,6 +1158,29 @@ impl Screen for SyncWithRemoteScreen {
             }
         }
 
+        // Commit message editor captures all events
+        if self.state.commit_editor.is_some() {
+            return Ok(self.handle_commit_editor_event(event, ctx));
+        }
+
+        // Junk prompt captures all keys
+        if !self.state.junk_patterns.is_empty() {
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press {
+                    match ctx.config.keymap.get_action(key.code, key.modifiers) {
+                        Some(Action::Yes | Action::Confirm) => {
+                            return Ok(self.resolve_junk_prompt(ctx, true));
+                        }
+                        Some(Action::No | Action::Cancel | Action::Quit) => {
+                            return Ok(self.resolve_junk_prompt(ctx, false));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
         // Normal mode: handle based on focus
         match event {
             Event::Key(key) if key.kind == KeyEventKind::Press => {
@@ -523,15 +1191,13 @@ impl Screen for SyncWithRemoteScreen {
                             return Ok(ScreenAction::Navigate(ScreenId::MainMenu));
                         }
                         Action::Confirm => {
-                            let has_remote_changes = if let Some(status) = &self.state.git_status {
-                                status.ahead > 0 || status.behind > 0
-                            } else {
-                                false
-                            };
-                            if !self.state.is_syncing
-                                && (!self.state.changed_files.is_empty() || has_remote_changes)
-                            {
-                                self.start_sync(ctx)?;
+                            if !self.state.is_syncing && self.can_sync(ctx.config.read_only) {
+                                if self.included_count() > 0 && !ctx.config.read_only {
+                                    self.open_commit_editor(ctx.config);
+                                } else {
+                                    // Nothing to commit, just pull and push
+                                    self.start_sync(ctx, None);
+                                }
                             }
                             return Ok(ScreenAction::None);
                         }
@@ -542,9 +1208,25 @@ impl Screen for SyncWithRemoteScreen {
                             };
                             return Ok(ScreenAction::None);
                         }
+                        Action::ForceSync if ctx.config.is_repo_configured() => {
+                            return Ok(ScreenAction::Navigate(ScreenId::ForceSync));
+                        }
+                        Action::RevealSecrets => {
+                            return Ok(ScreenAction::toggle_secrets());
+                        }
                         _ => {}
                     }
 
+                    // Nothing is committed on a pull-only machine
+                    if ctx.config.read_only
+                        && matches!(
+                            action,
+                            Action::ToggleSelect | Action::SelectAll | Action::DeselectAll
+                        )
+                    {
+                        return Ok(ScreenAction::pull_only_blocked());
+                    }
+
                     // Focus-specific actions
                     match self.focus {
                         SyncFocus::FilesList => match action {
@@ -587,14 +1269,36 @@ impl Screen for SyncWithRemoteScreen {
                                 self.state.list_state.select_last();
                                 self.update_diff_preview(ctx);
                             }
+                            Action::ToggleSelect => {
+                                if let Some(index) = self.state.list_state.selected() {
+                                    self.toggle_file(index);
+                                }
+                            }
+                            Action::SelectAll => {
+                                self.state.excluded_files.clear();
+                                self.state.excluded_hunks.clear();
+                            }
+                            Action::DeselectAll => {
+                                self.state.excluded_hunks.clear();
+                                self.state.excluded_files = self
+                                    .state
+                                    .changed_files
+                                    .iter()
+                                    .map(|file| GitService::entry_path(file).to_string())
+                                    .collect();
+                            }
                             _ => {}
                         },
                         SyncFocus::Preview => match action {
+                            Action::ToggleSelect => {
+                                self.toggle_hunk(ctx);
+                            }
                             Action::MoveUp | Action::ScrollUp => {
                                 self.state.preview_scroll =
                                     self.state.preview_scroll.saturating_sub(1);
                             }
                             Action::MoveDown | Action::ScrollDown => {
+                                self.show_more_diff(ctx);
                                 self.state.preview_scroll =
                                     self.state.preview_scroll.saturating_add(1);
                             }
@@ -603,6 +1307,7 @@ impl Screen for SyncWithRemoteScreen {
                                     self.state.preview_scroll.saturating_sub(20);
                             }
                             Action::PageDown => {
+                                self.show_more_diff(ctx);
                                 self.state.preview_scroll =
                                     self.state.preview_scroll.saturating_add(20);
                             }
@@ -610,6 +1315,7 @@ impl Screen for SyncWithRemoteScreen {
                                 self.state.preview_scroll = 0;
                             }
                             Action::GoToEnd => {
+                                self.show_more_diff(ctx);
                                 if let Some(content) = &self.state.diff_content {
                                     let total_lines = content.lines().count();
                                     let estimated_visible = 20;
@@ -626,8 +1332,49 @@ impl Screen for SyncWithRemoteScreen {
             }
             Event::Mouse(mouse) => {
                 let pos = Position::new(mouse.column, mouse.row);
+                let selected = self.state.list_state.selected().unwrap_or(0);
+                if let Some(index) = self.list_scrollbar.handle(mouse, selected) {
+                    self.focus = SyncFocus::FilesList;
+                    if index != selected {
+                        self.state.list_state.select(Some(index));
+                        self.update_diff_preview(ctx);
+                    }
+                    return Ok(ScreenAction::None);
+                }
+                if let Some(scroll) = self
+                    .preview_scrollbar
+                    .handle(mouse, self.state.preview_scroll)
+                {
+                    self.focus = SyncFocus::Preview;
+                    let down = scroll > self.state.preview_scroll;
+                    self.state.preview_scroll = scroll;
+                    if down {
+                        self.show_more_diff(ctx);
+                    }
+                    return Ok(ScreenAction::None);
+                }
                 match mouse.kind {
                     MouseEventKind::Down(MouseButton::Left) => {
+                        // Click a row to select it, or its checkbox (or double-click
+                        // the row) to toggle it
+                        if let Some(&index) = self.file_regions.hit_test(mouse.column, mouse.row) {
+                            self.focus = SyncFocus::FilesList;
+                            let inset = 1 + crate::styles::padding(Padding::uniform(1)).left;
+                            let checkbox_end = self
+                                .list_pane_area
+                                .map_or(0, |area| area.x + inset + CHECKBOX_COLUMNS);
+                            let toggle =
+                                mouse.column < checkbox_end || self.file_clicks.click(index);
+                            if toggle && !ctx.config.read_only {
+                                self.toggle_file(index);
+                            }
+                            if self.state.list_state.selected() != Some(index) {
+                                self.state.list_state.select(Some(index));
+                                self.update_diff_preview(ctx);
+                            }
+                            return Ok(ScreenAction::None);
+                        }
+
                         // Click to focus pane
                         if let Some(area) = self.preview_pane_area {
                             if area.contains(pos) {
@@ -653,6 +1400,7 @@ impl Screen for SyncWithRemoteScreen {
                         }
                         if let Some(area) = self.preview_pane_area {
                             if area.contains(pos) {
+                                self.show_more_diff(ctx);
                                 self.state.preview_scroll =
                                     self.state.preview_scroll.saturating_add(3);
                                 return Ok(ScreenAction::None);
@@ -685,7 +1433,7 @@ impl Screen for SyncWithRemoteScreen {
     }
 
     fn is_input_focused(&self) -> bool {
-        false // No text inputs on this screen
+        self.state.commit_editor.is_some()
     }
 }
 
@@ -700,6 +1448,19 @@ mod tests {
         assert!(screen.state.changed_files.is_empty());
     }
 
+    #[test]
+    fn test_commit_editor_prefilled_from_checked_files() {
+        let mut screen = SyncWithRemoteScreen::new();
+        screen.state.changed_files = vec!["M .zshrc".to_string(), "A .vimrc".to_string()];
+        screen.state.excluded_files.insert(".vimrc".to_string());
+
+        screen.open_commit_editor(&Config::default());
+        assert!(screen.is_input_focused());
+        let message = screen.state.commit_editor.as_ref().unwrap().text();
+        assert!(message.contains(".zshrc"));
+        assert!(!message.contains(".vimrc"));
+    }
+
     #[test]
     fn test_reset_state() {
         let mut screen = SyncWithRemoteScreen::new();
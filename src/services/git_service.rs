@@ -4,8 +4,19 @@
 //! the details of the git implementation from the UI layer.
 
 use crate::config::{Config, RepoMode};
-use crate::git::GitManager;
+use crate::git::{short_id, DivergedFile, FileDiff, GitManager, TransferProgress};
+use crate::utils::conflict_journal::{self, JournalEntry, JournalEvent};
+use crate::utils::doctor::{Doctor, DoctorOptions, ValidationResult, ValidationStatus};
+use crate::utils::hooks::{self, HookEvent};
+use crate::utils::machine_registry::{MachineRegistry, MACHINES_FILE};
+use crate::utils::profile_manifest::ProfileManifest;
+use crate::utils::reconcile::{self, Choice};
+use crate::utils::storage_backend;
+use crate::utils::sync_history::{self, SyncKind, SyncOrigin, SyncRecord};
+use crate::utils::StatusCache;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tracing::warn;
 
@@ -29,6 +40,76 @@ pub struct SyncResult {
     pub pulled_count: Option<usize>,
 }
 
+/// Progress update emitted while a sync runs.
+#[derive(Debug, Clone)]
+pub enum SyncProgress {
+    /// A new phase started (e.g. "Pulling from remote...").
+    Stage(String),
+    /// Network transfer update for the current phase.
+    Transfer(TransferProgress),
+}
+
+/// Which changes a sync should commit, and how.
+#[derive(Debug, Clone, Default)]
+pub struct SyncSelection {
+    /// Paths to commit.
+    pub paths: Vec<String>,
+    /// Hunk indices to leave out, per path in `paths`.
+    pub skipped_hunks: HashMap<String, HashSet<usize>>,
+    /// Commit message to use instead of the generated one.
+    pub message: Option<String>,
+}
+
+/// Which side wins when local and remote are forced back in step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForceSyncMode {
+    /// Discard local commits and changes and match the remote.
+    MatchRemote,
+    /// Replace the remote branch with the local state.
+    OverwriteRemote,
+}
+
+/// What each force sync option would change, shown before confirming.
+#[derive(Debug, Clone, Default)]
+pub struct ForceSyncPreview {
+    /// Branch being compared.
+    pub branch: String,
+    /// Local commits the remote doesn't have.
+    pub ahead: usize,
+    /// Remote commits missing locally.
+    pub behind: usize,
+    /// Uncommitted local changes ("X filename").
+    pub uncommitted_files: Vec<String>,
+    /// Files that change locally when matching the remote.
+    pub incoming_files: Vec<String>,
+    /// Files that change on the remote when it is overwritten.
+    pub outgoing_files: Vec<String>,
+    /// Why the remote couldn't be compared (forcing is unsafe then).
+    pub error: Option<String>,
+}
+
+/// Files that differ between this machine and the remote, for the
+/// reconcile wizard.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcilePreview {
+    /// Branch being compared.
+    pub branch: String,
+    /// Local commits the remote doesn't have.
+    pub ahead: usize,
+    /// Remote commits missing locally.
+    pub behind: usize,
+    /// Remote commit the files were compared with.
+    pub remote_commit: Option<git2::Oid>,
+    /// Files whose local content differs from the remote.
+    pub files: Vec<DivergedFile>,
+    /// Why the remote couldn't be compared.
+    pub error: Option<String>,
+}
+
+/// A file reconciled in the wizard: its path, the choice made and the
+/// content that choice leaves (`None` deletes the file).
+pub type ReconciledFile = (String, Choice, Option<Vec<u8>>);
+
 /// Detailed status of the git repository.
 #[derive(Debug, Clone, Default)]
 pub struct GitStatus {
@@ -42,6 +123,12 @@ pub struct GitStatus {
     pub behind: usize,
     /// Any error message encountered during check.
     pub error: Option<String>,
+    /// Whether the repository has an `origin` remote.
+    pub has_remote: bool,
+    /// Whether origin was fetched successfully, so ahead/behind is current.
+    pub remote_fetched: bool,
+    /// Why fetching origin failed (ahead/behind may be stale).
+    pub fetch_error: Option<String>,
 }
 
 /// Service for git-related operations.
@@ -113,6 +200,7 @@ impl GitService {
     ///
     /// A `GitStatus` with detailed repository state.
     pub fn fetch_and_check_status(config: &Config) -> GitStatus {
+        let _span = tracing::info_span!("git_fetch_status").entered();
         let mut status = GitStatus::default();
 
         // Check if repository is configured and repo exists
@@ -155,6 +243,7 @@ impl GitService {
 
         // Check if repo has a remote configured
         let has_remote = git_mgr.has_remote("origin");
+        status.has_remote = has_remote;
 
         if has_remote {
             let token = match config.repo_mode {
@@ -170,11 +259,17 @@ impl GitService {
 
             if should_fetch {
                 // Try to fetch
-                if let Err(e) = git_mgr.fetch("origin", &branch, token.as_deref()) {
-                    warn!("Background fetch failed: {}", e);
-                    // Don't fail the whole status check, just record error
-                    // We can still return uncommitted changes info
+                match git_mgr.fetch("origin", &branch, token.as_deref()) {
+                    Ok(()) => status.remote_fetched = true,
+                    Err(e) => {
+                        warn!("Background fetch failed: {}", e);
+                        // Don't fail the whole status check, just record error
+                        // We can still return uncommitted changes info
+                        status.fetch_error = Some(e.to_string());
+                    }
                 }
+            } else {
+                status.fetch_error = Some("GitHub token not found".to_string());
             }
 
             // Check ahead/behind counts (even if fetch failed, we might have cached data)
@@ -203,6 +298,7 @@ impl GitService {
     /// A vector of changed file descriptions.
     #[must_use]
     pub fn load_changed_files(repo_path: &Path) -> Vec<String> {
+        let _span = tracing::debug_span!("git_status").entered();
         if !repo_path.exists() {
             return vec![];
         }
@@ -215,27 +311,51 @@ impl GitService {
         git_mgr.get_changed_files().unwrap_or_default()
     }
 
+    /// Path part of a changed file entry ("X filename")
+    #[must_use]
+    pub fn entry_path(file_info: &str) -> &str {
+        file_info
+            .split_once(' ')
+            .map_or(file_info, |(_, path)| path.trim())
+    }
+
+    /// Default commit message for changed file entries ("X filename"),
+    /// used when syncing without editing the message. Formatted with the
+    /// config's commit template.
+    #[must_use]
+    pub fn commit_message_for(config: &Config, changed_files: &[String]) -> String {
+        config.format_commit_message(&GitManager::commit_message_for(changed_files))
+    }
+
     /// Get the diff for a specific file.
     ///
     /// # Arguments
     ///
     /// * `repo_path` - Path to the git repository.
     /// * `file_info` - File info string in format "X filename" where X is the status.
+    /// * `max_bytes` - How much of the diff to load; see [`crate::git::DIFF_CHUNK_BYTES`].
     ///
     /// # Returns
     ///
     /// The diff content if available.
     #[must_use]
-    pub fn get_diff_for_file(repo_path: &Path, file_info: &str) -> Option<String> {
+    pub fn get_diff_for_file(
+        repo_path: &Path,
+        file_info: &str,
+        max_bytes: usize,
+    ) -> Option<FileDiff> {
+        let _span = tracing::debug_span!("git_diff", file = file_info).entered();
         // Format is "X filename"
-        let parts: Vec<&str> = file_info.splitn(2, ' ').collect();
-        if parts.len() != 2 {
+        if !file_info.contains(' ') {
             return None;
         }
-        let path_str = parts[1].trim();
+        let path_str = Self::entry_path(file_info);
 
         let git_mgr = GitManager::open_or_init(repo_path).ok()?;
-        git_mgr.get_diff_for_file(path_str).ok().flatten()
+        git_mgr
+            .get_diff_for_file_limited(path_str, max_bytes)
+            .ok()
+            .flatten()
     }
 
     /// Perform a sync operation: commit -> pull with rebase -> push.
@@ -248,6 +368,64 @@ impl GitService {
     ///
     /// A `SyncResult` describing the outcome of the operation.
     pub fn sync(config: &Config) -> SyncResult {
+        Self::sync_with_progress(config, None, &|_| {})
+    }
+
+    /// Perform a sync like [`Self::sync`], recording it in the sync history
+    /// as started from `origin`.
+    pub fn sync_from(config: &Config, origin: SyncOrigin) -> SyncResult {
+        Self::record_sync(config, origin, None, &|_| {})
+    }
+
+    /// Perform a sync operation, reporting each phase and the pull/push
+    /// transfer progress to `on_progress`.
+    ///
+    /// With a `selection`, only those changes are committed. The rest are
+    /// stashed while pulling and pushing and restored afterwards, so they
+    /// stay uncommitted in the working tree.
+    ///
+    /// On a pull-only machine (`config.read_only`) nothing is committed or
+    /// pushed: local changes are set aside the same way while pulling.
+    ///
+    /// The outcome is added to the sync history.
+    pub fn sync_with_progress(
+        config: &Config,
+        selection: Option<&SyncSelection>,
+        on_progress: &dyn Fn(SyncProgress),
+    ) -> SyncResult {
+        Self::record_sync(config, SyncOrigin::Tui, selection, on_progress)
+    }
+
+    fn record_sync(
+        config: &Config,
+        origin: SyncOrigin,
+        selection: Option<&SyncSelection>,
+        on_progress: &dyn Fn(SyncProgress),
+    ) -> SyncResult {
+        let _span = tracing::info_span!("git_sync").entered();
+        let kind = if config.read_only {
+            SyncKind::Pull
+        } else {
+            SyncKind::Sync
+        };
+        let mut record = SyncRecord::start(kind, origin, &config.active_profile);
+        let result = Self::run_sync(config, selection, on_progress, &mut record);
+        record.finish(result.success, &result.message);
+        sync_history::save(&record);
+        hooks::fire(
+            &config.repo_path,
+            &config.active_profile,
+            HookEvent::Sync(&record),
+        );
+        result
+    }
+
+    fn run_sync(
+        config: &Config,
+        selection: Option<&SyncSelection>,
+        on_progress: &dyn Fn(SyncProgress),
+        record: &mut SyncRecord,
+    ) -> SyncResult {
         // Check if repository is configured
         if !config.is_repo_configured() {
             warn!("Sync attempted but repository not configured");
@@ -275,8 +453,35 @@ impl GitService {
             };
         }
 
+        // Block early if something would make the sync fail half-way
+        if let Some(message) = Self::preflight(config) {
+            warn!("Sync blocked by pre-flight checks");
+            return SyncResult {
+                success: false,
+                message,
+                pulled_count: None,
+            };
+        }
+
+        // Keep other machines sharing the directory through a file-sync
+        // service from writing to it at the same time
+        let _lock = if config.file_sync_mode {
+            match crate::utils::file_sync::acquire(repo_path) {
+                Ok(lock) => Some(lock),
+                Err(e) => {
+                    return SyncResult {
+                        success: false,
+                        message: Self::format_error_chain("Repository is locked", &e),
+                        pulled_count: None,
+                    }
+                }
+            }
+        } else {
+            None
+        };
+
         // Open git repository
-        let git_mgr = match GitManager::open_or_init(repo_path) {
+        let mut git_mgr = match GitManager::open_or_init(repo_path) {
             Ok(mgr) => mgr,
             Err(e) => {
                 return SyncResult {
@@ -290,6 +495,7 @@ impl GitService {
         let branch = git_mgr
             .get_current_branch()
             .unwrap_or_else(|| config.default_branch.clone());
+        record.branch = Some(branch.clone());
 
         // Get token based on repo mode
         let token_string = match config.repo_mode {
@@ -314,15 +520,117 @@ impl GitService {
             };
         }
 
+        // A newer dotstate's repository format must not be written to
+        if !config.read_only {
+            if let Err(message) = Self::ensure_writable(repo_path) {
+                return SyncResult {
+                    success: false,
+                    message,
+                    pulled_count: None,
+                };
+            }
+        }
+
+        // Encrypt edited decrypted copies back so the changes get committed
+        if !config.read_only {
+            if let Err(e) = crate::utils::sops::reencrypt_changed(repo_path) {
+                return SyncResult {
+                    success: false,
+                    message: format!("Error: Failed to re-encrypt sops files: {e:#}"),
+                    pulled_count: None,
+                };
+            }
+            if let Err(e) = crate::utils::managed_block::capture_changed(repo_path) {
+                return SyncResult {
+                    success: false,
+                    message: format!("Error: Failed to read managed blocks: {e:#}"),
+                    pulled_count: None,
+                };
+            }
+            if let Err(e) = crate::utils::integrity::record(repo_path) {
+                warn!("Failed to record content hashes: {e:#}");
+            }
+        }
+
+        // Refresh this machine's entry so it's committed with the sync
+        if !config.read_only {
+            Self::record_machine(config, &git_mgr);
+            if let Err(e) = crate::utils::repo_readme::update(config) {
+                warn!("Failed to regenerate repository README: {e:#}");
+            }
+        }
+
         // Step 1: Only commit if there are uncommitted changes
         // This prevents creating empty commits on retry after a failed push
-        let has_changes = git_mgr.has_uncommitted_changes().unwrap_or(false);
+        let changed = git_mgr.get_changed_files().unwrap_or_default();
+        let to_commit: Vec<String> = match selection {
+            _ if config.read_only => Vec::new(),
+            Some(selection) => changed
+                .iter()
+                .filter(|entry| {
+                    let entry_path = Self::entry_path(entry);
+                    entry_path == MACHINES_FILE
+                        || selection.paths.iter().any(|path| path == entry_path)
+                })
+                .cloned()
+                .collect(),
+            None => changed.clone(),
+        };
+        let no_skipped_hunks = HashMap::new();
+        let skipped_hunks = selection.map_or(&no_skipped_hunks, |s| &s.skipped_hunks);
+        let partially_committed = to_commit
+            .iter()
+            .filter(|entry| {
+                skipped_hunks
+                    .get(Self::entry_path(entry))
+                    .is_some_and(|hunks| !hunks.is_empty())
+            })
+            .count();
+        let partial = to_commit.len() < changed.len() || partially_committed > 0;
+        let custom_message = selection
+            .and_then(|s| s.message.as_deref())
+            .map(str::trim)
+            .filter(|message| !message.is_empty());
         let mut made_commit = false;
 
-        if has_changes {
-            let commit_msg = git_mgr
-                .generate_commit_message()
-                .unwrap_or_else(|_| "Update dotfiles".to_string());
+        if partial {
+            if !to_commit.is_empty() {
+                on_progress(SyncProgress::Stage(format!(
+                    "Committing {} of {} changed files...",
+                    to_commit.len(),
+                    changed.len()
+                )));
+                let paths: Vec<String> = to_commit
+                    .iter()
+                    .map(|entry| Self::entry_path(entry).to_string())
+                    .collect();
+                let commit_msg = custom_message.map_or_else(
+                    || config.format_commit_message(&GitManager::commit_message_for(&to_commit)),
+                    str::to_string,
+                );
+                if let Err(e) = git_mgr.commit_paths(&paths, skipped_hunks, &commit_msg) {
+                    return SyncResult {
+                        success: false,
+                        message: Self::format_error_chain("Failed to commit changes", &e),
+                        pulled_count: None,
+                    };
+                }
+                made_commit = true;
+                record.committed.clone_from(&to_commit);
+                record.set_commit(git_mgr.head_oid());
+            }
+        } else if !config.read_only && git_mgr.has_uncommitted_changes().unwrap_or(false) {
+            on_progress(SyncProgress::Stage("Committing changes...".to_string()));
+            let commit_msg = custom_message.map_or_else(
+                || {
+                    config.format_commit_message(
+                        &git_mgr
+                            .generate_commit_message()
+                            .unwrap_or_else(|_| "Update dotfiles".to_string()),
+                    )
+                },
+                str::to_string,
+            );
 
             if let Err(e) = git_mgr.commit_all(&commit_msg) {
                 return SyncResult {
@@ -332,12 +640,120 @@ impl GitService {
                 };
             }
             made_commit = true;
+            record.committed.clone_from(&changed);
+            record.set_commit(git_mgr.head_oid());
+        }
+
+        // Changes left out of the commit are stashed so the rebase sees a clean tree
+        let stashed = if partial {
+            match git_mgr.stash_changes("dotstate: changes left out of sync") {
+                Ok(stashed) => stashed,
+                Err(e) => {
+                    if made_commit {
+                        if let Err(reset_err) = git_mgr.reset_soft_head() {
+                            warn!("Failed to reset commit after stash failure: {}", reset_err);
+                        }
+                    }
+                    return SyncResult {
+                        success: false,
+                        message: Self::format_error_chain(
+                            "Failed to set aside unselected changes",
+                            &e,
+                        ),
+                        pulled_count: None,
+                    };
+                }
+            }
+        } else {
+            false
+        };
+
+        let mut result = Self::pull_and_push(
+            config,
+            &git_mgr,
+            &branch,
+            token,
+            made_commit,
+            on_progress,
+            record,
+        );
+
+        // Put back the changes that were left out of this sync
+        if stashed {
+            match git_mgr.stash_pop() {
+                Ok(()) => {
+                    if result.success {
+                        let left_out = changed.len() - to_commit.len() + partially_committed;
+                        if let Err(e) = StatusCache::new(left_out, 0, 0).save() {
+                            warn!("Failed to update prompt status: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to restore stashed changes: {}", e);
+                    result.message.push_str(&format!(
+                        "\n\nWarning: Could not restore the changes left out of this sync: {e}\n\
+                        They are saved in the stash. Run 'git stash pop' in the repository to restore them."
+                    ));
+                }
+            }
         }
 
+        result
+    }
+
+    /// Pull with rebase, push (skipped on a pull-only machine), and create
+    /// symlinks for pulled files.
+    ///
+    /// If `made_commit` is set and the pull or push fails, the commit is
+    /// undone so the changes stay staged for the next attempt.
+    fn pull_and_push(
+        config: &Config,
+        git_mgr: &GitManager,
+        branch: &str,
+        token: Option<&str>,
+        made_commit: bool,
+        on_progress: &dyn Fn(SyncProgress),
+        record: &mut SyncRecord,
+    ) -> SyncResult {
+        let repo_path = &config.repo_path;
+        let report_transfer = |progress| on_progress(SyncProgress::Transfer(progress));
+        // Without an origin remote, a storage backend stands in for it
+        let backend_only = storage_backend::replacing_remote(config, git_mgr);
+
         // Step 2: Pull with rebase
-        let pulled_count = match git_mgr.pull_with_rebase("origin", &branch, token) {
-            Ok(count) => count,
+        on_progress(SyncProgress::Stage("Pulling from remote...".to_string()));
+        let head_before_pull = git_mgr.head_oid();
+        let pulled = match backend_only {
+            Some(backend) => storage_backend::pull(backend, repo_path, branch),
+            None => git_mgr.pull_with_rebase_with_progress(
+                "origin",
+                branch,
+                token,
+                Some(&report_transfer),
+            ),
+        };
+        let pulled_count = match pulled {
+            Ok(count) => {
+                record.pulled_commits = count;
+                if let Some(old) = head_before_pull.filter(|_| count > 0) {
+                    record.pulled = git_mgr.changed_since(old).unwrap_or_default();
+                    if let Err(e) = crate::utils::local_only::restore_after_pull(repo_path, old) {
+                        warn!("Failed to restore local-only files: {:#}", e);
+                    }
+                }
+                count
+            }
             Err(e) => {
+                conflict_journal::record_pull_error(
+                    &e,
+                    record,
+                    if made_commit {
+                        "Sync stopped; its commit was undone and the changes are still staged"
+                    } else {
+                        "Stopped before anything changed; local changes were kept"
+                    },
+                );
                 // Pull/rebase failed - the rebase.abort() inside pull_with_rebase should
                 // have restored the repo state. Try to reset our commit to preserve user's changes.
                 if made_commit {
@@ -345,7 +761,7 @@ impl GitService {
                         // reset_soft_head failed - repo might be in a bad state (mid-rebase)
                         // Try cleanup as fallback (this will lose changes but at least recover)
                         warn!("Failed to reset commit: {}, trying cleanup", reset_err);
-                        if let Err(cleanup_err) = git_mgr.cleanup_failed_operation(&branch) {
+                        if let Err(cleanup_err) = git_mgr.cleanup_failed_operation(branch) {
                             warn!("Failed to cleanup after pull failure: {}", cleanup_err);
                             return SyncResult {
                                 success: false,
@@ -385,8 +801,37 @@ impl GitService {
             }
         };
 
-        // Step 3: Push to remote
-        if let Err(e) = git_mgr.push("origin", &branch, token) {
+        // Step 3: Push to remote (never from a pull-only machine), unless the
+        // pull brought in a manifest written by a newer dotstate
+        let writable = if config.read_only || pulled_count == 0 {
+            Ok(())
+        } else {
+            Self::ensure_writable(repo_path)
+        };
+        if let Err(message) = writable {
+            if made_commit {
+                if let Err(reset_err) = git_mgr.reset_soft_head() {
+                    warn!("Failed to reset commit after pull: {}", reset_err);
+                }
+            }
+            return SyncResult {
+                success: false,
+                message,
+                pulled_count: Some(pulled_count),
+            };
+        }
+        let push_result = if config.read_only {
+            Ok(())
+        } else if let Some(backend) = backend_only {
+            on_progress(SyncProgress::Stage(
+                "Uploading encrypted archive...".to_string(),
+            ));
+            storage_backend::push(backend, repo_path, branch)
+        } else {
+            on_progress(SyncProgress::Stage("Pushing to remote...".to_string()));
+            git_mgr.push_with_progress("origin", branch, token, Some(&report_transfer))
+        };
+        if let Err(e) = push_result {
             // Push failed - reset the commit so user can fix the issue and retry
             // This prevents the bad commit from blocking future pushes
             if made_commit {
@@ -418,9 +863,14 @@ impl GitService {
 
         // Success! Build the success message
         let mut success_msg = format!(
-            "✓ Successfully synced with remote!\n\n\
+            "✓ Successfully {} remote!\n\n\
             Branch: {branch}\n\
-            Repository: {repo_path:?}"
+            Repository: {repo_path:?}",
+            if config.read_only {
+                "pulled from"
+            } else {
+                "synced with"
+            }
         );
 
         if pulled_count > 0 {
@@ -428,63 +878,615 @@ impl GitService {
 
             // Step 4: Ensure symlinks for any new files pulled from remote
             // This is efficient - only creates symlinks for missing files
-            use crate::services::ProfileService;
-            match ProfileService::ensure_profile_symlinks(
-                repo_path,
-                &config.active_profile,
-                config.backup_enabled,
-            ) {
-                Ok((created, _skipped, errors)) => {
-                    if created > 0 {
-                        success_msg
-                            .push_str(&format!("\nCreated {created} symlink(s) for new files."));
-                    }
-                    if !errors.is_empty() {
-                        success_msg.push_str(&format!(
-                            "\n\nWarning: {} error(s) creating symlinks:\n{}",
-                            errors.len(),
-                            errors.join("\n")
-                        ));
+            on_progress(SyncProgress::Stage("Updating symlinks...".to_string()));
+            Self::ensure_symlinks(config, &mut success_msg);
+        } else {
+            success_msg.push_str("\n\nNo changes pulled from remote.");
+        }
+
+        // Mirror the pushed branch to the storage backend next to origin
+        if let Some(backend) = config
+            .storage_backend
+            .as_ref()
+            .filter(|_| backend_only.is_none() && !config.read_only)
+        {
+            on_progress(SyncProgress::Stage(
+                "Uploading encrypted archive...".to_string(),
+            ));
+            if let Err(e) = storage_backend::push(backend, repo_path, branch) {
+                warn!("Failed to upload archive to storage backend: {:#}", e);
+                success_msg.push_str(&format!(
+                    "\n\n⚠️  Failed to upload the encrypted archive: {e:#}"
+                ));
+            }
+        }
+
+        Self::record_successful_sync();
+
+        SyncResult {
+            success: true,
+            message: success_msg,
+            pulled_count: Some(pulled_count),
+        }
+    }
+
+    /// Pull from remote without committing or pushing, the way a pull-only
+    /// machine syncs: local changes are set aside while pulling and restored.
+    pub fn pull(config: &Config) -> SyncResult {
+        let mut config = config.clone();
+        config.read_only = true;
+        Self::sync_with_progress(&config, None, &|_| {})
+    }
+
+    /// Fetch the remote and summarize what each force sync option would do.
+    pub fn force_sync_preview(config: &Config) -> ForceSyncPreview {
+        let mut preview = ForceSyncPreview::default();
+        let git_mgr = match Self::open_for_force_sync(config) {
+            Ok(mgr) => mgr,
+            Err(e) => {
+                preview.error = Some(e);
+                return preview;
+            }
+        };
+        preview.branch = git_mgr
+            .get_current_branch()
+            .unwrap_or_else(|| config.default_branch.clone());
+        preview.uncommitted_files = git_mgr.get_changed_files().unwrap_or_default();
+
+        let token = Self::token_for(config);
+        if let Err(e) = git_mgr.fetch("origin", &preview.branch, token.as_deref()) {
+            preview.error = Some(Self::format_error_chain("Failed to fetch from remote", &e));
+            return preview;
+        }
+
+        let compared = git_mgr
+            .get_ahead_behind("origin", &preview.branch)
+            .and_then(|(ahead, behind)| {
+                preview.ahead = ahead;
+                preview.behind = behind;
+                preview.incoming_files =
+                    git_mgr.diff_against_remote("origin", &preview.branch, true)?;
+                preview.outgoing_files =
+                    git_mgr.diff_against_remote("origin", &preview.branch, false)?;
+                Ok(())
+            });
+        if let Err(e) = compared {
+            preview.error = Some(Self::format_error_chain(
+                "Failed to compare with remote",
+                &e,
+            ));
+        }
+        preview
+    }
+
+    /// Force local and remote back in step, in the direction of `mode`.
+    ///
+    /// `MatchRemote` fetches, hard resets the branch to the remote and
+    /// recreates symlinks. `OverwriteRemote` commits any uncommitted changes
+    /// and force pushes; it is refused on a pull-only machine.
+    ///
+    /// The outcome is added to the sync history.
+    pub fn force_sync(config: &Config, mode: ForceSyncMode) -> SyncResult {
+        let kind = match mode {
+            ForceSyncMode::MatchRemote => SyncKind::MatchRemote,
+            ForceSyncMode::OverwriteRemote => SyncKind::OverwriteRemote,
+        };
+        let mut record = SyncRecord::start(kind, SyncOrigin::Tui, &config.active_profile);
+        let result = Self::run_force_sync(config, mode, &mut record);
+        record.finish(result.success, &result.message);
+        sync_history::save(&record);
+        hooks::fire(
+            &config.repo_path,
+            &config.active_profile,
+            HookEvent::Sync(&record),
+        );
+        result
+    }
+
+    fn run_force_sync(config: &Config, mode: ForceSyncMode, record: &mut SyncRecord) -> SyncResult {
+        let failed = |message: String| SyncResult {
+            success: false,
+            message,
+            pulled_count: None,
+        };
+
+        if mode == ForceSyncMode::OverwriteRemote && config.read_only {
+            return failed(
+                "Error: This is a pull-only machine.\n\n\
+                Overwriting the remote is disabled. Turn off Pull-Only Machine in Settings first."
+                    .to_string(),
+            );
+        }
+
+        let git_mgr = match Self::open_for_force_sync(config) {
+            Ok(mgr) => mgr,
+            Err(e) => return failed(e),
+        };
+        let branch = git_mgr
+            .get_current_branch()
+            .unwrap_or_else(|| config.default_branch.clone());
+        let token = Self::token_for(config);
+        record.branch = Some(branch.clone());
+
+        match mode {
+            ForceSyncMode::MatchRemote => {
+                if let Err(e) = git_mgr.fetch("origin", &branch, token.as_deref()) {
+                    return failed(Self::format_error_chain("Failed to fetch from remote", &e));
+                }
+                let head_before = git_mgr.head_oid();
+                if let Ok((_, behind)) = git_mgr.get_ahead_behind("origin", &branch) {
+                    record.pulled_commits = behind;
+                }
+                let mut entry = JournalEntry::new(
+                    JournalEvent::MatchRemote,
+                    record,
+                    "Discarded local commits and changes; the repository now matches the remote",
+                );
+                entry.files = git_mgr.get_changed_files().unwrap_or_default();
+                entry.commits = git_mgr
+                    .divergent_commits("origin", &branch, true)
+                    .unwrap_or_default();
+                entry.local_commit = head_before.map(short_id);
+                if let Err(e) = git_mgr.reset_hard_to_remote("origin", &branch) {
+                    return failed(Self::format_error_chain("Failed to reset to remote", &e));
+                }
+                if let Some(old) = head_before {
+                    record.pulled = git_mgr.changed_since(old).unwrap_or_default();
+                }
+                entry.remote_commit = git_mgr.head_oid().map(short_id);
+                for file in &record.pulled {
+                    if !entry.files.contains(file) {
+                        entry.files.push(file.clone());
                     }
                 }
-                Err(e) => {
-                    warn!("Failed to ensure symlinks after pull: {}", e);
-                    success_msg.push_str(&format!(
-                        "\n\nWarning: Failed to create symlinks for new files: {e}"
-                    ));
+                conflict_journal::save(&entry);
+
+                let mut message = format!(
+                    "✓ Local repository now matches the remote.\n\n\
+                    Branch: {branch}\n\
+                    Repository: {:?}",
+                    config.repo_path
+                );
+                Self::ensure_symlinks(config, &mut message);
+                Self::record_successful_sync();
+                SyncResult {
+                    success: true,
+                    message,
+                    pulled_count: None,
                 }
             }
-
-            // Also ensure common symlinks
-            match ProfileService::ensure_common_symlinks(repo_path, config.backup_enabled) {
-                Ok((created, _skipped, errors)) => {
-                    if created > 0 {
-                        success_msg.push_str(&format!("\nCreated {created} common symlink(s)."));
-                    }
-                    if !errors.is_empty() {
-                        success_msg.push_str(&format!(
-                            "\n\nWarning: {} error(s) creating common symlinks:\n{}",
-                            errors.len(),
-                            errors.join("\n")
-                        ));
+            ForceSyncMode::OverwriteRemote => {
+                if let Err(message) = Self::ensure_writable(&config.repo_path) {
+                    return failed(message);
+                }
+                let mut made_commit = false;
+                if git_mgr.has_uncommitted_changes().unwrap_or(false) {
+                    let commit_msg = config.format_commit_message(
+                        &git_mgr
+                            .generate_commit_message()
+                            .unwrap_or_else(|_| "Update dotfiles".to_string()),
+                    );
+                    record.committed = git_mgr.get_changed_files().unwrap_or_default();
+                    if let Err(e) = git_mgr.commit_all(&commit_msg) {
+                        return failed(Self::format_error_chain("Failed to commit changes", &e));
                     }
+                    made_commit = true;
+                    record.set_commit(git_mgr.head_oid());
                 }
-                Err(e) => {
-                    warn!("Failed to ensure common symlinks after pull: {}", e);
-                    success_msg.push_str(&format!(
-                        "\n\nWarning: Failed to create common symlinks: {e}"
+
+                let mut entry = JournalEntry::new(
+                    JournalEvent::OverwriteRemote,
+                    record,
+                    "Force pushed the local state; the remote's own commits were replaced",
+                );
+                entry.files = git_mgr
+                    .diff_against_remote("origin", &branch, false)
+                    .unwrap_or_default();
+                entry.commits = git_mgr
+                    .divergent_commits("origin", &branch, false)
+                    .unwrap_or_default();
+                entry.local_commit = git_mgr.head_oid().map(short_id);
+                entry.remote_commit = git_mgr
+                    .remote_branch_oid("origin", &branch)
+                    .ok()
+                    .flatten()
+                    .map(short_id);
+
+                if let Err(e) = git_mgr.force_push("origin", &branch, token.as_deref()) {
+                    if made_commit {
+                        if let Err(reset_err) = git_mgr.reset_soft_head() {
+                            warn!(
+                                "Failed to reset commit after force push failure: {}",
+                                reset_err
+                            );
+                        }
+                    }
+                    return failed(Self::format_error_chain(
+                        "Failed to force push to remote",
+                        &e,
                     ));
                 }
+
+                conflict_journal::save(&entry);
+                Self::record_successful_sync();
+                SyncResult {
+                    success: true,
+                    message: format!(
+                        "✓ Remote now matches your local repository.\n\n\
+                        Branch: {branch}\n\
+                        Repository: {:?}",
+                        config.repo_path
+                    ),
+                    pulled_count: None,
+                }
             }
-        } else {
-            success_msg.push_str("\n\nNo changes pulled from remote.");
+        }
+    }
+
+    /// Fetch the remote and list the files that differ from it, with their
+    /// local, remote and common versions.
+    pub fn reconcile_preview(config: &Config) -> ReconcilePreview {
+        let mut preview = ReconcilePreview::default();
+        let git_mgr = match Self::open_for_force_sync(config) {
+            Ok(mgr) => mgr,
+            Err(e) => {
+                preview.error = Some(e);
+                return preview;
+            }
+        };
+        preview.branch = git_mgr
+            .get_current_branch()
+            .unwrap_or_else(|| config.default_branch.clone());
+
+        let token = Self::token_for(config);
+        if let Err(e) = git_mgr.fetch("origin", &preview.branch, token.as_deref()) {
+            preview.error = Some(Self::format_error_chain("Failed to fetch from remote", &e));
+            return preview;
+        }
+
+        let compared = git_mgr
+            .get_ahead_behind("origin", &preview.branch)
+            .and_then(|(ahead, behind)| {
+                preview.ahead = ahead;
+                preview.behind = behind;
+                preview.remote_commit = git_mgr.remote_branch_oid("origin", &preview.branch)?;
+                preview.files = git_mgr.diverged_files("origin", &preview.branch)?;
+                Ok(())
+            });
+        if let Err(e) = compared {
+            preview.error = Some(Self::format_error_chain(
+                "Failed to compare with remote",
+                &e,
+            ));
+        }
+        preview
+    }
+
+    /// Write the reconciled files, commit them as a merge of the local branch
+    /// and `remote_commit`, and push. Refused when the remote has moved on
+    /// since the files were compared, or on a pull-only machine.
+    ///
+    /// The outcome is added to the sync history and the conflict journal.
+    pub fn reconcile(
+        config: &Config,
+        remote_commit: git2::Oid,
+        files: &[ReconciledFile],
+    ) -> SyncResult {
+        let mut record =
+            SyncRecord::start(SyncKind::Reconcile, SyncOrigin::Tui, &config.active_profile);
+        let result = Self::run_reconcile(config, remote_commit, files, &mut record);
+        record.finish(result.success, &result.message);
+        sync_history::save(&record);
+        hooks::fire(
+            &config.repo_path,
+            &config.active_profile,
+            HookEvent::Sync(&record),
+        );
+        result
+    }
+
+    fn run_reconcile(
+        config: &Config,
+        remote_commit: git2::Oid,
+        files: &[ReconciledFile],
+        record: &mut SyncRecord,
+    ) -> SyncResult {
+        let failed = |message: String| SyncResult {
+            success: false,
+            message,
+            pulled_count: None,
+        };
+
+        if config.read_only {
+            return failed(
+                "Error: This is a pull-only machine.\n\n\
+                Reconciling pushes the result. Turn off Pull-Only Machine in Settings first."
+                    .to_string(),
+            );
+        }
+        let git_mgr = match Self::open_for_force_sync(config) {
+            Ok(mgr) => mgr,
+            Err(e) => return failed(e),
+        };
+        let branch = git_mgr
+            .get_current_branch()
+            .unwrap_or_else(|| config.default_branch.clone());
+        let token = Self::token_for(config);
+        record.branch = Some(branch.clone());
+
+        if let Err(e) = git_mgr.fetch("origin", &branch, token.as_deref()) {
+            return failed(Self::format_error_chain("Failed to fetch from remote", &e));
+        }
+        if git_mgr.remote_branch_oid("origin", &branch).ok().flatten() != Some(remote_commit) {
+            return failed(
+                "Error: The remote changed while you were reconciling.\n\n\
+                Reload and go through the files again."
+                    .to_string(),
+            );
+        }
+        if let Ok((_, behind)) = git_mgr.get_ahead_behind("origin", &branch) {
+            record.pulled_commits = behind;
+        }
+        let head_before = git_mgr.head_oid();
+        let mut entry = JournalEntry::new(JournalEvent::Reconcile, record, "");
+        entry.files = files
+            .iter()
+            .map(|(path, choice, _)| format!("{path}: {}", choice.label()))
+            .collect();
+        entry.commits = git_mgr
+            .divergent_commits("origin", &branch, false)
+            .unwrap_or_default();
+        entry.local_commit = head_before.map(short_id);
+        entry.remote_commit = Some(short_id(remote_commit));
+
+        if let Err(message) = Self::ensure_writable(&config.repo_path) {
+            return failed(message);
+        }
+        let contents: Vec<(String, Option<Vec<u8>>)> = files
+            .iter()
+            .map(|(path, _, content)| (path.clone(), content.clone()))
+            .collect();
+        if let Err(e) = reconcile::apply(&config.repo_path, &contents) {
+            return failed(Self::format_error_chain(
+                "Failed to write reconciled files",
+                &e,
+            ));
+        }
+        // The remote's manifest may have been picked
+        if let Err(message) = Self::ensure_writable(&config.repo_path) {
+            return failed(message);
+        }
+        if let Err(e) = crate::utils::integrity::record(&config.repo_path) {
+            warn!("Failed to record content hashes: {e:#}");
+        }
+        record.committed = git_mgr.get_changed_files().unwrap_or_default();
+        let commit_msg =
+            config.format_commit_message(&format!("Reconcile {} file(s) with remote", files.len()));
+        let commit = match git_mgr.commit_merge("origin", &branch, &commit_msg) {
+            Ok(oid) => oid,
+            Err(e) => {
+                return failed(Self::format_error_chain(
+                    "Failed to commit reconciled files",
+                    &e,
+                ))
+            }
+        };
+        record.set_commit(Some(commit));
+        if let Some(old) = head_before {
+            record.pulled = git_mgr.changed_since(old).unwrap_or_default();
         }
 
+        if let Err(e) = git_mgr.push("origin", &branch, token.as_deref()) {
+            entry.resolution = format!(
+                "Reconciled in commit {}; pushing it failed",
+                short_id(commit)
+            );
+            conflict_journal::save(&entry);
+            return failed(format!(
+                "{}\n\nThe reconciled commit was kept. Sync to push it.",
+                Self::format_error_chain("Failed to push to remote", &e)
+            ));
+        }
+        entry.resolution = format!("Reconciled and pushed in commit {}", short_id(commit));
+        conflict_journal::save(&entry);
+
+        let mut message = format!(
+            "✓ Reconciled {} file(s) with the remote.\n\n\
+            Branch: {branch}\n\
+            Commit: {}",
+            files.len(),
+            short_id(commit)
+        );
+        Self::ensure_symlinks(config, &mut message);
+        Self::record_successful_sync();
         SyncResult {
             success: true,
-            message: success_msg,
-            pulled_count: Some(pulled_count),
+            message,
+            pulled_count: None,
+        }
+    }
+
+    /// Open the repository for a force sync, or explain why it can't run.
+    fn open_for_force_sync(config: &Config) -> std::result::Result<GitManager, String> {
+        if !config.is_repo_configured() {
+            return Err("Error: Repository not configured.".to_string());
+        }
+        if matches!(config.repo_mode, RepoMode::GitHub) && config.get_github_token().is_none() {
+            return Err("Error: GitHub token not found.".to_string());
+        }
+        let git_mgr = GitManager::open_or_init(&config.repo_path)
+            .map_err(|e| format!("Error: Failed to open repository: {e}"))?;
+        if !git_mgr.has_remote("origin") {
+            return Err("Error: Repository has no remote to sync with.".to_string());
+        }
+        Ok(git_mgr)
+    }
+
+    /// Refuse to commit or push when a newer dotstate last wrote the
+    /// repository's manifest.
+    fn ensure_writable(repo_path: &Path) -> std::result::Result<(), String> {
+        ProfileManifest::load(repo_path)
+            .and_then(|manifest| manifest.ensure_writable())
+            .map_err(|e| format!("Error: {e:#}"))
+    }
+
+    /// Token for remote operations (None for Local mode)
+    fn token_for(config: &Config) -> Option<String> {
+        match config.repo_mode {
+            RepoMode::Local => None,
+            RepoMode::GitHub => config.get_github_token(),
+        }
+    }
+
+    /// Create symlinks for files the repository now has, appending any
+    /// created counts and warnings to `message`.
+    fn ensure_symlinks(config: &Config, message: &mut String) {
+        use crate::services::ProfileService;
+        let repo_path = &config.repo_path;
+
+        match ProfileService::ensure_profile_symlinks(
+            repo_path,
+            &config.active_profile,
+            config.backup_enabled,
+        ) {
+            Ok((created, _skipped, errors)) => {
+                if created > 0 {
+                    message.push_str(&format!("\nCreated {created} symlink(s) for new files."));
+                }
+                if !errors.is_empty() {
+                    message.push_str(&format!(
+                        "\n\nWarning: {} error(s) creating symlinks:\n{}",
+                        errors.len(),
+                        errors.join("\n")
+                    ));
+                }
+            }
+            Err(e) => {
+                warn!("Failed to ensure symlinks after pull: {}", e);
+                message.push_str(&format!(
+                    "\n\nWarning: Failed to create symlinks for new files: {e}"
+                ));
+            }
+        }
+
+        // Also ensure common symlinks
+        match ProfileService::ensure_common_symlinks(repo_path, config.backup_enabled) {
+            Ok((created, _skipped, errors)) => {
+                if created > 0 {
+                    message.push_str(&format!("\nCreated {created} common symlink(s)."));
+                }
+                if !errors.is_empty() {
+                    message.push_str(&format!(
+                        "\n\nWarning: {} error(s) creating common symlinks:\n{}",
+                        errors.len(),
+                        errors.join("\n")
+                    ));
+                }
+            }
+            Err(e) => {
+                warn!("Failed to ensure common symlinks after pull: {}", e);
+                message.push_str(&format!(
+                    "\n\nWarning: Failed to create common symlinks: {e}"
+                ));
+            }
+        }
+    }
+
+    /// Update this machine's entry in `machines.toml` before committing.
+    ///
+    /// Failures are only logged: the registry is informational.
+    pub fn record_machine(config: &Config, git_mgr: &GitManager) {
+        let has_changes = git_mgr.has_uncommitted_changes().unwrap_or(false);
+        if let Err(e) =
+            MachineRegistry::record_sync(&config.repo_path, &config.active_profile, has_changes)
+        {
+            warn!("Failed to update machine registry: {}", e);
+        }
+    }
+
+    /// Remember that a sync just succeeded: records the sync time and marks
+    /// the cached prompt status as in sync.
+    ///
+    /// Failures are only logged: both records are informational.
+    pub fn record_successful_sync() {
+        let path = crate::utils::get_data_dir().join("last_sync");
+        if let Err(e) = std::fs::write(&path, Utc::now().to_rfc3339()) {
+            warn!("Failed to record sync time at {:?}: {}", path, e);
+        }
+        if let Err(e) = StatusCache::new(0, 0, 0).save() {
+            warn!("Failed to update prompt status: {}", e);
+        }
+    }
+
+    /// Save a status check result for `dotstate prompt`.
+    ///
+    /// Results from failed checks are ignored so the prompt keeps showing the
+    /// last known state.
+    pub fn record_status(status: &GitStatus) {
+        if status.error.is_some() {
+            return;
+        }
+        let cache = StatusCache::new(status.uncommitted_files.len(), status.ahead, status.behind);
+        if let Err(e) = cache.save() {
+            warn!("Failed to update prompt status: {}", e);
+        }
+    }
+
+    /// When the last successful sync finished, if one was recorded.
+    #[must_use]
+    pub fn last_sync_time() -> Option<DateTime<Utc>> {
+        let content =
+            std::fs::read_to_string(crate::utils::get_data_dir().join("last_sync")).ok()?;
+        DateTime::parse_from_rfc3339(content.trim())
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    }
+
+    /// Run the quick doctor checks that must pass before syncing.
+    ///
+    /// Checks that the repository is valid, the remote is reachable, there is
+    /// enough disk space and the active profile exists in the manifest.
+    ///
+    /// # Returns
+    ///
+    /// `None` if it is safe to sync, or an error message explaining each
+    /// blocking problem.
+    #[must_use]
+    pub fn preflight(config: &Config) -> Option<String> {
+        let options = DoctorOptions {
+            fix_mode: false,
+            interactive: false,
+            fix_only: Vec::new(),
+            verbose: false,
+            json_output: false,
+            quiet: true,
+        };
+        let results = Doctor::new(config.clone(), options).run_preflight();
+        Self::format_preflight_failures(&results)
+    }
+
+    /// Build the blocking message from pre-flight results, if any check failed.
+    fn format_preflight_failures(results: &[ValidationResult]) -> Option<String> {
+        let failures: Vec<&ValidationResult> = results
+            .iter()
+            .filter(|r| r.status == ValidationStatus::Error)
+            .collect();
+        if failures.is_empty() {
+            return None;
         }
+
+        let mut msg = String::from(
+            "Error: Sync blocked by pre-flight checks.\n\n\
+            Nothing was committed, pulled or pushed.\n",
+        );
+        for failure in failures {
+            msg.push_str(&format!("\n✗ {}", failure.message));
+            for detail in failure.details.iter().flatten() {
+                msg.push_str(&format!("\n    {detail}"));
+            }
+        }
+        msg.push_str("\n\nRun 'dotstate doctor' for a full report.");
+        Some(msg)
     }
 
     /// Format an error with its full chain for display.
@@ -548,9 +1550,56 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_preflight_failures_message() {
+        let result = |message: &str, status| ValidationResult {
+            category: "Repository".to_string(),
+            check_name: "git_repo".to_string(),
+            message: message.to_string(),
+            status,
+            fixable: false,
+            fix_action: None,
+            details: Some(vec!["detail".to_string()]),
+            duration_ms: 0,
+        };
+
+        assert!(GitService::format_preflight_failures(&[result(
+            "Valid git repository",
+            ValidationStatus::Pass
+        )])
+        .is_none());
+
+        let msg = GitService::format_preflight_failures(&[
+            result("Disk nearly full", ValidationStatus::Warning),
+            result("Not a git repository", ValidationStatus::Error),
+        ])
+        .unwrap();
+        assert!(msg.starts_with("Error:"));
+        assert!(msg.contains("✗ Not a git repository\n    detail"));
+        assert!(!msg.contains("Disk nearly full"));
+    }
+
+    #[test]
+    fn test_newer_manifest_is_not_writable() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(GitService::ensure_writable(dir.path()).is_ok());
+
+        ProfileManifest::default().save(dir.path()).unwrap();
+        assert!(GitService::ensure_writable(dir.path()).is_ok());
+
+        let path = ProfileManifest::manifest_path(dir.path());
+        let content = std::fs::read_to_string(&path).unwrap().replace(
+            &format!("written_by = \"{}\"", env!("CARGO_PKG_VERSION")),
+            "written_by = \"999.0.0\"",
+        );
+        std::fs::write(&path, content).unwrap();
+        let message = GitService::ensure_writable(dir.path()).unwrap_err();
+        assert!(message.contains("dotstate 999.0.0"));
+    }
+
     #[test]
     fn test_get_diff_invalid_format() {
-        let result = GitService::get_diff_for_file(&PathBuf::from("/tmp"), "invalid");
+        let result = GitService::get_diff_for_file(&PathBuf::from("/tmp"), "invalid", usize::MAX);
         assert!(result.is_none());
     }
 }
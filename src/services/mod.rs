@@ -19,9 +19,9 @@
 //! │  ┌─────────────┐ ┌───────────────┐ ┌──────────────┐│
 //! │  │ SyncService │ │ProfileService │ │PackageService││
 //! │  └─────────────┘ └───────────────┘ └──────────────┘│
-//! │  ┌───────────────┐                                 │
-//! │  │ GitService    │                                 │
-//! │  └───────────────┘                                 │
+//! │  ┌───────────────┐ ┌──────────────┐                │
+//! │  │ GitService    │ │ StatsService │                │
+//! │  └───────────────┘ └──────────────┘                │
 //! └─────────────────────┬──────────────────────────────┘
 //!                       │
 //!                       ▼
@@ -34,12 +34,20 @@
 pub mod git_service;
 pub mod package_service;
 pub mod profile_service;
+pub mod stats_service;
+#[cfg(feature = "tui")]
 pub mod storage_setup_service;
 pub mod sync_service;
+pub mod token_service;
 
 // Re-export common types
 pub use git_service::GitService;
 pub use package_service::{PackageCheckStatus, PackageCreationParams, PackageService};
 pub use profile_service::ProfileService;
-pub use storage_setup_service::{StepHandle, StepResult, StorageSetupService};
-pub use sync_service::{AddFileResult, RemoveFileResult, SyncService};
+pub use stats_service::{RepoStats, StatsService};
+#[cfg(feature = "tui")]
+pub use storage_setup_service::{SetupProgress, StepHandle, StepResult, StorageSetupService};
+pub use sync_service::{
+    AddFileResult, AddStorage, FileOperation, MoveManyResult, RemoveFileResult, SyncService,
+};
+pub use token_service::{ReauthReason, TokenService};
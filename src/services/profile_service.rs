@@ -3,6 +3,7 @@
 //! This module provides a service layer for profile-related operations,
 //! abstracting the details of the profile management from the UI layer.
 
+use crate::utils::hooks::{self, HookEvent};
 use crate::utils::profile_manifest::{Package, ProfileInfo, ResolvedFile};
 use crate::utils::symlink_manager::{OperationStatus, SymlinkManager};
 use crate::utils::{sanitize_profile_name, validate_profile_name, ProfileManifest};
@@ -289,6 +290,7 @@ impl ProfileService {
             }
         };
 
+        Self::refresh_shell_snippets(repo_path, target_profile_name);
         info!(
             "Switched from '{}' to '{}'",
             old_profile_name, target_profile_name
@@ -299,6 +301,14 @@ impl ProfileService {
             created.len()
         );
 
+        hooks::fire(
+            repo_path,
+            target_profile_name,
+            HookEvent::Activate {
+                created: created.len(),
+            },
+        );
+
         Ok(ProfileSwitchResult {
             removed_count: removed.len(),
             created_count: created.len(),
@@ -466,6 +476,7 @@ impl ProfileService {
         // Resolve the full file list (inheritance chain + common, with overrides)
         let resolved_files = manifest.resolve_files(profile_name)?;
         let resolved_packages = manifest.resolve_packages(profile_name)?;
+        Self::refresh_shell_snippets(repo_path, profile_name);
 
         if resolved_files.is_empty() {
             info!(
@@ -493,6 +504,13 @@ impl ProfileService {
                     "Activated profile '{}' with {} files (including inherited/common)",
                     profile_name, success_count
                 );
+                hooks::fire(
+                    repo_path,
+                    profile_name,
+                    HookEvent::Activate {
+                        created: success_count,
+                    },
+                );
 
                 Ok(ProfileActivationResult {
                     success_count,
@@ -540,6 +558,8 @@ impl ProfileService {
 
         // Resolve the full file list (inheritance + common with overrides)
         let resolved_files = manifest.resolve_files(profile_name)?;
+        // A pull may have brought new snippets
+        Self::refresh_shell_snippets(repo_path, profile_name);
 
         if resolved_files.is_empty() {
             info!(
@@ -556,6 +576,15 @@ impl ProfileService {
         symlink_mgr.ensure_resolved_symlinks(profile_name, &resolved_files)
     }
 
+    /// Regenerate the shell snippet loader for `profile_name`. A failure
+    /// only leaves the previous loader in place, so it doesn't fail the
+    /// activation.
+    fn refresh_shell_snippets(repo_path: &Path, profile_name: &str) {
+        if let Err(e) = crate::utils::shell_snippets::write_loader(repo_path, profile_name) {
+            warn!("Failed to update shell snippets: {:#}", e);
+        }
+    }
+
     /// Ensure all common files have their symlinks created.
     ///
     /// This is an efficient "reconciliation" method that only creates
@@ -0,0 +1,252 @@
+//! Statistics about the storage repository.
+//!
+//! Collects how many files each profile tracks, how much space the
+//! repository and backups take, the largest files, and the files changed
+//! most often in git history, for spotting bloat worth pruning.
+
+use crate::config::Config;
+use crate::utils::{BackupManager, ProfileManifest};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of entries kept in the largest and most changed lists
+const TOP_FILES: usize = 10;
+
+/// Commits scanned when counting changes, newest first
+const MAX_COMMITS: usize = 2000;
+
+/// Files tracked by one profile (or common).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileStats {
+    pub name: String,
+    /// Files the profile lists itself
+    pub files: usize,
+    /// Files linked when the profile is active, with inherited and common files
+    pub resolved: usize,
+    /// Size of the profile's directory in the repository
+    pub size: u64,
+}
+
+/// Statistics for the storage repository and backups.
+#[derive(Debug, Clone, Default)]
+pub struct RepoStats {
+    /// Common files first, then each profile in manifest order
+    pub profiles: Vec<ProfileStats>,
+    /// Size of the files in the working tree, without `.git`
+    pub repo_size: u64,
+    /// Size of the `.git` directory
+    pub git_size: u64,
+    /// Largest files in the repository, as (path, size)
+    pub largest_files: Vec<(String, u64)>,
+    /// Files changed in the most commits, as (path, commits)
+    pub most_changed: Vec<(String, usize)>,
+    /// Commits scanned for `most_changed`
+    pub commits_scanned: usize,
+    /// Backup directory
+    pub backup_root: PathBuf,
+    /// Backup sessions in the backup directory
+    pub backup_sessions: usize,
+    /// Total size of the backups
+    pub backup_size: u64,
+}
+
+/// Service for repository statistics.
+pub struct StatsService;
+
+impl StatsService {
+    /// Collect statistics for the configured repository. Walks the whole
+    /// repository and its history, so call it off the UI thread.
+    pub fn collect(config: &Config) -> Result<RepoStats> {
+        let repo_path = &config.repo_path;
+        let manifest = ProfileManifest::load_or_backfill(repo_path)
+            .context("Failed to load profile manifest")?;
+
+        let mut files = Vec::new();
+        walk_files(repo_path, repo_path, &mut files);
+        let repo_size = files.iter().map(|(_, size)| size).sum();
+
+        let mut stats = RepoStats {
+            profiles: profile_stats(&manifest, &files),
+            repo_size,
+            git_size: dir_size(&repo_path.join(".git")),
+            largest_files: largest(files),
+            backup_root: BackupManager::default_root(),
+            ..RepoStats::default()
+        };
+
+        match most_changed(repo_path) {
+            Ok((changed, scanned)) => {
+                stats.most_changed = changed;
+                stats.commits_scanned = scanned;
+            }
+            Err(e) => tracing::warn!("Failed to count file changes: {}", e),
+        }
+
+        if let Ok(entries) = fs::read_dir(&stats.backup_root) {
+            for entry in entries.flatten() {
+                if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    stats.backup_sessions += 1;
+                    stats.backup_size += dir_size(&entry.path());
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// Per-profile counts, with sizes from the repository's top-level directories.
+fn profile_stats(manifest: &ProfileManifest, files: &[(String, u64)]) -> Vec<ProfileStats> {
+    let mut sizes: HashMap<&str, u64> = HashMap::new();
+    for (path, size) in files {
+        if let Some((dir, _)) = path.split_once('/') {
+            *sizes.entry(dir).or_default() += size;
+        }
+    }
+
+    let common = manifest.get_common_files().len();
+    let mut stats = vec![ProfileStats {
+        name: "common".to_string(),
+        files: common,
+        resolved: common,
+        size: sizes.get("common").copied().unwrap_or(0),
+    }];
+    stats.extend(manifest.profiles.iter().map(|profile| {
+        ProfileStats {
+            name: profile.name.clone(),
+            files: profile.synced_files.len(),
+            resolved: manifest
+                .resolve_files(&profile.name)
+                .map_or(profile.synced_files.len(), |files| files.len()),
+            size: sizes.get(profile.name.as_str()).copied().unwrap_or(0),
+        }
+    }));
+    stats
+}
+
+/// The [`TOP_FILES`] largest files, leaving out `DotState`'s own files at
+/// the repository root
+fn largest(mut files: Vec<(String, u64)>) -> Vec<(String, u64)> {
+    files.retain(|(path, _)| path.contains('/'));
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    files.truncate(TOP_FILES);
+    files
+}
+
+/// Count the commits touching each file, for the last [`MAX_COMMITS`]
+/// commits. Files at the repository root (the manifest, `machines.toml`)
+/// are `DotState`'s own and left out.
+fn most_changed(repo_path: &Path) -> Result<(Vec<(String, usize)>, usize)> {
+    let repo = git2::Repository::open(repo_path).context("Failed to open repository")?;
+    let mut revwalk = repo.revwalk()?;
+    if revwalk.push_head().is_err() {
+        // No commits yet
+        return Ok((Vec::new(), 0));
+    }
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut scanned = 0;
+    for oid in revwalk.take(MAX_COMMITS) {
+        let commit = repo.find_commit(oid?)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                continue;
+            };
+            let path = path.to_string_lossy();
+            if path.contains('/') {
+                *counts.entry(path.into_owned()).or_default() += 1;
+            }
+        }
+        scanned += 1;
+    }
+
+    let mut changed: Vec<(String, usize)> = counts.into_iter().collect();
+    changed.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    changed.truncate(TOP_FILES);
+    Ok((changed, scanned))
+}
+
+/// Collect every file under `dir` (skipping `.git`) as (path relative to
+/// `root`, size). Symlinks are not followed.
+fn walk_files(root: &Path, dir: &Path, files: &mut Vec<(String, u64)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            if entry.file_name() != ".git" {
+                walk_files(root, &path, files);
+            }
+        } else if meta.is_file() {
+            if let Ok(relative) = path.strip_prefix(root) {
+                // Same separators as git paths on every platform
+                let relative = relative.to_string_lossy().replace('\\', "/");
+                files.push((relative, meta.len()));
+            }
+        }
+    }
+}
+
+/// Total size of the files under `path`
+fn dir_size(path: &Path) -> u64 {
+    let mut files = Vec::new();
+    walk_files(path, path, &mut files);
+    files.iter().map(|(_, size)| size).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::GitManager;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collect_counts_sizes_and_changes() {
+        let temp = TempDir::new().unwrap();
+        let repo_path = temp.path().join("repo");
+        fs::create_dir_all(repo_path.join("Work")).unwrap();
+        fs::create_dir_all(repo_path.join("common")).unwrap();
+        let git_mgr = GitManager::open_or_init(&repo_path).unwrap();
+
+        let mut manifest = ProfileManifest::default();
+        manifest.add_profile("Work".to_string(), None);
+        manifest
+            .update_synced_files("Work", vec![".zshrc".to_string(), ".vimrc".to_string()])
+            .unwrap();
+        manifest.add_common_file(".gitconfig");
+        manifest.save(&repo_path).unwrap();
+
+        fs::write(repo_path.join("Work/.zshrc"), "a".repeat(100)).unwrap();
+        fs::write(repo_path.join("Work/.vimrc"), "b".repeat(10)).unwrap();
+        fs::write(repo_path.join("common/.gitconfig"), "c".repeat(50)).unwrap();
+        git_mgr.commit_all("Add files").unwrap();
+        fs::write(repo_path.join("Work/.zshrc"), "a".repeat(120)).unwrap();
+        git_mgr.commit_all("Update zshrc").unwrap();
+
+        let config = Config {
+            repo_path: repo_path.clone(),
+            ..Config::default()
+        };
+        let stats = StatsService::collect(&config).unwrap();
+
+        let work = stats.profiles.iter().find(|p| p.name == "Work").unwrap();
+        assert_eq!((work.files, work.resolved, work.size), (2, 3, 130));
+        assert_eq!(stats.profiles[0].size, 50);
+        assert_eq!(stats.largest_files[0], ("Work/.zshrc".to_string(), 120));
+        assert_eq!(stats.most_changed[0], ("Work/.zshrc".to_string(), 2));
+        assert_eq!(stats.commits_scanned, 2);
+        assert!(stats.git_size > 0);
+    }
+}
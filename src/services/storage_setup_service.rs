@@ -6,17 +6,22 @@
 //! - Repository cloning or creation
 //! - Repository initialization
 //! - Profile discovery
+//! - Listing existing repositories to restore from
+//! - Recording how far setup got, so an interrupted or failed setup resumes
+//!   from the step that didn't finish
 //!
 //! The service uses tokio spawn and oneshot channels to run operations
 //! asynchronously while the UI remains responsive.
 
 use crate::config::{Config, GitHubConfig};
-use crate::git::GitManager;
-use crate::github::GitHubClient;
+use crate::git::{GitManager, TransferProgress};
+use crate::github::{GitHubClient, GitHubRepo, RepoPermissions};
 use crate::ui::{GitHubSetupData, GitHubSetupStep};
 use crate::utils::ProfileManifest;
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::{info, warn};
 
@@ -54,13 +59,148 @@ pub enum StepResult {
     },
 }
 
+/// Name of the file in the data directory recording an unfinished setup
+const PROGRESS_FILE: &str = "setup_progress.toml";
+
+/// How far a GitHub setup got. Written as each step starts and removed when
+/// setup completes, so after a crash, Ctrl+C or a failed step the next launch
+/// finds it and offers to carry on with the same inputs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupProgress {
+    /// Step that was running, or that failed
+    pub step: GitHubSetupStep,
+    pub token: String,
+    pub repo_name: String,
+    pub is_private: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_exists: Option<bool>,
+    #[serde(default)]
+    pub is_new_repo: bool,
+    /// Why the step failed; `None` when setup was interrupted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SetupProgress {
+    /// Progress at `step` with the inputs and findings in `data`
+    #[must_use]
+    pub fn new(step: GitHubSetupStep, data: &GitHubSetupData) -> Self {
+        Self {
+            step,
+            token: data.token.clone(),
+            repo_name: data.repo_name.clone(),
+            is_private: data.is_private,
+            username: data.username.clone(),
+            repo_exists: data.repo_exists,
+            is_new_repo: data.is_new_repo,
+            error: None,
+        }
+    }
+
+    /// Setup data to resume with
+    #[must_use]
+    pub fn setup_data(&self) -> GitHubSetupData {
+        GitHubSetupData {
+            token: self.token.clone(),
+            repo_name: self.repo_name.clone(),
+            username: self.username.clone(),
+            repo_exists: self.repo_exists,
+            is_private: self.is_private,
+            delay_until: None,
+            is_new_repo: self.is_new_repo,
+        }
+    }
+
+    /// Step to resume from. The checks against GitHub are cheap and their
+    /// answers may have changed (a new token scope, a repository created
+    /// meanwhile), so they run again; later steps resume where they stopped.
+    #[must_use]
+    pub fn resume_step(&self) -> GitHubSetupStep {
+        match self.step {
+            GitHubSetupStep::Connecting
+            | GitHubSetupStep::ValidatingToken
+            | GitHubSetupStep::CheckingRepo => GitHubSetupStep::Connecting,
+            _ if self.username.is_none() => GitHubSetupStep::Connecting,
+            step => step,
+        }
+    }
+
+    /// Whether the user is resuming with the inputs this setup started with
+    #[must_use]
+    pub fn matches(&self, token: &str, repo_name: &str, is_private: bool) -> bool {
+        self.token == token && self.repo_name == repo_name && self.is_private == is_private
+    }
+
+    fn file() -> PathBuf {
+        crate::utils::get_data_dir().join(PROGRESS_FILE)
+    }
+
+    /// The unfinished setup, if there is one
+    #[must_use]
+    pub fn load() -> Option<Self> {
+        Self::load_from(&Self::file())
+    }
+
+    /// Record the progress. Failing to is logged: setup itself goes on.
+    pub fn save(&self) {
+        if let Err(e) = self.save_to(&Self::file()) {
+            warn!("Failed to record setup progress: {}", e);
+        }
+    }
+
+    /// Forget the unfinished setup
+    pub fn clear() {
+        let path = Self::file();
+        if path.exists() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("Failed to remove {:?}: {}", path, e);
+            }
+        }
+    }
+
+    fn load_from(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        toml::from_str(&content)
+            .map_err(|e| warn!("Ignoring unreadable {:?}: {}", path, e))
+            .ok()
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = toml::to_string(self).context("Failed to serialize setup progress")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        // The token is in it, like in the config file
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("Failed to set permissions on {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
 /// Handle for polling step completion
 pub struct StepHandle {
     /// Oneshot receiver for the step result
     pub receiver: oneshot::Receiver<Result<StepResult>>,
+    /// Transfer progress reported while cloning
+    pub progress: mpsc::Receiver<TransferProgress>,
 }
 
 impl StepHandle {
+    /// Most recent transfer progress since the last call, if any
+    #[must_use]
+    pub fn latest_progress(&self) -> Option<TransferProgress> {
+        self.progress.try_iter().last()
+    }
+
     /// Try to receive the result without blocking
     pub fn try_recv(&mut self) -> Option<Result<StepResult>> {
         match self.receiver.try_recv() {
@@ -87,6 +227,7 @@ impl StorageSetupService {
         config: &Config,
     ) -> StepHandle {
         let (sender, receiver) = oneshot::channel();
+        let (progress_sender, progress) = mpsc::channel();
 
         // Clone data needed for the async task
         let repo_path = config.repo_path.clone();
@@ -101,12 +242,41 @@ impl StorageSetupService {
                 repo_path,
                 default_branch,
                 active_profile,
+                progress_sender,
             )
             .await;
             let _ = sender.send(result);
         });
 
-        StepHandle { receiver }
+        StepHandle { receiver, progress }
+    }
+
+    /// List the user's repositories to restore an existing setup from.
+    ///
+    /// Blocks until GitHub answers, so call it off the UI thread. Names that
+    /// look like dotfiles repositories come first; otherwise the most
+    /// recently pushed repositories lead.
+    pub fn list_restorable_repos(token: &str) -> Result<Vec<GitHubRepo>> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("Failed to start async runtime")?;
+        let client = GitHubClient::new(token.to_string());
+        let mut repos = runtime.block_on(client.list_repos())?;
+        repos.sort_by_key(|repo| Self::restore_rank(&repo.name));
+        Ok(repos)
+    }
+
+    /// Sort key for restore candidates (lower is more likely to hold dotfiles)
+    fn restore_rank(name: &str) -> u8 {
+        let name = name.to_lowercase();
+        if name.contains("dotstate") {
+            0
+        } else if name.contains("dotfiles") || name.starts_with("dots") || name.contains("config") {
+            1
+        } else {
+            2
+        }
     }
 
     /// Process a setup step asynchronously
@@ -116,12 +286,15 @@ impl StorageSetupService {
         repo_path: PathBuf,
         default_branch: String,
         active_profile: String,
+        progress: mpsc::Sender<TransferProgress>,
     ) -> Result<StepResult> {
         match step {
             GitHubSetupStep::Connecting => Self::handle_connecting(setup_data).await,
             GitHubSetupStep::ValidatingToken => Self::handle_validating_token(setup_data).await,
             GitHubSetupStep::CheckingRepo => Self::handle_checking_repo(setup_data).await,
-            GitHubSetupStep::CloningRepo => Self::handle_cloning_repo(setup_data, &repo_path).await,
+            GitHubSetupStep::CloningRepo => {
+                Self::handle_cloning_repo(setup_data, &repo_path, progress).await
+            }
             GitHubSetupStep::CreatingRepo => Self::handle_creating_repo(setup_data).await,
             GitHubSetupStep::InitializingRepo => {
                 Self::handle_initializing_repo(
@@ -155,9 +328,28 @@ impl StorageSetupService {
 
         match client.get_user().await {
             Ok(user) => {
-                let repo_exists = client
-                    .repo_exists(&user.login, &setup_data.repo_name)
+                // Catch missing scopes before creating or cloning fails vaguely
+                if let Some(problem) =
+                    Self::scope_problem(user.scopes.as_deref(), setup_data.is_private)
+                {
+                    return Ok(StepResult::Failed {
+                        error_message: problem,
+                        cleanup_repo: false,
+                    });
+                }
+
+                let permissions = client
+                    .repo_permissions(&user.login, &setup_data.repo_name)
                     .await?;
+                if let Some(problem) = permissions
+                    .and_then(|p| Self::permission_problem(p, &user.login, &setup_data.repo_name))
+                {
+                    return Ok(StepResult::Failed {
+                        error_message: problem,
+                        cleanup_repo: false,
+                    });
+                }
+                let repo_exists = permissions.is_some();
 
                 setup_data.username = Some(user.login);
                 setup_data.repo_exists = Some(repo_exists);
@@ -176,6 +368,48 @@ impl StorageSetupService {
         }
     }
 
+    /// Explain which scope a classic token is missing, if any.
+    ///
+    /// Fine-grained tokens (`scopes` is `None`) don't list their permissions;
+    /// they are checked against the repository instead.
+    fn scope_problem(scopes: Option<&[String]>, private: bool) -> Option<String> {
+        let scopes = scopes?;
+        let has = |scope: &str| scopes.iter().any(|s| s == scope);
+        if has("repo") || (!private && has("public_repo")) {
+            return None;
+        }
+        let current = if scopes.is_empty() {
+            "none".to_string()
+        } else {
+            scopes.join(", ")
+        };
+        let (missing, why) = if private {
+            ("repo", "to create and sync a private repository")
+        } else {
+            ("public_repo", "to create and sync a public repository")
+        };
+        Some(format!(
+            "Your token is missing the '{missing}' scope, which is needed {why}.\n\n\
+            Current scopes: {current}\n\n\
+            Edit the token at https://github.com/settings/tokens and check '{missing}' \
+            (or 'repo', which covers private and public repositories), then try again."
+        ))
+    }
+
+    /// Explain why the token can't sync an existing repository, if it can't.
+    fn permission_problem(permissions: RepoPermissions, owner: &str, repo: &str) -> Option<String> {
+        if permissions.push {
+            return None;
+        }
+        Some(format!(
+            "Your token can read {owner}/{repo} but can't push to it.\n\n\
+            • Fine-grained token: give it access to this repository with \
+            'Contents: Read and write'\n\
+            • Classic token: check the 'repo' scope\n\n\
+            Update the token at https://github.com/settings/tokens, then try again."
+        ))
+    }
+
     /// Handle the `CheckingRepo` step
     async fn handle_checking_repo(setup_data: GitHubSetupData) -> Result<StepResult> {
         // Extract and validate required fields using pattern matching
@@ -212,6 +446,7 @@ impl StorageSetupService {
     async fn handle_cloning_repo(
         mut setup_data: GitHubSetupData,
         repo_path: &Path,
+        progress: mpsc::Sender<TransferProgress>,
     ) -> Result<StepResult> {
         let username = match setup_data.username.as_ref() {
             Some(u) => u.clone(),
@@ -233,7 +468,15 @@ impl StorageSetupService {
         let repo_path_clone = repo_path.to_path_buf();
         let token = setup_data.token.clone();
         let clone_result = tokio::task::spawn_blocking(move || {
-            GitManager::clone_or_open(&remote_url, &repo_path_clone, Some(&token))
+            let report = |p| {
+                let _ = progress.send(p);
+            };
+            GitManager::clone_or_open_with_progress(
+                &remote_url,
+                &repo_path_clone,
+                Some(&token),
+                &report,
+            )
         })
         .await?;
 
@@ -291,10 +534,20 @@ impl StorageSetupService {
                     delay_ms: Some(500),
                 })
             }
-            Err(e) => Ok(StepResult::Failed {
-                error_message: format!("Failed to create repository: {e}"),
-                cleanup_repo: false, // No local repo created yet
-            }),
+            Err(e) => {
+                let mut error_message = format!("Failed to create repository: {e}");
+                if setup_data.token.starts_with("github_pat_") {
+                    error_message.push_str(
+                        "\n\nFine-grained tokens need the account permission \
+                        'Administration: Read and write' to create repositories. \
+                        Add it to the token, or create the repository on GitHub first.",
+                    );
+                }
+                Ok(StepResult::Failed {
+                    error_message,
+                    cleanup_repo: false, // No local repo created yet
+                })
+            }
         }
     }
 
@@ -593,6 +846,24 @@ impl StorageSetupService {
         })
     }
 
+    /// Remove a clone that was cut off before it finished, so resuming the
+    /// clone starts over instead of opening a repository with nothing
+    /// checked out. A repository with a commit checked out is kept.
+    pub fn discard_partial_clone(repo_path: &Path) {
+        if !repo_path.join(".git").exists() {
+            return;
+        }
+        let complete = git2::Repository::open(repo_path)
+            .and_then(|repo| repo.head().map(|_| ()))
+            .is_ok();
+        if !complete {
+            info!("Removing interrupted clone at {:?}", repo_path);
+            if let Err(e) = std::fs::remove_dir_all(repo_path) {
+                warn!("Failed to remove interrupted clone: {}", e);
+            }
+        }
+    }
+
     /// Clean up a failed setup attempt
     ///
     /// This should be called when setup fails to ensure a clean state for retry.
@@ -650,6 +921,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scope_problem() {
+        let scopes = |list: &[&str]| list.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+
+        assert!(
+            StorageSetupService::scope_problem(Some(&scopes(&["repo", "gist"])), true).is_none()
+        );
+        assert!(
+            StorageSetupService::scope_problem(Some(&scopes(&["public_repo"])), false).is_none()
+        );
+        // Fine-grained tokens are checked against the repository instead
+        assert!(StorageSetupService::scope_problem(None, true).is_none());
+
+        let problem = StorageSetupService::scope_problem(Some(&scopes(&["public_repo"])), true)
+            .expect("private repo needs 'repo'");
+        assert!(problem.contains("missing the 'repo' scope"));
+        assert!(problem.contains("Current scopes: public_repo"));
+
+        let problem = StorageSetupService::scope_problem(Some(&[]), false).unwrap();
+        assert!(problem.contains("'public_repo'"));
+        assert!(problem.contains("Current scopes: none"));
+    }
+
+    #[test]
+    fn test_permission_problem() {
+        let read_only = RepoPermissions {
+            admin: false,
+            push: false,
+            pull: true,
+        };
+        let problem = StorageSetupService::permission_problem(read_only, "me", "dotfiles").unwrap();
+        assert!(problem.contains("me/dotfiles"));
+        assert!(problem.contains("Contents: Read and write"));
+
+        let writable = RepoPermissions {
+            push: true,
+            ..read_only
+        };
+        assert!(StorageSetupService::permission_problem(writable, "me", "dotfiles").is_none());
+    }
+
+    #[test]
+    fn test_restore_rank() {
+        let mut names = vec!["website", "my-dotfiles", "dotstate-storage", "nvim-config"];
+        names.sort_by_key(|name| StorageSetupService::restore_rank(name));
+        assert_eq!(
+            names,
+            vec!["dotstate-storage", "my-dotfiles", "nvim-config", "website"]
+        );
+    }
+
+    #[test]
+    fn test_setup_progress() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(PROGRESS_FILE);
+        let data = GitHubSetupData {
+            token: "ghp_abc".to_string(),
+            repo_name: "dotfiles".to_string(),
+            username: Some("me".to_string()),
+            repo_exists: Some(true),
+            is_private: true,
+            delay_until: None,
+            is_new_repo: false,
+        };
+        let mut progress = SetupProgress::new(GitHubSetupStep::CloningRepo, &data);
+        progress.error = Some("network unreachable".to_string());
+        progress.save_to(&path).unwrap();
+
+        let loaded = SetupProgress::load_from(&path).unwrap();
+        assert_eq!(loaded, progress);
+        assert_eq!(loaded.resume_step(), GitHubSetupStep::CloningRepo);
+        assert_eq!(loaded.setup_data().username.as_deref(), Some("me"));
+        assert!(loaded.matches("ghp_abc", "dotfiles", true));
+        assert!(!loaded.matches("ghp_abc", "dotfiles", false));
+
+        // Checks against GitHub run again
+        let checking = SetupProgress::new(GitHubSetupStep::CheckingRepo, &data);
+        assert_eq!(checking.resume_step(), GitHubSetupStep::Connecting);
+
+        std::fs::write(&path, "not toml [").unwrap();
+        assert!(SetupProgress::load_from(&path).is_none());
+    }
+
     #[test]
     fn test_failed_result() {
         let failed_result = StepResult::Failed {
@@ -6,6 +6,10 @@
 
 use crate::config::Config;
 use crate::file_manager::{copy_dir_all, Dotfile, FileManager};
+use crate::utils::hooks::{self, HookEvent};
+use crate::utils::installed_apps::InstalledApps;
+use crate::utils::scan_cache::ScanCache;
+use crate::utils::unicode_path::nfc;
 use crate::utils::{get_home_dir, sync_validation, ProfileManifest, SymlinkManager};
 use anyhow::{Context, Result};
 use std::collections::HashSet;
@@ -32,6 +36,18 @@ pub enum AddFileResult {
     ValidationFailed(String),
 }
 
+/// How an added file is stored in the repository.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddStorage {
+    /// Committed as it is.
+    #[default]
+    Plain,
+    /// Committed as a Git LFS pointer, the content kept in LFS storage.
+    Lfs,
+    /// Encrypted with sops; the home directory gets a decrypted copy.
+    Encrypted,
+}
+
 /// Result of removing a file from sync.
 #[derive(Debug)]
 pub enum RemoveFileResult {
@@ -41,6 +57,74 @@ pub enum RemoveFileResult {
     NotSynced,
 }
 
+/// Result of moving several files to common at once.
+#[derive(Debug, Default)]
+pub struct MoveManyResult {
+    /// Files that were moved.
+    pub moved: Vec<String>,
+    /// Files that couldn't be moved, with the error.
+    pub failed: Vec<(String, String)>,
+    /// Whether the moves were committed.
+    pub committed: bool,
+}
+
+/// A change to the synced files of the active profile, queued to be
+/// applied with others by [`SyncService::apply_operations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileOperation {
+    /// Start syncing a file in the home directory, in the profile or common
+    Add { relative_path: String, common: bool },
+    /// Stop syncing a file, putting it back in the home directory
+    Remove { relative_path: String, common: bool },
+    /// Move a file of the profile to common
+    MoveToCommon { relative_path: String },
+    /// Move a common file to the profile
+    MoveFromCommon { relative_path: String },
+}
+
+impl FileOperation {
+    /// The file the operation changes, relative to the home directory
+    #[must_use]
+    pub fn relative_path(&self) -> &str {
+        match self {
+            FileOperation::Add { relative_path, .. }
+            | FileOperation::Remove { relative_path, .. }
+            | FileOperation::MoveToCommon { relative_path }
+            | FileOperation::MoveFromCommon { relative_path } => relative_path,
+        }
+    }
+
+    /// What the operation does, e.g. "Add ~/.zshrc"
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let verb = match self {
+            FileOperation::Add { common: false, .. } => "Add",
+            FileOperation::Add { common: true, .. } => "Add to common",
+            FileOperation::Remove { .. } => "Remove",
+            FileOperation::MoveToCommon { .. } => "Move to common",
+            FileOperation::MoveFromCommon { .. } => "Move to profile",
+        };
+        format!("{verb} ~/{}", self.relative_path())
+    }
+
+    /// The operation that undoes this one
+    fn inverse(&self) -> FileOperation {
+        let relative_path = self.relative_path().to_string();
+        match *self {
+            FileOperation::Add { common, .. } => FileOperation::Remove {
+                relative_path,
+                common,
+            },
+            FileOperation::Remove { common, .. } => FileOperation::Add {
+                relative_path,
+                common,
+            },
+            FileOperation::MoveToCommon { .. } => FileOperation::MoveFromCommon { relative_path },
+            FileOperation::MoveFromCommon { .. } => FileOperation::MoveToCommon { relative_path },
+        }
+    }
+}
+
 /// Service for file synchronization operations.
 ///
 /// This service provides a clean interface for file sync operations without
@@ -72,9 +156,32 @@ impl SyncService {
         full_path: &Path,
         relative_path: &str,
         backup_enabled: bool,
+    ) -> Result<AddFileResult> {
+        Self::add_file_to_sync_as(
+            config,
+            full_path,
+            relative_path,
+            backup_enabled,
+            AddStorage::Plain,
+        )
+    }
+
+    /// Add a file to sync, stored in the repository as `storage` says.
+    ///
+    /// Same as [`Self::add_file_to_sync`], for large or binary files that
+    /// shouldn't be committed as they are.
+    pub fn add_file_to_sync_as(
+        config: &Config,
+        full_path: &Path,
+        relative_path: &str,
+        backup_enabled: bool,
+        storage: AddStorage,
     ) -> Result<AddFileResult> {
         let profile_name = &config.active_profile;
         let repo_path = &config.repo_path;
+        // Stored and recorded precomposed, whatever the name on disk
+        let relative_path = nfc(relative_path);
+        let relative_path: &str = &relative_path;
 
         // Get previously synced files
         let previously_synced = Self::get_synced_files(repo_path, profile_name)?;
@@ -133,9 +240,40 @@ impl SyncService {
             return Ok(AddFileResult::ValidationFailed(error_msg));
         }
 
+        let ignore_validation = sync_validation::validate_not_ignored(
+            repo_path,
+            &format!("{profile_name}/{relative_path}"),
+        );
+        if let Some(error_msg) = ignore_validation.error_message {
+            warn!("{}", error_msg);
+            return Ok(AddFileResult::ValidationFailed(error_msg));
+        }
+
+        match storage {
+            AddStorage::Plain => {}
+            AddStorage::Lfs if !crate::utils::lfs::is_available() => {
+                return Ok(AddFileResult::ValidationFailed(
+                    "Git LFS isn't installed. Install it from https://git-lfs.com to store large files with it.".to_string(),
+                ));
+            }
+            AddStorage::Lfs => {}
+            AddStorage::Encrypted if !crate::utils::sops::is_enabled(repo_path) => {
+                return Ok(AddFileResult::ValidationFailed(format!(
+                    "Encryption needs sops: add a {} to the repository first.",
+                    crate::utils::sops::SOPS_CONFIG
+                )));
+            }
+            AddStorage::Encrypted if original_source.is_dir() => {
+                return Ok(AddFileResult::ValidationFailed(
+                    "Only files can be encrypted, not directories".to_string(),
+                ));
+            }
+            AddStorage::Encrypted => {}
+        }
+
         info!(
-            "Adding file to sync: {} (profile: {})",
-            relative_path, profile_name
+            "Adding file to sync: {} (profile: {}, storage: {:?})",
+            relative_path, profile_name, storage
         );
         debug!("Source path: {:?}", full_path);
         debug!("Repo destination: {:?}", repo_file_path);
@@ -167,6 +305,28 @@ impl SyncService {
             .context("Failed to copy file to repo")?;
         info!("Successfully copied file to repository");
 
+        let stored = match storage {
+            AddStorage::Plain => Ok(()),
+            AddStorage::Lfs => crate::utils::lfs::track(
+                repo_path,
+                &format!("{profile_name}/{relative_path}"),
+                repo_file_path.is_dir(),
+            ),
+            // Encrypted in place; the symlink step then writes a decrypted copy
+            AddStorage::Encrypted => {
+                crate::utils::sops::encrypt(repo_path, &repo_file_path, &repo_file_path)
+            }
+        };
+        if let Err(e) = stored {
+            // The home file hasn't been touched yet; drop the copy
+            let _ = if repo_file_path.is_dir() {
+                std::fs::remove_dir_all(&repo_file_path)
+            } else {
+                std::fs::remove_file(&repo_file_path)
+            };
+            return Err(e);
+        }
+
         // Create symlink using SymlinkManager
         info!("Creating symlink...");
         let mut symlink_mgr = SymlinkManager::new_with_backup(repo_path.clone(), backup_enabled)?;
@@ -180,6 +340,145 @@ impl SyncService {
         Self::add_file_to_manifest(repo_path, profile_name, relative_path)?;
 
         info!("Successfully added file to sync: {}", relative_path);
+        hooks::fire(
+            repo_path,
+            profile_name,
+            HookEvent::Add {
+                path: relative_path,
+            },
+        );
+        Ok(AddFileResult::Success)
+    }
+
+    /// Add a file to sync as a managed block.
+    ///
+    /// Instead of replacing the file with a symlink, dotstate keeps a
+    /// delimited block inside it and leaves the rest alone. An existing block
+    /// in the file (synced from another machine) becomes the stored content;
+    /// otherwise the block starts out empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Application configuration.
+    /// * `relative_path` - Path relative to home directory.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success, already synced, or validation failure.
+    pub fn add_block_to_sync(config: &Config, relative_path: &str) -> Result<AddFileResult> {
+        let profile_name = &config.active_profile;
+        let repo_path = &config.repo_path;
+
+        if Self::get_synced_files(repo_path, profile_name)?.contains(relative_path) {
+            debug!("File already synced: {}", relative_path);
+            return Ok(AddFileResult::AlreadySynced);
+        }
+
+        let target_path = get_home_dir().join(relative_path);
+        if target_path.is_dir() {
+            return Ok(AddFileResult::ValidationFailed(
+                "Managed blocks only work for files, not directories".to_string(),
+            ));
+        }
+        let existing = std::fs::read_to_string(&target_path).unwrap_or_default();
+        let body = crate::utils::managed_block::extract(&existing).unwrap_or_default();
+
+        info!(
+            "Adding {} as a managed block (profile: {})",
+            relative_path, profile_name
+        );
+        let repo_file_path = repo_path.join(profile_name).join(relative_path);
+        if let Some(parent) = repo_file_path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create repo directory")?;
+        }
+        std::fs::write(&repo_file_path, body).context("Failed to write block to repo")?;
+
+        Self::add_file_to_manifest(repo_path, profile_name, relative_path)?;
+        let mut manifest = ProfileManifest::load_or_backfill(repo_path)?;
+        if !manifest.managed_blocks.iter().any(|f| f == relative_path) {
+            manifest.managed_blocks.push(relative_path.to_string());
+            manifest.managed_blocks.sort();
+            manifest.save(repo_path)?;
+        }
+
+        // Created after the manifest is saved so it sees the new block
+        let mut symlink_mgr = SymlinkManager::new_with_backup(repo_path.clone(), false)?;
+        symlink_mgr
+            .add_symlink_to_profile(profile_name, relative_path)
+            .context("Failed to write managed block")?;
+
+        info!("Successfully added managed block: {}", relative_path);
+        Ok(AddFileResult::Success)
+    }
+
+    /// Add a git config file in include mode.
+    ///
+    /// Its shared settings move to the repository and the real file gets an
+    /// `include.path` pointing at them, keeping the machine's identity,
+    /// credential helpers and includes local.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Application configuration.
+    /// * `relative_path` - Path relative to home directory.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success, already synced, or validation failure.
+    pub fn add_git_include_to_sync(config: &Config, relative_path: &str) -> Result<AddFileResult> {
+        let profile_name = &config.active_profile;
+        let repo_path = &config.repo_path;
+
+        if Self::get_synced_files(repo_path, profile_name)?.contains(relative_path) {
+            debug!("File already synced: {}", relative_path);
+            return Ok(AddFileResult::AlreadySynced);
+        }
+
+        let target_path = get_home_dir().join(relative_path);
+        if target_path.is_dir() || target_path.is_symlink() {
+            return Ok(AddFileResult::ValidationFailed(
+                "Include mode needs a regular git config file".to_string(),
+            ));
+        }
+        let existing = std::fs::read_to_string(&target_path).unwrap_or_default();
+        let (shared, local) =
+            crate::utils::git_include::split(&crate::utils::managed_block::strip(&existing));
+
+        info!(
+            "Adding {} in include mode (profile: {})",
+            relative_path, profile_name
+        );
+        if config.backup_enabled && target_path.exists() {
+            let backups = crate::utils::BackupManager::new()?;
+            let session = backups.create_backup_session()?;
+            backups
+                .backup_path(&session, &target_path, relative_path)
+                .context("Failed to back up git config")?;
+        }
+        let repo_file_path = repo_path.join(profile_name).join(relative_path);
+        if let Some(parent) = repo_file_path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create repo directory")?;
+        }
+        std::fs::write(&repo_file_path, shared).context("Failed to write shared settings")?;
+        if target_path.exists() {
+            std::fs::write(&target_path, local).context("Failed to write local settings")?;
+        }
+
+        Self::add_file_to_manifest(repo_path, profile_name, relative_path)?;
+        let mut manifest = ProfileManifest::load_or_backfill(repo_path)?;
+        if !manifest.git_includes.iter().any(|f| f == relative_path) {
+            manifest.git_includes.push(relative_path.to_string());
+            manifest.git_includes.sort();
+            manifest.save(repo_path)?;
+        }
+
+        // Created after the manifest is saved so it sees the new include
+        let mut symlink_mgr = SymlinkManager::new_with_backup(repo_path.clone(), false)?;
+        symlink_mgr
+            .add_symlink_to_profile(profile_name, relative_path)
+            .context("Failed to write include")?;
+
+        info!("Successfully added git include: {}", relative_path);
         Ok(AddFileResult::Success)
     }
 
@@ -236,6 +535,9 @@ impl SyncService {
                             .context("Failed to restore file from repo")?;
                     }
                 }
+            } else if crate::utils::git_include::is_installed(&repo_file_path, &target_path) {
+                // Inline the included settings before they leave the repo
+                crate::utils::managed_block::write(&repo_file_path, &target_path)?;
             }
         }
 
@@ -266,6 +568,17 @@ impl SyncService {
 
         let mut manifest = ProfileManifest::load_or_backfill(repo_path)?;
         manifest.update_synced_files(profile_name, remaining_files)?;
+        // A managed block stays in the file; it's just no longer synced
+        let still_synced = manifest
+            .profiles
+            .iter()
+            .flat_map(|p| &p.synced_files)
+            .chain(&manifest.common.synced_files)
+            .any(|f| f == relative_path);
+        if !still_synced {
+            manifest.managed_blocks.retain(|f| f != relative_path);
+            manifest.git_includes.retain(|f| f != relative_path);
+        }
         manifest.save(repo_path)?;
 
         info!("Successfully removed file from sync: {}", relative_path);
@@ -305,6 +618,7 @@ impl SyncService {
             .find(|p| p.name == profile_name)
             .map(|p| p.synced_files.clone())
             .unwrap_or_default();
+        let relative_path = nfc(relative_path);
 
         if current_files.contains(&relative_path.to_string()) {
             debug!("File already in manifest, skipping update");
@@ -332,11 +646,19 @@ impl SyncService {
     ///
     /// List of dotfiles found, with sync status marked.
     pub fn scan_dotfiles(config: &Config) -> Result<Vec<Dotfile>> {
+        let _span = tracing::info_span!("scan_dotfiles").entered();
         use crate::dotfile_candidates::get_default_dotfile_paths;
 
         let file_manager = FileManager::new()?;
         let dotfile_names = get_default_dotfile_paths();
-        let mut found = file_manager.scan_dotfiles(&dotfile_names);
+
+        // Only re-check candidates in directories that changed since the last scan
+        let mut scan_cache = ScanCache::load();
+        let existing = scan_cache.existing(file_manager.home_dir(), &dotfile_names);
+        if let Err(e) = scan_cache.save() {
+            warn!("Failed to save dotfile scan cache: {}", e);
+        }
+        let mut found = file_manager.scan_dotfiles(&existing);
 
         debug!(
             "Found {} dotfiles from scan. Paths: {:?}",
@@ -357,7 +679,7 @@ impl SyncService {
                 .iter()
                 .map(|p| {
                     let p = p.replace('\\', "/");
-                    p.strip_prefix("./").unwrap_or(&p).to_string()
+                    nfc(p.strip_prefix("./").unwrap_or(&p)).into_owned()
                 })
                 .collect();
 
@@ -369,7 +691,7 @@ impl SyncService {
             .iter()
             .map(|p| {
                 let p = p.replace('\\', "/");
-                p.strip_prefix("./").unwrap_or(&p).to_string()
+                nfc(p.strip_prefix("./").unwrap_or(&p)).into_owned()
             })
             .collect();
 
@@ -377,7 +699,11 @@ impl SyncService {
 
         for dotfile in &mut found {
             let rel_raw = dotfile.relative_path.to_string_lossy().replace('\\', "/");
-            let rel = rel_raw.strip_prefix("./").unwrap_or(&rel_raw).to_string();
+            // Names read from disk may be decomposed (macOS); the manifest's aren't
+            let rel = nfc(rel_raw.strip_prefix("./").unwrap_or(&rel_raw)).into_owned();
+            if rel != rel_raw {
+                dotfile.relative_path = PathBuf::from(&rel);
+            }
 
             if synced_set.contains(&rel) {
                 dotfile.synced = true;
@@ -393,6 +719,13 @@ impl SyncService {
             }
         }
 
+        // Leave out configs of applications that aren't installed anymore
+        let installed = InstalledApps::detect();
+        found.retain(|dotfile| {
+            let rel = dotfile.relative_path.to_string_lossy().replace('\\', "/");
+            dotfile.synced || installed.has_app_for(&rel) != Some(false)
+        });
+
         // Also add custom files from config
         let home_dir = get_home_dir();
         for custom_path in &config.custom_files {
@@ -596,6 +929,13 @@ impl SyncService {
             return Ok(AddFileResult::ValidationFailed(error_msg));
         }
 
+        let ignore_validation =
+            sync_validation::validate_not_ignored(repo_path, &format!("common/{relative_path}"));
+        if let Some(error_msg) = ignore_validation.error_message {
+            warn!("{}", error_msg);
+            return Ok(AddFileResult::ValidationFailed(error_msg));
+        }
+
         info!("Adding common file to sync: {}", relative_path);
 
         // Ensure common directory exists
@@ -859,6 +1199,187 @@ impl SyncService {
         Ok(())
     }
 
+    /// Move several files from the active profile to common and commit them
+    /// together.
+    ///
+    /// Each entry is a file's relative path and the profiles holding an
+    /// identical copy to clean up (see [`Self::move_to_common_with_cleanup`]).
+    /// The files should already have been validated; one failing doesn't stop
+    /// the others. Only the paths the moves touched are committed, so other
+    /// pending changes are left for the next sync.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Application configuration.
+    /// * `files` - Relative paths with the profiles to clean up for each.
+    ///
+    /// # Returns
+    ///
+    /// The files moved and failed, and whether the commit was made.
+    pub fn move_many_to_common(
+        config: &Config,
+        files: &[(String, Vec<String>)],
+    ) -> Result<MoveManyResult> {
+        let mut result = MoveManyResult::default();
+        let mut touched = vec![".dotstate-profiles.toml".to_string()];
+
+        for (relative_path, profiles_to_cleanup) in files {
+            match Self::move_to_common_with_cleanup(config, relative_path, profiles_to_cleanup) {
+                Ok(()) => {
+                    touched.push(format!("common/{relative_path}"));
+                    touched.push(format!("{}/{relative_path}", config.active_profile));
+                    touched.extend(
+                        profiles_to_cleanup
+                            .iter()
+                            .map(|profile| format!("{profile}/{relative_path}")),
+                    );
+                    result.moved.push(relative_path.clone());
+                }
+                Err(e) => {
+                    warn!("Error moving {} to common: {}", relative_path, e);
+                    result
+                        .failed
+                        .push((relative_path.clone(), format!("{e:#}")));
+                }
+            }
+        }
+
+        if result.moved.is_empty() {
+            return Ok(result);
+        }
+
+        let summary = if result.moved.len() == 1 {
+            format!("Move {} to common", result.moved[0])
+        } else {
+            format!("Move {} files to common", result.moved.len())
+        };
+        match Self::commit_touched(config, &touched, &summary) {
+            Ok(committed) => result.committed = committed,
+            // The moves stand; the next sync commits them
+            Err(e) => warn!("Failed to commit files moved to common: {:#}", e),
+        }
+
+        info!("Moved {} file(s) to common", result.moved.len());
+        Ok(result)
+    }
+
+    /// Apply `operations` in order, all or nothing: when one fails, the ones
+    /// applied before it are undone in reverse order and the error says
+    /// which failed. Backups are only taken going forward, not while undoing.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Application configuration.
+    /// * `operations` - The operations, in the order to apply them.
+    /// * `backup_enabled` - Whether to back up files that get replaced.
+    ///
+    /// # Returns
+    ///
+    /// The number of operations applied.
+    pub fn apply_operations(
+        config: &Config,
+        operations: &[FileOperation],
+        backup_enabled: bool,
+    ) -> Result<usize> {
+        for (applied, operation) in operations.iter().enumerate() {
+            let Err(e) = Self::apply_operation(config, operation, backup_enabled) else {
+                continue;
+            };
+            warn!(
+                "{} failed, undoing {} change(s): {:#}",
+                operation.describe(),
+                applied,
+                e
+            );
+            let mut not_undone = Vec::new();
+            for done in operations[..applied].iter().rev() {
+                if let Err(undo_error) = Self::apply_operation(config, &done.inverse(), false) {
+                    warn!("Failed to undo {}: {:#}", done.describe(), undo_error);
+                    not_undone.push(done.describe());
+                }
+            }
+            let mut message = format!("{} failed: {e:#}", operation.describe());
+            if applied > 0 {
+                message.push_str(&format!(". The {applied} change(s) before it were undone"));
+            }
+            if !not_undone.is_empty() {
+                message.push_str(&format!(", except: {}", not_undone.join(", ")));
+            }
+            anyhow::bail!(message);
+        }
+        info!("Applied {} queued file operation(s)", operations.len());
+        Ok(operations.len())
+    }
+
+    /// Apply one queued operation, failing when it would do nothing
+    fn apply_operation(
+        config: &Config,
+        operation: &FileOperation,
+        backup_enabled: bool,
+    ) -> Result<()> {
+        let relative_path = operation.relative_path();
+        match operation {
+            FileOperation::Add { common, .. } => {
+                let full_path = get_home_dir().join(relative_path);
+                let result = if *common {
+                    Self::add_common_file_to_sync(
+                        config,
+                        &full_path,
+                        relative_path,
+                        backup_enabled,
+                    )?
+                } else {
+                    Self::add_file_to_sync(config, &full_path, relative_path, backup_enabled)?
+                };
+                match result {
+                    AddFileResult::Success => Ok(()),
+                    AddFileResult::AlreadySynced => {
+                        anyhow::bail!("{relative_path} is already synced")
+                    }
+                    AddFileResult::ValidationFailed(message) => anyhow::bail!(message),
+                }
+            }
+            FileOperation::Remove { common, .. } => {
+                let result = if *common {
+                    Self::remove_common_file_from_sync(config, relative_path)?
+                } else {
+                    Self::remove_file_from_sync(config, relative_path)?
+                };
+                match result {
+                    RemoveFileResult::Success => Ok(()),
+                    RemoveFileResult::NotSynced => anyhow::bail!("{relative_path} is not synced"),
+                }
+            }
+            FileOperation::MoveToCommon { .. } => Self::move_to_common(config, relative_path),
+            FileOperation::MoveFromCommon { .. } => Self::move_from_common(config, relative_path),
+        }
+    }
+
+    /// Commit the changed paths equal to or under one of `touched`. Returns
+    /// false if none of them changed.
+    fn commit_touched(config: &Config, touched: &[String], summary: &str) -> Result<bool> {
+        let git_mgr = crate::git::GitManager::open_or_init(&config.repo_path)?;
+        let paths: Vec<String> = git_mgr
+            .get_changed_files()?
+            .iter()
+            .map(|entry| entry.get(2..).unwrap_or(entry).to_string())
+            .filter(|path| {
+                touched
+                    .iter()
+                    .any(|t| path == t || path.starts_with(&format!("{t}/")))
+            })
+            .collect();
+        if paths.is_empty() {
+            return Ok(false);
+        }
+        git_mgr.commit_paths(
+            &paths,
+            &std::collections::HashMap::new(),
+            &config.format_commit_message(summary),
+        )?;
+        Ok(true)
+    }
+
     /// Move a file from common to the current profile.
     ///
     /// # Arguments
@@ -928,6 +1449,206 @@ impl SyncService {
         Ok(())
     }
 
+    /// Rename or move a synced file (profile or common).
+    ///
+    /// The file is renamed inside the storage folder, so git sees a rename
+    /// and keeps its history. The manifest entry is updated, the symlink is
+    /// created at the new location and the old one removed. If the manifest
+    /// or the new symlink can't be written, the rename is undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Application configuration.
+    /// * `old_path` - Current path relative to the home directory.
+    /// * `new_path` - New path relative to the home directory.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub fn rename_file(config: &Config, old_path: &str, new_path: &str) -> Result<()> {
+        let repo_path = &config.repo_path;
+        let profile_name = &config.active_profile;
+        let old_path = old_path.trim_end_matches('/');
+        let new_path = new_path.trim_end_matches('/');
+
+        let is_relative = |p: &str| {
+            !p.is_empty()
+                && Path::new(p)
+                    .components()
+                    .all(|c| matches!(c, std::path::Component::Normal(_)))
+        };
+        if !is_relative(new_path) {
+            return Err(anyhow::anyhow!(
+                "'{new_path}' must be a path inside the home directory, e.g. .config/app/config"
+            ));
+        }
+        if old_path == new_path {
+            return Err(anyhow::anyhow!("The new path is the same as the old one"));
+        }
+
+        let original_manifest = ProfileManifest::load_or_backfill(repo_path)?;
+        let mut manifest = original_manifest.clone();
+        let is_common = manifest.is_common_file(old_path);
+        // A managed block or git include lives in the user's real file, which
+        // moves along
+        let in_place = manifest.managed_blocks.iter().any(|f| f == old_path)
+            || manifest.git_includes.iter().any(|f| f == old_path);
+        let new_is_synced = manifest.is_common_file(new_path);
+        let profile = manifest
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == *profile_name)
+            .ok_or_else(|| anyhow::anyhow!("Profile '{profile_name}' not found"))?;
+        if !is_common && !profile.synced_files.iter().any(|f| f == old_path) {
+            return Err(anyhow::anyhow!(
+                "File '{old_path}' is not synced in profile '{profile_name}' or common"
+            ));
+        }
+        if new_is_synced || profile.synced_files.iter().any(|f| f == new_path) {
+            return Err(anyhow::anyhow!("'{new_path}' is already synced"));
+        }
+
+        let folder = if is_common {
+            "common"
+        } else {
+            profile_name.as_str()
+        };
+        let source = repo_path.join(folder).join(old_path);
+        let dest = repo_path.join(folder).join(new_path);
+        let home_dir = get_home_dir();
+        let old_target = home_dir.join(old_path);
+        let new_target = home_dir.join(new_path);
+
+        if source.symlink_metadata().is_err() {
+            return Err(anyhow::anyhow!(
+                "'{old_path}' is missing from the repository"
+            ));
+        }
+        if dest.symlink_metadata().is_ok() {
+            return Err(anyhow::anyhow!(
+                "{} already exists in the repository",
+                dest.display()
+            ));
+        }
+        if new_target.symlink_metadata().is_ok() {
+            return Err(anyhow::anyhow!("{} already exists", new_target.display()));
+        }
+
+        info!(
+            "Renaming {} to {} ({})",
+            old_path,
+            new_path,
+            if is_common { "common" } else { profile_name }
+        );
+
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create parent directories")?;
+        }
+        std::fs::rename(&source, &dest).context("Failed to rename the file in the repository")?;
+
+        if is_common {
+            manifest.remove_common_file(old_path);
+            manifest.add_common_file(new_path);
+        } else if let Some(profile) = manifest
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == *profile_name)
+        {
+            for file in &mut profile.synced_files {
+                if file == old_path {
+                    *file = new_path.to_string();
+                }
+            }
+            profile.synced_files.sort();
+        }
+        rename_entry(&mut manifest.managed_blocks, old_path, new_path);
+        rename_entry(&mut manifest.git_includes, old_path, new_path);
+        // Children of a renamed directory kept local, and the recorded hash
+        let old_prefix = format!("{old_path}/");
+        for entry in &mut manifest.local_only {
+            if let Some(rest) = entry.strip_prefix(&old_prefix) {
+                *entry = format!("{new_path}/{rest}");
+            }
+        }
+        manifest.local_only.sort();
+        if let Some(hash) = manifest.hashes.remove(&format!("{folder}/{old_path}")) {
+            manifest.hashes.insert(format!("{folder}/{new_path}"), hash);
+        }
+
+        // Disable backups since nothing of the user's is replaced
+        let mut symlink_mgr = SymlinkManager::new_with_backup(repo_path.clone(), false)?;
+        let was_decrypted = symlink_mgr
+            .tracking
+            .symlinks
+            .iter()
+            .any(|s| s.source == source && (s.decrypted || s.rendered));
+        let moved_home_file = in_place && old_target.is_file() && !old_target.is_symlink();
+        let linked = manifest.save(repo_path).and_then(|()| {
+            if moved_home_file {
+                if let Some(parent) = new_target.parent() {
+                    std::fs::create_dir_all(parent)
+                        .context("Failed to create parent directories")?;
+                }
+                std::fs::rename(&old_target, &new_target)
+                    .with_context(|| format!("Failed to move {}", old_target.display()))?;
+            }
+            // Reloaded so it knows how the new path is kept
+            symlink_mgr = SymlinkManager::new_with_backup(repo_path.clone(), false)?;
+            let operation = if is_common {
+                symlink_mgr.add_common_symlink(new_path)?
+            } else {
+                symlink_mgr.add_symlink_to_profile(profile_name, new_path)?
+            };
+            match operation.status {
+                crate::utils::symlink_manager::OperationStatus::Success => Ok(()),
+                status => Err(anyhow::anyhow!(
+                    "Failed to create the symlink at {}: {status:?}",
+                    new_target.display()
+                )),
+            }
+        });
+        if let Err(e) = linked {
+            warn!("Rename of {} failed, rolling back: {}", old_path, e);
+            if moved_home_file && new_target.is_file() && !old_target.exists() {
+                if let Err(e) = std::fs::rename(&new_target, &old_target) {
+                    warn!("Failed to move {:?} back: {}", new_target, e);
+                }
+            }
+            if let Err(e) = std::fs::rename(&dest, &source) {
+                warn!("Failed to move {:?} back: {}", dest, e);
+            }
+            if let Err(e) = original_manifest.save(repo_path) {
+                warn!("Failed to restore the manifest: {}", e);
+            }
+            return Err(e);
+        }
+
+        // The old symlink (or decrypted copy) now points nowhere
+        if is_common {
+            symlink_mgr.remove_common_symlink_from_tracking(old_path)?;
+        } else {
+            symlink_mgr.remove_symlink_from_tracking(profile_name, old_path)?;
+        }
+        if let Ok(metadata) = old_target.symlink_metadata() {
+            if metadata.is_symlink() || (was_decrypted && metadata.is_file()) {
+                std::fs::remove_file(&old_target).context("Failed to remove the old symlink")?;
+            }
+        }
+
+        // Drop directories the rename left empty in the repository
+        let folder_path = repo_path.join(folder);
+        let mut dir = source.parent();
+        while let Some(parent) = dir.filter(|d| *d != folder_path && d.starts_with(&folder_path)) {
+            if std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+
+        info!("Successfully renamed {} to {}", old_path, new_path);
+        Ok(())
+    }
+
     /// Get the set of common files.
     ///
     /// # Arguments
@@ -943,6 +1664,14 @@ impl SyncService {
     }
 }
 
+/// Replace `old` with `new` in a sorted list of paths, if it's there
+fn rename_entry(entries: &mut [String], old: &str, new: &str) {
+    if let Some(entry) = entries.iter_mut().find(|entry| *entry == old) {
+        *entry = new.to_string();
+        entries.sort();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,247 @@
+//! GitHub token health checks and in-place token replacement.
+//!
+//! Fine-grained personal access tokens expire, and any token can be revoked.
+//! This service detects both (ahead of time through the API, or after the
+//! fact from git's authentication errors) so the UI can ask for a new token
+//! instead of failing clone, fetch and push with generic errors.
+
+use crate::config::{Config, RepoMode};
+use crate::git::GitManager;
+use crate::github::{GitHubClient, TokenStatus};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use tracing::{debug, warn};
+
+/// How long before expiry the user is asked to re-authenticate
+pub const EXPIRY_WARNING_DAYS: i64 = 7;
+
+/// Timeout for token API requests
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Why the user needs to provide a new token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReauthReason {
+    /// GitHub or git rejected the stored token
+    Rejected,
+    /// The stored token expires soon
+    Expiring { expires_at: DateTime<Utc> },
+}
+
+impl ReauthReason {
+    /// One-line explanation for the re-authenticate screen
+    #[must_use]
+    pub fn message(&self, now: DateTime<Utc>) -> String {
+        match self {
+            Self::Rejected => {
+                "GitHub rejected the stored token. It has expired, been revoked or lost access to the repository."
+                    .to_string()
+            }
+            Self::Expiring { expires_at } => {
+                let hours = (*expires_at - now).num_hours().max(0);
+                let when = if hours < 24 {
+                    format!("in {hours} hour(s)")
+                } else {
+                    format!("in {} day(s)", hours / 24)
+                };
+                format!(
+                    "The stored token expires {when} ({}). Replace it now to keep syncing.",
+                    expires_at.format("%Y-%m-%d %H:%M UTC")
+                )
+            }
+        }
+    }
+}
+
+/// Service for checking and replacing the GitHub token.
+pub struct TokenService;
+
+impl TokenService {
+    /// Whether syncing uses a GitHub token that can be checked
+    #[must_use]
+    pub fn uses_token(config: &Config) -> bool {
+        config.repo_mode == RepoMode::GitHub
+            && config.github.is_some()
+            && config.get_github_token().is_some()
+    }
+
+    /// Whether the token comes from `DOTSTATE_GITHUB_TOKEN` (and so can't be
+    /// replaced from the config)
+    #[must_use]
+    pub fn token_from_env() -> bool {
+        std::env::var("DOTSTATE_GITHUB_TOKEN").is_ok_and(|t| !t.is_empty())
+    }
+
+    /// Ask GitHub whether the stored token still works and when it expires.
+    ///
+    /// Network problems are not reported; the user is only asked to
+    /// re-authenticate when GitHub actually answers.
+    #[must_use]
+    pub fn check(config: &Config) -> Option<ReauthReason> {
+        if !Self::uses_token(config) {
+            return None;
+        }
+        let github = config.github.as_ref()?;
+        let token = config.get_github_token()?;
+        match Self::token_status(token, &github.owner, &github.repo) {
+            Ok(status) => Self::reason_for(&status, Utc::now()),
+            Err(e) => {
+                debug!("Token check skipped: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Decide whether a token status needs re-authentication.
+    #[must_use]
+    pub fn reason_for(status: &TokenStatus, now: DateTime<Utc>) -> Option<ReauthReason> {
+        match status {
+            TokenStatus::Rejected => Some(ReauthReason::Rejected),
+            TokenStatus::Valid {
+                expires_at: Some(expires_at),
+            } if *expires_at - now <= Duration::days(EXPIRY_WARNING_DAYS) => {
+                Some(ReauthReason::Expiring {
+                    expires_at: *expires_at,
+                })
+            }
+            TokenStatus::Valid { .. } => None,
+        }
+    }
+
+    /// Whether a git or API error message means the credentials were rejected.
+    #[must_use]
+    pub fn is_auth_failure(message: &str) -> bool {
+        let message = message.to_lowercase();
+        [
+            "401 unauthorized",
+            "(401",
+            "authentication replays",
+            "authentication failed",
+            "authentication required",
+            "bad credentials",
+            "invalid username or password",
+            "invalid token",
+        ]
+        .iter()
+        .any(|needle| message.contains(needle))
+    }
+
+    /// Validate `token` and store it in place of the current one, updating
+    /// the `origin` URL when it embeds the token. The caller saves the config.
+    pub fn replace_token(config: &mut Config, token: &str) -> Result<()> {
+        let token = token.trim();
+        if token.is_empty() {
+            anyhow::bail!("Token cannot be empty");
+        }
+        let Some(github) = config.github.as_mut() else {
+            anyhow::bail!("No GitHub configuration to update");
+        };
+
+        match Self::token_status(token.to_string(), &github.owner, &github.repo)? {
+            TokenStatus::Rejected => anyhow::bail!("GitHub rejected this token"),
+            TokenStatus::Valid { .. } => {}
+        }
+        if !Self::repo_visible(token.to_string(), &github.owner, &github.repo)? {
+            anyhow::bail!(
+                "Token cannot access repository {}/{}",
+                github.owner,
+                github.repo
+            );
+        }
+
+        github.token = Some(token.to_string());
+
+        if config.repo_path.exists() {
+            match GitManager::open_or_init(&config.repo_path) {
+                Ok(mut git_manager) => {
+                    if let Err(e) = git_manager.update_remote_token("origin", token) {
+                        // Non-fatal: the credentials callback uses the new token anyway
+                        warn!("Failed to update remote URL with new token: {}", e);
+                    }
+                }
+                Err(e) => warn!("Failed to open git repository to update token: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    fn token_status(token: String, owner: &str, repo: &str) -> Result<TokenStatus> {
+        Self::block_on(async { GitHubClient::new(token).token_status(owner, repo).await })
+    }
+
+    fn repo_visible(token: String, owner: &str, repo: &str) -> Result<bool> {
+        Self::block_on(async { GitHubClient::new(token).repo_exists(owner, repo).await })
+    }
+
+    fn block_on<T>(future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("Failed to start async runtime")?;
+        runtime.block_on(async {
+            tokio::time::timeout(REQUEST_TIMEOUT, future)
+                .await
+                .map_err(|_| anyhow::anyhow!("GitHub API request timed out"))?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_for_status() {
+        let now = Utc::now();
+        assert_eq!(
+            TokenService::reason_for(&TokenStatus::Rejected, now),
+            Some(ReauthReason::Rejected)
+        );
+        assert_eq!(
+            TokenService::reason_for(&TokenStatus::Valid { expires_at: None }, now),
+            None
+        );
+
+        let soon = now + Duration::days(3);
+        assert_eq!(
+            TokenService::reason_for(
+                &TokenStatus::Valid {
+                    expires_at: Some(soon)
+                },
+                now
+            ),
+            Some(ReauthReason::Expiring { expires_at: soon })
+        );
+        let later = now + Duration::days(30);
+        assert_eq!(
+            TokenService::reason_for(
+                &TokenStatus::Valid {
+                    expires_at: Some(later)
+                },
+                now
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_auth_failure() {
+        assert!(TokenService::is_auth_failure(
+            "Error: Failed to push: too many redirects or authentication replays"
+        ));
+        assert!(TokenService::is_auth_failure(
+            "remote: Invalid username or password."
+        ));
+        assert!(!TokenService::is_auth_failure(
+            "Error: Failed to push: rejected (non-fast-forward)"
+        ));
+    }
+
+    #[test]
+    fn test_expiring_message() {
+        let now = Utc::now();
+        let reason = ReauthReason::Expiring {
+            expires_at: now + Duration::days(3) + Duration::hours(1),
+        };
+        assert!(reason.message(now).contains("in 3 day(s)"));
+    }
+}
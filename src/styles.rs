@@ -4,10 +4,12 @@
 //! light and dark themes.
 
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::widgets::BorderType;
-use std::str::FromStr;
+use ratatui::widgets::{BorderType, Padding};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::RwLock;
 
+pub use crate::utils::theme_type::ThemeType;
+
 /// List selection indicator shown next to the selected item
 pub const LIST_HIGHLIGHT_SYMBOL: &str = "» ";
 
@@ -45,124 +47,38 @@ pub fn init_theme(theme_type: ThemeType) {
     *theme = Theme::new(theme_type);
 }
 
-/// Get the current theme
-pub fn theme() -> Theme {
-    // Recover from poison - theme should always be accessible
-    THEME
-        .read()
-        .unwrap_or_else(std::sync::PoisonError::into_inner)
-        .clone()
-}
+/// Compact layout: a one-line header, no padding inside panes and thin
+/// borders, so more of each list fits on small screens
+static COMPACT: AtomicBool = AtomicBool::new(false);
 
-/// Theme type selector
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum ThemeType {
-    #[default]
-    Dark,
-    Light,
-    /// Disable all UI colors (equivalent to `NO_COLOR=1` / `--no-colors`)
-    NoColor,
-    /// Midnight colors regardless of Terminal color presets, RGB values only
-    Midnight,
-    /// Solarized Dark theme
-    SolarizedDark,
-    /// Solarized Light theme
-    SolarizedLight,
-    /// Gruvbox Dark theme
-    GruvboxDark,
-    /// Gruvbox Light theme
-    GruvboxLight,
-    /// Catppuccin Mocha (dark) theme
-    CatppuccinMocha,
-    /// Catppuccin Latte (light) theme
-    CatppuccinLatte,
-    /// Tokyo Night (dark) theme
-    TokyoNightDark,
-    /// Tokyo Night (light) theme
-    TokyoNightLight,
+/// Turn the compact layout on or off (the `compact` setting)
+pub fn set_compact(compact: bool) {
+    COMPACT.store(compact, Ordering::Relaxed);
 }
 
-impl ThemeType {
-    /// Get the display name of this theme
-    #[must_use]
-    pub fn name(&self) -> &'static str {
-        match self {
-            ThemeType::Dark => "Dark",
-            ThemeType::Light => "Light",
-            ThemeType::NoColor => "No Color",
-            ThemeType::Midnight => "Midnight",
-            ThemeType::SolarizedDark => "Solarized Dark",
-            ThemeType::SolarizedLight => "Solarized Light",
-            ThemeType::GruvboxDark => "Gruvbox Dark",
-            ThemeType::GruvboxLight => "Gruvbox Light",
-            ThemeType::CatppuccinMocha => "Catppuccin Mocha",
-            ThemeType::CatppuccinLatte => "Catppuccin Latte",
-            ThemeType::TokyoNightDark => "Tokyo Night",
-            ThemeType::TokyoNightLight => "Tokyo Night Light",
-        }
-    }
+/// Whether the compact layout is on
+#[must_use]
+pub fn is_compact() -> bool {
+    COMPACT.load(Ordering::Relaxed)
+}
 
-    /// Get the config string value for this theme
-    #[must_use]
-    pub fn to_config_string(&self) -> &'static str {
-        match self {
-            ThemeType::Dark => "dark",
-            ThemeType::Light => "light",
-            ThemeType::NoColor => "nocolor",
-            ThemeType::Midnight => "midnight",
-            ThemeType::SolarizedDark => "solarized-dark",
-            ThemeType::SolarizedLight => "solarized-light",
-            ThemeType::GruvboxDark => "gruvbox-dark",
-            ThemeType::GruvboxLight => "gruvbox-light",
-            ThemeType::CatppuccinMocha => "catppuccin-mocha",
-            ThemeType::CatppuccinLatte => "catppuccin-latte",
-            ThemeType::TokyoNightDark => "tokyonight-dark",
-            ThemeType::TokyoNightLight => "tokyonight-light",
-        }
-    }
-
-    /// Get all available themes
-    #[must_use]
-    pub fn all() -> &'static [ThemeType] {
-        &[
-            ThemeType::Dark,
-            ThemeType::Light,
-            ThemeType::Midnight,
-            ThemeType::SolarizedDark,
-            ThemeType::SolarizedLight,
-            ThemeType::GruvboxDark,
-            ThemeType::GruvboxLight,
-            ThemeType::CatppuccinMocha,
-            ThemeType::CatppuccinLatte,
-            ThemeType::TokyoNightDark,
-            ThemeType::TokyoNightLight,
-            ThemeType::NoColor,
-        ]
+/// `padding` for a pane, or none in the compact layout
+#[must_use]
+pub fn padding(padding: Padding) -> Padding {
+    if is_compact() {
+        Padding::ZERO
+    } else {
+        padding
     }
 }
 
-impl FromStr for ThemeType {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s.to_lowercase().as_str() {
-            "light" => ThemeType::Light,
-            "midnight" => ThemeType::Midnight,
-            "solarized-dark" | "solarized_dark" | "solarized" => ThemeType::SolarizedDark,
-            "solarized-light" | "solarized_light" => ThemeType::SolarizedLight,
-            "gruvbox-dark" | "gruvbox_dark" | "gruvbox" => ThemeType::GruvboxDark,
-            "gruvbox-light" | "gruvbox_light" => ThemeType::GruvboxLight,
-            "catppuccin-mocha" | "catppuccin_mocha" | "catppuccin" => ThemeType::CatppuccinMocha,
-            "catppuccin-latte" | "catppuccin_latte" => ThemeType::CatppuccinLatte,
-            "tokyonight-dark" | "tokyonight_dark" | "tokyonight" | "tokyo-night"
-            | "tokyo_night" => ThemeType::TokyoNightDark,
-            "tokyonight-light" | "tokyonight_light" | "tokyo-night-light" | "tokyo_night_light" => {
-                ThemeType::TokyoNightLight
-            }
-            "nocolor" | "no-color" | "no_color" => ThemeType::NoColor,
-            _ => ThemeType::Dark,
-        })
-    }
+/// Get the current theme
+pub fn theme() -> Theme {
+    // Recover from poison - theme should always be accessible
+    THEME
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
 }
 
 /// Color palette for the application
@@ -809,10 +725,13 @@ impl Theme {
         }
     }
 
-    /// Get the border type based on focus
+    /// Get the border type based on focus. The compact layout always uses
+    /// thin borders.
     #[must_use]
     pub fn border_type(&self, focused: bool) -> BorderType {
-        if focused {
+        if is_compact() {
+            BorderType::Plain
+        } else if focused {
             self.border_focused_type
         } else {
             self.border_type
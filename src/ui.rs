@@ -1,18 +1,31 @@
 use ratatui::widgets::{ListState, ScrollbarState};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
+pub use crate::utils::package_installer::InstallationStatus;
+
 /// Application screens
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Screen {
     MainMenu,
     DotfileSelection,
+    Import,
     StorageSetup,
     SyncWithRemote,
     ManageProfiles,
     ProfileSelection, // For selecting which profile to activate after setup
     ManagePackages,
+    Aliases,
     Settings,
+    Health,
+    History,
+    Machines,
+    Stats,
+    Notifications,
+    Bootstrap, // Runs a profile's bootstrap scripts after its first activation
+    ForceSync,
+    Reconcile, // File-by-file reconciliation of a diverged local and remote
+    Reauth,
 }
 
 /// GitHub auth state (also handles local repo setup)
@@ -73,7 +86,8 @@ pub enum GitHubAuthStep {
 }
 
 /// State machine for GitHub setup process
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GitHubSetupStep {
     Connecting,
     ValidatingToken,
@@ -85,6 +99,23 @@ pub enum GitHubSetupStep {
     Complete,
 }
 
+impl GitHubSetupStep {
+    /// Display label for progress lists
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitHubSetupStep::Connecting => "Connecting to GitHub",
+            GitHubSetupStep::ValidatingToken => "Validating token",
+            GitHubSetupStep::CheckingRepo => "Checking repository",
+            GitHubSetupStep::CloningRepo => "Cloning repository",
+            GitHubSetupStep::CreatingRepo => "Creating repository",
+            GitHubSetupStep::InitializingRepo => "Initializing repository",
+            GitHubSetupStep::DiscoveringProfiles => "Discovering profiles",
+            GitHubSetupStep::Complete => "Complete",
+        }
+    }
+}
+
 /// Setup mode for repository configuration
 /// Determines which setup flow the user is in
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -111,11 +142,7 @@ pub enum LocalSetupStep {
 
 impl Default for GitHubAuthState {
     fn default() -> Self {
-        let default_repo_path = dirs::home_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join(".config")
-            .join("dotstate")
-            .join("storage");
+        let default_repo_path = crate::utils::get_default_storage_path();
 
         Self {
             // Setup mode selection
@@ -154,15 +181,23 @@ pub struct SyncWithRemoteState {
     pub changed_files: Vec<String>,
     pub is_syncing: bool,
     pub sync_progress: Option<String>, // Current progress message (e.g., "Committing...", "Pulling...", "Pushing...")
-    pub sync_result: Option<String>,   // Final result message
-    pub show_result_popup: bool,       // Whether to show result popup
+    pub transfer_progress: Option<crate::git::TransferProgress>, // Latest pull/push transfer snapshot
+    pub sync_result: Option<String>,                             // Final result message
+    pub show_result_popup: bool,                                 // Whether to show result popup
     pub pulled_changes_count: Option<usize>, // Number of changes pulled from remote
     pub list_state: ListState,
     pub scrollbar_state: ScrollbarState,
     pub diff_content: Option<String>, // Content of the diff for preview
+    pub diff_limit: usize,            // Bytes of the diff loaded so far
+    pub diff_truncated: bool,         // Whether more of the diff follows diff_content
     pub preview_scroll: usize,        // Scroll state for preview
     pub result_scroll: u16,           // Scroll state for result popup
     pub git_status: Option<crate::services::git_service::GitStatus>, // Detailed git status
+    pub excluded_files: HashSet<String>, // Paths unchecked by the user, left out of the commit
+    pub excluded_hunks: HashMap<String, HashSet<usize>>, // Hunks unchecked per path
+    pub commit_editor: Option<crate::utils::TextInput>, // Commit message being edited before syncing
+    pub junk_patterns: Vec<String>, // .gitignore patterns offered for junk among new files
+    pub junk_prompt_dismissed: bool, // Whether the junk prompt was declined on this visit
 }
 
 impl Default for SyncWithRemoteState {
@@ -171,15 +206,23 @@ impl Default for SyncWithRemoteState {
             changed_files: Vec::new(),
             is_syncing: false,
             sync_progress: None,
+            transfer_progress: None,
             sync_result: None,
             show_result_popup: false,
             pulled_changes_count: None,
             list_state: ListState::default(),
             scrollbar_state: ScrollbarState::new(0),
             diff_content: None,
+            diff_limit: crate::git::DIFF_CHUNK_BYTES,
+            diff_truncated: false,
             preview_scroll: 0,
             result_scroll: 0,
             git_status: None,
+            excluded_files: HashSet::new(),
+            excluded_hunks: HashMap::new(),
+            commit_editor: None,
+            junk_patterns: Vec::new(),
+            junk_prompt_dismissed: false,
         }
     }
 }
@@ -392,16 +435,6 @@ pub enum InstallationStep {
     },
 }
 
-/// Installation status message from background thread
-#[derive(Debug, Clone)]
-pub enum InstallationStatus {
-    Output(String), // Output line
-    Complete {
-        success: bool,
-        error: Option<String>,
-    }, // Installation complete
-}
-
 /// Application UI state
 #[derive(Debug)]
 pub struct UiState {
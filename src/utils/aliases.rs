@@ -0,0 +1,196 @@
+//! Shell aliases kept as data.
+//!
+//! `aliases.toml` in `common/` or a profile directory of the storage
+//! repository holds an `[aliases]` table of name = command. They are a
+//! gentler start than syncing a whole shell config: the Aliases screen edits
+//! them, and activation renders the active profile's aliases (common's,
+//! then those of the profiles it inherits from, later ones overriding) into
+//! the shell snippet loader.
+
+use crate::utils::ssh_remote::shell_quote;
+use crate::utils::ProfileManifest;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the alias file in `common/` and each profile directory
+pub const ALIASES_FILE: &str = "aliases.toml";
+
+/// Directory of the aliases shared by every profile
+const COMMON_DIR: &str = "common";
+
+/// Aliases of one profile (or common).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasFile {
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// An alias in effect for a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedAlias {
+    pub command: String,
+    /// Profile (or `common`) that defines it
+    pub source: String,
+}
+
+impl AliasFile {
+    fn path(repo_path: &Path, dir: &str) -> PathBuf {
+        repo_path.join(dir).join(ALIASES_FILE)
+    }
+
+    /// Load the aliases of `dir` (a profile name or `common`); none if the
+    /// file doesn't exist
+    pub fn load(repo_path: &Path, dir: &str) -> Result<Self> {
+        let path = Self::path(repo_path, dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save the aliases of `dir`. Without aliases the file is removed.
+    pub fn save(&self, repo_path: &Path, dir: &str) -> Result<()> {
+        let path = Self::path(repo_path, dir);
+        if self.aliases.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize aliases")?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Check an alias name: letters, digits and `_ - . + :`, not starting with `-`
+pub fn validate_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() {
+        return Err("Alias name can't be empty".to_string());
+    }
+    if name.starts_with('-') {
+        return Err("Alias name can't start with '-'".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || "_-.+:".contains(*c)))
+    {
+        return Err(format!("Alias name can't contain '{c}'"));
+    }
+    Ok(())
+}
+
+/// Check an alias command: not empty, one line
+pub fn validate_command(command: &str) -> std::result::Result<(), String> {
+    if command.trim().is_empty() {
+        return Err("Command can't be empty".to_string());
+    }
+    if command.contains('\n') {
+        return Err("Command must be a single line".to_string());
+    }
+    Ok(())
+}
+
+/// Aliases in effect for `profile`: common's, then those of the profiles it
+/// inherits from, then its own, a later definition replacing an earlier one
+pub fn resolve(repo_path: &Path, profile: &str) -> Result<BTreeMap<String, ResolvedAlias>> {
+    let manifest =
+        ProfileManifest::load_or_backfill(repo_path).context("Failed to load profile manifest")?;
+    let mut chain = manifest.inheritance_chain(profile)?;
+    chain.reverse();
+
+    let mut resolved = BTreeMap::new();
+    for dir in std::iter::once(COMMON_DIR.to_string()).chain(chain) {
+        for (name, command) in AliasFile::load(repo_path, &dir)?.aliases {
+            resolved.insert(
+                name,
+                ResolvedAlias {
+                    command,
+                    source: dir.clone(),
+                },
+            );
+        }
+    }
+    Ok(resolved)
+}
+
+/// `alias` lines for sh, zsh and bash
+#[must_use]
+pub fn render(aliases: &BTreeMap<String, ResolvedAlias>) -> String {
+    let mut out = String::new();
+    for (name, alias) in aliases {
+        out.push_str(&format!("alias {name}={}\n", shell_quote(&alias.command)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::ProfileInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_follows_inheritance() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        let profile = |name: &str, inherits: Option<&str>| ProfileInfo {
+            name: name.to_string(),
+            description: None,
+            inherits: inherits.map(str::to_string),
+            synced_files: Vec::new(),
+            packages: Vec::new(),
+        };
+        ProfileManifest {
+            profiles: vec![profile("Base", None), profile("Work", Some("Base"))],
+            ..ProfileManifest::default()
+        }
+        .save(repo)
+        .unwrap();
+
+        let file = |pairs: &[(&str, &str)]| AliasFile {
+            aliases: pairs
+                .iter()
+                .map(|(n, c)| ((*n).to_string(), (*c).to_string()))
+                .collect(),
+        };
+        file(&[("ll", "ls -l"), ("g", "git")])
+            .save(repo, "common")
+            .unwrap();
+        file(&[("ll", "ls -lah")]).save(repo, "Base").unwrap();
+        file(&[("k", "kubectl --context 'work'")])
+            .save(repo, "Work")
+            .unwrap();
+
+        let resolved = resolve(repo, "Work").unwrap();
+        assert_eq!(resolved["ll"].command, "ls -lah");
+        assert_eq!(resolved["ll"].source, "Base");
+        assert_eq!(resolved["g"].source, "common");
+        assert_eq!(
+            render(&resolved),
+            "alias g='git'\nalias k='kubectl --context '\\''work'\\'''\nalias ll='ls -lah'\n"
+        );
+
+        // Saving an empty file removes it
+        AliasFile::default().save(repo, "Work").unwrap();
+        assert!(!repo.join("Work").join(ALIASES_FILE).exists());
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("gs").is_ok());
+        assert!(validate_name("..").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("-x").is_err());
+        assert!(validate_name("a b").is_err());
+        assert!(validate_name("a=b").is_err());
+    }
+}
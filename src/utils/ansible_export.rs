@@ -0,0 +1,297 @@
+//! Export of a profile to an Ansible role.
+//!
+//! `dotstate export ansible` writes a role that installs the profile's
+//! tracked files (copied from the role's `files/`, or linked from a
+//! checkout of the storage repository on the host) and its packages with
+//! the matching Ansible modules, so teams can fold personal dotfiles into
+//! the provisioning they already run.
+
+use crate::utils::profile_manifest::{Package, PackageManager};
+use crate::utils::ProfileManifest;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// What a written role contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoleSummary {
+    pub files: usize,
+    pub packages: usize,
+}
+
+/// `value` as a double-quoted YAML string that Ansible won't template
+fn yaml_str(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    if ["{{", "{%", "{#"].iter().any(|m| value.contains(m)) {
+        format!("\"{{% raw %}}{escaped}{{% endraw %}}\"")
+    } else {
+        format!("\"{escaped}\"")
+    }
+}
+
+/// Write the role for `profile` into `out_dir`. `home` is the local home
+/// directory, used to express the storage repository's path on the host.
+pub fn write_role(
+    manifest: &ProfileManifest,
+    repo_path: &Path,
+    profile: &str,
+    home: &Path,
+    out_dir: &Path,
+) -> Result<RoleSummary> {
+    let files = manifest.resolve_files(profile)?;
+    let packages = manifest.resolve_packages(profile)?;
+
+    let mut vars = String::from("---\ndotfiles_files:\n");
+    for file in &files {
+        let source = format!("{}/{}", file.source_profile, file.relative_path);
+        let from = repo_path.join(&source);
+        let to = out_dir.join("files").join(&source);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let is_dir = from.is_dir();
+        if is_dir {
+            crate::file_manager::copy_dir_all(&from, &to)?;
+        } else {
+            fs::copy(&from, &to).with_context(|| format!("Failed to copy {source}"))?;
+        }
+        let _ = writeln!(
+            vars,
+            "  - {{ src: {}, dest: {}, directory: {is_dir} }}",
+            yaml_str(&source),
+            yaml_str(&file.relative_path)
+        );
+    }
+
+    let repo_on_host = repo_path.strip_prefix(home).map_or_else(
+        |_| repo_path.to_string_lossy().to_string(),
+        |relative| format!("{{{{ ansible_env.HOME }}}}/{}", relative.to_string_lossy()),
+    );
+    let header = format!("# Generated by dotstate from profile {}", yaml_str(profile));
+    let defaults = format!(
+        "---\n{header}\n\n\
+         # copy: install the files bundled in files/\n\
+         # link: symlink them from a checkout of the storage repository at dotfiles_repo\n\
+         dotfiles_mode: copy\n\
+         dotfiles_repo: \"{repo_on_host}\"\n\
+         dotfiles_home: \"{{{{ ansible_env.HOME }}}}\"\n\
+         dotfiles_install_packages: true\n"
+    );
+
+    let write = |relative: &str, content: &str| -> Result<()> {
+        let path = out_dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+    };
+    write("defaults/main.yml", &defaults)?;
+    write("vars/main.yml", &vars)?;
+    write(
+        "tasks/main.yml",
+        &format!("---\n{header}\n\n{}", tasks(&packages)),
+    )?;
+    write(
+        "meta/main.yml",
+        &format!(
+            "---\ngalaxy_info:\n  description: Dotfiles of the dotstate profile {}\n  min_ansible_version: \"2.12\"\ndependencies: []\n",
+            yaml_str(profile)
+        ),
+    )?;
+
+    Ok(RoleSummary {
+        files: files.len(),
+        packages: packages.len(),
+    })
+}
+
+const FILE_TASKS: &str = r#"- name: Create the parent directories of the dotfiles
+  ansible.builtin.file:
+    path: "{{ (dotfiles_home ~ '/' ~ item.dest) | dirname }}"
+    state: directory
+    mode: "0755"
+  loop: "{{ dotfiles_files }}"
+
+- name: Copy the dotfiles
+  ansible.builtin.copy:
+    src: "{{ item.src }}{{ '/' if item.directory else '' }}"
+    dest: "{{ dotfiles_home }}/{{ item.dest }}{{ '/' if item.directory else '' }}"
+    mode: preserve
+  loop: "{{ dotfiles_files }}"
+  when: dotfiles_mode == 'copy'
+
+- name: Link the dotfiles from the storage repository
+  ansible.builtin.file:
+    src: "{{ dotfiles_repo }}/{{ item.src }}"
+    dest: "{{ dotfiles_home }}/{{ item.dest }}"
+    state: link
+    force: true
+  loop: "{{ dotfiles_files }}"
+  when: dotfiles_mode == 'link'
+"#;
+
+/// `tasks/main.yml` after its header: the file tasks, then one task per
+/// package manager (one per package for custom install commands)
+fn tasks(packages: &[Package]) -> String {
+    let mut out = String::from(FILE_TASKS);
+    let mut by_manager: BTreeMap<String, (PackageManager, Vec<&Package>)> = BTreeMap::new();
+    for package in packages {
+        by_manager
+            .entry(format!("{:?}", package.manager))
+            .or_insert_with(|| (package.manager.clone(), Vec::new()))
+            .1
+            .push(package);
+    }
+    for (manager, packages) in by_manager.values() {
+        let list = packages.iter().fold(String::new(), |mut list, p| {
+            let name = yaml_str(p.package_name.as_deref().unwrap_or(&p.name));
+            let _ = writeln!(list, "      - {name}");
+            list
+        });
+        let when = "  when: dotfiles_install_packages | bool\n";
+        let _ = writeln!(out);
+        match manager {
+            PackageManager::Custom => {
+                for (i, package) in packages.iter().enumerate() {
+                    let Some(install) = package.install_command.as_deref() else {
+                        continue;
+                    };
+                    if i > 0 {
+                        let _ = writeln!(out);
+                    }
+                    let script = format!(
+                        "command -v {} >/dev/null 2>&1 || {install}",
+                        package.binary_name
+                    );
+                    let _ = write!(
+                        out,
+                        "- name: {}\n  ansible.builtin.shell: {}\n{when}",
+                        yaml_str(&format!("Install {}", package.name)),
+                        yaml_str(&script)
+                    );
+                }
+            }
+            // Installed through a plugin on this machine, which the host
+            // doesn't have
+            PackageManager::Plugin(name) => {
+                let _ = write!(
+                    out,
+                    "# Not exported, installed with the '{name}' dotstate plugin:\n{}",
+                    list.replace("      - ", "#   - ")
+                );
+            }
+            // These modules take one package at a time
+            PackageManager::Npm | PackageManager::Gem => {
+                let (module, extra) = if *manager == PackageManager::Npm {
+                    ("community.general.npm", "    global: true\n")
+                } else {
+                    ("community.general.gem", "    user_install: true\n")
+                };
+                let _ = write!(
+                    out,
+                    "- name: Install {manager:?} packages\n  {module}:\n    name: \"{{{{ item }}}}\"\n    state: present\n{extra}  loop:\n{}{when}",
+                    list.replace("      - ", "    - ")
+                );
+            }
+            _ => {
+                let (module, extra, become_root) = match manager {
+                    PackageManager::Brew => ("community.general.homebrew", "", false),
+                    PackageManager::Apt => ("ansible.builtin.apt", "", true),
+                    PackageManager::Yum | PackageManager::Dnf => ("ansible.builtin.dnf", "", true),
+                    PackageManager::Pacman => ("community.general.pacman", "", true),
+                    PackageManager::Snap => ("community.general.snap", "", true),
+                    PackageManager::Cargo => ("community.general.cargo", "", false),
+                    PackageManager::Pip3 => {
+                        ("ansible.builtin.pip", "    executable: pip3\n", false)
+                    }
+                    _ => ("ansible.builtin.pip", "", false),
+                };
+                let become_line = if become_root { "  become: true\n" } else { "" };
+                let _ = write!(
+                    out,
+                    "- name: Install {manager:?} packages\n  {module}:\n    name:\n{list}    state: present\n{extra}{become_line}{when}"
+                );
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::ProfileInfo;
+    use tempfile::TempDir;
+
+    fn package(name: &str, manager: PackageManager) -> Package {
+        Package {
+            name: name.to_string(),
+            description: None,
+            manager,
+            package_name: None,
+            binary_name: name.to_string(),
+            install_command: Some(format!("curl -fsSL https://{name}.sh | sh")),
+            existence_check: None,
+            manager_check: None,
+        }
+    }
+
+    #[test]
+    fn test_write_role() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        let repo = home.join(".config/dotstate/storage");
+        fs::create_dir_all(repo.join("Work/.config/nvim")).unwrap();
+        fs::write(repo.join("Work/.zshrc"), "export A=1\n").unwrap();
+        fs::write(repo.join("Work/.config/nvim/init.lua"), "-- nvim\n").unwrap();
+        let manifest = ProfileManifest {
+            profiles: vec![ProfileInfo {
+                name: "Work".to_string(),
+                description: None,
+                inherits: None,
+                synced_files: vec![".zshrc".to_string(), ".config/nvim".to_string()],
+                packages: vec![
+                    package("ripgrep", PackageManager::Apt),
+                    package("fd-find", PackageManager::Apt),
+                    package("mytool", PackageManager::Custom),
+                    package("node", PackageManager::Plugin("mise".to_string())),
+                ],
+            }],
+            ..ProfileManifest::default()
+        };
+
+        let out = temp.path().join("role");
+        let summary = write_role(&manifest, &repo, "Work", &home, &out).unwrap();
+        assert_eq!(
+            summary,
+            RoleSummary {
+                files: 2,
+                packages: 4
+            }
+        );
+        assert!(out.join("files/Work/.config/nvim/init.lua").exists());
+
+        let vars = fs::read_to_string(out.join("vars/main.yml")).unwrap();
+        assert!(vars.contains(
+            "  - { src: \"Work/.config/nvim\", dest: \".config/nvim\", directory: true }\n"
+        ));
+        let defaults = fs::read_to_string(out.join("defaults/main.yml")).unwrap();
+        assert!(defaults
+            .contains("dotfiles_repo: \"{{ ansible_env.HOME }}/.config/dotstate/storage\"\n"));
+        let tasks = fs::read_to_string(out.join("tasks/main.yml")).unwrap();
+        assert!(tasks.contains(
+            "  ansible.builtin.apt:\n    name:\n      - \"fd-find\"\n      - \"ripgrep\"\n"
+        ));
+        assert!(tasks.contains(
+            "  ansible.builtin.shell: \"command -v mytool >/dev/null 2>&1 || curl -fsSL https://mytool.sh | sh\"\n"
+        ));
+        assert!(tasks.contains(
+            "# Not exported, installed with the 'mise' dotstate plugin:\n#   - \"node\"\n"
+        ));
+        assert_eq!(yaml_str("a {{ b }}"), "\"{% raw %}a {{ b }}{% endraw %}\"");
+    }
+}
@@ -2,23 +2,27 @@ use anyhow::{Context, Result};
 use chrono::Local;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
-/// Manages centralized backups in ~/.dotstate-backups
+/// Backup directory from `config.toml`, set when the config is loaded
+static CONFIGURED_BACKUP_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Set the backup directory configured in `config.toml` (`None` for the default).
+pub fn set_configured_backup_dir(dir: Option<PathBuf>) {
+    if let Ok(mut guard) = CONFIGURED_BACKUP_DIR.write() {
+        *guard = dir;
+    }
+}
+
+/// Manages centralized backups in ~/.dotstate-backups (or `backup_dir` from the config)
 pub struct BackupManager {
     backup_root: PathBuf,
 }
 
 impl BackupManager {
-    /// Create a new `BackupManager`
-    ///
-    /// In tests, set `DOTSTATE_TEST_BACKUP_DIR` env var to override the backup location.
+    /// Create a new `BackupManager`, creating the backup directory if needed
     pub fn new() -> Result<Self> {
-        let backup_root = if let Ok(test_backup) = std::env::var("DOTSTATE_TEST_BACKUP_DIR") {
-            PathBuf::from(test_backup)
-        } else {
-            let home_dir = crate::utils::get_home_dir();
-            home_dir.join(".dotstate-backups")
-        };
+        let backup_root = Self::default_root();
 
         // Ensure backup directory exists
         fs::create_dir_all(&backup_root).context("Failed to create backup directory")?;
@@ -26,6 +30,20 @@ impl BackupManager {
         Ok(Self { backup_root })
     }
 
+    /// Location of the backup directory, without creating it
+    ///
+    /// In tests, set `DOTSTATE_TEST_BACKUP_DIR` env var to override the backup location.
+    #[must_use]
+    pub fn default_root() -> PathBuf {
+        if let Ok(test_backup) = std::env::var("DOTSTATE_TEST_BACKUP_DIR") {
+            return PathBuf::from(test_backup);
+        }
+        if let Some(dir) = CONFIGURED_BACKUP_DIR.read().ok().and_then(|g| g.clone()) {
+            return dir;
+        }
+        crate::utils::get_home_dir().join(".dotstate-backups")
+    }
+
     /// Create a new timestamped backup directory for a sync operation
     pub fn create_backup_session(&self) -> Result<PathBuf> {
         // Use filesystem-safe timestamp (':' is invalid on some platforms/filesystems).
@@ -77,6 +95,58 @@ impl BackupManager {
     }
 }
 
+/// One backup session: the files set aside during a sync or activation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupSession {
+    /// Directory name, the time the session was taken
+    pub name: String,
+    pub path: PathBuf,
+    /// Files backed up in the session
+    pub files: usize,
+    /// Total size of the files
+    pub size: u64,
+}
+
+/// The backup sessions in `backup_root`, newest first
+#[must_use]
+pub fn list_sessions(backup_root: &Path) -> Vec<BackupSession> {
+    let Ok(entries) = fs::read_dir(backup_root) else {
+        return Vec::new();
+    };
+    let mut sessions: Vec<BackupSession> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| {
+            let (files, size) = count_files(&entry.path());
+            BackupSession {
+                name: entry.file_name().to_string_lossy().to_string(),
+                path: entry.path(),
+                files,
+                size,
+            }
+        })
+        .collect();
+    sessions.sort_by(|a, b| b.name.cmp(&a.name));
+    sessions
+}
+
+/// Number and total size of the files under `dir`
+fn count_files(dir: &Path) -> (usize, u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (0, 0);
+    };
+    entries
+        .flatten()
+        .fold((0, 0), |(files, size), entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => {
+                let (inner_files, inner_size) = count_files(&entry.path());
+                (files + inner_files, size + inner_size)
+            }
+            Ok(_) => (files + 1, size + entry.metadata().map_or(0, |m| m.len())),
+            Err(_) => (files, size),
+        })
+}
+
 impl Default for BackupManager {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| {
@@ -122,4 +192,21 @@ mod tests {
         assert!(dir_name.len() == 19); // YYYY-MM-DDTHH-MM-SS
         assert!(dir_name.contains('T'));
     }
+
+    #[test]
+    fn test_list_sessions() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("2024-01-01T10-00-00/.config/nvim")).unwrap();
+        fs::write(root.join("2024-01-01T10-00-00/.zshrc"), "abc").unwrap();
+        fs::write(root.join("2024-01-01T10-00-00/.config/nvim/init.lua"), "ab").unwrap();
+        fs::create_dir_all(root.join("2024-02-01T10-00-00")).unwrap();
+        fs::write(root.join("stray-file"), "").unwrap();
+
+        let sessions = list_sessions(root);
+        let names: Vec<_> = sessions.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["2024-02-01T10-00-00", "2024-01-01T10-00-00"]);
+        assert_eq!((sessions[1].files, sessions[1].size), (2, 5));
+        assert!(list_sessions(&root.join("missing")).is_empty());
+    }
 }
@@ -0,0 +1,179 @@
+//! Binary file detection and hex dumps for previews.
+//!
+//! Uses the same heuristic as git: content with a NUL byte in the first
+//! 8000 bytes is binary. Invalid UTF-8 is treated as binary too, since the
+//! previews can only render text.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Number of leading bytes inspected when sniffing content (matches git)
+pub const SNIFF_LEN: usize = 8000;
+
+/// Bytes shown per hex dump line
+const BYTES_PER_LINE: usize = 16;
+
+/// Magic byte signatures, checked in order
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "PNG image"),
+    (b"\xff\xd8\xff", "JPEG image"),
+    (b"GIF87a", "GIF image"),
+    (b"GIF89a", "GIF image"),
+    (b"%PDF-", "PDF document"),
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"\x1f\x8b", "gzip archive"),
+    (b"BZh", "bzip2 archive"),
+    (b"\xfd7zXZ\x00", "xz archive"),
+    (b"7z\xbc\xaf\x27\x1c", "7-Zip archive"),
+    (b"\x7fELF", "ELF executable"),
+    (b"\xcf\xfa\xed\xfe", "Mach-O executable"),
+    (b"\xce\xfa\xed\xfe", "Mach-O executable"),
+    (b"\xca\xfe\xba\xbe", "Mach-O universal binary"),
+    (b"BM", "BMP image"),
+    (b"\x00\x00\x01\x00", "ICO image"),
+    (b"MZ", "Windows executable"),
+    (b"SQLite format 3\x00", "SQLite database"),
+    (b"\x00\x01\x00\x00\x00", "TrueType font"),
+    (b"OTTO", "OpenType font"),
+    (b"wOFF", "WOFF font"),
+    (b"wOF2", "WOFF2 font"),
+    (b"bplist", "binary property list"),
+];
+
+/// Whether `bytes` (typically the first [`SNIFF_LEN`] bytes) look binary.
+#[must_use]
+pub fn is_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if sample.contains(&0) {
+        return true;
+    }
+    // A multi-byte character cut off at the end of the sample is still text
+    match std::str::from_utf8(sample) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+/// Read up to `limit` leading bytes of a file.
+pub fn read_prefix(path: &Path, limit: usize) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(limit.min(64 * 1024));
+    File::open(path)?.take(limit as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Identify a file type from its magic bytes.
+#[must_use]
+pub fn detect_type(bytes: &[u8]) -> &'static str {
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return "WebP image";
+    }
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+        .map_or("binary data", |(_, name)| name)
+}
+
+/// Whether the magic bytes identify an image format.
+#[must_use]
+pub fn is_image(bytes: &[u8]) -> bool {
+    detect_type(bytes).ends_with(" image")
+}
+
+/// One-line summary such as "PNG image, 1.2 KB".
+#[must_use]
+pub fn describe(bytes: &[u8], size: u64) -> String {
+    format!("{}, {}", detect_type(bytes), format_size(size))
+}
+
+/// Format one hex dump line per 16 bytes, `xxd`/`hexdump -C` style:
+/// `00000010  00 00 00 0d 49 48 44 52  ...  |....IHDR........|`
+#[must_use]
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(BYTES_PER_LINE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut hex = String::with_capacity(BYTES_PER_LINE * 3 + 1);
+            for index in 0..BYTES_PER_LINE {
+                if index == BYTES_PER_LINE / 2 {
+                    hex.push(' ');
+                }
+                match chunk.get(index) {
+                    Some(byte) => hex.push_str(&format!("{byte:02x} ")),
+                    None => hex.push_str("   "),
+                }
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {hex} |{ascii}|", i * BYTES_PER_LINE)
+        })
+        .collect()
+}
+
+/// Format byte size to human readable string
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else if bytes < 1024 * 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b"export PATH=$HOME/bin:$PATH\n"));
+        assert!(!is_binary("set -g status-right '⚡'".as_bytes()));
+        assert!(is_binary(b"\x89PNG\r\n\x1a\n\x00\x00"));
+        assert!(is_binary(&[0xff, 0xfe, 0x41, 0x42]));
+        // Multi-byte character cut off by the sample boundary
+        let text = "é".repeat(SNIFF_LEN);
+        assert!(!is_binary(&text.as_bytes()[..SNIFF_LEN - 1]));
+    }
+
+    #[test]
+    fn test_detect_type() {
+        assert_eq!(detect_type(b"\x89PNG\r\n\x1a\n...."), "PNG image");
+        assert_eq!(detect_type(b"RIFF\x00\x00\x00\x00WEBPVP8 "), "WebP image");
+        assert_eq!(detect_type(b"\x7fELF\x02\x01"), "ELF executable");
+        assert_eq!(detect_type(b"\x00\x13\x37"), "binary data");
+        assert!(is_image(b"GIF89a\x01\x00"));
+        assert!(!is_image(b"%PDF-1.7"));
+        assert_eq!(describe(b"%PDF-1.7", 2048), "PDF document, 2.0 KB");
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let lines = hex_dump(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x01");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "00000000  89 50 4e 47 0d 0a 1a 0a  00 00 00 0d 49 48 44 52  |.PNG........IHDR|"
+        );
+        assert!(lines[1].starts_with("00000010  01 "));
+        assert!(lines[1].ends_with("|.|"));
+    }
+}
@@ -0,0 +1,302 @@
+//! Per-profile bootstrap scripts.
+//!
+//! A profile can keep an `install.sh` and/or a `bootstrap/` directory next
+//! to its dotfiles in the storage repository. They run (after confirmation)
+//! the first time the profile is activated on a machine, for setup symlinks
+//! can't do: plugin managers, shell plugins, fonts. Profiles it inherits
+//! from contribute their scripts first. Which profiles were bootstrapped is
+//! remembered per machine in `bootstrap.json` in the data directory.
+
+use crate::utils::ProfileManifest;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+/// Script run from the profile directory
+pub const BOOTSTRAP_SCRIPT: &str = "install.sh";
+
+/// Directory of scripts run in name order, after [`BOOTSTRAP_SCRIPT`]
+pub const BOOTSTRAP_DIR: &str = "bootstrap";
+
+/// Name of the state file in the data directory
+const STATE_FILE: &str = "bootstrap.json";
+
+/// A bootstrap script of a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapScript {
+    /// Profile the script belongs to
+    pub profile: String,
+    pub path: PathBuf,
+}
+
+impl BootstrapScript {
+    /// Path relative to the repository, e.g. `Work/bootstrap/10-tmux.sh`
+    #[must_use]
+    pub fn display_name(&self, repo_path: &Path) -> String {
+        self.path
+            .strip_prefix(repo_path)
+            .unwrap_or(&self.path)
+            .display()
+            .to_string()
+    }
+
+    /// Command running the script from its profile directory. Executable
+    /// files run directly (so any shebang works), others through `sh`.
+    #[must_use]
+    pub fn command(&self, repo_path: &Path, active_profile: &str) -> Command {
+        let mut command = if is_executable(&self.path) {
+            Command::new(&self.path)
+        } else {
+            let mut command = Command::new("sh");
+            command.arg(&self.path);
+            command
+        };
+        command
+            .current_dir(repo_path.join(&self.profile))
+            .env("DOTSTATE_PROFILE", active_profile)
+            .env("DOTSTATE_REPO", repo_path);
+        command
+    }
+
+    /// Start the script with its stdout and stderr streamed line by line
+    /// (stderr prefixed with `[stderr]`). Stdin is closed so prompts fail
+    /// instead of hanging.
+    pub fn spawn(
+        &self,
+        repo_path: &Path,
+        active_profile: &str,
+    ) -> Result<(Child, mpsc::Receiver<String>)> {
+        let mut child = self
+            .command(repo_path, active_profile)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run {}", self.path.display()))?;
+
+        let (tx, rx) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    let _ = tx.send(line);
+                }
+            });
+        }
+        if let Some(stderr) = child.stderr.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    let _ = tx.send(format!("[stderr] {line}"));
+                }
+            });
+        }
+        Ok((child, rx))
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+/// Bootstrap scripts of one profile directory: `install.sh`, then the files
+/// in `bootstrap/` by name (hidden files skipped).
+pub fn profile_scripts(repo_path: &Path, profile: &str) -> Vec<BootstrapScript> {
+    let dir = repo_path.join(profile);
+    let mut paths = Vec::new();
+    let install = dir.join(BOOTSTRAP_SCRIPT);
+    if install.is_file() {
+        paths.push(install);
+    }
+    if let Ok(entries) = fs::read_dir(dir.join(BOOTSTRAP_DIR)) {
+        let mut scripts: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .collect();
+        scripts.sort();
+        paths.extend(scripts);
+    }
+    paths
+        .into_iter()
+        .map(|path| BootstrapScript {
+            profile: profile.to_string(),
+            path,
+        })
+        .collect()
+}
+
+/// Bootstrap scripts for `profile`, those of the profiles it inherits from first.
+pub fn scripts(repo_path: &Path, profile: &str) -> Result<Vec<BootstrapScript>> {
+    let manifest =
+        ProfileManifest::load_or_backfill(repo_path).context("Failed to load profile manifest")?;
+    let mut chain = manifest.inheritance_chain(profile)?;
+    chain.reverse();
+    Ok(chain
+        .iter()
+        .flat_map(|name| profile_scripts(repo_path, name))
+        .collect())
+}
+
+/// Scripts to offer after activating `profile`: none once the profile has
+/// been bootstrapped (or skipped) on this machine.
+#[must_use]
+pub fn pending(repo_path: &Path, profile: &str) -> Vec<BootstrapScript> {
+    if BootstrapState::load().is_done(profile) {
+        return Vec::new();
+    }
+    scripts(repo_path, profile).unwrap_or_else(|e| {
+        tracing::warn!("Failed to list bootstrap scripts for '{}': {}", profile, e);
+        Vec::new()
+    })
+}
+
+/// Remember that `profile` was bootstrapped (or skipped) on this machine,
+/// logging instead of failing.
+pub fn mark_done(profile: &str) {
+    let mut state = BootstrapState::load();
+    state.profiles.insert(profile.to_string(), Utc::now());
+    if let Err(e) = state.save() {
+        tracing::warn!("Failed to save bootstrap state: {}", e);
+    }
+}
+
+/// Profiles bootstrapped on this machine.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootstrapState {
+    /// Profile name to when its scripts ran or were skipped
+    #[serde(default)]
+    pub profiles: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl BootstrapState {
+    fn state_file() -> PathBuf {
+        crate::utils::get_data_dir().join(STATE_FILE)
+    }
+
+    /// Load the state. A missing or unreadable file means nothing ran yet.
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from(&Self::state_file())
+    }
+
+    fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the state to the data directory.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::state_file())
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create data directory")?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialize bootstrap state")?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, json).context("Failed to write temp bootstrap state")?;
+        fs::rename(&temp_path, path).context("Failed to rename temp bootstrap state")?;
+        Ok(())
+    }
+
+    /// Whether `profile` was bootstrapped on this machine
+    #[must_use]
+    pub fn is_done(&self, profile: &str) -> bool {
+        self.profiles.contains_key(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scripts_follow_inheritance_and_name_order() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        let mut manifest = ProfileManifest::default();
+        manifest.add_profile("base".to_string(), None);
+        manifest.add_profile_with_inherits("Work".to_string(), None, Some("base".to_string()));
+        manifest.save(repo).unwrap();
+
+        fs::create_dir_all(repo.join("base")).unwrap();
+        fs::write(repo.join("base/install.sh"), "echo base").unwrap();
+        fs::create_dir_all(repo.join("Work/bootstrap")).unwrap();
+        fs::write(repo.join("Work/bootstrap/20-fonts.sh"), "").unwrap();
+        fs::write(repo.join("Work/bootstrap/10-tmux.sh"), "").unwrap();
+        fs::write(repo.join("Work/bootstrap/.notes"), "").unwrap();
+
+        let names: Vec<String> = scripts(repo, "Work")
+            .unwrap()
+            .iter()
+            .map(|s| s.display_name(repo))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "base/install.sh",
+                "Work/bootstrap/10-tmux.sh",
+                "Work/bootstrap/20-fonts.sh"
+            ]
+        );
+        assert_eq!(scripts(repo, "base").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(STATE_FILE);
+        assert!(!BootstrapState::load_from(&path).is_done("Work"));
+
+        let mut state = BootstrapState::default();
+        state.profiles.insert("Work".to_string(), Utc::now());
+        state.save_to(&path).unwrap();
+        let loaded = BootstrapState::load_from(&path);
+        assert!(loaded.is_done("Work"));
+        assert!(!loaded.is_done("Home"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_streams_output() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        fs::create_dir_all(repo.join("Work")).unwrap();
+        fs::write(
+            repo.join("Work/install.sh"),
+            "echo \"hello $DOTSTATE_PROFILE\"\necho oops >&2\nexit 3\n",
+        )
+        .unwrap();
+        let script = BootstrapScript {
+            profile: "Work".to_string(),
+            path: repo.join("Work/install.sh"),
+        };
+
+        let (mut child, rx) = script.spawn(repo, "Work").unwrap();
+        let status = child.wait().unwrap();
+        let mut lines: Vec<String> = rx.iter().collect();
+        lines.sort();
+        assert_eq!(lines, ["[stderr] oops", "hello Work"]);
+        assert_eq!(status.code(), Some(3));
+    }
+}
@@ -0,0 +1,145 @@
+//! Broadcast syncs to the other registered machines.
+//!
+//! After a local sync pushes, `dotstate broadcast` logs in to every other
+//! machine in `machines.toml` over SSH and runs `dotstate sync` there, which
+//! pulls the change and links any new files, then `dotstate activate` in
+//! case the profile wasn't active. Machines are reached by hostname (so
+//! `~/.ssh/config` aliases work) unless `ssh_hosts` in the config names
+//! another destination. Hosts run in parallel; each reports on its own.
+
+use crate::utils::deploy::parse_target;
+use crate::utils::machine_registry::MachineRegistry;
+use crate::utils::ssh_remote::{ssh, SshTarget, PATH_EXPORT};
+use anyhow::{bail, Result};
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::process::Stdio;
+
+/// Exit status of `ssh` itself failing (connection, authentication)
+const SSH_ERROR: i32 = 255;
+
+/// A machine to broadcast to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Host {
+    /// Name in `machines.toml`
+    pub hostname: String,
+    pub target: SshTarget,
+}
+
+/// What happened on a host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Synced and activated
+    Updated,
+    /// SSH couldn't log in
+    Unreachable(String),
+    /// dotstate failed on the host
+    Failed(String),
+}
+
+/// The registered machines other than `current`, limited to `only` when
+/// given. Machines that haven't synced for a long time are left out unless
+/// named in `only`.
+pub fn hosts(
+    registry: &MachineRegistry,
+    ssh_hosts: &BTreeMap<String, String>,
+    current: &str,
+    only: &[String],
+) -> Result<Vec<Host>> {
+    for name in only {
+        if !registry.machines.contains_key(name) {
+            bail!("'{name}' isn't in machines.toml");
+        }
+    }
+    let now = Utc::now();
+    registry
+        .machines
+        .iter()
+        .filter(|(name, info)| {
+            name.as_str() != current
+                && if only.is_empty() {
+                    !info.is_stale(now)
+                } else {
+                    only.contains(name)
+                }
+        })
+        .map(|(name, _)| {
+            let destination = ssh_hosts.get(name).unwrap_or(name);
+            Ok(Host {
+                hostname: name.clone(),
+                target: parse_target(destination)?,
+            })
+        })
+        .collect()
+}
+
+/// Sync and activate on `host`
+#[must_use]
+pub fn run(host: &Host) -> Outcome {
+    let script = format!("{PATH_EXPORT}; dotstate sync && dotstate activate");
+    let output = match ssh(&host.target)
+        .arg(script)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => return Outcome::Unreachable(format!("Failed to run 'ssh': {e}")),
+    };
+    if output.status.success() {
+        return Outcome::Updated;
+    }
+    // The last thing said is usually the reason
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let reason = stderr
+        .lines()
+        .chain(stdout.lines())
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("no output")
+        .to_string();
+    if output.status.code() == Some(SSH_ERROR) {
+        Outcome::Unreachable(reason)
+    } else {
+        Outcome::Failed(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::machine_registry::MachineInfo;
+    use chrono::Duration;
+
+    #[test]
+    fn test_hosts() {
+        let entry = |days: i64| MachineInfo {
+            os: "linux x86_64".to_string(),
+            active_profile: "work".to_string(),
+            dotstate_version: "1.0.0".to_string(),
+            last_sync: Utc::now() - Duration::days(days),
+        };
+        let mut registry = MachineRegistry::default();
+        registry.machines.insert("laptop".to_string(), entry(0));
+        registry.machines.insert("server".to_string(), entry(1));
+        registry.machines.insert("old-box".to_string(), entry(90));
+        let ssh_hosts = BTreeMap::from([(
+            "server".to_string(),
+            "ssh://deploy@server.example.com:2222".to_string(),
+        )]);
+
+        let found = hosts(&registry, &ssh_hosts, "laptop", &[]).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].hostname, "server");
+        assert_eq!(found[0].target.destination(), "deploy@server.example.com");
+        assert_eq!(found[0].target.port, Some(2222));
+
+        // Naming a stale machine includes it
+        let found = hosts(&registry, &ssh_hosts, "laptop", &["old-box".to_string()]).unwrap();
+        assert_eq!(found[0].target.destination(), "old-box");
+        assert!(hosts(&registry, &ssh_hosts, "laptop", &["nope".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+//! Headless checks of a storage repository for `dotstate ci`.
+//!
+//! Runs against a checkout of the repository, without a config, a home
+//! directory or a terminal:
+//!
+//! - **verify**: the layout checks of `dotstate fsck` (every profile's files
+//!   are where the manifest says, nothing is unaccounted for)
+//! - **doctor**: the doctor's profile checks for each profile
+//! - **activate**: each profile is activated into a scratch home directory,
+//!   so files that can't be linked, decrypted or merged show up before the
+//!   change reaches a machine
+//!
+//! [`annotation`] formats a finding as a GitHub Actions workflow command.
+
+use crate::config::Config;
+use crate::utils::doctor::{Doctor, DoctorOptions, ValidationStatus};
+use crate::utils::fsck::{self, Severity};
+use crate::utils::symlink_manager::OperationStatus;
+use crate::utils::{ProfileManifest, SymlinkManager};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Which check reported a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Verify,
+    Doctor,
+    Activate,
+}
+
+impl Stage {
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Stage::Verify => "verify",
+            Stage::Doctor => "doctor",
+            Stage::Activate => "activate",
+        }
+    }
+}
+
+/// A problem found in the repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub stage: Stage,
+    pub severity: Severity,
+    /// Profile, `common`, `manifest` or `tracking`
+    pub location: String,
+    /// File in the repository the finding is about, when there is one
+    pub file: Option<String>,
+    pub message: String,
+}
+
+/// Profiles to check: the requested ones, or every profile in the manifest
+fn profiles_to_check(repo_path: &Path, requested: &[String]) -> Vec<String> {
+    if !requested.is_empty() {
+        return requested.to_vec();
+    }
+    ProfileManifest::load(repo_path)
+        .map(|manifest| manifest.profiles.into_iter().map(|p| p.name).collect())
+        .unwrap_or_default()
+}
+
+/// Check the repository at `repo_path` for `profiles` (every profile when
+/// empty). Scratch directories are created under `scratch` and removed.
+pub fn run(repo_path: &Path, profiles: &[String], scratch: &Path) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let profiles = profiles_to_check(repo_path, profiles);
+
+    // The tracking file lives on a machine, so there is none to verify
+    let data_dir = scratch.join("data");
+    findings.extend(
+        fsck::check(repo_path, &data_dir)
+            .into_iter()
+            .map(|issue| Finding {
+                stage: Stage::Verify,
+                severity: issue.severity,
+                location: issue.location,
+                file: None,
+                message: issue.message,
+            }),
+    );
+
+    let mut seen = HashSet::new();
+    for profile in &profiles {
+        let config = Config {
+            repo_path: repo_path.to_path_buf(),
+            active_profile: profile.clone(),
+            ..Config::default()
+        };
+        let options = DoctorOptions {
+            fix_mode: false,
+            interactive: false,
+            fix_only: Vec::new(),
+            verbose: false,
+            json_output: false,
+            quiet: true,
+        };
+        for result in Doctor::new(config, options).run_profile_checks() {
+            let severity = match result.status {
+                ValidationStatus::Pass => continue,
+                ValidationStatus::Warning => Severity::Warning,
+                ValidationStatus::Error => Severity::Error,
+            };
+            let mut message = result.message;
+            if let Some(details) = result.details {
+                message = format!("{message}: {}", details.join(", "));
+            }
+            // Manifest, common and hash checks repeat for every profile
+            if seen.insert(message.clone()) {
+                findings.push(Finding {
+                    stage: Stage::Doctor,
+                    severity,
+                    location: profile.clone(),
+                    file: None,
+                    message,
+                });
+            }
+        }
+    }
+
+    for (i, profile) in profiles.iter().enumerate() {
+        let dir = scratch.join(format!("activate-{i}"));
+        let result = activate(repo_path, profile, &dir);
+        let _ = std::fs::remove_dir_all(&dir);
+        match result {
+            Ok(profile_findings) => findings.extend(profile_findings),
+            Err(e) => findings.push(Finding {
+                stage: Stage::Activate,
+                severity: Severity::Error,
+                location: profile.clone(),
+                file: None,
+                message: format!("{e:#}"),
+            }),
+        }
+    }
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    Ok(findings)
+}
+
+/// Activate `profile` into a scratch home under `dir` and report the files
+/// that weren't linked
+fn activate(repo_path: &Path, profile: &str, dir: &Path) -> Result<Vec<Finding>> {
+    let manifest = ProfileManifest::load(repo_path)?;
+    let resolved = manifest.resolve_files(profile)?;
+    let home = dir.join("home");
+    std::fs::create_dir_all(&home).context("Failed to create scratch home directory")?;
+    let mut symlink_mgr =
+        SymlinkManager::new_with_data_dir(repo_path.to_path_buf(), false, dir.join("data"))?;
+    let operations = symlink_mgr.activate_resolved_with_home(profile, &resolved, &home)?;
+
+    Ok(operations
+        .into_iter()
+        .filter_map(|op| {
+            let (severity, reason) = match op.status {
+                OperationStatus::Failed(reason) => (Severity::Error, reason),
+                OperationStatus::Skipped(reason) => (Severity::Warning, reason),
+                OperationStatus::Success | OperationStatus::RolledBack => return None,
+            };
+            let file = op
+                .source
+                .strip_prefix(repo_path)
+                .map_or_else(|_| op.source.clone(), Path::to_path_buf);
+            Some(Finding {
+                stage: Stage::Activate,
+                severity,
+                location: profile.to_string(),
+                file: Some(file.to_string_lossy().to_string()),
+                message: format!(
+                    "Can't link {}: {reason}",
+                    relative_target(&op.target, &home)
+                ),
+            })
+        })
+        .collect())
+}
+
+fn relative_target(target: &Path, home: &Path) -> String {
+    let relative = target.strip_prefix(home).map_or(target, |p| p);
+    PathBuf::from("~").join(relative).display().to_string()
+}
+
+/// Format a finding as a GitHub Actions annotation, e.g.
+/// `::error file=work/.zshrc,title=dotstate activate (work)::...`
+#[must_use]
+pub fn annotation(finding: &Finding) -> String {
+    let level = match finding.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+    let title = format!("dotstate {} ({})", finding.stage.label(), finding.location);
+    let mut properties = Vec::new();
+    if let Some(file) = &finding.file {
+        properties.push(format!("file={}", escape_property(file)));
+    }
+    properties.push(format!("title={}", escape_property(&title)));
+    format!(
+        "::{level} {}::{}",
+        properties.join(","),
+        escape_data(&finding.message)
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::ProfileInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reports_missing_files_and_annotates() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        std::fs::create_dir_all(repo.join("work")).unwrap();
+        std::fs::write(repo.join("work/.zshrc"), "export EDITOR=vim").unwrap();
+        ProfileManifest {
+            profiles: vec![ProfileInfo {
+                name: "work".to_string(),
+                description: None,
+                inherits: None,
+                synced_files: vec![".zshrc".to_string(), ".vimrc".to_string()],
+                packages: Vec::new(),
+            }],
+            ..ProfileManifest::default()
+        }
+        .save(&repo)
+        .unwrap();
+
+        let findings = run(&repo, &[], &temp_dir.path().join("scratch")).unwrap();
+        let errors: Vec<&Finding> = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .collect();
+        assert!(errors.iter().any(|f| f.stage == Stage::Verify));
+        assert!(errors.iter().any(|f| f.stage == Stage::Doctor));
+        let missing = errors
+            .iter()
+            .find(|f| f.stage == Stage::Activate)
+            .expect("activation of the missing file fails");
+        assert_eq!(missing.file.as_deref(), Some("work/.vimrc"));
+        assert!(!temp_dir.path().join("scratch/activate-0").exists());
+
+        let line = annotation(&Finding {
+            stage: Stage::Activate,
+            severity: Severity::Error,
+            location: "work".to_string(),
+            file: Some("work/a,b.conf".to_string()),
+            message: "100% broken\nsee log".to_string(),
+        });
+        assert_eq!(
+            line,
+            "::error file=work/a%2Cb.conf,title=dotstate activate (work)::100%25 broken%0Asee log"
+        );
+    }
+}
@@ -0,0 +1,475 @@
+//! Validation of the config file against the `Config` schema.
+//!
+//! serde ignores unknown keys and a few settings fall back to a default when
+//! their value isn't recognized (an unknown theme silently becomes `dark`).
+//! This module reports those cases, and values of the wrong type, with the
+//! line they are on and the values that are accepted. [`repair_config`]
+//! removes the offending keys so their defaults apply.
+
+use crate::config::{Config, GitHubConfig, ProfileOverrides};
+use crate::utils::syntax_theme::{AUTO_SYNTAX_THEME, SYNTAX_THEMES};
+use crate::utils::theme_type::ThemeType;
+use anyhow::{Context, Result};
+use std::fmt;
+use std::path::Path;
+use toml::{Table, Value};
+
+/// Accepted `icon_set` values (`auto` detects the terminal's capabilities)
+pub const ICON_SET_VALUES: &[&str] = &["auto", "nerd", "unicode", "emoji", "ascii"];
+
+/// Older spellings of `icon_set` values that are still accepted
+const ICON_SET_ALIASES: &[&str] = &["nerdfont", "nerdfonts", "plain"];
+
+/// Repairs stop after this many passes, in case a fix doesn't stick
+const MAX_REPAIR_PASSES: usize = 32;
+
+/// A problem found in the config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// 1-based line number, when the problem can be located
+    pub line: Option<usize>,
+    /// Dotted path of the key, e.g. `updates.check_enabled` (empty for syntax errors)
+    pub field: String,
+    pub message: String,
+    /// Values the field accepts, when there is a fixed set
+    pub accepted: Vec<String>,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        if !self.field.is_empty() {
+            write!(f, "`{}`: ", self.field)?;
+        }
+        write!(f, "{}", self.message)?;
+        if !self.accepted.is_empty() {
+            write!(f, " (accepted: {})", self.accepted.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check config file contents, returning every problem found.
+#[must_use]
+pub fn validate_config(content: &str) -> Vec<ConfigIssue> {
+    let table = match content.parse::<Table>() {
+        Ok(table) => table,
+        Err(e) => return vec![syntax_issue(content, &e)],
+    };
+
+    let mut issues = schema_issues(content, &table);
+    if let Err(e) = toml::from_str::<Config>(content) {
+        issues.push(deserialize_issue(content, &e));
+    }
+    issues
+}
+
+/// Remove keys that are unknown or have invalid values so their defaults
+/// apply, keeping every valid setting.
+///
+/// Returns the repaired contents and the issues that were fixed. Fails when
+/// the file isn't valid TOML, since there is no way to tell what was meant.
+pub fn repair_config(content: &str) -> Result<(String, Vec<ConfigIssue>)> {
+    let mut table = content
+        .parse::<Table>()
+        .map_err(|e| anyhow::anyhow!("{}", syntax_issue(content, &e)))?;
+
+    let mut fixed = schema_issues(content, &table);
+    for issue in &fixed {
+        remove_key(&mut table, &issue.field);
+    }
+
+    let defaults = Table::try_from(Config::default())?;
+    for _ in 0..MAX_REPAIR_PASSES {
+        let text = toml::to_string(&table)?;
+        let Err(e) = toml::from_str::<Config>(&text) else {
+            return Ok((text, fixed));
+        };
+
+        let issue = deserialize_issue(&text, &e);
+        if let Some(missing) = missing_field(e.message()) {
+            let Some(default) = defaults.get(missing) else {
+                anyhow::bail!("Can't repair config: {issue}");
+            };
+            table.insert(missing.to_string(), default.clone());
+        } else if issue.field.is_empty() || !remove_key(&mut table, &issue.field) {
+            anyhow::bail!("Can't repair config: {issue}");
+        }
+        fixed.push(ConfigIssue {
+            line: locate(content, &issue.field),
+            ..issue
+        });
+    }
+    anyhow::bail!("Can't repair config: too many problems")
+}
+
+/// Repair the config file at `path` in place, keeping a copy of the
+/// original next to it as `config.toml.bak`. Returns the issues fixed.
+pub fn repair_config_file(path: &Path) -> Result<Vec<ConfigIssue>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {path:?}"))?;
+    let (repaired, fixed) = repair_config(&content)?;
+    if fixed.is_empty() {
+        return Ok(fixed);
+    }
+
+    let backup_path = path.with_extension("toml.bak");
+    std::fs::copy(path, &backup_path)
+        .with_context(|| format!("Failed to back up config to {backup_path:?}"))?;
+    let config: Config = toml::from_str(&repaired).context("Repaired config is invalid")?;
+    config.save(path)?;
+    Ok(fixed)
+}
+
+/// Describe a deserialize error with its line and field.
+#[must_use]
+pub fn describe_parse_error(content: &str, error: &toml::de::Error) -> String {
+    deserialize_issue(content, error).to_string()
+}
+
+/// Unknown keys and unrecognized values for settings that would silently fall back.
+fn schema_issues(content: &str, table: &Table) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let known = known_keys();
+    collect_unknown_keys(content, table, &known, "", &mut issues);
+
+    let theme_values: Vec<String> = ThemeType::all()
+        .iter()
+        .map(|t| t.to_config_string().to_string())
+        .collect();
+    let is_theme = |v: &str| {
+        v.parse::<ThemeType>().unwrap_or_default() != ThemeType::Dark
+            || v.eq_ignore_ascii_case("dark")
+    };
+    check_string(
+        content,
+        table,
+        "theme",
+        &mut issues,
+        &theme_values,
+        is_theme,
+    );
+    if let Some(Value::Table(overrides)) = table.get("profile_overrides") {
+        for (profile, value) in overrides {
+            if let Value::Table(profile_table) = value {
+                let prefix = format!("profile_overrides.{profile}.");
+                let start = issues.len();
+                check_string(
+                    content,
+                    profile_table,
+                    "theme",
+                    &mut issues,
+                    &theme_values,
+                    is_theme,
+                );
+                for issue in &mut issues[start..] {
+                    issue.field.insert_str(0, &prefix);
+                    issue.line = locate(content, &issue.field);
+                }
+            }
+        }
+    }
+
+    let icon_values: Vec<String> = ICON_SET_VALUES.iter().map(ToString::to_string).collect();
+    check_string(content, table, "icon_set", &mut issues, &icon_values, |v| {
+        let v = v.to_lowercase();
+        ICON_SET_VALUES.contains(&v.as_str()) || ICON_SET_ALIASES.contains(&v.as_str())
+    });
+
+    let syntax_values: Vec<String> = std::iter::once(AUTO_SYNTAX_THEME)
+        .chain(SYNTAX_THEMES.iter().copied())
+        .map(ToString::to_string)
+        .collect();
+    check_string(
+        content,
+        table,
+        "syntax_theme",
+        &mut issues,
+        &syntax_values,
+        |v| syntax_values.iter().any(|s| s == v),
+    );
+
+    issues
+}
+
+/// Report a top-level string setting whose value `is_valid` rejects.
+fn check_string(
+    content: &str,
+    table: &Table,
+    key: &str,
+    issues: &mut Vec<ConfigIssue>,
+    accepted: &[String],
+    is_valid: impl Fn(&str) -> bool,
+) {
+    let Some(Value::String(value)) = table.get(key) else {
+        return;
+    };
+    if !is_valid(value) {
+        issues.push(ConfigIssue {
+            line: locate(content, key),
+            field: key.to_string(),
+            message: format!("unknown value '{value}'"),
+            accepted: accepted.to_vec(),
+        });
+    }
+}
+
+/// Every key the schema knows, as a table of the same shape. Tables keyed by
+/// name (like `profile_overrides`) have a single `*` entry.
+///
+/// Built by serializing a config with every optional section filled in, so
+/// new fields are picked up without listing them here.
+fn known_keys() -> Table {
+    let config = Config {
+        github: Some(GitHubConfig {
+            owner: String::new(),
+            repo: String::new(),
+            token: Some(String::new()),
+        }),
+        proxy: Some(String::new()),
+        backup_dir: Some(std::path::PathBuf::new()),
+        commit_template: Some(String::new()),
+        profile_overrides: [(
+            "*".to_string(),
+            ProfileOverrides {
+                theme: Some(String::new()),
+                keymap_preset: Some(crate::keymap::KeymapPreset::default()),
+                backup_enabled: Some(true),
+                commit_template: Some(String::new()),
+            },
+        )]
+        .into(),
+        ..Config::default()
+    };
+    Table::try_from(config).unwrap_or_default()
+}
+
+fn collect_unknown_keys(
+    content: &str,
+    table: &Table,
+    known: &Table,
+    prefix: &str,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match known.get(key).or_else(|| known.get("*")) {
+            None => issues.push(ConfigIssue {
+                line: locate(content, &path),
+                field: path,
+                message: "unknown key".to_string(),
+                accepted: known.keys().cloned().collect(),
+            }),
+            Some(Value::Table(known_child)) => {
+                if let Value::Table(child) = value {
+                    collect_unknown_keys(content, child, known_child, &path, issues);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+fn syntax_issue(content: &str, error: &toml::de::Error) -> ConfigIssue {
+    ConfigIssue {
+        line: error.span().map(|span| line_of(content, span.start)),
+        field: String::new(),
+        message: error.message().trim().to_string(),
+        accepted: Vec::new(),
+    }
+}
+
+fn deserialize_issue(content: &str, error: &toml::de::Error) -> ConfigIssue {
+    let line = error.span().map(|span| line_of(content, span.start));
+    let field = missing_field(error.message())
+        .map(ToString::to_string)
+        .or_else(|| line.and_then(|line| key_at_line(content, line)))
+        .unwrap_or_default();
+    ConfigIssue {
+        // A missing field has no line of its own
+        line: line.filter(|_| missing_field(error.message()).is_none()),
+        field,
+        message: error.message().trim().to_string(),
+        accepted: Vec::new(),
+    }
+}
+
+/// The field named by serde's "missing field `x`" message
+fn missing_field(message: &str) -> Option<&str> {
+    message
+        .strip_prefix("missing field `")?
+        .split('`')
+        .next()
+        .filter(|field| !field.is_empty())
+}
+
+/// 1-based line number of a byte offset
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())]
+        .bytes()
+        .filter(|&b| b == b'\n')
+        .count()
+        + 1
+}
+
+/// Iterate over `(line number, table path, key)` for every `key = value` line.
+fn key_lines(content: &str) -> impl Iterator<Item = (usize, String, &str)> {
+    let mut section = String::new();
+    content.lines().enumerate().filter_map(move |(i, line)| {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            section = header
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or_default()
+                .split('.')
+                .map(|part| part.trim().trim_matches('"'))
+                .collect::<Vec<_>>()
+                .join(".");
+            return None;
+        }
+        if line.starts_with('#') {
+            return None;
+        }
+        let (key, _) = line.split_once('=')?;
+        Some((i + 1, section.clone(), key.trim().trim_matches('"')))
+    })
+}
+
+/// Line where the dotted `path` is assigned
+fn locate(content: &str, path: &str) -> Option<usize> {
+    let (section, key) = path.rsplit_once('.').unwrap_or(("", path));
+    key_lines(content)
+        .find(|(_, s, k)| s == section && *k == key)
+        .map(|(line, _, _)| line)
+}
+
+/// Dotted path of the key assigned on `line`
+fn key_at_line(content: &str, line: usize) -> Option<String> {
+    key_lines(content)
+        .find(|(l, _, _)| *l == line)
+        .map(|(_, section, key)| {
+            if section.is_empty() {
+                key.to_string()
+            } else {
+                format!("{section}.{key}")
+            }
+        })
+}
+
+/// Remove the dotted `path` from `table`, returning whether it was there
+fn remove_key(table: &mut Table, path: &str) -> bool {
+    match path.split_once('.') {
+        None => table.remove(path).is_some(),
+        Some((head, rest)) => match table.get_mut(head) {
+            Some(Value::Table(child)) => remove_key(child, rest),
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = r#"
+active_profile = "work"
+repo_path = "/home/me/.config/dotstate/storage"
+theme = "midnight"
+
+[updates]
+check_enabled = true
+"#;
+
+    #[test]
+    fn test_valid_config_has_no_issues() {
+        assert!(validate_config(VALID).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_located() {
+        let content = VALID.replace("check_enabled", "check_enabeld");
+        let issues = validate_config(&content);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "updates.check_enabeld");
+        assert_eq!(issues[0].line, Some(7));
+        assert!(issues[0].accepted.contains(&"check_enabled".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_theme_lists_accepted_values() {
+        let content = VALID.replace("midnight", "midnite");
+        let issues = validate_config(&content);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "theme");
+        assert_eq!(issues[0].line, Some(4));
+        assert!(issues[0].accepted.contains(&"midnight".to_string()));
+    }
+
+    #[test]
+    fn test_profile_overrides_are_checked() {
+        let content =
+            format!("{VALID}\n[profile_overrides.Work]\ntheme = \"solarised\"\nbackup = true\n");
+        let issues = validate_config(&content);
+        let fields: Vec<&str> = issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "profile_overrides.Work.backup",
+                "profile_overrides.Work.theme"
+            ]
+        );
+        assert_eq!(issues[1].line, Some(10));
+    }
+
+    #[test]
+    fn test_wrong_type_is_located() {
+        let content = VALID.replace("check_enabled = true", "check_enabled = \"yes\"");
+        let issues = validate_config(&content);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "updates.check_enabled");
+        assert_eq!(issues[0].line, Some(7));
+    }
+
+    #[test]
+    fn test_syntax_error_has_line() {
+        let issues = validate_config("theme = \"dark\"\nbackup_enabled = \n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_repair_keeps_valid_settings() {
+        let content = VALID.replace("midnight", "midnite").replace(
+            "check_enabled = true",
+            "check_enabled = \"yes\"\ncolour = 1",
+        );
+        let (repaired, fixed) = repair_config(&content).unwrap();
+        assert_eq!(fixed.len(), 3);
+
+        let config: Config = toml::from_str(&repaired).unwrap();
+        assert_eq!(config.active_profile, "work");
+        assert_eq!(config.theme, "dark");
+        assert!(config.updates.check_enabled);
+        assert!(validate_config(&repaired).is_empty());
+    }
+
+    #[test]
+    fn test_repair_fills_missing_required_fields() {
+        let (repaired, fixed) = repair_config("theme = \"light\"\n").unwrap();
+        assert!(fixed.iter().any(|i| i.field == "active_profile"));
+        assert!(toml::from_str::<Config>(&repaired).is_ok());
+    }
+
+    #[test]
+    fn test_repair_refuses_invalid_toml() {
+        assert!(repair_config("theme = \n").is_err());
+    }
+}
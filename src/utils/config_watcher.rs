@@ -0,0 +1,102 @@
+//! Detects edits to the config file while the TUI is running.
+//!
+//! The event loop polls [`ConfigWatcher::poll`] on every iteration. The file
+//! is stat-ed at most once per [`CHECK_INTERVAL`], and a change in its
+//! modification time or size reports it as edited.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the config file is stat-ed
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What identifies a version of the file on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Watches the config file for changes by polling its metadata.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: PathBuf,
+    stamp: Option<FileStamp>,
+    last_check: Instant,
+}
+
+impl ConfigWatcher {
+    /// Start watching `path` from its current state on disk
+    #[must_use]
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            stamp: FileStamp::read(path),
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Whether the file changed since the last call. Cheap to call often.
+    pub fn poll(&mut self) -> bool {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return false;
+        }
+        self.last_check = Instant::now();
+        self.changed()
+    }
+
+    /// Compare the file with the last seen state and remember the new one.
+    /// A deleted file is not reported, since there is nothing to reload.
+    fn changed(&mut self) -> bool {
+        let stamp = FileStamp::read(&self.path);
+        if stamp == self.stamp {
+            return false;
+        }
+        self.stamp = stamp;
+        stamp.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_edits_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::fs::write(&path, "theme = \"dark\"\n").unwrap();
+
+        let mut watcher = ConfigWatcher::new(&path);
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "theme = \"midnight\"\n").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+    }
+
+    #[test]
+    fn test_ignores_deleted_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::fs::write(&path, "theme = \"dark\"\n").unwrap();
+
+        let mut watcher = ConfigWatcher::new(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "theme = \"light\"\n").unwrap();
+        assert!(watcher.changed());
+    }
+}
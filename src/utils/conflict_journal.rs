@@ -0,0 +1,178 @@
+//! Journal of sync conflicts and force syncs.
+//!
+//! When a sync stops on conflicting changes, a force sync discards one
+//! side or the two sides are reconciled file by file, an entry is appended to `conflict_journal.jsonl` in the data
+//! directory: the files involved, the commits on each side, and what was
+//! done about it. Unlike the sync history it is never pruned, so a
+//! multi-machine mishap can be pieced together long after it happened.
+
+use crate::git::PullConflict;
+use crate::utils::sync_history::{SyncOrigin, SyncRecord};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Name of the journal file in the data directory
+const JOURNAL_FILE: &str = "conflict_journal.jsonl";
+
+/// What happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// A pull stopped on files changed on both sides
+    Conflict,
+    /// Force sync discarded local changes to match the remote
+    MatchRemote,
+    /// Force sync replaced the remote with the local state
+    OverwriteRemote,
+    /// Local and remote were merged file by file
+    Reconcile,
+}
+
+impl JournalEvent {
+    /// Display label
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            JournalEvent::Conflict => "Conflict",
+            JournalEvent::MatchRemote => "Force sync (match remote)",
+            JournalEvent::OverwriteRemote => "Force sync (overwrite remote)",
+            JournalEvent::Reconcile => "Reconcile",
+        }
+    }
+}
+
+/// One conflict or force sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Utc>,
+    pub event: JournalEvent,
+    pub origin: SyncOrigin,
+    /// Machine it happened on
+    pub hostname: String,
+    /// Profile active at the time
+    pub profile: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Local commit involved, short id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_commit: Option<String>,
+    /// Remote commit involved, short id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_commit: Option<String>,
+    /// Files in conflict, discarded/replaced by the force sync, or
+    /// reconciled with the choice made for each
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Commits discarded, replaced or merged, as "abc1234 subject" entries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<String>,
+    /// What was done about it
+    pub resolution: String,
+}
+
+impl JournalEntry {
+    /// Entry for `event` during the sync described by `record`
+    #[must_use]
+    pub fn new(event: JournalEvent, record: &SyncRecord, resolution: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            event,
+            origin: record.origin,
+            hostname: crate::utils::machine_registry::current_hostname(),
+            profile: record.profile.clone(),
+            branch: record.branch.clone(),
+            local_commit: None,
+            remote_commit: None,
+            files: Vec::new(),
+            commits: Vec::new(),
+            resolution: resolution.to_string(),
+        }
+    }
+}
+
+fn journal_file() -> PathBuf {
+    crate::utils::get_data_dir().join(JOURNAL_FILE)
+}
+
+/// All entries, newest first. A missing journal is empty.
+#[must_use]
+pub fn load() -> Vec<JournalEntry> {
+    load_from(&journal_file())
+}
+
+/// Append `entry`, logging instead of failing: the journal must never fail
+/// a sync.
+pub fn save(entry: &JournalEntry) {
+    if let Err(e) = append_to(&journal_file(), entry) {
+        warn!("Failed to write conflict journal: {}", e);
+    }
+}
+
+/// Journal `error` if it is a pull conflict. `resolution` says what became
+/// of the sync's changes.
+pub fn record_pull_error(error: &anyhow::Error, record: &SyncRecord, resolution: &str) {
+    let Some(conflict) = error.downcast_ref::<PullConflict>() else {
+        return;
+    };
+    let mut entry = JournalEntry::new(JournalEvent::Conflict, record, resolution);
+    entry.files.clone_from(&conflict.files);
+    entry.local_commit = Some(conflict.local_commit.clone());
+    entry.remote_commit = Some(conflict.remote_commit.clone());
+    save(&entry);
+}
+
+fn append_to(path: &Path, entry: &JournalEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create data directory")?;
+    }
+    let line = serde_json::to_string(entry).context("Failed to serialize journal entry")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{line}").with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn load_from(path: &Path) -> Vec<JournalEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<JournalEntry> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    entries.reverse();
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sync_history::SyncKind;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_load_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(JOURNAL_FILE);
+        let record = SyncRecord::start(SyncKind::Sync, SyncOrigin::Tui, "Work");
+
+        let mut conflict = JournalEntry::new(JournalEvent::Conflict, &record, "Sync stopped");
+        conflict.files = vec!["Work/.zshrc".to_string()];
+        append_to(&path, &conflict).unwrap();
+        let mut forced = JournalEntry::new(JournalEvent::MatchRemote, &record, "Matched");
+        forced.commits = vec!["abc1234 Update .zshrc".to_string()];
+        append_to(&path, &forced).unwrap();
+
+        let entries = load_from(&path);
+        assert_eq!(entries, vec![forced, conflict]);
+        assert!(load_from(&temp_dir.path().join("missing.jsonl")).is_empty());
+    }
+}
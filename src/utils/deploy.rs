@@ -0,0 +1,294 @@
+//! One-way deploys of dotfiles to a remote host over SSH.
+//!
+//! `dotstate deploy me@server` is for servers that should get the dotfiles
+//! without a full setup: it copies the storage repository to the host over
+//! `ssh` (with `tar` on both ends, so the host needs no git) and runs
+//! `dotstate activate` there, installing the binary first when asked. A
+//! deploy only replaces a directory an earlier deploy left its marker in, or
+//! one that is empty or missing. With `--export` it copies the profile's
+//! files straight into the remote home directory instead, for hosts that
+//! shouldn't run dotstate at all, decrypting sops files and rendering
+//! templates the way activation does.
+
+use crate::utils::devcontainer::INSTALLER_URL;
+use crate::utils::scratch::ScratchDir;
+use crate::utils::ssh_remote::{shell_quote, ssh, SshTarget, PATH_EXPORT};
+use crate::utils::{secrets, sops, ProfileManifest};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Where the repository goes on the host when the target names no path,
+/// relative to the home directory
+pub const DEFAULT_DIR: &str = ".local/share/dotstate/deploy";
+
+/// Left in the deployed repository's `.git` directory, so a later deploy
+/// knows the directory is its own to replace
+pub const MARKER: &str = ".git/dotstate-deploy";
+
+/// Shell commands failing unless `dir` (quoted) is missing, empty, or holds
+/// the [`MARKER`] of an earlier deploy. An empty directory is removed.
+fn replace_check(dir: &str) -> String {
+    format!(
+        "{{ {{ ! [ -e {dir} ] && ! [ -L {dir} ]; }} || [ -f {dir}/{MARKER} ] || {{ [ -d {dir} ] && rmdir -- {dir} 2>/dev/null; }} || \
+         {{ printf '%s was not created by dotstate deploy; refusing to replace it\\n' {dir} >&2; exit 1; }}; }}"
+    )
+}
+
+/// Parse `[user@]host[:path]` or `ssh://[user@]host[:port][/path]`,
+/// defaulting the path to [`DEFAULT_DIR`]
+pub fn parse_target(input: &str) -> Result<SshTarget> {
+    let input = input.trim();
+    match input.strip_prefix("ssh://") {
+        Some(rest) if !rest.contains('/') => SshTarget::parse(&format!("{input}/~/{DEFAULT_DIR}")),
+        None if !input.contains(':') => SshTarget::parse(&format!("{input}:{DEFAULT_DIR}")),
+        _ => SshTarget::parse(input),
+    }
+}
+
+/// `.git/config` without credentials in HTTP remote URLs (the GitHub setup
+/// keeps the token in the `origin` URL)
+#[must_use]
+pub fn sanitize_git_config(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            let is_url = line
+                .split_once('=')
+                .is_some_and(|(key, _)| matches!(key.trim(), "url" | "pushurl"));
+            match (is_url, line.find("://")) {
+                // Only HTTP URLs carry passwords; `git@` in SSH URLs is the login
+                (true, Some(scheme_end))
+                    if line[..scheme_end].ends_with("http")
+                        || line[..scheme_end].ends_with("https") =>
+                {
+                    let rest = &line[scheme_end + 3..];
+                    let host_start = rest
+                        .find('@')
+                        .filter(|&at| !rest[..at].contains('/'))
+                        .map_or(0, |at| at + 1);
+                    format!("{}{}", &line[..scheme_end + 3], &rest[host_start..])
+                }
+                _ => line.to_string(),
+            }
+        })
+        .fold(String::new(), |mut out, line| {
+            out.push_str(&line);
+            out.push('\n');
+            out
+        })
+}
+
+/// Pack `dir` with `tar` and unpack it on the host with `remote_script`,
+/// which reads the archive from stdin
+fn send_tar(dir: &Path, excludes: &[&str], target: &SshTarget, remote_script: &str) -> Result<()> {
+    let mut tar = Command::new("tar");
+    tar.arg("-C").arg(dir).arg("-cf").arg("-");
+    for exclude in excludes {
+        tar.arg(format!("--exclude={exclude}"));
+    }
+    let mut tar = tar
+        .arg(".")
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to run 'tar'")?;
+    let archive = tar.stdout.take().context("Failed to read from 'tar'")?;
+    let output = ssh(target)
+        .arg(remote_script)
+        .stdin(archive)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run 'ssh'. Is OpenSSH installed?")?;
+    let packed = tar.wait().context("Failed to run 'tar'")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to copy to {}: {}\n\
+             Check that you can log in with 'ssh {}' without a password prompt.",
+            target.host,
+            String::from_utf8_lossy(&output.stderr).trim(),
+            target.destination()
+        );
+    }
+    if !packed.success() {
+        bail!("Failed to pack {}", dir.display());
+    }
+    Ok(())
+}
+
+/// Run `script` on the host, streaming its output
+fn run_remote(target: &SshTarget, script: &str) -> Result<()> {
+    let status = ssh(target)
+        .arg(script)
+        .stdin(Stdio::null())
+        .status()
+        .context("Failed to run 'ssh'. Is OpenSSH installed?")?;
+    if !status.success() {
+        bail!("The command on {} failed", target.host);
+    }
+    Ok(())
+}
+
+/// Copy the repository at `repo_path` to the target's path, replacing what
+/// an earlier deploy put there. Anything else at that path is refused.
+/// Credentials are left out of `.git/config`.
+pub fn send_repo(repo_path: &Path, target: &SshTarget) -> Result<()> {
+    let dir = shell_quote(&target.path);
+    let staged = shell_quote(&format!("{}.new", target.path));
+    let check = replace_check(&dir);
+    send_tar(
+        repo_path,
+        &[
+            "./.git/config",
+            &format!("./.git/{}", crate::utils::file_sync::LOCK_FILE),
+        ],
+        target,
+        &format!("{check} && rm -rf -- {staged} && mkdir -p -- {staged} && tar -xf - -C {staged}"),
+    )?;
+
+    let config = fs::read_to_string(repo_path.join(".git/config"))
+        .context("Failed to read the repository's git config")?;
+    let output = ssh(target)
+        .arg(format!(
+            "cat > {staged}/.git/config && touch {staged}/{MARKER} && {check} && rm -rf -- {dir} && mv -- {staged} {dir}"
+        ))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(sanitize_git_config(&config).as_bytes())?;
+            }
+            child.wait_with_output()
+        })
+        .context("Failed to run 'ssh'. Is OpenSSH installed?")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to set up the repository on {}: {}",
+            target.host,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Run `dotstate activate` for `profile` on the host against the deployed
+/// repository, installing dotstate first when `install` is set
+pub fn activate(target: &SshTarget, profile: &str, install: bool) -> Result<()> {
+    let mut script = format!("{PATH_EXPORT}; ");
+    if install {
+        script.push_str(&format!(
+            "command -v dotstate >/dev/null 2>&1 || {{ curl -fsSL {INSTALLER_URL} || wget -qO- {INSTALLER_URL}; }} | bash && "
+        ));
+    } else {
+        script.push_str(
+            "command -v dotstate >/dev/null 2>&1 || { echo 'dotstate is not installed on this host; deploy again with --install' >&2; exit 1; } && ",
+        );
+    }
+    script.push_str(&format!(
+        "dotstate activate --repo {} --profile {}",
+        shell_quote(&target.path),
+        shell_quote(profile)
+    ));
+    run_remote(target, &script)
+}
+
+/// Copy the files `profile` resolves to into the remote home directory, as
+/// plain files: sops files decrypted and templates rendered, readable only
+/// by the user. Returns how many were copied.
+pub fn send_export(
+    manifest: &ProfileManifest,
+    repo_path: &Path,
+    profile: &str,
+    target: &SshTarget,
+) -> Result<usize> {
+    let files = manifest.resolve_files(profile)?;
+    // The staged files are plaintext, so they go into a private directory
+    let staging = ScratchDir::new("dotstate-deploy")?;
+    for file in &files {
+        let from = repo_path
+            .join(&file.source_profile)
+            .join(&file.relative_path);
+        let to = staging.path().join(&file.relative_path);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        if from.is_dir() {
+            crate::file_manager::copy_dir_all(&from, &to)?;
+        } else if sops::is_managed(repo_path, &from) {
+            sops::write_plaintext(&to, &sops::decrypt(repo_path, &from)?)?;
+        } else if secrets::is_template(&from) {
+            sops::write_plaintext(&to, &secrets::render_file(&from)?)?;
+        } else {
+            fs::copy(&from, &to)
+                .with_context(|| format!("Failed to copy {}", file.relative_path))?;
+        }
+    }
+    send_tar(staging.path(), &[], target, "cd && tar -xf -")?;
+    Ok(files.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_and_git_config() {
+        let target = parse_target("me@server").unwrap();
+        assert_eq!(target.destination(), "me@server");
+        assert_eq!(target.path, DEFAULT_DIR);
+        assert_eq!(
+            parse_target("ssh://me@server:2222").unwrap().port,
+            Some(2222)
+        );
+        assert_eq!(
+            parse_target("server:/srv/dotfiles").unwrap().path,
+            "/srv/dotfiles"
+        );
+
+        let config = "[core]\n\tbare = false\n[remote \"origin\"]\n\
+                      \turl = https://ghp_secret@github.com/me/dotfiles.git\n\
+                      \tfetch = +refs/heads/*:refs/remotes/origin/*\n\
+                      [remote \"mirror\"]\n\turl = ssh://git@example.com/me/dotfiles.git\n";
+        let sanitized = sanitize_git_config(config);
+        assert!(sanitized.contains("\turl = https://github.com/me/dotfiles.git\n"));
+        assert!(!sanitized.contains("ghp_secret"));
+        assert!(sanitized.contains("\turl = ssh://git@example.com/me/dotfiles.git\n"));
+        assert!(sanitized.contains("\tfetch = +refs/heads/*:refs/remotes/origin/*\n"));
+    }
+
+    #[test]
+    fn test_replace_check() {
+        let temp = tempfile::tempdir().unwrap();
+        let replaceable = |name: &str| {
+            let dir = shell_quote(&temp.path().join(name).to_string_lossy());
+            Command::new("sh")
+                .arg("-c")
+                .arg(replace_check(&dir))
+                .stderr(Stdio::null())
+                .status()
+                .unwrap()
+                .success()
+        };
+
+        assert!(replaceable("missing"));
+
+        fs::create_dir(temp.path().join("empty")).unwrap();
+        assert!(replaceable("empty"));
+        assert!(!temp.path().join("empty").exists());
+
+        let home = temp.path().join("home me");
+        fs::create_dir(&home).unwrap();
+        fs::write(home.join(".bashrc"), "").unwrap();
+        assert!(!replaceable("home me"));
+        assert!(home.join(".bashrc").exists());
+
+        fs::create_dir(home.join(".git")).unwrap();
+        fs::write(home.join(MARKER), "").unwrap();
+        assert!(replaceable("home me"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Bootstrap script for GitHub Codespaces and dev containers.
+//!
+//! Codespaces and the dev containers CLI clone a "dotfiles repository" into
+//! every new container and run the first of `install.sh`, `install`,
+//! `bootstrap.sh`, ... found at its root; without one, Codespaces links
+//! every top-level dotfile into the home directory, which for a storage
+//! repository means the manifest and `.gitignore`. `dotstate devcontainer`
+//! writes an `install.sh` there that installs dotstate when the container
+//! doesn't have it, points it at the clone and activates a profile, so the
+//! storage repository works as a dotfiles repository as it is.
+
+use crate::utils::repo_workflow::WriteOutcome;
+use crate::utils::ssh_remote::{shell_quote, PATH_EXPORT};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Path of the script in the repository
+pub const SCRIPT_PATH: &str = "install.sh";
+
+/// Second line of a generated script, after the shebang
+const MARKER: &str = "# Generated by `dotstate devcontainer`; run it again to update.";
+
+/// Installer for the prebuilt binary, which falls back to `cargo install`
+pub const INSTALLER_URL: &str = "https://dotstate.serkan.dev/install.sh";
+
+/// Whether `content` is a script dotstate generated
+#[must_use]
+pub fn is_generated(content: &str) -> bool {
+    content.lines().nth(1) == Some(MARKER)
+}
+
+/// Script contents, activating `profile` unless `DOTSTATE_PROFILE` names
+/// another one
+#[must_use]
+pub fn generate(profile: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+{MARKER}
+#
+# GitHub Codespaces and dev containers run this after cloning the repository
+# as a dotfiles repository. It installs dotstate when it's missing, uses this
+# clone as the storage repository and activates a profile.
+#
+#   DOTSTATE_PROFILE=name   activate another profile
+#   DOTSTATE_BOOTSTRAP=0    don't run the profile's bootstrap scripts
+set -eu
+
+repo="$(cd "$(dirname "$0")" && pwd)"
+profile="${{DOTSTATE_PROFILE:-}}"
+[ -n "$profile" ] || profile={profile}
+
+# The installer puts the binary in ~/.local/bin, which may not be on PATH yet
+{PATH_EXPORT}
+
+if ! command -v dotstate >/dev/null 2>&1; then
+    echo "Installing dotstate..."
+    if command -v curl >/dev/null 2>&1; then
+        curl -fsSL {INSTALLER_URL} | bash
+    else
+        wget -qO- {INSTALLER_URL} | bash
+    fi
+fi
+
+dotstate activate --repo "$repo" --profile "$profile"
+
+if [ "${{DOTSTATE_BOOTSTRAP:-1}}" != "0" ]; then
+    dotstate bootstrap --profile "$profile" --yes
+fi
+"#,
+        profile = shell_quote(profile)
+    )
+}
+
+/// Where the script is written in the repository at `repo_path`
+#[must_use]
+pub fn path(repo_path: &Path) -> PathBuf {
+    repo_path.join(SCRIPT_PATH)
+}
+
+/// Write the script into the repository at `repo_path`, executable. A
+/// script that wasn't generated by dotstate is only replaced with `force`.
+pub fn write(repo_path: &Path, profile: &str, force: bool) -> Result<WriteOutcome> {
+    let path = path(repo_path);
+    let content = generate(profile);
+    let outcome = match fs::read_to_string(&path) {
+        Ok(existing) if existing == content => return Ok(WriteOutcome::Unchanged),
+        Ok(existing) if !is_generated(&existing) && !force => {
+            bail!(
+                "{} exists and wasn't generated by dotstate (use --force to replace it)",
+                path.display()
+            )
+        }
+        Ok(_) => WriteOutcome::Updated,
+        Err(_) => WriteOutcome::Created,
+    };
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", path.display()))?;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_install_script() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+
+        assert_eq!(write(repo, "work", false).unwrap(), WriteOutcome::Created);
+        let content = fs::read_to_string(path(repo)).unwrap();
+        assert!(is_generated(&content));
+        assert!(content.contains("[ -n \"$profile\" ] || profile='work'\n"));
+        assert!(content.contains("dotstate activate --repo \"$repo\" --profile \"$profile\"\n"));
+        assert_eq!(write(repo, "work", false).unwrap(), WriteOutcome::Unchanged);
+        assert_eq!(
+            write(repo, "Jo's laptop", false).unwrap(),
+            WriteOutcome::Updated
+        );
+        assert!(fs::read_to_string(path(repo))
+            .unwrap()
+            .contains("profile='Jo'\\''s laptop'\n"));
+
+        // A script the user wrote is kept unless forced
+        fs::write(path(repo), "#!/bin/sh\necho mine\n").unwrap();
+        assert!(write(repo, "work", false).is_err());
+        assert_eq!(write(repo, "work", true).unwrap(), WriteOutcome::Updated);
+    }
+}
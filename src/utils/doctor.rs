@@ -1,14 +1,19 @@
 use anyhow::Result;
+#[cfg(feature = "tui")]
 use crossterm::style::{Attribute, Color, Stylize};
 use serde::Serialize;
 use std::collections::HashSet;
 use std::fs;
 use std::io::{self, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
 use std::process::Command;
 use std::time::{Duration, Instant};
 
-use crate::config::Config;
+use crate::config::{Config, RepoMode};
+use crate::utils::foreign_managers::{detect_managers, find_foreign_symlinks, ForeignSymlink};
 use crate::utils::{ProfileManifest, SymlinkManager};
+#[cfg(not(feature = "tui"))]
+use plain::{Attribute, Color, Stylize};
 
 // ============================================================================
 // Types and Structs
@@ -43,9 +48,18 @@ pub struct DoctorSummary {
     pub errors: usize,
     pub fixable: usize,
     pub fixed: usize,
+    /// Fixes that were attempted during this run, in the order they ran
+    pub applied_fixes: Vec<AppliedFix>,
     pub total_duration_ms: u64,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedFix {
+    pub check_name: String,
+    pub action: String,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct DoctorReport {
     pub version: String,
@@ -56,17 +70,81 @@ pub struct DoctorReport {
 
 pub struct DoctorOptions {
     pub fix_mode: bool,
+    /// Ask before applying each fix instead of applying everything
+    pub interactive: bool,
+    /// Only fix issues from these checks (empty means every fixable check)
+    pub fix_only: Vec<String>,
     pub verbose: bool,
     pub json_output: bool,
+    /// Suppress all terminal output (used when running inside the TUI)
+    pub quiet: bool,
+}
+
+/// Checks whose issues `--fix` can repair, the names `--fix-only` accepts
+const FIXABLE_CHECKS: &[&str] = &[
+    "activation_status",
+    "broken",
+    "config_schema",
+    "coverage",
+    "file_integrity",
+    "foreign_symlinks",
+    "invalid",
+    "manifest",
+    "orphaned",
+    "state_layout",
+    "tracking",
+];
+
+/// Fail on a `--fix-only` name that isn't a fixable check, so a typo isn't
+/// reported as "nothing to fix".
+pub fn validate_fix_only(names: &[String]) -> Result<()> {
+    for name in names {
+        if !FIXABLE_CHECKS.contains(&name.as_str()) {
+            anyhow::bail!(
+                "Unknown check '{name}'. Checks that can be fixed: {}",
+                FIXABLE_CHECKS.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Answer to the per-fix prompt in interactive mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixAnswer {
+    Yes,
+    No,
+    All,
+}
+
+impl FixAnswer {
+    /// Parse a y/n/a answer. Anything unrecognized is treated as "no".
+    fn parse(input: &str) -> Self {
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => Self::Yes,
+            "a" | "all" => Self::All,
+            _ => Self::No,
+        }
+    }
 }
 
 pub struct Doctor {
     config: Config,
     options: DoctorOptions,
     results: Vec<ValidationResult>,
+    applied_fixes: Vec<AppliedFix>,
     start_time: Instant,
 }
 
+/// Timeout for individual network probes (TCP connect, API calls, SSH handshake)
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Fetches slower than this are reported as a warning
+const SLOW_FETCH_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Pre-flight checks block a sync when less than this much space is free (100 MB)
+const MIN_FREE_SPACE_KB: u64 = 100 * 1024;
+
 // ============================================================================
 // Check Categories
 // ============================================================================
@@ -93,6 +171,11 @@ const CATEGORIES: &[CheckCategory] = &[
         icon: "📦",
         description: "Git repository status",
     },
+    CheckCategory {
+        name: "Network",
+        icon: "🌐",
+        description: "Remote reachability and authentication",
+    },
     CheckCategory {
         name: "Profiles",
         icon: "👤",
@@ -103,6 +186,11 @@ const CATEGORIES: &[CheckCategory] = &[
         icon: "🔗",
         description: "Symlink tracking and validity",
     },
+    CheckCategory {
+        name: "Conflicts",
+        icon: "🧩",
+        description: "Other dotfile managers",
+    },
     CheckCategory {
         name: "Backups",
         icon: "💾",
@@ -113,12 +201,51 @@ const CATEGORIES: &[CheckCategory] = &[
         icon: "📁",
         description: "Filesystem permissions and space",
     },
+    CheckCategory {
+        name: "Plugins",
+        icon: "🔌",
+        description: "Checks provided by plugins",
+    },
 ];
 
 // ============================================================================
 // Output Helpers
 // ============================================================================
 
+/// Uncolored stand-ins for crossterm's styling, for builds without the TUI
+#[cfg(not(feature = "tui"))]
+mod plain {
+    #[derive(Clone, Copy)]
+    pub enum Color {
+        Blue,
+        Cyan,
+        DarkCyan,
+        DarkGrey,
+        Green,
+        Red,
+        White,
+        Yellow,
+    }
+
+    #[derive(Clone, Copy)]
+    pub enum Attribute {
+        Bold,
+    }
+
+    pub trait Stylize: Sized {
+        fn with(self, _color: Color) -> Self {
+            self
+        }
+
+        fn attribute(self, _attribute: Attribute) -> Self {
+            self
+        }
+    }
+
+    impl Stylize for &str {}
+    impl Stylize for String {}
+}
+
 fn print_header(title: &str) {
     let width = 60;
     let padding = (width - title.len() - 2) / 2;
@@ -249,7 +376,7 @@ fn print_summary(summary: &DoctorSummary, fix_mode: bool) {
         println!(
             "  {} {}",
             "🔧".with(Color::Green),
-            format!("{} issues auto-fixed", summary.fixed).with(Color::Green)
+            format!("{} issues fixed", summary.fixed).with(Color::Green)
         );
     } else if summary.fixable > 0 {
         println!(
@@ -287,6 +414,56 @@ fn print_summary(summary: &DoctorSummary, fix_mode: bool) {
     println!();
 }
 
+/// Extract the SSH user from `user@host:path` or `ssh://user@host/path`.
+fn ssh_user(url: &str) -> Option<&str> {
+    let rest = url.strip_prefix("ssh://").unwrap_or(url);
+    let (user, _) = rest.split_once('@')?;
+    (!user.is_empty() && !user.contains('/')).then_some(user)
+}
+
+/// Verify a GitHub token via the API, returning the login and whether the
+/// configured repository (if any) is visible to the token.
+fn verify_github_token(token: String, repo: Option<(String, String)>) -> Result<(String, bool)> {
+    use crate::github::GitHubClient;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        let client = GitHubClient::new(token);
+        let user = tokio::time::timeout(NETWORK_TIMEOUT, client.get_user())
+            .await
+            .map_err(|_| anyhow::anyhow!("GitHub API request timed out"))??;
+        let repo_ok = match repo {
+            Some((owner, name)) => {
+                tokio::time::timeout(NETWORK_TIMEOUT, client.repo_exists(&owner, &name))
+                    .await
+                    .map_err(|_| anyhow::anyhow!("GitHub API request timed out"))??
+            }
+            None => true,
+        };
+        Ok((user.login, repo_ok))
+    })
+}
+
+fn prompt_fix(issue: &ValidationResult, action: &str) -> Result<FixAnswer> {
+    println!(
+        "  {} {}",
+        status_icon(&issue.status),
+        issue.message.clone().with(Color::White)
+    );
+    print!(
+        "    {} {} ",
+        format!("Fix: {action}?").with(Color::Blue),
+        "[y/n/a]".with(Color::DarkGrey)
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(FixAnswer::parse(&input))
+}
+
 // ============================================================================
 // Doctor Implementation
 // ============================================================================
@@ -298,12 +475,13 @@ impl Doctor {
             config,
             options,
             results: Vec::new(),
+            applied_fixes: Vec::new(),
             start_time: Instant::now(),
         }
     }
 
     pub fn run_diagnostics(&mut self) -> Result<DoctorReport> {
-        if !self.options.json_output {
+        if self.is_printing() {
             print_header("DotState Doctor");
             println!(
                 "  {} Running comprehensive diagnostics...",
@@ -314,7 +492,7 @@ impl Doctor {
 
         // Run all checks by category
         for (i, category) in CATEGORIES.iter().enumerate() {
-            if !self.options.json_output {
+            if self.is_printing() {
                 print_category_header(category, i, CATEGORIES.len());
             }
 
@@ -322,10 +500,13 @@ impl Doctor {
                 "Environment" => self.check_environment()?,
                 "Configuration" => self.check_configuration()?,
                 "Repository" => self.check_repository()?,
+                "Network" => self.check_network()?,
                 "Profiles" => self.check_profiles()?,
                 "Symlinks" => self.check_symlinks()?,
+                "Conflicts" => self.check_conflicts()?,
                 "Backups" => self.check_backups()?,
                 "Filesystem" => self.check_filesystem()?,
+                "Plugins" => self.check_plugins(),
                 _ => {}
             }
         }
@@ -338,7 +519,7 @@ impl Doctor {
         // Calculate summary
         let summary = self.calculate_summary();
 
-        if !self.options.json_output {
+        if self.is_printing() {
             print_summary(&summary, self.options.fix_mode);
         }
 
@@ -350,6 +531,87 @@ impl Doctor {
         })
     }
 
+    /// Run the fast subset of checks that decide whether a sync can succeed:
+    /// repository validity, remote reachability, free disk space and the
+    /// active profile. Network auth and fetch latency are skipped to keep this
+    /// quick. Returns every result; `Error` results should block the sync.
+    pub fn run_preflight(&mut self) -> Vec<ValidationResult> {
+        let start = Instant::now();
+        if crate::utils::is_git_repo(&self.config.repo_path) {
+            self.add_result(
+                "Repository",
+                "git_repo",
+                "Valid git repository",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            );
+            self.check_remote_reachable();
+        } else {
+            self.add_result(
+                "Repository",
+                "git_repo",
+                &format!(
+                    "{} is not a git repository",
+                    self.config.repo_path.display()
+                ),
+                ValidationStatus::Error,
+                None,
+                Some(vec![
+                    "Set up the repository again from the main menu".to_string()
+                ]),
+                start,
+            );
+        }
+
+        self.check_free_space();
+        if self.config.file_sync_mode {
+            self.check_file_sync_conflicts();
+        }
+
+        let start = Instant::now();
+        match ProfileManifest::load(&self.config.repo_path) {
+            Ok(manifest) => {
+                self.check_repo_version(&manifest);
+                self.check_active_profile_exists(&manifest);
+            }
+            Err(e) => self.add_result(
+                "Profiles",
+                "manifest",
+                &format!("Failed to load manifest: {e}"),
+                ValidationStatus::Error,
+                None,
+                None,
+                start,
+            ),
+        }
+
+        std::mem::take(&mut self.results)
+    }
+
+    /// Run the profile checks only: the manifest, the active profile's and
+    /// the common files in storage, and the hashes recorded at the last sync.
+    /// They read nothing but the repository. Returns every result.
+    pub fn run_profile_checks(&mut self) -> Vec<ValidationResult> {
+        if let Err(e) = self.check_profiles() {
+            self.add_result(
+                "Profiles",
+                "manifest",
+                &format!("{e:#}"),
+                ValidationStatus::Error,
+                None,
+                None,
+                Instant::now(),
+            );
+        }
+        std::mem::take(&mut self.results)
+    }
+
+    fn is_printing(&self) -> bool {
+        !self.options.json_output && !self.options.quiet
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn add_result(
         &mut self,
@@ -372,7 +634,7 @@ impl Doctor {
             duration_ms: start_time.elapsed().as_millis() as u64,
         };
 
-        if !self.options.json_output {
+        if self.is_printing() {
             print_check_result(&result, self.options.verbose);
         }
 
@@ -400,7 +662,7 @@ impl Doctor {
             .iter()
             .filter(|r| r.fixable && r.status != ValidationStatus::Pass)
             .count();
-        let fixed = if self.options.fix_mode { fixable } else { 0 };
+        let fixed = self.applied_fixes.iter().filter(|f| f.success).count();
 
         DoctorSummary {
             total_checks: self.results.len(),
@@ -409,6 +671,7 @@ impl Doctor {
             errors,
             fixable,
             fixed,
+            applied_fixes: self.applied_fixes.clone(),
             total_duration_ms: self.start_time.elapsed().as_millis() as u64,
         }
     }
@@ -593,6 +856,68 @@ impl Doctor {
             );
         }
 
+        // Check for unknown keys and invalid values
+        let start = Instant::now();
+        if let Ok(content) = fs::read_to_string(&config_path) {
+            let issues = crate::utils::config_validation::validate_config(&content);
+            if issues.is_empty() {
+                self.add_result(
+                    "Configuration",
+                    "config_schema",
+                    "Configuration keys and values are valid",
+                    ValidationStatus::Pass,
+                    None,
+                    None,
+                    start,
+                );
+            } else {
+                self.add_result(
+                    "Configuration",
+                    "config_schema",
+                    &format!("{} problems in the configuration file", issues.len()),
+                    ValidationStatus::Warning,
+                    Some("Repair config file"),
+                    Some(issues.iter().map(ToString::to_string).collect()),
+                    start,
+                );
+            }
+        }
+
+        // Check for state files left in the config directory by older versions
+        let start = Instant::now();
+        let legacy = crate::utils::state_migration::pending_legacy_files();
+        if legacy.is_empty() {
+            self.add_result(
+                "Configuration",
+                "state_layout",
+                "State and caches are in their XDG directories",
+                ValidationStatus::Pass,
+                None,
+                if self.options.verbose {
+                    Some(vec![
+                        format!("Data: {}", crate::utils::get_data_dir().display()),
+                        format!("Cache: {}", crate::utils::get_cache_dir().display()),
+                    ])
+                } else {
+                    None
+                },
+                start,
+            );
+        } else {
+            self.add_result(
+                "Configuration",
+                "state_layout",
+                &format!(
+                    "{} state files left in the config directory by an older version",
+                    legacy.len()
+                ),
+                ValidationStatus::Warning,
+                Some("Move legacy state files"),
+                Some(legacy.iter().map(|p| p.display().to_string()).collect()),
+                start,
+            );
+        }
+
         // Check repository path
         let start = Instant::now();
         if self.config.repo_path.exists() {
@@ -904,114 +1229,540 @@ impl Doctor {
     }
 
     // ========================================================================
-    // Profile Checks
+    // Network Checks
     // ========================================================================
 
-    fn check_profiles(&mut self) -> Result<()> {
-        let start = Instant::now();
+    fn check_network(&mut self) -> Result<()> {
+        if !crate::utils::is_git_repo(&self.config.repo_path) {
+            return Ok(());
+        }
 
-        match ProfileManifest::load(&self.config.repo_path) {
-            Ok(manifest) => {
-                self.add_result(
-                    "Profiles",
-                    "manifest",
-                    &format!(
-                        "Manifest loaded: {} profiles, {} common files",
-                        manifest.profiles.len(),
-                        manifest.common.synced_files.len()
-                    ),
-                    ValidationStatus::Pass,
-                    None,
-                    if self.options.verbose {
-                        Some(
-                            manifest
-                                .profiles
-                                .iter()
-                                .map(|p| format!("  {} ({} files)", p.name, p.synced_files.len()))
-                                .collect(),
-                        )
-                    } else {
-                        None
-                    },
-                    start,
-                );
+        if let Some((url, host, port)) = self.check_remote_reachable() {
+            self.check_remote_auth(&url, &host, port)?;
+            self.check_fetch_latency()?;
+        }
 
-                // Check active profile exists
-                if !self.config.active_profile.is_empty() {
-                    let start = Instant::now();
-                    if let Some(profile) = manifest
-                        .profiles
-                        .iter()
-                        .find(|p| p.name == self.config.active_profile)
-                    {
-                        self.add_result(
-                            "Profiles",
-                            "active_profile_exists",
-                            "Active profile exists in manifest",
-                            ValidationStatus::Pass,
-                            None,
-                            None,
-                            start,
-                        );
+        Ok(())
+    }
 
-                        // Check profile files exist in storage
-                        self.check_profile_files(&profile.name, &profile.synced_files)?;
-                    } else {
-                        self.add_result(
-                            "Profiles",
-                            "active_profile_exists",
-                            &format!(
-                                "Active profile '{}' not found in manifest",
-                                self.config.active_profile
-                            ),
-                            ValidationStatus::Error,
-                            None,
-                            None,
-                            start,
-                        );
-                    }
-                }
+    /// Resolve and connect to the `origin` host.
+    ///
+    /// Returns the remote URL, host and port when the remote is reachable over
+    /// the network, or `None` if it isn't (or there is no network remote).
+    fn check_remote_reachable(&mut self) -> Option<(String, String, u16)> {
+        let start = Instant::now();
+        let Some(url) = self.origin_url() else {
+            self.add_result(
+                "Network",
+                "remote_endpoint",
+                "No remote configured, network checks skipped",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            );
+            return None;
+        };
 
-                // Check common files exist
-                if !manifest.common.synced_files.is_empty() {
-                    self.check_common_files(&manifest.common.synced_files)?;
-                }
-            }
+        let Some((host, port)) = crate::git::remote_endpoint(&url) else {
+            self.add_result(
+                "Network",
+                "remote_endpoint",
+                "Remote is a local path, network checks skipped",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            );
+            return None;
+        };
+
+        // DNS resolution
+        let addrs: Vec<SocketAddr> = match (host.as_str(), port).to_socket_addrs() {
+            Ok(addrs) => addrs.collect(),
             Err(e) => {
                 self.add_result(
-                    "Profiles",
-                    "manifest",
-                    &format!("Failed to load manifest: {e}"),
+                    "Network",
+                    "remote_dns",
+                    &format!("Cannot resolve host '{host}'"),
                     ValidationStatus::Error,
-                    Some("Rebuild manifest"),
                     None,
+                    Some(vec![
+                        format!("Error: {e}"),
+                        "Check your network connection and DNS settings".to_string(),
+                    ]),
                     start,
                 );
+                return None;
             }
+        };
+        self.add_result(
+            "Network",
+            "remote_dns",
+            &format!("Resolved {host} ({} addresses)", addrs.len()),
+            ValidationStatus::Pass,
+            None,
+            None,
+            start,
+        );
+
+        // TCP reachability
+        let start = Instant::now();
+        let reachable = addrs
+            .iter()
+            .any(|addr| TcpStream::connect_timeout(addr, NETWORK_TIMEOUT).is_ok());
+        if !reachable {
+            self.add_result(
+                "Network",
+                "remote_reachable",
+                &format!("Cannot connect to {host}:{port}"),
+                ValidationStatus::Error,
+                None,
+                Some(vec![
+                    "Connection timed out or was refused".to_string(),
+                    "A firewall or proxy may be blocking access".to_string(),
+                ]),
+                start,
+            );
+            return None;
         }
+        self.add_result(
+            "Network",
+            "remote_reachable",
+            &format!("Remote reachable: {host}:{port}"),
+            ValidationStatus::Pass,
+            None,
+            None,
+            start,
+        );
 
-        Ok(())
+        Some((url, host, port))
     }
 
-    fn check_profile_files(&mut self, profile_name: &str, files: &[String]) -> Result<()> {
+    fn origin_url(&self) -> Option<String> {
+        let output = Command::new("git")
+            .args(["remote", "get-url", "origin"])
+            .current_dir(&self.config.repo_path)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!url.is_empty()).then_some(url)
+    }
+
+    fn check_remote_auth(&mut self, url: &str, host: &str, port: u16) -> Result<()> {
         let start = Instant::now();
-        let profile_path = self.config.repo_path.join(profile_name);
-        let mut missing = Vec::new();
 
-        for file in files {
-            if !profile_path.join(file).exists() {
-                missing.push(file.clone());
-            }
+        if crate::git::is_ssh_url(url) {
+            return self.check_ssh_auth(url, host, port, start);
         }
 
-        if missing.is_empty() {
+        if self.config.repo_mode == RepoMode::Local {
             self.add_result(
-                "Profiles",
-                "profile_files",
-                &format!("All {} profile files exist in storage", files.len()),
+                "Network",
+                "remote_auth",
+                &format!("Using system git credentials for {host}"),
                 ValidationStatus::Pass,
                 None,
-                None,
+                Some(vec![
+                    "Credentials are verified by the fetch check below".to_string()
+                ]),
+                start,
+            );
+            return Ok(());
+        }
+
+        let Some(token) = self.config.get_github_token() else {
+            self.add_result(
+                "Network",
+                "remote_auth",
+                "No GitHub token configured",
+                ValidationStatus::Error,
+                None,
+                Some(vec![
+                    "Set DOTSTATE_GITHUB_TOKEN or re-run setup to provide a token".to_string(),
+                ]),
+                start,
+            );
+            return Ok(());
+        };
+
+        let repo = self
+            .config
+            .github
+            .as_ref()
+            .map(|gh| (gh.owner.clone(), gh.repo.clone()));
+
+        match verify_github_token(token, repo.clone()) {
+            Ok((login, repo_ok)) => {
+                if repo_ok {
+                    self.add_result(
+                        "Network",
+                        "remote_auth",
+                        &format!("GitHub token valid (authenticated as {login})"),
+                        ValidationStatus::Pass,
+                        None,
+                        None,
+                        start,
+                    );
+                } else {
+                    let (owner, name) = repo.unwrap_or_default();
+                    self.add_result(
+                        "Network",
+                        "remote_auth",
+                        &format!("GitHub token cannot access {owner}/{name}"),
+                        ValidationStatus::Error,
+                        None,
+                        Some(vec![
+                            format!("Authenticated as {login}"),
+                            "Grant the token access to this repository".to_string(),
+                        ]),
+                        start,
+                    );
+                }
+            }
+            Err(e) => {
+                let reason = e.to_string().lines().next().unwrap_or_default().to_string();
+                self.add_result(
+                    "Network",
+                    "remote_auth",
+                    "GitHub token rejected",
+                    ValidationStatus::Error,
+                    None,
+                    Some(vec![
+                        reason,
+                        "Check https://github.com/settings/tokens for expiry or revocation"
+                            .to_string(),
+                    ]),
+                    start,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_ssh_auth(&mut self, url: &str, host: &str, port: u16, start: Instant) -> Result<()> {
+        let user = ssh_user(url).unwrap_or("git");
+        let output = Command::new("ssh")
+            .args([
+                "-T",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                &format!("ConnectTimeout={}", NETWORK_TIMEOUT.as_secs()),
+                "-p",
+                &port.to_string(),
+                &format!("{user}@{host}"),
+            ])
+            .stdin(std::process::Stdio::null())
+            .output();
+
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => {
+                self.add_result(
+                    "Network",
+                    "remote_auth",
+                    "Could not run ssh to verify the SSH key",
+                    ValidationStatus::Warning,
+                    None,
+                    Some(vec![format!("Error: {e}")]),
+                    start,
+                );
+                return Ok(());
+            }
+        };
+
+        // Git hosts close the session after the greeting, so the exit code is not
+        // meaningful; look at the message instead.
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let lower = text.to_lowercase();
+        let first_line = text.lines().next().unwrap_or_default().trim().to_string();
+
+        if lower.contains("successfully authenticated")
+            || lower.contains("welcome to gitlab")
+            || lower.contains("logged in as")
+            || lower.contains("authenticated via")
+        {
+            self.add_result(
+                "Network",
+                "remote_auth",
+                &format!("SSH key accepted by {host}"),
+                ValidationStatus::Pass,
+                None,
+                if self.options.verbose {
+                    Some(vec![first_line])
+                } else {
+                    None
+                },
+                start,
+            );
+        } else if lower.contains("permission denied") {
+            self.add_result(
+                "Network",
+                "remote_auth",
+                &format!("SSH key rejected by {host}"),
+                ValidationStatus::Error,
+                None,
+                Some(vec![
+                    first_line,
+                    "Check 'ssh-add -l' and the SSH keys registered with your provider".to_string(),
+                ]),
+                start,
+            );
+        } else if lower.contains("host key verification failed") {
+            self.add_result(
+                "Network",
+                "remote_auth",
+                &format!("Host key for {host} is not trusted"),
+                ValidationStatus::Error,
+                None,
+                Some(vec![format!(
+                    "Run 'ssh {user}@{host}' once to verify and add the host key"
+                )]),
+                start,
+            );
+        } else {
+            self.add_result(
+                "Network",
+                "remote_auth",
+                &format!("Could not verify SSH authentication with {host}"),
+                ValidationStatus::Warning,
+                None,
+                (!first_line.is_empty()).then(|| vec![first_line]),
+                start,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_fetch_latency(&mut self) -> Result<()> {
+        use crate::git::GitManager;
+
+        let start = Instant::now();
+        let git_mgr = match GitManager::open_or_init(&self.config.repo_path) {
+            Ok(mgr) => mgr,
+            Err(e) => {
+                self.add_result(
+                    "Network",
+                    "fetch",
+                    "Could not open repository to test fetch",
+                    ValidationStatus::Warning,
+                    None,
+                    Some(vec![format!("Error: {e}")]),
+                    start,
+                );
+                return Ok(());
+            }
+        };
+
+        let branch = git_mgr
+            .get_current_branch()
+            .unwrap_or_else(|| self.config.default_branch.clone());
+        let token = match self.config.repo_mode {
+            RepoMode::Local => None,
+            RepoMode::GitHub => self.config.get_github_token(),
+        };
+
+        match git_mgr.fetch("origin", &branch, token.as_deref()) {
+            Ok(()) => {
+                let elapsed = start.elapsed();
+                let status = if elapsed > SLOW_FETCH_THRESHOLD {
+                    ValidationStatus::Warning
+                } else {
+                    ValidationStatus::Pass
+                };
+                self.add_result(
+                    "Network",
+                    "fetch",
+                    &format!("Fetched origin/{branch} in {}", format_duration(elapsed)),
+                    status,
+                    None,
+                    None,
+                    start,
+                );
+            }
+            Err(e) => {
+                self.add_result(
+                    "Network",
+                    "fetch",
+                    &format!("Fetch from origin/{branch} failed"),
+                    ValidationStatus::Error,
+                    None,
+                    Some(format!("{e:#}").lines().map(str::to_string).collect()),
+                    start,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Profile Checks
+    // ========================================================================
+
+    fn check_profiles(&mut self) -> Result<()> {
+        let start = Instant::now();
+
+        match ProfileManifest::load(&self.config.repo_path) {
+            Ok(manifest) => {
+                self.add_result(
+                    "Profiles",
+                    "manifest",
+                    &format!(
+                        "Manifest loaded: {} profiles, {} common files",
+                        manifest.profiles.len(),
+                        manifest.common.synced_files.len()
+                    ),
+                    ValidationStatus::Pass,
+                    None,
+                    if self.options.verbose {
+                        Some(
+                            manifest
+                                .profiles
+                                .iter()
+                                .map(|p| format!("  {} ({} files)", p.name, p.synced_files.len()))
+                                .collect(),
+                        )
+                    } else {
+                        None
+                    },
+                    start,
+                );
+
+                self.check_repo_version(&manifest);
+
+                // Check active profile exists, then its files exist in storage
+                if self.check_active_profile_exists(&manifest) {
+                    if let Some(profile) = manifest
+                        .profiles
+                        .iter()
+                        .find(|p| p.name == self.config.active_profile)
+                    {
+                        self.check_profile_files(&profile.name, &profile.synced_files)?;
+                    }
+                }
+
+                // Check common files exist
+                if !manifest.common.synced_files.is_empty() {
+                    self.check_common_files(&manifest.common.synced_files)?;
+                }
+
+                self.check_integrity();
+            }
+            Err(e) => {
+                self.add_result(
+                    "Profiles",
+                    "manifest",
+                    &format!("Failed to load manifest: {e}"),
+                    ValidationStatus::Error,
+                    Some("Rebuild manifest"),
+                    None,
+                    start,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A newer dotstate than this one last wrote the repository; writing to
+    /// it with this one could drop what the newer version added.
+    fn check_repo_version(&mut self, manifest: &ProfileManifest) {
+        let start = Instant::now();
+        match manifest.required_version() {
+            Some(version) => self.add_result(
+                "Profiles",
+                "repo_version",
+                &format!(
+                    "Repository was last written by dotstate {version}, newer than {}",
+                    crate::version_check::current_version()
+                ),
+                ValidationStatus::Error,
+                None,
+                Some(vec![
+                    "Run 'dotstate upgrade' before changing the repository".to_string(),
+                ]),
+                start,
+            ),
+            None => self.add_result(
+                "Profiles",
+                "repo_version",
+                "Repository was written by this or an older dotstate",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            ),
+        }
+    }
+
+    /// Returns true if the active profile is set and present in the manifest.
+    fn check_active_profile_exists(&mut self, manifest: &ProfileManifest) -> bool {
+        if self.config.active_profile.is_empty() {
+            return false;
+        }
+
+        let start = Instant::now();
+        let exists = manifest
+            .profiles
+            .iter()
+            .any(|p| p.name == self.config.active_profile);
+        if exists {
+            self.add_result(
+                "Profiles",
+                "active_profile_exists",
+                "Active profile exists in manifest",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            );
+        } else {
+            self.add_result(
+                "Profiles",
+                "active_profile_exists",
+                &format!(
+                    "Active profile '{}' not found in manifest",
+                    self.config.active_profile
+                ),
+                ValidationStatus::Error,
+                None,
+                None,
+                start,
+            );
+        }
+        exists
+    }
+
+    fn check_profile_files(&mut self, profile_name: &str, files: &[String]) -> Result<()> {
+        let start = Instant::now();
+        let profile_path = self.config.repo_path.join(profile_name);
+        let mut missing = Vec::new();
+
+        for file in files {
+            if !profile_path.join(file).exists() {
+                missing.push(file.clone());
+            }
+        }
+
+        if missing.is_empty() {
+            self.add_result(
+                "Profiles",
+                "profile_files",
+                &format!("All {} profile files exist in storage", files.len()),
+                ValidationStatus::Pass,
+                None,
+                None,
                 start,
             );
         } else {
@@ -1065,6 +1816,133 @@ impl Doctor {
         Ok(())
     }
 
+    /// Tracked files whose content no longer matches the hash recorded at
+    /// the last sync, though git sees no pending change
+    fn check_integrity(&mut self) {
+        let start = Instant::now();
+        match crate::utils::integrity::verify(&self.config.repo_path) {
+            Ok(issues) if issues.is_empty() => {
+                self.add_result(
+                    "Profiles",
+                    "file_integrity",
+                    "Tracked files match the hashes recorded at the last sync",
+                    ValidationStatus::Pass,
+                    None,
+                    None,
+                    start,
+                );
+            }
+            Ok(issues) => {
+                self.add_result(
+                    "Profiles",
+                    "file_integrity",
+                    &format!(
+                        "{} tracked file(s) changed outside dotstate (direct commits or corruption)",
+                        issues.len()
+                    ),
+                    ValidationStatus::Warning,
+                    Some("Record current file hashes"),
+                    Some(
+                        issues
+                            .iter()
+                            .take(5)
+                            .map(crate::utils::integrity::IntegrityIssue::describe)
+                            .collect(),
+                    ),
+                    start,
+                );
+            }
+            Err(e) => {
+                self.add_result(
+                    "Profiles",
+                    "file_integrity",
+                    &format!("Failed to verify tracked files: {e:#}"),
+                    ValidationStatus::Warning,
+                    None,
+                    None,
+                    start,
+                );
+            }
+        }
+    }
+
+    // ========================================================================
+    // Plugin Checks
+    // ========================================================================
+
+    /// Run the checks of plugins with the doctor capability. Each check is
+    /// named `<plugin>/<check>`.
+    fn check_plugins(&mut self) {
+        use crate::utils::plugins::{self, CheckStatus};
+
+        let start = Instant::now();
+        let installed = plugins::discover();
+        if installed.is_empty() {
+            self.add_result(
+                "Plugins",
+                "plugins",
+                "No plugins installed",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            );
+            return;
+        }
+
+        for plugin in installed {
+            let start = Instant::now();
+            let name = plugin.name();
+            if let Err(e) = &plugin.description {
+                self.add_result(
+                    "Plugins",
+                    &name,
+                    &format!("Plugin {} is broken: {e}", plugin.path.display()),
+                    ValidationStatus::Warning,
+                    None,
+                    None,
+                    start,
+                );
+                continue;
+            }
+            if !plugin.provides(plugins::DOCTOR_CAPABILITY) {
+                continue;
+            }
+            match plugin.doctor(&self.config.repo_path, &self.config.active_profile) {
+                Ok(checks) => {
+                    for check in checks {
+                        let status = match check.status {
+                            CheckStatus::Pass => ValidationStatus::Pass,
+                            CheckStatus::Warning => ValidationStatus::Warning,
+                            CheckStatus::Error => ValidationStatus::Error,
+                        };
+                        let details = (!check.details.is_empty()).then_some(check.details);
+                        self.add_result(
+                            "Plugins",
+                            &format!("{name}/{}", check.name),
+                            &check.message,
+                            status,
+                            None,
+                            details,
+                            start,
+                        );
+                    }
+                }
+                Err(e) => {
+                    self.add_result(
+                        "Plugins",
+                        &name,
+                        &format!("Plugin checks failed: {e:#}"),
+                        ValidationStatus::Warning,
+                        None,
+                        None,
+                        start,
+                    );
+                }
+            }
+        }
+    }
+
     // ========================================================================
     // Symlink Checks
     // ========================================================================
@@ -1287,7 +2165,7 @@ impl Doctor {
                             ));
                         }
                     }
-                } else {
+                } else if !tracked.block {
                     // File exists but is not a symlink
                     invalid.push(format!(
                         "{} exists but is not a symlink",
@@ -1348,6 +2226,165 @@ impl Doctor {
         Ok(())
     }
 
+    // ========================================================================
+    // Conflict Checks
+    // ========================================================================
+
+    fn check_conflicts(&mut self) -> Result<()> {
+        let start = Instant::now();
+        let home = crate::utils::get_home_dir();
+
+        let detected = detect_managers(&home);
+        if detected.is_empty() {
+            self.add_result(
+                "Conflicts",
+                "other_managers",
+                "No other dotfile managers detected",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            );
+        } else {
+            let names: Vec<&str> = detected.iter().map(|d| d.manager.name()).collect();
+            let mut details: Vec<String> = detected
+                .iter()
+                .map(|d| format!("{}: {}", d.manager.name(), d.evidence.display()))
+                .collect();
+            details.push(
+                "Files managed by both tools are overwritten by whichever runs last".to_string(),
+            );
+            details.push(
+                "Add files through Manage Files to adopt them, then release them from the other tool"
+                    .to_string(),
+            );
+            self.add_result(
+                "Conflicts",
+                "other_managers",
+                &format!("Other dotfile managers detected: {}", names.join(", ")),
+                ValidationStatus::Warning,
+                None,
+                Some(details),
+                start,
+            );
+        }
+
+        let start = Instant::now();
+        let foreign = self.tracked_foreign_symlinks(&home);
+        if foreign.is_empty() {
+            self.add_result(
+                "Conflicts",
+                "foreign_symlinks",
+                "No tracked files are linked by another tool",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            );
+        } else {
+            let details = foreign
+                .iter()
+                .map(|f| match f.manager {
+                    Some(manager) => format!(
+                        "~/{} -> {} ({}; release with `{}`)",
+                        f.relative_path,
+                        f.target.display(),
+                        manager.name(),
+                        manager.release_hint()
+                    ),
+                    None => format!("~/{} -> {}", f.relative_path, f.target.display()),
+                })
+                .collect();
+            self.add_result(
+                "Conflicts",
+                "foreign_symlinks",
+                &format!(
+                    "{} tracked files are symlinked outside the repository",
+                    foreign.len()
+                ),
+                ValidationStatus::Warning,
+                Some("Adopt foreign symlinks"),
+                Some(details),
+                start,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Tracked files of the active profile (including common) that are
+    /// symlinked somewhere other than the repository.
+    fn tracked_foreign_symlinks(&self, home: &std::path::Path) -> Vec<ForeignSymlink> {
+        if self.config.active_profile.is_empty() {
+            return Vec::new();
+        }
+        let Ok(manifest) = ProfileManifest::load(&self.config.repo_path) else {
+            return Vec::new();
+        };
+        let Ok(resolved) = manifest.resolve_files(&self.config.active_profile) else {
+            return Vec::new();
+        };
+        let tracked: Vec<String> = resolved.into_iter().map(|f| f.relative_path).collect();
+        find_foreign_symlinks(home, &self.config.repo_path, &tracked)
+    }
+
+    /// Copy the live content behind each foreign symlink into the repository
+    /// and replace the symlink with one managed by `DotState`.
+    fn adopt_foreign_symlinks(&self) -> Result<bool> {
+        use crate::services::ProfileService;
+
+        let home = crate::utils::get_home_dir();
+        let foreign = self.tracked_foreign_symlinks(&home);
+        if foreign.is_empty() {
+            return Ok(false);
+        }
+
+        let manifest = ProfileManifest::load(&self.config.repo_path)?;
+        let resolved = manifest.resolve_files(&self.config.active_profile)?;
+
+        for link in &foreign {
+            let Some(file) = resolved
+                .iter()
+                .find(|f| f.relative_path == link.relative_path)
+            else {
+                continue;
+            };
+            // Skip broken links: there is nothing to adopt
+            if !link.target.exists() {
+                continue;
+            }
+
+            let dest = self
+                .config
+                .repo_path
+                .join(&file.source_profile)
+                .join(&file.relative_path);
+            if dest.is_dir() {
+                fs::remove_dir_all(&dest)?;
+            } else if dest.symlink_metadata().is_ok() {
+                fs::remove_file(&dest)?;
+            }
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if link.target.is_dir() {
+                crate::file_manager::copy_dir_all(&link.target, &dest)?;
+            } else {
+                fs::copy(&link.target, &dest)?;
+            }
+
+            fs::remove_file(home.join(&link.relative_path))?;
+        }
+
+        // Recreate the now-missing links, pointing at the repository
+        ProfileService::ensure_profile_symlinks(
+            &self.config.repo_path,
+            &self.config.active_profile,
+            self.config.backup_enabled,
+        )?;
+        Ok(true)
+    }
+
     // ========================================================================
     // Backup Checks
     // ========================================================================
@@ -1381,9 +2418,7 @@ impl Doctor {
 
         // Check backup directory - it's in ~/.dotstate-backups
         let start = Instant::now();
-        let backup_dir = dirs::home_dir()
-            .map(|h| h.join(".dotstate-backups"))
-            .unwrap_or_default();
+        let backup_dir = crate::utils::BackupManager::default_root();
 
         if !backup_dir.exists() {
             let message = if self.config.backup_enabled {
@@ -1517,9 +2552,59 @@ impl Doctor {
         // Check disk space
         self.check_disk_space()?;
 
+        if self.config.file_sync_mode {
+            self.check_file_sync_conflicts();
+        }
+
         Ok(())
     }
 
+    /// Conflict copies left in the repository by a file-sync service
+    fn check_file_sync_conflicts(&mut self) {
+        let start = Instant::now();
+        match crate::utils::file_sync::find_conflicts(&self.config.repo_path) {
+            Ok(conflicts) if conflicts.is_empty() => self.add_result(
+                "Filesystem",
+                "file_sync_conflicts",
+                "No file-sync conflict copies",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            ),
+            Ok(conflicts) => {
+                let mut details: Vec<String> = conflicts
+                    .iter()
+                    .map(|c| format!("{} (copy of {})", c.path, c.original))
+                    .collect();
+                details
+                    .push("Keep one version of each with 'dotstate file-sync resolve'".to_string());
+                self.add_result(
+                    "Filesystem",
+                    "file_sync_conflicts",
+                    &format!(
+                        "{} conflict cop{} left by the file-sync service",
+                        conflicts.len(),
+                        if conflicts.len() == 1 { "y" } else { "ies" }
+                    ),
+                    ValidationStatus::Error,
+                    None,
+                    Some(details),
+                    start,
+                );
+            }
+            Err(e) => self.add_result(
+                "Filesystem",
+                "file_sync_conflicts",
+                &format!("Could not look for conflict copies: {e:#}"),
+                ValidationStatus::Warning,
+                None,
+                None,
+                start,
+            ),
+        }
+    }
+
     fn check_write_permissions(&mut self) -> Result<()> {
         let start = Instant::now();
         let test_file = self.config.repo_path.join(".doctor_write_test");
@@ -1654,23 +2739,87 @@ impl Doctor {
         Ok(())
     }
 
+    /// Check there is enough free space left for a sync to write objects.
+    ///
+    /// Unlike `check_disk_space`, which warns by usage percentage, this looks
+    /// at the absolute space available and only fails when it is critically low.
+    fn check_free_space(&mut self) {
+        let start = Instant::now();
+        let output = Command::new("df")
+            .args(["-Pk", self.config.repo_path.to_str().unwrap_or(".")])
+            .output();
+
+        // POSIX format: Filesystem 1024-blocks Used Available Capacity Mounted-on
+        let available_kb = output.ok().and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .nth(1)
+                .and_then(|line| line.split_whitespace().nth(3))
+                .and_then(|kb| kb.parse::<u64>().ok())
+        });
+
+        match available_kb {
+            Some(kb) if kb < MIN_FREE_SPACE_KB => self.add_result(
+                "Filesystem",
+                "disk_space",
+                &format!("Only {} MB of disk space left", kb / 1024),
+                ValidationStatus::Error,
+                None,
+                Some(vec![
+                    "The sync could run out of space while writing to the repository".to_string(),
+                    "Free up some disk space and try again".to_string(),
+                ]),
+                start,
+            ),
+            Some(kb) => self.add_result(
+                "Filesystem",
+                "disk_space",
+                &format!("{} MB of disk space available", kb / 1024),
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            ),
+            // Don't block a sync just because df is unavailable
+            None => self.add_result(
+                "Filesystem",
+                "disk_space",
+                "Could not determine disk space",
+                ValidationStatus::Pass,
+                None,
+                None,
+                start,
+            ),
+        }
+    }
+
     // ========================================================================
     // Fix Implementation
     // ========================================================================
 
     fn apply_fixes(&mut self) -> Result<()> {
+        let fix_only = &self.options.fix_only;
         let fixable: Vec<ValidationResult> = self
             .results
             .iter()
             .filter(|r| r.fixable && r.status != ValidationStatus::Pass)
+            .filter(|r| fix_only.is_empty() || fix_only.contains(&r.check_name))
             .cloned()
             .collect();
 
         if fixable.is_empty() {
+            if self.is_printing() && !fix_only.is_empty() {
+                println!();
+                println!(
+                    "  {} {}",
+                    "💡".with(Color::Blue),
+                    format!("Nothing to fix for: {}", fix_only.join(", ")).with(Color::Blue)
+                );
+            }
             return Ok(());
         }
 
-        if !self.options.json_output {
+        if self.is_printing() {
             println!();
             println!(
                 "{} {} {}",
@@ -1683,25 +2832,80 @@ impl Doctor {
             println!("{}", "─".repeat(50).with(Color::DarkGrey));
         }
 
+        let mut apply_all = !self.options.interactive;
+
         for issue in fixable {
-            if let Some(action) = &issue.fix_action {
-                let success = self.apply_single_fix(action)?;
-                if !self.options.json_output {
-                    let icon = if success {
-                        "✓".with(Color::Green).to_string()
-                    } else {
-                        "✗".with(Color::Red).to_string()
-                    };
-                    println!("  {icon} {action}");
+            let Some(action) = &issue.fix_action else {
+                continue;
+            };
+
+            // Several checks share the same fix; only run it once
+            if self
+                .applied_fixes
+                .iter()
+                .any(|f| f.action == *action && f.success)
+            {
+                continue;
+            }
+
+            if !apply_all {
+                match prompt_fix(&issue, action)? {
+                    FixAnswer::Yes => {}
+                    FixAnswer::No => {
+                        println!(
+                            "  {} {}",
+                            "-".with(Color::DarkGrey),
+                            "Skipped".with(Color::DarkGrey)
+                        );
+                        continue;
+                    }
+                    FixAnswer::All => apply_all = true,
                 }
             }
+
+            let success = self.apply_fix(&issue)?;
+
+            if self.is_printing() {
+                let icon = if success {
+                    "✓".with(Color::Green).to_string()
+                } else {
+                    "✗".with(Color::Red).to_string()
+                };
+                println!("  {icon} {action}");
+            }
         }
 
         Ok(())
     }
 
+    /// Apply the fix for a single result and record it as applied.
+    ///
+    /// Returns `Ok(false)` if the result has no fix or the fix could not be applied.
+    pub fn apply_fix(&mut self, result: &ValidationResult) -> Result<bool> {
+        let Some(action) = &result.fix_action else {
+            return Ok(false);
+        };
+        let success = self.apply_single_fix(action)?;
+        self.applied_fixes.push(AppliedFix {
+            check_name: result.check_name.clone(),
+            action: action.clone(),
+            success,
+        });
+        Ok(success)
+    }
+
     fn apply_single_fix(&mut self, action: &str) -> Result<bool> {
         match action {
+            "Repair config file" => {
+                let config_path = crate::utils::get_config_path();
+                crate::utils::config_validation::repair_config_file(&config_path)?;
+                self.config = Config::load_or_create(&config_path)?;
+                Ok(true)
+            }
+            "Move legacy state files" => {
+                crate::utils::state_migration::migrate_legacy_state()?;
+                Ok(crate::utils::state_migration::pending_legacy_files().is_empty())
+            }
             "Sync activation state" => {
                 let mut config = self.config.clone();
                 config.profile_activated = false;
@@ -1731,6 +2935,11 @@ impl Doctor {
                     Ok(true)
                 }
             }
+            "Adopt foreign symlinks" => self.adopt_foreign_symlinks(),
+            "Record current file hashes" => {
+                crate::utils::integrity::record(&self.config.repo_path)?;
+                Ok(true)
+            }
             "Rebuild manifest" => {
                 // Re-scan filesystem and rebuild manifest
                 let _ = ProfileManifest::load_or_backfill(&self.config.repo_path)?;
@@ -1750,9 +2959,44 @@ impl Doctor {
             config,
             DoctorOptions {
                 fix_mode,
+                interactive: false,
+                fix_only: Vec::new(),
                 verbose: false,
                 json_output: false,
+                quiet: false,
             },
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fix_answer_parse() {
+        assert_eq!(FixAnswer::parse("y"), FixAnswer::Yes);
+        assert_eq!(FixAnswer::parse(" YES \n"), FixAnswer::Yes);
+        assert_eq!(FixAnswer::parse("a"), FixAnswer::All);
+        assert_eq!(FixAnswer::parse("all"), FixAnswer::All);
+        assert_eq!(FixAnswer::parse("n"), FixAnswer::No);
+        assert_eq!(FixAnswer::parse(""), FixAnswer::No);
+        assert_eq!(FixAnswer::parse("maybe"), FixAnswer::No);
+    }
+
+    #[test]
+    fn test_validate_fix_only() {
+        assert!(validate_fix_only(&[]).is_ok());
+        assert!(validate_fix_only(&["tracking".to_string(), "manifest".to_string()]).is_ok());
+        let err = validate_fix_only(&["trackng".to_string()]).unwrap_err();
+        assert!(err.to_string().starts_with("Unknown check 'trackng'"));
+        assert!(err.to_string().contains("tracking"));
+    }
+
+    #[test]
+    fn test_ssh_user() {
+        assert_eq!(ssh_user("git@github.com:user/repo.git"), Some("git"));
+        assert_eq!(ssh_user("ssh://deploy@host:2222/repo.git"), Some("deploy"));
+        assert_eq!(ssh_user("ssh://host/repo.git"), None);
+    }
+}
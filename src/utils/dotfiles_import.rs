@@ -0,0 +1,306 @@
+//! Cherry-picking files from someone else's public dotfiles repository.
+//!
+//! The Import screen takes a GitHub repository (`owner/repo` or its URL),
+//! makes a shallow clone of it in the cache directory and lists its files.
+//! An imported file is copied into the home directory with a comment saying
+//! where it came from, then synced to the active profile like any other
+//! file. Nothing in the home directory is ever replaced.
+
+use anyhow::{bail, Context, Result};
+use git2::build::RepoBuilder;
+use git2::FetchOptions;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Most files listed from one repository
+pub const MAX_FILES: usize = 5000;
+
+/// A public GitHub repository to import from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub owner: String,
+    pub repo: String,
+}
+
+impl Source {
+    /// Parse `owner/repo`, `github.com/owner/repo` or a GitHub URL (HTTPS or
+    /// SSH, with or without `.git` and a trailing path)
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let rest = input
+            .strip_prefix("git@github.com:")
+            .or_else(|| {
+                let without_scheme = input
+                    .strip_prefix("https://")
+                    .or_else(|| input.strip_prefix("http://"))
+                    .unwrap_or(input);
+                without_scheme
+                    .strip_prefix("www.github.com/")
+                    .or_else(|| without_scheme.strip_prefix("github.com/"))
+            })
+            .unwrap_or(input);
+        if rest.contains("://") || rest.contains(':') {
+            bail!("Only GitHub repositories can be imported from (e.g. owner/dotfiles)");
+        }
+        let mut parts = rest.split('/').filter(|part| !part.is_empty());
+        let (Some(owner), Some(repo)) = (parts.next(), parts.next()) else {
+            bail!("Enter a GitHub repository as owner/repo or its URL");
+        };
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        let valid = |part: &str| {
+            !part.is_empty()
+                && part != "."
+                && part != ".."
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        if !valid(owner) || !valid(repo) {
+            bail!("'{owner}/{repo}' isn't a valid GitHub repository name");
+        }
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// Web URL of the repository, used in attribution comments
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("https://github.com/{}/{}", self.owner, self.repo)
+    }
+
+    /// Where the repository is cloned
+    #[must_use]
+    pub fn checkout_dir(&self) -> PathBuf {
+        crate::utils::get_cache_dir()
+            .join("imports")
+            .join(&self.owner)
+            .join(&self.repo)
+    }
+}
+
+/// Make a fresh shallow clone of the repository in `dest`, replacing an
+/// earlier one
+pub fn fetch(source: &Source, dest: &Path) -> Result<()> {
+    if dest.exists() {
+        fs::remove_dir_all(dest)
+            .with_context(|| format!("Failed to remove the old clone at {}", dest.display()))?;
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let url = format!("{}.git", source.url());
+    let mut fetch_opts = FetchOptions::new();
+    fetch_opts.depth(1);
+    fetch_opts.proxy_options(crate::utils::proxy::git_proxy_options(&url));
+    RepoBuilder::new()
+        .fetch_options(fetch_opts)
+        .clone(&url, dest)
+        .map_err(|e| anyhow::anyhow!("Failed to clone {}: {}", source.url(), e.message()))?;
+    Ok(())
+}
+
+/// Files in the clone at `checkout`, relative and sorted, leaving out `.git`
+/// and symlinks. At most [`MAX_FILES`].
+pub fn list_files(checkout: &Path) -> Result<Vec<String>> {
+    fn walk(dir: &Path, root: &Path, files: &mut Vec<String>) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            if files.len() >= MAX_FILES {
+                return Ok(());
+            }
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if entry.file_name() != ".git" {
+                    walk(&path, root, files)?;
+                }
+            } else if file_type.is_file() {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    files.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    walk(checkout, checkout, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// Where a file of the repository most likely goes in the home directory.
+/// Stow-style packages (`tmux/.tmux.conf`) lose their package directory and
+/// top-level files named without the dot (`zshrc`) get it back when that
+/// makes a known dotfile.
+#[must_use]
+pub fn suggest_destination(path: &str) -> String {
+    let mut parts = path.splitn(2, '/');
+    let first = parts.next().unwrap_or_default();
+    match parts.next() {
+        Some(rest) if !first.starts_with('.') && rest.starts_with('.') => rest.to_string(),
+        Some(_) => path.to_string(),
+        None => {
+            let dotted = format!(".{first}");
+            let known = crate::dotfile_candidates::get_default_dotfile_paths().contains(&dotted);
+            if !first.starts_with('.') && known {
+                dotted
+            } else {
+                path.to_string()
+            }
+        }
+    }
+}
+
+/// Line comment marker for the file at `path`, or `None` when its format
+/// has none (JSON, markup)
+#[must_use]
+pub fn comment_prefix(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    if name.ends_with("vimrc") || extension == "vim" {
+        return Some("\"");
+    }
+    match extension.as_str() {
+        "json" | "md" | "markdown" | "html" | "xml" | "plist" | "css" | "svg" => None,
+        "lua" | "hs" | "sql" => Some("--"),
+        "el" | "lisp" | "scm" => Some(";;"),
+        "ini" => Some(";"),
+        "js" | "ts" | "jsonc" | "json5" | "kdl" | "rs" | "go" | "c" | "h" => Some("//"),
+        _ => Some("#"),
+    }
+}
+
+/// `content` with a comment crediting `source`, after a shebang line if
+/// there is one
+#[must_use]
+pub fn with_attribution(content: &str, path: &str, source: &Source) -> String {
+    let Some(prefix) = comment_prefix(path) else {
+        return content.to_string();
+    };
+    let line = format!(
+        "{prefix} Imported from {} ({path}) with dotstate\n",
+        source.url()
+    );
+    if content.starts_with("#!") {
+        let (shebang, rest) = content.split_once('\n').unwrap_or((content, ""));
+        format!("{shebang}\n{line}{rest}")
+    } else {
+        format!("{line}{content}")
+    }
+}
+
+/// Check that `destination` is a plain path inside the home directory
+pub fn validate_destination(destination: &str) -> Result<()> {
+    let path = Path::new(destination);
+    if destination.trim().is_empty() {
+        bail!("Enter where the file goes, relative to your home directory");
+    }
+    if path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        bail!("The path must be relative to your home directory, without '.' or '..'");
+    }
+    Ok(())
+}
+
+/// Copy `path` from the clone at `checkout` to `destination` in `home`,
+/// crediting `source` in text files. Fails when the destination exists.
+/// Returns the written path.
+pub fn import(
+    checkout: &Path,
+    source: &Source,
+    path: &str,
+    home: &Path,
+    destination: &str,
+) -> Result<PathBuf> {
+    validate_destination(destination)?;
+    let target = home.join(destination);
+    if target.symlink_metadata().is_ok() {
+        bail!(
+            "~/{destination} already exists. Pick another path, or move yours out of the way first"
+        );
+    }
+    let bytes = fs::read(checkout.join(path)).with_context(|| format!("Failed to read {path}"))?;
+    let content = match String::from_utf8(bytes) {
+        Ok(text) => with_attribution(&text, path, source).into_bytes(),
+        Err(e) => e.into_bytes(),
+    };
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&target, content).with_context(|| format!("Failed to write ~/{destination}"))?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_source() {
+        let expected = Source {
+            owner: "octo".to_string(),
+            repo: "dotfiles".to_string(),
+        };
+        for input in [
+            "octo/dotfiles",
+            "github.com/octo/dotfiles",
+            "https://github.com/octo/dotfiles.git",
+            "https://github.com/octo/dotfiles/tree/main/zsh",
+            "git@github.com:octo/dotfiles.git",
+        ] {
+            assert_eq!(Source::parse(input).unwrap(), expected, "{input}");
+        }
+        assert!(Source::parse("octo").is_err());
+        assert!(Source::parse("https://gitlab.com/octo/dotfiles").is_err());
+        assert!(Source::parse("../etc").is_err());
+    }
+
+    #[test]
+    fn test_import_with_attribution() {
+        let temp_dir = TempDir::new().unwrap();
+        let checkout = temp_dir.path().join("checkout");
+        let home = temp_dir.path().join("home");
+        fs::create_dir_all(checkout.join("tmux")).unwrap();
+        fs::create_dir_all(&home).unwrap();
+        fs::write(checkout.join("tmux/.tmux.conf"), "set -g mouse on\n").unwrap();
+        fs::write(checkout.join("vimrc"), "set number\n").unwrap();
+        fs::write(checkout.join("setup.sh"), "#!/bin/sh\necho hi\n").unwrap();
+        let source = Source::parse("octo/dotfiles").unwrap();
+
+        assert_eq!(
+            list_files(&checkout).unwrap(),
+            vec!["setup.sh", "tmux/.tmux.conf", "vimrc"]
+        );
+        assert_eq!(suggest_destination("tmux/.tmux.conf"), ".tmux.conf");
+        assert_eq!(suggest_destination("vimrc"), ".vimrc");
+        assert_eq!(suggest_destination("setup.sh"), "setup.sh");
+
+        let written = import(&checkout, &source, "vimrc", &home, ".vimrc").unwrap();
+        assert_eq!(
+            fs::read_to_string(written).unwrap(),
+            "\" Imported from https://github.com/octo/dotfiles (vimrc) with dotstate\nset number\n"
+        );
+        import(&checkout, &source, "setup.sh", &home, "bin/setup.sh").unwrap();
+        assert!(fs::read_to_string(home.join("bin/setup.sh"))
+            .unwrap()
+            .starts_with("#!/bin/sh\n# Imported from"));
+
+        // Existing files are never replaced
+        assert!(import(&checkout, &source, "vimrc", &home, ".vimrc").is_err());
+        assert!(import(&checkout, &source, "vimrc", &home, "../.vimrc").is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Exported environment variables kept as data.
+//!
+//! `env.toml` in `common/` or a profile directory of the storage repository
+//! holds an `[env]` table of name = value, plus `[machines.<hostname>]`
+//! tables whose values only apply on that machine. Like aliases (see
+//! [`aliases`](crate::utils::aliases)), the active profile's variables
+//! (common's, then those of the profiles it inherits from, machine values
+//! overriding within each) are rendered on activation, into `env.sh` and
+//! `env.fish` in the data directory. The shell snippet loader sources
+//! `env.sh`.
+//!
+//! Values are double-quoted, so `$HOME` or `$PATH` in them expand when the
+//! shell starts. A value with a `{{ secret "..." }}` placeholder (see
+//! [`secrets`]) is resolved when rendering and written literally; the files
+//! are readable only by the user.
+
+use crate::utils::ssh_remote::shell_quote;
+use crate::utils::{get_data_dir, secrets, sops, ProfileManifest};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Name of the variable file in `common/` and each profile directory
+pub const ENV_FILE: &str = "env.toml";
+
+/// Directory of the variables shared by every profile
+const COMMON_DIR: &str = "common";
+
+/// Variables of one profile (or common).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvFile {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Values for a single machine, by hostname
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub machines: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// A variable in effect for a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedVar {
+    pub value: String,
+    /// Profile (or `common`) that defines it, with the hostname for a
+    /// machine value (`Work@laptop`)
+    pub source: String,
+}
+
+/// Shell an env file is rendered for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvShell {
+    /// sh, bash and zsh
+    Posix,
+    Fish,
+}
+
+impl EnvShell {
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::Posix => "env.sh",
+            Self::Fish => "env.fish",
+        }
+    }
+
+    /// Path of the rendered file in the data directory
+    #[must_use]
+    pub fn path(self) -> PathBuf {
+        get_data_dir().join(self.file_name())
+    }
+}
+
+impl EnvFile {
+    fn path(repo_path: &Path, dir: &str) -> PathBuf {
+        repo_path.join(dir).join(ENV_FILE)
+    }
+
+    /// Load the variables of `dir` (a profile name or `common`); none if the
+    /// file doesn't exist
+    pub fn load(repo_path: &Path, dir: &str) -> Result<Self> {
+        let path = Self::path(repo_path, dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save the variables of `dir`. Without variables the file is removed.
+    pub fn save(&mut self, repo_path: &Path, dir: &str) -> Result<()> {
+        self.machines.retain(|_, vars| !vars.is_empty());
+        let path = Self::path(repo_path, dir);
+        if self.env.is_empty() && self.machines.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content =
+            toml::to_string_pretty(self).context("Failed to serialize environment variables")?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The table values for `machine` go in, or the shared one
+    pub fn vars_mut(&mut self, machine: Option<&str>) -> &mut BTreeMap<String, String> {
+        match machine {
+            Some(host) => self.machines.entry(host.to_string()).or_default(),
+            None => &mut self.env,
+        }
+    }
+}
+
+/// Check a variable name: letters, digits and `_`, not starting with a digit
+pub fn validate_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() {
+        return Err("Variable name can't be empty".to_string());
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err("Variable name can't start with a digit".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '_'))
+    {
+        return Err(format!("Variable name can't contain '{c}'"));
+    }
+    Ok(())
+}
+
+/// Variables in effect for `profile` on `hostname`: common's, then those of
+/// the profiles it inherits from, then its own, a later definition
+/// replacing an earlier one. Within each, machine values win.
+pub fn resolve(
+    repo_path: &Path,
+    profile: &str,
+    hostname: &str,
+) -> Result<BTreeMap<String, ResolvedVar>> {
+    let manifest =
+        ProfileManifest::load_or_backfill(repo_path).context("Failed to load profile manifest")?;
+    let mut chain = manifest.inheritance_chain(profile)?;
+    chain.reverse();
+
+    let mut resolved = BTreeMap::new();
+    for dir in std::iter::once(COMMON_DIR.to_string()).chain(chain) {
+        let mut file = EnvFile::load(repo_path, &dir)?;
+        let machine = file.machines.remove(hostname).unwrap_or_default();
+        let shared = file.env.into_iter().map(|var| (var, dir.clone()));
+        let local = machine
+            .into_iter()
+            .map(|var| (var, format!("{dir}@{hostname}")));
+        for ((name, value), source) in shared.chain(local) {
+            resolved.insert(name, ResolvedVar { value, source });
+        }
+    }
+    Ok(resolved)
+}
+
+/// `value` double-quoted, with `$` left to expand
+fn quote_expanding(value: &str, shell: EnvShell) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        let escape = match shell {
+            EnvShell::Posix => matches!(c, '"' | '\\' | '`'),
+            EnvShell::Fish => matches!(c, '"' | '\\'),
+        };
+        if escape {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
+/// `value` single-quoted, taken literally
+fn quote_literal(value: &str, shell: EnvShell) -> String {
+    match shell {
+        EnvShell::Posix => shell_quote(value),
+        EnvShell::Fish => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'")),
+    }
+}
+
+/// Export lines for `shell`. Secret placeholders are resolved with
+/// `resolve_secrets`; a variable whose secret can't be fetched is left out
+/// with a warning.
+pub fn render_with(
+    vars: &BTreeMap<String, ResolvedVar>,
+    shell: EnvShell,
+    mut resolve_secrets: impl FnMut(&str) -> Result<String>,
+) -> String {
+    let mut out = String::new();
+    for (name, var) in vars {
+        let value = if secrets::has_placeholders(&var.value) {
+            match resolve_secrets(&var.value) {
+                Ok(secret) => quote_literal(&secret, shell),
+                Err(e) => {
+                    warn!("Skipping ${name} from {}: {e:#}", var.source);
+                    continue;
+                }
+            }
+        } else {
+            quote_expanding(&var.value, shell)
+        };
+        out.push_str(&match shell {
+            EnvShell::Posix => format!("export {name}={value}\n"),
+            EnvShell::Fish => format!("set -gx {name} {value}\n"),
+        });
+    }
+    out
+}
+
+/// Render the variables of `profile` on this machine into `env.sh` and
+/// `env.fish`. Without variables the files are removed. Returns the path of
+/// `env.sh` if it was written.
+pub fn write(repo_path: &Path, profile: &str) -> Result<Option<PathBuf>> {
+    let hostname = crate::utils::machine_registry::current_hostname();
+    let vars = resolve(repo_path, profile, &hostname)?;
+    // Each secret is fetched once for both files
+    let mut fetched: BTreeMap<String, String> = BTreeMap::new();
+    let mut resolve_secrets = |text: &str| -> Result<String> {
+        if let Some(value) = fetched.get(text) {
+            return Ok(value.clone());
+        }
+        let value = secrets::render(text)?;
+        fetched.insert(text.to_string(), value.clone());
+        Ok(value)
+    };
+    for shell in [EnvShell::Posix, EnvShell::Fish] {
+        let path = shell.path();
+        if vars.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            continue;
+        }
+        let content = format!(
+            "# Generated by dotstate for profile '{profile}'; activation rewrites it.\n\
+             # Edit the variables with 'dotstate env' instead.\n{}",
+            render_with(&vars, shell, &mut resolve_secrets)
+        );
+        if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+            sops::write_plaintext(&path, content.as_bytes())?;
+            info!("Wrote {} variable(s) to {:?}", vars.len(), path);
+        }
+    }
+    Ok((!vars.is_empty()).then(|| EnvShell::Posix.path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::ProfileInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_with_machine_values() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        ProfileManifest {
+            profiles: vec![ProfileInfo {
+                name: "Work".to_string(),
+                description: None,
+                inherits: None,
+                synced_files: Vec::new(),
+                packages: Vec::new(),
+            }],
+            ..ProfileManifest::default()
+        }
+        .save(repo)
+        .unwrap();
+
+        let mut common = EnvFile::default();
+        common.vars_mut(None).insert("EDITOR".into(), "vim".into());
+        common
+            .vars_mut(Some("laptop"))
+            .insert("PROXY".into(), "off".into());
+        common.save(repo, "common").unwrap();
+        let mut work = EnvFile::default();
+        work.vars_mut(None).insert("EDITOR".into(), "nvim".into());
+        work.vars_mut(None).insert("PROXY".into(), "on".into());
+        work.save(repo, "Work").unwrap();
+
+        let on_laptop = resolve(repo, "Work", "laptop").unwrap();
+        assert_eq!(on_laptop["EDITOR"].value, "nvim");
+        assert_eq!(on_laptop["PROXY"].source, "Work");
+        let mut work = EnvFile::load(repo, "Work").unwrap();
+        work.vars_mut(Some("laptop"))
+            .insert("PROXY".into(), "off".into());
+        work.save(repo, "Work").unwrap();
+        let on_laptop = resolve(repo, "Work", "laptop").unwrap();
+        assert_eq!(on_laptop["PROXY"].value, "off");
+        assert_eq!(on_laptop["PROXY"].source, "Work@laptop");
+        assert_eq!(
+            resolve(repo, "Work", "desktop").unwrap()["PROXY"].value,
+            "on"
+        );
+
+        // Saving an empty file removes it
+        let mut empty = EnvFile::default();
+        empty.vars_mut(Some("laptop"));
+        empty.save(repo, "Work").unwrap();
+        assert!(!repo.join("Work").join(ENV_FILE).exists());
+    }
+
+    #[test]
+    fn test_render_quotes_and_secrets() {
+        let var = |value: &str| ResolvedVar {
+            value: value.to_string(),
+            source: "common".to_string(),
+        };
+        let vars = BTreeMap::from([
+            ("PATH".to_string(), var("$HOME/bin:$PATH")),
+            ("QUOTED".to_string(), var("say \"hi\" `now`")),
+            ("TOKEN".to_string(), var("{{ secret \"pass://github\" }}")),
+            ("MISSING".to_string(), var("{{ secret \"pass://gone\" }}")),
+        ]);
+        let secret = |text: &str| {
+            if text.contains("gone") {
+                anyhow::bail!("not found")
+            }
+            Ok("it's $ecret".to_string())
+        };
+
+        assert_eq!(
+            render_with(&vars, EnvShell::Posix, secret),
+            "export PATH=\"$HOME/bin:$PATH\"\n\
+             export QUOTED=\"say \\\"hi\\\" \\`now\\`\"\n\
+             export TOKEN='it'\\''s $ecret'\n"
+        );
+        assert_eq!(
+            render_with(&vars, EnvShell::Fish, secret),
+            "set -gx PATH \"$HOME/bin:$PATH\"\n\
+             set -gx QUOTED \"say \\\"hi\\\" `now`\"\n\
+             set -gx TOKEN 'it\\'s $ecret'\n"
+        );
+    }
+}
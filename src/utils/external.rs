@@ -0,0 +1,88 @@
+//! Handing a file to another program: the user's editor, or the system
+//! file manager.
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// The editor command from `$VISUAL` or `$EDITOR` (which may carry
+/// arguments, like `code --wait`), falling back to `vi`
+fn editor_command(visual: Option<String>, editor: Option<String>) -> Vec<String> {
+    let command = [visual, editor]
+        .into_iter()
+        .flatten()
+        .find(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+    command.split_whitespace().map(str::to_string).collect()
+}
+
+/// Open `path` in the user's editor and wait for it to exit. The caller
+/// leaves the terminal to the editor while it runs.
+pub fn edit(path: &Path) -> Result<()> {
+    let command = editor_command(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok());
+    let status = Command::new(&command[0])
+        .args(&command[1..])
+        .arg(path)
+        .status()
+        .with_context(|| {
+            format!(
+                "Failed to start {}. Set $EDITOR to your editor.",
+                command[0]
+            )
+        })?;
+    if !status.success() {
+        bail!("{} exited with {}", command[0], status);
+    }
+    Ok(())
+}
+
+/// Show `path` in the system file manager: selected in Finder or Explorer,
+/// elsewhere by opening the directory holding it
+pub fn reveal(path: &Path) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    } else if cfg!(windows) {
+        let mut command = Command::new("explorer");
+        command.arg(format!("/select,{}", path.display()));
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(path.parent().unwrap_or(path));
+        command
+    };
+    let status = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .context("No file manager to open")?;
+    // explorer exits with 1 even when it opened the window
+    if !status.success() && !cfg!(windows) {
+        bail!("The file manager couldn't open {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command() {
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(
+            editor_command(some("code --wait"), some("vim")),
+            vec!["code", "--wait"]
+        );
+        assert_eq!(editor_command(some(" "), some("nvim")), vec!["nvim"]);
+        assert!(!editor_command(None, None).is_empty());
+    }
+}
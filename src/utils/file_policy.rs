@@ -0,0 +1,159 @@
+//! Size and content checks before a path is added to the repository.
+//!
+//! git keeps every version of every file it's given, so a cache directory or
+//! a disk image added by accident bloats the repository for good and slows
+//! down every clone. Before a path is added, [`inspect`] walks it and
+//! reports its size and the binary files in it, so the add can ask whether
+//! to store it with Git LFS, encrypt it, or leave it out.
+
+use crate::utils::binary;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files listed as the largest in a report
+const LARGEST_SHOWN: usize = 3;
+
+/// Files walked at most; a directory this big is reported as it is
+const MAX_FILES: usize = 100_000;
+
+/// What was found in a path that's too large or holds binary files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddReport {
+    /// Bytes in all the files
+    pub total_bytes: u64,
+    /// Files walked
+    pub files: usize,
+    /// Whether the walk stopped at [`MAX_FILES`]
+    pub incomplete: bool,
+    /// Whether `total_bytes` is over the threshold
+    pub over_limit: bool,
+    /// Binary files, relative to the inspected path
+    pub binary: Vec<PathBuf>,
+    /// The largest files with their sizes, largest first
+    pub largest: Vec<(PathBuf, u64)>,
+}
+
+impl AddReport {
+    /// A few lines describing what was found
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let plus = if self.incomplete { "+" } else { "" };
+        let mut lines = vec![format!(
+            "Size: {} in {}{plus} file(s)",
+            binary::format_size(self.total_bytes),
+            self.files
+        )];
+        if let Some(first) = self.binary.first() {
+            if first.as_os_str().is_empty() {
+                lines.push("Binary: yes".to_string());
+            } else {
+                lines.push(format!(
+                    "Binary: {} file(s), e.g. {}",
+                    self.binary.len(),
+                    first.display()
+                ));
+            }
+        }
+        if self.files > 1 {
+            let largest: Vec<String> = self
+                .largest
+                .iter()
+                .map(|(path, size)| format!("{} ({})", path.display(), binary::format_size(*size)))
+                .collect();
+            lines.push(format!("Largest: {}", largest.join(", ")));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Inspect `path` before it's added. Returns a report when it holds more
+/// than `threshold_mb` megabytes or any binary file, `None` when it's fine
+/// to add or the check is turned off (`threshold_mb` of 0).
+#[must_use]
+pub fn inspect(path: &Path, threshold_mb: u64) -> Option<AddReport> {
+    if threshold_mb == 0 {
+        return None;
+    }
+    let mut report = AddReport {
+        total_bytes: 0,
+        files: 0,
+        incomplete: false,
+        over_limit: false,
+        binary: Vec::new(),
+        largest: Vec::new(),
+    };
+    // A symlinked dotfile is inspected where it points
+    let root = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    walk(&root, &root, &mut report);
+    report.over_limit = report.total_bytes > threshold_mb.saturating_mul(1024 * 1024);
+    (report.over_limit || !report.binary.is_empty()).then_some(report)
+}
+
+fn walk(root: &Path, path: &Path, report: &mut AddReport) {
+    if report.files >= MAX_FILES {
+        report.incomplete = true;
+        return;
+    }
+    // Symlinks aren't followed, so a link to / doesn't walk the disk
+    let Ok(metadata) = path.symlink_metadata() else {
+        return;
+    };
+    if metadata.is_dir() {
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            walk(root, &entry.path(), report);
+        }
+        return;
+    }
+    if !metadata.is_file() {
+        return;
+    }
+    let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    report.files += 1;
+    report.total_bytes += metadata.len();
+    if binary::read_prefix(path, binary::SNIFF_LEN).is_ok_and(|bytes| binary::is_binary(&bytes)) {
+        report.binary.push(relative.clone());
+    }
+    report.largest.push((relative, metadata.len()));
+    report
+        .largest
+        .sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    report.largest.truncate(LARGEST_SHOWN);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_inspect() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("cache");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("config.toml"), "a = 1\n").unwrap();
+        fs::write(dir.join("sub/data.bin"), [0u8, 1, 2, 3]).unwrap();
+
+        // A small text file is fine
+        assert_eq!(inspect(&dir.join("config.toml"), 10), None);
+
+        let report = inspect(&dir, 10).unwrap();
+        assert!(!report.over_limit);
+        assert_eq!(report.files, 2);
+        assert_eq!(report.binary, vec![PathBuf::from("sub/data.bin")]);
+        assert!(report
+            .summary()
+            .contains("Binary: 1 file(s), e.g. sub/data.bin"));
+
+        fs::write(dir.join("big.log"), "x".repeat(2 * 1024 * 1024)).unwrap();
+        fs::remove_file(dir.join("sub/data.bin")).unwrap();
+        let report = inspect(&dir, 1).unwrap();
+        assert!(report.over_limit);
+        assert_eq!(report.largest[0].0, PathBuf::from("big.log"));
+
+        // Turned off
+        assert_eq!(inspect(&dir, 0), None);
+    }
+}
@@ -0,0 +1,257 @@
+//! Storage directories kept in Syncthing, Dropbox or a similar file-sync
+//! service.
+//!
+//! Git and a file-sync service writing the same directory corrupt each
+//! other: two machines committing at once leave the service with two
+//! versions of `.git/index` or a ref, and it keeps the loser as a conflict
+//! copy next to the original. With `file_sync_mode` on, a sync first takes
+//! a lock file inside `.git` (which the service carries to the other
+//! machines) and refuses to run while conflict copies are around. `dotstate
+//! file-sync` lists the copies and resolves them.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Lock file, inside `.git` so it is synced between machines but never
+/// committed
+pub const LOCK_FILE: &str = "dotstate-sync.lock";
+
+/// Locks older than this are assumed to be left behind by a crash
+const STALE_AFTER_MINUTES: i64 = 10;
+
+/// Who holds the lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockHolder {
+    pub host: String,
+    pub pid: u32,
+    pub since: DateTime<Utc>,
+}
+
+impl LockHolder {
+    fn parse(content: &str) -> Option<Self> {
+        let mut host = None;
+        let mut pid = None;
+        let mut since = None;
+        for line in content.lines() {
+            match line.split_once('=') {
+                Some(("host", value)) => host = Some(value.to_string()),
+                Some(("pid", value)) => pid = value.parse().ok(),
+                Some(("since", value)) => since = DateTime::parse_from_rfc3339(value).ok(),
+                _ => {}
+            }
+        }
+        Some(Self {
+            host: host?,
+            pid: pid?,
+            since: since?.with_timezone(&Utc),
+        })
+    }
+
+    fn is_stale(&self) -> bool {
+        Utc::now() - self.since > chrono::Duration::minutes(STALE_AFTER_MINUTES)
+    }
+}
+
+/// A held lock, released on drop
+#[derive(Debug)]
+pub struct SyncLock {
+    path: PathBuf,
+}
+
+impl Drop for SyncLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_path(repo_path: &Path) -> PathBuf {
+    repo_path.join(".git").join(LOCK_FILE)
+}
+
+/// The current holder of the lock, if any
+#[must_use]
+pub fn holder(repo_path: &Path) -> Option<LockHolder> {
+    let content = fs::read_to_string(lock_path(repo_path)).ok()?;
+    LockHolder::parse(&content)
+}
+
+/// Take the lock, unless another machine or process holds it. A stale or
+/// unreadable lock is taken over.
+pub fn acquire(repo_path: &Path) -> Result<SyncLock> {
+    let path = lock_path(repo_path);
+    if path.exists() {
+        match holder(repo_path) {
+            Some(holder) if !holder.is_stale() => bail!(
+                "{} (pid {}) has been writing to the repository since {}. \
+                 Wait for it to finish and for the file sync to settle, then try again.",
+                holder.host,
+                holder.pid,
+                holder
+                    .since
+                    .with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+            ),
+            _ => fs::remove_file(&path).context("Failed to remove a stale sync lock")?,
+        }
+    }
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .context("Another sync took the repository lock first")?;
+    let content = format!(
+        "host={}\npid={}\nsince={}\n",
+        crate::utils::machine_registry::current_hostname(),
+        std::process::id(),
+        Utc::now().to_rfc3339()
+    );
+    file.write_all(content.as_bytes())
+        .context("Failed to write the sync lock")?;
+    Ok(SyncLock { path })
+}
+
+/// A conflict copy left by the file-sync service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictCopy {
+    /// The copy, relative to the repository
+    pub path: String,
+    /// The file it is a copy of, relative to the repository
+    pub original: String,
+}
+
+/// Name of the file `name` is a conflict copy of, or `None` when it isn't
+/// one. Knows Syncthing (`a.sync-conflict-20240101-120000-ABCDEFG.txt`) and
+/// Dropbox / Nextcloud (`a (Jo's conflicted copy 2024-01-01).txt`).
+#[must_use]
+pub fn original_name(name: &str) -> Option<String> {
+    const SYNCTHING: &str = ".sync-conflict-";
+    if let Some(start) = name.find(SYNCTHING) {
+        let rest = &name[start + SYNCTHING.len()..];
+        let extension = rest.find('.').map_or("", |dot| &rest[dot..]);
+        return Some(format!("{}{extension}", &name[..start]));
+    }
+    let marker = name.find("conflicted copy")?;
+    let open = name[..marker].rfind(" (")?;
+    let close = marker + name[marker..].find(')')?;
+    Some(format!("{}{}", &name[..open], &name[close + 1..]))
+}
+
+/// Every conflict copy in the repository, `.git` included, sorted by path
+pub fn find_conflicts(repo_path: &Path) -> Result<Vec<ConflictCopy>> {
+    fn walk(dir: &Path, root: &Path, found: &mut Vec<ConflictCopy>) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(original) = original_name(&name) {
+                let relative = |p: &Path| {
+                    p.strip_prefix(root)
+                        .unwrap_or(p)
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                };
+                found.push(ConflictCopy {
+                    path: relative(&path),
+                    original: relative(&path.with_file_name(original)),
+                });
+            } else if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                walk(&path, root, found)?;
+            }
+        }
+        Ok(())
+    }
+
+    let mut found = Vec::new();
+    walk(repo_path, repo_path, &mut found)?;
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(found)
+}
+
+/// Which version of a conflicted file to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    /// The file as it is, deleting the copy
+    Original,
+    /// The copy, moved over the original
+    Copy,
+}
+
+/// Resolve a conflict copy, keeping one version
+pub fn resolve(repo_path: &Path, conflict: &ConflictCopy, keep: Keep) -> Result<()> {
+    let copy = repo_path.join(&conflict.path);
+    match keep {
+        Keep::Original => {
+            if copy.is_dir() {
+                fs::remove_dir_all(&copy)
+            } else {
+                fs::remove_file(&copy)
+            }
+            .with_context(|| format!("Failed to remove {}", conflict.path))?;
+        }
+        Keep::Copy => {
+            let original = repo_path.join(&conflict.original);
+            if original.is_dir() {
+                fs::remove_dir_all(&original)
+                    .with_context(|| format!("Failed to remove {}", conflict.original))?;
+            }
+            fs::rename(&copy, &original).with_context(|| {
+                format!("Failed to move {} to {}", conflict.path, conflict.original)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_conflicts_and_lock() {
+        assert_eq!(
+            original_name("zshrc.sync-conflict-20240101-120000-ABCDEFG").as_deref(),
+            Some("zshrc")
+        );
+        assert_eq!(
+            original_name("init.sync-conflict-20240101-120000-ABCDEFG.lua").as_deref(),
+            Some("init.lua")
+        );
+        assert_eq!(
+            original_name("index (Jo's conflicted copy 2024-01-01)").as_deref(),
+            Some("index")
+        );
+        assert_eq!(original_name(".zshrc"), None);
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        fs::create_dir_all(repo.join(".git/refs/heads")).unwrap();
+        fs::write(repo.join(".git/refs/heads/main"), "a\n").unwrap();
+        fs::write(
+            repo.join(".git/refs/heads/main.sync-conflict-20240101-120000-ABCDEFG"),
+            "b\n",
+        )
+        .unwrap();
+        let conflicts = find_conflicts(repo).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].original, ".git/refs/heads/main");
+        resolve(repo, &conflicts[0], Keep::Copy).unwrap();
+        assert_eq!(
+            fs::read_to_string(repo.join(".git/refs/heads/main")).unwrap(),
+            "b\n"
+        );
+        assert!(find_conflicts(repo).unwrap().is_empty());
+
+        // Only one sync holds the lock at a time
+        let lock = acquire(repo).unwrap();
+        assert_eq!(holder(repo).unwrap().pid, std::process::id());
+        assert!(acquire(repo).is_err());
+        drop(lock);
+        assert!(holder(repo).is_none());
+        drop(acquire(repo).unwrap());
+    }
+}
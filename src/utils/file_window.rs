@@ -0,0 +1,205 @@
+//! Windowed reads of large text files for previews.
+//!
+//! Opening a file makes one streaming pass that counts lines and records a
+//! checkpoint offset every [`CHECKPOINT_INTERVAL`] lines. Previews then only
+//! keep the visible lines plus a buffer in memory, reloading from the nearest
+//! checkpoint when scrolling leaves the loaded window.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Files larger than this are previewed through a [`FileWindow`]
+pub const STREAM_THRESHOLD: u64 = 1024 * 1024;
+
+/// Lines between recorded byte offsets
+const CHECKPOINT_INTERVAL: usize = 1024;
+
+/// Extra lines loaded above and below the requested range
+const WINDOW_BUFFER: usize = 200;
+
+/// Longer lines are cut off, so a minified file can't blow up a redraw
+const MAX_LINE_LEN: usize = 4096;
+
+/// A text file indexed for random access by line number
+pub struct FileWindow {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    size: u64,
+    total_lines: usize,
+    /// Byte offset of line `i * CHECKPOINT_INTERVAL`
+    checkpoints: Vec<u64>,
+    window_start: usize,
+    window: Vec<String>,
+}
+
+impl FileWindow {
+    /// Index `path` without keeping its content in memory.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        let mut reader = File::open(path)?;
+        let mut chunk = vec![0u8; 64 * 1024];
+        let mut checkpoints = vec![0];
+        let mut newlines = 0;
+        let mut offset = 0u64;
+        let mut last_byte = None;
+
+        loop {
+            let read = reader.read(&mut chunk)?;
+            if read == 0 {
+                break;
+            }
+            for (i, &byte) in chunk[..read].iter().enumerate() {
+                if byte == b'\n' {
+                    newlines += 1;
+                    if newlines % CHECKPOINT_INTERVAL == 0 {
+                        checkpoints.push(offset + i as u64 + 1);
+                    }
+                }
+            }
+            offset += read as u64;
+            last_byte = Some(chunk[read - 1]);
+        }
+
+        // A final line without a trailing newline still counts
+        let total_lines = match last_byte {
+            Some(b'\n') | None => newlines,
+            Some(_) => newlines + 1,
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            modified: metadata.modified().ok(),
+            size: offset,
+            total_lines,
+            checkpoints,
+            window_start: 0,
+            window: Vec::new(),
+        })
+    }
+
+    /// Whether this index still describes `path` as it is on disk
+    #[must_use]
+    pub fn is_current(&self, path: &Path) -> bool {
+        self.path == path
+            && std::fs::metadata(path)
+                .is_ok_and(|m| m.len() == self.size && m.modified().ok() == self.modified)
+    }
+
+    #[must_use]
+    pub fn total_lines(&self) -> usize {
+        self.total_lines
+    }
+
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Lines `start..start + count` (fewer at the end of the file), loading
+    /// them from disk only if they're outside the buffered window.
+    pub fn lines(&mut self, start: usize, count: usize) -> io::Result<&[String]> {
+        let start = start.min(self.total_lines);
+        let end = (start + count).min(self.total_lines);
+        let window_end = self.window_start + self.window.len();
+        if start < self.window_start || end > window_end {
+            self.load(
+                start.saturating_sub(WINDOW_BUFFER),
+                count + 2 * WINDOW_BUFFER,
+            )?;
+        }
+        let from = start - self.window_start;
+        let to = (end - self.window_start).min(self.window.len());
+        Ok(&self.window[from.min(to)..to])
+    }
+
+    fn load(&mut self, start: usize, count: usize) -> io::Result<()> {
+        let checkpoint = start / CHECKPOINT_INTERVAL;
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        reader.seek(SeekFrom::Start(self.checkpoints[checkpoint]))?;
+
+        let mut line = Vec::new();
+        for _ in 0..start % CHECKPOINT_INTERVAL {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+        }
+
+        let mut window = Vec::with_capacity(count);
+        while window.len() < count {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            let text = String::from_utf8_lossy(&line);
+            let text = text.trim_end_matches(['\n', '\r']);
+            let cut = text.floor_char_boundary(MAX_LINE_LEN);
+            window.push(text[..cut].to_string());
+        }
+
+        self.window_start = start;
+        self.window = window;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write as _;
+
+    fn numbered_file(lines: usize, trailing_newline: bool) -> (tempfile::TempDir, PathBuf) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("big.log");
+        let mut content = String::new();
+        for i in 0..lines {
+            let _ = write!(content, "line {i}");
+            if i + 1 < lines || trailing_newline {
+                content.push('\n');
+            }
+        }
+        std::fs::write(&path, content).unwrap();
+        (temp_dir, path)
+    }
+
+    #[test]
+    fn test_counts_lines() {
+        let (_dir, path) = numbered_file(5000, true);
+        assert_eq!(FileWindow::open(&path).unwrap().total_lines(), 5000);
+
+        let (_dir, path) = numbered_file(5000, false);
+        assert_eq!(FileWindow::open(&path).unwrap().total_lines(), 5000);
+
+        let (_dir, path) = numbered_file(0, false);
+        assert_eq!(FileWindow::open(&path).unwrap().total_lines(), 0);
+    }
+
+    #[test]
+    fn test_reads_window_across_checkpoints() {
+        let (_dir, path) = numbered_file(5000, true);
+        let mut window = FileWindow::open(&path).unwrap();
+
+        let lines = window.lines(0, 3).unwrap();
+        assert_eq!(lines, ["line 0", "line 1", "line 2"]);
+
+        // Jump past several checkpoints, away from the loaded window
+        let lines = window.lines(3070, 2).unwrap();
+        assert_eq!(lines, ["line 3070", "line 3071"]);
+
+        // Clipped at the end of the file
+        let lines = window.lines(4998, 10).unwrap();
+        assert_eq!(lines, ["line 4998", "line 4999"]);
+    }
+
+    #[test]
+    fn test_is_current_detects_changes() {
+        let (_dir, path) = numbered_file(10, true);
+        let window = FileWindow::open(&path).unwrap();
+        assert!(window.is_current(&path));
+
+        std::fs::write(&path, "changed\n").unwrap();
+        assert!(!window.is_current(&path));
+    }
+}
@@ -0,0 +1,222 @@
+//! Detection of other dotfile managers (chezmoi, stow, yadm).
+//!
+//! Running another manager alongside `DotState` means the same file can be
+//! written by both tools, and whichever runs last wins. This module finds
+//! traces of those tools in the home directory, and tracked paths that are
+//! currently symlinked somewhere other than the `DotState` repository.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A dotfile manager that may conflict with `DotState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForeignManager {
+    Chezmoi,
+    Stow,
+    Yadm,
+}
+
+impl ForeignManager {
+    /// Display name (also the command name)
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            ForeignManager::Chezmoi => "chezmoi",
+            ForeignManager::Stow => "stow",
+            ForeignManager::Yadm => "yadm",
+        }
+    }
+
+    /// How to stop the manager from handling a file once `DotState` has adopted it
+    #[must_use]
+    pub fn release_hint(&self) -> &'static str {
+        match self {
+            ForeignManager::Chezmoi => "chezmoi forget <file>",
+            ForeignManager::Stow => "stow -D <package>",
+            ForeignManager::Yadm => "yadm rm --cached <file>",
+        }
+    }
+
+    /// Paths (relative to home) whose existence shows the manager is set up
+    fn markers(&self) -> &'static [&'static str] {
+        match self {
+            ForeignManager::Chezmoi => &[".local/share/chezmoi", ".config/chezmoi"],
+            ForeignManager::Stow => &[".stowrc", ".stow-global-ignore"],
+            ForeignManager::Yadm => &[".local/share/yadm/repo.git", ".config/yadm", ".yadm"],
+        }
+    }
+
+    fn all() -> [ForeignManager; 3] {
+        [
+            ForeignManager::Chezmoi,
+            ForeignManager::Stow,
+            ForeignManager::Yadm,
+        ]
+    }
+}
+
+/// A manager found on this machine, with the path that gave it away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedManager {
+    pub manager: ForeignManager,
+    pub evidence: PathBuf,
+}
+
+/// A tracked path that is a symlink pointing outside the `DotState` repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignSymlink {
+    /// Path relative to home (as stored in the manifest)
+    pub relative_path: String,
+    /// Resolved symlink target
+    pub target: PathBuf,
+    /// Manager owning the target, if it could be identified
+    pub manager: Option<ForeignManager>,
+}
+
+/// Find traces of other dotfile managers in `home`.
+#[must_use]
+pub fn detect_managers(home: &Path) -> Vec<DetectedManager> {
+    ForeignManager::all()
+        .into_iter()
+        .filter_map(|manager| {
+            manager
+                .markers()
+                .iter()
+                .map(|marker| home.join(marker))
+                .find(|path| path.symlink_metadata().is_ok())
+                .map(|evidence| DetectedManager { manager, evidence })
+        })
+        .collect()
+}
+
+/// Find tracked paths that are symlinked somewhere other than `repo_path`.
+///
+/// Missing paths, regular files and symlinks into the repository are ignored.
+#[must_use]
+pub fn find_foreign_symlinks(
+    home: &Path,
+    repo_path: &Path,
+    tracked: &[String],
+) -> Vec<ForeignSymlink> {
+    let repo_path = normalize(repo_path);
+    tracked
+        .iter()
+        .filter_map(|relative_path| {
+            let path = home.join(relative_path);
+            let link = fs::read_link(&path).ok()?;
+            let target = match path.parent() {
+                Some(parent) if link.is_relative() => normalize(&parent.join(&link)),
+                _ => normalize(&link),
+            };
+            if target.starts_with(&repo_path) {
+                return None;
+            }
+            Some(ForeignSymlink {
+                relative_path: relative_path.clone(),
+                manager: owner_of(home, &target),
+                target,
+            })
+        })
+        .collect()
+}
+
+/// Guess which manager a symlink target belongs to.
+///
+/// chezmoi and yadm don't use symlinks for managed files, but their source
+/// directories can still be linked to by hand. Stow has no state directory;
+/// its packages conventionally live in `~/dotfiles` or `~/.dotfiles`.
+fn owner_of(home: &Path, target: &Path) -> Option<ForeignManager> {
+    let under = |dir: &str| target.starts_with(home.join(dir));
+    if under(".local/share/chezmoi") {
+        Some(ForeignManager::Chezmoi)
+    } else if under(".local/share/yadm") || under(".config/yadm") {
+        Some(ForeignManager::Yadm)
+    } else if under("dotfiles") || under(".dotfiles") || target.components().any(is_stow_dir) {
+        Some(ForeignManager::Stow)
+    } else {
+        None
+    }
+}
+
+fn is_stow_dir(component: Component) -> bool {
+    matches!(component, Component::Normal(name) if name == "stow")
+}
+
+/// Lexically resolve `.` and `..` without touching the filesystem, so that
+/// targets of broken symlinks can still be compared.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_managers() {
+        let home = TempDir::new().unwrap();
+        assert!(detect_managers(home.path()).is_empty());
+
+        fs::create_dir_all(home.path().join(".local/share/chezmoi")).unwrap();
+        fs::write(home.path().join(".stowrc"), "").unwrap();
+
+        let detected = detect_managers(home.path());
+        let managers: Vec<_> = detected.iter().map(|d| d.manager).collect();
+        assert_eq!(
+            managers,
+            vec![ForeignManager::Chezmoi, ForeignManager::Stow]
+        );
+        assert_eq!(detected[1].evidence, home.path().join(".stowrc"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_foreign_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let home = TempDir::new().unwrap();
+        let home_path = home.path();
+        let repo = home_path.join(".config/dotstate/storage");
+        fs::create_dir_all(repo.join("default")).unwrap();
+        fs::create_dir_all(home_path.join("dotfiles/zsh")).unwrap();
+
+        // Ours: points into the repo
+        symlink(repo.join("default/.vimrc"), home_path.join(".vimrc")).unwrap();
+        // Stow-style relative link into ~/dotfiles
+        symlink("dotfiles/zsh/.zshrc", home_path.join(".zshrc")).unwrap();
+        // Regular file
+        fs::write(home_path.join(".bashrc"), "").unwrap();
+
+        let tracked = vec![
+            ".vimrc".to_string(),
+            ".zshrc".to_string(),
+            ".bashrc".to_string(),
+            ".missing".to_string(),
+        ];
+        let foreign = find_foreign_symlinks(home_path, &repo, &tracked);
+
+        assert_eq!(foreign.len(), 1);
+        assert_eq!(foreign[0].relative_path, ".zshrc");
+        assert_eq!(foreign[0].target, home_path.join("dotfiles/zsh/.zshrc"));
+        assert_eq!(foreign[0].manager, Some(ForeignManager::Stow));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("/home/u/./a/../dotfiles/.zshrc")),
+            PathBuf::from("/home/u/dotfiles/.zshrc")
+        );
+    }
+}
@@ -0,0 +1,636 @@
+//! Storage repository structure checks for `dotstate fsck`.
+//!
+//! Where the doctor checks the active profile, fsck checks the whole layout
+//! of the storage repository against the manifest: every profile directory
+//! exists and holds the files the manifest lists, no directory or file sits
+//! in the repository that no profile accounts for, `common/` is intact, and
+//! `symlinks.json` in the data directory only tracks files that exist in the
+//! active profile. Most issues come with a [`Repair`] the command offers.
+
+use crate::utils::aliases::ALIASES_FILE;
+use crate::utils::bootstrap::{BOOTSTRAP_DIR, BOOTSTRAP_SCRIPT};
+use crate::utils::devcontainer::SCRIPT_PATH as DEVCONTAINER_SCRIPT;
+use crate::utils::env_vars::ENV_FILE;
+use crate::utils::machine_registry::MACHINES_FILE;
+use crate::utils::repo_gitignore::GITIGNORE;
+use crate::utils::repo_readme::README;
+use crate::utils::shell_snippets::SNIPPETS_DIR;
+use crate::utils::sops::SOPS_CONFIG;
+use crate::utils::symlink_manager::SymlinkTracking;
+use crate::utils::ProfileManifest;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of the files shared by every profile
+const COMMON_DIR: &str = "common";
+
+/// Name of the tracking file in the data directory
+const TRACKING_FILE: &str = "symlinks.json";
+
+/// Files dotstate (or git) keeps at the repository root
+const ROOT_FILES: &[&str] = &[
+    ".dotstate-profiles.toml",
+    GITIGNORE,
+    ".gitattributes",
+    README,
+    MACHINES_FILE,
+    SOPS_CONFIG,
+    DEVCONTAINER_SCRIPT,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A fix for an [`Issue`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Repair {
+    /// Rebuild the manifest from the profile directories, keeping the old
+    /// one as `.dotstate-profiles.toml.bak`
+    RebuildManifest,
+    /// Create the missing directory of a profile
+    CreateProfileDir(String),
+    /// Add a directory that isn't in the manifest as a profile, with the
+    /// files it holds
+    AddProfile(String),
+    /// Remove a file from a profile's (or `common`'s) list in the manifest
+    DropManifestEntry { profile: String, file: String },
+    /// Delete a path in the repository nothing accounts for
+    RemoveStray(PathBuf),
+    /// Remove the tracking entry for a target
+    DropTrackingEntry(PathBuf),
+    /// Keep only the first tracking entry of each target
+    DedupTracking,
+    /// Move an unreadable tracking file aside; activating rebuilds it
+    ResetTracking,
+}
+
+impl Repair {
+    #[must_use]
+    pub fn description(&self) -> String {
+        match self {
+            Repair::RebuildManifest => "Rebuild the manifest from the profile directories".into(),
+            Repair::CreateProfileDir(name) => format!("Create the '{name}' directory"),
+            Repair::AddProfile(name) => format!("Add '{name}' to the manifest as a profile"),
+            Repair::DropManifestEntry { profile, file } => {
+                format!("Remove '{file}' from '{profile}' in the manifest")
+            }
+            Repair::RemoveStray(path) => format!("Delete {}", path.display()),
+            Repair::DropTrackingEntry(target) => {
+                format!("Stop tracking {}", target.display())
+            }
+            Repair::DedupTracking => "Remove duplicate tracking entries".into(),
+            Repair::ResetTracking => {
+                format!("Move {TRACKING_FILE} aside (re-activate the profile to rebuild it)")
+            }
+        }
+    }
+}
+
+/// A problem with the repository layout or the tracking file.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+    /// What the issue is about: a profile, `common`, `manifest` or `tracking`
+    pub location: String,
+    pub message: String,
+    pub repair: Option<Repair>,
+}
+
+impl Issue {
+    fn new(severity: Severity, location: &str, message: String, repair: Option<Repair>) -> Self {
+        Self {
+            severity,
+            location: location.to_string(),
+            message,
+            repair,
+        }
+    }
+}
+
+/// Check the repository at `repo_path` and the tracking file in `data_dir`.
+#[must_use]
+pub fn check(repo_path: &Path, data_dir: &Path) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    let manifest_path = ProfileManifest::manifest_path(repo_path);
+    let manifest = if manifest_path.exists() {
+        match ProfileManifest::load(repo_path) {
+            Ok(manifest) => Some(manifest),
+            Err(e) => {
+                issues.push(Issue::new(
+                    Severity::Error,
+                    "manifest",
+                    format!("Can't read the manifest: {e:#}"),
+                    Some(Repair::RebuildManifest),
+                ));
+                None
+            }
+        }
+    } else {
+        issues.push(Issue::new(
+            Severity::Error,
+            "manifest",
+            "No .dotstate-profiles.toml in the repository".to_string(),
+            Some(Repair::RebuildManifest),
+        ));
+        None
+    };
+
+    if let Some(manifest) = &manifest {
+        check_manifest(manifest, &mut issues);
+        check_profiles(repo_path, manifest, &mut issues);
+        check_common(repo_path, manifest, &mut issues);
+        check_root(repo_path, manifest, &mut issues);
+    }
+    check_tracking(repo_path, data_dir, manifest.as_ref(), &mut issues);
+    issues
+}
+
+fn check_manifest(manifest: &ProfileManifest, issues: &mut Vec<Issue>) {
+    let mut seen = HashSet::new();
+    for profile in &manifest.profiles {
+        if !seen.insert(profile.name.as_str()) {
+            issues.push(Issue::new(
+                Severity::Error,
+                "manifest",
+                format!("Profile '{}' is listed more than once", profile.name),
+                None,
+            ));
+        }
+    }
+    if let Err(e) = manifest.validate_inheritance() {
+        issues.push(Issue::new(
+            Severity::Error,
+            "manifest",
+            format!("{e:#}"),
+            None,
+        ));
+    }
+}
+
+fn check_profiles(repo_path: &Path, manifest: &ProfileManifest, issues: &mut Vec<Issue>) {
+    for profile in &manifest.profiles {
+        let dir = repo_path.join(&profile.name);
+        if dir.exists() && !dir.is_dir() {
+            issues.push(Issue::new(
+                Severity::Error,
+                &profile.name,
+                format!("{} is a file, not a profile directory", dir.display()),
+                None,
+            ));
+            continue;
+        }
+        if !dir.exists() {
+            issues.push(Issue::new(
+                Severity::Error,
+                &profile.name,
+                "Profile directory is missing".to_string(),
+                Some(Repair::CreateProfileDir(profile.name.clone())),
+            ));
+        }
+        check_listed_files(&dir, &profile.name, &profile.synced_files, issues);
+        check_strays(&dir, &profile.name, &profile.synced_files, true, issues);
+    }
+}
+
+fn check_common(repo_path: &Path, manifest: &ProfileManifest, issues: &mut Vec<Issue>) {
+    let dir = repo_path.join(COMMON_DIR);
+    if dir.exists() && !dir.is_dir() {
+        issues.push(Issue::new(
+            Severity::Error,
+            COMMON_DIR,
+            format!("{} is a file, not a directory", dir.display()),
+            None,
+        ));
+        return;
+    }
+    let files = &manifest.common.synced_files;
+    if !dir.exists() && !files.is_empty() {
+        issues.push(Issue::new(
+            Severity::Error,
+            COMMON_DIR,
+            format!(
+                "Directory is missing, {} common file(s) are lost",
+                files.len()
+            ),
+            None,
+        ));
+    }
+    check_listed_files(&dir, COMMON_DIR, files, issues);
+    check_strays(&dir, COMMON_DIR, files, false, issues);
+}
+
+/// Files the manifest lists that aren't in `dir`
+fn check_listed_files(dir: &Path, location: &str, files: &[String], issues: &mut Vec<Issue>) {
+    for file in files {
+        if dir.join(file).symlink_metadata().is_err() {
+            issues.push(Issue::new(
+                Severity::Error,
+                location,
+                format!("'{file}' is in the manifest but not in the repository"),
+                Some(Repair::DropManifestEntry {
+                    profile: location.to_string(),
+                    file: file.clone(),
+                }),
+            ));
+        }
+    }
+}
+
+/// Paths in `dir` that aren't a listed file, inside one, or on the way to
+/// one. Bootstrap scripts are expected in profile directories, shell
+/// snippets and aliases in those and in common.
+fn check_strays(
+    dir: &Path,
+    location: &str,
+    files: &[String],
+    is_profile: bool,
+    issues: &mut Vec<Issue>,
+) {
+    let mut strays = Vec::new();
+    collect_strays(dir, "", files, is_profile, &mut strays);
+    strays.sort();
+    for path in strays {
+        issues.push(Issue::new(
+            Severity::Warning,
+            location,
+            format!("'{}' isn't listed in the manifest", path.display()),
+            Some(Repair::RemoveStray(path)),
+        ));
+    }
+}
+
+/// Whether `name`, at the top of a profile directory (or of common), is
+/// dotstate's own: bootstrap scripts, shell snippets and aliases
+fn is_dotstate_entry(name: &str, is_profile: bool) -> bool {
+    (is_profile && (name == BOOTSTRAP_SCRIPT || name == BOOTSTRAP_DIR))
+        || name == SNIPPETS_DIR
+        || name == ALIASES_FILE
+        || name == ENV_FILE
+}
+
+/// Whether the file at `relative` in a profile directory is dotstate's own
+fn is_dotstate_file(relative: &str) -> bool {
+    let top = relative.split('/').next().unwrap_or(relative);
+    is_dotstate_entry(top, true)
+}
+
+fn collect_strays(
+    dir: &Path,
+    prefix: &str,
+    files: &[String],
+    is_profile: bool,
+    strays: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative = format!("{prefix}{name}");
+        if prefix.is_empty() && is_dotstate_entry(&name, is_profile) {
+            continue;
+        }
+        let listed = files
+            .iter()
+            .any(|f| *f == relative || relative.starts_with(&format!("{f}/")));
+        if listed {
+            continue;
+        }
+        let leads_to_listed = files.iter().any(|f| f.starts_with(&format!("{relative}/")));
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if leads_to_listed && is_dir {
+            collect_strays(
+                &entry.path(),
+                &format!("{relative}/"),
+                files,
+                is_profile,
+                strays,
+            );
+        } else {
+            strays.push(entry.path());
+        }
+    }
+}
+
+/// Directories that aren't profiles and files dotstate doesn't keep at the root
+fn check_root(repo_path: &Path, manifest: &ProfileManifest, issues: &mut Vec<Issue>) {
+    let Ok(entries) = fs::read_dir(repo_path) else {
+        return;
+    };
+    let mut found: Vec<(String, bool)> = entries
+        .flatten()
+        .map(|e| {
+            let is_dir = e.file_type().is_ok_and(|t| t.is_dir());
+            (e.file_name().to_string_lossy().to_string(), is_dir)
+        })
+        .collect();
+    found.sort();
+    for (name, is_dir) in found {
+        if is_dir {
+            if name.starts_with('.') || name == COMMON_DIR || manifest.has_profile(&name) {
+                continue;
+            }
+            issues.push(Issue::new(
+                Severity::Warning,
+                &name,
+                "Directory isn't a profile in the manifest".to_string(),
+                Some(Repair::AddProfile(name.clone())),
+            ));
+        } else if !ROOT_FILES.contains(&name.as_str()) && !name.starts_with("LICENSE") {
+            issues.push(Issue::new(
+                Severity::Warning,
+                "repository",
+                format!("'{name}' at the repository root belongs to no profile"),
+                Some(Repair::RemoveStray(repo_path.join(&name))),
+            ));
+        }
+    }
+}
+
+fn load_tracking(data_dir: &Path) -> Result<Option<SymlinkTracking>> {
+    let path = data_dir.join(TRACKING_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read_to_string(&path).context("Failed to read tracking file")?;
+    let tracking = serde_json::from_str(&data).context("Failed to parse tracking file")?;
+    Ok(Some(tracking))
+}
+
+fn save_tracking(data_dir: &Path, tracking: &SymlinkTracking) -> Result<()> {
+    let path = data_dir.join(TRACKING_FILE);
+    let temp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(tracking).context("Failed to serialize tracking")?;
+    fs::write(&temp_path, json).context("Failed to write temp tracking file")?;
+    fs::rename(&temp_path, &path).context("Failed to rename temp tracking file")?;
+    Ok(())
+}
+
+fn check_tracking(
+    repo_path: &Path,
+    data_dir: &Path,
+    manifest: Option<&ProfileManifest>,
+    issues: &mut Vec<Issue>,
+) {
+    let tracking = match load_tracking(data_dir) {
+        Ok(Some(tracking)) => tracking,
+        Ok(None) => return,
+        Err(e) => {
+            issues.push(Issue::new(
+                Severity::Error,
+                "tracking",
+                format!("{e:#}"),
+                Some(Repair::ResetTracking),
+            ));
+            return;
+        }
+    };
+
+    // Directories the active profile's files come from
+    let allowed_dirs: Option<Vec<PathBuf>> = manifest
+        .filter(|_| !tracking.active_profile.is_empty())
+        .and_then(|m| m.inheritance_chain(&tracking.active_profile).ok())
+        .map(|chain| {
+            chain
+                .iter()
+                .map(|name| repo_path.join(name))
+                .chain([repo_path.join(COMMON_DIR)])
+                .collect()
+        });
+
+    let mut seen_targets = HashSet::new();
+    let mut duplicates = false;
+    for tracked in &tracking.symlinks {
+        if !seen_targets.insert(&tracked.target) {
+            duplicates = true;
+            continue;
+        }
+        let target = tracked.target.display();
+        let drop = Some(Repair::DropTrackingEntry(tracked.target.clone()));
+        if tracked.source.symlink_metadata().is_err() {
+            issues.push(Issue::new(
+                Severity::Error,
+                "tracking",
+                format!(
+                    "{target} points at {}, which isn't in the repository",
+                    tracked.source.display()
+                ),
+                drop,
+            ));
+        } else if allowed_dirs
+            .as_ref()
+            .is_some_and(|dirs| !dirs.iter().any(|d| tracked.source.starts_with(d)))
+        {
+            issues.push(Issue::new(
+                Severity::Warning,
+                "tracking",
+                format!(
+                    "{target} comes from {}, outside the active profile '{}'",
+                    tracked.source.display(),
+                    tracking.active_profile
+                ),
+                drop,
+            ));
+        } else if !tracked.decrypted
+            && !tracked.rendered
+            && !tracked.block
+            && fs::read_link(&tracked.target).ok().as_ref() != Some(&tracked.source)
+        {
+            issues.push(Issue::new(
+                Severity::Warning,
+                "tracking",
+                format!("{target} is tracked but isn't a symlink into the repository"),
+                drop,
+            ));
+        }
+    }
+    if duplicates {
+        issues.push(Issue::new(
+            Severity::Warning,
+            "tracking",
+            "Some targets are tracked more than once".to_string(),
+            Some(Repair::DedupTracking),
+        ));
+    }
+}
+
+/// Files in `dir`, relative to it
+fn files_in(dir: &Path) -> Vec<String> {
+    fn walk(base: &Path, dir: &Path, files: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                walk(base, &path, files);
+            } else if let Ok(relative) = path.strip_prefix(base) {
+                files.push(relative.to_string_lossy().to_string());
+            }
+        }
+    }
+    let mut files = Vec::new();
+    walk(dir, dir, &mut files);
+    files.sort();
+    files
+}
+
+/// Apply `repair` to the repository at `repo_path` and the tracking file in
+/// `data_dir`.
+pub fn apply(repo_path: &Path, data_dir: &Path, repair: &Repair) -> Result<()> {
+    match repair {
+        Repair::RebuildManifest => {
+            let path = ProfileManifest::manifest_path(repo_path);
+            if path.exists() {
+                fs::rename(&path, path.with_extension("toml.bak"))
+                    .context("Failed to back up the manifest")?;
+            }
+            let mut manifest = ProfileManifest::backfill_from_repo(repo_path)?;
+            for profile in &mut manifest.profiles {
+                profile.synced_files = files_in(&repo_path.join(&profile.name))
+                    .into_iter()
+                    .filter(|f| !is_dotstate_file(f))
+                    .collect();
+            }
+            manifest.save(repo_path)
+        }
+        Repair::CreateProfileDir(name) => fs::create_dir_all(repo_path.join(name))
+            .with_context(|| format!("Failed to create the '{name}' directory")),
+        Repair::AddProfile(name) => {
+            let mut manifest = ProfileManifest::load(repo_path)?;
+            manifest.add_profile(name.clone(), None);
+            let files: Vec<String> = files_in(&repo_path.join(name))
+                .into_iter()
+                .filter(|f| !is_dotstate_file(f))
+                .collect();
+            manifest.update_synced_files(name, files)?;
+            manifest.save(repo_path)
+        }
+        Repair::DropManifestEntry { profile, file } => {
+            let mut manifest = ProfileManifest::load(repo_path)?;
+            if profile == COMMON_DIR {
+                manifest.remove_common_file(file);
+            } else if let Some(info) = manifest.profiles.iter_mut().find(|p| p.name == *profile) {
+                info.synced_files.retain(|f| f != file);
+            }
+            manifest.save(repo_path)
+        }
+        Repair::RemoveStray(path) => if path.is_dir() && !path.is_symlink() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        }
+        .with_context(|| format!("Failed to delete {}", path.display())),
+        Repair::DropTrackingEntry(target) => {
+            if let Some(mut tracking) = load_tracking(data_dir)? {
+                tracking.symlinks.retain(|s| s.target != *target);
+                save_tracking(data_dir, &tracking)?;
+            }
+            Ok(())
+        }
+        Repair::DedupTracking => {
+            if let Some(mut tracking) = load_tracking(data_dir)? {
+                let mut seen = HashSet::new();
+                tracking.symlinks.retain(|s| seen.insert(s.target.clone()));
+                save_tracking(data_dir, &tracking)?;
+            }
+            Ok(())
+        }
+        Repair::ResetTracking => {
+            let path = data_dir.join(TRACKING_FILE);
+            fs::rename(&path, path.with_extension("json.bak"))
+                .context("Failed to move the tracking file aside")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        let mut manifest = ProfileManifest::default();
+        manifest.add_profile("Work".to_string(), None);
+        manifest.add_profile("Home".to_string(), None);
+        manifest.profiles[0].synced_files = vec![".zshrc".into(), ".config/nvim".into()];
+        manifest.profiles[1].synced_files = vec![".vimrc".into()];
+        manifest.common.synced_files = vec![".gitconfig".into()];
+        manifest.save(repo).unwrap();
+
+        fs::create_dir_all(repo.join("Work/.config/nvim")).unwrap();
+        fs::write(repo.join("Work/.zshrc"), "").unwrap();
+        fs::write(repo.join("Work/.config/nvim/init.lua"), "").unwrap();
+        fs::write(repo.join("Work/install.sh"), "").unwrap();
+        fs::create_dir_all(repo.join("common")).unwrap();
+        fs::write(repo.join("common/.gitconfig"), "").unwrap();
+        fs::write(repo.join(GITIGNORE), "").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_check_finds_layout_issues() {
+        let temp = repo();
+        let repo = temp.path();
+        fs::write(repo.join("Work/.config/starship.toml"), "").unwrap();
+        fs::create_dir_all(repo.join("Laptop")).unwrap();
+        fs::write(repo.join("Laptop/.bashrc"), "").unwrap();
+        let data = TempDir::new().unwrap();
+
+        let repairs: Vec<Repair> = check(repo, data.path())
+            .into_iter()
+            .filter_map(|i| i.repair)
+            .collect();
+        assert_eq!(
+            repairs,
+            [
+                Repair::RemoveStray(repo.join("Work/.config/starship.toml")),
+                Repair::CreateProfileDir("Home".into()),
+                Repair::DropManifestEntry {
+                    profile: "Home".into(),
+                    file: ".vimrc".into()
+                },
+                Repair::AddProfile("Laptop".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repairs_fix_issues() {
+        let temp = repo();
+        let repo = temp.path();
+        fs::create_dir_all(repo.join("Laptop")).unwrap();
+        fs::write(repo.join("Laptop/.bashrc"), "").unwrap();
+        let data = TempDir::new().unwrap();
+        fs::write(data.path().join(TRACKING_FILE), "{ not json").unwrap();
+
+        let issues = check(repo, data.path());
+        assert!(issues
+            .iter()
+            .any(|i| i.repair == Some(Repair::ResetTracking)));
+        for repair in issues.iter().filter_map(|i| i.repair.as_ref()) {
+            apply(repo, data.path(), repair).unwrap();
+        }
+
+        assert!(check(repo, data.path()).is_empty());
+        let manifest = ProfileManifest::load(repo).unwrap();
+        assert_eq!(
+            manifest
+                .profiles
+                .iter()
+                .find(|p| p.name == "Laptop")
+                .unwrap()
+                .synced_files,
+            [".bashrc"]
+        );
+        assert!(repo.join("Home").is_dir());
+    }
+}
@@ -0,0 +1,267 @@
+//! Git pre-commit hook for the storage repository.
+//!
+//! `dotstate pre-commit --install` puts a `pre-commit` hook into the storage
+//! clone that runs `dotstate pre-commit` before every commit, including the
+//! ones made with git directly. It checks what is staged:
+//!
+//! - no added or changed file holds something that looks like a secret (see
+//!   [`secret_mask::find_secrets`])
+//! - the staged manifest parses, lists each profile once, has no broken
+//!   inheritance, and every file it lists is in the commit
+//!
+//! `git commit --no-verify` skips the hook for a commit.
+
+use crate::utils::secret_mask;
+use crate::utils::ssh_remote::shell_quote;
+use crate::utils::ProfileManifest;
+use anyhow::{bail, Context, Result};
+use git2::{Delta, Repository};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Second line of an installed hook, after the shebang
+const MARKER: &str = "# Installed by dotstate; remove with `dotstate pre-commit --uninstall`.";
+
+/// Name of the manifest at the repository root
+const MANIFEST_FILE: &str = ".dotstate-profiles.toml";
+
+/// Directory of the files shared by every profile
+const COMMON_DIR: &str = "common";
+
+/// Something in the staged changes that should keep them from being committed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// Path in the repository
+    pub path: String,
+    /// Line the problem is on, when it is about one
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Where the repository's pre-commit hook lives, honoring `core.hooksPath`
+pub fn hook_path(repo_path: &Path) -> Result<PathBuf> {
+    let repo = Repository::open(repo_path)
+        .with_context(|| format!("{} is not a git repository", repo_path.display()))?;
+    let hooks_dir = repo
+        .config()
+        .and_then(|config| config.get_path("core.hooksPath"))
+        .map(|dir| {
+            if dir.is_relative() {
+                repo_path.join(dir)
+            } else {
+                dir
+            }
+        })
+        .unwrap_or_else(|_| repo.path().join("hooks"));
+    Ok(hooks_dir.join("pre-commit"))
+}
+
+/// Hook script running `dotstate pre-commit`, from `PATH` or at `exe`
+#[must_use]
+pub fn hook_script(exe: &Path) -> String {
+    let exe = shell_quote(&exe.display().to_string());
+    format!(
+        "#!/bin/sh\n{MARKER}\nDOTSTATE=$(command -v dotstate || echo {exe})\nexec \"$DOTSTATE\" pre-commit\n"
+    )
+}
+
+/// Whether the hook at `path` was installed by dotstate
+fn is_ours(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.lines().nth(1) == Some(MARKER))
+}
+
+/// Whether dotstate's hook is installed in the repository
+#[must_use]
+pub fn is_installed(repo_path: &Path) -> bool {
+    hook_path(repo_path).is_ok_and(|path| is_ours(&path))
+}
+
+/// Install the hook, calling `exe` when dotstate isn't on `PATH`. Another
+/// pre-commit hook is only replaced with `force`. Returns the hook's path.
+pub fn install(repo_path: &Path, exe: &Path, force: bool) -> Result<PathBuf> {
+    let path = hook_path(repo_path)?;
+    if path.exists() && !is_ours(&path) && !force {
+        bail!(
+            "{} already exists (use --force to replace it)",
+            path.display()
+        );
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, hook_script(exe))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", path.display()))?;
+    }
+    Ok(path)
+}
+
+/// Remove dotstate's hook. Returns whether there was one; another tool's
+/// hook is left alone.
+pub fn uninstall(repo_path: &Path) -> Result<bool> {
+    let path = hook_path(repo_path)?;
+    if !is_ours(&path) {
+        return Ok(false);
+    }
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(true)
+}
+
+/// Check the changes staged in the repository containing `path`
+pub fn check_staged(path: &Path) -> Result<Vec<Problem>> {
+    let repo = Repository::discover(path)
+        .with_context(|| format!("{} is not in a git repository", path.display()))?;
+    let index = repo.index().context("Failed to read the index")?;
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), Some(&index), None)
+        .context("Failed to diff the index")?;
+
+    let mut problems = Vec::new();
+    for delta in diff.deltas() {
+        if !matches!(
+            delta.status(),
+            Delta::Added | Delta::Modified | Delta::Renamed | Delta::Copied
+        ) {
+            continue;
+        }
+        let file = delta.new_file();
+        let Some(path) = file.path() else { continue };
+        let Ok(blob) = repo.find_blob(file.id()) else {
+            continue;
+        };
+        if blob.is_binary() {
+            continue;
+        }
+        let content = String::from_utf8_lossy(blob.content());
+        for line in secret_mask::find_secrets(&content) {
+            problems.push(Problem {
+                path: path.display().to_string(),
+                line: Some(line),
+                message: "Looks like a secret".to_string(),
+            });
+        }
+    }
+
+    let staged: HashSet<String> = index
+        .iter()
+        .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+        .collect();
+    if let Some(entry) = index.get_path(Path::new(MANIFEST_FILE), 0) {
+        let blob = repo
+            .find_blob(entry.id)
+            .context("Failed to read the staged manifest")?;
+        problems.extend(check_manifest(
+            &String::from_utf8_lossy(blob.content()),
+            &staged,
+        ));
+    }
+    Ok(problems)
+}
+
+/// Problems with the manifest `content`, whose files should be among `staged`
+fn check_manifest(content: &str, staged: &HashSet<String>) -> Vec<Problem> {
+    let problem = |message: String| Problem {
+        path: MANIFEST_FILE.to_string(),
+        line: None,
+        message,
+    };
+    let manifest: ProfileManifest = match toml::from_str(content) {
+        Ok(manifest) => manifest,
+        Err(e) => return vec![problem(format!("Can't parse the manifest: {e}"))],
+    };
+
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    for profile in &manifest.profiles {
+        if !seen.insert(profile.name.as_str()) {
+            problems.push(problem(format!(
+                "Profile '{}' is listed more than once",
+                profile.name
+            )));
+        }
+    }
+    if let Err(e) = manifest.validate_inheritance() {
+        problems.push(problem(format!("{e:#}")));
+    }
+
+    let listed = manifest
+        .profiles
+        .iter()
+        .map(|p| (p.name.as_str(), &p.synced_files))
+        .chain([(COMMON_DIR, &manifest.common.synced_files)]);
+    for (dir, files) in listed {
+        for file in files {
+            let path = format!("{dir}/{file}");
+            let prefix = format!("{path}/");
+            if !staged.contains(&path) && !staged.iter().any(|p| p.starts_with(&prefix)) {
+                problems.push(problem(format!(
+                    "'{file}' is in the manifest but {path} isn't in the commit"
+                )));
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn stage(repo: &Repository, dir: &Path, path: &str, content: &str) {
+        let full = dir.join(path);
+        fs::create_dir_all(full.parent().unwrap()).unwrap();
+        fs::write(&full, content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+    }
+
+    #[test]
+    fn test_install_and_check_staged_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let repo = Repository::init(dir).unwrap();
+
+        // Another tool's hook is kept unless forced
+        let hook = hook_path(dir).unwrap();
+        fs::create_dir_all(hook.parent().unwrap()).unwrap();
+        fs::write(&hook, "#!/bin/sh\nexit 0\n").unwrap();
+        assert!(install(dir, Path::new("/usr/bin/dotstate"), false).is_err());
+        assert!(!uninstall(dir).unwrap());
+        install(dir, Path::new("/usr/bin/dotstate"), true).unwrap();
+        assert!(is_installed(dir));
+        assert!(fs::read_to_string(&hook)
+            .unwrap()
+            .contains("echo '/usr/bin/dotstate'"));
+
+        stage(&repo, dir, "work/.zshrc", "export EDITOR=vim\n");
+        stage(
+            &repo,
+            dir,
+            "work/.config/gh/hosts.yml",
+            "github.com:\n    oauth_token: gho_abcdefghijklmnop1234\n",
+        );
+        stage(
+            &repo,
+            dir,
+            MANIFEST_FILE,
+            "[[profiles]]\nname = \"work\"\nsynced_files = [\".zshrc\", \".config/gh\", \".vimrc\"]\n",
+        );
+        let problems = check_staged(dir).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].path, "work/.config/gh/hosts.yml");
+        assert_eq!(problems[0].line, Some(2));
+        assert!(problems[1].message.contains("'.vimrc'"));
+
+        assert!(uninstall(dir).unwrap());
+        assert!(!hook.exists());
+    }
+}
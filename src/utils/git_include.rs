@@ -0,0 +1,128 @@
+//! `.gitconfig` include mode.
+//!
+//! Instead of symlinking a whole `.gitconfig`, a file listed in the
+//! manifest's `git_includes` keeps its shared settings (aliases, core,
+//! diff, ...) in the repository and the real file gets a managed block with
+//! an `include.path` pointing at it. Machine-local settings such as the
+//! `[user]` identity stay in the real file. The block goes at the top, so a
+//! local setting wins over a shared one.
+
+use crate::utils::managed_block;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// Sections left in the real file when a `.gitconfig` is split
+const LOCAL_SECTIONS: &[&str] = &["user", "credential", "include", "includeif"];
+
+/// The block body including `source`
+#[must_use]
+pub fn body(source: &Path) -> String {
+    format!("[include]\n\tpath = {}\n", source.display())
+}
+
+/// Name of the section a `[...]` header line opens, lowercased
+fn section_name(line: &str) -> Option<String> {
+    let inner = line.trim().strip_prefix('[')?;
+    let end = inner.find([' ', '"', ']', '.']).unwrap_or(inner.len());
+    Some(inner[..end].to_lowercase())
+}
+
+/// Split `content` into the settings to share and the ones to keep local.
+/// Identity, credential helpers and the file's own includes are local, as
+/// is anything before the first section.
+#[must_use]
+pub fn split(content: &str) -> (String, String) {
+    let mut shared = String::new();
+    let mut local = String::new();
+    let mut is_local = true;
+    for line in content.lines() {
+        if let Some(name) = section_name(line) {
+            is_local = LOCAL_SECTIONS.contains(&name.as_str());
+        }
+        let out = if is_local { &mut local } else { &mut shared };
+        out.push_str(line);
+        out.push('\n');
+    }
+    (shared, local)
+}
+
+/// Whether `target` already includes `source`
+#[must_use]
+pub fn is_installed(source: &Path, target: &Path) -> bool {
+    !target.is_symlink()
+        && fs::read_to_string(target)
+            .ok()
+            .and_then(|c| managed_block::extract(&c))
+            .is_some_and(|current| current == body(source))
+}
+
+/// Point `target` at `source` with an include block, creating the file if
+/// needed. A symlink at `target` pointing at `source` (the file was synced
+/// whole before) is replaced with a real file.
+pub fn install(source: &Path, target: &Path) -> Result<()> {
+    if target.is_symlink() && fs::canonicalize(target).ok() == fs::canonicalize(source).ok() {
+        fs::remove_file(target)
+            .with_context(|| format!("Failed to remove symlink {}", target.display()))?;
+    }
+    let existing = fs::read_to_string(target).unwrap_or_default();
+    let updated = if managed_block::has_block(&existing) || existing.is_empty() {
+        managed_block::apply(&existing, &body(source), "#")
+    } else {
+        format!(
+            "{}\n{existing}",
+            managed_block::apply("", &body(source), "#")
+        )
+    };
+    if updated != existing {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, updated)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+        info!("Included {:?} from {:?}", source, target);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let content = "# my config\n[user]\n\tname = Me\n\temail = me@work.com\n[alias]\n\tco = checkout\n[credential \"https://github.com\"]\n\thelper = store\n[core]\n\teditor = vim\n";
+        let (shared, local) = split(content);
+        assert_eq!(shared, "[alias]\n\tco = checkout\n[core]\n\teditor = vim\n");
+        assert_eq!(
+            local,
+            "# my config\n[user]\n\tname = Me\n\temail = me@work.com\n[credential \"https://github.com\"]\n\thelper = store\n"
+        );
+    }
+
+    #[test]
+    fn test_install_at_top() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source = temp.path().join("repo/.gitconfig");
+        let target = temp.path().join(".gitconfig");
+        fs::create_dir_all(source.parent().unwrap()).unwrap();
+        fs::write(&source, "[alias]\n\tco = checkout\n").unwrap();
+        fs::write(&target, "[user]\n\tname = Me\n").unwrap();
+
+        install(&source, &target).unwrap();
+        let content = fs::read_to_string(&target).unwrap();
+        assert_eq!(
+            content,
+            format!(
+                "# >>> dotstate >>>\n[include]\n\tpath = {}\n# <<< dotstate <<<\n\n[user]\n\tname = Me\n",
+                source.display()
+            )
+        );
+        assert!(is_installed(&source, &target));
+
+        // Installing again leaves it alone
+        install(&source, &target).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), content);
+    }
+}
@@ -0,0 +1,303 @@
+//! Lifecycle hooks written as Rhai scripts.
+//!
+//! With `hooks_enabled = true` in `config.toml`, every `*.rhai` file in the
+//! `hooks/` directory of the config directory is loaded when a file is
+//! added, a profile is activated or a sync finishes, and its `on_add`,
+//! `on_activate` or `on_sync` function is called with a map describing the
+//! event (see [`HookEvent`]). Scripts are machine-local and never synced.
+//!
+//! Scripts run sandboxed: Rhai has no access to processes or the network,
+//! `read_file` and `write_file` only reach files under the home directory,
+//! and a script is stopped after [`MAX_OPERATIONS`]. `print` writes to the
+//! log. A failing hook is logged and never fails the operation.
+
+use crate::utils::sync_history::SyncRecord;
+use anyhow::{anyhow, Context, Result};
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+/// Name of the hook directory in the config directory
+const HOOK_DIR: &str = "hooks";
+
+/// Operations a hook may run before it is stopped
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Whether `hooks_enabled` is set in `config.toml`
+static HOOKS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable hooks, from `hooks_enabled` in `config.toml`
+pub fn set_enabled(enabled: bool) {
+    HOOKS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// A lifecycle event hooks are called for.
+#[derive(Debug, Clone, Copy)]
+pub enum HookEvent<'a> {
+    /// A file was added to sync (`path`, relative to home)
+    Add { path: &'a str },
+    /// The profile's symlinks were created (`created` of them)
+    Activate { created: usize },
+    /// A sync, pull, force sync or reconcile finished
+    Sync(&'a SyncRecord),
+}
+
+impl HookEvent<'_> {
+    /// Script function called for the event
+    #[must_use]
+    pub fn function(&self) -> &'static str {
+        match self {
+            HookEvent::Add { .. } => "on_add",
+            HookEvent::Activate { .. } => "on_activate",
+            HookEvent::Sync(_) => "on_sync",
+        }
+    }
+
+    /// The map passed to the script: `profile`, `repo_path`, `home` and
+    /// `files` (the profile's resolved files, relative to home), plus the
+    /// event's own fields
+    fn to_map(self, repo_path: &Path, profile: &str, home: &Path) -> Map {
+        let mut map = Map::new();
+        map.insert(
+            "event".into(),
+            self.function().trim_start_matches("on_").into(),
+        );
+        map.insert("profile".into(), profile.into());
+        map.insert(
+            "repo_path".into(),
+            repo_path.to_string_lossy().to_string().into(),
+        );
+        map.insert("home".into(), home.to_string_lossy().to_string().into());
+        let files: Array = crate::utils::ProfileManifest::load_or_backfill(repo_path)
+            .and_then(|manifest| manifest.resolve_files(profile))
+            .map(|files| {
+                files
+                    .into_iter()
+                    .map(|file| file.relative_path.into())
+                    .collect()
+            })
+            .unwrap_or_default();
+        map.insert("files".into(), files.into());
+
+        match self {
+            HookEvent::Add { path } => {
+                map.insert("path".into(), path.into());
+            }
+            HookEvent::Activate { created } => {
+                map.insert(
+                    "created".into(),
+                    i64::try_from(created).unwrap_or(i64::MAX).into(),
+                );
+            }
+            HookEvent::Sync(record) => {
+                map.insert("kind".into(), record.kind.label().into());
+                map.insert("success".into(), record.success.into());
+                map.insert(
+                    "error".into(),
+                    record.error.clone().map_or(Dynamic::UNIT, Into::into),
+                );
+                let entries = |entries: &[String]| -> Array {
+                    entries.iter().map(|e| e.clone().into()).collect()
+                };
+                map.insert("committed".into(), entries(&record.committed).into());
+                map.insert("pulled".into(), entries(&record.pulled).into());
+            }
+        }
+        map
+    }
+}
+
+/// Where hook scripts live
+#[must_use]
+pub fn hook_dir() -> PathBuf {
+    crate::utils::get_config_dir().join(HOOK_DIR)
+}
+
+/// Hook scripts, by name
+fn scripts(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Resolve a path a script asked for: `~/` and relative paths are under
+/// `home`, and nothing outside `home` or with `..` is allowed
+fn sandboxed_path(path: &str, home: &Path) -> Option<PathBuf> {
+    let path = Path::new(path);
+    let path = match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) if path.is_relative() => home.join(path),
+        Err(_) => path.to_path_buf(),
+    };
+    let escapes = path.components().any(|c| c == Component::ParentDir);
+    (!escapes && path.starts_with(home)).then_some(path)
+}
+
+/// Engine with the sandbox limits and the file functions, reaching files
+/// under `home`
+fn engine(home: &Path) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(1 << 20);
+    engine.set_max_array_size(100_000);
+    engine.set_max_map_size(100_000);
+    engine.on_print(|text| info!("hook: {}", text));
+    engine.on_debug(|text, source, pos| info!("hook {:?} {}: {}", source, pos, text));
+
+    let home = home.to_path_buf();
+    let read_home = home.clone();
+    engine.register_fn(
+        "read_file",
+        move |path: &str| -> Result<String, Box<EvalAltResult>> {
+            let full = sandboxed_path(path, &read_home).ok_or_else(|| {
+                Box::<EvalAltResult>::from(format!("{path} is outside the home directory"))
+            })?;
+            std::fs::read_to_string(&full)
+                .map_err(|e| Box::<EvalAltResult>::from(format!("Failed to read {path}: {e}")))
+        },
+    );
+    engine.register_fn(
+        "write_file",
+        move |path: &str, content: &str| -> Result<(), Box<EvalAltResult>> {
+            let full = sandboxed_path(path, &home).ok_or_else(|| {
+                Box::<EvalAltResult>::from(format!("{path} is outside the home directory"))
+            })?;
+            std::fs::write(&full, content)
+                .map_err(|e| Box::<EvalAltResult>::from(format!("Failed to write {path}: {e}")))
+        },
+    );
+    engine
+}
+
+/// Call the event's function in the script at `path`, if it defines one.
+/// Returns whether it did.
+fn run_script(engine: &Engine, path: &Path, function: &str, event: &Map) -> Result<bool> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let ast = engine
+        .compile(&source)
+        .map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    if !ast
+        .iter_functions()
+        .any(|f| f.name == function && f.params.len() == 1)
+    {
+        return Ok(false);
+    }
+    // Only the hook runs, not the script's top-level statements
+    let options = CallFnOptions::new().eval_ast(false);
+    let _: Dynamic = engine
+        .call_fn_with_options(
+            options,
+            &mut Scope::new(),
+            &ast,
+            function,
+            (Dynamic::from_map(event.clone()),),
+        )
+        .map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    Ok(true)
+}
+
+/// Run the hooks for `event`. Does nothing unless hooks are enabled; errors
+/// are logged.
+pub fn fire(repo_path: &Path, profile: &str, event: HookEvent) {
+    if HOOKS_ENABLED.load(Ordering::Relaxed) {
+        run_in(
+            &hook_dir(),
+            &crate::utils::get_home_dir(),
+            repo_path,
+            profile,
+            event,
+        );
+    }
+}
+
+/// Run the hooks in `dir`, returning how many defined the event's function
+fn run_in(dir: &Path, home: &Path, repo_path: &Path, profile: &str, event: HookEvent) -> usize {
+    let scripts = scripts(dir);
+    if scripts.is_empty() {
+        return 0;
+    }
+    let engine = engine(home);
+    let map = event.to_map(repo_path, profile, home);
+    let function = event.function();
+    let mut ran = 0;
+    for script in scripts {
+        match run_script(&engine, &script, function, &map) {
+            Ok(true) => {
+                info!("Ran {} in {}", function, script.display());
+                ran += 1;
+            }
+            Ok(false) => {}
+            Err(e) => warn!("Hook {} failed: {e:#}", function),
+        }
+    }
+    ran
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sandboxed_paths() {
+        let home = Path::new("/home/me");
+        assert_eq!(
+            sandboxed_path("~/.zshrc", home),
+            Some(PathBuf::from("/home/me/.zshrc"))
+        );
+        assert_eq!(
+            sandboxed_path(".config/app", home),
+            Some(PathBuf::from("/home/me/.config/app"))
+        );
+        assert_eq!(sandboxed_path("/etc/passwd", home), None);
+        assert_eq!(sandboxed_path("~/../other/.zshrc", home), None);
+    }
+
+    #[test]
+    fn test_hooks_get_the_event() {
+        let temp_dir = TempDir::new().unwrap();
+        let home = temp_dir.path();
+        let dir = home.join("hooks");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("a_notes.rhai"),
+            r#"
+                write_file("top-level.txt", "ran");
+                fn on_add(event) {
+                    write_file("added.txt", event.event + " " + event.path + " " + event.profile);
+                }
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("b_escape.rhai"),
+            r#"fn on_add(event) { write_file("/etc/dotstate-hook", "x"); }"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("c_loop.rhai"), "fn on_add(event) { loop {} }").unwrap();
+
+        // The escaping and runaway hooks fail without stopping the others
+        let event = HookEvent::Add { path: ".zshrc" };
+        assert_eq!(run_in(&dir, home, home, "work", event), 1);
+        assert_eq!(
+            std::fs::read_to_string(home.join("added.txt")).unwrap(),
+            "add .zshrc work"
+        );
+        assert!(!home.join("top-level.txt").exists());
+
+        // No script handles activation
+        let event = HookEvent::Activate { created: 2 };
+        assert_eq!(run_in(&dir, home, home, "work", event), 0);
+    }
+}
@@ -0,0 +1,142 @@
+//! Inline image previews using terminal graphics protocols.
+//!
+//! The terminal is queried once at startup for kitty, iTerm2 or sixel
+//! support. When none is available, previews fall back to a text summary
+//! with the image dimensions and size.
+
+use image::ImageReader;
+use ratatui::layout::Rect;
+use ratatui::Frame;
+use ratatui_image::picker::{Picker, ProtocolType};
+use ratatui_image::protocol::StatefulProtocol;
+use ratatui_image::StatefulImage;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+/// Images larger than this are not decoded for inline display
+const MAX_INLINE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Graphics protocol picker, set by [`init`] when the terminal supports one
+static PICKER: Mutex<Option<Picker>> = Mutex::new(None);
+
+/// Last decoded image, so redraws don't decode the file again.
+/// `None` as the protocol records a file that failed to decode.
+static LOADED: Mutex<Option<(PathBuf, Option<StatefulProtocol>)>> = Mutex::new(None);
+
+/// Query the terminal for graphics support.
+///
+/// Must be called after entering the alternate screen and before reading
+/// terminal events, since the query reads the terminal's response from stdin.
+pub fn init() {
+    let picker = match Picker::from_query_stdio() {
+        Ok(picker) if picker.protocol_type() != ProtocolType::Halfblocks => {
+            info!(
+                "Inline image previews enabled ({:?})",
+                picker.protocol_type()
+            );
+            Some(picker)
+        }
+        Ok(_) => {
+            info!("Terminal has no graphics protocol, image previews use text");
+            None
+        }
+        Err(e) => {
+            debug!("Graphics protocol query failed: {}", e);
+            None
+        }
+    };
+    *PICKER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = picker;
+}
+
+/// Whether the terminal can display images inline
+#[must_use]
+pub fn is_supported() -> bool {
+    PICKER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .is_some()
+}
+
+/// Read image dimensions from the file header without decoding it
+#[must_use]
+pub fn dimensions(path: &Path) -> Option<(u32, u32)> {
+    ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// Render the image at `path` into `area`, scaled to fit.
+///
+/// Returns false when the terminal has no graphics support or the image
+/// can't be decoded, so the caller can show a text summary instead.
+pub fn render(frame: &mut Frame, area: Rect, path: &Path) -> bool {
+    if area.width == 0 || area.height == 0 {
+        return false;
+    }
+    let picker = PICKER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let Some(picker) = picker.as_ref() else {
+        return false;
+    };
+
+    let mut loaded = LOADED
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if loaded
+        .as_ref()
+        .is_none_or(|(loaded_path, _)| loaded_path != path)
+    {
+        let protocol = decode(path).map(|image| picker.new_resize_protocol(image));
+        *loaded = Some((path.to_path_buf(), protocol));
+    }
+
+    match loaded.as_mut().and_then(|(_, protocol)| protocol.as_mut()) {
+        Some(protocol) => {
+            frame.render_stateful_widget(StatefulImage::default(), area, protocol);
+            true
+        }
+        None => false,
+    }
+}
+
+fn decode(path: &Path) -> Option<image::DynamicImage> {
+    let size = std::fs::metadata(path).ok()?.len();
+    if size > MAX_INLINE_BYTES {
+        debug!("Skipping inline preview of {:?}: {} bytes", path, size);
+        return None;
+    }
+    match ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .decode()
+    {
+        Ok(image) => Some(image),
+        Err(e) => {
+            debug!("Failed to decode image {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimensions_from_header() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("wallpaper.png");
+        image::RgbImage::new(3, 2).save(&path).unwrap();
+
+        assert_eq!(dimensions(&path), Some((3, 2)));
+        assert_eq!(dimensions(&temp_dir.path().join("missing.png")), None);
+    }
+}
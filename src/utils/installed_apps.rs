@@ -0,0 +1,377 @@
+//! Detection of installed applications.
+//!
+//! Manage Files suggests the well-known config files found in the home
+//! directory. A config left behind by software that was uninstalled long ago
+//! isn't worth syncing, so each candidate that belongs to an application is
+//! matched against what is installed: a binary on `PATH`, a macOS app bundle,
+//! or a package known to Homebrew, apt (dpkg) or pacman. The package lists
+//! are only read when the cheaper checks come up empty, once per run.
+
+use std::cell::OnceCell;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+use tracing::debug;
+
+/// An application reading one of the candidate config files
+struct App {
+    /// Candidate paths (as in `DEFAULT_DOTFILES`) the application reads
+    candidates: &'static [&'static str],
+    /// Executables it installs
+    binaries: &'static [&'static str],
+    /// Package names in Homebrew, apt or pacman, when different from the binaries
+    packages: &'static [&'static str],
+    /// macOS app bundles, e.g. `Alacritty.app`
+    bundles: &'static [&'static str],
+}
+
+/// Applications behind the candidate config files. Candidates not listed
+/// (shell-agnostic files like `.profile`, `.ssh/config`) are always suggested.
+const APPS: &[App] = &[
+    App {
+        candidates: &[".zshrc", ".zprofile", ".zshenv", ".p10k.zsh", ".oh-my-zsh"],
+        binaries: &["zsh"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/fish"],
+        binaries: &["fish"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".vimrc"],
+        binaries: &["vim"],
+        packages: &[],
+        bundles: &["MacVim.app"],
+    },
+    App {
+        candidates: &[".config/nvim"],
+        binaries: &["nvim"],
+        packages: &["neovim"],
+        bundles: &["Neovide.app"],
+    },
+    App {
+        candidates: &[".emacs.d", ".config/emacs"],
+        binaries: &["emacs"],
+        packages: &[],
+        bundles: &["Emacs.app"],
+    },
+    App {
+        candidates: &[".config/helix"],
+        binaries: &["hx", "helix"],
+        packages: &["helix"],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/nano"],
+        binaries: &["nano"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".tmux.conf", ".config/tmux"],
+        binaries: &["tmux"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/zellij"],
+        binaries: &["zellij"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/screen"],
+        binaries: &["screen"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/alacritty"],
+        binaries: &["alacritty"],
+        packages: &[],
+        bundles: &["Alacritty.app"],
+    },
+    App {
+        candidates: &[".config/kitty"],
+        binaries: &["kitty"],
+        packages: &[],
+        bundles: &["kitty.app"],
+    },
+    App {
+        candidates: &[".config/wezterm"],
+        binaries: &["wezterm"],
+        packages: &[],
+        bundles: &["WezTerm.app"],
+    },
+    App {
+        candidates: &[".config/foot"],
+        binaries: &["foot"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/ghostty"],
+        binaries: &["ghostty"],
+        packages: &[],
+        bundles: &["Ghostty.app"],
+    },
+    App {
+        candidates: &[".config/starship.toml"],
+        binaries: &["starship"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/bat"],
+        binaries: &["bat", "batcat"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/ripgrep"],
+        binaries: &["rg"],
+        packages: &["ripgrep"],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/fd"],
+        binaries: &["fd", "fdfind"],
+        packages: &["fd-find"],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/eza"],
+        binaries: &["eza"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/direnv"],
+        binaries: &["direnv"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/gh/config.yml"],
+        binaries: &["gh"],
+        packages: &["github-cli"],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/lazygit"],
+        binaries: &["lazygit"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/yazi"],
+        binaries: &["yazi"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/atuin/config.toml"],
+        binaries: &["atuin"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".gnupg/gpg.conf", ".gnupg/gpg-agent.conf"],
+        binaries: &["gpg", "gpg2"],
+        packages: &["gnupg"],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".npmrc"],
+        binaries: &["npm"],
+        packages: &["node", "nodejs"],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".yarnrc", ".yarnrc.yml"],
+        binaries: &["yarn"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".pnpmrc"],
+        binaries: &["pnpm"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".cargo/config.toml", ".rustfmt.toml"],
+        binaries: &["cargo", "rustfmt"],
+        packages: &["rust"],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".tool-versions", ".config/asdf"],
+        binaries: &["asdf", "mise"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".pyenvrc"],
+        binaries: &["pyenv"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".claude/skills", ".claude/commands"],
+        binaries: &["claude"],
+        packages: &[],
+        bundles: &["Claude.app"],
+    },
+    App {
+        candidates: &[".gemini/skills"],
+        binaries: &["gemini"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".cursor/skills", ".cursor/commands"],
+        binaries: &["cursor"],
+        packages: &[],
+        bundles: &["Cursor.app"],
+    },
+    App {
+        candidates: &[".aider.conf.yml", ".aider.conventions.md"],
+        binaries: &["aider"],
+        packages: &[],
+        bundles: &[],
+    },
+    App {
+        candidates: &[".config/opencode/skills/", ".opencode/skills/"],
+        binaries: &["opencode"],
+        packages: &[],
+        bundles: &[],
+    },
+];
+
+/// What's installed on this machine, read as needed
+pub struct InstalledApps {
+    /// Directories searched for binaries
+    path: Vec<PathBuf>,
+    /// Directories searched for app bundles
+    app_dirs: Vec<PathBuf>,
+    /// Installed package names, listed on first use
+    packages: OnceCell<HashSet<String>>,
+}
+
+impl InstalledApps {
+    /// Look at `PATH`, the macOS application folders and the package managers
+    #[must_use]
+    pub fn detect() -> Self {
+        let path = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).collect())
+            .unwrap_or_default();
+        let app_dirs = vec![
+            PathBuf::from("/Applications"),
+            crate::utils::get_home_dir().join("Applications"),
+        ];
+        Self {
+            path,
+            app_dirs,
+            packages: OnceCell::new(),
+        }
+    }
+
+    /// Whether the application reading `candidate` is installed, or `None`
+    /// when the candidate doesn't belong to a particular application
+    #[must_use]
+    pub fn has_app_for(&self, candidate: &str) -> Option<bool> {
+        let app = APPS
+            .iter()
+            .find(|app| app.candidates.contains(&candidate))?;
+        let installed = app.binaries.iter().any(|binary| self.on_path(binary))
+            || app
+                .bundles
+                .iter()
+                .any(|bundle| self.app_dirs.iter().any(|dir| dir.join(bundle).exists()))
+            || app
+                .binaries
+                .iter()
+                .chain(app.packages)
+                .any(|package| self.packages().contains(*package));
+        Some(installed)
+    }
+
+    fn on_path(&self, binary: &str) -> bool {
+        self.path.iter().any(|dir| dir.join(binary).is_file())
+    }
+
+    fn packages(&self) -> &HashSet<String> {
+        self.packages.get_or_init(installed_packages)
+    }
+}
+
+/// Names of the packages Homebrew (formulae and casks), dpkg and pacman
+/// have installed, for the managers present
+fn installed_packages() -> HashSet<String> {
+    let listings: [(&str, &[&str]); 4] = [
+        ("brew", &["list", "--formula", "-1"]),
+        ("brew", &["list", "--cask", "-1"]),
+        ("dpkg-query", &["-W", "-f=${Package}\n"]),
+        ("pacman", &["-Qq"]),
+    ];
+    let mut packages = HashSet::new();
+    for (program, args) in listings {
+        let Ok(output) = Command::new(program).args(args).output() else {
+            continue;
+        };
+        if !output.status.success() {
+            continue;
+        }
+        packages.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty()),
+        );
+    }
+    debug!("Found {} installed packages", packages.len());
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_has_app_for() {
+        let temp_dir = TempDir::new().unwrap();
+        let bin = temp_dir.path().join("bin");
+        let apps = temp_dir.path().join("Applications");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(apps.join("Ghostty.app")).unwrap();
+        std::fs::write(bin.join("nvim"), "").unwrap();
+        let installed = InstalledApps {
+            path: vec![bin],
+            app_dirs: vec![apps],
+            packages: OnceCell::from(HashSet::from(["ripgrep".to_string()])),
+        };
+
+        assert_eq!(installed.has_app_for(".config/nvim"), Some(true));
+        assert_eq!(installed.has_app_for(".config/ghostty"), Some(true));
+        assert_eq!(installed.has_app_for(".config/ripgrep"), Some(true));
+        assert_eq!(installed.has_app_for(".config/alacritty"), Some(false));
+        assert_eq!(installed.has_app_for(".profile"), None);
+    }
+
+    #[test]
+    fn test_every_app_candidate_is_a_default_dotfile() {
+        for app in APPS {
+            for candidate in app.candidates {
+                assert!(
+                    crate::dotfile_candidates::find_candidate(candidate).is_some(),
+                    "{candidate} isn't in DEFAULT_DOTFILES"
+                );
+            }
+        }
+    }
+}
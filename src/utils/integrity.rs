@@ -0,0 +1,264 @@
+//! Content hashes of the tracked files.
+//!
+//! Each sync records a SHA-256 hash of every tracked file (directories
+//! hash their files) in the manifest's `hashes`. A tracked file that git
+//! considers unchanged but no longer matches its recorded hash was changed
+//! behind dotstate's back: committed directly in the repository (with git
+//! or on GitHub) or corrupted on disk. Changes waiting to be synced, made
+//! through the symlinks or not, are left alone; the next sync records
+//! them.
+
+use crate::utils::ProfileManifest;
+use anyhow::{Context, Result};
+use git2::{ObjectFormat, ObjectType, Oid};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// Why a tracked file failed verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityIssueKind {
+    /// The file is gone from the repository without being removed from sync
+    Missing,
+    /// The content doesn't match the hash recorded at the last sync
+    Modified,
+}
+
+/// A tracked file that failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityIssue {
+    /// Path in the repository (`common/.zshrc`)
+    pub path: String,
+    pub kind: IntegrityIssueKind,
+}
+
+impl IntegrityIssue {
+    /// One-line description for reports
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self.kind {
+            IntegrityIssueKind::Missing => format!("{}: missing from the repository", self.path),
+            IntegrityIssueKind::Modified => {
+                format!(
+                    "{}: changed outside dotstate since the last sync",
+                    self.path
+                )
+            }
+        }
+    }
+}
+
+/// Paths of the tracked files in the repository
+fn tracked_paths(manifest: &ProfileManifest) -> Vec<String> {
+    let common = manifest
+        .common
+        .synced_files
+        .iter()
+        .map(|file| format!("common/{file}"));
+    let profiles = manifest.profiles.iter().flat_map(|profile| {
+        profile
+            .synced_files
+            .iter()
+            .map(move |file| format!("{}/{file}", profile.name))
+    });
+    common.chain(profiles).collect()
+}
+
+fn hash_bytes(bytes: &[u8]) -> Result<String> {
+    Ok(Oid::hash_object_ext(ObjectType::Blob, bytes, ObjectFormat::Sha256)?.to_string())
+}
+
+/// Hash of the file at `path`. A directory hashes the relative paths and
+/// hashes of its files, a symlink its target.
+pub fn hash_path(path: &Path) -> Result<String> {
+    let meta =
+        fs::symlink_metadata(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if meta.is_symlink() {
+        let target = fs::read_link(path)?;
+        return hash_bytes(target.to_string_lossy().as_bytes());
+    }
+    if !meta.is_dir() {
+        let content =
+            fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        return hash_bytes(&content);
+    }
+
+    let mut entries: Vec<_> = fs::read_dir(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .flatten()
+        .collect();
+    entries.sort_by_key(fs::DirEntry::file_name);
+    let mut listing = String::new();
+    for entry in entries {
+        let name = entry.file_name();
+        listing.push_str(&format!(
+            "{}\0{}\n",
+            name.to_string_lossy(),
+            hash_path(&entry.path())?
+        ));
+    }
+    hash_bytes(listing.as_bytes())
+}
+
+/// Hashes of the tracked files as they are now
+fn current_hashes(
+    repo_path: &Path,
+    manifest: &ProfileManifest,
+) -> Result<BTreeMap<String, String>> {
+    let mut hashes = BTreeMap::new();
+    for path in tracked_paths(manifest) {
+        let full = repo_path.join(&path);
+        if full.symlink_metadata().is_ok() {
+            let hash = hash_path(&full)?;
+            hashes.insert(path, hash);
+        }
+    }
+    Ok(hashes)
+}
+
+/// Record the hashes of the tracked files in the manifest. Returns whether
+/// they changed.
+pub fn record(repo_path: &Path) -> Result<bool> {
+    let mut manifest =
+        ProfileManifest::load_or_backfill(repo_path).context("Failed to load profile manifest")?;
+    let hashes = current_hashes(repo_path, &manifest)?;
+    if hashes == manifest.hashes {
+        return Ok(false);
+    }
+    manifest.hashes = hashes;
+    manifest.save(repo_path)?;
+    info!("Recorded content hashes of the tracked files");
+    Ok(true)
+}
+
+/// Paths git reports as changed in the working tree or index
+fn pending_changes(repo_path: &Path) -> HashSet<String> {
+    let Ok(repo) = git2::Repository::open(repo_path) else {
+        return HashSet::new();
+    };
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    repo.statuses(Some(&mut opts))
+        .map(|statuses| {
+            statuses
+                .iter()
+                .filter_map(|entry| entry.path().ok().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Check the tracked files against the hashes recorded at the last sync.
+/// Files without a recorded hash and files with changes waiting to be
+/// synced are skipped.
+pub fn verify(repo_path: &Path) -> Result<Vec<IntegrityIssue>> {
+    let manifest =
+        ProfileManifest::load_or_backfill(repo_path).context("Failed to load profile manifest")?;
+    if manifest.hashes.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pending = pending_changes(repo_path);
+    let is_pending = |path: &str| {
+        let prefix = format!("{path}/");
+        pending
+            .iter()
+            .any(|changed| changed == path || changed.starts_with(&prefix))
+    };
+
+    let mut issues = Vec::new();
+    for path in tracked_paths(&manifest) {
+        let Some(recorded) = manifest.hashes.get(&path) else {
+            continue;
+        };
+        if is_pending(&path) {
+            continue;
+        }
+        let full = repo_path.join(&path);
+        let kind = if full.symlink_metadata().is_err() {
+            IntegrityIssueKind::Missing
+        } else if hash_path(&full)? != *recorded {
+            IntegrityIssueKind::Modified
+        } else {
+            continue;
+        };
+        issues.push(IntegrityIssue { path, kind });
+    }
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::ProfileInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_verify_against_recorded_hashes() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        let mut manifest = ProfileManifest {
+            profiles: vec![ProfileInfo {
+                name: "Work".to_string(),
+                description: None,
+                inherits: None,
+                synced_files: vec![".config/nvim".to_string()],
+                packages: Vec::new(),
+            }],
+            ..ProfileManifest::default()
+        };
+        manifest.common.synced_files = vec![".zshrc".to_string()];
+        manifest.save(repo).unwrap();
+        fs::create_dir_all(repo.join("common")).unwrap();
+        fs::write(repo.join("common/.zshrc"), "export A=1\n").unwrap();
+        fs::create_dir_all(repo.join("Work/.config/nvim/lua")).unwrap();
+        fs::write(repo.join("Work/.config/nvim/lua/init.lua"), "-- a\n").unwrap();
+
+        // Nothing recorded yet, so nothing to verify
+        assert!(verify(repo).unwrap().is_empty());
+        assert!(record(repo).unwrap());
+        assert!(!record(repo).unwrap());
+        assert!(verify(repo).unwrap().is_empty());
+
+        // Not a git repository, so every change counts as made outside dotstate
+        fs::write(repo.join("Work/.config/nvim/lua/init.lua"), "-- b\n").unwrap();
+        fs::remove_file(repo.join("common/.zshrc")).unwrap();
+        assert_eq!(
+            verify(repo).unwrap(),
+            vec![
+                IntegrityIssue {
+                    path: "common/.zshrc".to_string(),
+                    kind: IntegrityIssueKind::Missing,
+                },
+                IntegrityIssue {
+                    path: "Work/.config/nvim".to_string(),
+                    kind: IntegrityIssueKind::Modified,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_skips_pending_changes() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        let mut manifest = ProfileManifest::default();
+        manifest.common.synced_files = vec![".zshrc".to_string()];
+        manifest.save(repo).unwrap();
+        fs::create_dir_all(repo.join("common")).unwrap();
+        fs::write(repo.join("common/.zshrc"), "export A=1\n").unwrap();
+        record(repo).unwrap();
+        let git = crate::git::GitManager::open_or_init(repo).unwrap();
+        git.commit_all("Sync").unwrap();
+
+        // An edit waiting to be synced is fine
+        fs::write(repo.join("common/.zshrc"), "export A=2\n").unwrap();
+        assert!(verify(repo).unwrap().is_empty());
+
+        // Committed without recording its hash, it was changed outside dotstate
+        git.commit_all("Edit on GitHub").unwrap();
+        assert_eq!(verify(repo).unwrap().len(), 1);
+        record(repo).unwrap();
+        assert!(verify(repo).unwrap().is_empty());
+    }
+}
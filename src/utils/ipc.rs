@@ -0,0 +1,152 @@
+//! JSON protocol of the `dotstate daemon` socket.
+//!
+//! Clients connect to the Unix socket at [`socket_path`], write one request
+//! per line (`{"command":"status"}`) and read one response line for each.
+//! Menubar apps, status bar modules and editors use it to show the sync
+//! status and to trigger syncs without running the CLI.
+
+use crate::utils::link_monitor::LinkProblem;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Directory of the socket in the cache directory, private to the user
+const SOCKET_DIR: &str = "daemon";
+
+/// Name of the socket in [`SOCKET_DIR`]
+const SOCKET_FILE: &str = "dotstate.sock";
+
+/// A request sent to the daemon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Request {
+    /// Report the sync status
+    Status,
+    /// Commit, pull and push
+    Sync,
+    /// Create the active profile's symlinks
+    Activate,
+    /// Keep the file that replaced a tracked symlink, moving it into the
+    /// repository (`{"command":"adopt","path":"~/.zshrc"}`)
+    Adopt { path: String },
+    /// Re-create a tracked symlink that was replaced or removed
+    Restore { path: String },
+}
+
+/// Sync status reported by [`Request::Status`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Status {
+    pub profile: String,
+    pub branch: String,
+    /// Uncommitted files, as "X filename" entries
+    pub uncommitted: Vec<String>,
+    /// Commits ahead of origin, as last fetched
+    pub ahead: usize,
+    /// Commits behind origin, as last fetched
+    pub behind: usize,
+    /// Compact segment like `dotstate prompt` prints, e.g. `⚑2↑1`
+    pub segment: String,
+    /// When the last successful sync finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync: Option<DateTime<Utc>>,
+    pub read_only: bool,
+    /// Tracked symlinks that were replaced or broken
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_problems: Vec<LinkProblem>,
+}
+
+/// The daemon's answer to a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Response {
+    /// Whether the request succeeded
+    pub ok: bool,
+    /// What happened, or why it failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Set for status requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+}
+
+impl Response {
+    /// Successful response with a message
+    #[must_use]
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self {
+            ok: true,
+            message: Some(message.into()),
+            status: None,
+        }
+    }
+
+    /// Failed response
+    #[must_use]
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            message: Some(message.into()),
+            status: None,
+        }
+    }
+}
+
+/// Where the daemon listens
+#[must_use]
+pub fn socket_path() -> PathBuf {
+    crate::utils::get_cache_dir()
+        .join(SOCKET_DIR)
+        .join(SOCKET_FILE)
+}
+
+/// Parse one request line
+pub fn parse_request(line: &str) -> Result<Request> {
+    serde_json::from_str(line.trim())
+        .context("Invalid request, expected e.g. {\"command\":\"status\"}")
+}
+
+/// Serialize a response as one line
+#[must_use]
+pub fn response_line(response: &Response) -> String {
+    let mut line = serde_json::to_string(response).unwrap_or_else(|e| {
+        format!("{{\"ok\":false,\"message\":\"Failed to serialize response: {e}\"}}")
+    });
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_and_response_lines() {
+        assert_eq!(
+            parse_request("{\"command\":\"sync\"}\n").unwrap(),
+            Request::Sync
+        );
+        assert!(parse_request("{\"command\":\"reboot\"}").is_err());
+        assert_eq!(
+            parse_request("{\"command\":\"adopt\",\"path\":\"~/.zshrc\"}").unwrap(),
+            Request::Adopt {
+                path: "~/.zshrc".to_string()
+            }
+        );
+
+        let response = Response {
+            ok: true,
+            message: None,
+            status: Some(Status {
+                profile: "work".to_string(),
+                ahead: 1,
+                segment: "↑1".to_string(),
+                ..Status::default()
+            }),
+        };
+        let line = response_line(&response);
+        assert!(line.ends_with('\n') && !line.trim_end().contains('\n'));
+        assert!(!line.contains("last_sync") && !line.contains("message"));
+        assert!(!line.contains("link_problems"));
+        assert_eq!(serde_json::from_str::<Response>(&line).unwrap(), response);
+    }
+}
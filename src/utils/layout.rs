@@ -1,6 +1,28 @@
 use ratatui::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Create a standard vertical layout with header, content, and footer
+/// Areas narrower than this stack their panes instead of placing them side
+/// by side (an 80 column terminal does)
+pub const NARROW_WIDTH: u16 = 90;
+
+/// Whether the panes after the first are hidden on narrow areas
+static SIDE_PANES_HIDDEN: AtomicBool = AtomicBool::new(false);
+
+/// Whether `area` is too narrow for side-by-side panes
+#[must_use]
+pub fn is_narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+/// Show or hide the panes after the first on narrow areas, giving the first
+/// one the whole area. Returns whether they're hidden now.
+pub fn toggle_side_panes() -> bool {
+    !SIDE_PANES_HIDDEN.fetch_xor(true, Ordering::Relaxed)
+}
+
+/// Create a standard vertical layout with header, content, and footer. The
+/// compact layout (see [`crate::styles::is_compact`]) gives the header and
+/// footer one line each instead.
 ///
 /// # Arguments
 /// * `area` - The area to split
@@ -15,6 +37,25 @@ pub fn create_standard_layout(
     header_height: u16,
     footer_height: u16,
 ) -> (Rect, Rect, Rect) {
+    standard_layout(
+        area,
+        header_height,
+        footer_height,
+        crate::styles::is_compact(),
+    )
+}
+
+fn standard_layout(
+    area: Rect,
+    header_height: u16,
+    footer_height: u16,
+    compact: bool,
+) -> (Rect, Rect, Rect) {
+    let (header_height, footer_height) = if compact {
+        (1, 1)
+    } else {
+        (header_height, footer_height)
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -27,7 +68,10 @@ pub fn create_standard_layout(
     (chunks[0], chunks[1], chunks[2])
 }
 
-/// Create a horizontal split layout with given percentages
+/// Create a horizontal split layout with given percentages. Narrow areas
+/// (see [`is_narrow`]) are split vertically instead, or with the side panes
+/// hidden (see [`toggle_side_panes`]) the first section takes the whole area
+/// and the others are empty.
 ///
 /// # Arguments
 /// * `area` - The area to split
@@ -37,13 +81,31 @@ pub fn create_standard_layout(
 /// Vector of Rects for each section
 #[must_use]
 pub fn create_split_layout(area: Rect, percentages: &[u16]) -> Vec<Rect> {
+    split_layout(area, percentages, SIDE_PANES_HIDDEN.load(Ordering::Relaxed))
+}
+
+fn split_layout(area: Rect, percentages: &[u16], side_panes_hidden: bool) -> Vec<Rect> {
+    if is_narrow(area) && side_panes_hidden {
+        let empty = Rect::new(area.x, area.bottom(), area.width, 0);
+        return std::iter::once(area)
+            .chain(std::iter::repeat_n(
+                empty,
+                percentages.len().saturating_sub(1),
+            ))
+            .collect();
+    }
+
     let constraints: Vec<Constraint> = percentages
         .iter()
         .map(|&p| Constraint::Percentage(p))
         .collect();
 
     Layout::default()
-        .direction(Direction::Horizontal)
+        .direction(if is_narrow(area) {
+            Direction::Vertical
+        } else {
+            Direction::Horizontal
+        })
         .constraints(constraints)
         .split(area)
         .to_vec()
@@ -67,3 +129,37 @@ pub fn center_popup(area: Rect, width_percent: u16, height_percent: u16) -> Rect
 
     Rect::new(popup_x, popup_y, popup_width, popup_height)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_layout_narrow() {
+        let wide = split_layout(Rect::new(0, 0, 120, 30), &[40, 60], false);
+        assert_eq!((wide[0].width, wide[1].x), (48, 48));
+
+        // Stacked on narrow areas
+        let narrow = split_layout(Rect::new(0, 0, 80, 30), &[40, 60], false);
+        assert_eq!(narrow[0], Rect::new(0, 0, 80, 12));
+        assert_eq!(narrow[1], Rect::new(0, 12, 80, 18));
+
+        // The first pane takes it all with the side panes hidden
+        let hidden = split_layout(Rect::new(0, 0, 80, 30), &[40, 60], true);
+        assert_eq!(hidden[0], Rect::new(0, 0, 80, 30));
+        assert!(hidden[1].is_empty());
+        let wide = split_layout(Rect::new(0, 0, 120, 30), &[40, 60], true);
+        assert!(!wide[1].is_empty());
+    }
+
+    #[test]
+    fn test_standard_layout_compact() {
+        let area = Rect::new(0, 0, 80, 24);
+        let (header, content, footer) = standard_layout(area, 6, 2, false);
+        assert_eq!((header.height, content.height, footer.height), (6, 16, 2));
+
+        // One line each for the header and footer
+        let (header, content, footer) = standard_layout(area, 6, 2, true);
+        assert_eq!((header.height, content.height, footer.height), (1, 22, 1));
+    }
+}
@@ -0,0 +1,94 @@
+//! Git LFS for large files.
+//!
+//! A path added with Git LFS is committed as a small pointer, and its
+//! content is uploaded to the remote's LFS storage. libgit2 doesn't run the
+//! LFS filter or hooks, so dotstate calls the `git lfs` CLI itself: paths
+//! with `filter=lfs` are staged through `git add`, LFS objects are uploaded
+//! before a push and downloaded after a pull. Repositories without LFS rules
+//! in their `.gitattributes` never run any of it.
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tracing::info;
+
+/// Whether the `git lfs` CLI is installed
+#[must_use]
+pub fn is_available() -> bool {
+    Command::new("git")
+        .args(["lfs", "version"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Whether the repository's `.gitattributes` stores anything with LFS
+#[must_use]
+pub fn is_used(repo_path: &Path) -> bool {
+    std::fs::read_to_string(repo_path.join(".gitattributes"))
+        .is_ok_and(|content| content.contains("filter=lfs"))
+}
+
+fn run(repo_path: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run git. Is it installed?")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(())
+}
+
+/// Store `path` (relative to the repository root) with LFS from now on:
+/// `git lfs install` for the repository, then `git lfs track`, which adds
+/// the rule to `.gitattributes`. A directory is tracked with everything in it.
+pub fn track(repo_path: &Path, path: &str, is_dir: bool) -> Result<()> {
+    if !is_available() {
+        bail!("Git LFS isn't installed. Install it from https://git-lfs.com and try again.");
+    }
+    run(repo_path, &["lfs", "install", "--local"])?;
+    let pattern = if is_dir {
+        format!("{}/**", path.trim_end_matches('/'))
+    } else {
+        path.to_string()
+    };
+    info!("Tracking {} with Git LFS", pattern);
+    run(repo_path, &["lfs", "track", "--", &pattern])
+        .with_context(|| format!("Failed to track {path} with Git LFS"))
+}
+
+/// Stage `paths` with `git add`, which runs the LFS filter libgit2 can't
+pub fn stage(repo_path: &Path, paths: &[String]) -> Result<()> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+    let mut args = vec!["add", "--all", "--"];
+    args.extend(paths.iter().map(String::as_str));
+    run(repo_path, &args).context("Failed to stage Git LFS files")
+}
+
+/// Upload the LFS objects of `branch` before it's pushed
+pub fn push(repo_path: &Path, remote_name: &str, branch: &str) -> Result<()> {
+    if !is_used(repo_path) {
+        return Ok(());
+    }
+    info!("Uploading Git LFS objects to {}", remote_name);
+    run(repo_path, &["lfs", "push", remote_name, branch]).context("Failed to upload Git LFS files")
+}
+
+/// Download the LFS content of what was just pulled into the working tree
+pub fn pull(repo_path: &Path, remote_name: &str) -> Result<()> {
+    if !is_used(repo_path) || !is_available() {
+        return Ok(());
+    }
+    info!("Downloading Git LFS objects from {}", remote_name);
+    run(repo_path, &["lfs", "pull", remote_name]).context("Failed to download Git LFS files")
+}
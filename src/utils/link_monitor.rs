@@ -0,0 +1,302 @@
+//! Tracked symlinks that were replaced or broken behind dotstate's back.
+//!
+//! Installers and app updates like to write their own `.zshrc` over the
+//! symlink, and a deleted repository file leaves a link pointing nowhere.
+//! [`scan`] compares the tracked symlinks with what's on disk, and the
+//! daemon runs it every [`MONITOR_INTERVAL`], raising a desktop notification
+//! the first time each problem shows up. A replaced link can then be adopted
+//! (the new file moves into the repository) or restored (the new file is
+//! backed up and the link re-created), from `dotstate repair` or the daemon
+//! socket.
+
+use crate::utils::path::format_path_for_display;
+use crate::utils::symlink_manager::{SymlinkManager, TrackedSymlink};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How often the daemon checks the tracked symlinks
+pub const MONITOR_INTERVAL: Duration = Duration::from_secs(10);
+
+/// What happened to a tracked symlink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkProblemKind {
+    /// A real file or directory took the symlink's place
+    Replaced,
+    /// The symlink was deleted
+    Removed,
+    /// The symlink points somewhere else
+    Retargeted,
+    /// The symlink is in place but its file is gone from the repository
+    Broken,
+}
+
+/// A tracked symlink that's no longer what dotstate created
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkProblem {
+    /// The symlink in the home directory
+    pub target: PathBuf,
+    /// The repository file it should point to
+    pub source: PathBuf,
+    pub kind: LinkProblemKind,
+}
+
+impl LinkProblem {
+    /// One line describing the problem
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let target = format_path_for_display(&self.target);
+        match self.kind {
+            LinkProblemKind::Replaced => format!("{target} was replaced by a real file"),
+            LinkProblemKind::Removed => format!("{target} was removed"),
+            LinkProblemKind::Retargeted => format!("{target} now links somewhere else"),
+            LinkProblemKind::Broken => {
+                format!("{target} links to a file missing from the repository")
+            }
+        }
+    }
+
+    /// Whether the file in the link's place can be moved into the repository
+    #[must_use]
+    pub fn can_adopt(&self) -> bool {
+        self.kind == LinkProblemKind::Replaced
+    }
+
+    /// Whether the link can be re-created from the repository
+    #[must_use]
+    pub fn can_restore(&self) -> bool {
+        self.kind != LinkProblemKind::Broken
+    }
+
+    /// The `dotstate repair` commands that fix it
+    #[must_use]
+    pub fn hint(&self) -> String {
+        let target = format_path_for_display(&self.target);
+        match (self.can_adopt(), self.can_restore()) {
+            (true, _) => format!(
+                "Keep the new file with 'dotstate repair {target} --adopt', or relink with 'dotstate repair {target}'"
+            ),
+            (false, true) => format!("Relink with 'dotstate repair {target}'"),
+            (false, false) => "Pull the file again with 'dotstate sync', or remove it from sync".to_string(),
+        }
+    }
+}
+
+/// Path a symlink points to, resolved against its directory
+fn link_destination(target: &Path) -> Option<PathBuf> {
+    let destination = std::fs::read_link(target).ok()?;
+    Some(match target.parent() {
+        Some(parent) if destination.is_relative() => parent.join(destination),
+        _ => destination,
+    })
+}
+
+/// The tracked symlinks that aren't as dotstate left them. Decrypted and
+/// rendered copies and managed blocks are real files on purpose and aren't
+/// checked.
+#[must_use]
+pub fn scan(tracked: &[TrackedSymlink]) -> Vec<LinkProblem> {
+    tracked
+        .iter()
+        .filter(|link| !link.decrypted && !link.rendered && !link.block)
+        .filter_map(|link| {
+            let kind = match link.target.symlink_metadata() {
+                Err(_) => LinkProblemKind::Removed,
+                Ok(metadata) if !metadata.is_symlink() => LinkProblemKind::Replaced,
+                Ok(_) => {
+                    let destination = link_destination(&link.target)?;
+                    let same = destination == link.source
+                        || (link.source.exists()
+                            && destination.canonicalize().ok() == link.source.canonicalize().ok());
+                    if !same {
+                        LinkProblemKind::Retargeted
+                    } else if !link.source.exists() {
+                        LinkProblemKind::Broken
+                    } else {
+                        return None;
+                    }
+                }
+            };
+            Some(LinkProblem {
+                target: link.target.clone(),
+                source: link.source.clone(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// The problem with the symlink at `path` (`~/.zshrc`, `.zshrc` or absolute)
+#[must_use]
+pub fn find<'a>(problems: &'a [LinkProblem], path: &str) -> Option<&'a LinkProblem> {
+    let path = crate::utils::expand_path(path);
+    problems.iter().find(|problem| problem.target == path)
+}
+
+/// The problems with the tracked symlinks of the repository at `repo_path`
+pub fn check(repo_path: &Path) -> Result<Vec<LinkProblem>> {
+    let symlink_mgr = SymlinkManager::new_with_backup(repo_path.to_path_buf(), false)?;
+    Ok(scan(&symlink_mgr.tracking.symlinks))
+}
+
+/// Fix the symlink at `path`: re-create it from the repository, or with
+/// `adopt`, move the file found in its place into the repository first.
+/// Returns what was done.
+pub fn repair(repo_path: &Path, backup_enabled: bool, path: &str, adopt: bool) -> Result<String> {
+    let mut symlink_mgr = SymlinkManager::new_with_backup(repo_path.to_path_buf(), backup_enabled)?;
+    let problems = scan(&symlink_mgr.tracking.symlinks);
+    let Some(problem) = find(&problems, path) else {
+        bail!("{path} isn't a tracked symlink that needs repair");
+    };
+    let target = format_path_for_display(&problem.target);
+    if adopt {
+        if !problem.can_adopt() {
+            bail!("{}; there's no file to adopt", problem.describe());
+        }
+        symlink_mgr.adopt_link(&problem.target)?;
+        Ok(format!(
+            "Adopted {target} into the repository and relinked it. Commit it with 'dotstate sync'."
+        ))
+    } else {
+        if !problem.can_restore() {
+            bail!("{}. {}", problem.describe(), problem.hint());
+        }
+        let operation = symlink_mgr.restore_link(&problem.target)?;
+        Ok(match operation.backup {
+            Some(backup) if problem.kind == LinkProblemKind::Replaced => format!(
+                "Relinked {target}; the file found there was backed up to {}",
+                backup.display()
+            ),
+            _ => format!("Relinked {target}"),
+        })
+    }
+}
+
+/// Remembers which problems were already reported, so each one is
+/// notified once and again only after it was fixed and came back
+#[derive(Debug, Default)]
+pub struct LinkMonitor {
+    reported: BTreeSet<PathBuf>,
+}
+
+impl LinkMonitor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The problems in `problems` that weren't there on the last poll
+    pub fn poll<'a>(&mut self, problems: &'a [LinkProblem]) -> Vec<&'a LinkProblem> {
+        let current: BTreeSet<PathBuf> = problems.iter().map(|p| p.target.clone()).collect();
+        let new = problems
+            .iter()
+            .filter(|problem| !self.reported.contains(&problem.target))
+            .collect();
+        self.reported = current;
+        new
+    }
+}
+
+/// Show a desktop notification, where the platform has a way to. Failures
+/// are ignored: the problem is also logged and reported over the socket.
+pub fn notify_desktop(title: &str, body: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let quote = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut command = Command::new("osascript");
+        command.args([
+            "-e",
+            &format!(
+                "display notification \"{}\" with title \"{}\"",
+                quote(body),
+                quote(title)
+            ),
+        ]);
+        command
+    } else if cfg!(unix) {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=dotstate", title, body]);
+        command
+    } else {
+        return;
+    };
+    let _ = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn tracked(target: PathBuf, source: PathBuf) -> TrackedSymlink {
+        TrackedSymlink {
+            target,
+            source,
+            created_at: Utc::now(),
+            backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_and_monitor() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let (home, repo) = (temp_dir.path().join("home"), temp_dir.path().join("repo"));
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::create_dir_all(&repo).unwrap();
+        let link = |name: &str| {
+            let source = repo.join(name);
+            std::fs::write(&source, "repo").unwrap();
+            symlink(&source, home.join(name)).unwrap();
+            tracked(home.join(name), source)
+        };
+        let links = vec![
+            link(".fine"),
+            link(".zshrc"),
+            link(".vimrc"),
+            link(".gitconfig"),
+            link(".tmux.conf"),
+        ];
+
+        std::fs::remove_file(home.join(".zshrc")).unwrap();
+        std::fs::write(home.join(".zshrc"), "installer").unwrap();
+        std::fs::remove_file(home.join(".vimrc")).unwrap();
+        std::fs::remove_file(home.join(".gitconfig")).unwrap();
+        symlink(home.join(".fine"), home.join(".gitconfig")).unwrap();
+        std::fs::remove_file(repo.join(".tmux.conf")).unwrap();
+
+        let problems = scan(&links);
+        let kinds: Vec<_> = problems.iter().map(|p| p.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LinkProblemKind::Replaced,
+                LinkProblemKind::Removed,
+                LinkProblemKind::Retargeted,
+                LinkProblemKind::Broken,
+            ]
+        );
+        assert!(problems[0].can_adopt() && !problems[1].can_adopt());
+        assert!(!problems[3].can_restore());
+
+        // Each problem is reported once, and again after it came back
+        let mut monitor = LinkMonitor::new();
+        assert_eq!(monitor.poll(&problems).len(), 4);
+        assert!(monitor.poll(&problems).is_empty());
+        assert!(monitor.poll(&problems[1..]).is_empty());
+        assert_eq!(monitor.poll(&problems).len(), 1);
+    }
+}
@@ -0,0 +1,260 @@
+//! Local-only children of synced directories.
+//!
+//! A synced directory is symlinked as a whole, so whatever a tool writes
+//! into it lands in the repository. Marking a child local-only (say
+//! `.config/fish/fish_variables`) keeps it out: it is listed in the
+//! manifest, ignored by the repository's `.gitignore` in every profile
+//! directory and in common, and dropped from git's index while the file
+//! itself stays where it is. Each machine keeps its own copy from then on.
+
+use crate::file_manager::copy_dir_all;
+use crate::utils::{repo_gitignore, ProfileManifest};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Ignore pattern covering `relative_path` in every profile directory and in common
+#[must_use]
+pub fn pattern(relative_path: &str) -> String {
+    format!("/*/{relative_path}")
+}
+
+/// The synced directory that `relative_path` lives in, if any
+fn synced_parent<'a>(manifest: &'a ProfileManifest, relative_path: &str) -> Option<&'a str> {
+    manifest
+        .common
+        .synced_files
+        .iter()
+        .chain(manifest.profiles.iter().flat_map(|p| &p.synced_files))
+        .map(String::as_str)
+        .find(|dir| {
+            relative_path
+                .strip_prefix(dir)
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+}
+
+/// Whether `relative_path` is local-only or inside a local-only directory
+#[must_use]
+pub fn is_local_only(manifest: &ProfileManifest, relative_path: &str) -> bool {
+    manifest.local_only.iter().any(|entry| {
+        relative_path
+            .strip_prefix(entry.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+/// Mark `relative_path`, a child of a synced directory, local-only
+pub fn mark(repo_path: &Path, relative_path: &str) -> Result<()> {
+    let relative_path = relative_path.trim_matches('/');
+    let mut manifest = ProfileManifest::load_or_backfill(repo_path)?;
+    if synced_parent(&manifest, relative_path).is_none() {
+        anyhow::bail!("'{relative_path}' isn't inside a synced directory");
+    }
+
+    if !manifest.local_only.iter().any(|p| p == relative_path) {
+        manifest.local_only.push(relative_path.to_string());
+        manifest.local_only.sort();
+        manifest.save(repo_path)?;
+    }
+    repo_gitignore::append(repo_path, &[pattern(relative_path)])?;
+    untrack(repo_path, &manifest, relative_path)?;
+
+    info!("Marked {} local-only", relative_path);
+    Ok(())
+}
+
+/// Sync `relative_path` again. Returns whether it was local-only.
+pub fn unmark(repo_path: &Path, relative_path: &str) -> Result<bool> {
+    let relative_path = relative_path.trim_matches('/');
+    let mut manifest = ProfileManifest::load_or_backfill(repo_path)?;
+    let before = manifest.local_only.len();
+    manifest.local_only.retain(|p| p != relative_path);
+    if manifest.local_only.len() == before {
+        return Ok(false);
+    }
+    manifest.save(repo_path)?;
+    repo_gitignore::remove(repo_path, &pattern(relative_path))?;
+
+    info!("{} is synced again", relative_path);
+    Ok(true)
+}
+
+/// Drop every copy of `relative_path` from git's index (git rm --cached),
+/// leaving the files on disk
+fn untrack(repo_path: &Path, manifest: &ProfileManifest, relative_path: &str) -> Result<()> {
+    let Ok(repo) = git2::Repository::open(repo_path) else {
+        return Ok(());
+    };
+    let specs: Vec<String> = manifest
+        .profiles
+        .iter()
+        .map(|p| p.name.as_str())
+        .chain(std::iter::once("common"))
+        .map(|dir| format!("{dir}/{relative_path}"))
+        .collect();
+    let mut index = repo.index().context("Failed to get repository index")?;
+    index
+        .remove_all(&specs, None)
+        .context("Failed to remove local-only files from the index")?;
+    index.write().context("Failed to write index")
+}
+
+/// Before the directory `target` is replaced with a symlink to `source`,
+/// copy its local-only children that `source` lacks into it, so the
+/// machine's own copies survive activation.
+pub fn preserve_children(repo_path: &Path, source: &Path, target: &Path, relative_name: &str) {
+    let Ok(manifest) = ProfileManifest::load(repo_path) else {
+        return;
+    };
+    for entry in &manifest.local_only {
+        let Some(child) = entry
+            .strip_prefix(relative_name)
+            .and_then(|rest| rest.strip_prefix('/'))
+        else {
+            continue;
+        };
+        let from = target.join(child);
+        let to = source.join(child);
+        if !from.exists() || to.symlink_metadata().is_ok() {
+            continue;
+        }
+        let copied = to
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(anyhow::Error::from)
+            .and_then(|()| {
+                if from.is_dir() {
+                    copy_dir_all(&from, &to)
+                } else {
+                    fs::copy(&from, &to).map(|_| ()).map_err(Into::into)
+                }
+            });
+        match copied {
+            Ok(()) => info!("Kept local-only {}", entry),
+            Err(e) => warn!("Failed to keep local-only {}: {}", entry, e),
+        }
+    }
+}
+
+/// Put back the local-only files a pull deleted. A machine that still had
+/// such a file tracked loses it from its working tree when it pulls the
+/// commit that untracked it; the version from `old`, the commit before the
+/// pull, is written back, where the `.gitignore` now keeps it out. Returns
+/// the number of files restored.
+pub fn restore_after_pull(repo_path: &Path, old: git2::Oid) -> Result<usize> {
+    let manifest = ProfileManifest::load(repo_path)?;
+    if manifest.local_only.is_empty() {
+        return Ok(0);
+    }
+    let repo = git2::Repository::open(repo_path).context("Failed to open repository")?;
+    let old_tree = repo.find_commit(old)?.tree()?;
+    let head_tree = repo.head()?.peel_to_tree()?;
+    let diff = repo
+        .diff_tree_to_tree(Some(&old_tree), Some(&head_tree), None)
+        .context("Failed to compare commits")?;
+
+    let mut restored = 0;
+    for delta in diff.deltas() {
+        if delta.status() != git2::Delta::Deleted {
+            continue;
+        }
+        let Some(path) = delta.old_file().path() else {
+            continue;
+        };
+        // Strip the profile (or common) directory
+        let home_relative = path.components().skip(1).collect::<std::path::PathBuf>();
+        if !is_local_only(&manifest, &home_relative.to_string_lossy()) {
+            continue;
+        }
+        let dest = repo_path.join(path);
+        if dest.symlink_metadata().is_ok() {
+            continue;
+        }
+        let blob = repo.find_blob(delta.old_file().id())?;
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&dest, blob.content())
+            .with_context(|| format!("Failed to restore {}", dest.display()))?;
+        restored += 1;
+    }
+    if restored > 0 {
+        info!(
+            "Restored {} local-only file(s) removed by the pull",
+            restored
+        );
+    }
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::ProfileInfo;
+    use tempfile::TempDir;
+
+    fn setup() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let manifest = ProfileManifest {
+            profiles: vec![ProfileInfo {
+                name: "Work".to_string(),
+                description: None,
+                inherits: None,
+                synced_files: vec![".config/fish".to_string()],
+                packages: Vec::new(),
+            }],
+            ..ProfileManifest::default()
+        };
+        manifest.save(temp.path()).unwrap();
+        fs::create_dir_all(temp.path().join("Work/.config/fish")).unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_mark_and_unmark() {
+        let temp = setup();
+        let repo = temp.path();
+
+        assert!(mark(repo, ".zshrc").is_err());
+        assert!(mark(repo, ".config/fishy").is_err());
+
+        mark(repo, ".config/fish/fish_variables/").unwrap();
+        let manifest = ProfileManifest::load(repo).unwrap();
+        assert_eq!(manifest.local_only, [".config/fish/fish_variables"]);
+        assert!(is_local_only(&manifest, ".config/fish/fish_variables"));
+        assert!(!is_local_only(&manifest, ".config/fish/config.fish"));
+        assert!(fs::read_to_string(repo.join(repo_gitignore::GITIGNORE))
+            .unwrap()
+            .contains("/*/.config/fish/fish_variables\n"));
+
+        assert!(unmark(repo, ".config/fish/fish_variables").unwrap());
+        assert!(!unmark(repo, ".config/fish/fish_variables").unwrap());
+        assert!(ProfileManifest::load(repo).unwrap().local_only.is_empty());
+        assert!(!fs::read_to_string(repo.join(repo_gitignore::GITIGNORE))
+            .unwrap()
+            .contains("fish_variables"));
+    }
+
+    #[test]
+    fn test_preserve_children() {
+        let temp = setup();
+        let repo = temp.path();
+        mark(repo, ".config/fish/fish_variables").unwrap();
+
+        let home = temp.path().join("home/.config/fish");
+        fs::create_dir_all(&home).unwrap();
+        fs::write(home.join("fish_variables"), "SETUVAR x").unwrap();
+        fs::write(home.join("config.fish"), "local config").unwrap();
+
+        let source = repo.join("Work/.config/fish");
+        preserve_children(repo, &source, &home, ".config/fish");
+        assert_eq!(
+            fs::read_to_string(source.join("fish_variables")).unwrap(),
+            "SETUVAR x"
+        );
+        // Synced children come from the repository
+        assert!(!source.join("config.fish").exists());
+    }
+}
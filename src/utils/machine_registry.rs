@@ -0,0 +1,216 @@
+//! Registry of the machines that sync with the storage repository.
+//!
+//! Every machine records its hostname, OS, active profile, `DotState` version
+//! and last sync time in `machines.toml` at the root of the repository. The
+//! entry is refreshed during sync and committed with the synced files, so each
+//! machine sees when the others last synced.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Current version of the `machines.toml` file format.
+/// Increment this when making breaking changes to the schema.
+const CURRENT_VERSION: u32 = 1;
+
+/// Name of the registry file in the repository root
+pub const MACHINES_FILE: &str = "machines.toml";
+
+/// A sync with nothing else to commit only refreshes this machine's entry
+/// when it is older than this, so syncing doesn't create a commit every time.
+const REFRESH_AFTER_HOURS: i64 = 24;
+
+/// A machine that hasn't synced for this long is shown as stale
+pub const STALE_AFTER_DAYS: i64 = 30;
+
+/// One machine's entry in the registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineInfo {
+    /// Operating system and architecture, e.g. "macos aarch64"
+    pub os: String,
+    /// Profile active on the machine
+    #[serde(default)]
+    pub active_profile: String,
+    /// `DotState` version the machine last synced with
+    pub dotstate_version: String,
+    /// When the machine last synced
+    pub last_sync: DateTime<Utc>,
+}
+
+impl MachineInfo {
+    /// Entry for the current machine, synced now
+    #[must_use]
+    pub fn current(active_profile: &str) -> Self {
+        Self {
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            active_profile: active_profile.to_string(),
+            dotstate_version: crate::version_check::current_version().to_string(),
+            last_sync: Utc::now(),
+        }
+    }
+
+    /// Whether the machine hasn't synced for [`STALE_AFTER_DAYS`]
+    #[must_use]
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.last_sync) > Duration::days(STALE_AFTER_DAYS)
+    }
+
+    /// Whether `self` says the same as `other`, apart from the sync time
+    fn same_details(&self, other: &Self) -> bool {
+        self.os == other.os
+            && self.active_profile == other.active_profile
+            && self.dotstate_version == other.dotstate_version
+    }
+}
+
+/// Contents of `machines.toml`, keyed by hostname.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineRegistry {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub machines: BTreeMap<String, MachineInfo>,
+}
+
+impl MachineRegistry {
+    /// Path of the registry in the repository
+    #[must_use]
+    pub fn path(repo_path: &Path) -> PathBuf {
+        repo_path.join(MACHINES_FILE)
+    }
+
+    /// Load the registry. A missing file is an empty registry.
+    pub fn load(repo_path: &Path) -> Result<Self> {
+        let path = Self::path(repo_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Write the registry to the repository.
+    pub fn save(&self, repo_path: &Path) -> Result<()> {
+        let path = Self::path(repo_path);
+        let content = toml::to_string_pretty(&Self {
+            version: CURRENT_VERSION,
+            machines: self.machines.clone(),
+        })
+        .context("Failed to serialize machine registry")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Machines sorted by most recent sync first
+    #[must_use]
+    pub fn by_last_sync(&self) -> Vec<(&String, &MachineInfo)> {
+        let mut machines: Vec<_> = self.machines.iter().collect();
+        machines.sort_by_key(|(_, info)| std::cmp::Reverse(info.last_sync));
+        machines
+    }
+
+    /// Record `entry` for `hostname`. Returns whether the registry changed.
+    ///
+    /// When `force` is false, an entry with the same details that is newer
+    /// than [`REFRESH_AFTER_HOURS`] is left alone.
+    fn record(&mut self, hostname: &str, entry: MachineInfo, force: bool) -> bool {
+        if let Some(existing) = self.machines.get(hostname) {
+            let fresh = entry.last_sync.signed_duration_since(existing.last_sync)
+                < Duration::hours(REFRESH_AFTER_HOURS);
+            if !force && fresh && existing.same_details(&entry) {
+                return false;
+            }
+        }
+        self.machines.insert(hostname.to_string(), entry);
+        true
+    }
+
+    /// Refresh this machine's entry before a sync commits.
+    ///
+    /// `has_changes` says whether the sync commits anything else; if not, the
+    /// entry is only rewritten when it changed or is getting old. Returns
+    /// whether the file was written.
+    pub fn record_sync(repo_path: &Path, active_profile: &str, has_changes: bool) -> Result<bool> {
+        let mut registry = Self::load(repo_path)?;
+        if !registry.record(
+            &current_hostname(),
+            MachineInfo::current(active_profile),
+            has_changes,
+        ) {
+            return Ok(false);
+        }
+        registry.save(repo_path)?;
+        Ok(true)
+    }
+}
+
+/// Name of this machine, from the `hostname` command or the environment.
+#[must_use]
+pub fn current_hostname() -> String {
+    let from_command = std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    from_command
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(profile: &str, last_sync: DateTime<Utc>) -> MachineInfo {
+        MachineInfo {
+            os: "linux x86_64".to_string(),
+            active_profile: profile.to_string(),
+            dotstate_version: "1.0.0".to_string(),
+            last_sync,
+        }
+    }
+
+    #[test]
+    fn test_record_skips_fresh_unchanged_entries() {
+        let now = Utc::now();
+        let mut registry = MachineRegistry::default();
+        assert!(registry.record("laptop", entry("Work", now - Duration::hours(2)), false));
+
+        assert!(!registry.record("laptop", entry("Work", now), false));
+        assert!(registry.record("laptop", entry("Personal", now), false));
+        assert!(registry.record("laptop", entry("Personal", now), true));
+        assert!(registry.record(
+            "laptop",
+            entry("Personal", now + Duration::hours(REFRESH_AFTER_HOURS + 1)),
+            false
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = Utc::now();
+        let mut registry = MachineRegistry::default();
+        registry.record("desktop", entry("Personal", now - Duration::days(90)), true);
+        registry.record("laptop", entry("Work", now), true);
+        registry.save(temp_dir.path()).unwrap();
+
+        let loaded = MachineRegistry::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded.version, CURRENT_VERSION);
+        let order: Vec<&str> = loaded
+            .by_last_sync()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(order, vec!["laptop", "desktop"]);
+        assert!(loaded.machines["desktop"].is_stale(now));
+        assert!(!loaded.machines["laptop"].is_stale(now));
+    }
+}
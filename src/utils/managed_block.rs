@@ -0,0 +1,247 @@
+//! Managed blocks: syncing part of a file.
+//!
+//! Some files can't be owned by dotstate, like a `.zshrc` an employer's
+//! tooling also writes to. A file listed in the manifest's `managed_blocks`
+//! isn't symlinked: its stored content is kept inside a delimited block of
+//! the real file in the home directory, and the rest of that file is left
+//! alone. Activation writes the block; edits made inside it are copied back
+//! into the repository before syncing, like sops-decrypted copies.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+const BEGIN: &str = ">>> dotstate >>>";
+const END: &str = "<<< dotstate <<<";
+
+/// Comment leader for the marker lines in `path`
+fn comment_prefix(path: &Path) -> &'static str {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if name.ends_with("vimrc") || ext == "vim" {
+        "\""
+    } else if ext == "lua" {
+        "--"
+    } else if ext == "ini" {
+        ";"
+    } else {
+        "#"
+    }
+}
+
+/// Line range of the block in `lines`, begin and end marker included
+fn find(lines: &[&str]) -> Option<(usize, usize)> {
+    let begin = lines.iter().position(|l| l.trim_end().ends_with(BEGIN))?;
+    let end = lines[begin..]
+        .iter()
+        .position(|l| l.trim_end().ends_with(END))?;
+    Some((begin, begin + end))
+}
+
+/// Whether `content` holds a managed block
+#[must_use]
+pub fn has_block(content: &str) -> bool {
+    find(&content.lines().collect::<Vec<_>>()).is_some()
+}
+
+/// The lines inside the block of `content`, if it has one
+#[must_use]
+pub fn extract(content: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (begin, end) = find(&lines)?;
+    let mut body = lines[begin + 1..end].join("\n");
+    if !body.is_empty() {
+        body.push('\n');
+    }
+    Some(body)
+}
+
+/// `content` with its block set to `body`. A file without a block gets one
+/// appended.
+#[must_use]
+pub fn apply(content: &str, body: &str, comment: &str) -> String {
+    let mut block = vec![format!("{comment} {BEGIN}")];
+    block.extend(body.lines().map(str::to_string));
+    block.push(format!("{comment} {END}"));
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut out: Vec<&str> = Vec::new();
+    if let Some((begin, end)) = find(&lines) {
+        out.extend(&lines[..begin]);
+        out.extend(block.iter().map(String::as_str));
+        out.extend(&lines[end + 1..]);
+    } else {
+        out.extend(&lines);
+        if out.last().is_some_and(|l| !l.is_empty()) {
+            out.push("");
+        }
+        out.extend(block.iter().map(String::as_str));
+    }
+    out.push("");
+    out.join("\n")
+}
+
+/// `content` without its block (and the blank line before it)
+#[must_use]
+pub fn strip(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some((mut begin, end)) = find(&lines) else {
+        return content.to_string();
+    };
+    if begin > 0 && lines[begin - 1].is_empty() {
+        begin -= 1;
+    }
+    let mut out: Vec<&str> = lines[..begin].to_vec();
+    out.extend(&lines[end + 1..]);
+    if out.is_empty() {
+        return String::new();
+    }
+    let mut content = out.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Whether the file at `path` holds a managed block
+#[must_use]
+pub fn file_has_block(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|c| has_block(&c))
+}
+
+/// Whether the block in `target` already matches `source`
+#[must_use]
+pub fn is_current(source: &Path, target: &Path) -> bool {
+    let (Ok(body), Ok(content)) = (fs::read_to_string(source), fs::read_to_string(target)) else {
+        return false;
+    };
+    !target.is_symlink() && extract(&content).is_some_and(|current| current == body)
+}
+
+/// Write the content of `source` into the block of `target`, creating the
+/// file if needed. A symlink at `target` pointing at `source` (the file was
+/// synced whole before) is replaced with a real file.
+pub fn write(source: &Path, target: &Path) -> Result<()> {
+    let body = fs::read_to_string(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?;
+
+    if target.is_symlink() {
+        let points_here = fs::canonicalize(target).ok() == fs::canonicalize(source).ok();
+        if points_here {
+            fs::remove_file(target)
+                .with_context(|| format!("Failed to remove symlink {}", target.display()))?;
+        }
+    }
+    let existing = fs::read_to_string(target).unwrap_or_default();
+    let updated = apply(&existing, &body, comment_prefix(target));
+    if updated != existing {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, updated)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+        info!("Updated managed block in {:?}", target);
+    }
+    Ok(())
+}
+
+/// Remove the block from `target`, leaving the rest of the file
+pub fn remove(target: &Path) -> Result<()> {
+    let Ok(content) = fs::read_to_string(target) else {
+        return Ok(());
+    };
+    let stripped = strip(&content);
+    if stripped != content {
+        fs::write(target, stripped)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+        info!("Removed managed block from {:?}", target);
+    }
+    Ok(())
+}
+
+/// Copy blocks edited in the home directory back into the repository, so
+/// the changes show up in git and get synced. Returns the repository paths
+/// that were updated.
+pub fn capture_changed(repo_path: &Path) -> Result<Vec<String>> {
+    let mgr = crate::utils::SymlinkManager::new_with_backup(repo_path.to_path_buf(), false)?;
+    let mut updated = Vec::new();
+    for tracked in mgr.get_tracked_symlinks().iter().filter(|t| t.block) {
+        // A git include's block only points at the source
+        if crate::utils::git_include::is_installed(&tracked.source, &tracked.target) {
+            continue;
+        }
+        let Some(body) = fs::read_to_string(&tracked.target)
+            .ok()
+            .and_then(|c| extract(&c))
+        else {
+            continue;
+        };
+        if fs::read_to_string(&tracked.source).ok().as_deref() == Some(body.as_str()) {
+            continue;
+        }
+        fs::write(&tracked.source, &body)
+            .with_context(|| format!("Failed to write {}", tracked.source.display()))?;
+        let relative = tracked
+            .source
+            .strip_prefix(repo_path)
+            .unwrap_or(&tracked.source)
+            .to_string_lossy()
+            .into_owned();
+        info!(
+            "Captured managed block {} from {:?}",
+            relative, tracked.target
+        );
+        updated.push(relative);
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_extract_strip() {
+        let original = "export WORK=1\nsource /opt/corp/env\n";
+        let with_block = apply(original, "alias ll='ls -l'\n", "#");
+        assert_eq!(
+            with_block,
+            "export WORK=1\nsource /opt/corp/env\n\n# >>> dotstate >>>\nalias ll='ls -l'\n# <<< dotstate <<<\n"
+        );
+        assert_eq!(extract(&with_block).as_deref(), Some("alias ll='ls -l'\n"));
+
+        // Updating replaces only the block, wherever it is
+        let edited = with_block.replace("export WORK=1", "export WORK=2") + "# after\n";
+        let updated = apply(&edited, "alias la='ls -a'\n", "#");
+        assert!(updated.starts_with("export WORK=2\n"));
+        assert!(updated.contains("alias la='ls -a'\n# <<< dotstate <<<\n# after\n"));
+        assert!(!updated.contains("ll="));
+
+        assert_eq!(strip(&with_block), original);
+        assert_eq!(strip(original), original);
+        assert_eq!(extract(original), None);
+    }
+
+    #[test]
+    fn test_write_and_remove() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source = temp.path().join("repo.vimrc");
+        let target = temp.path().join(".vimrc");
+        fs::write(&source, "set number\n").unwrap();
+        fs::write(&target, "set nocompatible\n").unwrap();
+
+        write(&source, &target).unwrap();
+        let content = fs::read_to_string(&target).unwrap();
+        assert!(content.starts_with("set nocompatible\n\n\" >>> dotstate >>>\nset number\n"));
+        assert!(is_current(&source, &target));
+        assert!(file_has_block(&target));
+
+        remove(&target).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "set nocompatible\n");
+    }
+}
@@ -1,44 +1,115 @@
+pub mod aliases;
+pub mod ansible_export;
 pub mod backup_manager;
+pub mod binary;
+pub mod bootstrap;
+pub mod broadcast;
+pub mod ci;
+pub mod config_validation;
+pub mod config_watcher;
+pub mod conflict_journal;
+pub mod deploy;
+pub mod devcontainer;
 pub mod doctor;
+pub mod dotfiles_import;
+pub mod env_vars;
+pub mod error_report;
+pub mod external;
+pub mod file_policy;
+pub mod file_sync;
+pub mod file_window;
+pub mod foreign_managers;
+pub mod fsck;
+pub mod git_hooks;
+pub mod git_include;
+pub mod hooks;
+#[cfg(feature = "tui")]
+pub mod image_preview;
+pub mod installed_apps;
+pub mod integrity;
+pub mod ipc;
+#[cfg(feature = "tui")]
 pub mod layout;
+pub mod lfs;
+pub mod link_monitor;
+#[cfg(feature = "tui")]
 pub mod list_navigation;
+pub mod local_only;
+pub mod log_redaction;
+pub mod machine_registry;
+pub mod managed_block;
+#[cfg(feature = "tui")]
 pub mod mouse;
 pub mod move_to_common_validation;
+pub mod nix_export;
 pub mod package_cache;
 pub mod package_discovery;
 pub mod package_installer;
 pub mod package_manager;
+pub mod parallel;
 pub mod path;
+pub mod plugins;
 pub mod profile_manifest;
 pub mod profile_validation;
+pub mod proxy;
+pub mod reconcile;
+pub mod repo_gitignore;
+pub mod repo_readme;
+pub mod repo_workflow;
+pub mod scan_cache;
+pub mod scratch;
+pub mod secret_mask;
+pub mod secrets;
+pub mod shell_snippets;
+pub mod sops;
+pub mod ssh_remote;
+pub mod starters;
+pub mod state_migration;
+pub mod status_cache;
+pub mod storage_backend;
+#[cfg(feature = "tui")]
 pub mod style;
 pub mod symlink_manager;
+pub mod sync_history;
 pub mod sync_validation;
 pub mod syntax_theme;
 pub mod text;
+#[cfg(feature = "tui")]
 pub mod text_input;
+pub mod theme_type;
+pub mod trace_export;
+pub mod unicode_path;
 pub mod versioned;
+pub mod word_diff;
 
 // Export utilities that are used
 pub use backup_manager::BackupManager;
-pub use layout::{center_popup, create_split_layout, create_standard_layout};
+pub use config_watcher::ConfigWatcher;
+#[cfg(feature = "tui")]
+pub use layout::{center_popup, create_split_layout, create_standard_layout, is_narrow};
+#[cfg(feature = "tui")]
 pub use list_navigation::{ListStateExt, DEFAULT_PAGE_SIZE};
-pub use mouse::MouseRegions;
+#[cfg(feature = "tui")]
+pub use mouse::{DoubleClick, MouseRegions, ScrollbarDrag};
 pub use move_to_common_validation::{
     validate_move_to_common, MoveToCommonConflict, MoveToCommonValidation,
 };
 pub use path::{
-    expand_path, get_config_dir, get_config_path, get_home_dir, get_repository_path, is_git_repo,
-    is_safe_to_add,
+    expand_path, get_cache_dir, get_config_dir, get_config_path, get_data_dir,
+    get_default_storage_path, get_home_dir, get_repository_path, is_git_repo, is_safe_to_add,
 };
 pub use profile_manifest::{ProfileInfo, ProfileManifest, ResolvedFile};
 pub use profile_validation::{sanitize_profile_name, validate_profile_name};
+pub use status_cache::StatusCache;
+#[cfg(feature = "tui")]
 pub use style::{
     disabled_border_style, disabled_text_style, focused_border_style, input_placeholder_style,
     input_text_style, unfocused_border_style,
 };
 pub use symlink_manager::SymlinkManager;
+#[cfg(feature = "tui")]
 pub use syntax_theme::get_current_syntax_theme;
+#[cfg(feature = "tui")]
 pub use text_input::TextInput;
 pub use versioned::migrate_file;
 
@@ -1,4 +1,11 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::{Position, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::Scrollbar;
+use std::time::{Duration, Instant};
+
+/// Longest gap between the two clicks of a double-click
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
 
 /// Generic utility for tracking clickable screen regions.
 ///
@@ -43,3 +50,214 @@ impl<T> Default for MouseRegions<T> {
         Self::new()
     }
 }
+
+/// Mouse handling for a vertical scrollbar drawn at the right edge of an
+/// area, with arrows at both ends.
+///
+/// Record where it was drawn with [`ScrollbarDrag::set`] during `render()`,
+/// then pass mouse events to [`ScrollbarDrag::handle`]: clicking an arrow
+/// steps by one, clicking the track jumps there, dragging the thumb scrolls
+/// along, and hovering highlights the thumb (see [`ScrollbarDrag::style`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrollbarDrag {
+    /// The scrollbar column, and how many positions it scrolls through
+    track: Option<(Rect, usize)>,
+    dragging: bool,
+    hovered: bool,
+}
+
+impl ScrollbarDrag {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            track: None,
+            dragging: false,
+            hovered: false,
+        }
+    }
+
+    /// Record a scrollbar drawn at the right edge of `area` over
+    /// `content_length` positions
+    pub fn set(&mut self, area: Rect, content_length: usize) {
+        self.track = (area.width > 0 && area.height >= 3 && content_length > 1).then(|| {
+            (
+                Rect::new(area.right() - 1, area.y, 1, area.height),
+                content_length,
+            )
+        });
+    }
+
+    /// Forget the scrollbar, for when it isn't drawn. A drag in progress
+    /// carries on once it's recorded again.
+    pub fn clear(&mut self) {
+        self.track = None;
+    }
+
+    /// Whether the thumb is hovered or being dragged
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.hovered || self.dragging
+    }
+
+    /// `scrollbar`, with its thumb highlighted while hovered or dragged
+    #[must_use]
+    pub fn style<'a>(&self, scrollbar: Scrollbar<'a>) -> Scrollbar<'a> {
+        if self.is_active() {
+            scrollbar.thumb_style(Style::default().fg(crate::styles::theme().primary))
+        } else {
+            scrollbar
+        }
+    }
+
+    /// The position at `row` on the track, between the arrows
+    fn position_at(track: Rect, length: usize, row: u16) -> usize {
+        let span = usize::from(track.height.saturating_sub(2).max(1));
+        let offset = usize::from(row.saturating_sub(track.y + 1)).min(span - 1);
+        if span == 1 {
+            return 0;
+        }
+        (offset * (length - 1) + (span - 1) / 2) / (span - 1)
+    }
+
+    /// Handle a mouse event, `current` being the position scrolled to now.
+    /// Returns the position to scroll to when the event clicked or dragged
+    /// the scrollbar.
+    pub fn handle(&mut self, mouse: MouseEvent, current: usize) -> Option<usize> {
+        let Some((track, length)) = self.track else {
+            self.dragging = false;
+            return None;
+        };
+        let on_track = track.contains(Position::new(mouse.column, mouse.row));
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if on_track => {
+                if mouse.row == track.y {
+                    return Some(current.saturating_sub(1));
+                }
+                if mouse.row == track.bottom() - 1 {
+                    return Some((current + 1).min(length - 1));
+                }
+                self.dragging = true;
+                Some(Self::position_at(track, length, mouse.row))
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                Some(Self::position_at(track, length, mouse.row))
+            }
+            MouseEventKind::Up(_) => {
+                self.dragging = false;
+                None
+            }
+            MouseEventKind::Moved => {
+                self.hovered = on_track;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Recognizes double-clicks, which terminals report as two plain clicks.
+///
+/// Pass every click on a list row to [`DoubleClick::click`] with the row's
+/// value: the first click selects as usual, and a second click on the same
+/// row within [`DOUBLE_CLICK_INTERVAL`] is the double-click.
+#[derive(Debug, Clone)]
+pub struct DoubleClick<T> {
+    last: Option<(Instant, T)>,
+}
+
+impl<T: PartialEq> DoubleClick<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Record a click on `item`. Returns true when it completes a
+    /// double-click; the click after that starts over.
+    pub fn click(&mut self, item: T) -> bool {
+        self.click_at(item, Instant::now())
+    }
+
+    fn click_at(&mut self, item: T, now: Instant) -> bool {
+        let double = self.last.as_ref().is_some_and(|(at, last)| {
+            *last == item && now.saturating_duration_since(*at) <= DOUBLE_CLICK_INTERVAL
+        });
+        self.last = if double { None } else { Some((now, item)) };
+        double
+    }
+}
+
+impl<T: PartialEq> Default for DoubleClick<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn mouse(kind: MouseEventKind, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: 19,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_scrollbar_drag() {
+        // Rows 0 and 11 are the arrows, 1..=10 the track
+        let mut scrollbar = ScrollbarDrag::new();
+        scrollbar.set(Rect::new(0, 0, 20, 12), 100);
+        let down = MouseEventKind::Down(MouseButton::Left);
+
+        assert_eq!(scrollbar.handle(mouse(down, 0), 5), Some(4));
+        assert_eq!(scrollbar.handle(mouse(down, 11), 99), Some(99));
+        assert!(!scrollbar.is_active());
+
+        // Click the track, then drag to its end
+        assert_eq!(scrollbar.handle(mouse(down, 1), 50), Some(0));
+        assert_eq!(
+            scrollbar.handle(mouse(MouseEventKind::Drag(MouseButton::Left), 30), 0),
+            Some(99)
+        );
+        assert!(scrollbar.is_active());
+        scrollbar.handle(mouse(MouseEventKind::Up(MouseButton::Left), 30), 99);
+        assert_eq!(
+            scrollbar.handle(mouse(MouseEventKind::Drag(MouseButton::Left), 5), 99),
+            None
+        );
+
+        // Hover highlights; clicks elsewhere are left alone
+        scrollbar.handle(mouse(MouseEventKind::Moved, 5), 0);
+        assert!(scrollbar.is_active());
+        let elsewhere = MouseEvent {
+            column: 3,
+            ..mouse(down, 5)
+        };
+        assert_eq!(scrollbar.handle(elsewhere, 0), None);
+
+        // Nothing to scroll
+        scrollbar.set(Rect::new(0, 0, 20, 12), 1);
+        assert_eq!(scrollbar.handle(mouse(down, 5), 0), None);
+    }
+
+    #[test]
+    fn test_double_click() {
+        let mut clicks = DoubleClick::new();
+        let start = Instant::now();
+        let later = |ms| start + Duration::from_millis(ms);
+
+        assert!(!clicks.click_at(3, start));
+        assert!(clicks.click_at(3, later(200)));
+        // A third click starts a new double-click
+        assert!(!clicks.click_at(3, later(300)));
+
+        // Another row, or too slow
+        assert!(!clicks.click_at(4, later(400)));
+        assert!(!clicks.click_at(4, later(1000)));
+        assert!(clicks.click_at(4, later(1100)));
+    }
+}
@@ -0,0 +1,337 @@
+//! Export of a profile to a Nix home-manager configuration.
+//!
+//! `dotstate export nix` prints a home-manager module declaring the
+//! profile's tracked files (through `home.file`, sourced from the storage
+//! repository) and its packages (through `home.packages`), for users moving
+//! toward Nix or wanting reproducible rebuilds. With `--flake` it prints a
+//! self-contained `flake.nix` meant for the root of the storage repository.
+
+use crate::utils::profile_manifest::{Package, PackageManager};
+use crate::utils::ProfileManifest;
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// home-manager release the flake's `home.stateVersion` is set to
+const STATE_VERSION: &str = "24.05";
+
+/// What gets exported for one profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NixExport {
+    pub profile: String,
+    pub files: Vec<NixFile>,
+    /// nixpkgs attribute paths, e.g. `ripgrep` or `python3Packages.black`
+    pub packages: Vec<String>,
+    /// Packages with no nixpkgs equivalent (custom install commands)
+    pub skipped: Vec<String>,
+}
+
+/// A `home.file` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NixFile {
+    /// Path relative to the home directory
+    pub target: String,
+    /// Path relative to the storage repository
+    pub source: String,
+    pub is_dir: bool,
+}
+
+/// Collect the files and packages `profile` resolves to, inheritance and
+/// common files included
+pub fn collect(manifest: &ProfileManifest, repo_path: &Path, profile: &str) -> Result<NixExport> {
+    let files = manifest
+        .resolve_files(profile)?
+        .into_iter()
+        .map(|file| {
+            let source = format!("{}/{}", file.source_profile, file.relative_path);
+            NixFile {
+                is_dir: repo_path.join(&source).is_dir(),
+                target: file.relative_path,
+                source,
+            }
+        })
+        .collect();
+    let mut packages = Vec::new();
+    let mut skipped = Vec::new();
+    for package in manifest.resolve_packages(profile)? {
+        match package_attr(&package) {
+            Some(attr) if !packages.contains(&attr) => packages.push(attr),
+            Some(_) => {}
+            None => skipped.push(package.name),
+        }
+    }
+    packages.sort();
+    Ok(NixExport {
+        profile: profile.to_string(),
+        files,
+        packages,
+        skipped,
+    })
+}
+
+/// nixpkgs attribute path for a package, by its name in its manager. Names
+/// mostly match nixpkgs; language packages live in their package sets.
+fn package_attr(package: &Package) -> Option<String> {
+    let name = package.package_name.as_deref().unwrap_or(&package.name);
+    let set = match package.manager {
+        PackageManager::Custom | PackageManager::Plugin(_) => return None,
+        PackageManager::Npm => Some("nodePackages"),
+        PackageManager::Pip | PackageManager::Pip3 => Some("python3Packages"),
+        PackageManager::Gem => Some("rubyPackages"),
+        _ => None,
+    };
+    let attr = attr_name(name);
+    Some(match set {
+        Some(set) => format!("{set}.{attr}"),
+        None => attr,
+    })
+}
+
+/// `name` as a Nix attribute name, quoted unless it is a plain identifier
+fn attr_name(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '\''));
+    if plain {
+        name.to_string()
+    } else {
+        string(name)
+    }
+}
+
+/// `value` as a Nix string literal
+fn string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace("${", "\\${")
+    )
+}
+
+/// `path` as a Nix path expression
+fn path_expr(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if path.starts_with('/')
+        && path
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '+'))
+    {
+        path.trim_end_matches('/').to_string()
+    } else {
+        format!("/. + {}", string(&path))
+    }
+}
+
+/// The `home.file` and `home.packages` attributes, indented for a module
+fn module_body(export: &NixExport, indent: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{indent}home.file = {{");
+    for file in &export.files {
+        // Interpolate the repository into the quoted relative path
+        let source = format!("\"${{dotfiles}}/{}", &string(&file.source)[1..]);
+        if file.is_dir {
+            let _ = writeln!(
+                out,
+                "{indent}  {} = {{ source = {source}; recursive = true; }};",
+                string(&file.target)
+            );
+        } else {
+            let _ = writeln!(out, "{indent}  {}.source = {source};", string(&file.target));
+        }
+    }
+    let _ = writeln!(out, "{indent}}};");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{indent}home.packages = [");
+    for attr in &export.packages {
+        let _ = writeln!(out, "{indent}  pkgs.{attr}");
+    }
+    let _ = writeln!(out, "{indent}];");
+    if !export.skipped.is_empty() {
+        let _ = writeln!(
+            out,
+            "{indent}# Custom packages with no nixpkgs equivalent: {}",
+            export.skipped.join(", ")
+        );
+    }
+    out
+}
+
+const NOTE: &str = "# Package names come from the package managers and may differ in nixpkgs;\n\
+                    # check them with `nix search nixpkgs <name>`.";
+
+/// A home-manager module, sourcing the files from the storage repository
+/// at `repo_path`
+#[must_use]
+pub fn home_module(export: &NixExport, repo_path: &Path) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Generated by dotstate from profile {}.",
+        string(&export.profile)
+    );
+    let _ = writeln!(
+        out,
+        "# Import it from your home-manager configuration: imports = [ ./home.nix ];"
+    );
+    let _ = writeln!(out, "{NOTE}");
+    let _ = writeln!(out, "{{ pkgs, ... }}:");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "let");
+    let _ = writeln!(out, "  dotfiles = {};", path_expr(repo_path));
+    let _ = writeln!(out, "in");
+    let _ = writeln!(out, "{{");
+    out.push_str(&module_body(export, "  "));
+    let _ = writeln!(out, "}}");
+    out
+}
+
+/// A standalone `flake.nix` for the root of the storage repository, with a
+/// home-manager configuration for `user` on `system`
+#[must_use]
+pub fn flake(export: &NixExport, user: &str, home: &Path, system: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Generated by dotstate from profile {}.",
+        string(&export.profile)
+    );
+    let _ = writeln!(
+        out,
+        "# Save it as flake.nix in the storage repository, then run:"
+    );
+    let _ = writeln!(
+        out,
+        "#   nix run home-manager/master -- switch --flake .#{user}"
+    );
+    let _ = writeln!(out, "{NOTE}");
+    let _ = writeln!(out, "{{");
+    let _ = writeln!(out, "  description = \"Dotfiles exported by dotstate\";");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "  inputs = {{");
+    let _ = writeln!(
+        out,
+        "    nixpkgs.url = \"github:nixos/nixpkgs/nixos-unstable\";"
+    );
+    let _ = writeln!(
+        out,
+        "    home-manager.url = \"github:nix-community/home-manager\";"
+    );
+    let _ = writeln!(
+        out,
+        "    home-manager.inputs.nixpkgs.follows = \"nixpkgs\";"
+    );
+    let _ = writeln!(out, "  }};");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "  outputs = {{ nixpkgs, home-manager, ... }}:");
+    let _ = writeln!(out, "    let");
+    let _ = writeln!(out, "      dotfiles = ./.;");
+    let _ = writeln!(
+        out,
+        "      pkgs = nixpkgs.legacyPackages.{};",
+        string(system)
+    );
+    let _ = writeln!(out, "    in");
+    let _ = writeln!(out, "    {{");
+    let _ = writeln!(
+        out,
+        "      homeConfigurations.{} = home-manager.lib.homeManagerConfiguration {{",
+        attr_name(user)
+    );
+    let _ = writeln!(out, "        inherit pkgs;");
+    let _ = writeln!(out, "        modules = [");
+    let _ = writeln!(out, "          {{");
+    let _ = writeln!(out, "            home.username = {};", string(user));
+    let _ = writeln!(
+        out,
+        "            home.homeDirectory = {};",
+        string(&home.to_string_lossy())
+    );
+    let _ = writeln!(out, "            home.stateVersion = \"{STATE_VERSION}\";");
+    out.push_str(&module_body(export, "            "));
+    let _ = writeln!(out, "          }}");
+    let _ = writeln!(out, "        ];");
+    let _ = writeln!(out, "      }};");
+    let _ = writeln!(out, "    }};");
+    let _ = writeln!(out, "}}");
+    out
+}
+
+/// Nix system double for the running machine, e.g. `x86_64-linux`
+#[must_use]
+pub fn current_system() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    format!("{}-{os}", std::env::consts::ARCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::{CommonSection, ProfileInfo};
+    use tempfile::TempDir;
+
+    fn package(name: &str, manager: PackageManager) -> Package {
+        Package {
+            name: name.to_string(),
+            description: None,
+            manager,
+            package_name: None,
+            binary_name: name.to_string(),
+            install_command: None,
+            existence_check: None,
+            manager_check: None,
+        }
+    }
+
+    #[test]
+    fn test_export() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("Work/.config/nvim")).unwrap();
+        let manifest = ProfileManifest {
+            common: CommonSection {
+                synced_files: vec![".gitconfig".to_string()],
+            },
+            profiles: vec![ProfileInfo {
+                name: "Work".to_string(),
+                description: None,
+                inherits: None,
+                synced_files: vec![".zshrc".to_string(), ".config/nvim".to_string()],
+                packages: vec![
+                    package("ripgrep", PackageManager::Brew),
+                    package("black", PackageManager::Pip),
+                    package("7zip", PackageManager::Apt),
+                    package("mytool", PackageManager::Custom),
+                ],
+            }],
+            ..ProfileManifest::default()
+        };
+
+        let export = collect(&manifest, temp.path(), "Work").unwrap();
+        assert_eq!(
+            export.packages,
+            vec!["\"7zip\"", "python3Packages.black", "ripgrep"]
+        );
+        assert_eq!(export.skipped, vec!["mytool"]);
+
+        let module = home_module(&export, Path::new("/home/me/.dotstate"));
+        assert!(module.contains("  dotfiles = /home/me/.dotstate;\n"));
+        assert!(module.contains(
+            "    \".config/nvim\" = { source = \"${dotfiles}/Work/.config/nvim\"; recursive = true; };\n"
+        ));
+        assert!(module.contains("    \".gitconfig\".source = \"${dotfiles}/common/.gitconfig\";\n"));
+        assert!(module.contains("    pkgs.python3Packages.black\n"));
+
+        let flake = flake(&export, "me", Path::new("/home/me"), "x86_64-linux");
+        assert!(flake.contains("      dotfiles = ./.;\n"));
+        assert!(flake.contains("homeConfigurations.me = "));
+        assert!(flake.contains("            home.homeDirectory = \"/home/me\";\n"));
+    }
+}
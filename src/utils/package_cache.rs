@@ -7,7 +7,7 @@ use tracing::{debug, info, warn};
 
 /// Current version of the `package_status.json` file format.
 /// Increment this when making breaking changes to the schema.
-const CURRENT_VERSION: u32 = 1;
+pub(crate) const CURRENT_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageCacheEntry {
@@ -75,9 +75,9 @@ impl Default for PackageCache {
                 );
                 // Fallback to a dummy path that probably won't write successfully but allows the app to validly construct the struct.
                 // Or better: use a sensible default path even if we couldn't create it right now.
-                let config_dir = crate::utils::get_config_dir();
+                let cache_dir = crate::utils::get_cache_dir();
                 Self {
-                    cache_file: config_dir.join("package_status.json"),
+                    cache_file: cache_dir.join("package_status.json"),
                     data: PackageCacheData::default(),
                 }
             }
@@ -87,9 +87,12 @@ impl Default for PackageCache {
 
 impl PackageCache {
     pub fn new() -> Result<Self> {
-        let config_dir = crate::utils::get_config_dir();
-        let cache_file = config_dir.join("package_status.json");
+        let cache_dir = crate::utils::get_cache_dir();
+        Self::load(cache_dir.join("package_status.json"))
+    }
 
+    /// Load the cache from `cache_file`, migrating and saving older formats
+    pub fn load(cache_file: PathBuf) -> Result<Self> {
         let mut data: PackageCacheData = if cache_file.exists() {
             match std::fs::read_to_string(&cache_file) {
                 Ok(content) => match serde_json::from_str(&content) {
@@ -175,7 +178,7 @@ impl PackageCache {
         let temp_path = self.cache_file.with_extension("json.tmp");
 
         if let Some(parent) = self.cache_file.parent() {
-            std::fs::create_dir_all(parent).context("Failed to create config directory")?;
+            std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
         }
 
         let json = serde_json::to_string_pretty(&self.data)
@@ -97,7 +97,8 @@ impl DiscoverySource {
             PackageManager::Pip => Some(DiscoverySource::Pip),
             PackageManager::Pip3 => Some(DiscoverySource::Pip3),
             PackageManager::Gem => Some(DiscoverySource::Gem),
-            PackageManager::Custom => None, // Custom doesn't support discovery
+            // Custom and plugin packages don't support discovery
+            PackageManager::Custom | PackageManager::Plugin(_) => None,
         }
     }
 
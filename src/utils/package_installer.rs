@@ -7,6 +7,16 @@ use std::sync::mpsc;
 use std::thread;
 use tracing::debug;
 
+/// Installation status message from background thread
+#[derive(Debug, Clone)]
+pub enum InstallationStatus {
+    Output(String), // Output line
+    Complete {
+        success: bool,
+        error: Option<String>,
+    }, // Installation complete
+}
+
 /// Package installer and checker utilities
 pub struct PackageInstaller;
 
@@ -17,8 +27,6 @@ pub struct InstallationHandle {
     pub output_rx: mpsc::Receiver<String>,
 }
 
-use crate::ui::InstallationStatus;
-
 impl PackageInstaller {
     /// Synchronous install that streams status to a sender
     pub fn install(package: &Package, tx: mpsc::Sender<InstallationStatus>) {
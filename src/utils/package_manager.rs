@@ -1,3 +1,4 @@
+use crate::utils::plugins::{self, PluginRequest, PACKAGE_MANAGER_CAPABILITY};
 use crate::utils::profile_manifest::{Package, PackageManager};
 use std::path::PathBuf;
 use std::process::Command;
@@ -96,6 +97,9 @@ impl PackageManagerImpl {
             PackageManager::Pip3 => "pip3",
             PackageManager::Gem => "gem",
             PackageManager::Custom => return true, // Always available
+            PackageManager::Plugin(name) => {
+                return plugins::find(name, PACKAGE_MANAGER_CAPABILITY).is_ok()
+            }
         };
 
         Self::check_binary_in_path(binary_name)
@@ -175,6 +179,27 @@ impl PackageManagerImpl {
                 // Command will be set by caller
                 cmd
             }
+            PackageManager::Plugin(name) => Self::plugin_command(
+                name,
+                &PluginRequest::PackageInstall {
+                    package: package_name.to_string(),
+                },
+            ),
+        }
+    }
+
+    /// Command sending `request` to the package-manager plugin `name`, or
+    /// one failing with why the plugin can't be used
+    fn plugin_command(name: &str, request: &PluginRequest) -> Command {
+        match plugins::find(name, PACKAGE_MANAGER_CAPABILITY) {
+            Ok(plugin) => plugin.package_command(request),
+            Err(e) => {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c")
+                    .arg("echo \"$0\" >&2; exit 1")
+                    .arg(format!("{e:#}"));
+                cmd
+            }
         }
     }
 
@@ -260,6 +285,12 @@ impl PackageManagerImpl {
                 Some(cmd)
             }
             PackageManager::Custom => None, // Custom uses user-provided check
+            PackageManager::Plugin(name) => Some(Self::plugin_command(
+                name,
+                &PluginRequest::PackageCheck {
+                    package: package_name.to_string(),
+                },
+            )),
         }
     }
 
@@ -336,6 +367,14 @@ impl PackageManagerImpl {
             available.push(PackageManager::Gem);
         }
 
+        // Plugins adapting other package managers
+        available.extend(
+            plugins::discover()
+                .into_iter()
+                .filter(|plugin| plugin.provides(PACKAGE_MANAGER_CAPABILITY))
+                .map(|plugin| PackageManager::Plugin(plugin.name())),
+        );
+
         // Custom is always available
         available.push(PackageManager::Custom);
 
@@ -368,6 +407,10 @@ impl PackageManagerImpl {
             PackageManager::Pip3 => "pip3 usually comes with Python 3".to_string(),
             PackageManager::Gem => "gem comes with Ruby".to_string(),
             PackageManager::Custom => "N/A - custom packages don't require a manager".to_string(),
+            PackageManager::Plugin(name) => format!(
+                "Install a plugin named '{name}' with the package_manager capability in {}",
+                plugins::plugin_dir().display()
+            ),
         }
     }
 }
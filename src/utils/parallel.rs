@@ -0,0 +1,84 @@
+//! Bounded parallel execution for filesystem-heavy operations.
+//!
+//! Activating a profile or copying a large `.config` tree is dominated by
+//! many small, independent syscalls. Running them on a few worker threads
+//! hides most of the per-file latency.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Upper bound on worker threads, regardless of core count. Filesystem work
+/// stops scaling well beyond this and we don't want to flood slow disks.
+const MAX_WORKERS: usize = 8;
+
+/// Number of workers to use for `len` items.
+fn worker_count(len: usize) -> usize {
+    let cores = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+    cores.min(MAX_WORKERS).min(len)
+}
+
+/// Apply `f` to every item on a bounded pool of scoped threads.
+///
+/// Results are returned in the same order as `items`. Small inputs (or a
+/// single core) run on the calling thread.
+pub fn parallel_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = worker_count(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = f(item);
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .into_iter()
+        .map(|r| r.expect("every item is processed exactly once"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_map_preserves_order() {
+        let items: Vec<usize> = (0..500).collect();
+        let doubled = parallel_map(&items, |n| n * 2);
+        assert_eq!(doubled, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_parallel_map_empty() {
+        let items: Vec<u8> = Vec::new();
+        assert!(parallel_map(&items, |n| *n).is_empty());
+    }
+
+    #[test]
+    fn test_worker_count_bounded() {
+        assert_eq!(worker_count(0), 0);
+        assert_eq!(worker_count(1), 1);
+        assert!(worker_count(1000) <= MAX_WORKERS);
+    }
+}
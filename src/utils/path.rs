@@ -62,33 +62,80 @@ pub fn is_safe_to_add(path: &Path, repo_path: &Path) -> (bool, Option<String>) {
     (true, None)
 }
 
-/// Get the config directory path (always ~/.config/dotstate, regardless of OS)
+/// Resolve an XDG base directory: the environment variable when it holds an
+/// absolute path (relative values are ignored, as the spec requires),
+/// otherwise the default under `home`.
+fn xdg_base_dir(var: &str, home: &Path, default: &str) -> PathBuf {
+    match std::env::var_os(var).map(PathBuf::from) {
+        Some(dir) if dir.is_absolute() => dir,
+        _ => home.join(default),
+    }
+}
+
+/// Get the config directory path (`$XDG_CONFIG_HOME/dotstate`, which defaults
+/// to ~/.config/dotstate on every OS)
 ///
-/// In tests, set `DOTSTATE_TEST_CONFIG_DIR` env var to override.
+/// Holds `config.toml` only. In tests, set `DOTSTATE_TEST_CONFIG_DIR` env var
+/// to override.
 #[must_use]
 pub fn get_config_dir() -> PathBuf {
     if let Ok(test_config) = std::env::var("DOTSTATE_TEST_CONFIG_DIR") {
         return PathBuf::from(test_config);
     }
-    get_home_dir().join(".config").join("dotstate")
+    xdg_base_dir("XDG_CONFIG_HOME", &get_home_dir(), ".config").join("dotstate")
 }
 
-/// Get the config file path (always ~/.config/dotstate/config.toml, regardless of OS)
+/// Get the data directory path (`$XDG_DATA_HOME/dotstate`, which defaults to
+/// ~/.local/share/dotstate on every OS)
+///
+/// Holds state that can't be regenerated: the default storage clone, symlink
+/// tracking and the last sync time. Tests that override the config directory
+/// get the same directory here.
+#[must_use]
+pub fn get_data_dir() -> PathBuf {
+    if let Ok(test_config) = std::env::var("DOTSTATE_TEST_CONFIG_DIR") {
+        return PathBuf::from(test_config);
+    }
+    xdg_base_dir("XDG_DATA_HOME", &get_home_dir(), ".local/share").join("dotstate")
+}
+
+/// Get the cache directory path (`$XDG_CACHE_HOME/dotstate`, which defaults to
+/// ~/.cache/dotstate on every OS)
+///
+/// Holds the log file and caches that are safe to delete. Tests that override
+/// the config directory get the same directory here.
+#[must_use]
+pub fn get_cache_dir() -> PathBuf {
+    if let Ok(test_config) = std::env::var("DOTSTATE_TEST_CONFIG_DIR") {
+        return PathBuf::from(test_config);
+    }
+    xdg_base_dir("XDG_CACHE_HOME", &get_home_dir(), ".cache").join("dotstate")
+}
+
+/// Get the config file path (`config.toml` in [`get_config_dir`])
 #[must_use]
 pub fn get_config_path() -> PathBuf {
     get_config_dir().join("config.toml")
 }
 
-/// Expand a path string, handling ~ and relative paths
+/// Default location of the storage repository for new installs
+#[must_use]
+pub fn get_default_storage_path() -> PathBuf {
+    get_data_dir().join("storage")
+}
+
+/// Expand a path string, handling ~, environment variables and relative paths
 ///
 /// # Arguments
-/// * `path_str` - Path string that may contain ~ or be relative
+/// * `path_str` - Path string that may contain ~ or `$VARS`, or be relative
 ///
 /// # Returns
 /// Expanded `PathBuf`
 #[must_use]
 pub fn expand_path(path_str: &str) -> PathBuf {
     let home_dir = get_home_dir();
+    let expanded = expand_vars(path_str);
+    let path_str = expanded.as_str();
 
     if path_str.starts_with('/') {
         PathBuf::from(path_str)
@@ -102,6 +149,78 @@ pub fn expand_path(path_str: &str) -> PathBuf {
     }
 }
 
+/// Expand a leading `~` and `$NAME` / `${NAME}` environment variables.
+///
+/// `$HOME` is the same home directory the rest of the app uses, and unset
+/// `XDG_*` base directories fall back to their spec defaults, so a config file
+/// written on one machine works on another. Other unset variables are left
+/// as written.
+#[must_use]
+pub fn expand_vars(input: &str) -> String {
+    expand_vars_with(input, &get_home_dir(), |name| std::env::var(name).ok())
+}
+
+fn expand_vars_with(input: &str, home: &Path, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let home_str = home.to_string_lossy();
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    if rest == "~" {
+        return home_str.into_owned();
+    }
+    if let Some(stripped) = rest.strip_prefix("~/") {
+        out.push_str(&home_str);
+        out.push('/');
+        rest = stripped;
+    }
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+
+        let value = if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            None
+        } else if name == "HOME" {
+            Some(home_str.to_string())
+        } else {
+            lookup(name)
+                .filter(|v| !v.is_empty())
+                .or_else(|| xdg_default(name).map(|d| home.join(d).to_string_lossy().into_owned()))
+        };
+
+        match value {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[pos..pos + 1 + consumed]),
+        }
+        rest = &after[consumed..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Spec default of an XDG base directory, relative to home
+fn xdg_default(name: &str) -> Option<&'static str> {
+    match name {
+        "XDG_CONFIG_HOME" => Some(".config"),
+        "XDG_DATA_HOME" => Some(".local/share"),
+        "XDG_CACHE_HOME" => Some(".cache"),
+        "XDG_STATE_HOME" => Some(".local/state"),
+        _ => None,
+    }
+}
+
 /// Format a path for display (shorten if too long, show ~ for home)
 ///
 /// # Arguments
@@ -109,7 +228,6 @@ pub fn expand_path(path_str: &str) -> PathBuf {
 ///
 /// # Returns
 /// Formatted string
-#[allow(dead_code)]
 #[must_use]
 pub fn format_path_for_display(path: &Path) -> String {
     let home_dir = get_home_dir();
@@ -151,3 +269,38 @@ pub fn get_repository_path() -> anyhow::Result<PathBuf> {
         .map_err(|e| anyhow::anyhow!("Failed to load config: {e}"))?;
     Ok(config.repo_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(input: &str) -> String {
+        expand_vars_with(input, Path::new("/home/me"), |name| match name {
+            "XDG_CONFIG_HOME" => Some("/xdg/config".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_expand_vars() {
+        assert_eq!(expand("~"), "/home/me");
+        assert_eq!(expand("~/dots"), "/home/me/dots");
+        assert_eq!(expand("$HOME/dots"), "/home/me/dots");
+        assert_eq!(expand("${HOME}/dots"), "/home/me/dots");
+        assert_eq!(expand("${XDG_CONFIG_HOME}/nvim"), "/xdg/config/nvim");
+        assert_eq!(
+            expand("$XDG_DATA_HOME/dotstate"),
+            "/home/me/.local/share/dotstate"
+        );
+        assert_eq!(expand("/srv/$EMPTY"), "/srv/$EMPTY");
+    }
+
+    #[test]
+    fn test_expand_vars_leaves_unknown_text() {
+        assert_eq!(expand("/opt/$UNSET/x"), "/opt/$UNSET/x");
+        assert_eq!(expand("/opt/${UNCLOSED"), "/opt/${UNCLOSED");
+        assert_eq!(expand("price$"), "price$");
+        assert_eq!(expand("a/~/b"), "a/~/b");
+    }
+}
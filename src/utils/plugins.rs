@@ -0,0 +1,422 @@
+//! External plugins speaking JSON over stdio.
+//!
+//! A plugin is an executable in the `plugins/` directory of the config
+//! directory. `DotState` runs it once per request, writes one JSON request to
+//! its stdin and reads one JSON response from its stdout:
+//!
+//! - `{"method":"describe"}` answers a [`Description`]: its name, version
+//!   and the capabilities it provides.
+//! - `{"method":"doctor","repo_path":"...","profile":"..."}` answers
+//!   `{"checks":[...]}` with [`PluginCheck`] results, shown by
+//!   `dotstate doctor` under "Plugins". Only asked of plugins with the
+//!   `doctor` capability.
+//! - `{"method":"storage_upload","location":"...","object":"...","path":"..."}`
+//!   stores the encrypted archive at `path` and answers `{}`;
+//!   `{"method":"storage_download",...}` writes the stored archive to `path`
+//!   and answers `{"found":true}`, or `{"found":false}` when nothing was
+//!   uploaded yet. Asked of the plugin named by a `[storage_backend]` of kind
+//!   `plugin`, which needs the `storage` capability.
+//! - `{"method":"package_check","package":"..."}` and
+//!   `{"method":"package_install","package":"..."}` are answered with the
+//!   exit status alone (0 when the package is installed, or was installed),
+//!   and their output is shown as it runs. Asked of the plugin named by a
+//!   package's `manager = { plugin = "<name>" }`, which needs the
+//!   `package_manager` capability.
+//!
+//! A plugin that fails, prints something else or doesn't answer within
+//! [`TIMEOUT`] ([`STORAGE_TIMEOUT`] for storage requests) is reported as
+//! broken instead of failing the caller.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Name of the plugin directory in the config directory
+const PLUGIN_DIR: &str = "plugins";
+
+/// How long a plugin may take to answer
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a storage plugin may take to move the archive
+const STORAGE_TIMEOUT: Duration = Duration::from_mins(5);
+
+/// Environment variable holding the request for plugins run through
+/// [`Plugin::package_command`], which pipes it to their stdin
+const REQUEST_ENV: &str = "DOTSTATE_PLUGIN_REQUEST";
+
+/// Capability of plugins that add doctor checks
+pub const DOCTOR_CAPABILITY: &str = "doctor";
+
+/// Capability of plugins that keep the storage backend's archive
+pub const STORAGE_CAPABILITY: &str = "storage";
+
+/// Capability of plugins that check and install packages
+pub const PACKAGE_MANAGER_CAPABILITY: &str = "package_manager";
+
+/// A request written to a plugin's stdin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum PluginRequest {
+    Describe,
+    Doctor {
+        repo_path: PathBuf,
+        profile: String,
+    },
+    StorageUpload {
+        location: String,
+        object: String,
+        path: PathBuf,
+    },
+    StorageDownload {
+        location: String,
+        object: String,
+        path: PathBuf,
+    },
+    PackageCheck {
+        package: String,
+    },
+    PackageInstall {
+        package: String,
+    },
+}
+
+/// What a plugin says about itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Description {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+/// Outcome of a plugin's doctor check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warning,
+    Error,
+}
+
+/// One doctor check reported by a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PluginCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    #[serde(default)]
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DoctorResponse {
+    #[serde(default)]
+    checks: Vec<PluginCheck>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StorageResponse {
+    #[serde(default)]
+    found: bool,
+}
+
+/// An installed plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    pub path: PathBuf,
+    /// Its description, or why it couldn't be described
+    pub description: Result<Description, String>,
+}
+
+impl Plugin {
+    /// Name from the description, falling back to the file name
+    #[must_use]
+    pub fn name(&self) -> String {
+        match &self.description {
+            Ok(description) if !description.name.is_empty() => description.name.clone(),
+            _ => self
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether the plugin declares `capability`
+    #[must_use]
+    pub fn provides(&self, capability: &str) -> bool {
+        self.description
+            .as_ref()
+            .is_ok_and(|description| description.capabilities.iter().any(|c| c == capability))
+    }
+
+    /// Run the plugin's doctor checks
+    pub fn doctor(&self, repo_path: &Path, profile: &str) -> Result<Vec<PluginCheck>> {
+        let response: DoctorResponse = call(
+            &self.path,
+            &PluginRequest::Doctor {
+                repo_path: repo_path.to_path_buf(),
+                profile: profile.to_string(),
+            },
+        )?;
+        Ok(response.checks)
+    }
+
+    /// Have the plugin store the file at `path` as `object` in `location`
+    pub fn upload(&self, location: &str, object: &str, path: &Path) -> Result<()> {
+        call_with_timeout::<StorageResponse>(
+            &self.path,
+            &PluginRequest::StorageUpload {
+                location: location.to_string(),
+                object: object.to_string(),
+                path: path.to_path_buf(),
+            },
+            STORAGE_TIMEOUT,
+        )?;
+        Ok(())
+    }
+
+    /// Have the plugin write `object` from `location` to `path`. Returns
+    /// whether it was found.
+    pub fn download(&self, location: &str, object: &str, path: &Path) -> Result<bool> {
+        let response: StorageResponse = call_with_timeout(
+            &self.path,
+            &PluginRequest::StorageDownload {
+                location: location.to_string(),
+                object: object.to_string(),
+                path: path.to_path_buf(),
+            },
+            STORAGE_TIMEOUT,
+        )?;
+        Ok(response.found)
+    }
+
+    /// Command sending `request` (a package check or install) to the
+    /// plugin. It answers with its exit status, and its output is shown as
+    /// the command runs.
+    #[must_use]
+    pub fn package_command(&self, request: &PluginRequest) -> Command {
+        let request = serde_json::to_string(request).unwrap_or_default();
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(format!("printf '%s\\n' \"${REQUEST_ENV}\" | \"$0\""))
+            .arg(&self.path)
+            .env(REQUEST_ENV, request);
+        cmd
+    }
+}
+
+/// Where plugins are installed
+#[must_use]
+pub fn plugin_dir() -> PathBuf {
+    crate::utils::get_config_dir().join(PLUGIN_DIR)
+}
+
+/// Executables in `dir`, by name (hidden files skipped)
+fn executables(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_executable(path))
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "exe")
+}
+
+/// Installed plugins, each asked to describe itself
+#[must_use]
+pub fn discover() -> Vec<Plugin> {
+    discover_in(&plugin_dir())
+}
+
+/// The installed plugin called `name` that provides `capability`
+pub fn find(name: &str, capability: &str) -> Result<Plugin> {
+    find_in(&plugin_dir(), name, capability)
+}
+
+fn find_in(dir: &Path, name: &str, capability: &str) -> Result<Plugin> {
+    let Some(plugin) = discover_in(dir)
+        .into_iter()
+        .find(|plugin| plugin.name() == name)
+    else {
+        bail!("No plugin named '{name}' is installed in {}", dir.display());
+    };
+    if !plugin.provides(capability) {
+        bail!("Plugin '{name}' doesn't provide the '{capability}' capability");
+    }
+    Ok(plugin)
+}
+
+fn discover_in(dir: &Path) -> Vec<Plugin> {
+    executables(dir)
+        .into_iter()
+        .map(|path| {
+            let description = call(&path, &PluginRequest::Describe).map_err(|e| format!("{e:#}"));
+            Plugin { path, description }
+        })
+        .collect()
+}
+
+/// Send `request` to the plugin at `path` and parse its answer
+fn call<T: serde::de::DeserializeOwned>(path: &Path, request: &PluginRequest) -> Result<T> {
+    call_with_timeout(path, request, TIMEOUT)
+}
+
+fn call_with_timeout<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    request: &PluginRequest,
+    timeout: Duration,
+) -> Result<T> {
+    let mut child = Command::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", path.display()))?;
+
+    let request = serde_json::to_string(request).context("Failed to serialize request")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A plugin that ignores its input may exit before reading it
+        let _ = writeln!(stdin, "{request}");
+    }
+    // Read on a thread so a full pipe can't block the timeout
+    let mut stdout = child
+        .stdout
+        .take()
+        .context("Failed to read plugin output")?;
+    let reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).map(|_| output)
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("Plugin didn't answer within {} seconds", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+    let output = reader
+        .join()
+        .map_err(|_| anyhow::anyhow!("Failed to read plugin output"))?
+        .context("Failed to read plugin output")?;
+    if !status.success() {
+        bail!("Plugin exited with {status}");
+    }
+    serde_json::from_str(output.trim()).context("Plugin answered with invalid JSON")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    fn write_plugin(dir: &Path, name: &str, script: &str) {
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_discover_and_run_doctor_checks() {
+        let temp_dir = TempDir::new().unwrap();
+        write_plugin(
+            temp_dir.path(),
+            "tmux-check",
+            r#"read request
+case "$request" in
+  *describe*) echo '{"name":"tmux","version":"1.0","capabilities":["doctor"]}' ;;
+  *doctor*) echo '{"checks":[{"name":"tpm","status":"warning","message":"tpm is missing"}]}' ;;
+esac
+"#,
+        );
+        write_plugin(temp_dir.path(), "broken", "echo not json\n");
+        std::fs::write(temp_dir.path().join("README"), "not a plugin").unwrap();
+
+        let plugins = discover_in(temp_dir.path());
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[0].name(), "broken");
+        assert!(plugins[0].description.is_err());
+        assert!(!plugins[0].provides(DOCTOR_CAPABILITY));
+
+        let tmux = &plugins[1];
+        assert_eq!(tmux.name(), "tmux");
+        assert!(tmux.provides(DOCTOR_CAPABILITY));
+        let checks = tmux.doctor(temp_dir.path(), "work").unwrap();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].status, CheckStatus::Warning);
+        assert!(checks[0].details.is_empty());
+    }
+
+    #[test]
+    fn test_storage_and_package_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        write_plugin(
+            temp_dir.path(),
+            "store",
+            r#"read request
+stored="$(dirname "$0")/.stored"
+path=$(echo "$request" | sed 's/.*"path":"\([^"]*\)".*/\1/')
+case "$request" in
+  *describe*) echo '{"name":"store","capabilities":["storage","package_manager"]}' ;;
+  *storage_upload*) cp "$path" "$stored" && echo '{}' ;;
+  *storage_download*)
+    if [ -f "$stored" ]; then cp "$stored" "$path" && echo '{"found":true}'
+    else echo '{"found":false}'; fi ;;
+  *'"package":"jq"'*) echo "jq is installed" ;;
+  *) exit 1 ;;
+esac
+"#,
+        );
+
+        assert!(find_in(temp_dir.path(), "other", STORAGE_CAPABILITY).is_err());
+        assert!(find_in(temp_dir.path(), "store", DOCTOR_CAPABILITY).is_err());
+        let plugin = find_in(temp_dir.path(), "store", STORAGE_CAPABILITY).unwrap();
+
+        let archive = temp_dir.path().join("archive");
+        assert!(!plugin.download("bucket", "a.age", &archive).unwrap());
+        std::fs::write(&archive, "ciphertext").unwrap();
+        plugin.upload("bucket", "a.age", &archive).unwrap();
+        std::fs::remove_file(&archive).unwrap();
+        assert!(plugin.download("bucket", "a.age", &archive).unwrap());
+        assert_eq!(std::fs::read_to_string(&archive).unwrap(), "ciphertext");
+
+        let check = |package: &str| {
+            plugin
+                .package_command(&PluginRequest::PackageCheck {
+                    package: package.to_string(),
+                })
+                .output()
+                .unwrap()
+        };
+        let output = check("jq");
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "jq is installed\n");
+        assert!(!check("fzf").status.success());
+    }
+}
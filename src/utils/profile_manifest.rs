@@ -1,6 +1,8 @@
-use anyhow::{Context, Result};
+use crate::utils::unicode_path::nfc;
+use crate::version_check::{current_version, is_newer};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 /// Current version of the manifest file format.
@@ -35,7 +37,7 @@ impl ResolvedFile {
 }
 
 /// Package manager types
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum PackageManager {
     Brew,   // Homebrew (macOS/Linux)
@@ -50,6 +52,32 @@ pub enum PackageManager {
     Pip3,   // Python packages (pip3)
     Gem,    // Ruby gems
     Custom, // Custom install command
+    /// Plugin with the `package_manager` capability, by name
+    /// (`manager = { plugin = "<name>" }`)
+    Plugin(String),
+}
+
+/// Shown as the manager's name; a plugin as `plugin:<name>`, which
+/// `dotstate packages add --manager` accepts
+impl std::fmt::Debug for PackageManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Brew => "Brew",
+            Self::Apt => "Apt",
+            Self::Yum => "Yum",
+            Self::Dnf => "Dnf",
+            Self::Pacman => "Pacman",
+            Self::Snap => "Snap",
+            Self::Cargo => "Cargo",
+            Self::Npm => "Npm",
+            Self::Pip => "Pip",
+            Self::Pip3 => "Pip3",
+            Self::Gem => "Gem",
+            Self::Custom => "Custom",
+            Self::Plugin(name) => return write!(f, "plugin:{name}"),
+        };
+        f.write_str(name)
+    }
 }
 
 /// Package definition
@@ -106,6 +134,26 @@ pub struct ProfileManifest {
     /// List of profile names
     #[serde(default)]
     pub profiles: Vec<ProfileInfo>,
+    /// Children of synced directories kept out of the repository (relative
+    /// paths from home directory). Each machine keeps its own copy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub local_only: Vec<String>,
+    /// Synced files kept in a managed block of the real file instead of
+    /// symlinked (relative paths from home directory)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub managed_blocks: Vec<String>,
+    /// Git config files whose shared settings are included from the
+    /// repository instead of symlinked (relative paths from home directory)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_includes: Vec<String>,
+    /// Content hash of each tracked file as of the last sync, by its path in
+    /// the repository (`common/.zshrc`, `Work/.config/nvim`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, String>,
+    /// dotstate version that last saved the manifest. Older versions refuse
+    /// to write to the repository, so they don't drop what they don't know.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub written_by: Option<String>,
 }
 
 impl Default for ProfileManifest {
@@ -114,6 +162,11 @@ impl Default for ProfileManifest {
             version: CURRENT_VERSION,
             common: CommonSection::default(),
             profiles: Vec::new(),
+            local_only: Vec::new(),
+            managed_blocks: Vec::new(),
+            git_includes: Vec::new(),
+            hashes: BTreeMap::new(),
+            written_by: None,
         }
     }
 }
@@ -138,6 +191,18 @@ pub struct ProfileInfo {
     pub packages: Vec<Package>,
 }
 
+/// Precompose (NFC) and sort `files`, dropping names that only differed
+/// in their Unicode normalization
+fn normalize_files(files: &mut Vec<String>) {
+    for file in files.iter_mut() {
+        if let std::borrow::Cow::Owned(composed) = nfc(file) {
+            *file = composed;
+        }
+    }
+    files.sort();
+    files.dedup();
+}
+
 impl ProfileManifest {
     /// Get the path to the manifest file in the repo
     #[must_use]
@@ -172,10 +237,11 @@ impl ProfileManifest {
                 })?;
             }
 
-            // Sort synced_files alphabetically to ensure consistent ordering
-            manifest.common.synced_files.sort();
+            // Precompose names recorded on another OS, and sort synced_files
+            // alphabetically to ensure consistent ordering
+            normalize_files(&mut manifest.common.synced_files);
             for profile in &mut manifest.profiles {
-                profile.synced_files.sort();
+                normalize_files(&mut profile.synced_files);
             }
 
             Ok(manifest)
@@ -298,14 +364,41 @@ impl ProfileManifest {
         }
     }
 
-    /// Save the manifest to the repository.
+    /// The dotstate version needed to write to the repository, when a newer
+    /// version than this one last saved the manifest
+    #[must_use]
+    pub fn required_version(&self) -> Option<&str> {
+        self.written_by
+            .as_deref()
+            .filter(|version| is_newer(version, current_version()))
+    }
+
+    /// Fail when a newer dotstate last saved the manifest
+    pub fn ensure_writable(&self) -> Result<()> {
+        if let Some(version) = self.required_version() {
+            bail!(
+                "This repository was last written by dotstate {version}, newer than this one ({}). \
+                 Run 'dotstate upgrade' before changing it on this machine.",
+                current_version()
+            );
+        }
+        Ok(())
+    }
+
+    /// Save the manifest to the repository, recording this dotstate version.
     /// Uses atomic write (temp file + rename) to prevent corruption on crash.
+    /// Refused when a newer dotstate last saved it.
     pub fn save(&self, repo_path: &Path) -> Result<()> {
+        self.ensure_writable()?;
         let manifest_path = Self::manifest_path(repo_path);
         let temp_path = manifest_path.with_extension("toml.tmp");
 
-        let content =
-            toml::to_string_pretty(self).with_context(|| "Failed to serialize profile manifest")?;
+        let manifest = Self {
+            written_by: Some(current_version().to_string()),
+            ..self.clone()
+        };
+        let content = toml::to_string_pretty(&manifest)
+            .with_context(|| "Failed to serialize profile manifest")?;
 
         // Write to temp file first
         std::fs::write(&temp_path, &content)
@@ -351,7 +444,7 @@ impl ProfileManifest {
         if let Some(profile) = self.profiles.iter_mut().find(|p| p.name == profile_name) {
             // Sort alphabetically to ensure consistent ordering and prevent unnecessary diffs
             let mut sorted_files = synced_files;
-            sorted_files.sort();
+            normalize_files(&mut sorted_files);
             profile.synced_files = sorted_files;
             Ok(())
         } else {
@@ -414,7 +507,7 @@ impl ProfileManifest {
 
     /// Add a file to the common section
     pub fn add_common_file(&mut self, relative_path: &str) {
-        let path = relative_path.to_string();
+        let path = nfc(relative_path).into_owned();
         if !self.common.synced_files.contains(&path) {
             self.common.synced_files.push(path);
             self.common.synced_files.sort();
@@ -424,7 +517,9 @@ impl ProfileManifest {
     /// Remove a file from the common section
     pub fn remove_common_file(&mut self, relative_path: &str) -> bool {
         let initial_len = self.common.synced_files.len();
-        self.common.synced_files.retain(|f| f != relative_path);
+        self.common
+            .synced_files
+            .retain(|f| !crate::utils::unicode_path::same(f, relative_path));
         self.common.synced_files.len() < initial_len
     }
 
@@ -439,7 +534,8 @@ impl ProfileManifest {
     pub fn is_common_file(&self, relative_path: &str) -> bool {
         self.common
             .synced_files
-            .contains(&relative_path.to_string())
+            .iter()
+            .any(|f| *f == nfc(relative_path))
     }
 
     // ==================== Migration Methods ====================
@@ -745,6 +841,20 @@ mod tests {
         assert!(!loaded.has_profile("Work"));
     }
 
+    #[test]
+    fn test_plugin_package_manager() {
+        let package: Package = toml::from_str(
+            "name = \"node\"\nmanager = { plugin = \"mise\" }\nbinary_name = \"node\"\n",
+        )
+        .unwrap();
+        assert_eq!(package.manager, PackageManager::Plugin("mise".to_string()));
+        assert_eq!(format!("{:?}", package.manager), "plugin:mise");
+        assert_eq!(format!("{:?}", PackageManager::Brew), "Brew");
+
+        let written: Package = toml::from_str(&toml::to_string(&package).unwrap()).unwrap();
+        assert_eq!(written.manager, package.manager);
+    }
+
     #[test]
     fn test_reserved_names() {
         assert!(ProfileManifest::is_reserved_name("common"));
@@ -882,6 +992,30 @@ synced_files = []
         assert_eq!(manifest.version, CURRENT_VERSION);
     }
 
+    #[test]
+    fn test_newer_repo_is_not_written() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        ProfileManifest::default().save(repo_path).unwrap();
+        let loaded = ProfileManifest::load(repo_path).unwrap();
+        assert_eq!(loaded.written_by.as_deref(), Some(current_version()));
+        assert!(loaded.required_version().is_none());
+
+        // A newer version wrote the repository since
+        let content = std::fs::read_to_string(ProfileManifest::manifest_path(repo_path)).unwrap();
+        let content = content.replace(current_version(), "999.0.0");
+        std::fs::write(ProfileManifest::manifest_path(repo_path), &content).unwrap();
+        let mut loaded = ProfileManifest::load(repo_path).unwrap();
+        assert_eq!(loaded.required_version(), Some("999.0.0"));
+        loaded.add_profile("laptop".to_string(), None);
+        assert!(loaded.save(repo_path).is_err());
+        assert_eq!(
+            std::fs::read_to_string(ProfileManifest::manifest_path(repo_path)).unwrap(),
+            content
+        );
+    }
+
     #[test]
     fn test_manifest_migration_v1_to_v2() {
         let temp_dir = TempDir::new().unwrap();
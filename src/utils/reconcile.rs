@@ -0,0 +1,216 @@
+//! Reconciling a repository with a remote it has diverged from.
+//!
+//! Every file that differs between the working tree and the remote branch
+//! gets a [`Choice`]: keep the local version, keep the remote one, or merge
+//! the two against the version both started from. [`apply`] writes the
+//! chosen contents to the working tree, which is then committed as a merge
+//! of both sides (see [`crate::git::GitManager::commit_merge`]) so nothing
+//! is force pushed and no history is lost.
+
+use crate::git::DivergedFile;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// What to keep of a diverged file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    KeepLocal,
+    KeepRemote,
+    /// Three-way merge; conflicting lines are kept with conflict markers
+    Merge,
+}
+
+impl Choice {
+    /// Display label
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Choice::KeepLocal => "Keep local",
+            Choice::KeepRemote => "Keep remote",
+            Choice::Merge => "Merge",
+        }
+    }
+}
+
+/// Which sides changed a file since the version both started from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Changed {
+    Local,
+    Remote,
+    Both,
+}
+
+impl Changed {
+    /// Display label
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Changed::Local => "Changed here",
+            Changed::Remote => "Changed on the remote",
+            Changed::Both => "Changed on both sides",
+        }
+    }
+}
+
+/// Result of merging both versions of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merged {
+    pub content: Vec<u8>,
+    /// Whether some lines conflicted and were kept with conflict markers
+    pub conflicts: bool,
+}
+
+/// Which sides changed `file`. Without a common version both did.
+#[must_use]
+pub fn changed(file: &DivergedFile) -> Changed {
+    let Some(base) = &file.base else {
+        return Changed::Both;
+    };
+    if file.local.as_ref() == Some(base) {
+        Changed::Remote
+    } else if file.remote.as_ref() == Some(base) {
+        Changed::Local
+    } else {
+        Changed::Both
+    }
+}
+
+fn is_binary(content: &[u8]) -> bool {
+    content.contains(&0)
+}
+
+/// Merge the local and remote versions of `file`. `None` when the file
+/// can't be merged: it was deleted on one side or isn't text.
+pub fn merge(file: &DivergedFile) -> Result<Option<Merged>> {
+    let (Some(local), Some(remote)) = (&file.local, &file.remote) else {
+        return Ok(None);
+    };
+    let base = file.base.as_deref().unwrap_or_default();
+    if file.binary || is_binary(local) || is_binary(remote) || is_binary(base) {
+        return Ok(None);
+    }
+
+    let path = Path::new(&file.path);
+    let input = |content| {
+        let mut input = git2::MergeFileInput::new();
+        input.content(content).path(path);
+        input
+    };
+    let mut opts = git2::MergeFileOptions::new();
+    opts.our_label("local").their_label("remote");
+    let result = git2::merge_file(&input(base), &input(local), &input(remote), Some(&mut opts))
+        .with_context(|| format!("Failed to merge {}", file.path))?;
+    Ok(Some(Merged {
+        content: result.content().to_vec(),
+        conflicts: !result.is_automergeable(),
+    }))
+}
+
+/// The choice to start from: the side that changed, a clean merge when
+/// both did, otherwise the local version.
+#[must_use]
+pub fn default_choice(file: &DivergedFile, merged: Option<&Merged>) -> Choice {
+    match changed(file) {
+        Changed::Local => Choice::KeepLocal,
+        Changed::Remote => Choice::KeepRemote,
+        Changed::Both if merged.is_some_and(|m| !m.conflicts) => Choice::Merge,
+        Changed::Both => Choice::KeepLocal,
+    }
+}
+
+/// Content `choice` leaves for `file`, `None` to delete it
+#[must_use]
+pub fn resolve<'a>(
+    file: &'a DivergedFile,
+    choice: Choice,
+    merged: Option<&'a Merged>,
+) -> Option<&'a [u8]> {
+    match choice {
+        Choice::KeepLocal => file.local.as_deref(),
+        Choice::KeepRemote => file.remote.as_deref(),
+        Choice::Merge => merged
+            .map(|m| m.content.as_slice())
+            .or(file.local.as_deref()),
+    }
+}
+
+/// Unified diff from `old` to `new`, empty when either isn't text
+#[must_use]
+pub fn unified_diff(path: &str, old: Option<&[u8]>, new: Option<&[u8]>) -> String {
+    let (old, new) = (old.unwrap_or_default(), new.unwrap_or_default());
+    if is_binary(old) || is_binary(new) {
+        return String::new();
+    }
+    let path = Path::new(path);
+    git2::Patch::from_buffers(old, Some(path), new, Some(path), None)
+        .and_then(|mut patch| patch.to_buf())
+        .map(|buf| String::from_utf8_lossy(&buf).into_owned())
+        .unwrap_or_default()
+}
+
+/// Write the resolved content of each file to the working tree at
+/// `repo_path`, deleting files resolved to nothing
+pub fn apply(repo_path: &Path, resolved: &[(String, Option<Vec<u8>>)]) -> Result<()> {
+    for (path, content) in resolved {
+        let full = repo_path.join(path);
+        match content {
+            Some(content) => {
+                if let Some(parent) = full.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create {}", parent.display()))?;
+                }
+                fs::write(&full, content)
+                    .with_context(|| format!("Failed to write {}", full.display()))?;
+            }
+            None if full.symlink_metadata().is_ok() => {
+                fs::remove_file(&full)
+                    .with_context(|| format!("Failed to remove {}", full.display()))?;
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(base: &str, local: &str, remote: &str) -> DivergedFile {
+        DivergedFile {
+            path: "common/.zshrc".to_string(),
+            base: Some(base.as_bytes().to_vec()),
+            local: Some(local.as_bytes().to_vec()),
+            remote: Some(remote.as_bytes().to_vec()),
+            binary: false,
+        }
+    }
+
+    #[test]
+    fn test_default_choice_follows_changes() {
+        let only_remote = file("a\n", "a\n", "b\n");
+        assert_eq!(changed(&only_remote), Changed::Remote);
+        assert_eq!(default_choice(&only_remote, None), Choice::KeepRemote);
+
+        // Different lines on each side merge cleanly
+        let both = file("a\nb\nc\n", "A\nb\nc\n", "a\nb\nC\n");
+        let merged = merge(&both).unwrap().unwrap();
+        assert!(!merged.conflicts);
+        assert_eq!(merged.content, b"A\nb\nC\n");
+        assert_eq!(default_choice(&both, Some(&merged)), Choice::Merge);
+
+        // The same line changed on both sides keeps the local version
+        let clash = file("a\n", "b\n", "c\n");
+        let merged = merge(&clash).unwrap().unwrap();
+        assert!(merged.conflicts);
+        assert_eq!(default_choice(&clash, Some(&merged)), Choice::KeepLocal);
+
+        let deleted = DivergedFile {
+            local: None,
+            ..file("a\n", "a\n", "b\n")
+        };
+        assert!(merge(&deleted).unwrap().is_none());
+        assert_eq!(resolve(&deleted, Choice::KeepLocal, None), None);
+    }
+}
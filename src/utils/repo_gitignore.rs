@@ -0,0 +1,247 @@
+//! The storage repository's `.gitignore`.
+//!
+//! Symlinked directories put whatever tools write into them straight into
+//! the repository: plugin checkouts under `nvim/lazy`, compiled caches, OS
+//! metadata files. The default `.gitignore` written on setup (or with
+//! `dotstate gitignore`) keeps the known offenders out, and the Sync screen
+//! offers to add patterns for junk it spots among new files.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Name of the ignore file at the repository root
+pub const GITIGNORE: &str = ".gitignore";
+
+/// Default patterns, by section
+const DEFAULT_SECTIONS: &[(&str, &[&str])] = &[
+    (
+        "OS files",
+        &[".DS_Store", "._*", "Thumbs.db", "desktop.ini"],
+    ),
+    (
+        "Backup and editor artifacts",
+        &["*.bak", "*.swp", "*.swo", "*~", "*.orig", "*.rej", "*.tmp"],
+    ),
+    (
+        "Caches and plugin checkouts",
+        &[
+            "**/nvim/lazy/",
+            "**/nvim/plugged/",
+            "**/nvim/site/pack/",
+            "**/tmux/plugins/",
+            "**/.zcompdump*",
+            "**/__pycache__/",
+            "**/node_modules/",
+            "**/.cache/",
+        ],
+    ),
+];
+
+/// Directories whose contents are caches, with the pattern ignoring them
+const JUNK_DIRS: &[(&str, &str)] = &[
+    ("nvim/lazy/", "**/nvim/lazy/"),
+    ("nvim/plugged/", "**/nvim/plugged/"),
+    ("nvim/site/pack/", "**/nvim/site/pack/"),
+    ("tmux/plugins/", "**/tmux/plugins/"),
+    ("__pycache__/", "**/__pycache__/"),
+    ("node_modules/", "**/node_modules/"),
+    (".cache/", "**/.cache/"),
+];
+
+/// Contents of the default `.gitignore`
+#[must_use]
+pub fn default_content() -> String {
+    DEFAULT_SECTIONS
+        .iter()
+        .map(|(title, patterns)| format!("# {title}\n{}\n", patterns.join("\n")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Patterns of the repository's `.gitignore`, comments and blank lines left out
+fn existing_patterns(repo_path: &Path) -> BTreeSet<String> {
+    fs::read_to_string(repo_path.join(GITIGNORE))
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn default_patterns() -> Vec<String> {
+    DEFAULT_SECTIONS
+        .iter()
+        .flat_map(|(_, patterns)| patterns.iter().map(|p| (*p).to_string()))
+        .collect()
+}
+
+/// Write the default `.gitignore`, or add the default patterns it lacks to
+/// an existing one. Returns the patterns that were added.
+pub fn ensure_defaults(repo_path: &Path) -> Result<Vec<String>> {
+    let path = repo_path.join(GITIGNORE);
+    if path.exists() {
+        return append(repo_path, &default_patterns());
+    }
+    fs::write(&path, default_content())
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    Ok(default_patterns())
+}
+
+/// Append `patterns` the `.gitignore` doesn't have yet under a dotstate
+/// comment. Returns the patterns that were added.
+pub fn append(repo_path: &Path, patterns: &[String]) -> Result<Vec<String>> {
+    let existing = existing_patterns(repo_path);
+    let mut added: Vec<String> = Vec::new();
+    for pattern in patterns {
+        if !existing.contains(pattern) && !added.contains(pattern) {
+            added.push(pattern.clone());
+        }
+    }
+    if added.is_empty() {
+        return Ok(added);
+    }
+
+    let path = repo_path.join(GITIGNORE);
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str("# Added by dotstate\n");
+    for pattern in &added {
+        content.push_str(pattern);
+        content.push('\n');
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to update {}", path.display()))?;
+    Ok(added)
+}
+
+/// Remove `pattern` from the `.gitignore`. Returns whether it was there.
+pub fn remove(repo_path: &Path, pattern: &str) -> Result<bool> {
+    let path = repo_path.join(GITIGNORE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(false);
+    };
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|line| line.trim() != pattern)
+        .collect();
+    if kept.len() == content.lines().count() {
+        return Ok(false);
+    }
+    let mut content = kept.join("\n");
+    content.push('\n');
+    fs::write(&path, content).with_context(|| format!("Failed to update {}", path.display()))?;
+    Ok(true)
+}
+
+/// The ignore pattern for a repository path that is obviously junk (OS
+/// metadata, editor swap files, cache directories), if it is.
+#[must_use]
+pub fn junk_pattern(path: &str) -> Option<&'static str> {
+    let path = path.trim_end_matches('/');
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name {
+        ".DS_Store" => return Some(".DS_Store"),
+        "Thumbs.db" => return Some("Thumbs.db"),
+        "desktop.ini" => return Some("desktop.ini"),
+        _ => {}
+    }
+    if name.starts_with("._") {
+        return Some("._*");
+    }
+    if name.starts_with(".zcompdump") {
+        return Some("**/.zcompdump*");
+    }
+    for ext in ["swp", "swo", "bak", "orig", "rej"] {
+        if name.len() > ext.len() + 1 && name.ends_with(&format!(".{ext}")) {
+            return Some(match ext {
+                "swp" => "*.swp",
+                "swo" => "*.swo",
+                "bak" => "*.bak",
+                "orig" => "*.orig",
+                _ => "*.rej",
+            });
+        }
+    }
+    if name.ends_with('~') {
+        return Some("*~");
+    }
+    let with_slash = format!("/{path}/");
+    JUNK_DIRS
+        .iter()
+        .find(|(dir, _)| with_slash.contains(&format!("/{dir}")))
+        .map(|(_, pattern)| *pattern)
+}
+
+/// Ignore patterns for the junk among `paths`, without duplicates
+#[must_use]
+pub fn junk_patterns<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut patterns: Vec<String> = Vec::new();
+    for pattern in paths.into_iter().filter_map(junk_pattern) {
+        if !patterns.iter().any(|p| p == pattern) {
+            patterns.push(pattern.to_string());
+        }
+    }
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_junk_patterns() {
+        assert_eq!(junk_pattern("Personal/.DS_Store"), Some(".DS_Store"));
+        assert_eq!(
+            junk_pattern("Personal/.config/nvim/lazy/plenary.nvim/README.md"),
+            Some("**/nvim/lazy/")
+        );
+        assert_eq!(junk_pattern("common/.vimrc.swp"), Some("*.swp"));
+        assert_eq!(
+            junk_pattern("Work/.zcompdump-host-5.9"),
+            Some("**/.zcompdump*")
+        );
+        assert_eq!(junk_pattern("Personal/.config/nvim/init.lua"), None);
+        assert_eq!(junk_pattern("Personal/.swp"), None);
+        assert_eq!(
+            junk_patterns(["a/.DS_Store", "b/.DS_Store", "a/x.bak", "a/.zshrc"]),
+            [".DS_Store", "*.bak"]
+        );
+    }
+
+    #[test]
+    fn test_ensure_defaults_and_append() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+
+        let added = ensure_defaults(repo).unwrap();
+        assert!(added.contains(&"**/nvim/lazy/".to_string()));
+        assert_eq!(
+            fs::read_to_string(repo.join(GITIGNORE)).unwrap(),
+            default_content()
+        );
+        assert!(ensure_defaults(repo).unwrap().is_empty());
+
+        fs::write(repo.join(GITIGNORE), "secrets/\n.DS_Store").unwrap();
+        let added = append(repo, &[".DS_Store".to_string(), "*.log".to_string()]).unwrap();
+        assert_eq!(added, ["*.log"]);
+        assert_eq!(
+            fs::read_to_string(repo.join(GITIGNORE)).unwrap(),
+            "secrets/\n.DS_Store\n\n# Added by dotstate\n*.log\n"
+        );
+
+        assert!(remove(repo, "*.log").unwrap());
+        assert!(!remove(repo, "*.log").unwrap());
+        assert_eq!(
+            fs::read_to_string(repo.join(GITIGNORE)).unwrap(),
+            "secrets/\n.DS_Store\n\n# Added by dotstate\n"
+        );
+    }
+}
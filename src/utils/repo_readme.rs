@@ -0,0 +1,207 @@
+//! Generated `README.md` for the storage repository.
+//!
+//! With `generate_readme` on, every sync rewrites the repository's README
+//! from the profile manifest: the profiles with their files, packages and
+//! bootstrap scripts, and how to set the dotfiles up on a new machine. The
+//! repository's GitHub page then always shows what's in it. A README the
+//! user wrote (one without the generated marker) is never touched.
+
+use crate::config::Config;
+use crate::utils::bootstrap;
+use crate::utils::ProfileManifest;
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Name of the README at the repository root
+pub const README: &str = "README.md";
+
+/// First line of a generated README
+const MARKER: &str = "<!-- Generated by dotstate. Edits are overwritten on sync; turn off \"Repository README\" in Settings to keep your own. -->";
+
+/// Whether `content` is a README dotstate generated
+#[must_use]
+pub fn is_generated(content: &str) -> bool {
+    content.lines().next() == Some(MARKER)
+}
+
+/// README contents for the repository at `repo_path`. `clone_url` is shown
+/// in the setup instructions when known.
+#[must_use]
+pub fn generate(manifest: &ProfileManifest, repo_path: &Path, clone_url: Option<&str>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{MARKER}\n");
+    let _ = writeln!(out, "# Dotfiles\n");
+    let _ = writeln!(
+        out,
+        "Managed with [dotstate](https://dotstate.serkan.dev). Each profile is a \
+         directory of dotfiles that dotstate symlinks into the home directory.\n"
+    );
+
+    let _ = writeln!(out, "## Set up on a new machine\n");
+    let _ = writeln!(out, "```sh");
+    let _ = writeln!(
+        out,
+        "curl -fsSL https://dotstate.serkan.dev/install.sh | bash"
+    );
+    let _ = writeln!(
+        out,
+        "dotstate  # choose \"Restore from GitHub\" and pick this repository"
+    );
+    if let Some(url) = clone_url {
+        let _ = writeln!(out, "# or: git clone {url} and point dotstate at the clone");
+    }
+    let _ = writeln!(out, "dotstate profile switch <profile>");
+    let _ = writeln!(out, "dotstate activate");
+    let _ = writeln!(out, "dotstate bootstrap  # run the profile's setup scripts");
+    let _ = writeln!(out, "```\n");
+
+    let _ = writeln!(out, "## Profiles\n");
+    if manifest.profiles.is_empty() {
+        let _ = writeln!(out, "No profiles yet.\n");
+    }
+    for profile in &manifest.profiles {
+        let _ = writeln!(out, "### {}\n", profile.name);
+        if let Some(description) = profile.description.as_deref().filter(|d| !d.is_empty()) {
+            let _ = writeln!(out, "{description}\n");
+        }
+        if let Some(parent) = &profile.inherits {
+            let _ = writeln!(out, "Inherits from **{parent}**.\n");
+        }
+        write_files(&mut out, &profile.synced_files);
+        if !profile.packages.is_empty() {
+            let names: Vec<&str> = profile.packages.iter().map(|p| p.name.as_str()).collect();
+            let _ = writeln!(out, "Packages: {}\n", names.join(", "));
+        }
+        let scripts = bootstrap::profile_scripts(repo_path, &profile.name);
+        if !scripts.is_empty() {
+            let names: Vec<String> = scripts
+                .iter()
+                .map(|s| format!("`{}`", s.display_name(repo_path)))
+                .collect();
+            let _ = writeln!(out, "Bootstrap scripts: {}\n", names.join(", "));
+        }
+    }
+
+    if !manifest.common.synced_files.is_empty() {
+        let _ = writeln!(out, "## Common files\n");
+        let _ = writeln!(out, "Shared by every profile.\n");
+        write_files(&mut out, &manifest.common.synced_files);
+    }
+
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+    out
+}
+
+fn write_files(out: &mut String, files: &[String]) {
+    if files.is_empty() {
+        let _ = writeln!(out, "No files.\n");
+        return;
+    }
+    for file in files {
+        let _ = writeln!(out, "- `~/{file}`");
+    }
+    out.push('\n');
+}
+
+/// Clone URL of the configured GitHub repository, without credentials
+fn clone_url(config: &Config) -> Option<String> {
+    config
+        .github
+        .as_ref()
+        .map(|gh| format!("https://github.com/{}/{}.git", gh.owner, gh.repo))
+}
+
+/// Regenerate the repository's README if `generate_readme` is on. Leaves a
+/// README without the generated marker alone. Returns whether the file was
+/// written.
+pub fn update(config: &Config) -> Result<bool> {
+    if !config.generate_readme {
+        return Ok(false);
+    }
+    let path = config.repo_path.join(README);
+    let existing = fs::read_to_string(&path).ok();
+    if existing.as_deref().is_some_and(|c| !is_generated(c)) {
+        tracing::info!(
+            "Not regenerating {}: it wasn't generated by dotstate",
+            README
+        );
+        return Ok(false);
+    }
+
+    let manifest = ProfileManifest::load_or_backfill(&config.repo_path)
+        .context("Failed to load profile manifest")?;
+    let content = generate(&manifest, &config.repo_path, clone_url(config).as_deref());
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::profile_manifest::{CommonSection, ProfileInfo};
+    use tempfile::TempDir;
+
+    fn manifest() -> ProfileManifest {
+        ProfileManifest {
+            common: CommonSection {
+                synced_files: vec![".gitconfig".to_string()],
+            },
+            profiles: vec![ProfileInfo {
+                name: "Work".to_string(),
+                description: Some("Work laptop".to_string()),
+                inherits: Some("Personal".to_string()),
+                synced_files: vec![".zshrc".to_string()],
+                packages: Vec::new(),
+            }],
+            ..ProfileManifest::default()
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("Work")).unwrap();
+        fs::write(temp.path().join("Work/install.sh"), "#!/bin/sh\n").unwrap();
+
+        let readme = generate(
+            &manifest(),
+            temp.path(),
+            Some("https://github.com/u/dotfiles.git"),
+        );
+        assert!(is_generated(&readme));
+        assert!(readme.contains("### Work\n\nWork laptop\n\nInherits from **Personal**."));
+        assert!(readme.contains("- `~/.zshrc`"));
+        assert!(readme.contains("Bootstrap scripts: `Work/install.sh`"));
+        assert!(readme.contains("## Common files"));
+        assert!(readme.contains("git clone https://github.com/u/dotfiles.git"));
+        assert!(!readme.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn test_update_keeps_user_readme() {
+        let temp = TempDir::new().unwrap();
+        let config = Config {
+            repo_path: temp.path().to_path_buf(),
+            generate_readme: true,
+            ..Config::default()
+        };
+        manifest().save(temp.path()).unwrap();
+
+        assert!(update(&config).unwrap());
+        assert!(!update(&config).unwrap());
+
+        fs::write(temp.path().join(README), "# My dotfiles\n").unwrap();
+        assert!(!update(&config).unwrap());
+        assert_eq!(
+            fs::read_to_string(temp.path().join(README)).unwrap(),
+            "# My dotfiles\n"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! GitHub Actions workflow for the storage repository.
+//!
+//! `dotstate workflow` writes `.github/workflows/dotstate.yml`, which runs on
+//! every push and pull request: it scans the history for leaked secrets
+//! with `TruffleHog`, then runs `dotstate ci` to validate the manifest, the
+//! repository layout and a dry-run activation of every profile. Rewriting
+//! a generated workflow updates it (e.g. to a newer dotstate version); one
+//! the user wrote is only replaced when asked to.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Path of the workflow in the repository
+pub const WORKFLOW_PATH: &str = ".github/workflows/dotstate.yml";
+
+/// First line of a generated workflow
+const MARKER: &str = "# Generated by `dotstate workflow`; run it again to update.";
+
+/// What writing the workflow did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+/// Whether `content` is a workflow dotstate generated
+#[must_use]
+pub fn is_generated(content: &str) -> bool {
+    content.lines().next() == Some(MARKER)
+}
+
+/// Workflow contents, installing dotstate `version`
+#[must_use]
+pub fn generate(version: &str) -> String {
+    format!(
+        r"{MARKER}
+name: dotstate
+
+on:
+  push:
+  pull_request:
+
+permissions:
+  contents: read
+
+jobs:
+  check:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+
+      - name: Scan for secrets
+        uses: trufflesecurity/trufflehog@main
+        with:
+          extra_args: --results=verified,unknown
+
+      - name: Cache dotstate
+        uses: actions/cache@v4
+        with:
+          path: ~/.cargo/bin/dotstate
+          key: dotstate-${{{{ runner.os }}}}-{version}
+
+      - name: Install dotstate
+        run: command -v dotstate || cargo install dotstate --locked --version {version}
+
+      - name: Validate the manifest and activate every profile
+        run: dotstate ci
+"
+    )
+}
+
+/// Where the workflow is written in the repository at `repo_path`
+#[must_use]
+pub fn path(repo_path: &Path) -> PathBuf {
+    repo_path.join(WORKFLOW_PATH)
+}
+
+/// Write the workflow into the repository at `repo_path`. A workflow that
+/// wasn't generated by dotstate is only replaced with `force`.
+pub fn write(repo_path: &Path, version: &str, force: bool) -> Result<WriteOutcome> {
+    let path = path(repo_path);
+    let content = generate(version);
+    let outcome = match fs::read_to_string(&path) {
+        Ok(existing) if existing == content => return Ok(WriteOutcome::Unchanged),
+        Ok(existing) if !is_generated(&existing) && !force => {
+            bail!(
+                "{} exists and wasn't generated by dotstate (use --force to replace it)",
+                path.display()
+            )
+        }
+        Ok(_) => WriteOutcome::Updated,
+        Err(_) => WriteOutcome::Created,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_and_update_workflow() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+
+        assert_eq!(write(repo, "0.4.0", false).unwrap(), WriteOutcome::Created);
+        let content = fs::read_to_string(path(repo)).unwrap();
+        assert!(is_generated(&content));
+        assert!(content.contains("key: dotstate-${{ runner.os }}-0.4.0"));
+        assert!(content.contains("run: dotstate ci"));
+        assert_eq!(
+            write(repo, "0.4.0", false).unwrap(),
+            WriteOutcome::Unchanged
+        );
+        assert_eq!(write(repo, "0.5.0", false).unwrap(), WriteOutcome::Updated);
+
+        // A workflow the user wrote is kept unless forced
+        fs::write(path(repo), "name: mine\n").unwrap();
+        assert!(write(repo, "0.5.0", false).is_err());
+        assert_eq!(fs::read_to_string(path(repo)).unwrap(), "name: mine\n");
+        assert_eq!(write(repo, "0.5.0", true).unwrap(), WriteOutcome::Updated);
+    }
+}
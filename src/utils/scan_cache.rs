@@ -0,0 +1,293 @@
+//! Metadata cache for the dotfile discovery scan.
+//!
+//! The scan checks a long list of candidate paths under the home directory.
+//! Instead of stat-ing every candidate each time Manage Files opens, we stat
+//! each distinct parent directory once and only re-check the candidates in
+//! directories whose modification time changed since the last scan (adding,
+//! removing or renaming an entry always bumps the directory's mtime), or
+//! whose list of candidates changed.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::{debug, info, warn};
+
+/// Current version of the `dotfile_scan.json` file format.
+/// Increment this when making breaking changes to the schema.
+const CURRENT_VERSION: u32 = 1;
+
+/// What we saw in one directory the last time it was scanned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirSnapshot {
+    /// Directory mtime in nanoseconds since the epoch (`None` if it didn't exist)
+    pub mtime_ns: Option<u64>,
+    /// Candidates (relative to home) that existed in this directory
+    pub present: Vec<String>,
+    /// Candidates checked in this directory, sorted. A different list (a
+    /// candidate added by an upgrade) means the snapshot can't be reused.
+    #[serde(default)]
+    pub checked: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanCacheData {
+    #[serde(default)]
+    pub version: u32,
+    /// Home directory the snapshots were taken from
+    #[serde(default)]
+    pub home: PathBuf,
+    /// Key: parent directory relative to home ("" for home itself)
+    #[serde(default)]
+    pub dirs: HashMap<String, DirSnapshot>,
+}
+
+impl Default for ScanCacheData {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            home: PathBuf::new(),
+            dirs: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScanCache {
+    cache_file: PathBuf,
+    data: ScanCacheData,
+}
+
+impl ScanCache {
+    /// Load the cache from the cache directory. A missing or unreadable
+    /// cache just means the next scan checks everything.
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from(crate::utils::get_cache_dir().join("dotfile_scan.json"))
+    }
+
+    fn load_from(cache_file: PathBuf) -> Self {
+        let data = match std::fs::read_to_string(&cache_file) {
+            Ok(content) => match serde_json::from_str::<ScanCacheData>(&content) {
+                // Unknown versions are rebuilt rather than migrated: it's only a cache
+                Ok(data) if data.version == CURRENT_VERSION => data,
+                Ok(data) => {
+                    info!(
+                        "Discarding dotfile scan cache v{} (current v{})",
+                        data.version, CURRENT_VERSION
+                    );
+                    ScanCacheData::default()
+                }
+                Err(e) => {
+                    warn!("Failed to parse dotfile scan cache: {}", e);
+                    ScanCacheData::default()
+                }
+            },
+            Err(_) => ScanCacheData::default(),
+        };
+
+        Self { cache_file, data }
+    }
+
+    /// Return the candidates that exist under `home`, in input order.
+    ///
+    /// Each candidate's parent directory is stat-ed once; candidates are only
+    /// re-checked when that directory's mtime differs from the cached one or
+    /// a different set of candidates is asked about.
+    pub fn existing(&mut self, home: &Path, candidates: &[String]) -> Vec<String> {
+        if self.data.home != home {
+            self.data = ScanCacheData {
+                home: home.to_path_buf(),
+                ..ScanCacheData::default()
+            };
+        }
+
+        // Group candidates by parent directory, keeping input order within each
+        let mut by_dir: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+        for candidate in candidates {
+            by_dir
+                .entry(parent_key(candidate))
+                .or_default()
+                .push(candidate);
+        }
+
+        let mut rescanned = 0;
+        let mut present: Vec<&String> = Vec::new();
+        for (dir, names) in by_dir {
+            let mtime_ns = dir_mtime_ns(&home.join(&dir));
+            let mut checked: Vec<String> = names.iter().map(|name| (*name).clone()).collect();
+            checked.sort();
+            let cached = self.data.dirs.get(&dir).filter(|snapshot| {
+                mtime_ns.is_some() && snapshot.mtime_ns == mtime_ns && snapshot.checked == checked
+            });
+
+            let snapshot = if let Some(snapshot) = cached {
+                snapshot.clone()
+            } else {
+                rescanned += 1;
+                let found = if mtime_ns.is_some() {
+                    names
+                        .iter()
+                        .filter(|name| home.join(name.as_str()).exists())
+                        .map(|name| (*name).clone())
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                let snapshot = DirSnapshot {
+                    mtime_ns,
+                    present: found,
+                    checked,
+                };
+                self.data.dirs.insert(dir, snapshot.clone());
+                snapshot
+            };
+
+            present.extend(names.into_iter().filter(|n| snapshot.present.contains(n)));
+        }
+
+        debug!(
+            "Dotfile scan: {} directories re-scanned, {} candidates found",
+            rescanned,
+            present.len()
+        );
+
+        // Restore the caller's ordering
+        candidates
+            .iter()
+            .filter(|c| present.contains(c))
+            .cloned()
+            .collect()
+    }
+
+    /// Save the cache to disk.
+    /// Uses atomic write (temp file + rename) to prevent corruption on crash.
+    pub fn save(&self) -> Result<()> {
+        let temp_path = self.cache_file.with_extension("json.tmp");
+
+        if let Some(parent) = self.cache_file.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+
+        let json =
+            serde_json::to_string(&self.data).context("Failed to serialize dotfile scan cache")?;
+        std::fs::write(&temp_path, &json).context("Failed to write temp dotfile scan cache")?;
+        std::fs::rename(&temp_path, &self.cache_file)
+            .context("Failed to rename temp dotfile scan cache")?;
+
+        debug!("Dotfile scan cache saved to {:?}", self.cache_file);
+        Ok(())
+    }
+}
+
+/// Parent directory of a candidate, relative to home ("" for home itself).
+fn parent_key(candidate: &str) -> String {
+    Path::new(candidate)
+        .parent()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default()
+}
+
+fn dir_mtime_ns(dir: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(dir).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    u64::try_from(nanos).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn candidates() -> Vec<String> {
+        vec![
+            ".zshrc".to_string(),
+            ".config/nvim".to_string(),
+            ".bashrc".to_string(),
+            ".config/kitty".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_existing_preserves_order() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        std::fs::create_dir_all(home.join(".config/kitty")).unwrap();
+        std::fs::write(home.join(".zshrc"), "").unwrap();
+        std::fs::write(home.join(".bashrc"), "").unwrap();
+
+        let mut cache = ScanCache::load_from(temp.path().join("scan.json"));
+        assert_eq!(
+            cache.existing(&home, &candidates()),
+            vec![".zshrc", ".bashrc", ".config/kitty"]
+        );
+    }
+
+    #[test]
+    fn test_unchanged_directory_uses_cache() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(home.join(".zshrc"), "").unwrap();
+
+        let cache_file = temp.path().join("scan.json");
+        let mut cache = ScanCache::load_from(cache_file.clone());
+        assert_eq!(cache.existing(&home, &candidates()), vec![".zshrc"]);
+        cache.save().unwrap();
+
+        // Pretend .bashrc exists without touching the filesystem: an
+        // unchanged directory mtime must mean no re-check.
+        let mut cache = ScanCache::load_from(cache_file);
+        cache
+            .data
+            .dirs
+            .get_mut("")
+            .unwrap()
+            .present
+            .push(".bashrc".to_string());
+        assert_eq!(
+            cache.existing(&home, &candidates()),
+            vec![".zshrc", ".bashrc"]
+        );
+    }
+
+    #[test]
+    fn test_changed_directory_is_rescanned() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        std::fs::create_dir_all(&home).unwrap();
+
+        let mut cache = ScanCache::load_from(temp.path().join("scan.json"));
+        assert!(cache.existing(&home, &candidates()).is_empty());
+
+        // A directory that didn't exist before is always re-checked
+        std::fs::create_dir_all(home.join(".config/nvim")).unwrap();
+        assert_eq!(cache.existing(&home, &candidates()), vec![".config/nvim"]);
+
+        // Force a stale mtime for home so the new file is noticed
+        cache.data.dirs.get_mut("").unwrap().mtime_ns = Some(0);
+        std::fs::write(home.join(".zshrc"), "").unwrap();
+        assert_eq!(
+            cache.existing(&home, &candidates()),
+            vec![".zshrc", ".config/nvim"]
+        );
+    }
+
+    #[test]
+    fn test_new_candidate_is_checked() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(home.join(".zshrc"), "").unwrap();
+        std::fs::write(home.join(".vimrc"), "").unwrap();
+
+        let mut cache = ScanCache::load_from(temp.path().join("scan.json"));
+        assert_eq!(cache.existing(&home, &candidates()), vec![".zshrc"]);
+
+        // A candidate added later is found although home's mtime is the same
+        let mut more = candidates();
+        more.push(".vimrc".to_string());
+        assert_eq!(cache.existing(&home, &more), vec![".zshrc", ".vimrc"]);
+    }
+}
@@ -0,0 +1,76 @@
+//! Private scratch directories.
+//!
+//! Plaintext that must not leak to other users of the machine (bundles of
+//! the repository, staged dotfiles) goes into a fresh directory under the
+//! temp directory. The directory is created in one step, readable only by
+//! the user, with a name that can't be guessed; one that already exists is
+//! never reused, so another user can't prepare it beforehand.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A private directory under the temp directory, removed on drop
+#[derive(Debug)]
+pub struct ScratchDir(TempDir);
+
+impl ScratchDir {
+    /// Create `<temp dir>/<prefix>-<random>`, readable only by the user.
+    pub fn new(prefix: &str) -> Result<Self> {
+        Self::new_in(&std::env::temp_dir(), prefix)
+    }
+
+    /// Create `<parent>/<prefix>-<random>`, readable only by the user.
+    pub fn new_in(parent: &Path, prefix: &str) -> Result<Self> {
+        let prefix = format!("{prefix}-");
+        let mut builder = tempfile::Builder::new();
+        builder.prefix(&prefix);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o700));
+        }
+        builder.tempdir_in(parent).map(Self).with_context(|| {
+            format!(
+                "Failed to create a scratch directory in {}",
+                parent.display()
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        self.0.path()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_scratch_dir_is_private_and_fresh() {
+        let temp = TempDir::new().unwrap();
+        let first = ScratchDir::new_in(temp.path(), "dotstate-test").unwrap();
+        let second = ScratchDir::new_in(temp.path(), "dotstate-test").unwrap();
+        assert_ne!(first.path(), second.path());
+        assert!(first.path().is_dir());
+        assert!(first
+            .path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("dotstate-test-"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(first.path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+    }
+}
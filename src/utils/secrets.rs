@@ -0,0 +1,313 @@
+//! Secret placeholders resolved from a secret manager.
+//!
+//! Text can reference secrets with `{{ secret "<reference>" }}` instead of
+//! holding them, so they never enter the storage repository. References are
+//! resolved through the secret manager's CLI when the text is rendered:
+//!
+//! | Reference                        | Resolved with                                |
+//! | -------------------------------- | -------------------------------------------- |
+//! | `op://vault/item/field`          | `op read` (1Password)                        |
+//! | `bw://item` or `bw://item/field` | `bw get` (Bitwarden, `password` by default)  |
+//! | `pass://path/to/entry`           | `pass show` (first line)                     |
+//!
+//! Other `{{ ... }}` blocks are left as they are.
+//!
+//! A synced file holding placeholders is a template: activation writes it
+//! rendered to the home directory, readable only by the user, instead of
+//! symlinking it. The rendered copy is refreshed on every activation and
+//! never synced back, so edit the template in the storage repository.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Files bigger than this aren't templates
+const MAX_TEMPLATE: u64 = 1024 * 1024;
+
+/// Fields `bw get <field> <item>` supports directly. Others are looked up
+/// among the item's custom fields.
+const BITWARDEN_FIELDS: &[&str] = &["password", "username", "uri", "totp", "notes"];
+
+/// A reference to a secret in a secret manager.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SecretRef {
+    /// 1Password secret reference, kept whole (`op://vault/item/field`)
+    OnePassword(String),
+    /// Bitwarden item (name or id) and field
+    Bitwarden { item: String, field: String },
+    /// `pass` entry path
+    Pass(String),
+}
+
+impl SecretRef {
+    /// Parse a reference like `op://vault/item/field`, `bw://item/field` or
+    /// `pass://path/to/entry`.
+    pub fn parse(reference: &str) -> Result<Self> {
+        if reference.starts_with("op://") {
+            return Ok(Self::OnePassword(reference.to_string()));
+        }
+        if let Some(rest) = reference.strip_prefix("bw://") {
+            let (item, field) = rest.rsplit_once('/').unwrap_or((rest, "password"));
+            if item.is_empty() || field.is_empty() {
+                bail!("Invalid Bitwarden reference '{reference}', expected bw://item or bw://item/field");
+            }
+            return Ok(Self::Bitwarden {
+                item: item.to_string(),
+                field: field.to_string(),
+            });
+        }
+        if let Some(path) = reference.strip_prefix("pass://") {
+            if path.is_empty() {
+                bail!("Invalid pass reference '{reference}', expected pass://path/to/entry");
+            }
+            return Ok(Self::Pass(path.to_string()));
+        }
+        bail!("Unknown secret reference '{reference}', expected op://, bw:// or pass://")
+    }
+
+    /// Fetch the secret from its secret manager.
+    pub fn resolve(&self) -> Result<String> {
+        match self {
+            Self::OnePassword(reference) => {
+                run("op", &["read", "--no-newline", reference], "op signin")
+            }
+            Self::Bitwarden { item, field } if BITWARDEN_FIELDS.contains(&field.as_str()) => run(
+                "bw",
+                &["get", field, item],
+                "bw unlock and export BW_SESSION",
+            ),
+            Self::Bitwarden { item, field } => {
+                let json = run(
+                    "bw",
+                    &["get", "item", item],
+                    "bw unlock and export BW_SESSION",
+                )?;
+                bitwarden_custom_field(&json, field)
+                    .with_context(|| format!("Bitwarden item '{item}' has no field '{field}'"))
+            }
+            Self::Pass(path) => {
+                let output = run("pass", &["show", path], "pass init")?;
+                Ok(output.lines().next().unwrap_or_default().to_string())
+            }
+        }
+    }
+}
+
+/// Run a secret manager CLI and return its output without the trailing newline.
+fn run(program: &str, args: &[&str], hint: &str) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run '{program}'. Is it installed?"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "'{program} {}' failed: {} (signed in? try `{hint}`)",
+            args.first().copied().unwrap_or_default(),
+            stderr.trim()
+        );
+    }
+    let stdout = String::from_utf8(output.stdout)
+        .with_context(|| format!("'{program}' returned a secret that isn't UTF-8"))?;
+    Ok(stdout.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Value of the custom field `name` in `bw get item` JSON output
+fn bitwarden_custom_field(json: &str, name: &str) -> Option<String> {
+    let item: serde_json::Value = serde_json::from_str(json).ok()?;
+    item.get("fields")?
+        .as_array()?
+        .iter()
+        .find(|f| f.get("name").and_then(|n| n.as_str()) == Some(name))?
+        .get("value")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Whether `source`, a file in the repository, is a template: a text file
+/// holding secret placeholders
+#[must_use]
+pub fn is_template(source: &Path) -> bool {
+    fs::metadata(source).is_ok_and(|m| m.is_file() && m.len() <= MAX_TEMPLATE)
+        && fs::read_to_string(source).is_ok_and(|text| has_placeholders(&text))
+}
+
+/// Render the template `source`, resolving its secrets.
+pub fn render_file(source: &Path) -> Result<Vec<u8>> {
+    let text = fs::read_to_string(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?;
+    render(&text)
+        .map(String::into_bytes)
+        .with_context(|| format!("Failed to render {}", source.display()))
+}
+
+/// Whether `text` contains any secret placeholder
+#[must_use]
+pub fn has_placeholders(text: &str) -> bool {
+    placeholders(text).is_ok_and(|found| !found.is_empty())
+}
+
+/// Replace every `{{ secret "..." }}` placeholder in `text` with the secret
+/// it references. Each secret is fetched once, however often it appears.
+pub fn render(text: &str) -> Result<String> {
+    render_with(text, SecretRef::resolve)
+}
+
+fn render_with(
+    text: &str,
+    mut resolve: impl FnMut(&SecretRef) -> Result<String>,
+) -> Result<String> {
+    let mut resolved: HashMap<SecretRef, String> = HashMap::new();
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for placeholder in placeholders(text)? {
+        out.push_str(&text[last..placeholder.start]);
+        if !resolved.contains_key(&placeholder.reference) {
+            let value = resolve(&placeholder.reference)?;
+            resolved.insert(placeholder.reference.clone(), value);
+        }
+        out.push_str(&resolved[&placeholder.reference]);
+        last = placeholder.end;
+    }
+    out.push_str(&text[last..]);
+    Ok(out)
+}
+
+/// A placeholder found in a text, with its byte range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Placeholder {
+    start: usize,
+    end: usize,
+    reference: SecretRef,
+}
+
+/// Find the secret placeholders in `text`, in order.
+fn placeholders(text: &str) -> Result<Vec<Placeholder>> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = text[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let inner = text[start + 2..end - 2].trim();
+
+        if let Some(arg) = inner
+            .strip_prefix("secret")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+        {
+            let arg = arg.trim();
+            let Some(reference) = arg
+                .strip_prefix('"')
+                .and_then(|a| a.strip_suffix('"'))
+                .filter(|r| !r.contains('"'))
+            else {
+                bail!(
+                    "Invalid secret placeholder '{}', expected {{{{ secret \"<reference>\" }}}}",
+                    &text[start..end]
+                );
+            };
+            found.push(Placeholder {
+                start,
+                end,
+                reference: SecretRef::parse(reference)?,
+            });
+        }
+        offset = end;
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_references() {
+        assert_eq!(
+            SecretRef::parse("op://Private/GitHub/token").unwrap(),
+            SecretRef::OnePassword("op://Private/GitHub/token".to_string())
+        );
+        assert_eq!(
+            SecretRef::parse("bw://GitHub").unwrap(),
+            SecretRef::Bitwarden {
+                item: "GitHub".to_string(),
+                field: "password".to_string()
+            }
+        );
+        assert_eq!(
+            SecretRef::parse("bw://GitHub/api key").unwrap(),
+            SecretRef::Bitwarden {
+                item: "GitHub".to_string(),
+                field: "api key".to_string()
+            }
+        );
+        assert_eq!(
+            SecretRef::parse("pass://work/npm").unwrap(),
+            SecretRef::Pass("work/npm".to_string())
+        );
+        assert!(SecretRef::parse("vault://x").is_err());
+        assert!(SecretRef::parse("pass://").is_err());
+    }
+
+    #[test]
+    fn test_render_replaces_placeholders_once_each() {
+        let text = "token = {{ secret \"op://Private/GitHub/token\" }}\n\
+                    again = {{secret \"op://Private/GitHub/token\"}}\n\
+                    npm = {{ secret \"pass://work/npm\" }}\n\
+                    other = {{ name }}\n";
+        let mut calls = 0;
+        let rendered = render_with(text, |reference| {
+            calls += 1;
+            Ok(match reference {
+                SecretRef::OnePassword(_) => "ghp_x".to_string(),
+                _ => "npm_y".to_string(),
+            })
+        })
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "token = ghp_x\nagain = ghp_x\nnpm = npm_y\nother = {{ name }}\n"
+        );
+        assert_eq!(calls, 2);
+        assert!(has_placeholders(text));
+        assert!(!has_placeholders("other = {{ name }}"));
+    }
+
+    #[test]
+    fn test_invalid_placeholder_is_an_error() {
+        assert!(render_with("{{ secret op://a/b/c }}", |_| Ok(String::new())).is_err());
+        assert!(render_with("{{ secret \"nope://a\" }}", |_| Ok(String::new())).is_err());
+    }
+
+    #[test]
+    fn test_is_template() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let template = temp.path().join(".npmrc");
+        fs::write(&template, "token={{ secret \"pass://work/npm\" }}\n").unwrap();
+        let plain = temp.path().join(".zshrc");
+        fs::write(&plain, "export EDITOR={{ editor }}\n").unwrap();
+
+        assert!(is_template(&template));
+        assert!(!is_template(&plain));
+        assert!(!is_template(temp.path()));
+        assert!(!is_template(&temp.path().join("missing")));
+    }
+
+    #[test]
+    fn test_bitwarden_custom_field() {
+        let json = r#"{"name":"GitHub","fields":[{"name":"api key","value":"abc","type":1}]}"#;
+        assert_eq!(
+            bitwarden_custom_field(json, "api key").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(bitwarden_custom_field(json, "missing"), None);
+    }
+}
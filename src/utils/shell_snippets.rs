@@ -0,0 +1,290 @@
+//! Shell rc snippets.
+//!
+//! Instead of syncing a whole `.zshrc` or `.bashrc`, shell config can live
+//! in `shell.d/` directories in the storage repository, in `common/` and in
+//! each profile. Activation writes a loader script to the data directory
+//! that sources the active profile's snippets (common first, then the
+//! profiles it inherits from, each in name order). The rc files only get a
+//! single line, marked with a comment, that sources the loader.
+//!
+//! `*.sh` snippets are sourced by every shell, `*.zsh` only by zsh and
+//! `*.bash` only by bash. The profile's environment variables (see
+//! [`env_vars`]) and aliases (see [`aliases`]) are set before them.
+
+use crate::utils::ssh_remote::shell_quote;
+use crate::utils::{aliases, env_vars, get_data_dir, get_home_dir, ProfileManifest};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Directory of snippets in `common/` and in each profile directory
+pub const SNIPPETS_DIR: &str = "shell.d";
+
+/// Rc files the source line goes into
+pub const RC_FILES: &[&str] = &[".zshrc", ".bashrc"];
+
+/// Name of the loader in the data directory
+const LOADER_FILE: &str = "shell.sh";
+
+/// Comment that marks the managed line
+const MARKER: &str = "# managed by dotstate";
+
+/// Path of the generated loader script
+#[must_use]
+pub fn loader_path() -> PathBuf {
+    get_data_dir().join(LOADER_FILE)
+}
+
+/// Snippets for `profile`: common's, then those of the profiles it inherits
+/// from, then its own. Hidden files and unknown extensions are skipped.
+pub fn snippets(repo_path: &Path, profile: &str) -> Result<Vec<PathBuf>> {
+    let manifest =
+        ProfileManifest::load_or_backfill(repo_path).context("Failed to load profile manifest")?;
+    let mut chain = manifest.inheritance_chain(profile)?;
+    chain.reverse();
+    let dirs = std::iter::once("common".to_string()).chain(chain);
+
+    let mut snippets = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(repo_path.join(dir).join(SNIPPETS_DIR)) else {
+            continue;
+        };
+        let mut found: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && shell_guard(p).is_some())
+            .collect();
+        found.sort();
+        snippets.extend(found);
+    }
+    Ok(snippets)
+}
+
+/// Condition a snippet is sourced under: `Some("")` for every shell,
+/// `None` if it isn't a snippet
+fn shell_guard(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "sh" => Some(""),
+        "zsh" => Some("[ -n \"$ZSH_VERSION\" ] && "),
+        "bash" => Some("[ -n \"$BASH_VERSION\" ] && "),
+        _ => None,
+    }
+}
+
+/// Content of the loader: sourcing the env file, the `alias` lines, then
+/// the snippets
+#[must_use]
+pub fn render_loader(
+    profile: &str,
+    env: Option<&Path>,
+    aliases: &str,
+    snippets: &[PathBuf],
+) -> String {
+    let mut out = format!(
+        "# Generated by dotstate for profile '{profile}'; activation rewrites it.\n\
+         # Edit the snippets in {SNIPPETS_DIR}/ in the storage repository instead.\n"
+    );
+    if let Some(env) = env {
+        out.push_str(&format!(". {}\n", shell_quote(&env.display().to_string())));
+    }
+    out.push_str(aliases);
+    for path in snippets {
+        let guard = shell_guard(path).unwrap_or_default();
+        out.push_str(&format!(
+            "{guard}. {}\n",
+            shell_quote(&path.display().to_string())
+        ));
+    }
+    out
+}
+
+/// Regenerate the loader (and the env files) for `profile`. Nothing is
+/// written while there are no snippets, variables or aliases and no loader
+/// yet. Returns the number of snippets.
+pub fn write_loader(repo_path: &Path, profile: &str) -> Result<usize> {
+    write(repo_path, profile, false)
+}
+
+fn write(repo_path: &Path, profile: &str, create: bool) -> Result<usize> {
+    let snippets = snippets(repo_path, profile)?;
+    let env = env_vars::write(repo_path, profile)?;
+    let aliases = aliases::render(&aliases::resolve(repo_path, profile)?);
+    let path = loader_path();
+    if snippets.is_empty() && env.is_none() && aliases.is_empty() && !path.exists() && !create {
+        return Ok(0);
+    }
+    let content = render_loader(profile, env.as_deref(), &aliases, &snippets);
+    if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        info!(
+            "Wrote shell snippet loader with {} snippet(s)",
+            snippets.len()
+        );
+    }
+    Ok(snippets.len())
+}
+
+/// The line that sources the loader, with `$HOME` in place of the home
+/// directory so a synced rc file works on every machine
+#[must_use]
+pub fn source_line() -> String {
+    let loader = loader_path();
+    let loader = loader.strip_prefix(get_home_dir()).map_or_else(
+        |_| loader.display().to_string(),
+        |rel| format!("$HOME/{}", rel.display()),
+    );
+    format!("[ -r \"{loader}\" ] && . \"{loader}\" {MARKER}")
+}
+
+/// Whether `content` has the managed line
+#[must_use]
+pub fn has_line(content: &str) -> bool {
+    content.lines().any(|l| l.trim_end().ends_with(MARKER))
+}
+
+/// `content` with the managed line set to `line`, appended if missing
+#[must_use]
+pub fn with_line(content: &str, line: &str) -> String {
+    if has_line(content) {
+        let mut out: Vec<&str> = content
+            .lines()
+            .map(|l| {
+                if l.trim_end().ends_with(MARKER) {
+                    line
+                } else {
+                    l
+                }
+            })
+            .collect();
+        out.push("");
+        return out.join("\n");
+    }
+    let mut out = content.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+    out.push_str(line);
+    out.push('\n');
+    out
+}
+
+/// `content` without the managed line
+#[must_use]
+pub fn without_line(content: &str) -> String {
+    if !has_line(content) {
+        return content.to_string();
+    }
+    let mut out: Vec<&str> = content
+        .lines()
+        .filter(|l| !l.trim_end().ends_with(MARKER))
+        .collect();
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+    if out.is_empty() {
+        return String::new();
+    }
+    out.push("");
+    out.join("\n")
+}
+
+/// Rc files to put the line in: those that exist, or the one for the login
+/// shell if none does
+#[must_use]
+pub fn rc_files() -> Vec<PathBuf> {
+    let home = get_home_dir();
+    let existing: Vec<PathBuf> = RC_FILES
+        .iter()
+        .map(|name| home.join(name))
+        .filter(|p| p.exists())
+        .collect();
+    if !existing.is_empty() {
+        return existing;
+    }
+    let zsh = std::env::var("SHELL").is_ok_and(|s| s.ends_with("zsh"));
+    vec![home.join(if zsh { ".zshrc" } else { ".bashrc" })]
+}
+
+/// Write the loader for `profile` and put the source line in the rc files.
+/// Returns the rc files that changed.
+pub fn install(repo_path: &Path, profile: &str) -> Result<Vec<PathBuf>> {
+    write(repo_path, profile, true)?;
+    let line = source_line();
+    let mut changed = Vec::new();
+    for rc in rc_files() {
+        let content = fs::read_to_string(&rc).unwrap_or_default();
+        let updated = with_line(&content, &line);
+        if updated != content {
+            fs::write(&rc, updated).with_context(|| format!("Failed to write {}", rc.display()))?;
+            info!("Added the snippet source line to {:?}", rc);
+            changed.push(rc);
+        }
+    }
+    Ok(changed)
+}
+
+/// Take the source line out of the rc files. Returns the files that changed.
+pub fn uninstall() -> Result<Vec<PathBuf>> {
+    let home = get_home_dir();
+    let mut changed = Vec::new();
+    for rc in RC_FILES.iter().map(|name| home.join(name)) {
+        let Ok(content) = fs::read_to_string(&rc) else {
+            continue;
+        };
+        let updated = without_line(&content);
+        if updated != content {
+            fs::write(&rc, updated).with_context(|| format!("Failed to write {}", rc.display()))?;
+            info!("Removed the snippet source line from {:?}", rc);
+            changed.push(rc);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_round_trip() {
+        let line = "[ -r \"$HOME/x\" ] && . \"$HOME/x\" # managed by dotstate";
+        let original = "export PATH=$HOME/bin:$PATH\n";
+        let with = with_line(original, line);
+        assert_eq!(with, format!("{original}\n{line}\n"));
+        assert!(has_line(&with));
+
+        // A different line replaces the existing one instead of adding another
+        let moved = with_line(&with, "[ -r /y ] && . /y # managed by dotstate");
+        assert_eq!(moved.matches(MARKER).count(), 1);
+        assert!(moved.contains(". /y"));
+
+        assert_eq!(without_line(&with), original);
+        assert_eq!(with_line("", line), format!("{line}\n"));
+    }
+
+    #[test]
+    fn test_render_loader_guards_by_extension() {
+        let snippets = vec![
+            PathBuf::from("/repo/common/shell.d/10-path.sh"),
+            PathBuf::from("/repo/Work/shell.d/it's.zsh"),
+            PathBuf::from("/repo/Work/shell.d/20-prompt.bash"),
+        ];
+        let env = PathBuf::from("/data/env.sh");
+        let loader = render_loader("Work", Some(&env), "alias ll='ls -l'\n", &snippets);
+        assert!(loader.contains(
+            "\n. '/data/env.sh'\nalias ll='ls -l'\n. '/repo/common/shell.d/10-path.sh'\n"
+        ));
+        assert!(loader.contains("[ -n \"$ZSH_VERSION\" ] && . '/repo/Work/shell.d/it'\\''s.zsh'\n"));
+        assert!(
+            loader.contains("[ -n \"$BASH_VERSION\" ] && . '/repo/Work/shell.d/20-prompt.bash'\n")
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Files encrypted with sops.
+//!
+//! When the storage repository has a `.sops.yaml`, tracked files that sops
+//! encrypted (age, KMS, PGP, ... any backend sops supports) are not
+//! symlinked: activation writes a decrypted copy to the home directory, and
+//! syncing encrypts changes to that copy back into the repository before
+//! committing. Only ciphertext is ever committed. Decrypting and encrypting
+//! go through the `sops` CLI, so its own configuration and key lookup apply.
+
+use crate::utils::SymlinkManager;
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracing::{info, warn};
+
+/// sops configuration file at the repository root
+pub const SOPS_CONFIG: &str = ".sops.yaml";
+
+/// Bytes read when checking a file for sops metadata. sops appends its
+/// metadata at the end, but files too big to read whole are not secrets.
+const MAX_SNIFF: u64 = 4 * 1024 * 1024;
+
+/// Whether the repository uses sops (has a `.sops.yaml` at its root)
+#[must_use]
+pub fn is_enabled(repo_path: &Path) -> bool {
+    repo_path.join(SOPS_CONFIG).is_file()
+}
+
+/// Whether `content` looks like a sops-encrypted document: the `sops`
+/// metadata block of YAML/JSON files, or the `sops_*` keys of dotenv/INI files.
+#[must_use]
+pub fn is_encrypted_content(content: &str) -> bool {
+    let has_mac = content.contains("mac");
+    let yaml = content.lines().any(|line| line == "sops:");
+    let json = content.contains("\"sops\":") && content.contains("\"mac\":");
+    let dotenv = content.lines().any(|line| line.starts_with("sops_mac="));
+    let ini = content.lines().any(|line| line.trim() == "[sops]");
+    has_mac && (yaml || json || dotenv || ini)
+}
+
+/// Whether the file at `path` is sops-encrypted
+#[must_use]
+pub fn is_encrypted(path: &Path) -> bool {
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    if !file
+        .metadata()
+        .is_ok_and(|m| m.is_file() && m.len() <= MAX_SNIFF)
+    {
+        return false;
+    }
+    let mut content = String::new();
+    file.take(MAX_SNIFF).read_to_string(&mut content).is_ok() && is_encrypted_content(&content)
+}
+
+/// Whether `source` (a file in the repository) is handled through sops
+/// instead of a symlink
+#[must_use]
+pub fn is_managed(repo_path: &Path, source: &Path) -> bool {
+    is_enabled(repo_path) && is_encrypted(source)
+}
+
+fn run_sops(repo_path: &Path, args: &[&std::ffi::OsStr]) -> Result<Vec<u8>> {
+    // Run from the repository so sops finds its .sops.yaml
+    let output = Command::new("sops")
+        .args(args)
+        .current_dir(repo_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run 'sops'. Is it installed?")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("sops failed: {}", stderr.trim());
+    }
+    Ok(output.stdout)
+}
+
+/// Decrypt `source`, a sops-encrypted file in the repository.
+pub fn decrypt(repo_path: &Path, source: &Path) -> Result<Vec<u8>> {
+    run_sops(repo_path, &["--decrypt".as_ref(), source.as_os_str()])
+        .with_context(|| format!("Failed to decrypt {}", source.display()))
+}
+
+/// Encrypt the plaintext file `plaintext` into `source` in the repository,
+/// using the creation rule `.sops.yaml` has for `source`. `source` is only
+/// replaced once encryption succeeded.
+pub fn encrypt(repo_path: &Path, plaintext: &Path, source: &Path) -> Result<()> {
+    let relative = source.strip_prefix(repo_path).unwrap_or(source);
+    let ciphertext = run_sops(
+        repo_path,
+        &[
+            "--encrypt".as_ref(),
+            "--filename-override".as_ref(),
+            relative.as_os_str(),
+            plaintext.as_os_str(),
+        ],
+    )
+    .with_context(|| format!("Failed to encrypt {}", plaintext.display()))?;
+
+    let temp_path = source.with_extension("sops.tmp");
+    fs::write(&temp_path, ciphertext)
+        .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+    fs::rename(&temp_path, source)
+        .with_context(|| format!("Failed to replace {}", source.display()))?;
+    Ok(())
+}
+
+/// Write `plaintext` to `target`, readable only by the user.
+pub fn write_plaintext(target: &Path, plaintext: &[u8]) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).context("Failed to create parent directories")?;
+    }
+    fs::write(target, plaintext)
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(target, fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to restrict permissions of {}", target.display()))?;
+    }
+    Ok(())
+}
+
+/// Home directory paths that hold decrypted copies of sops-encrypted files
+#[must_use]
+pub fn decrypted_targets(repo_path: &Path) -> HashSet<PathBuf> {
+    SymlinkManager::new_with_backup(repo_path.to_path_buf(), false)
+        .map(|mgr| {
+            mgr.get_tracked_symlinks()
+                .iter()
+                .filter(|tracked| tracked.decrypted)
+                .map(|tracked| tracked.target.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Encrypt decrypted copies that were edited back into the repository, so
+/// the changes show up in git and get synced. Returns the repository paths
+/// that were updated.
+pub fn reencrypt_changed(repo_path: &Path) -> Result<Vec<String>> {
+    if !is_enabled(repo_path) {
+        return Ok(Vec::new());
+    }
+    let mgr = SymlinkManager::new_with_backup(repo_path.to_path_buf(), false)?;
+    let mut updated = Vec::new();
+    for tracked in mgr.get_tracked_symlinks().iter().filter(|t| t.decrypted) {
+        let Ok(current) = fs::read(&tracked.target) else {
+            // Deleted or replaced locally; nothing to encrypt
+            continue;
+        };
+        if !tracked.source.is_file() || decrypt(repo_path, &tracked.source)? == current {
+            continue;
+        }
+        encrypt(repo_path, &tracked.target, &tracked.source)?;
+        let relative = tracked
+            .source
+            .strip_prefix(repo_path)
+            .unwrap_or(&tracked.source)
+            .to_string_lossy()
+            .into_owned();
+        info!("Re-encrypted {} from {:?}", relative, tracked.target);
+        updated.push(relative);
+    }
+    if !updated.is_empty() {
+        warn!("Re-encrypted {} edited sops file(s)", updated.len());
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_encrypted_documents() {
+        let yaml = "password: ENC[AES256_GCM,data:abc,type:str]\nsops:\n    mac: ENC[...]\n    version: 3.9.0\n";
+        let json = r#"{"password": "ENC[...]", "sops": {"mac": "ENC[...]", "version": "3.9.0"}}"#;
+        let dotenv = "TOKEN=ENC[...]\nsops_mac=ENC[...]\nsops_version=3.9.0\n";
+        assert!(is_encrypted_content(yaml));
+        assert!(is_encrypted_content(json));
+        assert!(is_encrypted_content(dotenv));
+        assert!(!is_encrypted_content("password: hunter2\n"));
+        assert!(!is_encrypted_content("# sops: not really\nmac = true\n"));
+    }
+
+    #[test]
+    fn test_managed_only_with_sops_config() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        let source = repo.join("secrets.yaml");
+        fs::write(&source, "a: ENC[x]\nsops:\n    mac: ENC[y]\n").unwrap();
+
+        assert!(is_encrypted(&source));
+        assert!(!is_managed(repo, &source));
+        fs::write(repo.join(SOPS_CONFIG), "creation_rules: []\n").unwrap();
+        assert!(is_managed(repo, &source));
+        assert!(!is_managed(repo, &repo.join("missing.yaml")));
+    }
+}
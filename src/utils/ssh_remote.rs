@@ -0,0 +1,183 @@
+//! Plain SSH hosts as the storage repository's remote.
+//!
+//! `dotstate ssh-remote me@server:dotfiles.git` creates a bare repository on
+//! a server the user controls, over SSH, and makes it the `origin` of the
+//! storage repository. No forge is involved: syncing pushes and pulls over
+//! SSH with the system `git`, like any SSH remote in Local mode.
+
+use anyhow::{bail, Context, Result};
+use std::process::{Command, Stdio};
+
+/// A repository path on an SSH host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshTarget {
+    pub user: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    /// Absolute, or relative to the user's home directory on the host
+    pub path: String,
+}
+
+impl SshTarget {
+    /// Parse `[user@]host:path` or `ssh://[user@]host[:port]/path`
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (authority, path, port) = if let Some(rest) = input.strip_prefix("ssh://") {
+            let (authority, path) = rest
+                .split_once('/')
+                .context("An ssh:// target needs a path, e.g. ssh://me@server/srv/dotfiles.git")?;
+            let path = path
+                .strip_prefix("~/")
+                .map_or_else(|| format!("/{path}"), std::string::ToString::to_string);
+            match authority.rsplit_once(':') {
+                Some((authority, port)) => {
+                    let port = port
+                        .parse::<u16>()
+                        .with_context(|| format!("Invalid SSH port '{port}'"))?;
+                    (authority, path, Some(port))
+                }
+                None => (authority, path, None),
+            }
+        } else {
+            let (authority, path) = input
+                .split_once(':')
+                .context("Enter the target as user@host:path, e.g. me@server:dotfiles.git")?;
+            // Relative paths already start in the home directory, and a
+            // quoted `~` wouldn't be expanded by the remote shell
+            let path = path.strip_prefix("~/").unwrap_or(path);
+            (authority, path.to_string(), None)
+        };
+        let (user, host) = match authority.split_once('@') {
+            Some((user, host)) => (Some(user.to_string()), host),
+            None => (None, authority),
+        };
+        if host.is_empty() || host.starts_with('-') || host.contains(['/', ' ']) {
+            bail!("'{host}' isn't a valid host name");
+        }
+        if user
+            .as_deref()
+            .is_some_and(|u| u.is_empty() || u.starts_with('-'))
+        {
+            bail!("Invalid SSH user in '{input}'");
+        }
+        let path = path.trim_end_matches('/').to_string();
+        if path.is_empty() || path == "/" {
+            bail!("Enter the path of the repository on the host");
+        }
+        Ok(Self {
+            user,
+            host: host.to_string(),
+            port,
+            path,
+        })
+    }
+
+    /// `[user@]host`, as passed to `ssh`
+    #[must_use]
+    pub fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{user}@{}", self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// The `origin` URL. Always `ssh://`, so pushes and pulls go through the
+    /// system `git` and the user's SSH agent and config.
+    #[must_use]
+    pub fn remote_url(&self) -> String {
+        let port = self.port.map(|p| format!(":{p}")).unwrap_or_default();
+        let path = if self.path.starts_with('/') {
+            self.path.clone()
+        } else {
+            format!("/~/{}", self.path)
+        };
+        format!("ssh://{}{port}{path}", self.destination())
+    }
+}
+
+/// Create the bare repository on the host (and its parent directories). An
+/// existing repository is left as it is.
+pub fn init_bare(target: &SshTarget) -> Result<()> {
+    let path = shell_quote(&target.path);
+    let script = format!("mkdir -p -- {path} && git init --bare --quiet -- {path}");
+    let output = ssh(target)
+        .arg(script)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run 'ssh'. Is OpenSSH installed?")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "Failed to create the repository on {}: {}\n\
+             Check that you can log in with 'ssh {}' without a password prompt \
+             and that git is installed there.",
+            target.host,
+            stderr.trim(),
+            target.destination()
+        );
+    }
+    Ok(())
+}
+
+/// `ssh` to the host of `target`, ready for the remote command
+#[must_use]
+pub fn ssh(target: &SshTarget) -> Command {
+    let mut command = Command::new("ssh");
+    // Fail instead of prompting: the TUI and scripts can't answer prompts
+    command.args(["-o", "BatchMode=yes"]);
+    // Give up on unreachable hosts instead of waiting for the TCP timeout
+    command.args(["-o", "ConnectTimeout=15"]);
+    if let Some(port) = target.port {
+        command.args(["-p", &port.to_string()]);
+    }
+    command.arg(target.destination());
+    command
+}
+
+/// Shell line putting the directories the installer and cargo use on
+/// `PATH`; non-login shells over SSH miss them
+pub const PATH_EXPORT: &str = "export PATH=\"$HOME/.local/bin:$HOME/.cargo/bin:$PATH\"";
+
+/// Quote `value` for a POSIX shell
+#[must_use]
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        let target = SshTarget::parse("me@box.lan:dotfiles.git").unwrap();
+        assert_eq!(target.destination(), "me@box.lan");
+        assert_eq!(target.remote_url(), "ssh://me@box.lan/~/dotfiles.git");
+
+        let target = SshTarget::parse("ssh://box.lan:2222/srv/git/dotfiles.git/").unwrap();
+        assert_eq!(target.user, None);
+        assert_eq!(target.port, Some(2222));
+        assert_eq!(target.path, "/srv/git/dotfiles.git");
+        assert_eq!(
+            target.remote_url(),
+            "ssh://box.lan:2222/srv/git/dotfiles.git"
+        );
+        assert_eq!(
+            SshTarget::parse("ssh://me@box.lan/~/dotfiles.git")
+                .unwrap()
+                .path,
+            "dotfiles.git"
+        );
+
+        let target = SshTarget::parse("me@box.lan:~/dots").unwrap();
+        assert_eq!(target.path, "dots");
+        assert_eq!(target.remote_url(), "ssh://me@box.lan/~/dots");
+
+        assert!(SshTarget::parse("box.lan").is_err());
+        assert!(SshTarget::parse("-oProxyCommand=x:repo").is_err());
+        assert!(SshTarget::parse("me@box.lan:").is_err());
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
@@ -0,0 +1,218 @@
+//! Starter configs for people starting from zero.
+//!
+//! `dotstate starters` lists a small gallery of sensible defaults (a
+//! `.tmux.conf`, a `.gitconfig`, a starship preset, ...). Adding one writes
+//! it to the home directory and syncs it like any other file; a starter is
+//! only offered while its file doesn't exist yet, so nothing the user wrote
+//! is ever replaced.
+
+use anyhow::{bail, Context, Result};
+use indoc::indoc;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A starter config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Starter {
+    /// Short name used on the command line
+    pub name: &'static str,
+    /// Path relative to the home directory
+    pub path: &'static str,
+    pub description: &'static str,
+    pub content: &'static str,
+}
+
+/// The gallery, in the order it is listed
+pub const STARTERS: &[Starter] = &[
+    Starter {
+        name: "tmux",
+        path: ".tmux.conf",
+        description: "tmux with mouse support, vi keys and 1-based windows",
+        content: indoc! {r##"
+            # Starter config added by dotstate
+            set -g mouse on
+            set -g history-limit 50000
+            set -g base-index 1
+            setw -g pane-base-index 1
+            set -g renumber-windows on
+            set -g escape-time 10
+            set -g default-terminal "tmux-256color"
+            setw -g mode-keys vi
+
+            # Split panes in the current directory
+            bind | split-window -h -c "#{pane_current_path}"
+            bind - split-window -v -c "#{pane_current_path}"
+            bind c new-window -c "#{pane_current_path}"
+
+            # Reload this file
+            bind r source-file ~/.tmux.conf \; display "Reloaded"
+        "##},
+    },
+    Starter {
+        name: "git",
+        path: ".gitconfig",
+        description: "git with rebase on pull, a default branch and handy aliases",
+        content: indoc! {r#"
+            # Starter config added by dotstate
+            # Set your identity with:
+            #   git config --global user.name "Your Name"
+            #   git config --global user.email you@example.com
+            [init]
+            	defaultBranch = main
+            [pull]
+            	rebase = true
+            [push]
+            	autoSetupRemote = true
+            [fetch]
+            	prune = true
+            [rebase]
+            	autoStash = true
+            [diff]
+            	colorMoved = default
+            [merge]
+            	conflictstyle = zdiff3
+            [alias]
+            	st = status -sb
+            	co = checkout
+            	br = branch
+            	lg = log --graph --oneline --decorate
+            	amend = commit --amend --no-edit
+        "#},
+    },
+    Starter {
+        name: "starship",
+        path: ".config/starship.toml",
+        description: "starship prompt with a compact two-line layout",
+        content: indoc! {r#"
+            # Starter config added by dotstate
+            add_newline = true
+
+            [character]
+            success_symbol = "[❯](bold green)"
+            error_symbol = "[❯](bold red)"
+
+            [directory]
+            truncation_length = 3
+            truncate_to_repo = true
+
+            [git_status]
+            disabled = false
+
+            [cmd_duration]
+            min_time = 2_000
+        "#},
+    },
+    Starter {
+        name: "vim",
+        path: ".vimrc",
+        description: "vim with line numbers, smart search and spaces for tabs",
+        content: indoc! {r#"
+            " Starter config added by dotstate
+            syntax on
+            filetype plugin indent on
+            set number
+            set relativenumber
+            set expandtab tabstop=4 shiftwidth=4
+            set ignorecase smartcase incsearch hlsearch
+            set scrolloff=5
+            set hidden
+            set undofile
+        "#},
+    },
+    Starter {
+        name: "inputrc",
+        path: ".inputrc",
+        description: "readline with case-insensitive completion and history search",
+        content: indoc! {r#"
+            # Starter config added by dotstate
+            $include /etc/inputrc
+            set completion-ignore-case on
+            set show-all-if-ambiguous on
+            set colored-stats on
+            "\e[A": history-search-backward
+            "\e[B": history-search-forward
+        "#},
+    },
+    Starter {
+        name: "editorconfig",
+        path: ".editorconfig",
+        description: "editorconfig defaults: UTF-8, LF and trimmed whitespace",
+        content: indoc! {r"
+            # Starter config added by dotstate
+            root = true
+
+            [*]
+            charset = utf-8
+            end_of_line = lf
+            insert_final_newline = true
+            trim_trailing_whitespace = true
+            indent_style = space
+            indent_size = 4
+
+            [Makefile]
+            indent_style = tab
+        "},
+    },
+];
+
+/// The starter called `name`
+#[must_use]
+pub fn find(name: &str) -> Option<&'static Starter> {
+    STARTERS.iter().find(|starter| starter.name == name)
+}
+
+/// Whether the starter's file is missing from `home`, so it can be added
+#[must_use]
+pub fn is_available(starter: &Starter, home: &Path) -> bool {
+    home.join(starter.path).symlink_metadata().is_err()
+}
+
+/// Write the starter into `home`. Fails when its file already exists.
+/// Returns the written path.
+pub fn write(starter: &Starter, home: &Path) -> Result<PathBuf> {
+    let path = home.join(starter.path);
+    if !is_available(starter, home) {
+        bail!(
+            "{} already exists; starters are only added for missing files",
+            path.display()
+        );
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, starter.content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_only_adds_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let home = temp_dir.path();
+        let starship = find("starship").unwrap();
+
+        assert!(is_available(starship, home));
+        let path = write(starship, home).unwrap();
+        assert_eq!(path, home.join(".config/starship.toml"));
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# Starter config added by dotstate\n"));
+        assert!(!is_available(starship, home));
+
+        // A file the user already has is never replaced
+        let tmux = find("tmux").unwrap();
+        fs::write(home.join(tmux.path), "set -g mouse off\n").unwrap();
+        assert!(write(tmux, home).is_err());
+        assert_eq!(
+            fs::read_to_string(home.join(tmux.path)).unwrap(),
+            "set -g mouse off\n"
+        );
+        assert!(find("emacs").is_none());
+    }
+}
@@ -0,0 +1,566 @@
+//! Brings state written by older versions up to date at startup.
+//!
+//! Earlier releases kept everything under ~/.config/dotstate. Symlink tracking
+//! and the last sync time now live in the data directory and caches in the
+//! cache directory (see [`crate::utils::path`]). [`migrate_legacy_state`]
+//! moves whatever is still in the old place.
+//!
+//! The versioned state files (the config, symlink tracking, the package
+//! cache) each migrate themselves when loaded. [`migrate_state`] runs both
+//! steps before anything else reads them: files in an older format are first
+//! copied to `migrations/<timestamp>/` in the data directory, next to a
+//! `report.txt` listing what changed, then loaded so they are rewritten.
+//! Adding a format version to a file is a new entry in [`state_files`].
+//! A file that fails to migrate is recorded in `migrations/failed.json` and
+//! left alone, so the failure is reported once, until the file changes or
+//! another dotstate version runs.
+//!
+//! The storage repository is left where it is: its path is recorded in the
+//! config and the symlinks in home point into it.
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// State that can't be regenerated, moved to the data directory
+const DATA_FILES: &[&str] = &["symlinks.json", "last_sync"];
+
+/// Caches, moved to the cache directory
+const CACHE_FILES: &[&str] = &[
+    "package_status.json",
+    "prompt_status.json",
+    "dotfile_scan.json",
+];
+
+/// Move state files left in the config directory by older versions.
+///
+/// Returns the names of the files that were moved.
+pub fn migrate_legacy_state() -> Result<Vec<String>> {
+    migrate_from(
+        &crate::utils::get_config_dir(),
+        &crate::utils::get_data_dir(),
+        &crate::utils::get_cache_dir(),
+    )
+}
+
+/// Legacy state files still waiting in the config directory.
+#[must_use]
+pub fn pending_legacy_files() -> Vec<PathBuf> {
+    pending_in(
+        &crate::utils::get_config_dir(),
+        &crate::utils::get_data_dir(),
+        &crate::utils::get_cache_dir(),
+    )
+}
+
+/// Each legacy file paired with where it belongs, skipping destinations that
+/// are the config directory itself.
+fn moves<'a>(
+    config_dir: &'a Path,
+    data_dir: &'a Path,
+    cache_dir: &'a Path,
+) -> impl Iterator<Item = (&'static str, PathBuf, PathBuf, bool)> + 'a {
+    let data = DATA_FILES.iter().map(move |name| (*name, data_dir, true));
+    let cache = CACHE_FILES
+        .iter()
+        .map(move |name| (*name, cache_dir, false));
+    data.chain(cache)
+        .filter(move |(_, dest, _)| *dest != config_dir)
+        .map(move |(name, dest, is_data)| (name, config_dir.join(name), dest.join(name), is_data))
+}
+
+fn pending_in(config_dir: &Path, data_dir: &Path, cache_dir: &Path) -> Vec<PathBuf> {
+    moves(config_dir, data_dir, cache_dir)
+        .map(|(_, from, _, _)| from)
+        .filter(|from| from.exists())
+        .collect()
+}
+
+fn migrate_from(config_dir: &Path, data_dir: &Path, cache_dir: &Path) -> Result<Vec<String>> {
+    let mut moved = Vec::new();
+
+    for (name, from, to, is_data) in moves(config_dir, data_dir, cache_dir) {
+        if !from.exists() {
+            continue;
+        }
+
+        if to.exists() {
+            if is_data {
+                // Both copies exist; the new location is the one in use, so
+                // keep the old file around rather than guess.
+                warn!(
+                    "{} exists in both {:?} and {:?}; leaving the old copy in place",
+                    name, config_dir, to
+                );
+            } else {
+                fs::remove_file(&from)
+                    .with_context(|| format!("Failed to remove stale cache {}", from.display()))?;
+            }
+            continue;
+        }
+
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        move_file(&from, &to)?;
+        info!("Moved {:?} to {:?}", from, to);
+        moved.push(name.to_string());
+    }
+
+    Ok(moved)
+}
+
+/// Rename, falling back to copy and delete when the directories are on
+/// different filesystems.
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)
+        .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    fs::remove_file(from).with_context(|| format!("Failed to remove {}", from.display()))?;
+    Ok(())
+}
+
+/// A state file with a versioned format
+struct StateFile {
+    name: &'static str,
+    path: PathBuf,
+    /// Version this build writes
+    current: u32,
+    /// Load the file with its own loader, which migrates and saves it
+    upgrade: fn(&Path) -> Result<()>,
+}
+
+/// The versioned state files of this machine
+fn state_files() -> Vec<StateFile> {
+    vec![
+        StateFile {
+            name: "config.toml",
+            path: crate::utils::get_config_path(),
+            current: crate::config::CURRENT_VERSION,
+            upgrade: |path| crate::Config::load_or_create(path).map(|_| ()),
+        },
+        StateFile {
+            name: "symlinks.json",
+            path: crate::utils::get_data_dir().join("symlinks.json"),
+            current: crate::utils::symlink_manager::CURRENT_VERSION,
+            upgrade: |path| crate::utils::symlink_manager::SymlinkTracking::load(path).map(|_| ()),
+        },
+        StateFile {
+            name: "package_status.json",
+            path: crate::utils::get_cache_dir().join("package_status.json"),
+            current: crate::utils::package_cache::CURRENT_VERSION,
+            upgrade: |path| {
+                crate::utils::package_cache::PackageCache::load(path.to_path_buf()).map(|_| ())
+            },
+        },
+    ]
+}
+
+/// A state file rewritten in the current format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upgrade {
+    pub name: String,
+    pub from: u32,
+    pub to: u32,
+}
+
+/// What [`migrate_state`] changed
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    /// Legacy files moved out of the config directory
+    pub moved: Vec<String>,
+    /// Files rewritten in a newer format
+    pub upgraded: Vec<Upgrade>,
+    /// Files that couldn't be migrated, with the reason
+    pub failed: Vec<(String, String)>,
+    /// Where the files were copied before they were rewritten
+    pub backup_dir: Option<PathBuf>,
+}
+
+impl MigrationReport {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty() && self.upgraded.is_empty() && self.failed.is_empty()
+    }
+
+    /// One line describing what changed, for a toast or the terminal
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.upgraded.is_empty() {
+            let files: Vec<String> = self
+                .upgraded
+                .iter()
+                .map(|u| format!("{} (v{} → v{})", u.name, u.from, u.to))
+                .collect();
+            parts.push(format!("Updated {}", files.join(", ")));
+        }
+        if !self.moved.is_empty() {
+            let verb = if parts.is_empty() { "Moved" } else { "moved" };
+            parts.push(format!("{verb} {}", self.moved.join(", ")));
+        }
+        for (name, reason) in &self.failed {
+            let verb = if parts.is_empty() {
+                "Couldn't"
+            } else {
+                "couldn't"
+            };
+            parts.push(format!("{verb} migrate {name}: {reason}"));
+        }
+        let mut summary = parts.join("; ");
+        if let Some(dir) = &self.backup_dir {
+            summary.push_str(&format!(". Old copies are in {}", dir.display()));
+        }
+        summary
+    }
+}
+
+/// Move legacy files, then rewrite the state files saved in an older format,
+/// keeping a copy of each under `migrations/` in the data directory
+pub fn migrate_state() -> Result<MigrationReport> {
+    let moved = migrate_legacy_state()?;
+    let mut report = upgrade(
+        &state_files(),
+        &crate::utils::get_data_dir().join("migrations"),
+    );
+    report.moved = moved;
+    Ok(report)
+}
+
+/// Failures recorded under the backup root
+const FAILED_FILE: &str = "failed.json";
+
+/// Files that failed to migrate, by name, with the hash of the content that
+/// failed. Only kept for the dotstate version that tried: a newer one may
+/// know how to migrate them.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FailedMigrations {
+    dotstate_version: String,
+    files: BTreeMap<String, String>,
+}
+
+impl FailedMigrations {
+    fn load(backup_root: &Path) -> Self {
+        fs::read_to_string(backup_root.join(FAILED_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|record| record.dotstate_version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default()
+    }
+
+    fn save(&self, backup_root: &Path) {
+        let path = backup_root.join(FAILED_FILE);
+        if self.files.is_empty() {
+            let _ = fs::remove_file(path);
+            return;
+        }
+        let record = Self {
+            dotstate_version: env!("CARGO_PKG_VERSION").to_string(),
+            files: self.files.clone(),
+        };
+        let written = fs::create_dir_all(backup_root)
+            .map_err(anyhow::Error::from)
+            .and_then(|()| Ok(serde_json::to_string_pretty(&record)?))
+            .and_then(|content| Ok(fs::write(&path, content)?));
+        if let Err(e) = written {
+            warn!("Failed to record migration failures in {:?}: {}", path, e);
+        }
+    }
+
+    /// Whether `path` already failed to migrate with its current content
+    fn already_failed(&self, name: &str, path: &Path) -> bool {
+        self.files.get(name).is_some_and(|hash| {
+            crate::utils::integrity::hash_path(path).is_ok_and(|current| current == *hash)
+        })
+    }
+}
+
+/// Format version of the state file at `path`, 0 when it has none, `None`
+/// when it can't be read
+fn file_version(path: &Path) -> Option<u32> {
+    let content = fs::read_to_string(path).ok()?;
+    let version = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str::<serde_json::Value>(&content)
+            .ok()?
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+    } else {
+        toml::from_str::<toml::Value>(&content)
+            .ok()?
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .and_then(|v| u64::try_from(v).ok())
+    };
+    Some(version.map_or(0, |v| u32::try_from(v).unwrap_or(u32::MAX)))
+}
+
+fn upgrade(files: &[StateFile], backup_root: &Path) -> MigrationReport {
+    let mut report = MigrationReport::default();
+    let mut failures = FailedMigrations::load(backup_root);
+    // Files migrated since, or changed by hand, are tried again
+    let still_failing: Vec<&str> = files
+        .iter()
+        .filter(|file| failures.already_failed(file.name, &file.path))
+        .map(|file| file.name)
+        .collect();
+    let recorded = failures.files.len();
+    failures
+        .files
+        .retain(|name, _| still_failing.contains(&name.as_str()));
+    let outdated: Vec<(&StateFile, u32)> = files
+        .iter()
+        .filter_map(|file| Some((file, file_version(&file.path)?)))
+        .filter(|(file, version)| *version < file.current)
+        .filter(|(file, _)| !still_failing.contains(&file.name))
+        .collect();
+    if outdated.is_empty() {
+        if failures.files.len() != recorded {
+            failures.save(backup_root);
+        }
+        return report;
+    }
+
+    let backup_dir = backup_root.join(Local::now().format("%Y%m%d-%H%M%S").to_string());
+    if let Err(e) = fs::create_dir_all(&backup_dir) {
+        // Without a copy, leave the files to migrate themselves when loaded
+        warn!("Failed to create {:?}: {}", backup_dir, e);
+        return report;
+    }
+    // A file that fails to migrate is left as it is and listed in the
+    // report, then skipped until it changes
+    for (file, version) in outdated {
+        let backup = backup_dir.join(file.name);
+        if let Err(e) = fs::copy(&file.path, &backup) {
+            warn!("Failed to back up {} before migrating: {}", file.name, e);
+            report.failed.push((file.name.to_string(), e.to_string()));
+            continue;
+        }
+        match (file.upgrade)(&file.path) {
+            Ok(()) => {
+                info!(
+                    "Migrated {} from v{} to v{}",
+                    file.name, version, file.current
+                );
+                report.upgraded.push(Upgrade {
+                    name: file.name.to_string(),
+                    from: version,
+                    to: file.current,
+                });
+            }
+            Err(e) => {
+                warn!("Failed to migrate {}: {:#}", file.name, e);
+                let _ = fs::remove_file(&backup);
+                if let Ok(hash) = crate::utils::integrity::hash_path(&file.path) {
+                    failures.files.insert(file.name.to_string(), hash);
+                }
+                report
+                    .failed
+                    .push((file.name.to_string(), format!("{e:#}")));
+            }
+        }
+    }
+    failures.save(backup_root);
+    if report.upgraded.is_empty() {
+        let _ = fs::remove_dir_all(&backup_dir);
+        return report;
+    }
+    report.backup_dir = Some(backup_dir.clone());
+    let _ = fs::write(backup_dir.join("report.txt"), report.summary() + "\n");
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct Dirs {
+        _temp: TempDir,
+        config: PathBuf,
+        data: PathBuf,
+        cache: PathBuf,
+    }
+
+    fn dirs() -> Dirs {
+        let temp = TempDir::new().unwrap();
+        let config = temp.path().join("config");
+        fs::create_dir_all(&config).unwrap();
+        Dirs {
+            config,
+            data: temp.path().join("data"),
+            cache: temp.path().join("cache"),
+            _temp: temp,
+        }
+    }
+
+    #[test]
+    fn test_moves_state_and_caches() {
+        let d = dirs();
+        fs::write(d.config.join("config.toml"), "").unwrap();
+        fs::write(d.config.join("symlinks.json"), "{}").unwrap();
+        fs::write(d.config.join("prompt_status.json"), "{}").unwrap();
+
+        assert_eq!(pending_in(&d.config, &d.data, &d.cache).len(), 2);
+        let moved = migrate_from(&d.config, &d.data, &d.cache).unwrap();
+
+        assert_eq!(moved, vec!["symlinks.json", "prompt_status.json"]);
+        assert!(d.data.join("symlinks.json").exists());
+        assert!(d.cache.join("prompt_status.json").exists());
+        assert!(d.config.join("config.toml").exists());
+        assert!(pending_in(&d.config, &d.data, &d.cache).is_empty());
+    }
+
+    #[test]
+    fn test_keeps_both_copies_of_tracking() {
+        let d = dirs();
+        fs::create_dir_all(&d.data).unwrap();
+        fs::write(d.config.join("symlinks.json"), "old").unwrap();
+        fs::write(d.data.join("symlinks.json"), "new").unwrap();
+
+        let moved = migrate_from(&d.config, &d.data, &d.cache).unwrap();
+
+        assert!(moved.is_empty());
+        assert_eq!(
+            fs::read_to_string(d.data.join("symlinks.json")).unwrap(),
+            "new"
+        );
+        assert!(d.config.join("symlinks.json").exists());
+    }
+
+    #[test]
+    fn test_upgrade_backs_up_and_reports() {
+        let d = dirs();
+        let path = d.config.join("state.json");
+        fs::write(&path, r#"{"entries": []}"#).unwrap();
+        let files = [StateFile {
+            name: "state.json",
+            path: path.clone(),
+            current: 2,
+            upgrade: |path| Ok(fs::write(path, r#"{"version": 2, "entries": []}"#)?),
+        }];
+
+        let report = upgrade(&files, &d.data);
+        assert_eq!(
+            report.upgraded,
+            vec![Upgrade {
+                name: "state.json".to_string(),
+                from: 0,
+                to: 2
+            }]
+        );
+        let backup_dir = report.backup_dir.clone().unwrap();
+        assert_eq!(
+            fs::read_to_string(backup_dir.join("state.json")).unwrap(),
+            r#"{"entries": []}"#
+        );
+        assert!(fs::read_to_string(backup_dir.join("report.txt"))
+            .unwrap()
+            .starts_with("Updated state.json (v0 → v2). Old copies are in"));
+        assert_eq!(file_version(&path), Some(2));
+
+        // Up to date now
+        assert!(upgrade(&files, &d.data).is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_reports_failures() {
+        let d = dirs();
+        let good = d.config.join("good.json");
+        let bad = d.config.join("bad.json");
+        fs::write(&good, "{}").unwrap();
+        fs::write(&bad, "{}").unwrap();
+        let files = [
+            StateFile {
+                name: "bad.json",
+                path: bad.clone(),
+                current: 1,
+                upgrade: |_| anyhow::bail!("unknown layout"),
+            },
+            StateFile {
+                name: "good.json",
+                path: good,
+                current: 1,
+                upgrade: |path| Ok(fs::write(path, r#"{"version": 1}"#)?),
+            },
+        ];
+
+        let report = upgrade(&files, &d.data);
+        assert!(!report.is_empty());
+        assert_eq!(
+            report.failed,
+            vec![("bad.json".to_string(), "unknown layout".to_string())]
+        );
+        assert_eq!(report.upgraded.len(), 1);
+        assert!(report.summary().starts_with(
+            "Updated good.json (v0 → v1); couldn't migrate bad.json: unknown layout."
+        ));
+        // The failed file is untouched and has no stale copy
+        assert_eq!(fs::read_to_string(&bad).unwrap(), "{}");
+        assert!(!report.backup_dir.unwrap().join("bad.json").exists());
+
+        // Only failures: still reported, no backup directory left behind
+        let report = upgrade(&files[..1], &d.config.join("only-failures"));
+        assert_eq!(
+            report.summary(),
+            "Couldn't migrate bad.json: unknown layout"
+        );
+        assert!(report.backup_dir.is_none());
+        let left: Vec<_> = fs::read_dir(d.config.join("only-failures"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(left, [FAILED_FILE]);
+    }
+
+    #[test]
+    fn test_failure_is_reported_once() {
+        let d = dirs();
+        let bad = d.config.join("bad.json");
+        fs::write(&bad, "{}").unwrap();
+        let files = [StateFile {
+            name: "bad.json",
+            path: bad.clone(),
+            current: 1,
+            upgrade: |_| anyhow::bail!("unknown layout"),
+        }];
+
+        assert_eq!(upgrade(&files, &d.data).failed.len(), 1);
+        assert!(upgrade(&files, &d.data).is_empty());
+
+        // Changed by hand: tried, and reported, again
+        fs::write(&bad, r#"{"entries": []}"#).unwrap();
+        assert_eq!(upgrade(&files, &d.data).failed.len(), 1);
+        assert!(upgrade(&files, &d.data).is_empty());
+
+        // Another version tries again
+        let record = d.data.join(FAILED_FILE);
+        let content = fs::read_to_string(&record)
+            .unwrap()
+            .replace(env!("CARGO_PKG_VERSION"), "0.0.1");
+        fs::write(&record, content).unwrap();
+        assert_eq!(upgrade(&files, &d.data).failed.len(), 1);
+
+        // Fixed: the record is dropped
+        fs::write(&bad, r#"{"version": 1}"#).unwrap();
+        assert!(upgrade(&files, &d.data).is_empty());
+        assert!(!record.exists());
+    }
+
+    #[test]
+    fn test_same_directory_is_a_no_op() {
+        let d = dirs();
+        fs::write(d.config.join("symlinks.json"), "{}").unwrap();
+
+        let moved = migrate_from(&d.config, &d.config, &d.config).unwrap();
+
+        assert!(moved.is_empty());
+        assert!(pending_in(&d.config, &d.config, &d.config).is_empty());
+    }
+}
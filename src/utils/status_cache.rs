@@ -0,0 +1,127 @@
+//! Last known repository status for shell prompts.
+//!
+//! `dotstate prompt` runs on every prompt render, so it must never open the
+//! repository or touch the network. The TUI's background status check,
+//! successful syncs and `dotstate prompt --refresh` write the status here,
+//! and the prompt only reads it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Current version of the `prompt_status.json` file format.
+/// Increment this when making breaking changes to the schema.
+const CURRENT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusCache {
+    #[serde(default)]
+    pub version: u32,
+    /// Number of uncommitted changes in the repository
+    #[serde(default)]
+    pub uncommitted: usize,
+    /// Commits ahead of origin
+    #[serde(default)]
+    pub ahead: usize,
+    /// Commits behind origin
+    #[serde(default)]
+    pub behind: usize,
+    /// When this status was recorded
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StatusCache {
+    /// Status recorded now
+    #[must_use]
+    pub fn new(uncommitted: usize, ahead: usize, behind: usize) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            uncommitted,
+            ahead,
+            behind,
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn cache_file() -> PathBuf {
+        crate::utils::get_cache_dir().join("prompt_status.json")
+    }
+
+    /// Load the cached status. Missing, unreadable or outdated caches yield `None`.
+    #[must_use]
+    pub fn load() -> Option<Self> {
+        Self::load_from(&Self::cache_file())
+    }
+
+    fn load_from(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str::<Self>(&content)
+            .ok()
+            .filter(|cache| cache.version == CURRENT_VERSION)
+    }
+
+    /// Save the status to the cache directory.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::cache_file())
+    }
+
+    /// Uses atomic write (temp file + rename) so a prompt never reads a partial file.
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+
+        let temp_path = path.with_extension("json.tmp");
+        let json = serde_json::to_string(self).context("Failed to serialize prompt status")?;
+        std::fs::write(&temp_path, json).context("Failed to write temp prompt status")?;
+        std::fs::rename(&temp_path, path).context("Failed to rename temp prompt status")?;
+
+        debug!("Prompt status saved to {:?}", path);
+        Ok(())
+    }
+
+    /// Compact prompt segment, e.g. `⚑2↑1`. Empty when everything is in sync.
+    #[must_use]
+    pub fn segment(&self) -> String {
+        let mut segment = String::new();
+        if self.uncommitted > 0 {
+            segment.push_str(&format!("⚑{}", self.uncommitted));
+        }
+        if self.ahead > 0 {
+            segment.push_str(&format!("↑{}", self.ahead));
+        }
+        if self.behind > 0 {
+            segment.push_str(&format!("↓{}", self.behind));
+        }
+        segment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_segment() {
+        assert_eq!(StatusCache::new(0, 0, 0).segment(), "");
+        assert_eq!(StatusCache::new(2, 1, 0).segment(), "⚑2↑1");
+        assert_eq!(StatusCache::new(0, 0, 3).segment(), "↓3");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("prompt_status.json");
+        assert!(StatusCache::load_from(&path).is_none());
+
+        let status = StatusCache::new(1, 2, 3);
+        status.save_to(&path).unwrap();
+        assert_eq!(StatusCache::load_from(&path), Some(status));
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(StatusCache::load_from(&path).is_none());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::utils::BackupManager;
+use crate::utils::parallel::parallel_map;
+use crate::utils::{git_include, managed_block, secrets, sops, unicode_path, BackupManager};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -8,7 +9,7 @@ use tracing::{debug, error, info, warn};
 
 /// Current version of the symlinks.json file format.
 /// Increment this when making breaking changes to the schema.
-const CURRENT_VERSION: u32 = 1;
+pub(crate) const CURRENT_VERSION: u32 = 1;
 
 /// Represents a symlink operation (create or remove)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +67,33 @@ pub struct TrackedSymlink {
     pub source: PathBuf,
     pub created_at: DateTime<Utc>,
     pub backup: Option<PathBuf>,
+    /// The target is a decrypted copy of a sops-encrypted source, not a symlink
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub decrypted: bool,
+    /// The target is a real file holding the source in a managed block
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub block: bool,
+    /// The target is a rendered copy of a source with secret placeholders
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rendered: bool,
+}
+
+impl TrackedSymlink {
+    /// Tracking entry for a successful create operation
+    fn from_operation(op: &SymlinkOperation) -> Self {
+        let is_file = op.target.symlink_metadata().is_ok_and(|m| m.is_file());
+        let block = is_file && managed_block::file_has_block(&op.target);
+        let rendered = is_file && !block && secrets::is_template(&op.source);
+        Self {
+            target: op.target.clone(),
+            source: op.source.clone(),
+            created_at: op.timestamp,
+            backup: op.backup.clone(),
+            decrypted: is_file && !block && !rendered,
+            block,
+            rendered,
+        }
+    }
 }
 
 /// Tracking data for all symlinks we manage
@@ -87,6 +115,35 @@ impl Default for SymlinkTracking {
 }
 
 impl SymlinkTracking {
+    /// Load the tracking file, or empty tracking when there is none.
+    /// Older formats are migrated and saved.
+    pub fn load(tracking_file: &Path) -> Result<Self> {
+        if !tracking_file.exists() {
+            return Ok(Self::default());
+        }
+        let data = fs::read_to_string(tracking_file).context("Failed to read tracking file")?;
+        let mut tracking: Self =
+            serde_json::from_str(&data).context("Failed to parse tracking file")?;
+
+        // Migrate if needed
+        if tracking.version < CURRENT_VERSION {
+            let old_version = tracking.version;
+            info!(
+                "Migrating symlinks.json from v{} to v{}",
+                old_version, CURRENT_VERSION
+            );
+            tracking = Self::migrate(tracking)?;
+
+            // Backup, save, cleanup
+            let tracking_json =
+                serde_json::to_string_pretty(&tracking).context("Failed to serialize tracking")?;
+            super::migrate_file(tracking_file, old_version, "json", || {
+                fs::write(tracking_file, &tracking_json).context("Failed to write tracking file")
+            })?;
+        }
+        Ok(tracking)
+    }
+
     // ==================== Migration Methods ====================
 
     /// Run all necessary migrations to bring tracking to current version.
@@ -108,6 +165,23 @@ impl SymlinkTracking {
     }
 }
 
+/// A symlink to create: `target` (in home) pointing at `source` (in the repo)
+struct SymlinkJob {
+    source: PathBuf,
+    target: PathBuf,
+    relative_name: String,
+}
+
+/// Result of reconciling a single file in `ensure_*_symlinks`
+enum EnsureOutcome {
+    /// Source missing or symlink already correct
+    Skipped,
+    /// A real file or directory is in the way
+    Conflict,
+    Created(SymlinkOperation),
+    Error(String),
+}
+
 /// Manages symlinks for dotfile profiles
 pub struct SymlinkManager {
     /// Path to the dotfiles repository
@@ -122,6 +196,10 @@ pub struct SymlinkManager {
     backup_manager: Option<BackupManager>,
     /// Current backup session directory (if backups are enabled and session started)
     backup_session: Option<PathBuf>,
+    /// Files kept in a managed block instead of symlinked (from the manifest)
+    managed_blocks: Vec<String>,
+    /// Git config files that include their source instead (from the manifest)
+    git_includes: Vec<String>,
 }
 
 impl SymlinkManager {
@@ -132,51 +210,26 @@ impl SymlinkManager {
 
     /// Create a new `SymlinkManager` with backup settings
     pub fn new_with_backup(repo_path: PathBuf, backup_enabled: bool) -> Result<Self> {
-        let config_dir = crate::utils::get_config_dir();
-        Self::new_with_config_dir(repo_path, backup_enabled, config_dir)
+        let data_dir = crate::utils::get_data_dir();
+        Self::new_with_data_dir(repo_path, backup_enabled, data_dir)
     }
 
-    /// Create a new `SymlinkManager` with a custom config directory.
+    /// Create a new `SymlinkManager` with a custom data directory.
     ///
     /// This is primarily used for testing to avoid polluting the real user's
-    /// config directory with test data.
-    pub fn new_with_config_dir(
+    /// data directory with test data.
+    pub fn new_with_data_dir(
         repo_path: PathBuf,
         backup_enabled: bool,
-        config_dir: PathBuf,
+        data_dir: PathBuf,
     ) -> Result<Self> {
-        // Ensure config directory exists
-        if !config_dir.exists() {
-            fs::create_dir_all(&config_dir).context("Failed to create config directory")?;
+        // Ensure data directory exists
+        if !data_dir.exists() {
+            fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
         }
 
-        let tracking_file = config_dir.join("symlinks.json");
-
-        // Load existing tracking data or create new
-        let mut tracking: SymlinkTracking = if tracking_file.exists() {
-            let data =
-                fs::read_to_string(&tracking_file).context("Failed to read tracking file")?;
-            serde_json::from_str(&data).context("Failed to parse tracking file")?
-        } else {
-            SymlinkTracking::default()
-        };
-
-        // Migrate if needed
-        if tracking_file.exists() && tracking.version < CURRENT_VERSION {
-            let old_version = tracking.version;
-            info!(
-                "Migrating symlinks.json from v{} to v{}",
-                old_version, CURRENT_VERSION
-            );
-            tracking = SymlinkTracking::migrate(tracking)?;
-
-            // Backup, save, cleanup
-            let tracking_json =
-                serde_json::to_string_pretty(&tracking).context("Failed to serialize tracking")?;
-            super::migrate_file(&tracking_file, old_version, "json", || {
-                fs::write(&tracking_file, &tracking_json).context("Failed to write tracking file")
-            })?;
-        }
+        let tracking_file = data_dir.join("symlinks.json");
+        let tracking = SymlinkTracking::load(&tracking_file)?;
 
         let backup_manager = if backup_enabled {
             Some(BackupManager::new()?)
@@ -184,6 +237,10 @@ impl SymlinkManager {
             None
         };
 
+        let (managed_blocks, git_includes) = crate::utils::ProfileManifest::load(&repo_path)
+            .map(|manifest| (manifest.managed_blocks, manifest.git_includes))
+            .unwrap_or_default();
+
         Ok(Self {
             repo_path,
             tracking_file,
@@ -191,6 +248,8 @@ impl SymlinkManager {
             backup_enabled,
             backup_manager,
             backup_session: None,
+            managed_blocks,
+            git_includes,
         })
     }
 
@@ -224,12 +283,20 @@ impl SymlinkManager {
         self.activate_resolved_with_home(profile_name, resolved_files, &home_dir)
     }
 
-    fn activate_resolved_with_home(
+    /// Activate resolved files into `home_dir` instead of the home directory,
+    /// e.g. a scratch directory for a dry run
+    pub fn activate_resolved_with_home(
         &mut self,
         profile_name: &str,
         resolved_files: &[crate::utils::profile_manifest::ResolvedFile],
         home_dir: &Path,
     ) -> Result<Vec<SymlinkOperation>> {
+        let _span = tracing::info_span!(
+            "activate",
+            profile = profile_name,
+            files = resolved_files.len()
+        )
+        .entered();
         info!(
             "Activating profile '{}' with {} resolved files (inheritance)",
             profile_name,
@@ -244,20 +311,20 @@ impl SymlinkManager {
             }
         }
 
-        let mut operations = Vec::new();
-
-        for resolved in resolved_files {
-            let source = self
-                .repo_path
-                .join(&resolved.source_profile)
-                .join(&resolved.relative_path);
-            let target = home_dir.join(&resolved.relative_path);
-
-            let operation = self.create_symlink(&source, &target, &resolved.relative_path)?;
-            operations.push(operation);
-        }
+        let jobs: Vec<SymlinkJob> = resolved_files
+            .iter()
+            .map(|resolved| SymlinkJob {
+                source: self
+                    .repo_path
+                    .join(&resolved.source_profile)
+                    .join(&resolved.relative_path),
+                target: home_dir.join(&resolved.relative_path),
+                relative_name: resolved.relative_path.clone(),
+            })
+            .collect();
+        let (operations, created) = self.create_symlinks(&jobs);
 
-        // Update tracking
+        // Update tracking, including the links made when others failed
         self.tracking.active_profile = profile_name.to_string();
         for op in &operations {
             if matches!(
@@ -266,17 +333,15 @@ impl SymlinkManager {
             ) {
                 let already_tracked = self.tracking.symlinks.iter().any(|s| s.target == op.target);
                 if !already_tracked {
-                    self.tracking.symlinks.push(TrackedSymlink {
-                        target: op.target.clone(),
-                        source: op.source.clone(),
-                        created_at: op.timestamp,
-                        backup: op.backup.clone(),
-                    });
+                    self.tracking
+                        .symlinks
+                        .push(TrackedSymlink::from_operation(op));
                 }
             }
         }
 
         self.save_tracking()?;
+        created?;
         info!(
             "Profile activated with inheritance: {} ({} symlinks)",
             profile_name,
@@ -303,6 +368,7 @@ impl SymlinkManager {
         _profile_name: &str, // Kept for API compatibility, but we deactivate ALL symlinks
         restore_files: bool,
     ) -> Result<Vec<SymlinkOperation>> {
+        let _span = tracing::info_span!("deactivate", restore_files).entered();
         info!(
             "Deactivating all symlinks (restore_files: {})",
             restore_files
@@ -376,6 +442,9 @@ impl SymlinkManager {
                                     source: common_path.join(&relative_path),
                                     created_at: Utc::now(),
                                     backup: None,
+                                    decrypted: false,
+                                    rendered: false,
+                                    block: false,
                                 };
                                 let operation = if restore_files {
                                     self.remove_symlink_with_restore(&tracked)?
@@ -540,6 +609,215 @@ impl SymlinkManager {
         Ok(false)
     }
 
+    /// Create symlinks for all `jobs` on a bounded worker pool.
+    ///
+    /// Operations that completed are returned in job order, whether or not
+    /// others failed, so the caller can still track the links it made. If
+    /// any symlink hit an I/O error, the second value is an error listing
+    /// every failed path instead of only the first.
+    fn create_symlinks(&self, jobs: &[SymlinkJob]) -> (Vec<SymlinkOperation>, Result<()>) {
+        let results = parallel_map(jobs, |job| {
+            self.create_symlink(&job.source, &job.target, &job.relative_name)
+        });
+
+        let mut operations = Vec::with_capacity(jobs.len());
+        let mut failures = Vec::new();
+        for (job, result) in jobs.iter().zip(results) {
+            match result {
+                Ok(operation) => operations.push(operation),
+                Err(e) => failures.push(format!("{}: {e:#}", job.relative_name)),
+            }
+        }
+
+        if failures.is_empty() {
+            return (operations, Ok(()));
+        }
+        let error = anyhow::anyhow!(
+            "Failed to create {} symlink(s):\n  {}",
+            failures.len(),
+            failures.join("\n  ")
+        );
+        (operations, Err(error))
+    }
+
+    /// Decide what to do for one file during reconciliation, creating the
+    /// symlink if it is missing or points elsewhere.
+    fn ensure_symlink(&self, source: &Path, target: &Path, relative_name: &str) -> EnsureOutcome {
+        // Names recorded on another OS may be normalized differently on disk
+        let (source, target) = (unicode_path::on_disk(source), unicode_path::on_disk(target));
+        let (source, target) = (source.as_path(), target.as_path());
+        // Check if source exists in repo
+        if !source.exists() {
+            debug!("Source file does not exist in repo, skipping: {:?}", source);
+            return EnsureOutcome::Skipped;
+        }
+
+        if self.is_git_include(relative_name) {
+            if git_include::is_installed(source, target) {
+                return EnsureOutcome::Skipped;
+            }
+            return match self.create_symlink(source, target, relative_name) {
+                Ok(operation) => EnsureOutcome::Created(operation),
+                Err(e) => EnsureOutcome::Error(e.to_string()),
+            };
+        }
+
+        if self.is_block(relative_name) {
+            if managed_block::is_current(source, target) {
+                return EnsureOutcome::Skipped;
+            }
+            return match self.create_symlink(source, target, relative_name) {
+                Ok(operation) => EnsureOutcome::Created(operation),
+                Err(e) => EnsureOutcome::Error(e.to_string()),
+            };
+        }
+
+        if sops::is_managed(&self.repo_path, source) {
+            return self.ensure_decrypted_copy(source, target, relative_name);
+        }
+
+        if secrets::is_template(source) {
+            return self.ensure_rendered_copy(source, target, relative_name);
+        }
+
+        // Check if symlink already exists and points to the right place
+        if let Ok(metadata) = target.symlink_metadata() {
+            if metadata.is_symlink() {
+                if let Ok(existing_target) = fs::read_link(target) {
+                    let existing_normalized = if existing_target.is_absolute() {
+                        existing_target.canonicalize().unwrap_or(existing_target)
+                    } else if let Some(parent) = target.parent() {
+                        parent
+                            .join(&existing_target)
+                            .canonicalize()
+                            .unwrap_or_else(|_| parent.join(&existing_target))
+                    } else {
+                        existing_target
+                    };
+
+                    let source_normalized = source.canonicalize().unwrap_or(source.to_path_buf());
+
+                    if existing_normalized == source_normalized {
+                        debug!(
+                            "Symlink already exists and is correct, skipping: {:?}",
+                            target
+                        );
+                        return EnsureOutcome::Skipped;
+                    }
+                }
+            } else {
+                return EnsureOutcome::Conflict;
+            }
+        }
+
+        // Symlink doesn't exist or is incorrect - create it
+        match self.create_symlink(source, target, relative_name) {
+            Ok(operation) => EnsureOutcome::Created(operation),
+            Err(e) => EnsureOutcome::Error(e.to_string()),
+        }
+    }
+
+    /// Reconcile the decrypted copy of a sops-encrypted file. A copy we made
+    /// is refreshed from the repository (edits were encrypted back before
+    /// syncing); any other real file is a conflict.
+    fn ensure_decrypted_copy(
+        &self,
+        source: &Path,
+        target: &Path,
+        relative_name: &str,
+    ) -> EnsureOutcome {
+        let is_file = target.symlink_metadata().is_ok_and(|m| m.is_file());
+        let is_tracked_copy = self
+            .tracking
+            .symlinks
+            .iter()
+            .any(|s| s.target == target && s.decrypted);
+        if is_file && !is_tracked_copy {
+            return EnsureOutcome::Conflict;
+        }
+        if !is_file {
+            return match self.create_symlink(source, target, relative_name) {
+                Ok(operation) => EnsureOutcome::Created(operation),
+                Err(e) => EnsureOutcome::Error(e.to_string()),
+            };
+        }
+
+        let refresh = sops::decrypt(&self.repo_path, source).and_then(|plaintext| {
+            if fs::read(target).ok().as_deref() != Some(plaintext.as_slice()) {
+                sops::write_plaintext(target, &plaintext)?;
+                info!("Refreshed decrypted copy: {:?}", target);
+            }
+            Ok(())
+        });
+        match refresh {
+            Ok(()) => EnsureOutcome::Skipped,
+            Err(e) => EnsureOutcome::Error(e.to_string()),
+        }
+    }
+
+    /// Reconcile the rendered copy of a template: a copy we made is rendered
+    /// again, since the template or its secrets may have changed; any other
+    /// real file is a conflict.
+    fn ensure_rendered_copy(
+        &self,
+        source: &Path,
+        target: &Path,
+        relative_name: &str,
+    ) -> EnsureOutcome {
+        let is_file = target.symlink_metadata().is_ok_and(|m| m.is_file());
+        let is_tracked_copy = self
+            .tracking
+            .symlinks
+            .iter()
+            .any(|s| s.target == target && s.rendered);
+        if is_file && !is_tracked_copy {
+            return EnsureOutcome::Conflict;
+        }
+        if !is_file {
+            return match self.create_symlink(source, target, relative_name) {
+                Ok(operation) => EnsureOutcome::Created(operation),
+                Err(e) => EnsureOutcome::Error(e.to_string()),
+            };
+        }
+
+        let refresh = secrets::render_file(source).and_then(|rendered| {
+            if fs::read(target).ok().as_deref() != Some(rendered.as_slice()) {
+                sops::write_plaintext(target, &rendered)?;
+                info!("Refreshed rendered copy: {:?}", target);
+            }
+            Ok(())
+        });
+        match refresh {
+            Ok(()) => EnsureOutcome::Skipped,
+            Err(e) => EnsureOutcome::Error(e.to_string()),
+        }
+    }
+
+    /// Whether `relative_name` is kept in a managed block instead of symlinked
+    /// Why `target` can't be linked: a symlinked parent directory (usually
+    /// a synced directory) leads into the storage repository, so the link
+    /// would replace a stored file with a link to itself or another one
+    fn repository_loop(&self, target: &Path) -> Option<String> {
+        let repo = self.repo_path.canonicalize().ok()?;
+        let parent = target.parent()?.canonicalize().ok()?;
+        parent.starts_with(&repo).then(|| {
+            format!(
+                "its directory resolves to {} inside the storage repository. \
+                 Is a parent directory synced as a whole?",
+                parent.display()
+            )
+        })
+    }
+
+    fn is_block(&self, relative_name: &str) -> bool {
+        self.managed_blocks.iter().any(|f| f == relative_name)
+    }
+
+    /// Whether `relative_name` includes its source instead of being symlinked
+    fn is_git_include(&self, relative_name: &str) -> bool {
+        self.git_includes.iter().any(|f| f == relative_name)
+    }
+
     /// Create a symlink, backing up any existing file
     fn create_symlink(
         &self,
@@ -548,6 +826,8 @@ impl SymlinkManager {
         relative_name: &str,
     ) -> Result<SymlinkOperation> {
         let timestamp = Utc::now();
+        let (source, target) = (unicode_path::on_disk(source), unicode_path::on_disk(target));
+        let (source, target) = (source.as_path(), target.as_path());
         info!("Creating symlink: {:?} -> {:?}", target, source);
 
         // Check if source exists
@@ -564,6 +844,45 @@ impl SymlinkManager {
 
         debug!("Source exists: {:?}", source);
 
+        if let Some(reason) = self.repository_loop(target) {
+            warn!("Not linking {:?}: {}", target, reason);
+            return Ok(SymlinkOperation {
+                source: source.to_path_buf(),
+                target: target.to_path_buf(),
+                backup: None,
+                status: OperationStatus::Failed(reason),
+                timestamp,
+            });
+        }
+
+        // Managed blocks and git includes are written into the real file,
+        // which stays the user's
+        if self.is_git_include(relative_name) || self.is_block(relative_name) {
+            if self.is_git_include(relative_name) {
+                git_include::install(source, target)?;
+            } else {
+                managed_block::write(source, target)?;
+            }
+            return Ok(SymlinkOperation {
+                source: source.to_path_buf(),
+                target: target.to_path_buf(),
+                backup: None,
+                status: OperationStatus::Success,
+                timestamp,
+            });
+        }
+
+        // sops-encrypted files get a decrypted copy instead of a symlink, and
+        // templates a rendered one. Decrypt or render before touching the
+        // target so a failure leaves it alone.
+        let plaintext = if sops::is_managed(&self.repo_path, source) {
+            Some(sops::decrypt(&self.repo_path, source)?)
+        } else if secrets::is_template(source) {
+            Some(secrets::render_file(source)?)
+        } else {
+            None
+        };
+
         let mut backup_path = None;
 
         // Handle existing target (file, directory, or symlink)
@@ -591,7 +910,7 @@ impl SymlinkManager {
                         let source_normalized =
                             source.canonicalize().unwrap_or(source.to_path_buf());
 
-                        if existing_normalized == source_normalized {
+                        if existing_normalized == source_normalized && plaintext.is_none() {
                             // Already points to the right place, skip
                             debug!("Symlink already exists and points to correct location: {:?} -> {:?}", target, source);
                             return Ok(SymlinkOperation {
@@ -651,6 +970,12 @@ impl SymlinkManager {
                         }
                     }
                     if metadata.is_dir() {
+                        super::local_only::preserve_children(
+                            &self.repo_path,
+                            source,
+                            target,
+                            relative_name,
+                        );
                         fs::remove_dir_all(target).with_context(|| {
                             format!("Failed to remove existing directory: {target:?}")
                         })?;
@@ -672,6 +997,21 @@ impl SymlinkManager {
             }
         }
 
+        if let Some(plaintext) = plaintext {
+            sops::write_plaintext(target, &plaintext)?;
+            info!(
+                "Wrote decrypted or rendered copy: {:?} (from {:?})",
+                target, source
+            );
+            return Ok(SymlinkOperation {
+                source: source.to_path_buf(),
+                target: target.to_path_buf(),
+                backup: backup_path,
+                status: OperationStatus::Success,
+                timestamp,
+            });
+        }
+
         // Create the symlink
         #[cfg(unix)]
         {
@@ -707,6 +1047,23 @@ impl SymlinkManager {
         let timestamp = Utc::now();
         info!("Removing symlink: {:?}", tracked.target);
 
+        if tracked.decrypted || tracked.rendered || tracked.block {
+            // The decrypted or rendered copy (or the block) already holds
+            // the file's content. An include is swapped for the settings it
+            // pointed at.
+            if git_include::is_installed(&tracked.source, &tracked.target) {
+                managed_block::write(&tracked.source, &tracked.target)?;
+            }
+            info!("Keeping decrypted copy: {:?}", tracked.target);
+            return Ok(SymlinkOperation {
+                source: tracked.source.clone(),
+                target: tracked.target.clone(),
+                backup: tracked.backup.clone(),
+                status: OperationStatus::Success,
+                timestamp,
+            });
+        }
+
         // Check if the symlink still exists
         if !tracked.target.exists() && tracked.target.symlink_metadata().is_err() {
             debug!(
@@ -861,6 +1218,30 @@ impl SymlinkManager {
     fn remove_symlink_completely(&self, tracked: &TrackedSymlink) -> Result<SymlinkOperation> {
         let timestamp = Utc::now();
 
+        if tracked.block {
+            managed_block::remove(&tracked.target)?;
+            return Ok(SymlinkOperation {
+                source: tracked.source.clone(),
+                target: tracked.target.clone(),
+                backup: tracked.backup.clone(),
+                status: OperationStatus::Success,
+                timestamp,
+            });
+        }
+
+        if (tracked.decrypted || tracked.rendered)
+            && tracked.target.symlink_metadata().is_ok_and(|m| m.is_file())
+        {
+            fs::remove_file(&tracked.target).context("Failed to remove decrypted copy")?;
+            return Ok(SymlinkOperation {
+                source: tracked.source.clone(),
+                target: tracked.target.clone(),
+                backup: tracked.backup.clone(),
+                status: OperationStatus::Success,
+                timestamp,
+            });
+        }
+
         // Check if the symlink still exists
         if !tracked.target.exists() && tracked.target.symlink_metadata().is_err() {
             return Ok(SymlinkOperation {
@@ -1049,6 +1430,9 @@ impl SymlinkManager {
                 source: new_source,
                 created_at: symlink.created_at,
                 backup: symlink.backup.clone(),
+                decrypted: symlink.decrypted,
+                rendered: symlink.rendered,
+                block: symlink.block,
             });
         }
 
@@ -1109,12 +1493,9 @@ impl SymlinkManager {
 
         // Update tracking if successful
         if matches!(operation.status, OperationStatus::Success) {
-            self.tracking.symlinks.push(TrackedSymlink {
-                target: operation.target.clone(),
-                source: operation.source.clone(),
-                created_at: operation.timestamp,
-                backup: operation.backup.clone(),
-            });
+            self.tracking
+                .symlinks
+                .push(TrackedSymlink::from_operation(&operation));
 
             // Update active profile if not set
             if self.tracking.active_profile.is_empty() {
@@ -1165,6 +1546,12 @@ impl SymlinkManager {
         profile_name: &str,
         resolved_files: &[crate::utils::profile_manifest::ResolvedFile],
     ) -> Result<(usize, usize, Vec<String>)> {
+        let _span = tracing::info_span!(
+            "ensure_symlinks",
+            profile = profile_name,
+            files = resolved_files.len()
+        )
+        .entered();
         info!(
             "Ensuring resolved symlinks for profile '{}' ({} files)",
             profile_name,
@@ -1184,65 +1571,27 @@ impl SymlinkManager {
             }
         }
 
-        for resolved in resolved_files {
+        let outcomes = parallel_map(resolved_files, |resolved| {
             let source = self
                 .repo_path
                 .join(&resolved.source_profile)
                 .join(&resolved.relative_path);
             let target = home_dir.join(&resolved.relative_path);
-
-            // Check if source exists in repo
-            if !source.exists() {
-                debug!("Source file does not exist in repo, skipping: {:?}", source);
-                skipped_count += 1;
-                continue;
-            }
-
-            // Check if symlink already exists and points to the right place
-            if let Ok(metadata) = target.symlink_metadata() {
-                if metadata.is_symlink() {
-                    if let Ok(existing_target) = fs::read_link(&target) {
-                        let existing_normalized = if existing_target.is_absolute() {
-                            existing_target.canonicalize().unwrap_or(existing_target)
-                        } else if let Some(parent) = target.parent() {
-                            parent
-                                .join(&existing_target)
-                                .canonicalize()
-                                .unwrap_or_else(|_| parent.join(&existing_target))
-                        } else {
-                            existing_target
-                        };
-
-                        let source_normalized = source.canonicalize().unwrap_or(source.clone());
-
-                        if existing_normalized == source_normalized {
-                            debug!(
-                                "Symlink already exists and is correct, skipping: {:?}",
-                                target
-                            );
-                            skipped_count += 1;
-                            continue;
-                        }
-                    }
-                } else {
-                    errors.push(format!(
-                        "File exists at {} (not a symlink)",
-                        resolved.relative_path
-                    ));
-                    continue;
-                }
-            }
-
-            // Symlink doesn't exist or is incorrect - create it
-            match self.create_symlink(&source, &target, &resolved.relative_path) {
-                Ok(operation) => {
+            self.ensure_symlink(&source, &target, &resolved.relative_path)
+        });
+
+        for (resolved, outcome) in resolved_files.iter().zip(outcomes) {
+            match outcome {
+                EnsureOutcome::Skipped => skipped_count += 1,
+                EnsureOutcome::Conflict => errors.push(format!(
+                    "File exists at {} (not a symlink)",
+                    resolved.relative_path
+                )),
+                EnsureOutcome::Created(operation) => {
                     if matches!(operation.status, OperationStatus::Success) {
-                        self.tracking.symlinks.push(TrackedSymlink {
-                            target: operation.target.clone(),
-                            source: operation.source.clone(),
-                            created_at: operation.timestamp,
-                            backup: operation.backup.clone(),
-                        });
+                        self.tracking
+                            .symlinks
+                            .push(TrackedSymlink::from_operation(&operation));
 
                         if self.tracking.active_profile.is_empty() {
                             self.tracking.active_profile = profile_name.to_string();
@@ -1261,7 +1610,7 @@ impl SymlinkManager {
                         ));
                     }
                 }
-                Err(e) => {
+                EnsureOutcome::Error(e) => {
                     error!(
                         "Error creating symlink for {}: {}",
                         resolved.relative_path, e
@@ -1332,6 +1681,156 @@ impl SymlinkManager {
         Ok(())
     }
 
+    /// Re-create the tracked symlink at `target` from the repository. A file
+    /// that took its place is backed up first, when backups are enabled.
+    pub fn restore_link(&mut self, target: &Path) -> Result<SymlinkOperation> {
+        let index = self
+            .tracking
+            .symlinks
+            .iter()
+            .position(|tracked| tracked.target == target)
+            .with_context(|| format!("{} isn't a tracked symlink", target.display()))?;
+        let tracked = self.tracking.symlinks[index].clone();
+        let home_dir = crate::utils::get_home_dir();
+        let relative_name = tracked
+            .target
+            .strip_prefix(&home_dir)
+            .unwrap_or(&tracked.target)
+            .to_string_lossy()
+            .to_string();
+
+        if self.backup_enabled && self.backup_session.is_none() {
+            if let Some(ref backup_mgr) = self.backup_manager {
+                self.backup_session = Some(backup_mgr.create_backup_session()?);
+            }
+        }
+
+        let operation = self.create_symlink(&tracked.source, &tracked.target, &relative_name)?;
+        if let OperationStatus::Failed(reason) = &operation.status {
+            anyhow::bail!("Failed to relink {}: {}", target.display(), reason);
+        }
+        let mut restored = TrackedSymlink::from_operation(&operation);
+        // Keep the backup of the original file when nothing new was backed up
+        restored.backup = restored.backup.or(tracked.backup);
+        self.tracking.symlinks[index] = restored;
+        self.save_tracking()?;
+        info!(
+            "Restored symlink {:?} -> {:?}",
+            tracked.target, tracked.source
+        );
+        Ok(operation)
+    }
+
+    /// Keep the file or directory that took the place of the tracked symlink
+    /// at `target`: it replaces the repository copy, and the symlink is
+    /// re-created pointing to it.
+    pub fn adopt_link(&mut self, target: &Path) -> Result<SymlinkOperation> {
+        let tracked = self
+            .tracking
+            .symlinks
+            .iter()
+            .find(|tracked| tracked.target == target)
+            .cloned()
+            .with_context(|| format!("{} isn't a tracked symlink", target.display()))?;
+        let metadata = target
+            .symlink_metadata()
+            .with_context(|| format!("{} doesn't exist", target.display()))?;
+        if metadata.is_symlink() {
+            anyhow::bail!(
+                "{} is a symlink, not a file that can be adopted",
+                target.display()
+            );
+        }
+
+        Self::replace_source(&tracked.source, target)?;
+
+        if metadata.is_dir() {
+            fs::remove_dir_all(target)
+        } else {
+            fs::remove_file(target)
+        }
+        .with_context(|| format!("Failed to remove {}", target.display()))?;
+        info!("Adopted {:?} into {:?}", target, tracked.source);
+        self.restore_link(target)
+    }
+
+    /// The backup taken of whatever was at the tracked symlink `target`
+    /// before dotstate linked it, if it's still there
+    #[must_use]
+    pub fn backup_of(&self, target: &Path) -> Option<&Path> {
+        self.tracking
+            .symlinks
+            .iter()
+            .find(|tracked| tracked.target == target)
+            .and_then(|tracked| tracked.backup.as_deref())
+            .filter(|backup| backup.symlink_metadata().is_ok())
+    }
+
+    /// Overwrite the repository file behind the tracked symlink at `target`
+    /// with its backup. The symlink is left alone, so the restored content
+    /// shows through it at once. Returns the backup that was restored.
+    pub fn restore_backup(&self, target: &Path) -> Result<PathBuf> {
+        let tracked = self
+            .tracking
+            .symlinks
+            .iter()
+            .find(|tracked| tracked.target == target)
+            .with_context(|| format!("{} isn't a tracked symlink", target.display()))?;
+        let backup = self
+            .backup_of(target)
+            .with_context(|| format!("{} has no backup", target.display()))?
+            .to_path_buf();
+        Self::replace_source(&tracked.source, &backup)?;
+        info!("Restored {:?} from backup {:?}", tracked.source, backup);
+        Ok(backup)
+    }
+
+    /// Replace the repository file or directory `source` with a copy of
+    /// `from`. The copy is made next to it first, so a failed copy leaves it
+    /// alone.
+    fn replace_source(source: &Path, from: &Path) -> Result<()> {
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let staging = source.with_file_name(format!(".{name}.dotstate-replace"));
+        let _ = fs::remove_dir_all(&staging);
+        let _ = fs::remove_file(&staging);
+        if let Some(parent) = staging.parent() {
+            fs::create_dir_all(parent).context("Failed to create repository directory")?;
+        }
+        let copied = if from.is_dir() {
+            crate::file_manager::copy_dir_all(from, &staging)
+        } else {
+            fs::copy(from, &staging).map(|_| ()).map_err(Into::into)
+        };
+        if let Err(e) = copied {
+            let _ = fs::remove_dir_all(&staging);
+            let _ = fs::remove_file(&staging);
+            return Err(e.context(format!(
+                "Failed to copy {} into the repository",
+                from.display()
+            )));
+        }
+
+        if source.is_dir() {
+            fs::remove_dir_all(source)
+        } else {
+            fs::remove_file(source)
+        }
+        .or_else(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                Ok(())
+            } else {
+                Err(e)
+            }
+        })
+        .with_context(|| format!("Failed to replace {}", source.display()))?;
+        fs::rename(&staging, source)
+            .with_context(|| format!("Failed to replace {}", source.display()))?;
+        Ok(())
+    }
+
     // ============================================================================
     // Common File Methods - For files shared across all profiles
     // ============================================================================
@@ -1374,12 +1873,9 @@ impl SymlinkManager {
 
         // Update tracking if successful
         if matches!(operation.status, OperationStatus::Success) {
-            self.tracking.symlinks.push(TrackedSymlink {
-                target: operation.target.clone(),
-                source: operation.source.clone(),
-                created_at: operation.timestamp,
-                backup: operation.backup.clone(),
-            });
+            self.tracking
+                .symlinks
+                .push(TrackedSymlink::from_operation(&operation));
 
             self.save_tracking()?;
             info!("Successfully added common symlink for {}", relative_path);
@@ -1500,65 +1996,27 @@ impl SymlinkManager {
             }
         }
 
-        for relative_path in files {
+        let outcomes = parallel_map(files, |relative_path| {
             let source = common_path.join(relative_path);
             let target = home_dir.join(relative_path);
-
-            // Check if source exists in repo
-            if !source.exists() {
-                debug!("Common source file does not exist, skipping: {:?}", source);
-                skipped_count += 1;
-                continue;
-            }
-
-            // Check if symlink already exists and points to the right place
-            if target.symlink_metadata().is_ok() {
-                if let Ok(metadata) = target.symlink_metadata() {
-                    if metadata.is_symlink() {
-                        if let Ok(existing_target) = fs::read_link(&target) {
-                            let existing_normalized = if existing_target.is_absolute() {
-                                existing_target.canonicalize().unwrap_or(existing_target)
-                            } else if let Some(parent) = target.parent() {
-                                parent
-                                    .join(&existing_target)
-                                    .canonicalize()
-                                    .unwrap_or_else(|_| parent.join(&existing_target))
-                            } else {
-                                existing_target
-                            };
-
-                            let source_normalized = source.canonicalize().unwrap_or(source.clone());
-
-                            if existing_normalized == source_normalized {
-                                debug!(
-                                    "Common symlink already exists and is correct, skipping: {:?}",
-                                    target
-                                );
-                                skipped_count += 1;
-                                continue;
-                            }
-                        }
-                    } else {
-                        errors.push(format!("File exists at {relative_path} (not a symlink)"));
-                        continue;
-                    }
+            self.ensure_symlink(&source, &target, relative_path)
+        });
+
+        for (relative_path, outcome) in files.iter().zip(outcomes) {
+            match outcome {
+                EnsureOutcome::Skipped => skipped_count += 1,
+                EnsureOutcome::Conflict => {
+                    errors.push(format!("File exists at {relative_path} (not a symlink)"));
                 }
-            }
-
-            // Create the symlink
-            match self.create_symlink(&source, &target, relative_path) {
-                Ok(op) => {
+                EnsureOutcome::Created(op) => {
                     if matches!(op.status, OperationStatus::Success) {
                         created_count += 1;
-                        self.tracking.symlinks.push(TrackedSymlink {
-                            target: op.target,
-                            source: op.source,
-                            created_at: op.timestamp,
-                            backup: op.backup,
-                        });
+                        self.tracking
+                            .symlinks
+                            .push(TrackedSymlink::from_operation(&op));
                     }
                 }
-                Err(e) => {
+                EnsureOutcome::Error(e) => {
                     errors.push(format!(
                         "Failed to create common symlink for {relative_path}: {e}"
                     ));
@@ -1606,15 +2064,18 @@ impl SymlinkManager {
             }
         }
 
-        let mut operations = Vec::new();
         let home_dir = crate::utils::get_home_dir();
+        let jobs: Vec<SymlinkJob> = files
+            .iter()
+            .map(|file| SymlinkJob {
+                source: common_path.join(file),
+                target: home_dir.join(file),
+                relative_name: file.clone(),
+            })
+            .collect();
+        let (operations, created) = self.create_symlinks(&jobs);
 
-        for file in files {
-            let source = common_path.join(file);
-            let target = home_dir.join(file);
-
-            let operation = self.create_symlink(&source, &target, file)?;
-
+        for operation in &operations {
             // Track both Success AND Skipped (Skipped = symlink already correct, still ours)
             if matches!(
                 operation.status,
@@ -1627,19 +2088,15 @@ impl SymlinkManager {
                     .iter()
                     .any(|s| s.target == operation.target);
                 if !already_tracked {
-                    self.tracking.symlinks.push(TrackedSymlink {
-                        target: operation.target.clone(),
-                        source: operation.source.clone(),
-                        created_at: operation.timestamp,
-                        backup: operation.backup.clone(),
-                    });
+                    self.tracking
+                        .symlinks
+                        .push(TrackedSymlink::from_operation(operation));
                 }
             }
-
-            operations.push(operation);
         }
 
         self.save_tracking()?;
+        created?;
         info!("Activated {} common files", operations.len());
 
         Ok(operations)
@@ -1671,12 +2128,12 @@ mod tests {
     fn setup_test_env() -> (TempDir, SymlinkManager) {
         let temp_dir = TempDir::new().unwrap();
         let repo_path = temp_dir.path().join("dotstate");
-        let config_dir = temp_dir.path().join("config"); // Isolated config directory
+        let data_dir = temp_dir.path().join("data"); // Isolated data directory
         fs::create_dir_all(&repo_path).unwrap();
-        fs::create_dir_all(&config_dir).unwrap();
+        fs::create_dir_all(&data_dir).unwrap();
 
-        // Use isolated config directory to avoid polluting real user config
-        let manager = SymlinkManager::new_with_config_dir(repo_path, false, config_dir).unwrap();
+        // Use isolated data directory to avoid polluting real user state
+        let manager = SymlinkManager::new_with_data_dir(repo_path, false, data_dir).unwrap();
         (temp_dir, manager)
     }
 
@@ -1725,5 +2182,203 @@ mod tests {
         let _ = fs::remove_file(&symlink_target);
     }
 
+    #[test]
+    fn test_activate_refuses_links_inside_repository() {
+        let (temp_dir, mut manager) = setup_test_env();
+        let home = temp_dir.path().join("home");
+        let repo = temp_dir.path().join("dotstate");
+        fs::create_dir_all(repo.join("test-profile/.config")).unwrap();
+        fs::write(repo.join("test-profile/.config/app"), "profile").unwrap();
+        fs::create_dir_all(repo.join("common/.config")).unwrap();
+        fs::write(repo.join("common/.config/app"), "common").unwrap();
+
+        // .config is synced as a whole, and a file inside it from common
+        let resolved = [(".config", "test-profile"), (".config/app", "common")].map(
+            |(relative_path, source_profile)| crate::utils::profile_manifest::ResolvedFile {
+                relative_path: relative_path.to_string(),
+                source_profile: source_profile.to_string(),
+            },
+        );
+        let operations = manager
+            .activate_resolved_with_home("test-profile", &resolved, &home)
+            .unwrap();
+        assert!(matches!(operations[0].status, OperationStatus::Success));
+        assert!(
+            matches!(&operations[1].status, OperationStatus::Failed(reason) if reason.contains("inside the storage repository"))
+        );
+        assert_eq!(
+            fs::read_to_string(repo.join("test-profile/.config/app")).unwrap(),
+            "profile"
+        );
+        assert!(!repo
+            .join("test-profile/.config/app")
+            .symlink_metadata()
+            .unwrap()
+            .is_symlink());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_activate_tracks_links_made_before_a_failure() {
+        let (temp_dir, mut manager) = setup_test_env();
+        let home = temp_dir.path().join("home");
+        let repo = temp_dir.path().join("dotstate");
+        fs::create_dir_all(repo.join("test-profile/.blocked")).unwrap();
+        fs::write(repo.join("test-profile/.zshrc"), "zsh").unwrap();
+        fs::write(repo.join("test-profile/.blocked/rc"), "rc").unwrap();
+        // A file where the second link's directory should be
+        fs::create_dir_all(&home).unwrap();
+        fs::write(home.join(".blocked"), "not a directory").unwrap();
+
+        let resolved = [".zshrc", ".blocked/rc"].map(|relative_path| {
+            crate::utils::profile_manifest::ResolvedFile {
+                relative_path: relative_path.to_string(),
+                source_profile: "test-profile".to_string(),
+            }
+        });
+        let error = manager
+            .activate_resolved_with_home("test-profile", &resolved, &home)
+            .unwrap_err();
+        assert!(error.to_string().contains(".blocked/rc"), "{error:#}");
+
+        // The link that was made is tracked and saved, so it can be removed
+        assert!(home.join(".zshrc").symlink_metadata().unwrap().is_symlink());
+        let reloaded =
+            SymlinkManager::new_with_data_dir(repo, false, temp_dir.path().join("data")).unwrap();
+        let tracked: Vec<&Path> = reloaded
+            .get_tracked_symlinks()
+            .iter()
+            .map(|s| s.target.as_path())
+            .collect();
+        assert_eq!(tracked, vec![home.join(".zshrc").as_path()]);
+    }
+
+    #[test]
+    fn test_templates_are_rendered_not_linked() {
+        let (temp_dir, mut manager) = setup_test_env();
+        let home = temp_dir.path().join("home");
+        let repo = temp_dir.path().join("dotstate");
+        fs::create_dir_all(repo.join("test-profile")).unwrap();
+        let source = repo.join("test-profile/.npmrc");
+        fs::write(
+            &source,
+            "//registry.npmjs.org/:_authToken={{ secret \"pass://dotstate-test/missing\" }}\n",
+        )
+        .unwrap();
+
+        // A secret that can't be resolved fails the file and leaves home alone
+        let resolved = [crate::utils::profile_manifest::ResolvedFile {
+            relative_path: ".npmrc".to_string(),
+            source_profile: "test-profile".to_string(),
+        }];
+        let error = manager
+            .activate_resolved_with_home("test-profile", &resolved, &home)
+            .unwrap_err();
+        assert!(error.to_string().contains(".npmrc"), "{error:#}");
+        assert!(home.join(".npmrc").symlink_metadata().is_err());
+
+        // A rendered copy is tracked as one, not as a decrypted copy
+        fs::create_dir_all(&home).unwrap();
+        fs::write(home.join(".npmrc"), "//registry.npmjs.org/:_authToken=x\n").unwrap();
+        let tracked = TrackedSymlink::from_operation(&SymlinkOperation {
+            source,
+            target: home.join(".npmrc"),
+            backup: None,
+            status: OperationStatus::Success,
+            timestamp: Utc::now(),
+        });
+        assert!(tracked.rendered);
+        assert!(!tracked.decrypted);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restore_and_adopt_replaced_links() {
+        let (temp_dir, mut manager) = setup_test_env();
+        let home = temp_dir.path().join("home");
+        let repo = temp_dir.path().join("dotstate");
+        fs::create_dir_all(repo.join("test-profile")).unwrap();
+        fs::write(repo.join("test-profile/.zshrc"), "synced").unwrap();
+        fs::write(repo.join("test-profile/.vimrc"), "synced").unwrap();
+        let resolved = [".zshrc", ".vimrc"].map(|relative_path| {
+            crate::utils::profile_manifest::ResolvedFile {
+                relative_path: relative_path.to_string(),
+                source_profile: "test-profile".to_string(),
+            }
+        });
+        manager
+            .activate_resolved_with_home("test-profile", &resolved, &home)
+            .unwrap();
+
+        // An installer wrote over both links
+        for name in [".zshrc", ".vimrc"] {
+            fs::remove_file(home.join(name)).unwrap();
+            fs::write(home.join(name), "installer").unwrap();
+        }
+
+        manager.restore_link(&home.join(".zshrc")).unwrap();
+        assert!(home.join(".zshrc").is_symlink());
+        assert_eq!(fs::read_to_string(home.join(".zshrc")).unwrap(), "synced");
+
+        manager.adopt_link(&home.join(".vimrc")).unwrap();
+        assert!(home.join(".vimrc").is_symlink());
+        assert_eq!(
+            fs::read_to_string(repo.join("test-profile/.vimrc")).unwrap(),
+            "installer"
+        );
+        assert!(manager.adopt_link(&home.join(".vimrc")).is_err());
+
+        // The backup of the original file can be put back in the repository
+        assert!(manager.backup_of(&home.join(".zshrc")).is_none());
+        let backup = temp_dir.path().join("backups/.zshrc");
+        fs::create_dir_all(backup.parent().unwrap()).unwrap();
+        fs::write(&backup, "original").unwrap();
+        let tracked = manager
+            .tracking
+            .symlinks
+            .iter_mut()
+            .find(|tracked| tracked.target == home.join(".zshrc"))
+            .unwrap();
+        tracked.backup = Some(backup.clone());
+        assert_eq!(
+            manager.restore_backup(&home.join(".zshrc")).unwrap(),
+            backup
+        );
+        assert!(home.join(".zshrc").is_symlink());
+        assert_eq!(fs::read_to_string(home.join(".zshrc")).unwrap(), "original");
+        assert!(manager.restore_backup(&home.join(".vimrc")).is_err());
+    }
+    #[test]
+    fn test_activate_many_files_keeps_order() {
+        let (temp_dir, mut manager) = setup_test_env();
+        let home = temp_dir.path().join("home");
+        let profile_path = temp_dir.path().join("dotstate/test-profile");
+
+        let resolved: Vec<_> = (0..50)
+            .map(|i| {
+                let relative_path = format!(".config/app{i}/config");
+                let source = profile_path.join(&relative_path);
+                fs::create_dir_all(source.parent().unwrap()).unwrap();
+                fs::write(&source, format!("{i}")).unwrap();
+                crate::utils::profile_manifest::ResolvedFile {
+                    relative_path,
+                    source_profile: "test-profile".to_string(),
+                }
+            })
+            .collect();
+
+        let operations = manager
+            .activate_resolved_with_home("test-profile", &resolved, &home)
+            .unwrap();
+
+        assert_eq!(operations.len(), 50);
+        for (i, op) in operations.iter().enumerate() {
+            assert_eq!(op.target, home.join(format!(".config/app{i}/config")));
+            assert!(matches!(op.status, OperationStatus::Success));
+            assert_eq!(fs::read_to_string(&op.target).unwrap(), format!("{i}"));
+        }
+        assert_eq!(manager.tracking.symlinks.len(), 50);
+    }
+
     // More tests would go here...
 }
@@ -0,0 +1,297 @@
+//! Local history of sync operations.
+//!
+//! Every sync, pull and force sync appends a record to `sync_history.jsonl`
+//! in the data directory: when it ran, from where, what was committed and
+//! pushed, what was pulled, and how it ended. Unlike the git log this also
+//! keeps failed syncs and conflicts, and it stays on this machine.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Name of the history file in the data directory
+const HISTORY_FILE: &str = "sync_history.jsonl";
+
+/// Oldest records are dropped once the history grows past this
+const MAX_RECORDS: usize = 500;
+
+/// What kind of sync ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncKind {
+    /// Commit, pull and push
+    Sync,
+    /// Pull only (pull-only machine or the pull banner)
+    Pull,
+    /// Force sync: discard local changes and match the remote
+    MatchRemote,
+    /// Force sync: force push local state over the remote
+    OverwriteRemote,
+    /// Reconcile wizard: merge both sides file by file and push
+    Reconcile,
+}
+
+impl SyncKind {
+    /// Display label
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            SyncKind::Sync => "Sync",
+            SyncKind::Pull => "Pull",
+            SyncKind::MatchRemote => "Force sync (match remote)",
+            SyncKind::OverwriteRemote => "Force sync (overwrite remote)",
+            SyncKind::Reconcile => "Reconcile",
+        }
+    }
+}
+
+/// Where the sync was started from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncOrigin {
+    Tui,
+    Cli,
+    Daemon,
+}
+
+impl SyncOrigin {
+    /// Display label
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            SyncOrigin::Tui => "TUI",
+            SyncOrigin::Cli => "CLI",
+            SyncOrigin::Daemon => "Daemon",
+        }
+    }
+}
+
+/// One sync operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncRecord {
+    /// When the sync started
+    pub timestamp: DateTime<Utc>,
+    pub kind: SyncKind,
+    pub origin: SyncOrigin,
+    /// Profile active during the sync
+    pub profile: String,
+    /// Branch synced, once known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Whether the sync completed
+    pub success: bool,
+    /// Short id of the commit the sync created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Files committed, as "X filename" entries. Pushed if the sync succeeded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub committed: Vec<String>,
+    /// Commits pulled from the remote
+    #[serde(default)]
+    pub pulled_commits: usize,
+    /// Files changed by the pull (or the reset to the remote)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pulled: Vec<String>,
+    /// Whether the sync stopped on a merge or rebase conflict
+    #[serde(default)]
+    pub conflict: bool,
+    /// Why the sync failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SyncRecord {
+    /// Record for a sync starting now
+    #[must_use]
+    pub fn start(kind: SyncKind, origin: SyncOrigin, profile: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            kind,
+            origin,
+            profile: profile.to_string(),
+            branch: None,
+            success: false,
+            commit: None,
+            committed: Vec::new(),
+            pulled_commits: 0,
+            pulled: Vec::new(),
+            conflict: false,
+            error: None,
+        }
+    }
+
+    /// Remember the commit the sync created
+    pub fn set_commit(&mut self, oid: Option<git2::Oid>) {
+        self.commit = oid.map(|oid| oid.to_string().chars().take(7).collect());
+    }
+
+    /// Set the outcome. `message` is the sync's result message; for a
+    /// failure its first line becomes the error.
+    pub fn finish(&mut self, success: bool, message: &str) {
+        self.success = success;
+        if success {
+            return;
+        }
+        // A failed sync undoes its commit
+        self.commit = None;
+        self.conflict = message.to_lowercase().contains("conflicts detected");
+        self.error = message
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(|line| line.trim_start_matches("Error: ").to_string());
+    }
+
+    /// One-line summary, e.g. "↑3 ↓2 (abc1234)" or the error
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if let Some(error) = &self.error {
+            return error.clone();
+        }
+        let mut parts = Vec::new();
+        if !self.committed.is_empty() {
+            parts.push(format!("↑{}", self.committed.len()));
+        }
+        if !self.pulled.is_empty() {
+            parts.push(format!("↓{}", self.pulled.len()));
+        }
+        if parts.is_empty() {
+            parts.push("No changes".to_string());
+        }
+        if let Some(commit) = &self.commit {
+            parts.push(format!("({commit})"));
+        }
+        parts.join(" ")
+    }
+}
+
+fn history_file() -> PathBuf {
+    crate::utils::get_data_dir().join(HISTORY_FILE)
+}
+
+/// Append a record to the history.
+pub fn append(record: &SyncRecord) -> Result<()> {
+    append_to(&history_file(), record)
+}
+
+/// All records, newest first. A missing history is empty.
+#[must_use]
+pub fn load() -> Vec<SyncRecord> {
+    load_from(&history_file())
+}
+
+/// Append `record`, logging instead of failing: the history is informational
+/// and must never fail a sync.
+pub fn save(record: &SyncRecord) {
+    if let Err(e) = append(record) {
+        warn!("Failed to write sync history: {}", e);
+    }
+}
+
+fn append_to(path: &Path, record: &SyncRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create data directory")?;
+    }
+    let line = serde_json::to_string(record).context("Failed to serialize sync record")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{line}").with_context(|| format!("Failed to write {}", path.display()))?;
+    drop(file);
+
+    prune(path)
+}
+
+/// Drop the oldest records once there are more than [`MAX_RECORDS`].
+fn prune(path: &Path) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.len() <= MAX_RECORDS {
+        return Ok(());
+    }
+    let mut kept = lines[lines.len() - MAX_RECORDS..].join("\n");
+    kept.push('\n');
+    let temp_path = path.with_extension("jsonl.tmp");
+    fs::write(&temp_path, kept).context("Failed to write temp sync history")?;
+    fs::rename(&temp_path, path).context("Failed to rename temp sync history")?;
+    Ok(())
+}
+
+fn load_from(path: &Path) -> Vec<SyncRecord> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    // Skip lines that don't parse rather than losing the whole history
+    let mut records: Vec<SyncRecord> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    records.reverse();
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_load_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(HISTORY_FILE);
+
+        let mut first = SyncRecord::start(SyncKind::Sync, SyncOrigin::Tui, "Work");
+        first.committed = vec!["M .zshrc".to_string()];
+        first.commit = Some("abc1234".to_string());
+        first.finish(true, "✓ Successfully synced with remote!");
+        append_to(&path, &first).unwrap();
+
+        let mut second = SyncRecord::start(SyncKind::Pull, SyncOrigin::Cli, "Work");
+        second.finish(
+            false,
+            "Error: Failed to pull from remote\n  Rebase conflicts detected. Please resolve manually",
+        );
+        append_to(&path, &second).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        let records = load_from(&path);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, SyncKind::Pull);
+        assert!(records[0].conflict);
+        assert_eq!(
+            records[0].error.as_deref(),
+            Some("Failed to pull from remote")
+        );
+        assert_eq!(records[1].summary(), "↑1 (abc1234)");
+        assert!(load_from(&temp_dir.path().join("missing.jsonl")).is_empty());
+    }
+
+    #[test]
+    fn test_prunes_oldest_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(HISTORY_FILE);
+
+        for i in 0..=MAX_RECORDS {
+            let mut record = SyncRecord::start(SyncKind::Sync, SyncOrigin::Cli, &i.to_string());
+            record.finish(true, "");
+            append_to(&path, &record).unwrap();
+        }
+
+        let records = load_from(&path);
+        assert_eq!(records.len(), MAX_RECORDS);
+        assert_eq!(records[0].profile, MAX_RECORDS.to_string());
+        assert_eq!(records[MAX_RECORDS - 1].profile, "1");
+    }
+}
@@ -6,6 +6,8 @@
 //! - Directories containing already-synced files
 //! - Nested git repositories
 //! - Symlinks that could cause issues (broken, circular, or external)
+//! - Paths that lead back into the storage repository or the home directory
+//! - Paths git would ignore, which would never be committed
 //! - Ability to create symlinks before deleting files
 
 use anyhow::{Context, Result};
@@ -658,6 +660,115 @@ pub fn validate_before_sync(
         );
     }
 
+    validate_no_self_reference(full_path, repo_path)
+}
+
+/// Check that `full_path`, once symlinks are resolved, doesn't lead back
+/// into the storage repository or take in the home directory.
+///
+/// The basic checks compare paths as written; a symlink (the path itself, a
+/// parent of it, or a link inside a directory) can still point at the
+/// repository, so adding it would copy the repository into itself or store
+/// a link to its own files.
+#[must_use]
+pub fn validate_no_self_reference(full_path: &Path, repo_path: &Path) -> ValidationResult {
+    check_self_reference(full_path, repo_path, &crate::utils::get_home_dir())
+}
+
+fn check_self_reference(full_path: &Path, repo_path: &Path, home: &Path) -> ValidationResult {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let (resolved, repo, home) = (canonical(full_path), canonical(repo_path), canonical(home));
+
+    if resolved.starts_with(&repo) {
+        return ValidationResult::unsafe_with(format!(
+            "Cannot add '{}': it resolves to '{}', inside the storage repository.\n\n\
+             It is probably a link to a file dotstate already stores.",
+            full_path.display(),
+            resolved.display()
+        ));
+    }
+    if repo.starts_with(&resolved) {
+        return ValidationResult::unsafe_with(format!(
+            "Cannot add '{}': it resolves to '{}', which contains the storage repository.\n\n\
+             Adding it would copy the repository into itself.",
+            full_path.display(),
+            resolved.display()
+        ));
+    }
+    if home.starts_with(&resolved) {
+        return ValidationResult::unsafe_with(format!(
+            "Cannot add '{}': it resolves to '{}', which contains your home directory.",
+            full_path.display(),
+            resolved.display()
+        ));
+    }
+
+    if resolved.is_dir() {
+        let mut links = Vec::new();
+        find_links_into(&resolved, &repo, &mut links, 0);
+        if !links.is_empty() {
+            let listed: Vec<String> = links
+                .iter()
+                .take(5)
+                .map(|(link, target)| format!("  • {} -> {}", link.display(), target.display()))
+                .collect();
+            return ValidationResult::unsafe_with(format!(
+                "Cannot sync directory '{}': it contains symlinks into the storage \
+                 repository.\n\n{}\n\n\
+                 Stored files would end up linking to themselves. Remove these links first.",
+                full_path.display(),
+                listed.join("\n")
+            ));
+        }
+    }
+
+    ValidationResult::safe()
+}
+
+/// Collect the symlinks under `dir` resolving into `root`, without
+/// following them. Links back up into the home directory are caught as
+/// circular by [`validate_directory_symlinks`].
+fn find_links_into(dir: &Path, root: &Path, links: &mut Vec<(PathBuf, PathBuf)>, depth: usize) {
+    if depth > MAX_VALIDATION_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            let Ok(target) = path.canonicalize() else {
+                continue; // Broken links are reported by validate_directory_symlinks
+            };
+            if target.starts_with(root) {
+                links.push((path, target));
+            }
+        } else if file_type.is_dir() {
+            find_links_into(&path, root, links, depth + 1);
+        }
+    }
+}
+
+/// Check that git won't ignore `repo_relative` (e.g. `work/.zshrc`) in the
+/// storage repository: a file matching the repository's `.gitignore`,
+/// `.git/info/exclude` or the user's `core.excludesFile` would be copied
+/// into the repository but never committed, so it would silently not sync.
+#[must_use]
+pub fn validate_not_ignored(repo_path: &Path, repo_relative: &str) -> ValidationResult {
+    let Ok(repo) = git2::Repository::open(repo_path) else {
+        return ValidationResult::safe();
+    };
+    if repo.is_path_ignored(repo_relative).unwrap_or(false) {
+        return ValidationResult::unsafe_with(format!(
+            "Cannot sync '{repo_relative}': git ignores it, so it would never be committed.\n\n\
+             Check the repository's .gitignore and your global excludes file \
+             (core.excludesFile, ~/.config/git/ignore)."
+        ));
+    }
     ValidationResult::safe()
 }
 
@@ -1212,6 +1323,57 @@ mod tests {
         // (In real scenario, get_home_dir() would return actual home)
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_no_self_reference() {
+        use std::os::unix::fs::symlink;
+        let temp_dir = TempDir::new().unwrap();
+        let home = temp_dir.path().join("home");
+        let repo = home.join(".config/dotstate/storage");
+        std::fs::create_dir_all(repo.join("work")).unwrap();
+        std::fs::write(repo.join("work/.zshrc"), "").unwrap();
+
+        // A link to a stored file
+        symlink(repo.join("work/.zshrc"), home.join(".zshrc")).unwrap();
+        let result = check_self_reference(&home.join(".zshrc"), &repo, &home);
+        assert!(result
+            .error_message
+            .unwrap()
+            .contains("inside the storage repository"));
+
+        // A link to a directory holding the repository, or to home
+        symlink(home.join(".config"), home.join("cfg")).unwrap();
+        let result = check_self_reference(&home.join("cfg"), &repo, &home);
+        assert!(result
+            .error_message
+            .unwrap()
+            .contains("copy the repository into itself"));
+        symlink(&home, home.join("me")).unwrap();
+        assert!(!check_self_reference(&home.join("me"), &repo, &home).is_safe);
+
+        // A directory with a link into the repository
+        let app = home.join(".app");
+        std::fs::create_dir_all(&app).unwrap();
+        std::fs::write(app.join("settings"), "").unwrap();
+        assert!(check_self_reference(&app, &repo, &home).is_safe);
+        symlink(repo.join("work/.zshrc"), app.join("rc")).unwrap();
+        let result = check_self_reference(&app, &repo, &home);
+        assert!(result.error_message.unwrap().contains(".app/rc ->"));
+    }
+
+    #[test]
+    fn test_validate_not_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        git2::Repository::init(repo_path).unwrap();
+        std::fs::write(repo_path.join(".gitignore"), "*.bak\n").unwrap();
+
+        assert!(validate_not_ignored(repo_path, "work/.zshrc").is_safe);
+        let result = validate_not_ignored(repo_path, "work/.zshrc.bak");
+        assert!(!result.is_safe);
+        assert!(result.error_message.unwrap().contains("git ignores it"));
+    }
+
     #[test]
     fn test_concurrent_operations_simulation() {
         // Simulate what happens if validation passes but file changes
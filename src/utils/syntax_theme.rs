@@ -1,11 +1,28 @@
 //! Syntax theme selection utilities for syntax highlighting.
 //!
 //! This module provides a unified way to select syntax themes based on
-//! the current UI theme type, avoiding duplication across the codebase.
+//! the `syntax_theme` setting and the current UI theme type, avoiding
+//! duplication across the codebase.
 
+#[cfg(feature = "tui")]
 use crate::styles::ThemeType;
+#[cfg(feature = "tui")]
 use syntect::highlighting::{Theme, ThemeSet};
 
+/// Setting value that pairs the syntax theme with the UI theme
+pub const AUTO_SYNTAX_THEME: &str = "auto";
+
+/// Syntax themes bundled with syntect that can be picked in settings
+pub const SYNTAX_THEMES: &[&str] = &[
+    "base16-ocean.dark",
+    "base16-eighties.dark",
+    "base16-mocha.dark",
+    "base16-ocean.light",
+    "InspiredGitHub",
+    "Solarized (dark)",
+    "Solarized (light)",
+];
+
 /// Get the appropriate syntax highlighting theme based on the current UI theme.
 ///
 /// This function selects a syntax theme that matches the current UI theme type
@@ -25,6 +42,7 @@ use syntect::highlighting::{Theme, ThemeSet};
 ///
 /// Panics if no syntect themes are available at all (should never happen
 /// with default themes loaded).
+#[cfg(feature = "tui")]
 #[must_use]
 pub fn get_syntax_theme(theme_set: &ThemeSet, theme_type: ThemeType) -> &Theme {
     let preferred_names = match theme_type {
@@ -60,26 +78,45 @@ pub fn get_syntax_theme(theme_set: &ThemeSet, theme_type: ThemeType) -> &Theme {
         .expect("No syntect themes available")
 }
 
-/// Get the syntax theme using the current global UI theme.
+/// Resolve the `syntax_theme` setting to a theme.
 ///
-/// This is a convenience function that automatically retrieves the current
-/// UI theme type from the global theme settings.
+/// `"auto"` (or a theme name that isn't available) picks the theme paired
+/// with `theme_type`, see [`get_syntax_theme`].
+#[cfg(feature = "tui")]
+#[must_use]
+pub fn resolve_syntax_theme<'a>(
+    theme_set: &'a ThemeSet,
+    setting: &str,
+    theme_type: ThemeType,
+) -> &'a Theme {
+    if setting != AUTO_SYNTAX_THEME {
+        if let Some(theme) = theme_set.themes.get(setting) {
+            return theme;
+        }
+    }
+    get_syntax_theme(theme_set, theme_type)
+}
+
+/// Get the syntax theme for the `syntax_theme` setting, pairing `"auto"`
+/// with the current global UI theme.
 ///
 /// # Arguments
 ///
 /// * `theme_set` - The syntect `ThemeSet` containing available themes
+/// * `setting` - The configured syntax theme name, or `"auto"`
 ///
 /// # Returns
 ///
 /// A reference to the selected syntax highlighting theme.
+#[cfg(feature = "tui")]
 #[must_use]
-pub fn get_current_syntax_theme(theme_set: &ThemeSet) -> &Theme {
+pub fn get_current_syntax_theme<'a>(theme_set: &'a ThemeSet, setting: &str) -> &'a Theme {
     use crate::styles::theme as ui_theme;
     let theme_type = ui_theme().theme_type;
-    get_syntax_theme(theme_set, theme_type)
+    resolve_syntax_theme(theme_set, setting, theme_type)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tui"))]
 mod tests {
     use super::*;
 
@@ -132,4 +169,25 @@ mod tests {
             .as_ref()
             .is_none_or(std::string::String::is_empty));
     }
+
+    #[test]
+    fn test_resolve_syntax_theme() {
+        let theme_set = ThemeSet::load_defaults();
+
+        // Every offered theme ships with syntect
+        for name in SYNTAX_THEMES {
+            let theme = resolve_syntax_theme(&theme_set, name, ThemeType::Dark);
+            assert_eq!(
+                theme.name.as_deref(),
+                theme_set.themes[*name].name.as_deref()
+            );
+        }
+
+        // Auto and unknown names fall back to the UI theme pairing
+        let paired = get_syntax_theme(&theme_set, ThemeType::Light).name.clone();
+        let auto = resolve_syntax_theme(&theme_set, AUTO_SYNTAX_THEME, ThemeType::Light);
+        assert_eq!(auto.name, paired);
+        let unknown = resolve_syntax_theme(&theme_set, "no-such-theme", ThemeType::Light);
+        assert_eq!(unknown.name, paired);
+    }
 }
@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+
 /// Calculate the X coordinate for a cursor position in text
 ///
 /// # Arguments
@@ -46,3 +48,41 @@ pub fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
         format!("{truncated}...")
     }
 }
+
+/// Short relative time, e.g. "just now", "5m ago", "3h ago", "2d ago".
+#[must_use]
+pub fn format_time_ago(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(time);
+    if elapsed.num_minutes() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{}h ago", elapsed.num_hours())
+    } else {
+        format!("{}d ago", elapsed.num_days())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_time_ago() {
+        let now = Utc::now();
+        assert_eq!(format_time_ago(now, now), "just now");
+        assert_eq!(
+            format_time_ago(now - chrono::Duration::minutes(5), now),
+            "5m ago"
+        );
+        assert_eq!(
+            format_time_ago(now - chrono::Duration::hours(3), now),
+            "3h ago"
+        );
+        assert_eq!(
+            format_time_ago(now - chrono::Duration::days(2), now),
+            "2d ago"
+        );
+    }
+}
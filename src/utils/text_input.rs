@@ -116,6 +116,56 @@ impl TextInput {
         self.cursor = self.text.chars().count();
     }
 
+    /// Insert a line break at the cursor position (multi-line inputs).
+    pub fn insert_newline(&mut self) {
+        let byte_index = self
+            .text
+            .char_indices()
+            .map(|(i, _)| i)
+            .nth(self.cursor)
+            .unwrap_or(self.text.len());
+        self.text.insert(byte_index, '\n');
+        self.cursor += 1;
+    }
+
+    /// Line and column of the cursor, counted in characters.
+    #[must_use]
+    pub fn cursor_line_col(&self) -> (usize, usize) {
+        let before: Vec<char> = self.text.chars().take(self.cursor).collect();
+        let line = before.iter().filter(|&&c| c == '\n').count();
+        let col = before.iter().rev().take_while(|&&c| c != '\n').count();
+        (line, col)
+    }
+
+    /// Move the cursor to the same column on the previous line.
+    pub fn move_up(&mut self) {
+        let (line, col) = self.cursor_line_col();
+        if line > 0 {
+            self.move_to_line_col(line - 1, col);
+        }
+    }
+
+    /// Move the cursor to the same column on the next line.
+    pub fn move_down(&mut self) {
+        let (line, col) = self.cursor_line_col();
+        if line + 1 < self.text.split('\n').count() {
+            self.move_to_line_col(line + 1, col);
+        }
+    }
+
+    /// Put the cursor at `col` on `line`, clamped to the line's length.
+    fn move_to_line_col(&mut self, line: usize, col: usize) {
+        let mut cursor = 0;
+        for (i, text) in self.text.split('\n').enumerate() {
+            let len = text.chars().count();
+            if i == line {
+                self.cursor = cursor + col.min(len);
+                return;
+            }
+            cursor += len + 1;
+        }
+    }
+
     /// Handle a key code event.
     ///
     /// Returns true if the key was handled.
@@ -568,4 +618,25 @@ mod tests {
         assert!(!TextInput::is_action_allowed_when_focused(&Action::Delete)); // List delete
         assert!(!TextInput::is_action_allowed_when_focused(&Action::Edit));
     }
+
+    #[test]
+    fn test_multiline_cursor_movement() {
+        let mut input = TextInput::with_text("Update zshrc\n\nlong");
+        assert_eq!(input.cursor_line_col(), (2, 4));
+
+        // Clamped to the empty line, then back to the same column
+        input.move_up();
+        assert_eq!(input.cursor_line_col(), (1, 0));
+        input.move_up();
+        assert_eq!(input.cursor_line_col(), (0, 0));
+        input.move_right();
+        input.move_right();
+        input.move_down();
+        input.move_down();
+        assert_eq!(input.cursor_line_col(), (2, 0));
+
+        input.insert_newline();
+        assert_eq!(input.text(), "Update zshrc\n\n\nlong");
+        assert_eq!(input.cursor_line_col(), (3, 0));
+    }
 }
@@ -0,0 +1,117 @@
+//! Theme names accepted by the `theme` setting.
+//!
+//! The colors for each theme live in `crate::styles`, which is only built
+//! with the TUI; the names are here so the config can be validated without it.
+
+use std::str::FromStr;
+
+/// Theme type selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeType {
+    #[default]
+    Dark,
+    Light,
+    /// Disable all UI colors (equivalent to `NO_COLOR=1` / `--no-colors`)
+    NoColor,
+    /// Midnight colors regardless of Terminal color presets, RGB values only
+    Midnight,
+    /// Solarized Dark theme
+    SolarizedDark,
+    /// Solarized Light theme
+    SolarizedLight,
+    /// Gruvbox Dark theme
+    GruvboxDark,
+    /// Gruvbox Light theme
+    GruvboxLight,
+    /// Catppuccin Mocha (dark) theme
+    CatppuccinMocha,
+    /// Catppuccin Latte (light) theme
+    CatppuccinLatte,
+    /// Tokyo Night (dark) theme
+    TokyoNightDark,
+    /// Tokyo Night (light) theme
+    TokyoNightLight,
+}
+
+impl ThemeType {
+    /// Get the display name of this theme
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeType::Dark => "Dark",
+            ThemeType::Light => "Light",
+            ThemeType::NoColor => "No Color",
+            ThemeType::Midnight => "Midnight",
+            ThemeType::SolarizedDark => "Solarized Dark",
+            ThemeType::SolarizedLight => "Solarized Light",
+            ThemeType::GruvboxDark => "Gruvbox Dark",
+            ThemeType::GruvboxLight => "Gruvbox Light",
+            ThemeType::CatppuccinMocha => "Catppuccin Mocha",
+            ThemeType::CatppuccinLatte => "Catppuccin Latte",
+            ThemeType::TokyoNightDark => "Tokyo Night",
+            ThemeType::TokyoNightLight => "Tokyo Night Light",
+        }
+    }
+
+    /// Get the config string value for this theme
+    #[must_use]
+    pub fn to_config_string(&self) -> &'static str {
+        match self {
+            ThemeType::Dark => "dark",
+            ThemeType::Light => "light",
+            ThemeType::NoColor => "nocolor",
+            ThemeType::Midnight => "midnight",
+            ThemeType::SolarizedDark => "solarized-dark",
+            ThemeType::SolarizedLight => "solarized-light",
+            ThemeType::GruvboxDark => "gruvbox-dark",
+            ThemeType::GruvboxLight => "gruvbox-light",
+            ThemeType::CatppuccinMocha => "catppuccin-mocha",
+            ThemeType::CatppuccinLatte => "catppuccin-latte",
+            ThemeType::TokyoNightDark => "tokyonight-dark",
+            ThemeType::TokyoNightLight => "tokyonight-light",
+        }
+    }
+
+    /// Get all available themes
+    #[must_use]
+    pub fn all() -> &'static [ThemeType] {
+        &[
+            ThemeType::Dark,
+            ThemeType::Light,
+            ThemeType::Midnight,
+            ThemeType::SolarizedDark,
+            ThemeType::SolarizedLight,
+            ThemeType::GruvboxDark,
+            ThemeType::GruvboxLight,
+            ThemeType::CatppuccinMocha,
+            ThemeType::CatppuccinLatte,
+            ThemeType::TokyoNightDark,
+            ThemeType::TokyoNightLight,
+            ThemeType::NoColor,
+        ]
+    }
+}
+
+impl FromStr for ThemeType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "light" => ThemeType::Light,
+            "midnight" => ThemeType::Midnight,
+            "solarized-dark" | "solarized_dark" | "solarized" => ThemeType::SolarizedDark,
+            "solarized-light" | "solarized_light" => ThemeType::SolarizedLight,
+            "gruvbox-dark" | "gruvbox_dark" | "gruvbox" => ThemeType::GruvboxDark,
+            "gruvbox-light" | "gruvbox_light" => ThemeType::GruvboxLight,
+            "catppuccin-mocha" | "catppuccin_mocha" | "catppuccin" => ThemeType::CatppuccinMocha,
+            "catppuccin-latte" | "catppuccin_latte" => ThemeType::CatppuccinLatte,
+            "tokyonight-dark" | "tokyonight_dark" | "tokyonight" | "tokyo-night"
+            | "tokyo_night" => ThemeType::TokyoNightDark,
+            "tokyonight-light" | "tokyonight_light" | "tokyo-night-light" | "tokyo_night_light" => {
+                ThemeType::TokyoNightLight
+            }
+            "nocolor" | "no-color" | "no_color" => ThemeType::NoColor,
+            _ => ThemeType::Dark,
+        })
+    }
+}
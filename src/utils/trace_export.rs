@@ -0,0 +1,371 @@
+//! Span recording for `--trace`, for performance debugging.
+//!
+//! [`TraceLayer`] records every span that closes while dotstate runs (the
+//! dotfile scan, symlink operations, git operations, TUI renders) with its
+//! start, duration, thread and fields. [`TraceExport::finish`] writes them at
+//! exit: to a file in the Chrome trace event format, which `chrome://tracing`
+//! and <https://ui.perfetto.dev> open, or, when the target is an `http(s)://`
+//! URL, to an OpenTelemetry collector as OTLP/HTTP JSON.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context as LayerContext;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Spans kept at most, so a long TUI session doesn't grow without bound
+const MAX_SPANS: usize = 500_000;
+
+/// OTLP/HTTP path for traces, added to collector URLs that don't name it
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+/// A closed span
+#[derive(Debug, Clone)]
+struct FinishedSpan {
+    id: u64,
+    parent: Option<u64>,
+    name: &'static str,
+    target: String,
+    /// Since the layer was created
+    start: Duration,
+    duration: Duration,
+    thread: u64,
+    fields: Vec<(String, String)>,
+}
+
+/// Start and fields of an open span, kept in its extensions
+struct OpenSpan {
+    start: Instant,
+    fields: Vec<(String, String)>,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+}
+
+/// Small, stable number for the current thread
+fn thread_number() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static NUMBER: Cell<u64> = const { Cell::new(0) };
+    }
+    NUMBER.with(|number| {
+        if number.get() == 0 {
+            number.set(NEXT.fetch_add(1, Ordering::Relaxed));
+        }
+        number.get()
+    })
+}
+
+#[derive(Default)]
+struct Recorded {
+    spans: Vec<FinishedSpan>,
+    dropped: usize,
+}
+
+/// Layer recording closed spans
+pub struct TraceLayer {
+    origin: Instant,
+    recorded: Arc<Mutex<Recorded>>,
+}
+
+impl<S> Layer<S> for TraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: LayerContext<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        span.extensions_mut().insert(OpenSpan {
+            start: Instant::now(),
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: LayerContext<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(open) = span.extensions_mut().get_mut::<OpenSpan>() {
+                values.record(&mut FieldVisitor(&mut open.fields));
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: LayerContext<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(open) = span.extensions_mut().remove::<OpenSpan>() else {
+            return;
+        };
+        let finished = FinishedSpan {
+            id: id.into_u64(),
+            parent: span.parent().map(|parent| parent.id().into_u64()),
+            name: span.metadata().name(),
+            target: span.metadata().target().to_string(),
+            start: open.start.saturating_duration_since(self.origin),
+            duration: open.start.elapsed(),
+            thread: thread_number(),
+            fields: open.fields,
+        };
+        let mut recorded = self
+            .recorded
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if recorded.spans.len() < MAX_SPANS {
+            recorded.spans.push(finished);
+        } else {
+            recorded.dropped += 1;
+        }
+    }
+}
+
+/// Where the recorded spans go when dotstate exits
+pub struct TraceExport {
+    target: String,
+    started_at: SystemTime,
+    recorded: Arc<Mutex<Recorded>>,
+}
+
+/// Start recording spans for `target`, a file path or an OTLP collector URL.
+/// Add the returned layer to the subscriber and call
+/// [`TraceExport::finish`] before exiting.
+#[must_use]
+pub fn start(target: &str) -> (TraceLayer, TraceExport) {
+    let recorded = Arc::new(Mutex::new(Recorded::default()));
+    let layer = TraceLayer {
+        origin: Instant::now(),
+        recorded: Arc::clone(&recorded),
+    };
+    let export = TraceExport {
+        target: target.to_string(),
+        started_at: SystemTime::now(),
+        recorded,
+    };
+    (layer, export)
+}
+
+impl TraceExport {
+    /// Write the spans recorded so far. Returns a line saying where they went.
+    pub fn finish(self) -> Result<String> {
+        let recorded = std::mem::take(
+            &mut *self
+                .recorded
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+        let mut message = if is_url(&self.target) {
+            let url = otlp_url(&self.target);
+            send_otlp(&url, &otlp(&recorded.spans, self.started_at))?;
+            format!("Sent {} spans to {url}", recorded.spans.len())
+        } else {
+            let content = serde_json::to_string(&chrome_trace(&recorded.spans))?;
+            std::fs::write(&self.target, content)
+                .with_context(|| format!("Failed to write trace to {}", self.target))?;
+            format!(
+                "Wrote {} spans to {} (open it in chrome://tracing or ui.perfetto.dev)",
+                recorded.spans.len(),
+                self.target
+            )
+        };
+        if recorded.dropped > 0 {
+            message.push_str(&format!(
+                "; {} later spans were dropped after the first {MAX_SPANS}",
+                recorded.dropped
+            ));
+        }
+        Ok(message)
+    }
+}
+
+fn is_url(target: &str) -> bool {
+    target.starts_with("http://") || target.starts_with("https://")
+}
+
+/// Collector URL with the traces path, e.g. `http://localhost:4318/v1/traces`
+fn otlp_url(target: &str) -> String {
+    let target = target.trim_end_matches('/');
+    if target.ends_with(OTLP_TRACES_PATH) {
+        target.to_string()
+    } else {
+        format!("{target}{OTLP_TRACES_PATH}")
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn micros(duration: Duration) -> u64 {
+    duration.as_micros() as u64
+}
+
+/// Chrome trace event format: one complete ("X") event per span
+fn chrome_trace(spans: &[FinishedSpan]) -> Value {
+    let events: Vec<Value> = spans
+        .iter()
+        .map(|span| {
+            let args: serde_json::Map<String, Value> = span
+                .fields
+                .iter()
+                .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+                .collect();
+            json!({
+                "name": span.name,
+                "cat": span.target,
+                "ph": "X",
+                "ts": micros(span.start),
+                "dur": micros(span.duration),
+                "pid": std::process::id(),
+                "tid": span.thread,
+                "args": args,
+            })
+        })
+        .collect();
+    json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
+/// Lowercase hex of the first `len` bytes of a hash of `parts`
+fn hex_id(parts: &[&[u8]], len: usize) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize()[..len]
+        .iter()
+        .fold(String::new(), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
+
+/// OTLP/HTTP JSON export request with every span in one trace
+fn otlp(spans: &[FinishedSpan], started_at: SystemTime) -> Value {
+    let base = started_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let trace_id = hex_id(
+        &[&base.to_le_bytes(), &std::process::id().to_le_bytes()],
+        16,
+    );
+    let otlp_spans: Vec<Value> = spans
+        .iter()
+        .map(|span| {
+            let start = base + span.start.as_nanos();
+            let end = start + span.duration.as_nanos();
+            let mut attributes = vec![json!({
+                "key": "code.namespace",
+                "value": { "stringValue": span.target },
+            })];
+            attributes.extend(
+                span.fields
+                    .iter()
+                    .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } })),
+            );
+            let mut otlp_span = json!({
+                "traceId": trace_id,
+                "spanId": format!("{:016x}", span.id),
+                "name": span.name,
+                "kind": 1,
+                "startTimeUnixNano": start.to_string(),
+                "endTimeUnixNano": end.to_string(),
+                "attributes": attributes,
+            });
+            if let Some(parent) = span.parent {
+                otlp_span["parentSpanId"] = Value::String(format!("{parent:016x}"));
+            }
+            otlp_span
+        })
+        .collect();
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "dotstate" } },
+                    { "key": "service.version", "value": { "stringValue": env!("CARGO_PKG_VERSION") } },
+                ],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "dotstate" },
+                "spans": otlp_spans,
+            }],
+        }],
+    })
+}
+
+fn send_otlp(url: &str, body: &Value) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+    let response = runtime
+        .block_on(
+            reqwest::Client::new()
+                .post(url)
+                .timeout(Duration::from_secs(10))
+                .json(body)
+                .send(),
+        )
+        .with_context(|| format!("Failed to send the trace to {url}"))?;
+    if !response.status().is_success() {
+        bail!("{url} rejected the trace: {}", response.status());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_records_and_exports_spans() {
+        let (layer, export) = start("unused");
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = tracing::info_span!("scan", files = 3).entered();
+            tracing::info_span!("symlink", path = ".zshrc").in_scope(|| {});
+            drop(outer);
+        });
+
+        let spans = export.recorded.lock().unwrap().spans.clone();
+        assert_eq!(spans.len(), 2);
+        let (inner, outer) = (&spans[0], &spans[1]);
+        assert_eq!(inner.name, "symlink");
+        assert_eq!(inner.parent, Some(outer.id));
+        assert_eq!(outer.fields, vec![("files".to_string(), "3".to_string())]);
+
+        let chrome = chrome_trace(&spans);
+        assert_eq!(chrome["traceEvents"][0]["ph"], "X");
+        assert_eq!(chrome["traceEvents"][0]["args"]["path"], ".zshrc");
+
+        let request = otlp(&spans, SystemTime::now());
+        let otlp_spans = &request["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(otlp_spans[0]["parentSpanId"], otlp_spans[1]["spanId"]);
+        assert_eq!(otlp_spans[0]["traceId"].as_str().unwrap().len(), 32);
+
+        assert_eq!(
+            otlp_url("http://localhost:4318/"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            otlp_url("http://localhost:4318/v1/traces"),
+            "http://localhost:4318/v1/traces"
+        );
+    }
+}
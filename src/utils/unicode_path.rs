@@ -0,0 +1,86 @@
+//! Unicode normalization of file names.
+//!
+//! The same accented name can be spelled two ways: precomposed (NFC, `é` as
+//! one character) or decomposed (NFD, `e` plus a combining accent). macOS
+//! hands out decomposed names for files on HFS+ and from some tools, Linux
+//! keeps whatever bytes it was given, and git compares bytes. dotstate
+//! records paths in the manifest precomposed, compares paths in that form,
+//! and looks a recorded path up on disk in either spelling, so a file named
+//! on one machine is found on the other.
+
+use icu_normalizer::ComposingNormalizerBorrowed;
+use std::borrow::Cow;
+use std::path::{Component, Path, PathBuf};
+
+/// `text` in precomposed (NFC) form; borrowed when it already is
+#[must_use]
+pub fn nfc(text: &str) -> Cow<'_, str> {
+    ComposingNormalizerBorrowed::new_nfc().normalize(text)
+}
+
+/// Whether `a` and `b` name the same path once normalized
+#[must_use]
+pub fn same(a: &str, b: &str) -> bool {
+    a == b || nfc(a) == nfc(b)
+}
+
+/// `path` as it's spelled on disk. When it doesn't exist as given, each
+/// missing component is looked up in its directory under the other
+/// normalization form; components not found either way are kept as given.
+#[must_use]
+pub fn on_disk(path: &Path) -> PathBuf {
+    if path.symlink_metadata().is_ok() || path.to_str().is_none() {
+        return path.to_path_buf();
+    }
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        let Component::Normal(name) = component else {
+            resolved.push(component);
+            continue;
+        };
+        let candidate = resolved.join(name);
+        if candidate.symlink_metadata().is_ok() {
+            resolved = candidate;
+            continue;
+        }
+        let wanted = nfc(&name.to_string_lossy()).into_owned();
+        let found = std::fs::read_dir(&resolved).ok().and_then(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name())
+                .find(|entry| {
+                    entry
+                        .to_str()
+                        .is_some_and(|entry| nfc(entry).as_ref() == wanted)
+                })
+        });
+        resolved.push(found.as_deref().unwrap_or(name));
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalization() {
+        let composed = "Caf\u{e9}/r\u{e9}sum\u{e9}.md";
+        let decomposed = "Cafe\u{301}/re\u{301}sume\u{301}.md";
+        assert_eq!(nfc(decomposed), composed);
+        assert!(matches!(nfc(composed), Cow::Borrowed(_)));
+        assert!(same(composed, decomposed));
+        assert!(!same(composed, "Cafe/resume.md"));
+
+        // A decomposed name on disk is found from the composed spelling
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("Cafe\u{301}")).unwrap();
+        std::fs::write(temp_dir.path().join(decomposed), "").unwrap();
+        let found = on_disk(&temp_dir.path().join(composed));
+        assert!(found.exists());
+        let missing = on_disk(&temp_dir.path().join("Caf\u{e9}/missing"));
+        assert!(missing.parent().unwrap().is_dir());
+        assert!(missing.ends_with("missing"));
+    }
+}
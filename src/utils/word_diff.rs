@@ -0,0 +1,205 @@
+//! Word-level changes within modified diff lines.
+//!
+//! The diff preview highlights the words that changed in a line that was
+//! edited rather than rewritten. A removed line is paired with the added line
+//! in the same position of the block that follows it, as `git diff
+//! --word-diff` does. Lines are paired and compared on demand, only for the
+//! lines on screen, so a large diff costs nothing until it's scrolled to.
+
+use std::ops::Range;
+
+/// Byte ranges within a line
+type Ranges = Vec<Range<usize>>;
+
+/// Lines with more tokens than this aren't compared
+const MAX_TOKENS: usize = 256;
+
+/// Longest block of removed or added lines searched for a pair
+const MAX_RUN: usize = 200;
+
+/// `'-'` or `'+'` for a removed or added line, `None` for anything else
+/// (including the `---`/`+++` file headers)
+fn kind(line: &str) -> Option<char> {
+    if line.starts_with("--- ") || line.starts_with("+++ ") {
+        return None;
+    }
+    line.chars().next().filter(|c| matches!(c, '-' | '+'))
+}
+
+/// First line of the block of `c` lines containing `index`, if the block
+/// isn't too long to search
+fn run_start(lines: &[&str], index: usize, c: char) -> Option<usize> {
+    let mut start = index;
+    while start > 0 && kind(lines[start - 1]) == Some(c) {
+        start -= 1;
+        if index - start > MAX_RUN {
+            return None;
+        }
+    }
+    Some(start)
+}
+
+/// Index of the line that line `index` of a diff was changed from or to
+#[must_use]
+pub fn partner(lines: &[&str], index: usize) -> Option<usize> {
+    let line_kind = |i: usize| lines.get(i).and_then(|line| kind(line));
+    if line_kind(index)? == '-' {
+        let offset = index - run_start(lines, index, '-')?;
+        let mut end = index;
+        while line_kind(end + 1) == Some('-') {
+            end += 1;
+            if end - index > MAX_RUN {
+                return None;
+            }
+        }
+        let added = end + 1 + offset;
+        (end + 1..=added)
+            .all(|i| line_kind(i) == Some('+'))
+            .then_some(added)
+    } else {
+        let start = run_start(lines, index, '+')?;
+        let removed_end = start.checked_sub(1)?;
+        if line_kind(removed_end) != Some('-') {
+            return None;
+        }
+        let removed = run_start(lines, removed_end, '-')? + index - start;
+        (removed <= removed_end).then_some(removed)
+    }
+}
+
+/// Byte ranges of the words, runs of whitespace and single symbols of `text`
+fn tokens(text: &str) -> Ranges {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Symbol,
+    }
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Symbol
+        }
+    };
+    let mut tokens: Ranges = Vec::new();
+    let mut previous = None;
+    for (i, c) in text.char_indices() {
+        let current = class(c);
+        match tokens.last_mut() {
+            Some(last) if current != Class::Symbol && previous.as_ref() == Some(&current) => {
+                last.end = i + c.len_utf8();
+            }
+            _ => tokens.push(i..i + c.len_utf8()),
+        }
+        previous = Some(current);
+    }
+    tokens
+}
+
+/// Byte ranges of the tokens of `old` and `new` that aren't part of their
+/// longest common subsequence, adjacent ones merged. `None` when the lines
+/// are too long or share no words, where highlighting would only add noise.
+#[must_use]
+pub fn changed_ranges(old: &str, new: &str) -> Option<(Ranges, Ranges)> {
+    let (old_tokens, new_tokens) = (tokens(old), tokens(new));
+    if old_tokens.len() > MAX_TOKENS || new_tokens.len() > MAX_TOKENS {
+        return None;
+    }
+    let (rows, cols) = (old_tokens.len(), new_tokens.len());
+    let same =
+        |row: usize, col: usize| old[old_tokens[row].clone()] == new[new_tokens[col].clone()];
+
+    // common(row, col): tokens shared by old_tokens[row..] and new_tokens[col..]
+    let width = cols + 1;
+    let mut common = vec![0u16; (rows + 1) * width];
+    for row in (0..rows).rev() {
+        for col in (0..cols).rev() {
+            common[row * width + col] = if same(row, col) {
+                common[(row + 1) * width + col + 1] + 1
+            } else {
+                common[(row + 1) * width + col].max(common[row * width + col + 1])
+            };
+        }
+    }
+
+    let (mut old_changed, mut new_changed) = (Vec::new(), Vec::new());
+    let mut shares_word = false;
+    let (mut row, mut col) = (0, 0);
+    while row < rows || col < cols {
+        if row < rows && col < cols && same(row, col) {
+            shares_word |= !old[old_tokens[row].clone()].trim().is_empty();
+            row += 1;
+            col += 1;
+        } else if col == cols
+            || (row < rows && common[(row + 1) * width + col] >= common[row * width + col + 1])
+        {
+            push_merged(&mut old_changed, old_tokens[row].clone());
+            row += 1;
+        } else {
+            push_merged(&mut new_changed, new_tokens[col].clone());
+            col += 1;
+        }
+    }
+    shares_word.then_some((old_changed, new_changed))
+}
+
+fn push_merged(ranges: &mut Ranges, range: Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+/// Byte ranges to emphasize in line `index` of a diff: the parts that
+/// differ from the line it was changed from or to
+#[must_use]
+pub fn emphasis(lines: &[&str], index: usize) -> Ranges {
+    let Some(other) = partner(lines, index) else {
+        return Vec::new();
+    };
+    let (line, other) = (lines[index], lines[other]);
+    // Compare without the +/- markers, then shift back past them
+    changed_ranges(&line[1..], &other[1..])
+        .map(|(ranges, _)| {
+            ranges
+                .into_iter()
+                .map(|range| range.start + 1..range.end + 1)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_emphasis() {
+        let diff = [
+            "--- a/zshrc",
+            "+++ b/zshrc",
+            "@@ -1,3 +1,3 @@",
+            " # prompt",
+            "-export EDITOR=vim",
+            "-alias ll='ls -l'",
+            "+export EDITOR=nvim",
+            "+alias ll='ls -la'",
+            "+alias gs='git status'",
+        ];
+        assert_eq!(partner(&diff, 0), None);
+        assert_eq!(partner(&diff, 4), Some(6));
+        assert_eq!(partner(&diff, 7), Some(5));
+        assert_eq!(partner(&diff, 8), None);
+
+        assert_eq!(emphasis(&diff, 4), vec![15..18]);
+        assert_eq!(emphasis(&diff, 6), vec![15..19]);
+        assert_eq!(&diff[7][emphasis(&diff, 7)[0].clone()], "la");
+        assert!(emphasis(&diff, 8).is_empty());
+
+        // Nothing in common: no highlighting
+        assert_eq!(changed_ranges("foo bar", "baz qux"), None);
+    }
+}
@@ -155,6 +155,24 @@ pub fn current_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+/// Whether `version` is newer than `than`, comparing the numeric
+/// `major.minor.patch` parts (pre-release and build suffixes are ignored)
+#[must_use]
+pub fn is_newer(version: &str, than: &str) -> bool {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    parts(version) > parts(than)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +191,15 @@ mod tests {
         assert_eq!(version, env!("CARGO_PKG_VERSION"));
     }
 
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("1.10.0", "1.9.3"));
+        assert!(is_newer("v2.0.0", "1.9.3"));
+        assert!(!is_newer("1.9.3", "1.9.3"));
+        assert!(!is_newer("1.9.3-beta.1", "1.9.3"));
+        assert!(!is_newer("0.9.0", "1.0.0"));
+    }
+
     #[test]
     fn test_install_script_url() {
         let url = UpdateInfo::install_script_url();
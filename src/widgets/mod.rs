@@ -3,11 +3,13 @@
 pub mod dialog;
 pub mod logo;
 pub mod menu;
+pub mod progress_bar;
 pub mod text_input;
 pub mod toast;
 
 pub use dialog::{Dialog, DialogVariant};
 pub use logo::{DotstateLogo, Size};
 pub use menu::{Menu, MenuItem, MenuState};
+pub use progress_bar::ProgressBar;
 pub use text_input::{TextInputWidget, TextInputWidgetExt};
 pub use toast::{Toast, ToastManager, ToastVariant, ToastWidget};
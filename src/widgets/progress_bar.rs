@@ -0,0 +1,37 @@
+//! Progress bar widget for git network transfers
+//!
+//! Renders a single-line gauge from a [`TransferProgress`] snapshot, with the
+//! stage, object counts and transferred bytes as the label.
+
+use crate::git::TransferProgress;
+use crate::styles::theme;
+use ratatui::prelude::*;
+use ratatui::widgets::{Gauge, Widget};
+
+/// Transfer progress bar
+pub struct ProgressBar<'a> {
+    progress: &'a TransferProgress,
+}
+
+impl<'a> ProgressBar<'a> {
+    /// Create a progress bar for a transfer snapshot
+    #[must_use]
+    pub fn new(progress: &'a TransferProgress) -> Self {
+        Self { progress }
+    }
+}
+
+impl Widget for ProgressBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let t = theme();
+        Gauge::default()
+            .gauge_style(Style::default().fg(t.primary).bg(t.highlight_bg))
+            .label(Span::styled(
+                self.progress.label(),
+                Style::default().fg(t.text_emphasis),
+            ))
+            .ratio(self.progress.ratio())
+            .use_unicode(true)
+            .render(area, buf);
+    }
+}
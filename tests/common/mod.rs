@@ -1,13 +1,13 @@
 //! Shared test utilities for critical workflow integration tests.
 //!
-//! Provides `TestEnv` - a complete isolated DotState environment for testing,
+//! Provides `TestEnv` - a complete isolated `DotState` environment for testing,
 //! with automatic cleanup via `TempDir`.
 //!
 //! ## E2E Tests with Real Services
 //!
-//! To test with actual service calls (SyncService, ProfileService, etc.),
+//! To test with actual service calls (`SyncService`, `ProfileService`, etc.),
 //! use `.with_env_override()` on the builder. This sets environment variables
-//! that redirect DotState's path functions to the test directories.
+//! that redirect `DotState`'s path functions to the test directories.
 //!
 //! **Important**: Tests using env overrides should run serially to avoid
 //! conflicts. Run with: `cargo test --test workflow_sync -- --test-threads=1`
@@ -54,9 +54,9 @@ impl Drop for EnvGuard {
     }
 }
 
-/// A complete isolated DotState test environment.
+/// A complete isolated `DotState` test environment.
 ///
-/// Creates a temporary directory structure mimicking a real DotState setup:
+/// Creates a temporary directory structure mimicking a real `DotState` setup:
 /// - `home/` - fake home directory for dotfiles
 /// - `repo/` - storage repository
 /// - `config/` - config directory (~/.config/dotstate equivalent)
@@ -76,7 +76,8 @@ pub struct TestEnv {
 
 #[allow(dead_code)]
 impl TestEnv {
-    /// Create a new TestEnvBuilder for fluent configuration.
+    /// Create a new `TestEnvBuilder` for fluent configuration.
+    #[allow(clippy::new_ret_no_self)]
     pub fn new() -> TestEnvBuilder {
         TestEnvBuilder::default()
     }
@@ -94,7 +95,7 @@ impl TestEnv {
     }
 
     /// Get the path for a profile's directory in the repo.
-    /// Note: Profiles are stored directly at repo_path/<profile_name>/, not repo_path/profiles/<profile_name>/
+    /// Note: Profiles are stored directly at `repo_path`/<`profile_name`>/, not `repo_path/profiles`/<`profile_name`>/
     pub fn profile_path(&self, profile_name: &str) -> PathBuf {
         self.repo_path.join(profile_name)
     }
@@ -271,7 +272,7 @@ impl TestEnv {
         );
     }
 
-    /// Assert that a file is listed in a profile's synced_files in the manifest.
+    /// Assert that a file is listed in a profile's `synced_files` in the manifest.
     pub fn assert_file_in_profile(&self, profile_name: &str, file_relative: &str) {
         let manifest = self.load_manifest().expect("Failed to load manifest");
         let profile = manifest
@@ -288,7 +289,7 @@ impl TestEnv {
         );
     }
 
-    /// Assert that a file is NOT in a profile's synced_files.
+    /// Assert that a file is NOT in a profile's `synced_files`.
     pub fn assert_file_not_in_profile(&self, profile_name: &str, file_relative: &str) {
         let manifest = self.load_manifest().expect("Failed to load manifest");
         let profile = manifest
@@ -334,7 +335,7 @@ impl TestEnv {
     /// Assert that a backup exists for a file (checks backup directory has files).
     pub fn assert_backup_exists_for(&self, _original_relative: &str) {
         let backup_count = fs::read_dir(&self.backup_dir)
-            .map(|entries| entries.count())
+            .map(std::iter::Iterator::count)
             .unwrap_or(0);
         assert!(
             backup_count > 0,
@@ -418,6 +419,9 @@ impl TestEnv {
             source: source.to_path_buf(),
             created_at: chrono::Utc::now(),
             backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
         });
         self.save_tracking(&tracking)
     }
@@ -459,7 +463,7 @@ impl TestEnv {
     }
 }
 
-/// Builder for TestEnv with fluent configuration.
+/// Builder for `TestEnv` with fluent configuration.
 #[derive(Default)]
 #[allow(dead_code)]
 pub struct TestEnvBuilder {
@@ -543,10 +547,10 @@ impl TestEnvBuilder {
     ///
     /// When enabled, sets `DOTSTATE_TEST_HOME`, `DOTSTATE_TEST_CONFIG_DIR`,
     /// and `DOTSTATE_TEST_BACKUP_DIR` to point to the test directories.
-    /// This allows calling real services (SyncService, ProfileService, etc.)
+    /// This allows calling real services (`SyncService`, `ProfileService`, etc.)
     /// that use `get_home_dir()` and `get_config_dir()` internally.
     ///
-    /// The environment is automatically restored when TestEnv is dropped.
+    /// The environment is automatically restored when `TestEnv` is dropped.
     ///
     /// **Note**: Tests using this should run serially (`--test-threads=1`)
     /// to avoid env var conflicts between parallel tests.
@@ -640,6 +644,9 @@ impl TestEnvBuilder {
                     source: repo_file_path.clone(),
                     created_at: chrono::Utc::now(),
                     backup: None,
+                    decrypted: false,
+                    rendered: false,
+                    block: false,
                 });
             }
 
@@ -674,6 +681,9 @@ impl TestEnvBuilder {
                     source: common_file_path.clone(),
                     created_at: chrono::Utc::now(),
                     backup: None,
+                    decrypted: false,
+                    rendered: false,
+                    block: false,
                 });
             }
 
@@ -707,7 +717,9 @@ impl TestEnvBuilder {
         let env_guard = if self.env_override {
             // Acquire mutex to prevent parallel tests from conflicting
             // Use unwrap_or_else to recover from a poisoned mutex (from a panicked test)
-            let lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+            let lock = ENV_MUTEX
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
 
             // Save current values
             let old_home = std::env::var("DOTSTATE_TEST_HOME").ok();
@@ -1,8 +1,8 @@
 //! End-to-end tests for sync operations using actual services.
 //!
-//! These tests use environment variable overrides to redirect DotState's
+//! These tests use environment variable overrides to redirect `DotState`'s
 //! path functions to test directories, allowing us to test the real
-//! SyncService, ProfileService, etc.
+//! `SyncService`, `ProfileService`, etc.
 //!
 //! **Run these tests serially to avoid env var conflicts:**
 //! ```
@@ -319,3 +319,492 @@ fn e2e_add_multiple_files() -> Result<()> {
 
     Ok(())
 }
+
+// ============================================================================
+// RENAME FILE - USING REAL SERVICE
+// ============================================================================
+
+#[test]
+fn e2e_rename_file_moves_storage_and_symlink() -> Result<()> {
+    // Given: file is synced
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_synced_file("default", ".vimrc", "set number")
+        .with_env_override()
+        .build()?;
+
+    let config = env.load_config()?;
+
+    // When: rename it into a subdirectory
+    SyncService::rename_file(&config, ".vimrc", ".config/vim/vimrc")?;
+
+    // Then: storage, manifest, symlink and tracking follow the new path
+    env.assert_symlink_points_to(
+        ".config/vim/vimrc",
+        &env.profile_file_path("default", ".config/vim/vimrc"),
+    );
+    env.assert_file_tracked(".config/vim/vimrc");
+    env.assert_file_in_profile("default", ".config/vim/vimrc");
+    assert_eq!(
+        env.home_file_content(".config/vim/vimrc"),
+        Some("set number".to_string())
+    );
+
+    // The old location is gone everywhere
+    assert!(!env.home_file_exists(".vimrc"));
+    env.assert_no_symlink(".vimrc");
+    env.assert_file_not_tracked(".vimrc");
+    env.assert_file_not_in_profile("default", ".vimrc");
+    assert!(!env.profile_file_path("default", ".vimrc").exists());
+
+    Ok(())
+}
+
+#[test]
+fn e2e_rename_common_file() -> Result<()> {
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_common_file(".gitconfig", "[user]")
+        .with_env_override()
+        .build()?;
+
+    let config = env.load_config()?;
+
+    SyncService::rename_file(&config, ".gitconfig", ".config/git/config")?;
+
+    env.assert_file_in_common(".config/git/config");
+    env.assert_file_not_in_common(".gitconfig");
+    env.assert_symlink_points_to(
+        ".config/git/config",
+        &env.common_path().join(".config/git/config"),
+    );
+    env.assert_no_symlink(".gitconfig");
+
+    Ok(())
+}
+
+#[test]
+fn e2e_rename_managed_block_moves_the_real_file() -> Result<()> {
+    // Given: .zshrc is synced as a managed block
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_home_file(".zshrc", "source /opt/corp/env\n")
+        .with_env_override()
+        .build()?;
+    let config = env.load_config()?;
+    SyncService::add_block_to_sync(&config, ".zshrc")?;
+    std::fs::write(
+        env.profile_file_path("default", ".zshrc"),
+        "alias ll='ls -l'\n",
+    )?;
+    let mut mgr =
+        dotstate::utils::SymlinkManager::new_with_backup(config.repo_path.clone(), false)?;
+    mgr.activate_profile("default", &[".zshrc".to_string()])?;
+
+    // When: rename it
+    SyncService::rename_file(&config, ".zshrc", ".config/zsh/.zshrc")?;
+
+    // Then: the real file moved, still a block, and the manifest follows
+    assert_eq!(
+        env.load_manifest()?.managed_blocks,
+        vec![".config/zsh/.zshrc"]
+    );
+    env.assert_home_regular_file(".config/zsh/.zshrc");
+    env.assert_file_tracked(".config/zsh/.zshrc");
+    let content = env.home_file_content(".config/zsh/.zshrc").unwrap();
+    assert!(content.starts_with("source /opt/corp/env\n\n# >>> dotstate >>>\nalias ll='ls -l'\n"));
+    assert!(!env.home_file_exists(".zshrc"));
+    env.assert_file_not_tracked(".zshrc");
+
+    Ok(())
+}
+
+#[test]
+fn e2e_rename_git_include_keeps_the_include() -> Result<()> {
+    // Given: .gitconfig is synced in include mode, with hashes recorded
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_home_file(
+            ".gitconfig",
+            "[user]\n\temail = me@work.com\n[alias]\n\tco = checkout\n",
+        )
+        .with_env_override()
+        .build()?;
+    let config = env.load_config()?;
+    SyncService::add_git_include_to_sync(&config, ".gitconfig")?;
+    dotstate::utils::integrity::record(&config.repo_path)?;
+    let hash = env.load_manifest()?.hashes["default/.gitconfig"].clone();
+
+    // When: rename it
+    SyncService::rename_file(&config, ".gitconfig", ".config/git/config")?;
+
+    // Then: the real file moved and includes the renamed source
+    let manifest = env.load_manifest()?;
+    assert_eq!(manifest.git_includes, vec![".config/git/config"]);
+    assert_eq!(
+        manifest.hashes.get("default/.config/git/config"),
+        Some(&hash)
+    );
+    assert!(!manifest.hashes.contains_key("default/.gitconfig"));
+    let source = env.profile_file_path("default", ".config/git/config");
+    env.assert_home_regular_file(".config/git/config");
+    env.assert_file_tracked(".config/git/config");
+    let content = env.home_file_content(".config/git/config").unwrap();
+    assert!(content.contains(&format!("\tpath = {}\n", source.display())));
+    assert!(content.ends_with("[user]\n\temail = me@work.com\n"));
+    assert!(!env.home_file_exists(".gitconfig"));
+    env.assert_file_not_tracked(".gitconfig");
+
+    Ok(())
+}
+
+#[test]
+fn e2e_rename_refuses_existing_target() -> Result<()> {
+    // Given: a synced file and an unrelated file at the new path
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_synced_file("default", ".zshrc", "synced")
+        .with_home_file(".zshrc.local", "mine")
+        .with_env_override()
+        .build()?;
+
+    let config = env.load_config()?;
+
+    // When/Then: the rename is refused and nothing changes
+    assert!(SyncService::rename_file(&config, ".zshrc", ".zshrc.local").is_err());
+    assert!(SyncService::rename_file(&config, ".zshrc", "../outside").is_err());
+    env.assert_is_symlink(".zshrc");
+    env.assert_file_in_profile("default", ".zshrc");
+    assert_eq!(
+        env.home_file_content(".zshrc.local"),
+        Some("mine".to_string())
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// MOVE MANY TO COMMON - USING REAL SERVICE
+// ============================================================================
+
+#[test]
+fn e2e_move_many_to_common_commits_once() -> Result<()> {
+    // Given: three synced files, one of them not part of the batch
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_profile("work")
+        .with_activated_profile("default")
+        .with_synced_file("default", ".zshrc", "zsh")
+        .with_synced_file("default", ".vimrc", "set number")
+        .with_synced_file("work", ".vimrc", "set number")
+        .with_synced_file("default", ".bashrc", "bash")
+        .with_git()
+        .with_env_override()
+        .build()?;
+
+    let config = env.load_config()?;
+
+    // When: move two of them, cleaning up work's identical .vimrc
+    let result = SyncService::move_many_to_common(
+        &config,
+        &[
+            (".zshrc".to_string(), Vec::new()),
+            (".vimrc".to_string(), vec!["work".to_string()]),
+        ],
+    )?;
+
+    // Then: both moved in a single commit
+    assert_eq!(result.moved, vec![".zshrc", ".vimrc"]);
+    assert!(result.failed.is_empty());
+    assert!(result.committed);
+    env.assert_file_in_common(".zshrc");
+    env.assert_file_in_common(".vimrc");
+    env.assert_file_not_in_profile("work", ".vimrc");
+    env.assert_symlink_points_to(".vimrc", &env.common_path().join(".vimrc"));
+
+    let repo = git2::Repository::open(&config.repo_path)?;
+    let head = repo.head()?.peel_to_commit()?;
+    assert_eq!(head.message()?, "Move 2 files to common");
+    assert_eq!(head.parent_count(), 0);
+    let tree = head.tree()?;
+    assert!(tree.get_path(std::path::Path::new("common/.zshrc")).is_ok());
+    assert!(tree.get_path(std::path::Path::new("common/.vimrc")).is_ok());
+    // The file that wasn't moved stays uncommitted
+    assert!(tree
+        .get_path(std::path::Path::new("default/.bashrc"))
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn e2e_move_many_to_common_reports_failures() -> Result<()> {
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_synced_file("default", ".zshrc", "zsh")
+        .with_git()
+        .with_env_override()
+        .build()?;
+
+    let config = env.load_config()?;
+
+    let result = SyncService::move_many_to_common(
+        &config,
+        &[
+            (".missing".to_string(), Vec::new()),
+            (".zshrc".to_string(), Vec::new()),
+        ],
+    )?;
+
+    assert_eq!(result.moved, vec![".zshrc"]);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].0, ".missing");
+    assert!(result.committed);
+    env.assert_file_in_common(".zshrc");
+
+    Ok(())
+}
+
+// ============================================================================
+// MANAGED BLOCKS
+// ============================================================================
+
+#[test]
+fn e2e_add_block_to_sync_and_capture() -> Result<()> {
+    // Given: a .zshrc some other tool also writes to
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_home_file(".zshrc", "source /opt/corp/env\n")
+        .with_env_override()
+        .build()?;
+
+    let config = env.load_config()?;
+
+    // When: add it as a managed block and put something in the block
+    let result = SyncService::add_block_to_sync(&config, ".zshrc")?;
+    assert!(matches!(result, dotstate::services::AddFileResult::Success));
+    std::fs::write(
+        env.profile_file_path("default", ".zshrc"),
+        "alias ll='ls -l'\n",
+    )?;
+    let mut mgr =
+        dotstate::utils::SymlinkManager::new_with_backup(config.repo_path.clone(), false)?;
+    mgr.activate_profile("default", &[".zshrc".to_string()])?;
+
+    // Then: the home file is a regular file with the block appended
+    env.assert_home_regular_file(".zshrc");
+    env.assert_file_tracked(".zshrc");
+    assert_eq!(env.load_manifest()?.managed_blocks, vec![".zshrc"]);
+    let content = env.home_file_content(".zshrc").unwrap();
+    assert!(content.starts_with("source /opt/corp/env\n\n# >>> dotstate >>>\nalias ll='ls -l'\n"));
+
+    // Edits inside the block go back to the repository
+    std::fs::write(env.home_path(".zshrc"), content.replace("ll=", "la="))?;
+    let captured = dotstate::utils::managed_block::capture_changed(&config.repo_path)?;
+    assert_eq!(captured, vec!["default/.zshrc"]);
+    assert_eq!(
+        env.file_content(&env.profile_file_path("default", ".zshrc"))
+            .unwrap(),
+        "alias la='ls -l'\n"
+    );
+
+    // Removing it from sync leaves the file alone
+    SyncService::remove_file_from_sync(&config, ".zshrc")?;
+    assert!(env
+        .home_file_content(".zshrc")
+        .unwrap()
+        .contains("alias la='ls -l'"));
+    assert!(env.load_manifest()?.managed_blocks.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn e2e_add_git_include_keeps_identity_local() -> Result<()> {
+    // Given: a .gitconfig with identity and shared aliases
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_home_file(
+            ".gitconfig",
+            "[user]\n\temail = me@work.com\n[alias]\n\tco = checkout\n",
+        )
+        .with_env_override()
+        .build()?;
+
+    let config = env.load_config()?;
+
+    // When: add it in include mode
+    let result = SyncService::add_git_include_to_sync(&config, ".gitconfig")?;
+    assert!(matches!(result, dotstate::services::AddFileResult::Success));
+
+    // Then: aliases are in the repository, identity stays in the real file
+    let source = env.profile_file_path("default", ".gitconfig");
+    assert_eq!(
+        env.file_content(&source).unwrap(),
+        "[alias]\n\tco = checkout\n"
+    );
+    env.assert_home_regular_file(".gitconfig");
+    env.assert_file_tracked(".gitconfig");
+    let content = env.home_file_content(".gitconfig").unwrap();
+    assert!(content.contains(&format!("\tpath = {}\n", source.display())));
+    assert!(content.ends_with("[user]\n\temail = me@work.com\n"));
+    assert_eq!(env.load_manifest()?.git_includes, vec![".gitconfig"]);
+
+    // The include isn't mistaken for an edited managed block
+    assert!(dotstate::utils::managed_block::capture_changed(&config.repo_path)?.is_empty());
+
+    // Removing it inlines the shared settings
+    SyncService::remove_file_from_sync(&config, ".gitconfig")?;
+    let content = env.home_file_content(".gitconfig").unwrap();
+    assert!(content.contains("\tco = checkout\n"));
+    assert!(!content.contains("[include]"));
+    assert!(env.load_manifest()?.git_includes.is_empty());
+
+    Ok(())
+}
+
+// ============================================================================
+// SHELL SNIPPETS
+// ============================================================================
+
+#[test]
+fn e2e_shell_snippets_loader_and_source_line() -> Result<()> {
+    use dotstate::utils::shell_snippets;
+
+    // Given: snippets in common and in the profile, and an existing .zshrc
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_synced_file("default", ".vimrc", "set number")
+        .with_home_file(".zshrc", "export WORK=1\n")
+        .with_env_override()
+        .build()?;
+    let config = env.load_config()?;
+    std::fs::create_dir_all(env.common_path().join("shell.d"))?;
+    std::fs::write(env.common_path().join("shell.d/10-path.sh"), "")?;
+    std::fs::create_dir_all(env.profile_path("default").join("shell.d"))?;
+    std::fs::write(
+        env.profile_path("default").join("shell.d/20-prompt.zsh"),
+        "",
+    )?;
+    std::fs::write(env.profile_path("default").join("shell.d/notes.txt"), "")?;
+
+    // When: the profile is activated
+    dotstate::services::ProfileService::activate_profile(&config.repo_path, "default", false)?;
+
+    // Then: the loader sources the snippets, common first
+    let loader = std::fs::read_to_string(shell_snippets::loader_path())?;
+    let common = loader.find("common/shell.d/10-path.sh").unwrap();
+    let profile = loader.find("default/shell.d/20-prompt.zsh").unwrap();
+    assert!(common < profile);
+    assert!(!loader.contains("notes.txt"));
+
+    // Installing adds one line to .zshrc and leaves the rest
+    let changed = shell_snippets::install(&config.repo_path, "default")?;
+    assert_eq!(changed, vec![env.home_path(".zshrc")]);
+    assert!(!env.home_file_exists(".bashrc"));
+    assert!(shell_snippets::install(&config.repo_path, "default")?.is_empty());
+    let zshrc = env.home_file_content(".zshrc").unwrap();
+    assert!(zshrc.starts_with("export WORK=1\n"));
+    assert_eq!(zshrc.lines().filter(|l| l.contains("dotstate")).count(), 1);
+
+    shell_snippets::uninstall()?;
+    assert_eq!(env.home_file_content(".zshrc").unwrap(), "export WORK=1\n");
+
+    Ok(())
+}
+
+// ============================================================================
+// QUEUED OPERATIONS
+// ============================================================================
+
+#[test]
+fn e2e_apply_operations_in_order() -> Result<()> {
+    use dotstate::services::FileOperation;
+
+    // Given: one home file to add and one synced file to move to common
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_home_file(".zshrc", "# zsh")
+        .with_synced_file("default", ".vimrc", "set number")
+        .with_env_override()
+        .build()?;
+    let config = env.load_config()?;
+
+    // When: both are applied
+    let operations = vec![
+        FileOperation::Add {
+            relative_path: ".zshrc".to_string(),
+            common: false,
+        },
+        FileOperation::MoveToCommon {
+            relative_path: ".vimrc".to_string(),
+        },
+    ];
+    let applied = SyncService::apply_operations(&config, &operations, false)?;
+
+    // Then: both took effect
+    assert_eq!(applied, 2);
+    env.assert_is_symlink(".zshrc");
+    env.assert_file_in_profile("default", ".zshrc");
+    env.assert_file_in_common(".vimrc");
+    env.assert_file_not_in_profile("default", ".vimrc");
+
+    Ok(())
+}
+
+#[test]
+fn e2e_apply_operations_undoes_on_failure() -> Result<()> {
+    use dotstate::services::FileOperation;
+
+    // Given: a file to add, a synced file to remove, and a missing file
+    let env = TestEnv::new()
+        .with_profile("default")
+        .with_activated_profile("default")
+        .with_home_file(".zshrc", "# zsh")
+        .with_synced_file("default", ".bashrc", "# bash")
+        .with_env_override()
+        .build()?;
+    let config = env.load_config()?;
+
+    // When: the last operation fails
+    let operations = vec![
+        FileOperation::Add {
+            relative_path: ".zshrc".to_string(),
+            common: false,
+        },
+        FileOperation::Remove {
+            relative_path: ".bashrc".to_string(),
+            common: false,
+        },
+        FileOperation::Add {
+            relative_path: ".missing".to_string(),
+            common: false,
+        },
+    ];
+    let error = SyncService::apply_operations(&config, &operations, false).unwrap_err();
+
+    // Then: the error names it and the earlier changes are undone
+    let message = error.to_string();
+    assert!(message.starts_with("Add ~/.missing failed"), "{message}");
+    assert!(
+        message.contains("2 change(s) before it were undone"),
+        "{message}"
+    );
+    env.assert_home_regular_file(".zshrc");
+    env.assert_file_not_in_profile("default", ".zshrc");
+    env.assert_is_symlink(".bashrc");
+    env.assert_file_in_profile("default", ".bashrc");
+    assert_eq!(env.home_file_content(".bashrc"), Some("# bash".to_string()));
+
+    Ok(())
+}
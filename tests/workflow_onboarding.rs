@@ -534,6 +534,11 @@ fn github_clone_existing_dotstate_repo() -> Result<()> {
                 packages: Vec::new(),
             },
         ],
+        local_only: Vec::new(),
+        managed_blocks: Vec::new(),
+        git_includes: Vec::new(),
+        hashes: std::collections::BTreeMap::new(),
+        written_by: None,
     };
     manifest.save(&temp_repo)?;
 
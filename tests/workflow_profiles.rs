@@ -145,6 +145,9 @@ fn activate_profile_creates_all_symlinks() -> Result<()> {
             source: zshrc.clone(),
             created_at: chrono::Utc::now(),
             backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
         });
     tracking
         .symlinks
@@ -153,6 +156,9 @@ fn activate_profile_creates_all_symlinks() -> Result<()> {
             source: vimrc.clone(),
             created_at: chrono::Utc::now(),
             backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
         });
     env.save_tracking(&tracking)?;
 
@@ -287,6 +293,9 @@ fn switch_profile_replaces_symlinks() -> Result<()> {
             source: work_file.clone(),
             created_at: chrono::Utc::now(),
             backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
         });
     tracking
         .symlinks
@@ -295,6 +304,9 @@ fn switch_profile_replaces_symlinks() -> Result<()> {
             source: work_shared.clone(),
             created_at: chrono::Utc::now(),
             backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
         });
     env.save_tracking(&tracking)?;
 
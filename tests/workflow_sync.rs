@@ -51,6 +51,9 @@ fn add_file_creates_symlink_and_tracks() -> Result<()> {
             source: repo_file.clone(),
             created_at: chrono::Utc::now(),
             backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
         });
     env.save_tracking(&tracking)?;
 
@@ -133,6 +136,9 @@ fn add_file_handles_nested_directory_structure() -> Result<()> {
             source: repo_file.clone(),
             created_at: chrono::Utc::now(),
             backup: None,
+            decrypted: false,
+            rendered: false,
+            block: false,
         });
     env.save_tracking(&tracking)?;
 